@@ -0,0 +1,16 @@
+#![no_main]
+
+use biblib::{CitationParser, PubMedParser};
+use libfuzzer_sys::fuzz_target;
+
+/// Longer inputs only slow the fuzzer down without reaching new code
+const MAX_INPUT: usize = 64 * 1024;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() > MAX_INPUT {
+        return;
+    }
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = PubMedParser::new().parse(input);
+    }
+});
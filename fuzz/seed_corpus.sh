@@ -0,0 +1,24 @@
+#!/bin/sh
+# Seeds the corpus of each fuzz target with the test fixtures it parses.
+# Run from any directory before the first `cargo +nightly fuzz run`.
+set -eu
+
+fuzz_dir=$(cd "$(dirname "$0")" && pwd)
+fixtures="$fuzz_dir/../tests/fixtures"
+
+seed() {
+    target=$1
+    shift
+    mkdir -p "$fuzz_dir/corpus/$target"
+    for fixture in "$@"; do
+        cp "$fixtures/$fixture" "$fuzz_dir/corpus/$target/seed_$fixture"
+    done
+}
+
+seed ris embase_conference_abstract.ris glued_lines.ris google_scholar.ris \
+    ovid_embase.ris ovid_medline_header.ris partly_broken.ris reference_manager.ris \
+    scopus_export.ris zotero_export.ris
+seed pubmed mixed_ris_pubmed.txt pubmed_export.nbib
+seed endnote_xml endnote_concatenated.xml endnote_x9.xml
+seed detect_and_parse endnote_x9.xml google_scholar.enw google_scholar.ris \
+    mixed_ris_pubmed.txt pubmed_export.nbib scopus_export.csv zotero_export.bib
@@ -0,0 +1,42 @@
+/*
+ * C interface to biblib, built with the `ffi` feature.
+ *
+ * All strings are NUL-terminated UTF-8. Citations and duplicate groups are
+ * exchanged as JSON arrays; a call that fails returns a JSON object
+ * {"error": {"kind": "...", "message": "..."}} instead. Every returned
+ * string must be released with biblib_free_string.
+ */
+
+#ifndef BIBLIB_H
+#define BIBLIB_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Parses citation content and returns the citations as a JSON array.
+ *
+ * format_hint is "ris", "pubmed", "nbib", "endnote_xml", "xml", "enw",
+ * "bibtex", "bib" or "csv"; NULL, "" or "auto" detects the format.
+ */
+char *biblib_parse(const char *content, const char *format_hint);
+
+/*
+ * Deduplicates a JSON array of citations and returns the duplicate groups as
+ * a JSON array of {"unique": ..., "duplicates": [...], "match_info": [...]}
+ * objects.
+ *
+ * json_config is NULL or a JSON object of deduplicator options, e.g.
+ * {"grouping_mode": "transitive_closure", "source_preferences": ["PubMed"]}.
+ */
+char *biblib_dedupe(const char *json_citations, const char *json_config);
+
+/* Releases a string returned by biblib_parse or biblib_dedupe. NULL is ignored. */
+void biblib_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BIBLIB_H */
@@ -0,0 +1,243 @@
+//! Decoding of the LaTeX markup in BibTeX values.
+//!
+//! Braces are removed, accent commands such as `{\"o}` or `\'{e}` give the
+//! accented letter, special letters such as `\ss` and `\o` give the letter,
+//! and escaped characters such as `\&` give the character. Other commands,
+//! such as `\emph`, are dropped and their arguments kept.
+
+/// Accent commands, the letters they combine with and the accented letters.
+const ACCENTS: [(char, &str, &str); 13] = [
+    ('"', "aeiouyAEIOUY", "äëïöüÿÄËÏÖÜŸ"),
+    (
+        '\'',
+        "aeiouycnszlrgAEIOUYCNSZLRG",
+        "áéíóúýćńśźĺŕǵÁÉÍÓÚÝĆŃŚŹĹŔǴ",
+    ),
+    ('`', "aeiouAEIOU", "àèìòùÀÈÌÒÙ"),
+    ('^', "aeiouwyAEIOUWY", "âêîôûŵŷÂÊÎÔÛŴŶ"),
+    ('~', "anoANO", "ãñõÃÑÕ"),
+    ('=', "aeiouAEIOU", "āēīōūĀĒĪŌŪ"),
+    ('.', "zecgZECGI", "żėċġŻĖĊĠİ"),
+    ('c', "cstCST", "çşţÇŞŢ"),
+    ('v', "csznrdteCSZNRDTE", "čšžňřďťěČŠŽŇŘĎŤĚ"),
+    ('u', "aguAGU", "ăğŭĂĞŬ"),
+    ('H', "ouOU", "őűŐŰ"),
+    ('k', "aeAE", "ąęĄĘ"),
+    ('r', "auAU", "åůÅŮ"),
+];
+
+/// Commands that stand for a letter or symbol.
+const SYMBOLS: [(&str, &str); 29] = [
+    ("ss", "ß"),
+    ("o", "ø"),
+    ("O", "Ø"),
+    ("ae", "æ"),
+    ("AE", "Æ"),
+    ("oe", "œ"),
+    ("OE", "Œ"),
+    ("aa", "å"),
+    ("AA", "Å"),
+    ("l", "ł"),
+    ("L", "Ł"),
+    ("i", "ı"),
+    ("j", "ȷ"),
+    ("textendash", "–"),
+    ("textemdash", "—"),
+    ("textregistered", "®"),
+    ("texttrademark", "™"),
+    // Greek letters, common in math mode in biomedical titles
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ε"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("pi", "π"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("omega", "ω"),
+];
+
+/// Decodes the LaTeX markup of a value and collapses its whitespace.
+pub(crate) fn decode(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let mut decoded = String::with_capacity(value.len());
+    decode_into(&chars, &mut decoded);
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decodes a URL or DOI, where only braces and escaped characters such as
+/// `\_` are markup and `~` or `$` are part of the value.
+pub(crate) fn verbatim(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '\\' => decoded.extend(chars.next()),
+            c if c.is_whitespace() => {}
+            c => decoded.push(c),
+        }
+    }
+    decoded
+}
+
+fn decode_into(chars: &[char], out: &mut String) {
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i = command(chars, i + 1, out),
+            '{' | '}' | '$' => i += 1,
+            '~' => {
+                out.push(' ');
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Decodes the command starting after a backslash at `start`, returning the
+/// position after it.
+fn command(chars: &[char], start: usize, out: &mut String) -> usize {
+    let Some(&first) = chars.get(start) else {
+        return start;
+    };
+    if !first.is_ascii_alphabetic() {
+        if ACCENTS.iter().any(|(accent, ..)| *accent == first) {
+            return accented(chars, first, start + 1, out);
+        }
+        // `\\` breaks the line and `\,` and `\;` are spaces
+        let spacing = first.is_whitespace() || matches!(first, '\\' | ',' | ';');
+        out.push(if spacing { ' ' } else { first });
+        return start + 1;
+    }
+
+    let end = start
+        + chars[start..]
+            .iter()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .count();
+    let name: String = chars[start..end].iter().collect();
+    if let Some(&(accent, ..)) = ACCENTS
+        .iter()
+        .find(|(known, ..)| known.is_ascii_alphabetic() && name.chars().eq([*known]))
+    {
+        return accented(chars, accent, end, out);
+    }
+    if let Some((_, symbol)) = SYMBOLS.iter().find(|(known, _)| *known == name) {
+        out.push_str(symbol);
+    }
+    // Control words swallow the spaces after them
+    end + chars[end..]
+        .iter()
+        .take_while(|c| c.is_whitespace())
+        .count()
+}
+
+/// Decodes the argument of an accent command starting at `start` and pushes
+/// it accented, returning the position after it.
+fn accented(chars: &[char], accent: char, start: usize, out: &mut String) -> usize {
+    let start = start
+        + chars[start..]
+            .iter()
+            .take_while(|c| c.is_whitespace())
+            .count();
+    let (argument, end) = match chars.get(start) {
+        Some('{') => {
+            let mut depth = 0;
+            let close = chars[start..]
+                .iter()
+                .position(|&c| {
+                    depth += match c {
+                        '{' => 1,
+                        '}' => -1,
+                        _ => 0,
+                    };
+                    depth == 0
+                })
+                .map_or(chars.len(), |offset| start + offset);
+            let mut argument = String::new();
+            decode_into(&chars[start + 1..close.min(chars.len())], &mut argument);
+            (argument, (close + 1).min(chars.len()))
+        }
+        Some('\\') => {
+            let mut argument = String::new();
+            let end = command(chars, start + 1, &mut argument);
+            (argument, end)
+        }
+        Some(&c) => (c.to_string(), start + 1),
+        None => (String::new(), start),
+    };
+
+    let mut letters = argument.chars();
+    if let Some(base) = letters.next() {
+        // Dotless i and j take accents in place of i and j
+        let base = match base {
+            'ı' => 'i',
+            'ȷ' => 'j',
+            base => base,
+        };
+        out.push(compose(accent, base));
+        out.extend(letters);
+    }
+    end
+}
+
+/// The letter `base` with `accent`, or `base` itself when there is no such letter.
+fn compose(accent: char, base: char) -> char {
+    ACCENTS
+        .iter()
+        .find(|(known, ..)| *known == accent)
+        .and_then(|(_, bases, accented)| {
+            let position = bases.chars().position(|c| c == base)?;
+            accented.chars().nth(position)
+        })
+        .unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"M{\"u}ller"#, "Müller")]
+    #[case(r#"M\"uller"#, "Müller")]
+    #[case(r#"M\"{u}ller"#, "Müller")]
+    #[case(r"Jos{\'e}", "José")]
+    #[case(r"Fran\c{c}ois", "François")]
+    #[case(r"Fran\c cois", "François")]
+    #[case(r"{\v{S}}koda", "Škoda")]
+    #[case(r"Ma{\'{\i}}z", "Maíz")]
+    #[case(r"Stra\ss e", "Straße")]
+    #[case(r"{\O}stergaard", "Østergaard")]
+    #[case(r"Ko{\l}odziej", "Kołodziej")]
+    #[case(r"Smith \& Sons", "Smith & Sons")]
+    #[case(r"50\% of cases", "50% of cases")]
+    #[case(r"{COVID-19} and {{AI}}", "COVID-19 and AI")]
+    #[case(r"The \emph{in vivo} effect", "The in vivo effect")]
+    #[case(r"$\alpha$-synuclein", "α-synuclein")]
+    #[case("Line\n   broken  value", "Line broken value")]
+    #[case(r"J.~Smith", "J. Smith")]
+    #[case(r"10\,mg", "10 mg")]
+    #[case(r"\'{}", "")]
+    #[case(r"trailing \", "trailing")]
+    fn test_decode(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(decode(value), expected);
+    }
+
+    #[test]
+    fn test_verbatim() {
+        assert_eq!(
+            verbatim(r"https://example.org/~user/a\_b?x=1\&y=2"),
+            "https://example.org/~user/a_b?x=1&y=2"
+        );
+        assert_eq!(verbatim("{10.1000/ABC}\n"), "10.1000/ABC");
+    }
+}
@@ -0,0 +1,720 @@
+//! BibTeX (`.bib`) format parser implementation.
+//!
+//! BibTeX is the format of LaTeX bibliographies, and the export format of many
+//! reference managers. Each entry starts with `@`, its type and a citation key,
+//! followed by `name = value` fields:
+//!
+//! ```bibtex
+//! @article{smith2023,
+//!   title   = {Early mobilisation of {ICU} patients},
+//!   author  = {Smith, John and M{\"u}ller, Anna},
+//!   journal = {Critical Care},
+//!   year    = 2023,
+//!   pages   = {100--110},
+//! }
+//! ```
+//!
+//! Values are decoded from LaTeX: braces are removed and accents, special
+//! letters and escaped characters such as `\&` are turned into text. `@string`
+//! abbreviations are expanded and values joined with `#` are concatenated.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::{BibTexParser, CitationParser};
+//!
+//! let input = r#"@article{smith2023,
+//!   title = {Early mobilisation of {ICU} patients},
+//!   author = {Smith, John and M{\"u}ller, Anna},
+//!   year = 2023,
+//! }"#;
+//!
+//! let citations = BibTexParser::new().parse(input).unwrap();
+//! assert_eq!(citations[0].title, "Early mobilisation of ICU patients");
+//! assert_eq!(citations[0].citation_type, vec!["article"]);
+//! assert_eq!(citations[0].authors[1].name, "Müller");
+//! ```
+
+mod latex;
+mod parse;
+
+pub(crate) use parse::{bibtex_coverage, starts_with_entry};
+
+use crate::doi::DoiValidation;
+use crate::error::{ParseError, ValueError};
+use crate::{
+    Author, Citation, CitationFormat, CitationParser, Contributor, ContributorRole, Date,
+    LenientOptions, LenientParse, NameOrder,
+};
+use serde::{Deserialize, Serialize};
+use latex::decode;
+use parse::{RawBibEntry, read_entries};
+
+/// Parser for BibTeX format citations.
+///
+/// The entry type is read into [`Citation::citation_type`], e.g. "article" or
+/// "inproceedings", and the citation key into `extra_fields["reference_id"]`,
+/// as the RIS parser keeps `ID` values. Editors are read into
+/// [`Citation::contributors`]. Fields without a [`Citation`] field of their own,
+/// such as `address` or `isbn`, are kept in `extra_fields` under their lowercase
+/// name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BibTexParser {
+    dedupe_keywords: bool,
+    max_abstract_length: Option<usize>,
+    max_authors: Option<usize>,
+    stamp_ingestion_time: bool,
+    fold_typography: bool,
+    sentence_case_titles: bool,
+    raw_abstracts: bool,
+}
+
+impl BibTexParser {
+    /// Creates a new BibTeX parser instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::BibTexParser;
+    /// let parser = BibTexParser::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to remove case-insensitive duplicate keywords from each citation.
+    ///
+    /// See [`Citation::dedupe_keywords`]. Disabled by default.
+    pub fn set_dedupe_keywords(&mut self, enabled: bool) -> &mut Self {
+        self.dedupe_keywords = enabled;
+        self
+    }
+
+    /// Sets the maximum abstract length in characters.
+    ///
+    /// Longer abstracts are shortened with [`Citation::truncate_abstract`] and
+    /// marked with [`crate::ABSTRACT_TRUNCATED_FIELD`]. `None`, the default,
+    /// keeps abstracts in full.
+    pub fn set_max_abstract_length(&mut self, max_chars: Option<usize>) -> &mut Self {
+        self.max_abstract_length = max_chars;
+        self
+    }
+
+    /// Sets the maximum number of authors kept per citation.
+    ///
+    /// Longer author lists, such as those of consortium papers, are shortened
+    /// with [`Citation::truncate_authors`], which records the full count in
+    /// `author_count` and marks the citation with
+    /// [`crate::AUTHORS_TRUNCATED_FIELD`]. `None`, the default, keeps all authors.
+    pub fn set_max_authors(&mut self, max_authors: Option<usize>) -> &mut Self {
+        self.max_authors = max_authors;
+        self
+    }
+
+    /// Sets whether each citation records when it was parsed in `ingested_at`.
+    ///
+    /// All citations of one parse get the same UTC time, as RFC 3339. Disabled by
+    /// default, so parsing the same input always gives the same output.
+    pub fn set_stamp_ingestion_time(&mut self, enabled: bool) -> &mut Self {
+        self.stamp_ingestion_time = enabled;
+        self
+    }
+
+    /// Sets whether typographic characters in the text fields are replaced with
+    /// ASCII equivalents.
+    ///
+    /// See [`Citation::fold_typography`]. Disabled by default, so stored fields
+    /// keep the characters of the source.
+    pub fn set_fold_typography(&mut self, enabled: bool) -> &mut Self {
+        self.fold_typography = enabled;
+        self
+    }
+
+    /// Sets whether all-caps titles are converted to sentence case.
+    ///
+    /// See [`Citation::sentence_case_title`]. Disabled by default, so titles
+    /// keep the case of the source.
+    pub fn set_sentence_case_titles(&mut self, enabled: bool) -> &mut Self {
+        self.sentence_case_titles = enabled;
+        self
+    }
+
+    /// Sets whether abstracts are cleaned of format-specific line breaks and
+    /// spacing.
+    ///
+    /// See [`Citation::clean_abstract`]. Enabled by default; disable it to keep
+    /// abstracts as decoded from the source.
+    pub fn set_clean_abstracts(&mut self, enabled: bool) -> &mut Self {
+        self.raw_abstracts = !enabled;
+        self
+    }
+
+    /// Parses `input` into `out`, as [`CitationParser::parse_into`] when `errors`
+    /// is `None`; otherwise entries that fail to read or convert are pushed to
+    /// `errors`.
+    fn parse_records(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        crate::utils::check_format(input, CitationFormat::BibTex)?;
+        let count = crate::utils::append_citations(
+            out,
+            read_entries(input)
+                .into_iter()
+                .map(|entry| entry.and_then(Citation::try_from)),
+            errors,
+        )?;
+        crate::utils::set_parsed_from_of_last(out, count, CitationFormat::BibTex);
+        if self.dedupe_keywords {
+            crate::utils::dedupe_keywords_of_last(out, count);
+        }
+        if self.fold_typography {
+            crate::utils::fold_typography_of_last(out, count);
+        }
+        if self.sentence_case_titles {
+            crate::utils::sentence_case_titles_of_last(out, count);
+        }
+        if !self.raw_abstracts {
+            crate::utils::clean_abstracts_of_last(out, count);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            crate::utils::truncate_abstracts_of_last(out, count, max_chars);
+        }
+        if let Some(max_authors) = self.max_authors {
+            crate::utils::truncate_authors_of_last(out, count, max_authors);
+        }
+        if self.stamp_ingestion_time {
+            crate::utils::stamp_ingestion_time_of_last(out, count);
+        }
+        Ok(count)
+    }
+}
+
+impl CitationParser for BibTexParser {
+    /// Parses a string containing one or more BibTeX entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if an entry is malformed or has neither a title nor
+    /// an author
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        let mut citations = Vec::new();
+        self.parse_into(input, &mut citations)?;
+        Ok(citations)
+    }
+
+    /// Parses BibTeX content, appending the citations to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if an entry is malformed or has neither a title nor
+    /// an author; `out` is left unchanged
+    fn parse_into(&self, input: &str, out: &mut Vec<Citation>) -> Result<usize, ParseError> {
+        self.parse_records(input, out, None)
+    }
+
+    /// Parses BibTeX content, skipping entries that are malformed or fail to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is another format or the failures exceed
+    /// `options`
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> Result<LenientParse, ParseError> {
+        let mut citations = Vec::new();
+        let mut errors = Vec::new();
+        self.parse_records(input, &mut citations, Some(&mut errors))?;
+        options.check(citations, errors, bibtex_coverage(input).unparsed)
+    }
+}
+
+impl TryFrom<RawBibEntry> for Citation {
+    type Error = ParseError;
+
+    fn try_from(entry: RawBibEntry) -> Result<Self, Self::Error> {
+        let mut citation = Citation {
+            citation_type: vec![entry.entry_type],
+            ..Default::default()
+        };
+        let mut anonymous = false;
+        let mut book_title = None;
+        let (mut year, mut month, mut date) = (None, None, None);
+
+        for (name, value) in entry.fields {
+            match name.as_str() {
+                "author" => {
+                    for name in split_names(&value) {
+                        match parse_name(&name) {
+                            Some(author) => citation.authors.push(author),
+                            None => anonymous |= !name.trim().is_empty(),
+                        }
+                    }
+                }
+                "editor" => citation.contributors.extend(
+                    split_names(&value)
+                        .iter()
+                        .filter_map(|name| parse_name(name))
+                        .map(|person| Contributor::new(person, ContributorRole::Editor)),
+                ),
+                "title" if citation.title.is_empty() => citation.title = decode(&value),
+                "journal" | "journaltitle" if citation.journal.is_none() => {
+                    citation.journal = Some(decode(&value));
+                }
+                "shortjournal" if citation.journal_abbr.is_none() => {
+                    citation.journal_abbr = Some(decode(&value));
+                }
+                "booktitle" if book_title.is_none() => book_title = Some(decode(&value)),
+                "year" if year.is_none() => year = Some(decode(&value)),
+                "month" if month.is_none() => month = Some(decode(&value)),
+                "date" if date.is_none() => date = Some(decode(&value)),
+                "volume" if citation.volume.is_none() => citation.volume = Some(decode(&value)),
+                "number" | "issue" if citation.issue.is_none() => {
+                    citation.issue = Some(decode(&value));
+                }
+                "pages" if citation.pages.is_none() => {
+                    let mut pages = decode(&value).replace(['–', '—'], "-");
+                    while pages.contains("--") {
+                        pages = pages.replace("--", "-");
+                    }
+                    citation.pages = Some(crate::utils::format_page_numbers(&pages));
+                }
+                "doi" if citation.doi.is_none() => {
+                    citation.doi = crate::utils::format_doi(&latex::verbatim(&value));
+                    if citation.doi.is_none() {
+                        insert_extra(&mut citation, name, decode(&value));
+                    }
+                }
+                "issn" => citation
+                    .issn
+                    .extend(crate::utils::split_issns(&decode(&value))),
+                "publisher" if citation.publisher.is_none() => {
+                    citation.publisher = Some(decode(&value));
+                }
+                "abstract" if citation.abstract_text.is_none() => {
+                    citation.abstract_text = Some(decode(&value));
+                }
+                "keywords" => citation.keywords.extend(
+                    decode(&value)
+                        .split([',', ';'])
+                        .map(str::trim)
+                        .filter(|keyword| !keyword.is_empty())
+                        .map(String::from),
+                ),
+                "url" => citation.urls.push(latex::verbatim(&value)),
+                "language" if citation.language.is_none() => {
+                    citation.language = Some(decode(&value));
+                }
+                "note" => {
+                    let note = decode(&value);
+                    match &mut citation.notes {
+                        Some(notes) => {
+                            notes.push_str("\n\n");
+                            notes.push_str(&note);
+                        }
+                        None => citation.notes = Some(note),
+                    }
+                }
+                "pmid" if citation.pmid.is_none() => citation.pmid = Some(decode(&value)),
+                "pmcid" if citation.pmc_id.is_none() => citation.pmc_id = Some(decode(&value)),
+                _ => insert_extra(&mut citation, name, decode(&value)),
+            }
+        }
+
+        citation.date = read_date(year.as_deref(), month.as_deref(), date.as_deref());
+        // Keep date parts that could not be read
+        for (name, value) in [("year", year), ("month", month), ("date", date)] {
+            if let Some(value) = value.filter(|_| citation.date.is_none()) {
+                insert_extra(&mut citation, name.to_string(), value);
+            }
+        }
+
+        // Papers in proceedings and chapters give the book under booktitle
+        if citation.journal.is_none() {
+            citation.journal = book_title;
+        } else if let Some(book_title) = book_title {
+            insert_extra(&mut citation, "booktitle".to_string(), book_title);
+        }
+
+        if citation.doi.is_none() {
+            citation.doi = citation
+                .urls
+                .iter()
+                .find_map(|url| crate::doi::extract(url, DoiValidation::Strict));
+        }
+        if !entry.key.is_empty() {
+            insert_extra(&mut citation, "reference_id".to_string(), entry.key);
+        }
+        crate::utils::mark_anonymous(&mut citation, anonymous);
+
+        if citation.title.is_empty() && citation.authors.is_empty() {
+            return Err(ParseError::at_line(
+                entry.line,
+                CitationFormat::BibTex,
+                ValueError::MissingValue {
+                    field: "title or author",
+                    key: "title/author",
+                },
+            ));
+        }
+
+        citation.retraction_status = crate::RetractionStatus::from_title(&citation.title);
+        Ok(citation)
+    }
+}
+
+/// Keeps a value in `extra_fields` under its field name.
+fn insert_extra(citation: &mut Citation, name: String, value: String) {
+    citation.extra_fields.entry(name).or_default().push(value);
+}
+
+/// Reads the publication date from `year` and `month`, or from a biblatex
+/// `date` such as "2023-05-14" when there is no year.
+fn read_date(year: Option<&str>, month: Option<&str>, date: Option<&str>) -> Option<Date> {
+    let Some(year) = year else {
+        let mut parts = date?.split('-').map(str::trim);
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next().and_then(|month| month.parse().ok());
+        let day = parts.next().and_then(|day| day.parse().ok());
+        return Some(Date {
+            year,
+            month: month.filter(|month| (1..=12).contains(month)),
+            day: day
+                .filter(|day| (1..=31).contains(day))
+                .filter(|_| month.is_some()),
+        });
+    };
+
+    let mut date = crate::utils::parse_year_only(year)?;
+    date.month = month.and_then(|month| {
+        let month = month.trim().trim_end_matches('.');
+        month
+            .parse()
+            .ok()
+            .filter(|month| (1..=12).contains(month))
+            .or_else(|| crate::utils::parse_month_name(month))
+    });
+    Some(date)
+}
+
+/// Splits an `author` or `editor` value into names at "and" outside braces.
+fn split_names(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let (mut depth, mut word_start) = (0usize, None);
+    for (i, c) in value.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            _ if c.is_whitespace() && depth == 0 => {
+                words.extend(word_start.take().map(|start| &value[start..i]));
+                continue;
+            }
+            _ => {}
+        }
+        word_start.get_or_insert(i);
+    }
+    words.extend(word_start.map(|start| &value[start..]));
+
+    words
+        .split(|word| word.eq_ignore_ascii_case("and"))
+        .filter(|name| !name.is_empty())
+        .map(|name| name.join(" "))
+        .collect()
+}
+
+/// Reads one name, written "Family, Given", "Family, Suffix, Given" or
+/// "Given Family", where a family name can start with lowercase particles as
+/// in "Ludwig van Beethoven". A name wholly in braces, such as
+/// "{World Health Organization}", is kept as one family name.
+///
+/// Returns `None` for "others", which BibTeX writes for "et al.", and for
+/// placeholder names such as "Anonymous".
+fn parse_name(name: &str) -> Option<Author> {
+    let name = name.trim();
+    let braced = name.starts_with('{')
+        && name.ends_with('}')
+        && split_names(&name[1..name.len() - 1]).len() <= 1
+        && !name[1..name.len() - 1].contains(['{', '}']);
+    let decoded = decode(name);
+    if decoded.eq_ignore_ascii_case("others") || crate::utils::is_placeholder_author(&decoded) {
+        return None;
+    }
+
+    let (family, given, suffix) = if braced {
+        (decoded, String::new(), None)
+    } else {
+        let parts: Vec<&str> = decoded.split(',').map(str::trim).collect();
+        match parts[..] {
+            [family, suffix, given] => (
+                family.to_string(),
+                given.to_string(),
+                Some(suffix.to_string()).filter(|suffix| !suffix.is_empty()),
+            ),
+            [_, _, ..] => crate::utils::parse_author_name(&decoded, NameOrder::FamilyFirst),
+            _ => {
+                let words: Vec<&str> = decoded.split_whitespace().collect();
+                let family_start = (1..words.len().saturating_sub(1))
+                    .find(|&i| words[i].starts_with(char::is_lowercase))
+                    .unwrap_or(words.len().saturating_sub(1));
+                (
+                    words[family_start..].join(" "),
+                    words[..family_start].join(" "),
+                    None,
+                )
+            }
+        }
+    };
+    let (given_name, middle_name) = crate::utils::split_given_and_middle(&given);
+    Some(Author {
+        name: family,
+        given_name,
+        middle_name,
+        suffix,
+        affiliations: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse_article() {
+        let input = r#"@article{smith2023early,
+  title = {Early mobilisation of {ICU} patients: a {\"O}sterreich cohort},
+  author = {Smith, John A. and M{\"u}ller, Anna},
+  journal = {Journal of Critical Care},
+  year = {2023},
+  month = mar,
+  volume = {45},
+  number = {2},
+  pages = {100--10},
+  doi = {10.1016/j.jcrc.2023.01.001},
+  issn = {0883-9441},
+  publisher = {Elsevier},
+  abstract = {Early mobilisation \& rehabilitation
+               reduce delirium.},
+  keywords = {delirium, mobilisation; intensive care},
+  url = {https://example.org/early\_mobilisation},
+  address = {Amsterdam},
+}"#;
+        let citations = BibTexParser::new().parse(input).unwrap();
+        assert_eq!(citations.len(), 1);
+
+        let citation = &citations[0];
+        assert_eq!(citation.citation_type, vec!["article"]);
+        assert_eq!(
+            citation.title,
+            "Early mobilisation of ICU patients: a Österreich cohort"
+        );
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.authors[0].name, "Smith");
+        assert_eq!(citation.authors[0].given_name.as_deref(), Some("John"));
+        assert_eq!(citation.authors[0].middle_name.as_deref(), Some("A."));
+        assert_eq!(citation.authors[1].name, "Müller");
+        assert_eq!(
+            citation.journal.as_deref(),
+            Some("Journal of Critical Care")
+        );
+        let date = citation.date.as_ref().unwrap();
+        assert_eq!((date.year, date.month, date.day), (2023, Some(3), None));
+        assert_eq!(citation.volume.as_deref(), Some("45"));
+        assert_eq!(citation.issue.as_deref(), Some("2"));
+        assert_eq!(citation.pages.as_deref(), Some("100-110"));
+        assert_eq!(citation.doi.as_deref(), Some("10.1016/j.jcrc.2023.01.001"));
+        assert_eq!(citation.issn, vec!["0883-9441"]);
+        assert_eq!(citation.publisher.as_deref(), Some("Elsevier"));
+        assert_eq!(
+            citation.abstract_text.as_deref(),
+            Some("Early mobilisation & rehabilitation reduce delirium.")
+        );
+        assert_eq!(
+            citation.keywords,
+            vec!["delirium", "mobilisation", "intensive care"]
+        );
+        assert_eq!(
+            citation.urls,
+            vec!["https://example.org/early_mobilisation"]
+        );
+        assert_eq!(citation.extra_fields["address"], vec!["Amsterdam"]);
+        assert_eq!(
+            citation.extra_fields["reference_id"],
+            vec!["smith2023early"]
+        );
+    }
+
+    #[test]
+    fn test_multiple_entries_and_concatenation() {
+        let input = r#"% Exported library
+@string{ccm = "Critical Care Medicine"}
+
+@Article{first,
+  title = "Sepsis " # {{ICU}} # " outcomes",
+  author = "Okafor, Chidi",
+  journal = ccm,
+  year = 2021
+}
+
+@InProceedings{second,
+  title = {Delirium screening},
+  author = {Tanaka, Hiroshi},
+  booktitle = "Proceedings of the " # {ESICM} # " Congress",
+  year = {2022},
+}
+
+@book(third,
+  title = {Intensive Care Medicine},
+  editor = {Moreau, Claire and Novak, Jan},
+  publisher = {Springer},
+  year = 2020)
+"#;
+        let citations = BibTexParser::new().parse(input).unwrap();
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].title, "Sepsis ICU outcomes");
+        assert_eq!(
+            citations[0].journal.as_deref(),
+            Some("Critical Care Medicine")
+        );
+        assert_eq!(citations[1].citation_type, vec!["inproceedings"]);
+        assert_eq!(
+            citations[1].journal.as_deref(),
+            Some("Proceedings of the ESICM Congress")
+        );
+        assert_eq!(
+            citations[1].normalized_type(),
+            crate::CitationType::ConferencePaper
+        );
+        assert_eq!(citations[2].normalized_type(), crate::CitationType::Book);
+        assert!(citations[2].authors.is_empty());
+        let editors: Vec<&str> = citations[2].editors().map(|e| e.name.as_str()).collect();
+        assert_eq!(editors, vec!["Moreau", "Novak"]);
+    }
+
+    #[rstest]
+    #[case("Smith, John", "Smith", Some("John"), None)]
+    #[case("John Smith", "Smith", Some("John"), None)]
+    #[case("John Adam Smith", "Smith", Some("John"), Some("Adam"))]
+    #[case("Ludwig van Beethoven", "van Beethoven", Some("Ludwig"), None)]
+    #[case("van Beethoven, Ludwig", "van Beethoven", Some("Ludwig"), None)]
+    #[case("Vincent {van Gogh}", "van Gogh", Some("Vincent"), None)]
+    #[case("{World Health Organization}", "World Health Organization", None, None)]
+    #[case("Smith", "Smith", None, None)]
+    #[case(r"Jos{\'e} Garc{\'\i}a", "García", Some("José"), None)]
+    fn test_names(
+        #[case] name: &str,
+        #[case] family: &str,
+        #[case] given: Option<&str>,
+        #[case] middle: Option<&str>,
+    ) {
+        let author = parse_name(name).unwrap();
+        assert_eq!(author.name, family);
+        assert_eq!(author.given_name.as_deref(), given);
+        assert_eq!(author.middle_name.as_deref(), middle);
+    }
+
+    #[test]
+    fn test_author_lists() {
+        assert_eq!(
+            split_names("Smith, John and {Barnes and Noble} AND Doe, Jane\n  and others"),
+            vec!["Smith, John", "{Barnes and Noble}", "Doe, Jane", "others"]
+        );
+        let suffixed = parse_name("King, Jr., Martin Luther").unwrap();
+        assert_eq!(suffixed.name, "King");
+        assert_eq!(suffixed.suffix.as_deref(), Some("Jr."));
+        assert_eq!(suffixed.given_name.as_deref(), Some("Martin"));
+
+        let input = "@article{a, title = {Editorial}, author = {Anonymous}}\n\
+                     @article{b, title = {Trial}, author = {Smith, John and others}}";
+        let citations = BibTexParser::new().parse(input).unwrap();
+        assert!(citations[0].authors.is_empty());
+        assert_eq!(
+            citations[0].extra_fields[crate::ANONYMOUS_FIELD],
+            vec!["true"]
+        );
+        assert_eq!(citations[1].authors.len(), 1);
+        assert!(
+            !citations[1]
+                .extra_fields
+                .contains_key(crate::ANONYMOUS_FIELD)
+        );
+    }
+
+    #[rstest]
+    #[case(Some("2023"), Some("jun"), None, Some((2023, Some(6), None)))]
+    #[case(Some("2023"), Some("6"), None, Some((2023, Some(6), None)))]
+    #[case(Some("2023"), Some("Sep."), None, Some((2023, Some(9), None)))]
+    #[case(Some("2023"), Some("13"), None, Some((2023, None, None)))]
+    #[case(None, None, Some("2023-05-14"), Some((2023, Some(5), Some(14))))]
+    #[case(None, None, Some("2023"), Some((2023, None, None)))]
+    #[case(Some("in press"), None, None, None)]
+    fn test_read_date(
+        #[case] year: Option<&str>,
+        #[case] month: Option<&str>,
+        #[case] date: Option<&str>,
+        #[case] expected: Option<(i32, Option<u8>, Option<u8>)>,
+    ) {
+        let date = read_date(year, month, date);
+        assert_eq!(date.map(|d| (d.year, d.month, d.day)), expected);
+    }
+
+    #[test]
+    fn test_unread_values_are_kept() {
+        let input = "@misc{a,\n  title = {Preprint},\n  year = {in press},\n  \
+                     doi = {not a doi},\n  url = {https://doi.org/10.1000/xyz123},\n  \
+                     note = {First note},\n  note = {Second note},\n  title = {Other title}\n}";
+        let citation = &BibTexParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.title, "Preprint");
+        assert_eq!(citation.date, None);
+        assert_eq!(citation.extra_fields["year"], vec!["in press"]);
+        assert_eq!(citation.extra_fields["doi"], vec!["not a doi"]);
+        assert_eq!(citation.extra_fields["title"], vec!["Other title"]);
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/xyz123"));
+        assert_eq!(citation.notes.as_deref(), Some("First note\n\nSecond note"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "@article{a, title = {First}}\n\n@article{b, year = 2020}\n\
+                     @article{c, title = {Unclosed}\n";
+        let parser = BibTexParser::new();
+        let err = parser.parse(input).unwrap_err();
+        assert_eq!(err.line, Some(3));
+        assert_eq!(err.format, CitationFormat::BibTex);
+
+        let result = parser
+            .parse_lenient(
+                input,
+                LenientOptions {
+                    max_error_ratio: None,
+                    max_errors: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(result.citations.len(), 1);
+        let lines: Vec<_> = result.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![Some(3), Some(4)]);
+    }
+
+    #[test]
+    fn test_empty_input_and_other_formats() {
+        assert!(BibTexParser::new().parse("").unwrap().is_empty());
+        assert!(
+            BibTexParser::new()
+                .parse("% only a comment\n")
+                .unwrap()
+                .is_empty()
+        );
+        let err = BibTexParser::new()
+            .parse("TY  - JOUR\nTI  - Title\nER  - \n")
+            .unwrap_err();
+        assert!(matches!(err.error, ValueError::WrongFormat { .. }));
+    }
+}
@@ -0,0 +1,442 @@
+//! Reading the entries of a BibTeX file.
+//!
+//! Entries start with `@`, their type and an opening brace or parenthesis, and
+//! hold a citation key followed by `name = value` fields. Values are braced,
+//! quoted, bare numbers or `@string` abbreviations, joined with `#`. Text
+//! outside entries is a comment, as are `@comment` and `@preamble` entries.
+
+use crate::CitationFormat;
+use crate::error::{ParseError, ValueError};
+use crate::utils::{Coverage, is_bibtex_entry_start};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// The month abbreviations every BibTeX style defines.
+const MONTHS: [(&str, &str); 12] = [
+    ("jan", "January"),
+    ("feb", "February"),
+    ("mar", "March"),
+    ("apr", "April"),
+    ("may", "May"),
+    ("jun", "June"),
+    ("jul", "July"),
+    ("aug", "August"),
+    ("sep", "September"),
+    ("oct", "October"),
+    ("nov", "November"),
+    ("dec", "December"),
+];
+
+/// The fields of one entry, in file order.
+#[derive(Debug, Default)]
+pub(crate) struct RawBibEntry {
+    /// 1-based line number of the entry's `@`
+    pub(crate) line: usize,
+    /// Entry type, lowercase, e.g. "article"
+    pub(crate) entry_type: String,
+    /// Citation key, empty when the entry has none
+    pub(crate) key: String,
+    /// Lowercase field names and their values, with abbreviations expanded and
+    /// `#` concatenations joined; braces and LaTeX markup are kept
+    pub(crate) fields: Vec<(String, String)>,
+}
+
+/// Reads every entry of `text`; entries that cannot be read are errors at their line.
+pub(crate) fn read_entries(text: &str) -> Vec<Result<RawBibEntry, ParseError>> {
+    scan(text).0
+}
+
+/// Count the lines of a BibTeX text that [`read_entries`] reads, skips and
+/// starts entries at.
+///
+/// Lines outside entries are comments to BibTeX, but only blank lines and lines
+/// starting with `%` are not counted as skipped.
+pub(crate) fn bibtex_coverage(text: &str) -> Coverage {
+    let (entries, groups) = scan(text);
+    let mut coverage = Coverage {
+        records: entries.len(),
+        ..Coverage::default()
+    };
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        coverage.lines += 1;
+        let number = index + 1;
+        if !line.starts_with('%') && !groups.iter().any(|group| group.contains(&number)) {
+            coverage.unparsed.push(number);
+        }
+    }
+    coverage
+}
+
+/// Whether the first line of `text` that is neither blank nor a `%` comment
+/// starts an entry, such as `@article{`.
+pub(crate) fn starts_with_entry(text: &str) -> bool {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('%'))
+        .is_some_and(is_bibtex_entry_start)
+}
+
+/// Reads the entries of `text`, with the lines spanned by each `@` group.
+fn scan(
+    text: &str,
+) -> (
+    Vec<Result<RawBibEntry, ParseError>>,
+    Vec<RangeInclusive<usize>>,
+) {
+    let mut strings: HashMap<String, String> = MONTHS
+        .iter()
+        .map(|(abbreviation, month)| (abbreviation.to_string(), month.to_string()))
+        .collect();
+    let mut scanner = Scanner {
+        text,
+        pos: 0,
+        line: 1,
+    };
+    let mut entries = Vec::new();
+    let mut groups = Vec::new();
+
+    while scanner.skip_to_entry() {
+        let (start, line) = (scanner.pos, scanner.line);
+        scanner.bump();
+        scanner.skip_whitespace();
+        let entry_type = scanner.identifier().to_lowercase();
+        scanner.skip_whitespace();
+        let close = match scanner.peek() {
+            Some(b'{') => b'}',
+            Some(b'(') => b')',
+            // An "@" in a comment, such as an email address
+            _ => continue,
+        };
+        scanner.bump();
+
+        let result = match entry_type.as_str() {
+            "comment" | "preamble" => scanner.skip_group(close),
+            "string" => scanner.fields(close, &strings).map(|fields| {
+                strings.extend(fields);
+            }),
+            _ => scanner.entry(close, &strings).map(|(key, fields)| {
+                entries.push(Ok(RawBibEntry {
+                    line,
+                    entry_type,
+                    key,
+                    fields,
+                }));
+            }),
+        };
+        match result {
+            Ok(()) => groups.push(line..=scanner.line),
+            Err(reason) => {
+                entries.push(Err(ParseError::at_line(
+                    line,
+                    CitationFormat::BibTex,
+                    ValueError::Syntax(reason),
+                )));
+                // Go on from the next line starting an entry after this one's `@`
+                (scanner.pos, scanner.line) = (start, line);
+                let next = scanner.skip_to_next_line_entry();
+                groups.push(line..=next.saturating_sub(1).max(line));
+            }
+        }
+    }
+
+    (entries, groups)
+}
+
+/// A position in the text being read, with its line number.
+struct Scanner<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+        if byte == b'\n' {
+            self.line += 1;
+        }
+        Some(byte)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.bump();
+        }
+    }
+
+    /// Moves to the next `@`, returning whether there is one.
+    fn skip_to_entry(&mut self) -> bool {
+        while let Some(byte) = self.peek() {
+            if byte == b'@' {
+                return true;
+            }
+            self.bump();
+        }
+        false
+    }
+
+    /// Moves to the next line that starts an entry, to recover from an error,
+    /// returning its line number, or the line after the last when there is none.
+    fn skip_to_next_line_entry(&mut self) -> usize {
+        while let Some(byte) = self.bump() {
+            if byte == b'\n' && is_bibtex_entry_start(&self.text[self.pos..]) {
+                return self.line;
+            }
+        }
+        self.line + 1
+    }
+
+    /// Reads a name: an entry type, field name or abbreviation.
+    fn identifier(&mut self) -> &'a str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| !b.is_ascii_whitespace() && !b"{}()\",=#%@".contains(&b))
+        {
+            self.bump();
+        }
+        &self.text[start..self.pos]
+    }
+
+    /// Skips to just after the `close` ending the group being read.
+    fn skip_group(&mut self, close: u8) -> Result<(), String> {
+        let mut depth = 0usize;
+        while let Some(byte) = self.bump() {
+            match byte {
+                b'{' => depth += 1,
+                b'}' if depth > 0 => depth -= 1,
+                _ if byte == close && depth == 0 => return Ok(()),
+                _ => {}
+            }
+        }
+        Err("entry is not closed".to_string())
+    }
+
+    /// Reads the citation key and fields of an entry, up to its `close`.
+    fn entry(
+        &mut self,
+        close: u8,
+        strings: &HashMap<String, String>,
+    ) -> Result<(String, Vec<(String, String)>), String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b != b',' && b != close) {
+            self.bump();
+        }
+        let key = self.text[start..self.pos].trim().to_string();
+        if self.peek() == Some(b',') {
+            self.bump();
+        }
+        Ok((key, self.fields(close, strings)?))
+    }
+
+    /// Reads `name = value` fields separated by commas, up to `close`.
+    fn fields(
+        &mut self,
+        close: u8,
+        strings: &HashMap<String, String>,
+    ) -> Result<Vec<(String, String)>, String> {
+        let mut fields = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Err("entry is not closed".to_string()),
+                Some(b',') => {
+                    self.bump();
+                    continue;
+                }
+                Some(byte) if byte == close => {
+                    self.bump();
+                    return Ok(fields);
+                }
+                Some(_) => {}
+            }
+
+            let name = self.identifier().to_lowercase();
+            if name.is_empty() {
+                let unexpected = self.text[self.pos..].chars().next().unwrap_or_default();
+                return Err(format!(
+                    "unexpected '{unexpected}' where a field name was expected"
+                ));
+            }
+            self.skip_whitespace();
+            if self.bump() != Some(b'=') {
+                return Err(format!("expected '=' after field \"{name}\""));
+            }
+            let value = self
+                .value(strings)
+                .map_err(|reason| format!("{reason} for field \"{name}\""))?;
+            fields.push((name, value));
+        }
+    }
+
+    /// Reads a value: braced or quoted text, a number or an abbreviation,
+    /// joined to more of them with `#`.
+    fn value(&mut self, strings: &HashMap<String, String>) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b'{') => {
+                    self.bump();
+                    value.push_str(self.delimited(b'}')?);
+                }
+                Some(b'"') => {
+                    self.bump();
+                    value.push_str(self.delimited(b'"')?);
+                }
+                _ => {
+                    let name = self.identifier();
+                    if name.is_empty() {
+                        return Err("missing value".to_string());
+                    }
+                    match strings.get(&name.to_lowercase()) {
+                        Some(expanded) => value.push_str(expanded),
+                        // Numbers, and abbreviations no @string defines
+                        None => value.push_str(name),
+                    }
+                }
+            }
+            self.skip_whitespace();
+            if self.peek() != Some(b'#') {
+                return Ok(value);
+            }
+            self.bump();
+        }
+    }
+
+    /// Reads text up to `end` outside any braces, after its opening delimiter,
+    /// returning it without the delimiters.
+    fn delimited(&mut self, end: u8) -> Result<&'a str, String> {
+        let start = self.pos;
+        let mut depth = 0usize;
+        while let Some(byte) = self.bump() {
+            match byte {
+                // Escaped quotes and braces do not delimit
+                b'\\' if self.peek().is_some_and(|b| b"\"{}".contains(&b)) => {
+                    self.bump();
+                }
+                _ if byte == end && depth == 0 => return Ok(&self.text[start..self.pos - 1]),
+                b'{' => depth += 1,
+                b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Err("unterminated value".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn fields(entry: &RawBibEntry) -> Vec<(&str, &str)> {
+        entry
+            .fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_values_and_concatenation() {
+        let input = r#"@String{jco = "J Clin Oncol"}
+@string(pre = {Early })
+
+@Article{smith2023,
+  Title = pre # "mobilisation of {ICU} patients",
+  journal = jco,
+  year = 2023, month = mar,
+  author = "M\"{u}ller, Hans" # { and {nested {braces}}},
+  publisher = unknownabbrev,
+}"#;
+        let entries = read_entries(input);
+        assert_eq!(entries.len(), 1);
+        let entry = entries[0].as_ref().unwrap();
+        assert_eq!(entry.line, 4);
+        assert_eq!(entry.entry_type, "article");
+        assert_eq!(entry.key, "smith2023");
+        assert_eq!(
+            fields(entry),
+            vec![
+                ("title", "Early mobilisation of {ICU} patients"),
+                ("journal", "J Clin Oncol"),
+                ("year", "2023"),
+                ("month", "March"),
+                ("author", r#"M\"{u}ller, Hans and {nested {braces}}"#),
+                ("publisher", "unknownabbrev"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comments_and_parenthesized_entries() {
+        let input = "Exported by a reference manager; contact me@example.org\n\
+                     @comment{jabref-meta: databaseType:bibtex;}\n\
+                     @preamble{ \"\\newcommand{\\noop}[1]{}\" }\n\
+                     @book(doe2020, title = {A Book}, author = {Doe, Jane})\n\
+                     @misc{nokey}\n";
+        let entries = read_entries(input);
+        assert_eq!(entries.len(), 2);
+        let book = entries[0].as_ref().unwrap();
+        assert_eq!(book.key, "doe2020");
+        assert_eq!(
+            fields(book),
+            vec![("title", "A Book"), ("author", "Doe, Jane")]
+        );
+        let misc = entries[1].as_ref().unwrap();
+        assert_eq!((misc.key.as_str(), misc.fields.len()), ("nokey", 0));
+    }
+
+    #[test]
+    fn test_errors_recover_at_next_entry() {
+        let input = "@article{first,\n  title = {Unclosed\n\n\
+                     @article{second, title {Missing equals}}\n\
+                     @article{third, title = {Fine}}\n";
+        let entries = read_entries(input);
+        assert_eq!(entries.len(), 3);
+        let errors: Vec<String> = entries[..2]
+            .iter()
+            .map(|entry| entry.as_ref().unwrap_err().to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "Error in BibTeX format at line 1: Bad syntax: unterminated value for field \"title\"",
+                "Error in BibTeX format at line 4: Bad syntax: expected '=' after field \"title\"",
+            ]
+        );
+        assert_eq!(entries[2].as_ref().unwrap().key, "third");
+
+        let coverage = bibtex_coverage(input);
+        assert_eq!((coverage.records, coverage.unparsed.len()), (3, 0));
+    }
+
+    #[test]
+    fn test_bibtex_coverage() {
+        let input =
+            "% A comment\nstray text\n@article{a,\n  title = {A}\n}\n\n@book{b, title = {B}}\n";
+        let coverage = bibtex_coverage(input);
+        assert_eq!(coverage.lines, 6);
+        assert_eq!(coverage.unparsed, vec![2]);
+        assert_eq!(coverage.records, 2);
+    }
+
+    #[test]
+    fn test_starts_with_entry() {
+        assert!(starts_with_entry("% Exported\n\n@article{a, title = {A}}"));
+        assert!(starts_with_entry("\u{feff}@Book (b, title = {B})"));
+        assert!(!starts_with_entry("TY  - JOUR\n@article{a,}"));
+        assert!(!starts_with_entry("@ not an entry"));
+    }
+}
@@ -0,0 +1,267 @@
+//! Equality and hashing of citations by their content.
+//!
+//! [`Citation`] implements neither `Eq` nor `Hash`, as two records of one work
+//! differ in fields such as the id, source or import time. [`ContentKey`]
+//! identifies the work a citation describes, and [`ContentHashedCitation`]
+//! wraps a citation to compare and hash it by that key, so exact repeats can
+//! be removed with a `HashSet`.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::{Citation, ContentHashedCitation};
+//! use std::collections::HashSet;
+//!
+//! let citation = |id: &str, doi: &str| {
+//!     let mut citation = Citation {
+//!         title: "Early mobilisation after cardiac surgery".to_string(),
+//!         doi: Some(doi.to_string()),
+//!         ..Default::default()
+//!     };
+//!     citation.set_id(id);
+//!     citation
+//! };
+//!
+//! let set: HashSet<ContentHashedCitation> = [
+//!     citation("pubmed-1", "10.1111/jocn.15600"),
+//!     citation("embase-7", "https://doi.org/10.1111/JOCN.15600"),
+//! ]
+//! .into_iter()
+//! .map(ContentHashedCitation::new)
+//! .collect();
+//! assert_eq!(set.len(), 1);
+//! ```
+
+use crate::Citation;
+use crate::text::normalize_title;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// What identifies the work a citation describes, from [`Citation::content_key`].
+///
+/// The first of these a citation has is used:
+///
+/// 1. the DOI, normalized like the deduplicator does, lowercase and without
+///    the resolver or a `[doi]` suffix
+/// 2. the PMID, trimmed
+/// 3. the title normalized with [`normalize_title`], with the publication
+///    year, the normalized family name of the first author, the trimmed and
+///    lowercased volume and the pages with the end page completed
+///
+/// Ids, sources, abstracts, keywords and all other fields are left out.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContentKey {
+    /// A normalized DOI
+    Doi(String),
+    /// A PMID
+    Pmid(String),
+    /// Normalized bibliographic fields, for citations without DOI or PMID
+    Fields {
+        /// Normalized title
+        title: String,
+        /// Publication year
+        year: Option<i32>,
+        /// Normalized family name of the first author
+        first_author: Option<String>,
+        /// Trimmed, lowercased volume
+        volume: Option<String>,
+        /// Pages with the end page completed, e.g. "210-218" for "210-8"
+        pages: Option<String>,
+    },
+}
+
+impl ContentKey {
+    /// The content key of `citation`.
+    #[must_use]
+    pub fn of(citation: &Citation) -> Self {
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        if let Some(doi) = citation.doi.as_deref().and_then(crate::utils::format_doi) {
+            return ContentKey::Doi(doi);
+        }
+        if let Some(pmid) = non_empty(&citation.pmid) {
+            return ContentKey::Pmid(pmid);
+        }
+        ContentKey::Fields {
+            title: normalize_title(&citation.title),
+            year: citation.date.as_ref().map(|date| date.year),
+            first_author: citation
+                .authors
+                .first()
+                .map(|author| normalize_title(&author.name))
+                .filter(|name| !name.is_empty()),
+            volume: non_empty(&citation.volume).map(|volume| volume.to_lowercase()),
+            pages: non_empty(&citation.pages)
+                .map(|pages| crate::utils::format_page_numbers(&pages)),
+        }
+    }
+}
+
+/// A citation compared and hashed by its [`ContentKey`], for use in sets and
+/// as a map key.
+///
+/// The key is computed once, when the citation is wrapped. The wrapper
+/// dereferences to the citation, and borrows as the key, so a set of wrapped
+/// citations can be searched with [`Citation::content_key`].
+#[derive(Debug, Clone)]
+pub struct ContentHashedCitation {
+    citation: Citation,
+    key: ContentKey,
+}
+
+impl ContentHashedCitation {
+    /// Wraps `citation`, computing its content key.
+    #[must_use]
+    pub fn new(citation: Citation) -> Self {
+        let key = citation.content_key();
+        Self { citation, key }
+    }
+
+    /// The content key the citation is compared by.
+    #[must_use]
+    pub fn key(&self) -> &ContentKey {
+        &self.key
+    }
+
+    /// Unwraps the citation.
+    #[must_use]
+    pub fn into_inner(self) -> Citation {
+        self.citation
+    }
+}
+
+impl From<Citation> for ContentHashedCitation {
+    fn from(citation: Citation) -> Self {
+        Self::new(citation)
+    }
+}
+
+impl Deref for ContentHashedCitation {
+    type Target = Citation;
+
+    fn deref(&self) -> &Citation {
+        &self.citation
+    }
+}
+
+impl Borrow<ContentKey> for ContentHashedCitation {
+    fn borrow(&self) -> &ContentKey {
+        &self.key
+    }
+}
+
+impl PartialEq for ContentHashedCitation {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for ContentHashedCitation {}
+
+impl Hash for ContentHashedCitation {
+    // Hashes like the key, as `Borrow<ContentKey>` requires
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, Date};
+    use std::collections::{HashMap, HashSet};
+
+    fn article() -> Citation {
+        Citation {
+            title: "Early mobilisation and delirium in critically ill adults".to_string(),
+            authors: vec![Author {
+                name: "Okafor".to_string(),
+                given_name: Some("Chidinma".to_string()),
+                middle_name: None,
+                suffix: None,
+                affiliations: Vec::new(),
+            }],
+            date: Some(Date {
+                year: 2021,
+                month: None,
+                day: None,
+            }),
+            volume: Some("45".to_string()),
+            pages: Some("210-218".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_representations_of_one_article_are_equal() {
+        let mut first = article();
+        first.set_id("1").set_source("PubMed");
+        let mut second = article();
+        second.set_id("2").set_source("Embase");
+        second.title = format!("  {}. ", second.title.replace(' ', "  "));
+        second.pages = Some(" 210-8".to_string());
+        let mut third = article();
+        third.set_id("3");
+        third.volume = Some("45 ".to_string());
+        third.abstract_text = Some("Background: ...".to_string());
+
+        let set: HashSet<ContentHashedCitation> = [first, second, third]
+            .into_iter()
+            .map(ContentHashedCitation::from)
+            .collect();
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&article().content_key()));
+        assert_eq!(set.iter().next().unwrap().id(), Some("1"));
+    }
+
+    #[test]
+    fn test_doi_and_pmid_come_first() {
+        let mut with_doi = article();
+        with_doi.doi = Some(" https://doi.org/10.5555/JCCR.2021.045 [doi]".to_string());
+        with_doi.pmid = Some("33510001".to_string());
+        let mut retitled = article();
+        retitled.title = "Another title".to_string();
+        retitled.doi = Some("10.5555/jccr.2021.045".to_string());
+        assert_eq!(
+            with_doi.content_key(),
+            ContentKey::Doi("10.5555/jccr.2021.045".to_string())
+        );
+        assert_eq!(with_doi.content_key(), retitled.content_key());
+
+        let mut with_pmid = article();
+        with_pmid.pmid = Some(" 33510001 ".to_string());
+        with_pmid.doi = Some("not a doi".to_string());
+        assert_eq!(
+            with_pmid.content_key(),
+            ContentKey::Pmid("33510001".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fields_that_identify_the_work_differ() {
+        let key = article().content_key();
+        let mut other_year = article();
+        other_year.date.as_mut().unwrap().year = 2020;
+        let mut other_author = article();
+        other_author.authors[0].name = "Lindqvist".to_string();
+        let mut other_pages = article();
+        other_pages.pages = Some("210-219".to_string());
+        for other in [other_year, other_author, other_pages] {
+            assert_ne!(other.content_key(), key);
+        }
+
+        let mut counts: HashMap<ContentHashedCitation, usize> = HashMap::new();
+        for citation in [article(), article()] {
+            *counts.entry(citation.into()).or_default() += 1;
+        }
+        assert_eq!(counts[&key], 2);
+    }
+}
@@ -0,0 +1,309 @@
+//! Heuristic checks for values that landed in the wrong column.
+//!
+//! A mapping that sends the abstracts to the title column, or the authors to the
+//! journal, still parses; the values are merely absurd. [`audit_citations`]
+//! looks for such values and reports them as [`AuditFinding`]s without changing
+//! the citations, and [`super::CsvParser::parse_with_audit`] does so while
+//! parsing.
+
+use crate::Citation;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Number of characters of a value kept in [`AuditFinding::excerpt`].
+const EXCERPT_CHARS: usize = 80;
+
+/// Limits checked by [`audit_citations`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::csv::AuditConfig;
+///
+/// let config = AuditConfig {
+///     max_title_length: 300,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    /// Maximum number of characters in a title
+    pub max_title_length: usize,
+    /// Maximum number of characters in an author's family name
+    pub max_family_name_length: usize,
+    /// Earliest plausible publication year
+    pub min_year: i32,
+    /// Latest plausible publication year
+    pub max_year: i32,
+    /// Maximum number of characters in a volume
+    pub max_volume_length: usize,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            max_title_length: 500,
+            max_family_name_length: 60,
+            min_year: 1500,
+            max_year: 2100,
+            max_volume_length: 10,
+        }
+    }
+}
+
+/// Why a value was reported by [`audit_citations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditReason {
+    /// The value has more characters than allowed
+    TooLong {
+        /// Number of characters of the value
+        length: usize,
+        /// The limit it exceeds
+        max: usize,
+    },
+    /// The title reads as several sentences, like an abstract
+    MultipleSentences {
+        /// Number of sentence-ending periods, question or exclamation marks
+        count: usize,
+    },
+    /// The family name contains digits
+    ContainsDigits,
+    /// The publication year is outside the plausible range
+    YearOutOfRange {
+        /// The year of the citation
+        year: i32,
+    },
+}
+
+impl fmt::Display for AuditReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditReason::TooLong { length, max } => {
+                write!(f, "{} characters, more than {}", length, max)
+            }
+            AuditReason::MultipleSentences { count } => write!(f, "{} sentences", count),
+            AuditReason::ContainsDigits => f.write_str("contains digits"),
+            AuditReason::YearOutOfRange { year } => write!(f, "implausible year {}", year),
+        }
+    }
+}
+
+/// A suspicious value found by [`audit_citations`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditFinding {
+    /// 1-based position of the citation, i.e. the data row of a CSV file
+    pub row: usize,
+    /// The citation field holding the value, e.g. "title" or "authors"
+    pub field: &'static str,
+    /// Why the value is suspicious
+    pub reason: AuditReason,
+    /// The start of the value, ending in "..." when shortened
+    pub excerpt: String,
+}
+
+impl fmt::Display for AuditFinding {
+    /// Writes the finding as e.g. `row 3, title: 812 characters, more than 500 ("Background: ...")`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {}, {}: {} (\"{}\")",
+            self.row, self.field, self.reason, self.excerpt
+        )
+    }
+}
+
+/// Reports values of `citations` that suggest a misconfigured column mapping.
+///
+/// The checks are:
+///
+/// - titles longer than [`AuditConfig::max_title_length`], or with two or more
+///   sentence ends, counting a period only after a word of three or more
+///   letters so that initials and abbreviations such as "vs." are not counted;
+/// - author family names longer than [`AuditConfig::max_family_name_length`] or
+///   containing digits;
+/// - publication years outside [`AuditConfig::min_year`] to
+///   [`AuditConfig::max_year`];
+/// - volumes longer than [`AuditConfig::max_volume_length`].
+///
+/// Findings are ordered by row, then in the order above.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::Citation;
+/// use biblib::csv::{AuditConfig, AuditReason, audit_citations};
+///
+/// let citation = Citation {
+///     title: "We studied mobilisation. It helped patients. Further work is needed.".to_string(),
+///     ..Default::default()
+/// };
+/// let findings = audit_citations(&[citation], &AuditConfig::default());
+///
+/// assert_eq!(findings[0].field, "title");
+/// assert_eq!(findings[0].reason, AuditReason::MultipleSentences { count: 3 });
+/// ```
+#[must_use]
+pub fn audit_citations(citations: &[Citation], config: &AuditConfig) -> Vec<AuditFinding> {
+    let mut findings = Vec::new();
+    for (index, citation) in citations.iter().enumerate() {
+        let mut report = |field, reason, value: &str| {
+            findings.push(AuditFinding {
+                row: index + 1,
+                field,
+                reason,
+                excerpt: excerpt(value),
+            });
+        };
+
+        let title = citation.title.trim();
+        if let Some(reason) = too_long(title, config.max_title_length) {
+            report("title", reason, title);
+        }
+        let sentences = sentence_ends(title);
+        if sentences >= 2 {
+            report("title", AuditReason::MultipleSentences { count: sentences }, title);
+        }
+
+        for author in &citation.authors {
+            if let Some(reason) = too_long(&author.name, config.max_family_name_length) {
+                report("authors", reason, &author.name);
+            }
+            if author.name.chars().any(|c| c.is_ascii_digit()) {
+                report("authors", AuditReason::ContainsDigits, &author.name);
+            }
+        }
+
+        if let Some(date) = &citation.date
+            && !(config.min_year..=config.max_year).contains(&date.year)
+        {
+            let year = date.year;
+            report("date", AuditReason::YearOutOfRange { year }, &year.to_string());
+        }
+
+        if let Some(volume) = &citation.volume
+            && let Some(reason) = too_long(volume, config.max_volume_length)
+        {
+            report("volume", reason, volume);
+        }
+    }
+    findings
+}
+
+/// Returns [`AuditReason::TooLong`] when `value` has more than `max` characters.
+fn too_long(value: &str, max: usize) -> Option<AuditReason> {
+    let length = value.chars().count();
+    (length > max).then_some(AuditReason::TooLong { length, max })
+}
+
+/// Counts the sentence ends of `text`: a ".", "?" or "!" at the end of the text
+/// or followed by whitespace and an uppercase letter.
+///
+/// A period only counts after a word of at least three letters.
+fn sentence_ends(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    (0..chars.len())
+        .filter(|&i| {
+            let ends_sentence = match chars[i] {
+                '?' | '!' => true,
+                '.' => chars[..i].iter().rev().take_while(|c| c.is_alphabetic()).count() >= 3,
+                _ => false,
+            };
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            ends_sentence
+                && match next {
+                    None => true,
+                    Some(next) => chars[i + 1].is_whitespace() && next.is_uppercase(),
+                }
+        })
+        .count()
+}
+
+/// Shortens `value` to [`EXCERPT_CHARS`] characters, marking the cut with "...".
+fn excerpt(value: &str) -> String {
+    match value.char_indices().nth(EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}...", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, Date};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Effect of early mobilisation after cardiac surgery", 0)]
+    #[case("Effect of early mobilisation after cardiac surgery.", 1)]
+    #[case("Is it safe? A cohort study", 1)]
+    #[case("Aspirin vs. placebo in J. Smith's cohort, e.g. Sydney.", 1)]
+    #[case("Version 2.0 of the tool. Field tests.", 2)]
+    #[case("Background. We enrolled 40 patients. Results were mixed.", 3)]
+    #[case("Ends with a period. then lowercase", 0)]
+    fn test_sentence_ends(#[case] text: &str, #[case] count: usize) {
+        assert_eq!(sentence_ends(text), count, "{text}");
+    }
+
+    #[test]
+    fn test_audit_citations() {
+        let author = |name: &str| Author {
+            name: name.to_string(),
+            given_name: None,
+            middle_name: None,
+            suffix: None,
+            affiliations: vec![],
+        };
+        let plausible = Citation {
+            title: "Effect of early mobilisation after cardiac surgery.".to_string(),
+            authors: vec![author("Nguyen"), author("van der Berg")],
+            date: Some(Date {
+                year: 2021,
+                month: None,
+                day: None,
+            }),
+            volume: Some("30".to_string()),
+            ..Default::default()
+        };
+        let suspicious = Citation {
+            title: "x".repeat(501),
+            authors: vec![author("Journal of Clinical Nursing 30(5)")],
+            date: Some(Date {
+                year: 30,
+                month: None,
+                day: None,
+            }),
+            volume: Some("Journal of Clinical Nursing".to_string()),
+            ..Default::default()
+        };
+
+        let citations = [plausible, suspicious];
+        let findings = audit_citations(&citations, &AuditConfig::default());
+        let summary: Vec<_> = findings.iter().map(|f| (f.row, f.field, f.reason.clone())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, "title", AuditReason::TooLong { length: 501, max: 500 }),
+                (2, "authors", AuditReason::ContainsDigits),
+                (2, "date", AuditReason::YearOutOfRange { year: 30 }),
+                (2, "volume", AuditReason::TooLong { length: 27, max: 10 }),
+            ]
+        );
+        assert_eq!(findings[0].excerpt, format!("{}...", "x".repeat(80)));
+        assert_eq!(
+            findings[3].to_string(),
+            "row 2, volume: 27 characters, more than 10 (\"Journal of Clinical Nursing\")"
+        );
+
+        let config = AuditConfig {
+            max_title_length: 600,
+            min_year: 0,
+            max_volume_length: 30,
+            ..Default::default()
+        };
+        let findings = audit_citations(&citations, &config);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].reason, AuditReason::ContainsDigits);
+    }
+}
@@ -3,7 +3,9 @@
 //! This module defines the default header mappings and configuration
 //! structures for CSV parsing.
 
-use std::collections::HashMap;
+use crate::NameOrder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Default header mappings for common CSV column names
 pub(crate) const DEFAULT_HEADERS: &[(&str, &[&str])] = &[
@@ -24,10 +26,72 @@ pub(crate) const DEFAULT_HEADERS: &[(&str, &[&str])] = &[
     ("language", &["language", "lang"]),
     ("publisher", &["publisher"]),
     ("url", &["url", "link", "web link"]),
+    ("source", &["source", "database", "database provider"]),
     ("label", &["label"]),
     ("duplicate_id", &["duplicateid", "duplicate_id"]),
 ];
 
+/// Header aliases of exports in languages other than English.
+///
+/// Each alias set is enabled with [`CsvConfig::add_header_language`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeaderLanguage {
+    /// German headers such as "Titel", "Autoren" and "Jahr"
+    German,
+    /// French headers such as "Titre", "Auteurs" and "Année"
+    French,
+    /// Spanish headers such as "Título", "Autores" and "Año"
+    Spanish,
+}
+
+impl HeaderLanguage {
+    /// Header aliases of this language, by field.
+    fn headers(self) -> &'static [(&'static str, &'static [&'static str])] {
+        match self {
+            HeaderLanguage::German => &[
+                ("title", &["titel"]),
+                ("authors", &["autor", "autoren", "verfasser"]),
+                ("journal", &["zeitschrift"]),
+                ("year", &["jahr", "erscheinungsjahr"]),
+                ("volume", &["band"]),
+                ("issue", &["heft", "ausgabe"]),
+                ("pages", &["seiten"]),
+                ("abstract", &["zusammenfassung"]),
+                ("keywords", &["schlagwörter", "schlagworte", "stichwörter"]),
+                ("language", &["sprache"]),
+                ("publisher", &["verlag"]),
+            ],
+            HeaderLanguage::French => &[
+                ("title", &["titre"]),
+                ("authors", &["auteur", "auteurs"]),
+                ("journal", &["revue"]),
+                ("year", &["année", "annee", "année de publication"]),
+                ("issue", &["numéro", "numero"]),
+                ("abstract", &["résumé", "resume"]),
+                ("keywords", &["mots-clés", "mots clés", "mots-cles"]),
+                ("language", &["langue"]),
+                ("publisher", &["éditeur", "editeur"]),
+                ("url", &["lien"]),
+            ],
+            HeaderLanguage::Spanish => &[
+                ("title", &["título", "titulo"]),
+                ("authors", &["autor", "autores"]),
+                ("journal", &["revista"]),
+                ("year", &["año", "ano", "año de publicación"]),
+                ("volume", &["volumen"]),
+                ("issue", &["número", "numero"]),
+                ("pages", &["páginas", "paginas"]),
+                ("abstract", &["resumen"]),
+                ("keywords", &["palabras clave"]),
+                ("language", &["idioma"]),
+                ("publisher", &["editorial"]),
+                ("url", &["enlace"]),
+            ],
+        }
+    }
+}
+
 /// Configuration for CSV parsing with custom header mappings.
 ///
 /// Allows customization of how CSV columns are mapped to citation fields,
@@ -39,6 +103,8 @@ pub(crate) const DEFAULT_HEADERS: &[(&str, &[&str])] = &[
 /// - "title" → ["title", "article title", "publication title"]
 /// - "authors" → ["author", "authors", "creator", "creators"]
 /// - "year" → ["year", "publication year", "pub year"]
+/// - "source" → ["source", "database", "database provider"], read into
+///   [`crate::Citation::source`]
 ///   etc.
 ///
 /// # Examples
@@ -50,12 +116,57 @@ pub(crate) const DEFAULT_HEADERS: &[(&str, &[&str])] = &[
 /// config.set_header_mapping("title", vec!["Article Name".to_string()]);
 /// config.set_delimiter(b';');
 /// ```
+///
+/// # Header Check
+///
+/// A file with headers must have a column mapped to the title. Otherwise
+/// parsing fails up front, naming the headers found and the closest known
+/// aliases, rather than failing on every row for the missing title. Exports in
+/// German, French or Spanish are read with [`CsvConfig::add_header_language`].
+///
+/// ```
+/// use biblib::csv::{CsvConfig, CsvParser, HeaderLanguage};
+/// use biblib::CitationParser;
+///
+/// let input = "Titel,Autoren,Jahr\nDelir auf der Intensivstation,Müller K,2021";
+/// let err = CsvParser::new().parse(input).unwrap_err();
+/// assert!(err.to_string().contains("\"Titel\" (did you mean \"title\"?)"));
+///
+/// let mut config = CsvConfig::new();
+/// config.add_header_language(HeaderLanguage::German);
+/// let citations = CsvParser::with_config(config).parse(input).unwrap();
+/// assert_eq!(citations[0].title, "Delir auf der Intensivstation");
+/// ```
+///
+/// # Configuration Files
+///
+/// The configuration serializes with serde. `header_mappings` replaces the
+/// aliases of the fields it lists, as [`CsvConfig::set_header_mapping`] does,
+/// `header_aliases` extends them as [`CsvConfig::add_header_aliases`] does and
+/// `header_languages` adds languages as [`CsvConfig::add_header_language`]
+/// does, in that order. The delimiter and quote are one-character strings, and
+/// the other options are named after their setters. Missing keys keep their
+/// defaults, and the result is checked with [`CsvConfig::validate`].
+///
+/// ```
+/// use biblib::csv::CsvConfig;
+///
+/// let config: CsvConfig = serde_json::from_str(
+///     r#"{"delimiter": ";", "header_mappings": {"title": ["Article Name"]}}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(config.get_field_mappings()["title"], ["Article Name"]);
+///
+/// assert!(serde_json::from_str::<CsvConfig>(r#"{"delimiter": "\n"}"#).is_err());
+/// ```
 #[derive(Debug, Clone)]
 pub struct CsvConfig {
     /// Custom header mappings for CSV columns
     pub(crate) header_map: HashMap<String, Vec<String>>,
     /// Reverse lookup map for O(1) header-to-field mapping
     pub(crate) reverse_map: HashMap<String, String>,
+    /// Fields whose aliases were set or extended by the user
+    pub(crate) custom_fields: HashSet<String>,
     /// Delimiter to use for parsing the CSV
     pub(crate) delimiter: u8,
     /// Whether the CSV has headers
@@ -68,6 +179,10 @@ pub struct CsvConfig {
     pub(crate) flexible: bool,
     /// Whether to store original record for debugging (memory optimization)
     pub(crate) store_original_record: bool,
+    /// Order of author names written without a comma
+    pub(crate) name_order: NameOrder,
+    /// Whether a file with headers must have a column mapped to the title
+    pub(crate) require_title_column: bool,
 }
 
 impl Default for CsvConfig {
@@ -83,12 +198,15 @@ impl CsvConfig {
         let mut config = Self {
             header_map: HashMap::new(),
             reverse_map: HashMap::new(),
+            custom_fields: HashSet::new(),
             delimiter: b',',
             has_header: true,
             quote: b'"',
             trim: true,
             flexible: false,
             store_original_record: false,
+            name_order: NameOrder::FamilyFirst,
+            require_title_column: true,
         };
         config.set_default_headers();
         config
@@ -115,15 +233,36 @@ impl CsvConfig {
         }
     }
 
+    /// Removes `aliases` from the default mappings of fields other than `field`,
+    /// so a custom mapping can take over a default alias such as "source".
+    fn claim_aliases(&mut self, field: &str, aliases: &[String]) {
+        let claimed: HashSet<String> = aliases.iter().map(|a| a.to_lowercase()).collect();
+        for (other, other_aliases) in &mut self.header_map {
+            if other != field && !self.custom_fields.contains(other) {
+                other_aliases.retain(|alias| !claimed.contains(&alias.to_lowercase()));
+            }
+        }
+        let custom_fields = &self.custom_fields;
+        self.header_map
+            .retain(|other, aliases| !aliases.is_empty() || custom_fields.contains(other));
+        self.custom_fields.insert(field.to_string());
+    }
+
     /// Sets a custom header mapping
+    ///
+    /// The aliases are removed from the default mappings of other fields.
     pub fn set_header_mapping(&mut self, field: &str, aliases: Vec<String>) -> &mut Self {
+        self.claim_aliases(field, &aliases);
         self.header_map.insert(field.to_string(), aliases);
         self.rebuild_reverse_map();
         self
     }
 
     /// Adds additional aliases to an existing field mapping
+    ///
+    /// The aliases are removed from the default mappings of other fields.
     pub fn add_header_aliases(&mut self, field: &str, aliases: Vec<String>) -> &mut Self {
+        self.claim_aliases(field, &aliases);
         self.header_map
             .entry(field.to_string())
             .or_default()
@@ -132,6 +271,26 @@ impl CsvConfig {
         self
     }
 
+    /// Adds the header aliases of exports in `language`, such as "Titel" and
+    /// "Autoren" for German.
+    ///
+    /// The aliases extend the default mappings, so English headers are still
+    /// recognized. Aliases already mapped to another field are not added.
+    pub fn add_header_language(&mut self, language: HeaderLanguage) -> &mut Self {
+        for (field, aliases) in language.headers() {
+            for alias in *aliases {
+                if !self.reverse_map.contains_key(*alias) {
+                    self.header_map
+                        .entry(field.to_string())
+                        .or_default()
+                        .push(alias.to_string());
+                    self.reverse_map.insert(alias.to_string(), field.to_string());
+                }
+            }
+        }
+        self
+    }
+
     /// Sets the delimiter character
     pub fn set_delimiter(&mut self, delimiter: u8) -> &mut Self {
         self.delimiter = delimiter;
@@ -168,6 +327,86 @@ impl CsvConfig {
         self
     }
 
+    /// Sets how author names without a comma are split, for files that list
+    /// authors given-first ("John Smith")
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::NameOrder;
+    /// use biblib::csv::CsvConfig;
+    ///
+    /// let mut config = CsvConfig::new();
+    /// config.set_name_order(NameOrder::Auto);
+    /// ```
+    pub fn set_name_order(&mut self, order: NameOrder) -> &mut Self {
+        self.name_order = order;
+        self
+    }
+
+    /// Sets whether parsing a file with headers fails when no column maps to the
+    /// title (see [Header Check](CsvConfig#header-check)).
+    ///
+    /// Enabled by default. Disable it to read files whose columns are all kept
+    /// in `extra_fields`; each row then fails for its missing title as it is
+    /// converted, which lenient parsing can collect. Files without headers are
+    /// never checked.
+    pub fn set_require_title_column(&mut self, require: bool) -> &mut Self {
+        self.require_title_column = require;
+        self
+    }
+
+    /// Checks that one of `headers` maps to the title, as required by
+    /// [`CsvConfig::set_require_title_column`].
+    ///
+    /// The error message lists the headers, each with the closest known alias
+    /// when one is within a small edit distance.
+    pub(crate) fn check_headers(&self, headers: &[String]) -> Result<(), String> {
+        if !self.require_title_column
+            || !self.has_header
+            || headers
+                .iter()
+                .any(|header| self.get_field_for_header(header) == Some("title"))
+        {
+            return Ok(());
+        }
+
+        let found: Vec<String> = headers
+            .iter()
+            .map(|header| match self.closest_alias(header) {
+                Some(alias) => format!("\"{}\" (did you mean \"{}\"?)", header, alias),
+                None => format!("\"{}\"", header),
+            })
+            .collect();
+        let mut title_aliases: Vec<&str> = self
+            .header_map
+            .get("title")
+            .map(|aliases| aliases.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        title_aliases.sort_unstable();
+        Err(format!(
+            "no column maps to the title; expected one of {}, found {}",
+            title_aliases.join(", "),
+            found.join(", ")
+        ))
+    }
+
+    /// The known alias closest to an unmapped `header`, within an edit distance
+    /// of 2 and less than half its length.
+    fn closest_alias(&self, header: &str) -> Option<&str> {
+        if self.get_field_for_header(header).is_some() {
+            return None;
+        }
+        let header = header.to_lowercase();
+        let max_distance = 2.min((header.chars().count().saturating_sub(1)) / 2);
+        self.reverse_map
+            .keys()
+            .map(|alias| (edit_distance(&header, alias), alias))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min()
+            .map(|(_, alias)| alias.as_str())
+    }
+
     /// Finds the field name for a given header using O(1) lookup
     pub(crate) fn get_field_for_header(&self, header: &str) -> Option<&str> {
         let header_lower = header.to_lowercase();
@@ -210,13 +449,13 @@ impl CsvConfig {
         for (field, aliases) in &self.header_map {
             for alias in aliases {
                 let alias_lower = alias.to_lowercase();
-                if let Some(existing_field) = all_aliases.get(&alias_lower) {
-                    if existing_field != field {
-                        return Err(format!(
-                            "Alias '{}' is mapped to both '{}' and '{}'",
-                            alias, existing_field, field
-                        ));
-                    }
+                if let Some(existing_field) = all_aliases.get(&alias_lower)
+                    && existing_field != field
+                {
+                    return Err(format!(
+                        "Alias '{}' is mapped to both '{}' and '{}'",
+                        alias, existing_field, field
+                    ));
                 }
                 all_aliases.insert(alias_lower, field.clone());
             }
@@ -226,6 +465,124 @@ impl CsvConfig {
     }
 }
 
+/// The serialized form of [`CsvConfig`].
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CsvConfigFields {
+    header_mappings: BTreeMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    header_aliases: BTreeMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    header_languages: Vec<HeaderLanguage>,
+    delimiter: char,
+    has_header: bool,
+    quote: char,
+    trim: bool,
+    flexible: bool,
+    store_original_record: bool,
+    name_order: NameOrder,
+    require_title_column: bool,
+}
+
+impl Default for CsvConfigFields {
+    fn default() -> Self {
+        let config = CsvConfig::new();
+        Self {
+            header_mappings: BTreeMap::new(),
+            header_aliases: BTreeMap::new(),
+            header_languages: Vec::new(),
+            delimiter: char::from(config.delimiter),
+            has_header: config.has_header,
+            quote: char::from(config.quote),
+            trim: config.trim,
+            flexible: config.flexible,
+            store_original_record: config.store_original_record,
+            name_order: config.name_order,
+            require_title_column: config.require_title_column,
+        }
+    }
+}
+
+impl Serialize for CsvConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CsvConfigFields {
+            header_mappings: self
+                .header_map
+                .iter()
+                .map(|(field, aliases)| (field.clone(), aliases.clone()))
+                .collect(),
+            header_aliases: BTreeMap::new(),
+            header_languages: Vec::new(),
+            delimiter: char::from(self.delimiter),
+            has_header: self.has_header,
+            quote: char::from(self.quote),
+            trim: self.trim,
+            flexible: self.flexible,
+            store_original_record: self.store_original_record,
+            name_order: self.name_order,
+            require_title_column: self.require_title_column,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CsvConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let fields = CsvConfigFields::deserialize(deserializer)?;
+        let byte = |c: char, name: &str| {
+            u8::try_from(c).ok().filter(u8::is_ascii).ok_or_else(|| {
+                D::Error::custom(format!("{} '{}' is not an ASCII character", name, c))
+            })
+        };
+
+        let mut config = CsvConfig::new();
+        for (field, aliases) in fields.header_mappings {
+            config.set_header_mapping(&field, aliases);
+        }
+        for (field, aliases) in fields.header_aliases {
+            config.add_header_aliases(&field, aliases);
+        }
+        for language in fields.header_languages {
+            config.add_header_language(language);
+        }
+        config
+            .set_delimiter(byte(fields.delimiter, "delimiter")?)
+            .set_has_header(fields.has_header)
+            .set_quote(byte(fields.quote, "quote")?)
+            .set_trim(fields.trim)
+            .set_flexible(fields.flexible)
+            .set_store_original_record(fields.store_original_record)
+            .set_name_order(fields.name_order)
+            .set_require_title_column(fields.require_title_column);
+        config.validate().map_err(D::Error::custom)?;
+        Ok(config)
+    }
+}
+
+/// The number of insertions, deletions, substitutions and transpositions of
+/// adjacent characters that turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<usize>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,6 +603,22 @@ mod tests {
         assert_eq!(config.get_field_for_header("my_title"), Some("title"));
     }
 
+    #[test]
+    fn test_custom_mapping_takes_over_default_alias() {
+        let mut config = CsvConfig::new();
+        assert_eq!(config.get_field_for_header("Source"), Some("source"));
+
+        config.set_header_mapping("journal", vec!["Source".to_string()]);
+        assert_eq!(config.get_field_for_header("source"), Some("journal"));
+        assert_eq!(config.get_field_for_header("database"), Some("source"));
+        assert!(config.validate().is_ok());
+
+        config.add_header_aliases("journal", vec!["Database".to_string()]);
+        config.add_header_aliases("journal", vec!["Database Provider".to_string()]);
+        assert!(!config.get_field_mappings().contains_key("source"));
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_add_header_aliases() {
         let mut config = CsvConfig::new();
@@ -258,6 +631,53 @@ mod tests {
         assert_eq!(config.get_field_for_header("article_name"), Some("title"));
     }
 
+    #[test]
+    fn test_add_header_language() {
+        let mut config = CsvConfig::new();
+        assert_eq!(config.get_field_for_header("Titel"), None);
+
+        config
+            .add_header_language(HeaderLanguage::German)
+            .add_header_language(HeaderLanguage::Spanish)
+            .add_header_language(HeaderLanguage::French);
+        assert_eq!(config.get_field_for_header("Titel"), Some("title"));
+        assert_eq!(config.get_field_for_header("SCHLAGWÖRTER"), Some("keywords"));
+        assert_eq!(config.get_field_for_header("Año"), Some("year"));
+        assert_eq!(config.get_field_for_header("Mots-clés"), Some("keywords"));
+        assert_eq!(config.get_field_for_header("title"), Some("title"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_check_headers() {
+        let headers = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let mut config = CsvConfig::new();
+        assert!(config.check_headers(&headers(&["Article Title", "Jahr"])).is_ok());
+
+        let err = config
+            .check_headers(&headers(&["Titel", "Autoren", "Jahr", "DOI"]))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "no column maps to the title; expected one of article title, publication title, \
+             title, found \"Titel\" (did you mean \"title\"?), \"Autoren\", \"Jahr\", \"DOI\""
+        );
+
+        config.set_require_title_column(false);
+        assert!(config.check_headers(&headers(&["Titel"])).is_ok());
+        config.set_require_title_column(true).set_has_header(false);
+        assert!(config.check_headers(&headers(&["Column1"])).is_ok());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("titel", "title"), 1);
+        assert_eq!(edit_distance("autor", "author"), 1);
+        assert_eq!(edit_distance("jahr", "year"), 3);
+        assert_eq!(edit_distance("", "doi"), 3);
+        assert_eq!(edit_distance("año", "ano"), 1);
+    }
+
     #[test]
     fn test_get_field_for_header_case_insensitive() {
         let config = CsvConfig::new();
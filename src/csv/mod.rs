@@ -16,13 +16,19 @@
 //! assert_eq!(citations[0].title, "Example Paper");
 //! ```
 
+mod audit;
 mod config;
 mod parse;
 mod structure;
+mod write;
 
-use crate::{Citation, CitationFormat, CitationParser};
-pub use config::CsvConfig;
+use crate::error::ParseError;
+use crate::{Citation, CitationFormat, CitationParser, LenientOptions, LenientParse};
+use serde::{Deserialize, Serialize};
+pub use audit::{AuditConfig, AuditFinding, AuditReason, audit_citations};
+pub use config::{CsvConfig, HeaderLanguage};
 use parse::csv_parse;
+pub use write::CsvWriter;
 
 /// Parser for CSV-formatted citation data with configurable mappings.
 ///
@@ -84,10 +90,35 @@ use parse::csv_parse;
 ///
 /// assert!(citations[0].extra_fields.contains_key("Custom Field"));
 /// ```
-#[derive(Debug, Clone)]
+///
+/// # Sources
+///
+/// A "Source", "Database" or "Database Provider" column, as in files merged from
+/// several database exports, sets [`crate::Citation::source`] for the rows where
+/// it is not empty. The deduplicator's source preferences then apply per row.
+///
+/// ```
+/// use biblib::{CitationParser, csv::CsvParser};
+///
+/// let input = "Title,Database\nFirst,Embase\nSecond,";
+/// let citations = CsvParser::new().parse(input).unwrap();
+///
+/// assert_eq!(citations[0].source(), Some("Embase"));
+/// assert_eq!(citations[1].source(), None);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CsvParser {
     config: CsvConfig,
     auto_detect: bool,
+    dedupe_keywords: bool,
+    max_abstract_length: Option<usize>,
+    max_authors: Option<usize>,
+    stamp_ingestion_time: bool,
+    fold_typography: bool,
+    sentence_case_titles: bool,
+    raw_abstracts: bool,
+    archival: bool,
 }
 
 impl Default for CsvParser {
@@ -103,6 +134,14 @@ impl CsvParser {
         Self {
             config: CsvConfig::new(),
             auto_detect: false,
+            dedupe_keywords: false,
+            max_abstract_length: None,
+            max_authors: None,
+            stamp_ingestion_time: false,
+            fold_typography: false,
+            sentence_case_titles: false,
+            raw_abstracts: false,
+            archival: false,
         }
     }
 
@@ -112,6 +151,14 @@ impl CsvParser {
         Self {
             config,
             auto_detect: false,
+            dedupe_keywords: false,
+            max_abstract_length: None,
+            max_authors: None,
+            stamp_ingestion_time: false,
+            fold_typography: false,
+            sentence_case_titles: false,
+            raw_abstracts: false,
+            archival: false,
         }
     }
 
@@ -121,9 +168,57 @@ impl CsvParser {
         Self {
             config: CsvConfig::new(),
             auto_detect: true,
+            dedupe_keywords: false,
+            max_abstract_length: None,
+            max_authors: None,
+            stamp_ingestion_time: false,
+            fold_typography: false,
+            sentence_case_titles: false,
+            raw_abstracts: false,
+            archival: false,
         }
     }
 
+    /// Creates a CSV parser for Scopus exports.
+    ///
+    /// In addition to the default mappings, "Author Keywords" and "Index Keywords"
+    /// are both read as keywords (with case-insensitive duplicates removed),
+    /// "Abbreviated Source Title" as the journal abbreviation, "PubMed ID" as the
+    /// PMID, "Document Type" as the citation type and "Language of Original
+    /// Document" as the language, and "Cited by" as `times_cited`. Other Scopus
+    /// columns, such as "EID", are kept in `extra_fields`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, CsvParser};
+    ///
+    /// let input = "Title,Author Keywords,Index Keywords,Document Type\n\
+    ///              Example,sepsis; ICU,Sepsis; human,Article";
+    /// let citations = CsvParser::scopus().parse(input).unwrap();
+    ///
+    /// assert_eq!(citations[0].keywords, vec!["sepsis", "ICU", "human"]);
+    /// assert_eq!(citations[0].citation_type, vec!["Article"]);
+    /// ```
+    #[must_use]
+    pub fn scopus() -> Self {
+        let mut config = CsvConfig::new();
+        config
+            .add_header_aliases(
+                "keywords",
+                vec!["author keywords".to_string(), "index keywords".to_string()],
+            )
+            .add_header_aliases("journal_abbr", vec!["abbreviated source title".to_string()])
+            .add_header_aliases("pmid", vec!["pubmed id".to_string()])
+            .add_header_aliases("type", vec!["document type".to_string()])
+            .add_header_aliases("language", vec!["language of original document".to_string()])
+            .add_header_aliases("times_cited", vec!["cited by".to_string()]);
+
+        let mut parser = Self::with_config(config);
+        parser.set_dedupe_keywords(true);
+        parser
+    }
+
     /// Sets the configuration for this parser
     pub fn set_config(&mut self, config: CsvConfig) -> &mut Self {
         self.config = config;
@@ -146,6 +241,141 @@ impl CsvParser {
         self
     }
 
+    /// Sets whether to remove case-insensitive duplicate keywords from each citation.
+    ///
+    /// See [`Citation::dedupe_keywords`]. Disabled by default.
+    pub fn set_dedupe_keywords(&mut self, enabled: bool) -> &mut Self {
+        self.dedupe_keywords = enabled;
+        self
+    }
+
+    /// Sets the maximum abstract length in characters.
+    ///
+    /// Longer abstracts are shortened with [`Citation::truncate_abstract`] and
+    /// marked with [`crate::ABSTRACT_TRUNCATED_FIELD`]. `None`, the default,
+    /// keeps abstracts in full.
+    pub fn set_max_abstract_length(&mut self, max_chars: Option<usize>) -> &mut Self {
+        self.max_abstract_length = max_chars;
+        self
+    }
+
+    /// Sets the maximum number of authors kept per citation.
+    ///
+    /// Longer author lists, such as those of consortium papers, are shortened
+    /// with [`Citation::truncate_authors`], which records the full count in
+    /// `author_count` and marks the citation with
+    /// [`crate::AUTHORS_TRUNCATED_FIELD`]. `None`, the default, keeps all authors.
+    pub fn set_max_authors(&mut self, max_authors: Option<usize>) -> &mut Self {
+        self.max_authors = max_authors;
+        self
+    }
+
+    /// Sets whether each citation records when it was parsed in `ingested_at`.
+    ///
+    /// All citations of one parse get the same UTC time, as RFC 3339. Disabled by
+    /// default, so parsing the same input always gives the same output.
+    pub fn set_stamp_ingestion_time(&mut self, enabled: bool) -> &mut Self {
+        self.stamp_ingestion_time = enabled;
+        self
+    }
+
+    /// Sets whether typographic characters in the text fields are replaced with
+    /// ASCII equivalents.
+    ///
+    /// See [`Citation::fold_typography`]. Disabled by default, so stored fields
+    /// keep the characters of the source.
+    pub fn set_fold_typography(&mut self, enabled: bool) -> &mut Self {
+        self.fold_typography = enabled;
+        self
+    }
+
+    /// Sets whether all-caps titles are converted to sentence case.
+    ///
+    /// See [`Citation::sentence_case_title`]. Disabled by default, so titles
+    /// keep the case of the source.
+    pub fn set_sentence_case_titles(&mut self, enabled: bool) -> &mut Self {
+        self.sentence_case_titles = enabled;
+        self
+    }
+
+    /// Sets whether abstracts are cleaned of format-specific line breaks and
+    /// spacing.
+    ///
+    /// See [`Citation::clean_abstract`]. Enabled by default; disable it to keep
+    /// abstracts exactly as written in the source.
+    pub fn set_clean_abstracts(&mut self, enabled: bool) -> &mut Self {
+        self.raw_abstracts = !enabled;
+        self
+    }
+
+    /// Sets whether values are stored exactly as the file gives them.
+    ///
+    /// Archival mode is for collections that must keep what the file said. It
+    /// disables the normalization this parser applies by default:
+    ///
+    /// - the pages column is stored without completing a shortened end page
+    /// - the DOI column is the DOI as written, without removing a resolver
+    ///   prefix or stray text
+    /// - the year column read into `date` is also kept in `extra_fields` under
+    ///   [`crate::RAW_FIELD_PREFIX`], as "raw:year"
+    /// - abstracts are not cleaned, whatever [`CsvParser::set_clean_abstracts`]
+    ///   says
+    ///
+    /// Options that are disabled by default, such as typography folding, still
+    /// apply when enabled. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, csv::CsvParser};
+    ///
+    /// let input = "Title,Year,Pages,DOI\nExample,2023,1234-45,https://doi.org/10.1000/XYZ";
+    /// let mut parser = CsvParser::new();
+    /// parser.set_archival(true);
+    /// let citation = &parser.parse(input).unwrap()[0];
+    /// assert_eq!(citation.pages.as_deref(), Some("1234-45"));
+    /// assert_eq!(citation.doi.as_deref(), Some("https://doi.org/10.1000/XYZ"));
+    /// assert_eq!(citation.extra_fields["raw:year"], vec!["2023"]);
+    /// ```
+    pub fn set_archival(&mut self, enabled: bool) -> &mut Self {
+        self.archival = enabled;
+        self
+    }
+
+    /// Parses CSV content as [`CitationParser::parse`] and audits the citations
+    /// for values that suggest a misconfigured column mapping.
+    ///
+    /// The findings are those of [`audit_citations`]; the citations are returned
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` as for [`CsvParser::parse`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::csv::{AuditConfig, CsvParser};
+    ///
+    /// let input = "Title,Author,Year\nExample Paper,Smith J,2023";
+    /// let (citations, findings) = CsvParser::new()
+    ///     .parse_with_audit(input, &AuditConfig::default())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(citations.len(), 1);
+    /// assert!(findings.is_empty());
+    /// ```
+    pub fn parse_with_audit(
+        &self,
+        input: &str,
+        audit: &AuditConfig,
+    ) -> Result<(Vec<Citation>, Vec<AuditFinding>), ParseError> {
+        let mut citations = Vec::new();
+        self.parse_records(input, &mut citations, None)?;
+        let findings = audit_citations(&citations, audit);
+        Ok((citations, findings))
+    }
+
     /// Auto-detects CSV format parameters from the input
     fn auto_detect_format(&self, input: &str) -> CsvConfig {
         let mut config = self.config.clone();
@@ -160,6 +390,67 @@ impl CsvParser {
 
         config
     }
+
+    /// Parses `input` into `out`, as [`CitationParser::parse_into`] when `errors`
+    /// is `None`; otherwise records that fail to convert are pushed to `errors`.
+    fn parse_records(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        crate::utils::check_format(input, CitationFormat::Csv)?;
+        let config = self.auto_detect_format(input);
+        let raw_citations = csv_parse(input, &config)?;
+
+        let citations = raw_citations.into_iter().map(|mut raw| {
+            raw.archival = self.archival;
+            // Convert the citation, handling potential errors
+            raw.into_citation_with_config(&config)
+                .map_err(|citation_err| {
+                    // Convert CitationError to ParseError
+                    match citation_err {
+                        crate::error::CitationError::Parse(parse_err) => parse_err,
+                        crate::error::CitationError::UnknownFormat => {
+                            crate::error::ParseError::without_position(
+                                CitationFormat::Csv,
+                                crate::error::ValueError::Syntax("Unknown format".to_string()),
+                            )
+                        }
+                        crate::error::CitationError::Io(io_err) => {
+                            crate::error::ParseError::without_position(
+                                CitationFormat::Csv,
+                                crate::error::ValueError::Syntax(io_err.to_string()),
+                            )
+                        }
+                    }
+                })
+        });
+        let count = crate::utils::append_citations(out, citations, errors)?;
+        crate::utils::set_parsed_from_of_last(out, count, CitationFormat::Csv);
+        if self.dedupe_keywords {
+            crate::utils::dedupe_keywords_of_last(out, count);
+        }
+        if self.fold_typography {
+            crate::utils::fold_typography_of_last(out, count);
+        }
+        if self.sentence_case_titles {
+            crate::utils::sentence_case_titles_of_last(out, count);
+        }
+        if !self.raw_abstracts && !self.archival {
+            crate::utils::clean_abstracts_of_last(out, count);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            crate::utils::truncate_abstracts_of_last(out, count, max_chars);
+        }
+        if let Some(max_authors) = self.max_authors {
+            crate::utils::truncate_authors_of_last(out, count, max_authors);
+        }
+        if self.stamp_ingestion_time {
+            crate::utils::stamp_ingestion_time_of_last(out, count);
+        }
+        Ok(count)
+    }
 }
 
 impl CitationParser for CsvParser {
@@ -180,36 +471,41 @@ impl CitationParser for CsvParser {
     /// - Field validation errors
     /// - Configuration validation errors
     fn parse(&self, input: &str) -> std::result::Result<Vec<Citation>, crate::error::ParseError> {
-        let config = self.auto_detect_format(input);
-        let raw_citations = csv_parse(input, &config)?;
+        let mut citations = Vec::new();
+        self.parse_into(input, &mut citations)?;
+        Ok(citations)
+    }
 
-        let mut citations = Vec::with_capacity(raw_citations.len());
-        for raw in raw_citations {
-            // Convert the citation, handling potential errors
-            let citation = raw
-                .into_citation_with_config(&config)
-                .map_err(|citation_err| {
-                    // Convert CitationError to ParseError
-                    match citation_err {
-                        crate::error::CitationError::Parse(parse_err) => parse_err,
-                        crate::error::CitationError::UnknownFormat => {
-                            crate::error::ParseError::without_position(
-                                CitationFormat::Csv,
-                                crate::error::ValueError::Syntax("Unknown format".to_string()),
-                            )
-                        }
-                    }
-                })?;
-            citations.push(citation);
-        }
+    /// Parses CSV formatted content, appending the citations to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` as for [`CsvParser::parse`]; `out` is left unchanged
+    fn parse_into(&self, input: &str, out: &mut Vec<Citation>) -> Result<usize, ParseError> {
+        self.parse_records(input, out, None)
+    }
 
-        Ok(citations)
+    /// Parses CSV content, skipping records that fail to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed or the failures exceed `options`
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> Result<LenientParse, ParseError> {
+        let mut citations = Vec::new();
+        let mut errors = Vec::new();
+        self.parse_records(input, &mut citations, Some(&mut errors))?;
+        options.check(citations, errors, Vec::new())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::NameOrder;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -263,6 +559,27 @@ Test Paper,\"Smith, John; Doe, Jane\",2023";
     assert_eq!(citations[0].authors[1].name, "Doe");
     }
 
+    #[test]
+    fn test_given_first_author_names() {
+        let input = "\
+Title,Authors,Year
+Test Paper,\"John Smith Jr.; J. Doe; Doe, Jane\",2023";
+
+        let mut config = CsvConfig::new();
+        config.set_name_order(NameOrder::Auto);
+        let citations = CsvParser::with_config(config).parse(input).unwrap();
+
+        let authors = &citations[0].authors;
+        assert_eq!(authors.len(), 3);
+        assert_eq!(authors[0].name, "Smith");
+        assert_eq!(authors[0].given_name.as_deref(), Some("John"));
+        assert_eq!(authors[0].suffix.as_deref(), Some("Jr."));
+        assert_eq!(authors[1].name, "Doe");
+        assert_eq!(authors[1].given_name.as_deref(), Some("J."));
+        assert_eq!(authors[2].name, "Doe");
+        assert_eq!(authors[2].given_name.as_deref(), Some("Jane"));
+    }
+
     #[test]
     fn test_custom_delimiter() {
         let input = "Title;Author;Year\nTest Paper;Smith J;2023";
@@ -436,4 +753,96 @@ Another Paper,Doe J,2024";
         parser.set_auto_detection(true);
         assert!(parser.auto_detect);
     }
+
+    #[test]
+    fn test_parse_into_appends() {
+        let input = "Title,Author,Year\nFirst,Smith J,2023\nSecond,Doe J,2022";
+        let parser = CsvParser::new();
+        let mut citations = parser.parse("Title,Year\nExisting,2020").unwrap();
+
+        let count = parser.parse_into(input, &mut citations).unwrap();
+        assert_eq!(count, parser.parse(input).unwrap().len());
+        assert_eq!(count, 2);
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].title, "Existing");
+        assert_eq!(citations[1].title, "First");
+
+        // The second row has no title, so the whole input is rejected
+        let invalid = "Title,Year\nValid,2020\n,2021";
+        assert!(parser.parse_into(invalid, &mut citations).is_err());
+        assert_eq!(citations.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let input = "Title,Year\nValid,2020\n,2021\n,2022\nAlso valid,2023";
+        let parser = CsvParser::new();
+
+        let result = parser
+            .parse_lenient(input, crate::LenientOptions::default())
+            .unwrap();
+        assert_eq!(result.citations.len(), 2);
+        assert_eq!(result.errors[0].line, Some(3));
+        assert_eq!(result.stats.failed, 2);
+
+        let options = crate::LenientOptions {
+            max_errors: Some(1),
+            ..Default::default()
+        };
+        assert!(parser.parse_lenient(input, options).is_err());
+    }
+
+    #[test]
+    fn test_german_headers() {
+        let input = "Titel,Autoren,Jahr,Zeitschrift\n\
+                     Frühmobilisation nach Herzoperationen,\"Müller, K; Schmidt, A\",2021,Pflege\n";
+        let mut config = CsvConfig::new();
+
+        let err = CsvParser::with_config(config.clone()).parse(input).unwrap_err();
+        assert_eq!(err.line, Some(1));
+        let message = err.to_string();
+        assert!(message.contains("no column maps to the title"));
+        assert!(message.contains("\"Titel\" (did you mean \"title\"?)"));
+        assert!(message.contains("\"Zeitschrift\""));
+        // Lenient parsing stops up front too, rather than failing every row
+        assert!(
+            CsvParser::with_config(config.clone())
+                .parse_lenient(input, crate::LenientOptions::default())
+                .is_err()
+        );
+
+        config.add_header_language(HeaderLanguage::German);
+        let citations = CsvParser::with_config(config).parse(input).unwrap();
+        assert_eq!(citations[0].title, "Frühmobilisation nach Herzoperationen");
+        assert_eq!(citations[0].authors.len(), 2);
+        assert_eq!(citations[0].date.as_ref().map(|d| d.year), Some(2021));
+        assert_eq!(citations[0].journal.as_deref(), Some("Pflege"));
+        assert!(citations[0].extra_fields.is_empty());
+    }
+
+    #[test]
+    fn test_max_abstract_length() {
+        let input = "Title,Abstract\nLong,Éléments prognostiques\nShort,Él";
+
+        let mut parser = CsvParser::new();
+        parser.set_max_abstract_length(Some(2));
+        let citations = parser.parse(input).unwrap();
+        assert_eq!(citations[0].abstract_text.as_deref(), Some("Él…"));
+        assert_eq!(citations[0].extra_fields[crate::ABSTRACT_TRUNCATED_FIELD], vec!["true"]);
+        assert_eq!(citations[1].abstract_text.as_deref(), Some("Él"));
+        assert!(citations[1].extra_fields.is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_authors() {
+        let input = "Title,Authors\nEditorial,\nComment,Anonymous\nLetter,Smith J; N/A";
+        let citations = CsvParser::new().parse(input).unwrap();
+
+        assert!(citations[0].authors.is_empty());
+        assert!(citations[0].extra_fields.is_empty());
+        assert!(citations[1].authors.is_empty());
+        assert_eq!(citations[1].extra_fields[crate::ANONYMOUS_FIELD], vec!["true"]);
+        assert_eq!(citations[2].authors.len(), 1);
+        assert!(citations[2].extra_fields.is_empty());
+    }
 }
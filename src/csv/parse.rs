@@ -71,6 +71,10 @@ pub fn csv_parse<S: AsRef<str>>(
         ));
     }
 
+    config.check_headers(&headers).map_err(|msg| {
+        ParseError::at_line(1, CitationFormat::Csv, ValueError::Syntax(msg))
+    })?;
+
     let mut raw_citations = Vec::new();
     let mut line_number = if config.has_header { 2 } else { 1 }; // Start counting from data lines
 
@@ -21,9 +21,14 @@ pub(crate) struct RawCsvData {
     pub(crate) urls: Vec<String>,
     /// ISSN values parsed from ISSN fields
     pub(crate) issn: Vec<String>,
+    /// Whether the authors field held a placeholder such as "Anonymous"
+    pub(crate) anonymous: bool,
     /// Line number for error reporting
     pub(crate) line_number: usize,
+    /// Whether values are kept as written, see [`crate::CsvParser::set_archival`]
+    pub(crate) archival: bool,
     /// Original record for debugging (optional for memory efficiency)
+    #[allow(dead_code)]
     pub(crate) original_record: Option<Vec<String>>,
 }
 
@@ -40,6 +45,7 @@ impl RawCsvData {
         let mut keywords = Vec::new();
         let mut urls = Vec::new();
         let mut issn = Vec::new();
+        let mut anonymous = false;
 
         // Store original record for debugging if enabled
         let original_record = if config.store_original_record {
@@ -76,8 +82,11 @@ impl RawCsvData {
                     "authors" => {
                         for author_str in value.split(';') {
                             let author_str = author_str.trim();
-                            if !author_str.is_empty() {
-                                let (family, given) = crate::utils::parse_author_name(author_str);
+                            if crate::utils::is_placeholder_author(author_str) {
+                                anonymous |= !author_str.is_empty();
+                            } else {
+                                let (family, given, suffix) =
+                                    crate::utils::parse_author_name(author_str, config.name_order);
                                 let (given_opt, middle_opt) = if given.is_empty() {
                                     (None, None)
                                 } else {
@@ -87,6 +96,7 @@ impl RawCsvData {
                                     name: family,
                                     given_name: given_opt,
                                     middle_name: middle_opt,
+                                    suffix,
                                     affiliations: Vec::new(),
                                 });
                             }
@@ -107,6 +117,13 @@ impl RawCsvData {
                     "issn" => {
                         issn.extend(crate::utils::split_issns(value));
                     }
+                    _ if fields.contains_key(field) => {
+                        // Keep later columns mapped to the same field as extra fields
+                        fields.insert(
+                            format!("{}{}", crate::EXTRA_FIELD_PREFIX, header),
+                            value.to_string(),
+                        );
+                    }
                     _ => {
                         fields.insert(field.to_string(), value.to_string());
                     }
@@ -123,7 +140,9 @@ impl RawCsvData {
             keywords,
             urls,
             issn,
+            anonymous,
             line_number,
+            archival: false,
             original_record,
         })
     }
@@ -134,7 +153,8 @@ impl RawCsvData {
         config: &CsvConfig,
     ) -> Result<crate::Citation, crate::error::CitationError> {
         let title = self.get_field("title").cloned().ok_or_else(|| {
-            ParseError::without_position(
+            ParseError::at_line(
+                self.line_number,
                 CitationFormat::Csv,
                 ValueError::MissingValue {
                     field: fields::TITLE,
@@ -154,13 +174,19 @@ impl RawCsvData {
         let volume = self.get_field("volume").cloned();
         let issue = self.get_field("issue").cloned();
 
-        let pages = self
-            .get_field("pages")
-            .map(|p| crate::utils::format_page_numbers(p));
+        let pages = if self.archival {
+            self.get_field("pages").cloned()
+        } else {
+            self.get_field("pages")
+                .map(|p| crate::utils::format_page_numbers(p))
+        };
 
-        let doi = self
-            .get_field("doi")
-            .and_then(|doi_str| crate::utils::format_doi(doi_str));
+        let doi = if self.archival {
+            self.get_field("doi").cloned()
+        } else {
+            self.get_field("doi")
+                .and_then(|doi_str| crate::utils::format_doi(doi_str))
+        };
 
         let abstract_text = self.get_field("abstract").cloned();
         let language = self.get_field("language").cloned();
@@ -173,15 +199,27 @@ impl RawCsvData {
             .unwrap_or_else(|| vec!["Journal Article".to_string()]);
 
         // Properly extract extra fields using the config
-        let extra_fields = self.get_extra_fields(config);
+        let mut extra_fields = self.get_extra_fields(config);
+        if self.archival
+            && date.is_some()
+            && let Some(year) = self.get_field("year")
+        {
+            let key = format!("{}year", crate::RAW_FIELD_PREFIX);
+            extra_fields.insert(key, vec![year.clone()]);
+        }
 
-        Ok(crate::Citation {
+        let mut citation = crate::Citation {
             citation_type,
             title,
             authors: self.authors.clone(),
+            author_count: None,
+            contributors: Vec::new(),
             journal,
             journal_abbr,
             date: date.clone(),
+            date_end: None,
+            electronic_date: None,
+            record_dates: HashMap::new(),
             volume,
             issue,
             pages,
@@ -192,11 +230,24 @@ impl RawCsvData {
             abstract_text,
             keywords: self.keywords.clone(),
             urls: self.urls.clone(),
+            link_kinds: HashMap::new(),
             language,
             mesh_terms: Vec::new(), // CSV typically doesn't have MeSH terms
             publisher,
+            notes: None,
+            conference: None,
+            times_cited: self.get_field("times_cited").and_then(|n| crate::utils::parse_count(n)),
+            retraction_status: None,
+            ingested_at: None,
+            parsed_from: None,
             extra_fields,
-        })
+        };
+        crate::utils::mark_anonymous(&mut citation, self.anonymous);
+        // Merged exports name the database each row came from
+        if let Some(source) = self.get_field("source") {
+            citation.set_source(source);
+        }
+        Ok(citation)
     }
 
     /// Get a field value by name.
@@ -245,11 +296,14 @@ fn is_standard_field(field_name: &str, config: &CsvConfig) -> bool {
         "publisher",
         "type",
         "url",
+        "source",
+        "times_cited",
     ];
 
-    STANDARD_FIELDS
-        .iter()
-        .any(|&standard| config.get_field_for_header(field_name) == Some(standard))
+    // Unmapped columns named after a standard field populate that field too
+    STANDARD_FIELDS.iter().any(|&standard| {
+        field_name == standard || config.get_field_for_header(field_name) == Some(standard)
+    })
 }
 
 impl TryFrom<RawCsvData> for crate::Citation {
@@ -354,6 +408,39 @@ mod tests {
         assert_eq!(citation.date.as_ref().unwrap().year, 2023);
     }
 
+    #[test]
+    fn test_repeated_standard_columns_are_not_overwritten() {
+        let headers = vec![
+            "Title".to_string(),
+            "DOI".to_string(),
+            "Digital Object Identifier".to_string(),
+            "pmid".to_string(),
+            "doi2".to_string(),
+        ];
+        let record = create_test_record(&[
+            "Test Article",
+            "10.1000/first",
+            "10.1000/second",
+            "12345678",
+            "10.1000/third",
+        ]);
+        let config = CsvConfig::new();
+
+        let raw = RawCsvData::from_record(&headers, &record, &config, 1).unwrap();
+        let citation = raw.into_citation_with_config(&config).unwrap();
+
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/first"));
+        assert_eq!(
+            citation.extra_fields["x-Digital Object Identifier"],
+            vec!["10.1000/second"]
+        );
+        assert_eq!(citation.extra_fields["doi2"], vec!["10.1000/third"]);
+        // An unmapped column named after a standard field fills it and is not duplicated
+        assert_eq!(citation.pmid.as_deref(), Some("12345678"));
+        assert!(!citation.extra_fields.contains_key("pmid"));
+        assert_eq!(citation.extra_fields.len(), 2);
+    }
+
     #[test]
     fn test_missing_title_error() {
         let headers = vec!["Author".to_string()];
@@ -0,0 +1,299 @@
+//! CSV format writer implementation.
+//!
+//! Writes one row per citation under a fixed header row whose column names are
+//! recognized by the default [`CsvConfig`] mappings, so written files parse back
+//! with [`crate::csv::CsvParser::new`].
+
+use crate::csv::config::CsvConfig;
+use crate::writer::sanitize::csv_list_item;
+use crate::{Citation, CitationError, CitationWriter};
+use csv::WriterBuilder;
+use std::io::Write;
+
+/// Column headers written by [`CsvWriter`], in output order.
+const HEADERS: [&str; 14] = [
+    "Title",
+    "Authors",
+    "Journal",
+    "Year",
+    "Volume",
+    "Issue",
+    "Pages",
+    "DOI",
+    "Abstract",
+    "Keywords",
+    "ISSN",
+    "Language",
+    "Publisher",
+    "URL",
+];
+
+/// Writer for CSV-formatted citation data.
+///
+/// Authors and keywords are joined with `"; "`, matching what the parser splits on.
+/// Only the first URL is written. The delimiter and quote character are taken from
+/// the configuration.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Citation, CitationWriter, csv::CsvWriter};
+///
+/// let citation = Citation {
+///     title: "Example Paper".to_string(),
+///     ..Default::default()
+/// };
+///
+/// let output = CsvWriter::new().write_to_string([&citation]).unwrap();
+/// assert!(output.lines().nth(1).unwrap().starts_with("Example Paper,"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CsvWriter {
+    config: CsvConfig,
+}
+
+impl CsvWriter {
+    /// Creates a new CSV writer with default configuration
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new CSV writer with custom configuration
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::csv::{CsvConfig, CsvWriter};
+    ///
+    /// let mut config = CsvConfig::new();
+    /// config.set_delimiter(b';');
+    /// let writer = CsvWriter::with_config(config);
+    /// ```
+    #[must_use]
+    pub fn with_config(config: CsvConfig) -> Self {
+        Self { config }
+    }
+
+    /// Gets a reference to the current configuration
+    pub fn config(&self) -> &CsvConfig {
+        &self.config
+    }
+}
+
+impl CitationWriter for CsvWriter {
+    /// Writes a header row followed by one row per citation.
+    ///
+    /// The output is flushed after every record, so rows reach the underlying
+    /// writer as they are produced.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CitationError::Io` if the underlying writer fails
+    fn write_to<'a, W: Write>(
+        &self,
+        citations: impl IntoIterator<Item = &'a Citation>,
+        writer: W,
+    ) -> Result<(), CitationError> {
+        let mut writer = WriterBuilder::new()
+            .delimiter(self.config.delimiter)
+            .quote(self.config.quote)
+            .has_headers(false)
+            .from_writer(writer);
+
+        if self.config.has_header {
+            writer.write_record(HEADERS).map_err(io_error)?;
+        }
+
+        for citation in citations {
+            writer.write_record(row(citation)).map_err(io_error)?;
+            writer.flush()?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Builds the row for a citation, one cell per entry in [`HEADERS`].
+fn row(citation: &Citation) -> [String; 14] {
+    let authors = citation
+        .authors
+        .iter()
+        .map(|author| csv_list_item(&crate::utils::format_author_name(author)))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let keywords = citation
+        .keywords
+        .iter()
+        .map(|keyword| csv_list_item(keyword))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+    [
+        citation.title.clone(),
+        authors,
+        optional(&citation.journal),
+        citation
+            .date
+            .as_ref()
+            .map_or(String::new(), |date| date.year.to_string()),
+        optional(&citation.volume),
+        optional(&citation.issue),
+        optional(&citation.pages),
+        optional(&citation.doi),
+        optional(&citation.abstract_text),
+        keywords,
+        citation.issn.join(", "),
+        optional(&citation.language),
+        optional(&citation.publisher),
+        citation.urls.first().cloned().unwrap_or_default(),
+    ]
+}
+
+/// Converts a csv error into an I/O error, unwrapping errors from the underlying writer.
+fn io_error(err: csv::Error) -> CitationError {
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => CitationError::Io(err),
+        kind => CitationError::Io(std::io::Error::other(format!("{:?}", kind))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::csv::CsvParser;
+    use crate::utils::CountingWriter;
+    use crate::{Author, CitationParser, Date};
+    use pretty_assertions::assert_eq;
+
+    fn synthetic_citation(i: usize) -> Citation {
+        Citation {
+            title: format!("Synthetic citation, number {}", i),
+            authors: vec![
+                Author {
+                    name: "Smith".to_string(),
+                    given_name: Some("John".to_string()),
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                },
+                Author {
+                    name: "Doe".to_string(),
+                    given_name: Some("Jane".to_string()),
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                },
+            ],
+            journal: Some("Journal of Testing".to_string()),
+            date: Some(Date {
+                year: 2000 + (i % 25) as i32,
+                month: None,
+                day: None,
+            }),
+            abstract_text: Some("Contains \"quotes\" and, commas".to_string()),
+            keywords: vec!["alpha".to_string(), "beta".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let citation = synthetic_citation(1);
+        let output = CsvWriter::new().write_to_string([&citation]).unwrap();
+
+        let parsed = CsvParser::new().parse(&output).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].title, citation.title);
+        assert_eq!(parsed[0].authors, citation.authors);
+        assert_eq!(parsed[0].journal, citation.journal);
+        assert_eq!(parsed[0].date, citation.date);
+        assert_eq!(parsed[0].abstract_text, citation.abstract_text);
+        assert_eq!(parsed[0].keywords, citation.keywords);
+        assert!(parsed[0].extra_fields.is_empty());
+    }
+
+    #[test]
+    fn test_write_honors_delimiter() {
+        let mut config = CsvConfig::new();
+        config.set_delimiter(b';');
+        let output = CsvWriter::with_config(config.clone())
+            .write_to_string([&synthetic_citation(1)])
+            .unwrap();
+
+        assert!(output.starts_with("Title;Authors;Journal;"));
+        let parsed = CsvParser::with_config(config).parse(&output).unwrap();
+        assert_eq!(parsed[0].title, "Synthetic citation, number 1");
+    }
+
+    #[test]
+    fn test_write_values_containing_csv_syntax() {
+        let dangerous = |field: &str| format!("{} \"a\";b,c\n'd'\r\ne|f", field);
+        let citation = Citation {
+            title: dangerous("title"),
+            journal: Some(dangerous("journal")),
+            volume: Some(dangerous("volume")),
+            issue: Some(dangerous("issue")),
+            pages: Some(dangerous("pages")),
+            doi: Some(dangerous("doi")),
+            abstract_text: Some(dangerous("abstract")),
+            keywords: vec!["one; two".to_string(), dangerous("keyword").replace(';', ",")],
+            language: Some(dangerous("language")),
+            publisher: Some(dangerous("publisher")),
+            urls: vec![dangerous("url")],
+            ..Default::default()
+        };
+
+        for (delimiter, quote) in [(b',', b'"'), (b';', b'"'), (b'|', b'\'')] {
+            let mut config = CsvConfig::new();
+            config.set_delimiter(delimiter).set_quote(quote);
+            let output = CsvWriter::with_config(config.clone())
+                .write_to_string([&citation])
+                .unwrap();
+            assert!(output.starts_with(&format!("Title{}", delimiter as char)));
+
+            // Keep the line breaks of the abstract to check that they are written
+            let mut parser = CsvParser::with_config(config);
+            parser.set_clean_abstracts(false);
+            let parsed = parser.parse(&output).unwrap();
+            assert_eq!(parsed.len(), 1);
+            let parsed = &parsed[0];
+            assert_eq!(parsed.title, citation.title);
+            assert_eq!(parsed.journal, citation.journal);
+            assert_eq!(parsed.volume, citation.volume);
+            assert_eq!(parsed.issue, citation.issue);
+            assert_eq!(parsed.pages, citation.pages);
+            assert_eq!(parsed.abstract_text, citation.abstract_text);
+            assert_eq!(parsed.keywords, vec!["one, two".to_string(), citation.keywords[1].clone()]);
+            assert_eq!(parsed.language, citation.language);
+            assert_eq!(parsed.publisher, citation.publisher);
+            assert_eq!(parsed.urls, citation.urls);
+        }
+    }
+
+    #[test]
+    fn test_write_streams_and_flushes_per_record() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::default();
+        CsvWriter::new().write_to(&citations, &mut counter).unwrap();
+
+        let expected = CsvWriter::new().write_to_string(&citations).unwrap();
+        assert_eq!(counter.bytes, expected.len());
+        assert!(counter.flushes >= 10_000);
+        assert_eq!(CsvParser::new().parse(&expected).unwrap().len(), 10_000);
+    }
+
+    #[test]
+    fn test_write_propagates_io_errors() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::failing_after(4096);
+        let result = CsvWriter::new().write_to(&citations, &mut counter);
+
+        assert!(matches!(result, Err(CitationError::Io(_))));
+        assert!(counter.bytes <= 4096);
+    }
+}
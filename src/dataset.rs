@@ -0,0 +1,175 @@
+//! Combining citations parsed from several files.
+//!
+//! [`merge_datasets`] concatenates labelled datasets, e.g. one per database
+//! export, in a way that keeps every citation traceable: each is stamped with
+//! the label of its dataset as its [`Citation::source`], and ids that repeat
+//! across or within datasets are replaced so that every citation has its own
+//! [`Citation::id`]. The result can be passed to the deduplicator as it is, and
+//! its [`MergedDataset::range`] reports which citations came from which file.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::{Citation, merge_datasets};
+//!
+//! let citation = |id: &str| {
+//!     let mut citation = Citation::new();
+//!     citation.set_id(id);
+//!     citation
+//! };
+//! let merged = merge_datasets(vec![
+//!     ("scopus.csv".to_string(), vec![citation("1"), citation("2")]),
+//!     ("wos.csv".to_string(), vec![citation("1")]),
+//! ]);
+//!
+//! let ids: Vec<_> = merged.citations.iter().map(|c| c.id().unwrap()).collect();
+//! assert_eq!(ids, vec!["1", "2", "wos.csv:1"]);
+//! assert_eq!(merged.citations[2].source(), Some("wos.csv"));
+//! assert_eq!(merged.range("wos.csv"), Some(2..3));
+//! ```
+
+use crate::Citation;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// [`Citation::extra_fields`] key holding the id a citation had before
+/// [`merge_datasets`] replaced it.
+pub const ORIGINAL_ID_FIELD: &str = "original_id";
+
+/// Citations of several datasets combined by [`merge_datasets`].
+#[derive(Debug, Clone, Default)]
+pub struct MergedDataset {
+    /// The citations of all datasets, in the order they were given
+    pub citations: Vec<Citation>,
+    /// The label of each dataset and the positions of its citations in
+    /// `citations`, in the order the datasets were given
+    pub ranges: Vec<(String, Range<usize>)>,
+}
+
+impl MergedDataset {
+    /// Returns the positions in `citations` of the dataset labelled `label`.
+    ///
+    /// When several datasets share a label, the first one is returned.
+    #[must_use]
+    pub fn range(&self, label: &str) -> Option<Range<usize>> {
+        self.ranges
+            .iter()
+            .find(|(own, _)| own == label)
+            .map(|(_, range)| range.clone())
+    }
+
+    /// Returns the ids of the citations of the dataset labelled `label`.
+    pub fn ids(&self, label: &str) -> impl Iterator<Item = &str> {
+        self.range(label)
+            .map_or(&[][..], |range| &self.citations[range])
+            .iter()
+            .filter_map(Citation::id)
+    }
+
+    /// Returns the label of the dataset the citation at `index` came from.
+    #[must_use]
+    pub fn label_of(&self, index: usize) -> Option<&str> {
+        self.ranges
+            .iter()
+            .find(|(_, range)| range.contains(&index))
+            .map(|(label, _)| label.as_str())
+    }
+}
+
+/// Concatenates labelled datasets, giving every citation a distinct id and
+/// recording its dataset's label as its source.
+///
+/// Each citation's [`Citation::source`] is set to its label, replacing any
+/// previous source. The first citation with a given id keeps it; later ones
+/// get the id prefixed with their label, e.g. "wos.csv:1", with "-2", "-3" and
+/// so on appended if that is taken too, and keep their original id under
+/// [`ORIGINAL_ID_FIELD`]. Citations without an id get one from their label and
+/// 1-based position in their dataset, e.g. "wos.csv:#3".
+///
+/// Replacement ids never repeat an id of another citation, whether original or
+/// replaced.
+#[must_use]
+pub fn merge_datasets(datasets: Vec<(String, Vec<Citation>)>) -> MergedDataset {
+    let original_ids: HashSet<String> = datasets
+        .iter()
+        .flat_map(|(_, citations)| citations.iter().filter_map(Citation::id))
+        .map(str::to_string)
+        .collect();
+    let mut used: HashSet<String> = HashSet::with_capacity(original_ids.len());
+    let mut merged = MergedDataset::default();
+
+    for (label, citations) in datasets {
+        let start = merged.citations.len();
+        for (position, mut citation) in citations.into_iter().enumerate() {
+            let id = match citation.id().map(str::to_string) {
+                Some(id) if !used.contains(&id) => id,
+                Some(id) => {
+                    let replacement = unused_id(&format!("{}:{}", label, id), &used, &original_ids);
+                    citation
+                        .extra_fields
+                        .insert(ORIGINAL_ID_FIELD.to_string(), vec![id]);
+                    replacement
+                }
+                None => unused_id(&format!("{}:#{}", label, position + 1), &used, &original_ids),
+            };
+            citation.set_id(&id).set_source(&label);
+            used.insert(id);
+            merged.citations.push(citation);
+        }
+        merged.ranges.push((label, start..merged.citations.len()));
+    }
+
+    merged
+}
+
+/// Returns `base`, or `base` with the first "-N" suffix that is neither used
+/// nor an original id.
+fn unused_id(base: &str, used: &HashSet<String>, original_ids: &HashSet<String>) -> String {
+    let taken = |id: &String| used.contains(id) || original_ids.contains(id);
+    let mut id = base.to_string();
+    let mut n = 1;
+    while taken(&id) {
+        n += 1;
+        id = format!("{}-{}", base, n);
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn citation(id: Option<&str>) -> Citation {
+        let mut citation = Citation::new();
+        if let Some(id) = id {
+            citation.set_id(id);
+        }
+        citation
+    }
+
+    #[test]
+    fn test_merge_datasets() {
+        let merged = merge_datasets(vec![
+            ("a".to_string(), vec![citation(Some("1")), citation(Some("1")), citation(None)]),
+            ("b".to_string(), vec![]),
+            // "a:1" is an original id, so the second "1" of "a" skips it
+            ("c".to_string(), vec![citation(Some("a:1")), citation(Some("1"))]),
+        ]);
+
+        let ids: Vec<_> = merged.citations.iter().filter_map(Citation::id).collect();
+        assert_eq!(ids, vec!["1", "a:1-2", "a:#3", "a:1", "c:1"]);
+        assert_eq!(merged.citations[1].extra_fields[ORIGINAL_ID_FIELD], vec!["1"]);
+        assert!(!merged.citations[0].extra_fields.contains_key(ORIGINAL_ID_FIELD));
+        assert!(!merged.citations[2].extra_fields.contains_key(ORIGINAL_ID_FIELD));
+
+        assert_eq!(merged.range("a"), Some(0..3));
+        assert_eq!(merged.range("b"), Some(3..3));
+        assert_eq!(merged.range("c"), Some(3..5));
+        assert_eq!(merged.range("d"), None);
+        assert_eq!(merged.ids("c").collect::<Vec<_>>(), vec!["a:1", "c:1"]);
+        assert_eq!(merged.label_of(4), Some("c"));
+        assert_eq!(merged.label_of(5), None);
+        assert_eq!(merged.citations[3].source(), Some("c"));
+    }
+}
@@ -8,12 +8,20 @@
 //!
 //! - Flexible deduplication based on multiple citation fields
 //! - Smart matching of journal names and abbreviations
-//! - Support for DOI and non-DOI based citations
+//! - Support for DOI and non-DOI based citations; DOIs are compared
+//!   case-insensitively, as the DOI system defines them, ignoring URL prefixes, a
+//!   trailing slash and trailing `.`, `,` or `;`
 //! - Optional year-based grouping for improved performance
 //! - Parallel processing support
-//! - Unicode character handling
+//! - Unicode character handling (see [`crate::text`] for the title normalization rules)
 //! - Configurable matching thresholds
 //! - Source-aware deduplication with preferences
+//! - Exclusion of pairs marked as not duplicates, by citation id
+//! - Custom per-pair matching rules, see [`DeduplicatorConfig::custom_filter`]
+//! - Retraction notices kept apart from the works they retract, see
+//!   [`Citation::retraction_status`]
+//! - Results as maps between citation ids and canonical ids, see
+//!   [`to_canonical_map`] and [`canonical_to_members`]
 //!
 //! ## Usage
 //!
@@ -31,6 +39,7 @@
 //!                 name: "Smith".to_string(),
 //!                 given_name: Some("John".to_string()),
 //!                 middle_name: None,
+//!                 suffix: None,
 //!                 affiliations: vec![],
 //!             }
 //!         ],
@@ -46,6 +55,7 @@
 //!                 name: "Smith".to_string(),
 //!                 given_name: Some("John".to_string()),
 //!                 middle_name: None,
+//!                 suffix: None,
 //!                 affiliations: vec![],
 //!             }
 //!         ],
@@ -91,10 +101,10 @@
 //! // Sources corresponding to each citation
 //! let sources = vec!["Embase", "PubMed"];
 //!
-//! let config = biblib::dedupe::DeduplicatorConfig {
-//!     source_preferences: vec!["PubMed".to_string(), "Embase".to_string()],
-//!     ..Default::default()
-//! };
+//! let config = biblib::dedupe::DeduplicatorConfig::builder()
+//!     .source_preferences(["PubMed", "Embase"])
+//!     .build()
+//!     .unwrap();
 //!
 //! let deduplicator = Deduplicator::new().with_config(config);
 //! let duplicate_groups = deduplicator.find_duplicates_with_sources(&citations, &sources).unwrap();
@@ -109,11 +119,15 @@
 //! ```rust
 //! use biblib::dedupe::{Deduplicator, DeduplicatorConfig};
 //!
-//! let config = DeduplicatorConfig {
-//!     group_by_year: false,     // Disable year-based grouping
-//!     run_in_parallel: true,    // Enable parallel processing
-//!     source_preferences: vec!["PubMed".to_string(), "CrossRef".to_string()],
-//! };
+//! use biblib::dedupe::GroupingMode;
+//!
+//! let config = DeduplicatorConfig::builder()
+//!     .group_by_year(false)     // Disable year-based grouping
+//!     .run_in_parallel(true)    // Enable parallel processing
+//!     .source_preferences(["PubMed", "CrossRef"])
+//!     .grouping_mode(GroupingMode::TransitiveClosure)
+//!     .build()
+//!     .unwrap();
 //!
 //! let deduplicator = Deduplicator::new().with_config(config);
 //! ```
@@ -124,41 +138,54 @@
 //!
 //! 1. With DOIs:
 //!    - Matching DOIs and high title similarity (≥ 0.85)
-//!    - Matching journal names or ISSNs
+//!    - Matching journal names or ISSNs, or matching authors
 //!
 //! 2. Without DOIs:
 //!    - Very high title similarity (≥ 0.93)
 //!    - Matching volume or page numbers
 //!    - Matching journal names or ISSNs
+//!
+//! Unless their DOIs, PMIDs or PMC IDs match, citations that both list authors
+//! must also agree on them, see [`DeduplicatorConfig::require_author_match`].
+//!
+//! How matching pairs are combined into groups is controlled by [`GroupingMode`].
+//!
+//! Fields a source fills with placeholders can be left out of the comparison
+//! with [`DeduplicatorConfig::ignore_fields`], and placeholder DOIs such as
+//! "10.0000/unknown" are treated as missing through
+//! [`DeduplicatorConfig::doi_denylist`].
 
-use crate::regex::Regex;
-use crate::{Citation, DuplicateGroup};
-use std::collections::HashMap;
-use std::sync::LazyLock;
+use crate::text::{
+    Script, dominant_script, fold_typography, normalize_journal, normalize_title,
+    script_aware_similarity,
+};
+use crate::{Citation, Diagnostic, Diagnostics, DuplicateGroup, IssnCatalog, MatchInfo, MatchReason};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
 use strsim::jaro;
 use strsim::jaro_winkler;
 
 const DOI_TITLE_SIMILARITY_THRESHOLD: f64 = 0.85;
 const NO_DOI_TITLE_SIMILARITY_THRESHOLD: f64 = 0.93;
+/// Distance from a title similarity threshold within which a decision is
+/// borderline, see [`DeduplicatorConfig::use_abstract_similarity`].
+const BORDERLINE_TITLE_MARGIN: f64 = 0.02;
+/// Number of normalized characters of an abstract compared for
+/// [`DeduplicatorConfig::use_abstract_similarity`].
+const ABSTRACT_COMPARED_CHARS: usize = 1000;
+
+/// Placeholder DOIs treated as missing by default, see
+/// [`DeduplicatorConfig::doi_denylist`].
+pub const DEFAULT_DOI_DENYLIST: &[&str] = &["10.0000/", "10.9999/", "n/a", "na", "none", "unknown", "-"];
+
+/// [`Citation::extra_fields`] key set on the unique citation of a group that
+/// was capped by [`DeduplicatorConfig::max_group_size`], holding the number of
+/// matching citations left out of the group, e.g. `["40"]`.
+pub const SPILLED_DUPLICATES_FIELD: &str = "duplicates_spilled";
 
-static UNICODE_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"<U\+([0-9A-Fa-f]+)>").unwrap());
-
-const HTML_REPLACEMENTS: [(&str, &str); 13] = [
-    ("&lt;", "<"),
-    ("&gt;", ">"),
-    ("<sup>", ""),
-    ("</sup>", ""),
-    ("<sub>", ""),
-    ("</sub>", ""),
-    ("<inf>", ""),
-    ("</inf>", ""),
-    ("beta", "b"),
-    ("alpha", "a"),
-    ("α", "a"),
-    ("ß", "b"),
-    ("γ", "g"),
-];
 
 /// Configuration options for controlling the deduplication process.
 ///
@@ -171,13 +198,19 @@ const HTML_REPLACEMENTS: [(&str, &str); 13] = [
 /// ```
 /// use biblib::dedupe::DeduplicatorConfig;
 ///
-/// let config = DeduplicatorConfig {
-///     group_by_year: true,    // Enable year-based grouping
-///     run_in_parallel: true,  // Enable parallel processing
-///     source_preferences: vec!["PubMed".to_string(), "Google Scholar      ".to_string()],
-/// };
+/// let config = DeduplicatorConfig::builder()
+///     .group_by_year(true)    // Enable year-based grouping
+///     .run_in_parallel(true)  // Enable parallel processing
+///     .source_preferences(["PubMed", "Google Scholar"])
+///     .build()
+///     .unwrap();
 /// ```
 ///
+/// The struct is `#[non_exhaustive]`, so new options can be added without
+/// breaking code that builds it: outside this crate, configurations are created
+/// with [`DeduplicatorConfig::builder`] or [`Default`], and fields can still be
+/// read and assigned.
+///
 /// # Performance Impact
 ///
 /// - `group_by_year`: Significant performance improvement for large datasets
@@ -187,7 +220,28 @@ const HTML_REPLACEMENTS: [(&str, &str); 13] = [
 ///
 /// - When `group_by_year` is false, `run_in_parallel` is automatically disabled
 /// - Year grouping is recommended for datasets with > 1000 citations
-#[derive(Debug, Default, Clone)]
+///
+/// # Configuration Files
+///
+/// The configuration serializes with serde under its field names, enums as
+/// snake_case strings such as `"mutual_match"`. Missing fields take their
+/// [`Default`] values, the options are checked as by
+/// [`DeduplicatorConfigBuilder::build`], and `custom_filter` is neither written
+/// nor read.
+///
+/// ```
+/// use biblib::dedupe::{DedupeField, DeduplicatorConfig, GroupingMode};
+///
+/// let json = r#"{"grouping_mode": "mutual_match", "ignore_fields": ["pages"]}"#;
+/// let config: DeduplicatorConfig = serde_json::from_str(json).unwrap();
+/// assert_eq!(config.grouping_mode, GroupingMode::MutualMatch);
+/// assert!(config.ignore_fields.contains(&DedupeField::Pages));
+///
+/// assert!(serde_json::from_str::<DeduplicatorConfig>(r#"{"max_group_size": 0}"#).is_err());
+/// ```
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(remote = "Self", default, deny_unknown_fields)]
+#[non_exhaustive]
 pub struct DeduplicatorConfig {
     /// Whether to group citations by year before processing.
     /// This can significantly improve performance for large datasets.
@@ -197,7 +251,580 @@ pub struct DeduplicatorConfig {
     pub run_in_parallel: bool,
     /// Ordered list of preferred sources for unique citations.
     /// First source in the list has highest priority.
+    ///
+    /// The unique citation of a group is chosen by narrowing its members down
+    /// to those from the first listed source any member comes from, then to
+    /// those with an abstract, then to those with a DOI, skipping a step when
+    /// no member passes it. The earliest in input order of the members left
+    /// is chosen.
     pub source_preferences: Vec<String>,
+    /// How matching pairs of citations are combined into duplicate groups.
+    pub grouping_mode: GroupingMode,
+    /// Which publication year citations are grouped and compared by.
+    pub year_source: YearSource,
+    /// Fields treated as missing when comparing citations, for sources that fill
+    /// them with placeholders. An ignored field never counts as matching.
+    #[serde(serialize_with = "serialize_sorted")]
+    pub ignore_fields: HashSet<DedupeField>,
+    /// DOIs treated as missing, compared with the lowercase DOI: an entry ending
+    /// in "/" is a prefix, any other entry a whole DOI. Defaults to
+    /// [`DEFAULT_DOI_DENYLIST`].
+    pub doi_denylist: Vec<String>,
+    /// Largest number of citations in a group, counting the unique citation.
+    ///
+    /// This is a safety valve rather than a way to improve matching: a group
+    /// that large usually means over-matching, such as hundreds of near-identical
+    /// conference abstracts. Citations that would join a full group are reported
+    /// alone instead, and the unique citation of the group gets
+    /// [`SPILLED_DUPLICATES_FIELD`]. `None`, the default, sets no limit.
+    pub max_group_size: Option<usize>,
+    /// Whether abstracts settle the pairs titles cannot, such as two "Letter to
+    /// the editor" records in the same journal issue.
+    ///
+    /// When both citations have an abstract and either title is shorter than
+    /// `short_title_length` once normalized, or the title similarity is within
+    /// 0.02 of the threshold of the rule applied, the abstracts are compared:
+    /// the Jaccard similarity of the word 3-shingles of their first 1,000
+    /// normalized characters must reach `abstract_similarity_threshold` for
+    /// the pair to match, and lets a pair just below the title threshold
+    /// match. Disabled by default; abstracts are only prepared when enabled.
+    pub use_abstract_similarity: bool,
+    /// Normalized title length, in characters, below which a title is too short
+    /// to tell works apart, see `use_abstract_similarity`. Defaults to 30.
+    pub short_title_length: usize,
+    /// Abstract similarity from 0.0 to 1.0 that pairs settled by their abstracts
+    /// must reach, see `use_abstract_similarity`. Defaults to 0.5.
+    pub abstract_similarity_threshold: f64,
+    /// Whether pairs with identical normalized titles are matched without
+    /// computing their title similarity.
+    ///
+    /// Such a pair is a duplicate when the years match, or both are missing,
+    /// and no field contradicts it: both citations have a DOI and they differ,
+    /// or both have pages and the page ranges do not overlap. This also merges
+    /// sparse records that share nothing but a title and a year, which the
+    /// similarity rules leave apart, whatever the length of the title. Within
+    /// each block the citations sharing a title are grouped first, before any
+    /// title similarity is computed. Enabled by default.
+    pub exact_title_fast_path: bool,
+    /// Whether citations that both list authors must agree on them to match,
+    /// unless their DOIs, PMIDs or PMC IDs match. This keeps apart works that
+    /// share a title, journal and volume, such as two errata or two letters to
+    /// the editor.
+    ///
+    /// Authors agree when the family names of the first authors are equal, or
+    /// when at least `author_overlap_threshold` of the family names of the
+    /// shorter author list are in the other; names are compared without case,
+    /// punctuation or diacritics, and only the first `compared_authors` and
+    /// the last author of each list count. Citations with the same DOI and
+    /// similar titles also match on agreeing authors alone, whatever this
+    /// option. Enabled by default.
+    pub require_author_match: bool,
+    /// Share of the family names of the shorter author list, from 0.0 to 1.0,
+    /// that the other list must contain for the authors to agree when the
+    /// first authors differ, see `require_author_match`. Defaults to 0.5.
+    pub author_overlap_threshold: f64,
+    /// How many leading authors of each citation are compared, along with the
+    /// last author, see `require_author_match`. This bounds the comparison of
+    /// consortium papers listing thousands of authors. Defaults to 5.
+    pub compared_authors: usize,
+    /// A hook for matching rules of one's own, such as never merging records
+    /// of two given sources.
+    ///
+    /// It is called for every pair the built-in rules compare, with the two
+    /// citations and a [`MatchContext`] holding the built-in decision, and can
+    /// overturn that decision either way. The citations are those passed to the
+    /// deduplicator, so sources given to
+    /// [`Deduplicator::find_duplicates_with_sources`] are not visible to the hook;
+    /// store them with [`Citation::set_source`] instead. Pairs excluded with
+    /// [`Deduplicator::with_exclusions`] are never compared, so the hook cannot
+    /// group them. `None`, the default, adds no cost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::dedupe::{DeduplicatorConfig, MatchOverride};
+    ///
+    /// // Never merge trial register records with database records
+    /// let config = DeduplicatorConfig::builder()
+    ///     .custom_filter(|a, b, _context| {
+    ///         let register = |c: &biblib::Citation| c.source() == Some("CT.gov");
+    ///         if register(a) != register(b) {
+    ///             MatchOverride::Reject
+    ///         } else {
+    ///             MatchOverride::NoOpinion
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[serde(skip)]
+    pub custom_filter: Option<CustomMatchFilter>,
+}
+
+impl Serialize for DeduplicatorConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DeduplicatorConfig::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DeduplicatorConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut config = DeduplicatorConfig::deserialize(deserializer)?;
+        config.validate().map_err(serde::de::Error::custom)?;
+        Ok(config)
+    }
+}
+
+/// Serializes a set in order, so that written configurations do not change
+/// from run to run.
+fn serialize_sorted<S: serde::Serializer>(
+    fields: &HashSet<DedupeField>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(fields.iter().collect::<std::collections::BTreeSet<_>>())
+}
+
+/// A per-pair matching hook, see [`DeduplicatorConfig::custom_filter`].
+pub type CustomMatchFilter =
+    Arc<dyn Fn(&Citation, &Citation, &MatchContext) -> MatchOverride + Send + Sync>;
+
+/// What the built-in rules found when comparing two citations, passed to
+/// [`DeduplicatorConfig::custom_filter`].
+///
+/// Field comparisons are made on the normalized values the deduplicator uses,
+/// and a field missing from either citation or ignored through
+/// [`DeduplicatorConfig::ignore_fields`] never matches.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MatchContext {
+    /// Similarity of the titles from 0.0 to 1.0: Jaro when both citations have
+    /// a DOI, otherwise Jaro-Winkler, counting transliterated titles
+    pub title_similarity: f64,
+    /// Whether both citations have a DOI and they are equal
+    pub dois_match: bool,
+    /// Whether the citations have the same PMID or the same PMC ID
+    pub pubmed_ids_match: bool,
+    /// Whether the journal names or abbreviations are equal
+    pub journals_match: bool,
+    /// Whether the citations share an ISSN
+    pub issns_match: bool,
+    /// Whether the volume numbers are equal
+    pub volumes_match: bool,
+    /// Whether the pages are equal
+    pub pages_match: bool,
+    /// Whether the publication years are equal
+    pub years_match: bool,
+    /// Whether the authors agree, see [`DeduplicatorConfig::require_author_match`]
+    pub authors_match: bool,
+    /// Similarity of the abstracts from 0.0 to 1.0, when
+    /// [`DeduplicatorConfig::use_abstract_similarity`] is set and both citations
+    /// have an abstract
+    pub abstract_similarity: Option<f64>,
+    /// The decision of the built-in rules
+    pub is_duplicate: bool,
+}
+
+/// The verdict of a [`DeduplicatorConfig::custom_filter`] on a pair of citations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchOverride {
+    /// The citations are duplicates, whatever the built-in rules decided
+    Accept,
+    /// The citations are not duplicates, whatever the built-in rules decided
+    Reject,
+    /// Keep the decision of the built-in rules
+    NoOpinion,
+}
+
+impl fmt::Debug for DeduplicatorConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeduplicatorConfig")
+            .field("group_by_year", &self.group_by_year)
+            .field("run_in_parallel", &self.run_in_parallel)
+            .field("source_preferences", &self.source_preferences)
+            .field("grouping_mode", &self.grouping_mode)
+            .field("year_source", &self.year_source)
+            .field("ignore_fields", &self.ignore_fields)
+            .field("doi_denylist", &self.doi_denylist)
+            .field("max_group_size", &self.max_group_size)
+            .field("use_abstract_similarity", &self.use_abstract_similarity)
+            .field("short_title_length", &self.short_title_length)
+            .field(
+                "abstract_similarity_threshold",
+                &self.abstract_similarity_threshold,
+            )
+            .field("exact_title_fast_path", &self.exact_title_fast_path)
+            .field("require_author_match", &self.require_author_match)
+            .field("author_overlap_threshold", &self.author_overlap_threshold)
+            .field("compared_authors", &self.compared_authors)
+            .field(
+                "custom_filter",
+                &self.custom_filter.as_ref().map(|_| "Fn(..)"),
+            )
+            .finish()
+    }
+}
+
+impl Default for DeduplicatorConfig {
+    fn default() -> Self {
+        Self {
+            group_by_year: false,
+            run_in_parallel: false,
+            source_preferences: Vec::new(),
+            grouping_mode: GroupingMode::default(),
+            year_source: YearSource::default(),
+            ignore_fields: HashSet::new(),
+            doi_denylist: default_doi_denylist(),
+            max_group_size: None,
+            use_abstract_similarity: false,
+            short_title_length: 30,
+            abstract_similarity_threshold: 0.5,
+            exact_title_fast_path: true,
+            require_author_match: true,
+            author_overlap_threshold: 0.5,
+            compared_authors: 5,
+            custom_filter: None,
+        }
+    }
+}
+
+/// [`DEFAULT_DOI_DENYLIST`] as owned strings.
+fn default_doi_denylist() -> Vec<String> {
+    DEFAULT_DOI_DENYLIST.iter().map(|doi| doi.to_string()).collect()
+}
+
+impl DeduplicatorConfig {
+    /// Starts building a configuration from the default settings.
+    #[must_use]
+    pub fn builder() -> DeduplicatorConfigBuilder {
+        DeduplicatorConfigBuilder::default()
+    }
+
+    /// Checks the options as [`DeduplicatorConfigBuilder::build`] does, and
+    /// trims and lowercases the DOI denylist.
+    ///
+    /// Deserialized configurations are checked already; this is for
+    /// configurations whose fields were assigned directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DedupeError::ConfigError` if a source preference is blank or
+    /// listed more than once, a DOI denylist entry is blank, the maximum group
+    /// size or the number of compared authors is zero, or the abstract
+    /// similarity or author overlap threshold is not between 0.0 and 1.0.
+    pub fn validate(&mut self) -> Result<(), DedupeError> {
+        if self.max_group_size == Some(0) {
+            return Err(DedupeError::ConfigError(
+                "Maximum group size must be at least 1".to_string(),
+            ));
+        }
+        if self.compared_authors == 0 {
+            return Err(DedupeError::ConfigError(
+                "Number of compared authors must be at least 1".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.abstract_similarity_threshold) {
+            return Err(DedupeError::ConfigError(format!(
+                "Abstract similarity threshold {} is not between 0 and 1",
+                self.abstract_similarity_threshold
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.author_overlap_threshold) {
+            return Err(DedupeError::ConfigError(format!(
+                "Author overlap threshold {} is not between 0 and 1",
+                self.author_overlap_threshold
+            )));
+        }
+        for doi in &mut self.doi_denylist {
+            *doi = doi.trim().to_lowercase();
+            if doi.is_empty() {
+                return Err(DedupeError::ConfigError(
+                    "DOI denylist entry is blank".to_string(),
+                ));
+            }
+        }
+
+        let preferences = &self.source_preferences;
+        for (i, source) in preferences.iter().enumerate() {
+            if source.trim().is_empty() {
+                return Err(DedupeError::ConfigError(format!(
+                    "Source preference {} is blank",
+                    i + 1
+                )));
+            }
+            if preferences[..i].contains(source) {
+                return Err(DedupeError::ConfigError(format!(
+                    "Source preference '{}' is listed more than once",
+                    source
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builder for [`DeduplicatorConfig`], validating the options in
+/// [`DeduplicatorConfigBuilder::build`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::dedupe::{Deduplicator, DeduplicatorConfig, GroupingMode};
+///
+/// let config = DeduplicatorConfig::builder()
+///     .grouping_mode(GroupingMode::TransitiveClosure)
+///     .source_preferences(["PubMed", "Embase"])
+///     .build()
+///     .unwrap();
+/// let deduplicator = Deduplicator::new().with_config(config);
+///
+/// // Blank source names are rejected
+/// assert!(DeduplicatorConfig::builder().source_preferences(["PubMed", " "]).build().is_err());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct DeduplicatorConfigBuilder {
+    config: DeduplicatorConfig,
+}
+
+impl DeduplicatorConfigBuilder {
+    /// Sets [`DeduplicatorConfig::group_by_year`].
+    #[must_use]
+    pub fn group_by_year(mut self, enabled: bool) -> Self {
+        self.config.group_by_year = enabled;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::run_in_parallel`].
+    #[must_use]
+    pub fn run_in_parallel(mut self, enabled: bool) -> Self {
+        self.config.run_in_parallel = enabled;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::source_preferences`], highest priority first.
+    #[must_use]
+    pub fn source_preferences<I, S>(mut self, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.source_preferences = sources.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::grouping_mode`].
+    #[must_use]
+    pub fn grouping_mode(mut self, mode: GroupingMode) -> Self {
+        self.config.grouping_mode = mode;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::year_source`].
+    #[must_use]
+    pub fn year_source(mut self, source: YearSource) -> Self {
+        self.config.year_source = source;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::ignore_fields`].
+    #[must_use]
+    pub fn ignore_fields<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = DedupeField>,
+    {
+        self.config.ignore_fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::doi_denylist`], replacing the default entries.
+    #[must_use]
+    pub fn doi_denylist<I, S>(mut self, dois: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.doi_denylist = dois.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::max_group_size`].
+    #[must_use]
+    pub fn max_group_size(mut self, max: Option<usize>) -> Self {
+        self.config.max_group_size = max;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::use_abstract_similarity`].
+    #[must_use]
+    pub fn use_abstract_similarity(mut self, enabled: bool) -> Self {
+        self.config.use_abstract_similarity = enabled;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::short_title_length`].
+    #[must_use]
+    pub fn short_title_length(mut self, length: usize) -> Self {
+        self.config.short_title_length = length;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::abstract_similarity_threshold`].
+    #[must_use]
+    pub fn abstract_similarity_threshold(mut self, threshold: f64) -> Self {
+        self.config.abstract_similarity_threshold = threshold;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::exact_title_fast_path`].
+    #[must_use]
+    pub fn exact_title_fast_path(mut self, enabled: bool) -> Self {
+        self.config.exact_title_fast_path = enabled;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::require_author_match`].
+    #[must_use]
+    pub fn require_author_match(mut self, enabled: bool) -> Self {
+        self.config.require_author_match = enabled;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::author_overlap_threshold`].
+    #[must_use]
+    pub fn author_overlap_threshold(mut self, threshold: f64) -> Self {
+        self.config.author_overlap_threshold = threshold;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::compared_authors`].
+    #[must_use]
+    pub fn compared_authors(mut self, count: usize) -> Self {
+        self.config.compared_authors = count;
+        self
+    }
+
+    /// Sets [`DeduplicatorConfig::custom_filter`].
+    #[must_use]
+    pub fn custom_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Citation, &Citation, &MatchContext) -> MatchOverride + Send + Sync + 'static,
+    {
+        self.config.custom_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Validates the options and returns the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DedupeError::ConfigError` if a source preference is blank or
+    /// listed more than once, a DOI denylist entry is blank, the maximum group
+    /// size or the number of compared authors is zero, or the abstract
+    /// similarity or author overlap threshold is not between 0.0 and 1.0.
+    pub fn build(mut self) -> Result<DeduplicatorConfig, DedupeError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+/// Strategy for turning pairwise matches into duplicate groups.
+///
+/// Matching is not transitive: A may match B and B match C while A and C differ
+/// too much to match directly. The modes differ only in how such chains are handled.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::dedupe::{Deduplicator, DeduplicatorConfig, GroupingMode};
+///
+/// let config = DeduplicatorConfig::builder()
+///     .grouping_mode(GroupingMode::MutualMatch)
+///     .build()
+///     .unwrap();
+/// let deduplicator = Deduplicator::new().with_config(config);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupingMode {
+    /// Each ungrouped citation, in input order, becomes a seed and collects every
+    /// remaining citation that matches it directly. For an A-B-C chain this yields
+    /// `{A, B}` and `{C}`.
+    #[default]
+    SeedMatch,
+    /// Citations are grouped when connected by any chain of matches, so an A-B-C
+    /// chain forms a single group `{A, B, C}`.
+    TransitiveClosure,
+    /// A citation joins a group only if it matches every existing member. Groups
+    /// are built greedily in input order, so for an A-B-C chain this yields
+    /// `{A, B}` and `{C}` even when B comes first and would seed `{B, A, C}`
+    /// under `SeedMatch`.
+    MutualMatch,
+}
+
+/// A citation field the deduplicator can be told to ignore, see
+/// [`DeduplicatorConfig::ignore_fields`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::dedupe::{DedupeField, DeduplicatorConfig};
+///
+/// // A source that gives every record the pages "1"
+/// let config = DeduplicatorConfig::builder()
+///     .ignore_fields([DedupeField::Pages])
+///     .build()
+///     .unwrap();
+/// assert!(config.ignore_fields.contains(&DedupeField::Pages));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupeField {
+    /// [`Citation::doi`]
+    Doi,
+    /// [`Citation::pages`]
+    Pages,
+    /// [`Citation::volume`]
+    Volume,
+    /// [`Citation::journal`] and [`Citation::journal_abbr`]
+    Journal,
+    /// [`Citation::issn`]
+    Issn,
+    /// The publication year. Ignoring it also compares citations across years,
+    /// as when `group_by_year` is disabled.
+    Year,
+}
+
+/// Which publication year the deduplicator uses for a citation with both a
+/// print date ([`Citation::date`]) and an electronic publication date
+/// ([`Citation::electronic_date`]).
+///
+/// Articles published online ahead of print often carry an electronic date in
+/// one year and a print date in the next, and databases differ in which one
+/// they give as the year. Citations with only one of the dates use that one
+/// whatever the setting.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::dedupe::{DeduplicatorConfig, YearSource};
+///
+/// let config = DeduplicatorConfig::builder()
+///     .year_source(YearSource::PrintOnly)
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.year_source, YearSource::PrintOnly);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YearSource {
+    /// The year of the print date
+    PrintOnly,
+    /// The year of the electronic publication date
+    ElectronicOnly,
+    /// Both years: a citation whose two years differ is compared with the
+    /// citations of either year, and years match when any of them are equal.
+    /// It is still reported in a single group.
+    #[default]
+    Either,
 }
 
 /// Core deduplication engine for finding duplicate citations.
@@ -227,11 +854,12 @@ pub struct DeduplicatorConfig {
 /// // Create with default settings
 ///
 /// // Or with custom configuration
-/// let config = DeduplicatorConfig {
-///     group_by_year: true,
-///     run_in_parallel: true,
-///     source_preferences: vec!["PubMed".to_string(), "Embase".to_string()],
-/// };
+/// let config = DeduplicatorConfig::builder()
+///     .group_by_year(true)
+///     .run_in_parallel(true)
+///     .source_preferences(["PubMed", "Embase"])
+///     .build()
+///     .unwrap();
 /// let deduplicator = Deduplicator::new().with_config(config);
 /// ```
 ///
@@ -243,16 +871,164 @@ pub struct DeduplicatorConfig {
 #[derive(Debug, Default, Clone)]
 pub struct Deduplicator {
     config: DeduplicatorConfig,
+    /// Pairs of citation ids never grouped together, each ordered smaller id first
+    exclusions: HashSet<(String, String)>,
+    /// Print/electronic ISSN pairings added to the ISSNs of each citation
+    issn_catalog: IssnCatalog,
 }
 
 #[derive(Debug)]
 struct PreprocessedCitation<'a> {
     original: &'a Citation,
+    /// Publication year, read once instead of on every comparison
+    year: Option<i32>,
+    /// The other publication year under [`YearSource::Either`], when it differs
+    other_year: Option<i32>,
+    /// Index of the citation in the input
+    index: usize,
     normalized_title: String,
+    /// Hash of the normalized title, by which citations are bucketed for
+    /// [`DeduplicatorConfig::exact_title_fast_path`]
+    title_hash: u64,
+    title_script: Script,
+    /// Normalized transliterated title (PubMed `TT`) and its script
+    transliterated_title: Option<(String, Script)>,
     normalized_journal: Option<String>,
     normalized_journal_abbr: Option<String>,
     normalized_issn: Vec<String>,
     normalized_volume: String,
+    /// Pages with shortened end pages completed, `None` when missing or ignored
+    normalized_pages: Option<String>,
+    /// Normalized DOI, `None` when missing, blank, ignored or denylisted
+    normalized_doi: Option<String>,
+    /// Whether years are compared; `false` when they are ignored, so they never match
+    compare_years: bool,
+    /// Whether the citation is a retraction notice, which shares its title with
+    /// the retracted work
+    retraction_notice: bool,
+    /// Hashed word 3-shingles of the abstract, only with
+    /// [`DeduplicatorConfig::use_abstract_similarity`]
+    abstract_shingles: Option<HashSet<u64>>,
+    /// The citation's [`Citation::id`]
+    id: Option<&'a str>,
+    /// Trimmed PMID, `None` when missing or blank
+    pmid: Option<&'a str>,
+    /// PMC ID without its "PMC" prefix, `None` when missing or blank
+    pmc_id: Option<&'a str>,
+    /// Normalized family names of the leading authors and the last author, in
+    /// order, see [`DeduplicatorConfig::compared_authors`]
+    author_names: Vec<String>,
+}
+
+impl PreprocessedCitation<'_> {
+    /// The normalized title followed by the transliterated title, if any.
+    fn titles(&self) -> impl Iterator<Item = (&str, Script)> {
+        std::iter::once((self.normalized_title.as_str(), self.title_script)).chain(
+            self.transliterated_title
+                .as_ref()
+                .map(|(title, script)| (title.as_str(), *script)),
+        )
+    }
+
+    /// Whether both normalized titles are present and identical.
+    fn same_title(&self, other: &Self) -> bool {
+        self.title_hash == other.title_hash
+            && !self.normalized_title.is_empty()
+            && self.normalized_title == other.normalized_title
+    }
+}
+
+/// The excluded pairs among the citations of one block, by position in the block.
+#[derive(Debug, Default)]
+struct ExcludedPairs {
+    /// For each position, the positions it must not be grouped with; empty when
+    /// there are no exclusions
+    partners: Vec<Vec<usize>>,
+}
+
+impl ExcludedPairs {
+    fn new(exclusions: &HashSet<(String, String)>, preprocessed: &[PreprocessedCitation]) -> Self {
+        if exclusions.is_empty() {
+            return Self::default();
+        }
+
+        let mut positions: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (position, citation) in preprocessed.iter().enumerate() {
+            if let Some(id) = citation.id {
+                positions.entry(id).or_default().push(position);
+            }
+        }
+
+        let mut partners = vec![Vec::new(); preprocessed.len()];
+        for (a, b) in exclusions {
+            let (Some(a), Some(b)) = (positions.get(a.as_str()), positions.get(b.as_str())) else {
+                continue;
+            };
+            for &i in a {
+                for &j in b {
+                    partners[i].push(j);
+                    partners[j].push(i);
+                }
+            }
+        }
+        Self { partners }
+    }
+
+    /// Whether the citations at positions `i` and `j` must not be grouped together.
+    fn contains(&self, i: usize, j: usize) -> bool {
+        self.partners.get(i).is_some_and(|partners| partners.contains(&j))
+    }
+
+    /// Whether any citation at `group` must not be grouped with the one at `j`.
+    fn conflicts(&self, group: &[usize], j: usize) -> bool {
+        !self.partners.is_empty() && group.iter().any(|&member| self.contains(member, j))
+    }
+}
+
+/// Disjoint-set forest used by [`GroupingMode::TransitiveClosure`].
+#[derive(Debug)]
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Path compression
+        let mut node = x;
+        while self.parent[node] != root {
+            let next = self.parent[node];
+            self.parent[node] = root;
+            node = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return;
+        }
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            std::cmp::Ordering::Less => self.parent[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parent[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+    }
 }
 
 /// Error types for dedupe operations
@@ -285,9 +1061,10 @@ impl Deduplicator {
         Self {
             config: DeduplicatorConfig {
                 group_by_year: true,
-                run_in_parallel: false,
-                source_preferences: Vec::new(),
+                ..Default::default()
             },
+            exclusions: HashSet::new(),
+            issn_catalog: IssnCatalog::new(),
         }
     }
 
@@ -304,11 +1081,12 @@ impl Deduplicator {
     /// ```
     /// use biblib::dedupe::{Deduplicator, DeduplicatorConfig};
     ///
-    /// let config = DeduplicatorConfig {
-    ///     group_by_year: true,
-    ///     run_in_parallel: true,
-    ///     source_preferences: vec!["PubMed".to_string(), "Google Scholar".to_string()],   
-    /// };
+    /// let config = DeduplicatorConfig::builder()
+    ///     .group_by_year(true)
+    ///     .run_in_parallel(true)
+    ///     .source_preferences(["PubMed", "Google Scholar"])
+    ///     .build()
+    ///     .unwrap();
     /// let deduplicator = Deduplicator::new().with_config(config);
     /// ```
     #[must_use]
@@ -321,29 +1099,91 @@ impl Deduplicator {
         self
     }
 
-    /// Processes a list of citations and returns groups of duplicates.
+    /// Keeps the given pairs of citations out of the same group.
     ///
-    /// This method analyzes the provided citations and groups them based on
-    /// similarity criteria including DOIs, titles, and other metadata.
-    /// One citation in each group is designated as the unique (original) citation.
+    /// Each pair names two citations by [`Citation::id`], e.g. records a reviewer
+    /// marked as "not duplicates". Excluded pairs are never compared, whatever
+    /// their similarity, and in every [`GroupingMode`] a citation is kept out of a
+    /// group holding a citation it is excluded against, so the two are not
+    /// grouped through a third citation either. Pairs are symmetric: `("a", "b")`
+    /// also excludes `("b", "a")`. Citations without an id are never excluded,
+    /// and calling this again adds to the pairs already given.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `citations` - A slice of Citation objects to be analyzed
+    /// ```
+    /// use biblib::{dedupe::Deduplicator, Citation};
     ///
-    /// # Returns
+    /// let mut first = Citation {
+    ///     title: "Example Title".to_string(),
+    ///     doi: Some("10.1234/example".to_string()),
+    ///     journal: Some("Example Journal".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let mut second = first.clone();
+    /// first.set_id("rec-1");
+    /// second.set_id("rec-2");
     ///
-    /// Returns a vector of `DuplicateGroup`s, where each group contains
-    /// one unique citation and its identified duplicates.
+    /// let groups = Deduplicator::new()
+    ///     .with_exclusions(vec![("rec-1".to_string(), "rec-2".to_string())])
+    ///     .find_duplicates(&[first, second])
+    ///     .unwrap();
+    /// assert_eq!(groups.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn with_exclusions(mut self, pairs: Vec<(String, String)>) -> Self {
+        self.exclusions.extend(
+            pairs
+                .into_iter()
+                .map(|(a, b)| if a <= b { (a, b) } else { (b, a) }),
+        );
+        self
+    }
+
+    /// Compares the ISSNs of citations together with their counterparts in `catalog`.
+    ///
+    /// ISSNs are always compared by value whatever their kind, so a citation
+    /// giving only the print ISSN of a journal matches one giving only the
+    /// electronic ISSN once the catalog pairs the two.
     ///
     /// # Examples
     ///
     /// ```
-    /// use biblib::{dedupe::Deduplicator, Citation};
+    /// use biblib::{dedupe::Deduplicator, IssnCatalog};
     ///
-    /// let citations = vec![
-    ///     Citation {
-    ///         title: "Example Title".to_string(),
+    /// let mut catalog = IssnCatalog::new();
+    /// catalog.add_pair("0028-4793", "1533-4406");
+    /// let deduplicator = Deduplicator::new().with_issn_catalog(catalog);
+    /// ```
+    #[must_use]
+    pub fn with_issn_catalog(mut self, catalog: IssnCatalog) -> Self {
+        self.issn_catalog = catalog;
+        self
+    }
+
+    /// Processes a list of citations and returns groups of duplicates.
+    ///
+    /// This method analyzes the provided citations and groups them based on
+    /// similarity criteria including DOIs, titles, and other metadata.
+    /// One citation in each group is designated as the unique (original) citation.
+    ///
+    /// # Arguments
+    ///
+    /// * `citations` - A slice of Citation objects to be analyzed
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of `DuplicateGroup`s, where each group contains
+    /// one unique citation and its identified duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{dedupe::Deduplicator, Citation};
+    ///
+    /// let citations = vec![
+    ///     Citation {
+    ///         title: "Example Title".to_string(),
     ///         doi: Some("10.1234/example".to_string()),
     ///         ..Default::default()
     ///     },
@@ -364,7 +1204,12 @@ impl Deduplicator {
     ///
     /// This method is similar to `find_duplicates` but allows you to specify source
     /// information for each citation, enabling source-based preferences during deduplication.
-    /// Citations without corresponding source entries are treated as having no source.
+    /// Citations without corresponding source entries fall back to the source stored in
+    /// their extra fields (see [`crate::SOURCE_FIELD`]), and otherwise have no source.
+    ///
+    /// The citations in the returned groups are copies of the input with the source
+    /// passed for them recorded with [`Citation::set_source`], so
+    /// [`Citation::source`] tells which source each group member came from.
     ///
     /// # Arguments
     ///
@@ -405,360 +1250,992 @@ impl Deduplicator {
         citations: &[Citation],
         sources: &[&str],
     ) -> Result<Vec<DuplicateGroup>, DedupeError> {
-        if citations.is_empty() {
-            return Ok(Vec::new());
-        }
+        self.find_duplicates_with_warnings(citations, sources)
+            .map(|(groups, _)| groups)
+    }
 
-        // Validate input - warn if sources length exceeds citations
-        if sources.len() > citations.len() {
-            return Err(DedupeError::ConfigError(format!(
-                "Number of sources ({}) exceeds number of citations ({}). Each source must correspond to a citation.",
-                sources.len(),
-                citations.len()
-            )));
+    /// Finds duplicates like [`Deduplicator::find_duplicates_with_sources`],
+    /// also returning the [`Diagnostics`] of the run.
+    ///
+    /// Citations whose DOI is in [`DeduplicatorConfig::doi_denylist`] get a
+    /// [`DiagnosticCode::DoiDenylisted`] diagnostic, and groups capped by
+    /// [`DeduplicatorConfig::max_group_size`] a [`DiagnosticCode::GroupCapped`]
+    /// one at their unique citation. `record_index` is the index into `citations`.
+    ///
+    /// [`DiagnosticCode::DoiDenylisted`]: crate::DiagnosticCode::DoiDenylisted
+    /// [`DiagnosticCode::GroupCapped`]: crate::DiagnosticCode::GroupCapped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, DiagnosticCode, dedupe::Deduplicator};
+    ///
+    /// let citation = |title: &str| Citation {
+    ///     title: title.to_string(),
+    ///     doi: Some("10.0000/placeholder".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let citations = vec![citation("First study"), citation("Second study")];
+    ///
+    /// let (groups, diagnostics) = Deduplicator::new()
+    ///     .find_duplicates_with_warnings(&citations, &[])
+    ///     .unwrap();
+    /// assert_eq!(groups.len(), 2);
+    /// assert_eq!(diagnostics[1].code, DiagnosticCode::DoiDenylisted);
+    /// assert_eq!(diagnostics[1].record_index, Some(1));
+    /// ```
+    pub fn find_duplicates_with_warnings(
+        self,
+        citations: &[Citation],
+        sources: &[&str],
+    ) -> Result<(Vec<DuplicateGroup>, Diagnostics), DedupeError> {
+        if citations.is_empty() {
+            return Ok((Vec::new(), Diagnostics::new()));
         }
 
-        // Create source mapping using citation indices instead of IDs
-        let source_map: HashMap<usize, Option<&str>> = citations
+        let mut diagnostics: Diagnostics = citations
             .iter()
             .enumerate()
-            .zip(
-                sources
-                    .iter()
-                    .map(|&s| Some(s))
-                    .chain(std::iter::repeat(None)),
-            )
-            .map(|((idx, _citation), source)| (idx, source))
+            .filter(|_| !self.config.ignore_fields.contains(&DedupeField::Doi))
+            .filter_map(|(index, citation)| {
+                let doi = citation.doi.as_deref().and_then(Self::normalize_doi)?;
+                self.is_denylisted_doi(&doi)
+                    .then(|| Diagnostic::doi_denylisted(index, &doi))
+            })
             .collect();
 
-        // Create global mapping from citation pointers to original indices
-        let global_ptr_to_index: HashMap<*const Citation, usize> = citations
-            .iter()
-            .enumerate()
-            .map(|(i, citation)| (citation as *const Citation, i))
-            .collect();
+        let source_map = Self::source_map(citations, sources)?;
+        let chains = self.block_chains(citations);
+
+        // Members are returned with the source they were deduplicated with, so
+        // groups show which sources contributed even when it came from `sources`
+        let with_source = |citation: &PreprocessedCitation| {
+            let mut member = citation.original.clone();
+            if let Some(source) = sources.get(citation.index) {
+                member.set_source(source);
+            }
+            member
+        };
+
+        let groups_in = |chain: &Vec<Vec<usize>>| {
+            let mut groups = Vec::new();
+            let mut diagnostics = Diagnostics::new();
+            self.process_chain(citations, chain, &source_map, |preprocessed, unique, members, spilled| {
+                let mut unique_citation = with_source(&preprocessed[unique]);
+                if spilled > 0 {
+                    unique_citation
+                        .extra_fields
+                        .insert(SPILLED_DUPLICATES_FIELD.to_string(), vec![spilled.to_string()]);
+                    diagnostics.push(Diagnostic::group_capped(preprocessed[unique].index, spilled));
+                }
+                let duplicates = members.iter().filter(|&&member| member != unique);
+                groups.push(DuplicateGroup {
+                    unique: unique_citation,
+                    duplicates: duplicates
+                        .clone()
+                        .map(|&member| with_source(&preprocessed[member]))
+                        .collect(),
+                    match_info: duplicates
+                        .map(|&member| {
+                            self.match_info(&preprocessed[unique], &preprocessed[member])
+                        })
+                        .collect(),
+                });
+            })?;
+            Ok((groups, diagnostics))
+        };
 
-        if self.config.group_by_year {
-            let year_groups = Self::group_by_year_with_indices(citations);
+        let chain_results: Vec<(Vec<DuplicateGroup>, Diagnostics)> =
             if self.config.run_in_parallel {
                 use rayon::prelude::*;
 
-                let duplicate_groups: Result<Vec<_>, _> = year_groups
-                    .par_iter()
-                    .map(|(_, citations_with_indices)| {
-                        let citations_in_year: Vec<&Citation> = citations_with_indices
-                            .iter()
-                            .map(|(citation, _)| *citation)
-                            .collect();
-                        // Create a local mapping for this year group
-                        let local_to_global: HashMap<*const Citation, usize> =
-                            citations_with_indices
-                                .iter()
-                                .map(|(citation, global_idx)| {
-                                    (*citation as *const Citation, *global_idx)
-                                })
-                                .collect();
-                        self.process_citation_group_with_sources(
-                            &citations_in_year,
-                            &source_map,
-                            &local_to_global,
-                        )
-                    })
-                    .collect();
-
-                // Flatten results
-                Ok(duplicate_groups?.into_iter().flatten().collect())
+                chains.par_iter().map(groups_in).collect::<Result<_, DedupeError>>()?
             } else {
-                let mut duplicate_groups = Vec::new();
+                chains.iter().map(groups_in).collect::<Result<_, DedupeError>>()?
+            };
 
-                for citations_with_indices in year_groups.values() {
-                    let citations_in_year: Vec<&Citation> = citations_with_indices
-                        .iter()
-                        .map(|(citation, _)| *citation)
-                        .collect();
-                    // Create a local mapping for this year group
-                    let local_to_global: HashMap<*const Citation, usize> = citations_with_indices
-                        .iter()
-                        .map(|(citation, global_idx)| (*citation as *const Citation, *global_idx))
-                        .collect();
-                    duplicate_groups.extend(self.process_citation_group_with_sources(
-                        &citations_in_year,
-                        &source_map,
-                        &local_to_global,
-                    )?);
-                }
-                Ok(duplicate_groups)
-            }
-        } else {
-            let citations_refs: Vec<&Citation> = citations.iter().collect();
-            self.process_citation_group_with_sources(
-                &citations_refs,
-                &source_map,
-                &global_ptr_to_index,
-            )
+        let mut duplicate_groups = Vec::new();
+        for (groups, chain_diagnostics) in chain_results {
+            duplicate_groups.extend(groups);
+            diagnostics.extend(chain_diagnostics);
         }
+        Ok((duplicate_groups, diagnostics))
+    }
+
+    /// Calls `f` with each pair of a unique citation and one of its duplicates, as
+    /// they are found, instead of collecting [`DuplicateGroup`]s.
+    ///
+    /// `f` receives the index of the unique citation, the index of the duplicate
+    /// (both into `citations`) and the title similarity of the two, in `0.0..=1.0`.
+    /// Groups and their unique citations are the same as for
+    /// [`Deduplicator::find_duplicates`], and only the groups of one year (or of
+    /// the whole input without year grouping) are held in memory at a time.
+    /// Citations without duplicates produce no calls.
+    ///
+    /// With `run_in_parallel`, years are processed on several threads and the
+    /// order of the calls is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{dedupe::Deduplicator, Citation};
+    /// use std::sync::Mutex;
+    ///
+    /// let citation = Citation {
+    ///     title: "Example Title".to_string(),
+    ///     doi: Some("10.1234/example".to_string()),
+    ///     journal: Some("Example Journal".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let citations = vec![citation.clone(), citation];
+    ///
+    /// let pairs = Mutex::new(Vec::new());
+    /// Deduplicator::new()
+    ///     .for_each_duplicate_pair(&citations, |unique, duplicate, _score| {
+    ///         pairs.lock().unwrap().push((unique, duplicate));
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(pairs.into_inner().unwrap(), vec![(0, 1)]);
+    /// ```
+    pub fn for_each_duplicate_pair<F>(&self, citations: &[Citation], f: F) -> Result<(), DedupeError>
+    where
+        F: Fn(usize, usize, f64) + Sync,
+    {
+        self.for_each_duplicate_pair_with_sources(citations, &[], f)
     }
 
-    /// Get the year from a citation.
-    fn get_citation_year(citation: &Citation) -> Option<i32> {
-        Self::get_citation_year_static(citation)
+    /// Like [`Deduplicator::for_each_duplicate_pair`], using `sources` for
+    /// source preferences as in [`Deduplicator::find_duplicates_with_sources`].
+    pub fn for_each_duplicate_pair_with_sources<F>(
+        &self,
+        citations: &[Citation],
+        sources: &[&str],
+        f: F,
+    ) -> Result<(), DedupeError>
+    where
+        F: Fn(usize, usize, f64) + Sync,
+    {
+        if citations.is_empty() {
+            return Ok(());
+        }
+
+        let source_map = Self::source_map(citations, sources)?;
+        let chains = self.block_chains(citations);
+
+        let pairs_in = |chain: &Vec<Vec<usize>>| {
+            self.process_chain(citations, chain, &source_map, |preprocessed, unique, members, _| {
+                let unique = &preprocessed[unique];
+                for &member in members {
+                    let duplicate = &preprocessed[member];
+                    if duplicate.index != unique.index {
+                        f(unique.index, duplicate.index, Self::match_score(unique, duplicate));
+                    }
+                }
+            })
+        };
+
+        if self.config.run_in_parallel {
+            use rayon::prelude::*;
+
+            chains.par_iter().try_for_each(pairs_in)
+        } else {
+            chains.iter().try_for_each(pairs_in)
+        }
     }
 
-    fn select_unique_citation<'a>(&self, citations: &[&'a Citation]) -> &'a Citation {
-        if citations.len() == 1 {
-            return citations[0];
+    /// The source of each citation, by index, checking `sources` is not longer
+    /// than `citations`.
+    fn source_map<'a>(
+        citations: &'a [Citation],
+        sources: &[&'a str],
+    ) -> Result<Vec<Option<&'a str>>, DedupeError> {
+        // Validate input - warn if sources length exceeds citations
+        if sources.len() > citations.len() {
+            return Err(DedupeError::ConfigError(format!(
+                "Number of sources ({}) exceeds number of citations ({}). Each source must correspond to a citation.",
+                sources.len(),
+                citations.len()
+            )));
         }
 
-        // If no source preference matches, prefer citations with abstracts
-        let citations_with_abstract: Vec<_> = citations
+        Ok(citations
             .iter()
-            .filter(|c| c.abstract_text.is_some())
-            .collect();
+            .enumerate()
+            .map(|(idx, citation)| sources.get(idx).copied().or_else(|| citation.source()))
+            .collect())
+    }
 
-        match citations_with_abstract.len() {
-            0 => citations[0],               // If no abstracts, use first citation
-            1 => citations_with_abstract[0], // If one abstract, use that
-            _ => {
-                // Multiple abstracts, prefer ones with DOI
-                let with_doi = citations_with_abstract
-                    .iter()
-                    .find(|c| c.doi.as_ref().is_some_and(|d| !d.is_empty()));
+    /// Splits citation indices into the blocks compared with each other: one per
+    /// year when grouping by year, otherwise a single block.
+    ///
+    /// A citation with two years under [`YearSource::Either`] is in the blocks of
+    /// both. Blocks sharing citations are returned together as a chain, in year
+    /// order, to be processed one after the other by
+    /// [`Deduplicator::process_chain`]; the chains are independent of each other.
+    fn block_chains(&self, citations: &[Citation]) -> Vec<Vec<Vec<usize>>> {
+        if !self.config.group_by_year {
+            return vec![vec![(0..citations.len()).collect()]];
+        }
 
-                with_doi.copied().unwrap_or(citations_with_abstract[0])
+        let mut blocks = self.year_blocks(citations);
+        let mut years: Vec<i32> = blocks.keys().copied().collect();
+        years.sort_unstable();
+        let position: HashMap<i32, usize> =
+            years.iter().enumerate().map(|(i, &year)| (year, i)).collect();
+
+        // Years linked by a citation in both end up in one chain
+        let mut union_find = UnionFind::new(years.len());
+        for citation in citations {
+            if let (year, Some(other)) = self.citation_years(citation) {
+                union_find.union(position[&year.unwrap_or(0)], position[&other]);
             }
         }
+
+        let mut chains: Vec<Vec<Vec<usize>>> = Vec::new();
+        let mut chain_of_root: HashMap<usize, usize> = HashMap::new();
+        for (i, year) in years.iter().enumerate() {
+            let chain = *chain_of_root.entry(union_find.find(i)).or_insert_with(|| {
+                chains.push(Vec::new());
+                chains.len() - 1
+            });
+            chains[chain].push(blocks.remove(year).unwrap_or_default());
+        }
+        chains
     }
 
-    fn select_unique_citation_with_sources<'a>(
+    /// Processes the blocks of a chain in order, as [`Deduplicator::process_block`]
+    /// does one block.
+    ///
+    /// A citation in several blocks is reported with the duplicates it has in the
+    /// first block where it has any, and left out of the later blocks; one without
+    /// duplicates is reported alone from its last block.
+    fn process_chain<'a, F>(
         &self,
-        citations: &[&'a Citation],
-        citation_indices: &[usize],
-        source_map: &HashMap<usize, Option<&str>>,
-    ) -> &'a Citation {
-        if citations.len() == 1 {
-            return citations[0];
-        }
-
-        // First try source preferences
-        if !self.config.source_preferences.is_empty() {
-            for preferred_source in &self.config.source_preferences {
-                for (citation, &idx) in citations.iter().zip(citation_indices.iter()) {
-                    if source_map.get(&idx) == Some(&Some(preferred_source.as_str())) {
-                        return citation;
-                    }
+        citations: &'a [Citation],
+        chain: &[Vec<usize>],
+        source_map: &[Option<&str>],
+        mut visit: F,
+    ) -> Result<(), DedupeError>
+    where
+        F: FnMut(&[PreprocessedCitation<'a>], usize, &[usize], usize),
+    {
+        if let [block] = chain {
+            return self.process_block(citations, block, source_map, visit);
+        }
+
+        // Blocks still to come for each citation in more than one block
+        let mut remaining: HashMap<usize, usize> = HashMap::new();
+        for &index in chain.iter().flatten() {
+            *remaining.entry(index).or_default() += 1;
+        }
+        remaining.retain(|_, blocks| *blocks > 1);
+        let mut grouped: HashSet<usize> = HashSet::new();
+
+        for block in chain {
+            let block: Vec<usize> = block
+                .iter()
+                .copied()
+                .filter(|index| !grouped.contains(index))
+                .collect();
+            let mut newly_grouped = Vec::new();
+            self.process_block(citations, &block, source_map, |preprocessed, unique, members, spilled| {
+                let shared = |&member: &usize| remaining.contains_key(&preprocessed[member].index);
+                if let [member] = members
+                    && remaining.get(&preprocessed[*member].index) > Some(&1)
+                {
+                    return;
+                }
+                if members.len() > 1 {
+                    newly_grouped.extend(
+                        members
+                            .iter()
+                            .filter(|member| shared(member))
+                            .map(|&member| preprocessed[member].index),
+                    );
+                }
+                visit(preprocessed, unique, members, spilled);
+            })?;
+            for index in &block {
+                if let Some(blocks) = remaining.get_mut(index) {
+                    *blocks -= 1;
                 }
             }
+            grouped.extend(newly_grouped);
         }
 
-        // If no source preference matches, use the standard selection logic
-        self.select_unique_citation(citations)
+        Ok(())
     }
 
-    fn process_citation_group_with_sources(
+    /// Picks the unique citation of a group from its members, given as pairs of
+    /// original input index and citation, returning the chosen index.
+    ///
+    /// Each step below keeps only the members that qualify, unless none does:
+    ///
+    /// 1. members from the first of [`DeduplicatorConfig::source_preferences`]
+    ///    that any member comes from
+    /// 2. members with an abstract
+    /// 3. members with a non-empty DOI
+    ///
+    /// The member with the lowest input index among those left is chosen, so the
+    /// result does not depend on the order of the group.
+    fn select_unique_citation(
         &self,
-        citations: &[&Citation],
-        source_map: &HashMap<usize, Option<&str>>,
-        global_ptr_to_index: &HashMap<*const Citation, usize>,
-    ) -> Result<Vec<DuplicateGroup>, DedupeError> {
-        let mut duplicate_groups = Vec::new();
+        members: &[(usize, &Citation)],
+        source_map: &[Option<&str>],
+    ) -> usize {
+        fn narrow<'m, 'a>(
+            candidates: Vec<&'m (usize, &'a Citation)>,
+            keep: impl Fn(&(usize, &'a Citation)) -> bool,
+        ) -> Vec<&'m (usize, &'a Citation)> {
+            let kept: Vec<_> = candidates.iter().copied().filter(|&m| keep(m)).collect();
+            if kept.is_empty() {
+                candidates
+            } else {
+                kept
+            }
+        }
+
+        let mut candidates: Vec<_> = members.iter().collect();
+        if let Some(preferred) = self.config.source_preferences.iter().find(|source| {
+            candidates
+                .iter()
+                .any(|(idx, _)| source_map[*idx] == Some(source.as_str()))
+        }) {
+            candidates = narrow(candidates, |(idx, _)| {
+                source_map[*idx] == Some(preferred.as_str())
+            });
+        }
+        candidates = narrow(candidates, |(_, c)| c.abstract_text.is_some());
+        candidates = narrow(candidates, |(_, c)| c.doi.as_ref().is_some_and(|d| !d.is_empty()));
 
+        candidates
+            .into_iter()
+            .map(|&(idx, _)| idx)
+            .min()
+            .expect("a duplicate group has members")
+    }
+
+    /// Groups the citations at `block` (indices into `citations`).
+    ///
+    /// `visit` is called once per group, singletons included, with the
+    /// preprocessed citations of the block, the position of the group's unique
+    /// citation in them, the positions of all the group's members and the number
+    /// of members moved out by [`DeduplicatorConfig::max_group_size`].
+    fn process_block<'a, F>(
+        &self,
+        citations: &'a [Citation],
+        block: &[usize],
+        source_map: &[Option<&str>],
+        mut visit: F,
+    ) -> Result<(), DedupeError>
+    where
+        F: FnMut(&[PreprocessedCitation<'a>], usize, &[usize], usize),
+    {
+        let ignored = |field| self.config.ignore_fields.contains(&field);
         // Preprocess all citations in this group
-        let preprocessed: Vec<PreprocessedCitation> = citations
+        let preprocessed: Vec<PreprocessedCitation> = block
             .iter()
-            .map(|c| {
+            .map(|&index| {
+                let c = &citations[index];
+                let years = self.citation_years(c);
+                if c.title.is_empty() {
+                    return Err(DedupeError::ProcessingError(
+                        "Failed to normalize title".to_string(),
+                    ));
+                }
+                let normalized_title = normalize_title(&c.title);
+                let transliterated_title = c
+                    .extra_fields
+                    .get("TT")
+                    .and_then(|values| values.first())
+                    .map(|tt| normalize_title(tt))
+                    .filter(|tt| !tt.is_empty())
+                    .map(|tt| {
+                        let script = dominant_script(&tt);
+                        (tt, script)
+                    });
                 Ok(PreprocessedCitation {
                     original: c,
-                    normalized_title: Self::normalize_string(&Self::convert_unicode_string(
-                        &c.title,
-                    ))
-                    .ok_or_else(|| {
-                        DedupeError::ProcessingError("Failed to normalize title".to_string())
-                    })?,
-                    normalized_journal: Self::format_journal_name(c.journal.as_deref()),
-                    normalized_journal_abbr: Self::format_journal_name(c.journal_abbr.as_deref()),
+                    index,
+                    year: years.0,
+                    other_year: years.1,
+                    title_script: dominant_script(&normalized_title),
+                    title_hash: {
+                        let mut hasher = DefaultHasher::new();
+                        normalized_title.hash(&mut hasher);
+                        hasher.finish()
+                    },
+                    normalized_title,
+                    transliterated_title,
+                    normalized_journal: Self::format_journal_name(c.journal.as_deref())
+                        .filter(|_| !ignored(DedupeField::Journal)),
+                    normalized_journal_abbr: Self::format_journal_name(c.journal_abbr.as_deref())
+                        .filter(|_| !ignored(DedupeField::Journal)),
                     normalized_volume: c
                         .volume
                         .as_deref()
+                        .filter(|_| !ignored(DedupeField::Volume))
                         .map_or(String::new(), Deduplicator::normalize_volume),
-                    normalized_issn: c
-                        .issn
-                        .iter()
-                        .filter_map(|issn| Deduplicator::format_issn(issn))
-                        .collect(),
+                    normalized_issn: if ignored(DedupeField::Issn) {
+                        Vec::new()
+                    } else {
+                        self.normalized_issns(c)
+                    },
+                    normalized_pages: c
+                        .pages
+                        .as_deref()
+                        .filter(|_| !ignored(DedupeField::Pages))
+                        .map(|pages| crate::utils::format_page_numbers(pages.trim())),
+                    normalized_doi: c
+                        .doi
+                        .as_deref()
+                        .filter(|_| !ignored(DedupeField::Doi))
+                        .and_then(Deduplicator::normalize_doi)
+                        .filter(|doi| !self.is_denylisted_doi(doi)),
+                    compare_years: !ignored(DedupeField::Year),
+                    retraction_notice: c.retraction_status
+                        == Some(crate::RetractionStatus::RetractionNotice),
+                    abstract_shingles: c
+                        .abstract_text
+                        .as_deref()
+                        .filter(|_| self.config.use_abstract_similarity)
+                        .and_then(abstract_shingles),
+                    id: c.id(),
+                    pmid: c.pmid.as_deref().map(str::trim).filter(|pmid| !pmid.is_empty()),
+                    pmc_id: c.pmc_id.as_deref().and_then(Deduplicator::normalize_pmc_id),
+                    author_names: self.compared_author_names(&c.authors),
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let mut processed_indices = std::collections::HashSet::new();
+        let excluded = ExcludedPairs::new(&self.exclusions, &preprocessed);
+        let groups = match self.config.grouping_mode {
+            GroupingMode::SeedMatch => self.seed_match_groups(&preprocessed, &excluded),
+            GroupingMode::TransitiveClosure => self.transitive_groups(&preprocessed, &excluded),
+            GroupingMode::MutualMatch => self.mutual_match_groups(&preprocessed, &excluded),
+        };
 
-        for i in 0..preprocessed.len() {
-            if processed_indices.contains(&i) {
+        for (group_indices, spilled) in self.cap_groups(groups) {
+            let unique = if group_indices.len() > 1 {
+                let members: Vec<(usize, &Citation)> = group_indices
+                    .iter()
+                    .map(|&local_idx| {
+                        let member = &preprocessed[local_idx];
+                        (member.index, member.original)
+                    })
+                    .collect();
+                let unique = self.select_unique_citation(&members, source_map);
+                group_indices
+                    .iter()
+                    .copied()
+                    .find(|&local_idx| preprocessed[local_idx].index == unique)
+                    .unwrap_or(group_indices[0])
+            } else {
+                group_indices[0]
+            };
+            visit(&preprocessed, unique, &group_indices, spilled);
+        }
+
+        Ok(())
+    }
+
+    /// Applies [`DeduplicatorConfig::max_group_size`], pairing each group with the
+    /// number of its members moved out; those follow it as groups of their own.
+    fn cap_groups(&self, groups: Vec<Vec<usize>>) -> Vec<(Vec<usize>, usize)> {
+        let Some(max) = self.config.max_group_size else {
+            return groups.into_iter().map(|group| (group, 0)).collect();
+        };
+
+        let mut capped = Vec::with_capacity(groups.len());
+        for mut group in groups {
+            if group.len() <= max {
+                capped.push((group, 0));
                 continue;
             }
+            let spilled = group.split_off(max);
+            capped.push((group, spilled.len()));
+            capped.extend(spilled.into_iter().map(|member| (vec![member], 0)));
+        }
+        capped
+    }
 
-            let mut group_citations = vec![preprocessed[i].original];
-            let mut group_indices = vec![i];
-            let current = &preprocessed[i];
+    /// Groups each unprocessed citation with every later citation that matches it directly.
+    fn seed_match_groups(
+        &self,
+        preprocessed: &[PreprocessedCitation],
+        excluded: &ExcludedPairs,
+    ) -> Vec<Vec<usize>> {
+        self.greedy_groups(preprocessed, excluded, |group, j| {
+            self.is_duplicate(&preprocessed[group[0]], &preprocessed[j])
+        })
+    }
 
-            for (j, other) in preprocessed.iter().enumerate() {
-                if i == j || processed_indices.contains(&j) {
+    /// Starts a group at each unprocessed citation and adds every later
+    /// unprocessed citation that `joins` the group.
+    ///
+    /// Citations sharing a title are grouped among themselves first, see
+    /// [`Self::title_buckets`]. Each of those groups then joins a group started
+    /// earlier as a whole, or starts its own at its first member.
+    fn greedy_groups(
+        &self,
+        preprocessed: &[PreprocessedCitation],
+        excluded: &ExcludedPairs,
+        joins: impl Fn(&[usize], usize) -> bool,
+    ) -> Vec<Vec<usize>> {
+        let mut processed = vec![false; preprocessed.len()];
+        // Groups of identical titles by their first member
+        let mut settled: HashMap<usize, Vec<usize>> = HashMap::new();
+        for bucket in self.title_buckets(preprocessed) {
+            for (k, &i) in bucket.iter().enumerate() {
+                if processed[i] {
                     continue;
                 }
-
-                let journal_match = Self::journals_match(
-                    &current.normalized_journal,
-                    &current.normalized_journal_abbr,
-                    &other.normalized_journal,
-                    &other.normalized_journal_abbr,
-                );
-                let issns_match =
-                    Self::match_issns(&current.normalized_issn, &other.normalized_issn);
-                let volumes_match = !current.normalized_volume.is_empty()
-                    && !other.normalized_volume.is_empty()
-                    && current.normalized_volume == other.normalized_volume;
-                let pages_match = current.original.pages.is_some()
-                    && other.original.pages.is_some()
-                    && current.original.pages == other.original.pages;
-                let years_match = Self::get_citation_year(current.original)
-                    == Self::get_citation_year(other.original);
-
-                let is_duplicate = match (&current.original.doi, &other.original.doi) {
-                    // With DOIs
-                    (Some(doi1), Some(doi2)) if !doi1.is_empty() && !doi2.is_empty() => {
-                        let title_similarity =
-                            jaro(&current.normalized_title, &other.normalized_title);
-
-                        // With Journal/ISSN match
-                        (doi1 == doi2 && title_similarity >= DOI_TITLE_SIMILARITY_THRESHOLD && (journal_match || issns_match))
-                        // Without Journal/ISSN match: only when we have same DOI (and we use volume/pages instead)
-                        || (doi1 == doi2 && title_similarity >= 0.99 && (volumes_match || pages_match))
-                        // Without DOI match: only when we have a very high title similarity and all other fields match
-                        || (title_similarity >= 0.99 && years_match && (volumes_match || pages_match) && (journal_match || issns_match))
+                let mut group = vec![i];
+                for &j in &bucket[k + 1..] {
+                    if !processed[j] && !excluded.conflicts(&group, j) && joins(&group, j) {
+                        processed[j] = true;
+                        group.push(j);
                     }
-                    // Without DOIs
-                    _ => {
-                        let title_similarity =
-                            jaro_winkler(&current.normalized_title, &other.normalized_title);
-
-                        // With Journal/ISSN match
-                        (title_similarity >= NO_DOI_TITLE_SIMILARITY_THRESHOLD && (volumes_match || pages_match) && (journal_match || issns_match))
-                        // Without Journal/ISSN match: only when we have a very high title similarity and all other fields match
-                        || (title_similarity >= 0.99 && years_match && (volumes_match && pages_match))
-                    }
-                };
-
-                if is_duplicate {
-                    group_citations.push(other.original);
-                    group_indices.push(j);
-                    processed_indices.insert(j);
+                }
+                // Citations matching none of their bucket stay free to join other groups
+                if group.len() > 1 {
+                    processed[i] = true;
+                    settled.insert(i, group);
                 }
             }
+        }
 
-            if group_citations.len() > 1 {
-                // Convert citation indices to original indices for source lookup
-                let original_indices: Vec<usize> = group_indices
-                    .iter()
-                    .map(|&local_idx| {
-                        let citation_ptr = preprocessed[local_idx].original as *const Citation;
-                        global_ptr_to_index[&citation_ptr]
-                    })
-                    .collect();
-
-                let unique = self.select_unique_citation_with_sources(
-                    &group_citations,
-                    &original_indices,
-                    source_map,
-                );
+        let mut groups = Vec::new();
+        for i in 0..preprocessed.len() {
+            let mut group = match settled.remove(&i) {
+                Some(group) => group,
+                None if processed[i] => continue,
+                None => {
+                    processed[i] = true;
+                    vec![i]
+                }
+            };
+            for (j, done) in processed.iter_mut().enumerate().skip(i + 1) {
+                if let Some(members) = settled.get(&j) {
+                    if members
+                        .iter()
+                        .all(|&member| !excluded.conflicts(&group, member) && joins(&group, member))
+                    {
+                        group.extend(settled.remove(&j).unwrap_or_default());
+                    }
+                } else if !*done && !excluded.conflicts(&group, j) && joins(&group, j) {
+                    *done = true;
+                    group.push(j);
+                }
+            }
+            group.sort_unstable();
+            groups.push(group);
+        }
 
-                let duplicates: Vec<Citation> = group_citations
-                    .into_iter()
-                    .filter(|c| !std::ptr::eq(*c, unique))
-                    .map(|c| (*c).clone())
-                    .collect();
+        groups
+    }
 
-                duplicate_groups.push(DuplicateGroup {
-                    unique: unique.clone(),
-                    duplicates,
-                });
-                processed_indices.insert(i);
-            } else {
-                duplicate_groups.push(DuplicateGroup {
-                    unique: current.original.clone(),
-                    duplicates: Vec::new(),
-                });
+    /// Positions of the citations sharing a normalized title, by bucket of
+    /// [`PreprocessedCitation::title_hash`], each in input order and holding
+    /// at least two. Empty unless [`DeduplicatorConfig::exact_title_fast_path`]
+    /// is enabled.
+    fn title_buckets(&self, preprocessed: &[PreprocessedCitation]) -> Vec<Vec<usize>> {
+        if !self.config.exact_title_fast_path {
+            return Vec::new();
+        }
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (i, citation) in preprocessed.iter().enumerate() {
+            if !citation.normalized_title.is_empty() {
+                buckets.entry(citation.title_hash).or_default().push(i);
             }
         }
-
-        Ok(duplicate_groups)
+        let mut buckets: Vec<Vec<usize>> =
+            buckets.into_values().filter(|bucket| bucket.len() > 1).collect();
+        buckets.sort_unstable_by_key(|bucket| bucket[0]);
+        buckets
     }
 
-    fn group_by_year_with_indices(citations: &[Citation]) -> HashMap<i32, Vec<(&Citation, usize)>> {
-        let mut year_map: HashMap<i32, Vec<(&Citation, usize)>> = HashMap::new();
+    /// Groups citations by the connected components of the match graph.
+    fn transitive_groups(
+        &self,
+        preprocessed: &[PreprocessedCitation],
+        excluded: &ExcludedPairs,
+    ) -> Vec<Vec<usize>> {
+        let mut union_find = UnionFind::new(preprocessed.len());
+        // Members of each component by root, tracked only to check exclusions
+        let mut components: HashMap<usize, Vec<usize>> = if excluded.partners.is_empty() {
+            HashMap::new()
+        } else {
+            (0..preprocessed.len()).map(|i| (i, vec![i])).collect()
+        };
 
-        // TODO: handle citations without a year when grouping by year
-        for (index, citation) in citations.iter().enumerate() {
-            let year = Self::get_citation_year_static(citation).unwrap_or(0);
-            year_map.entry(year).or_default().push((citation, index));
+        // Pairs sharing a title are settled first, so the rest can skip them
+        let buckets = self.title_buckets(preprocessed);
+        let bucket_pairs = buckets.iter().flat_map(|bucket| {
+            bucket
+                .iter()
+                .enumerate()
+                .flat_map(move |(k, &i)| bucket[k + 1..].iter().map(move |&j| (i, j)))
+        });
+        let other_pairs = (0..preprocessed.len())
+            .flat_map(|i| ((i + 1)..preprocessed.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| buckets.is_empty() || !preprocessed[i].same_title(&preprocessed[j]));
+
+        for (i, j) in bucket_pairs.chain(other_pairs) {
+            // Pairs already connected need no comparison
+            let (root_i, root_j) = (union_find.find(i), union_find.find(j));
+            if root_i == root_j {
+                continue;
+            }
+            if let (Some(group_i), Some(group_j)) =
+                (components.get(&root_i), components.get(&root_j))
+                && group_j.iter().any(|&member| excluded.conflicts(group_i, member))
+            {
+                continue;
+            }
+            if self.is_duplicate(&preprocessed[i], &preprocessed[j]) {
+                union_find.union(i, j);
+                if let (Some(mut merged), Some(group_j)) =
+                    (components.remove(&root_i), components.remove(&root_j))
+                {
+                    merged.extend(group_j);
+                    components.insert(union_find.find(i), merged);
+                }
+            }
         }
 
-        year_map
+        // Order groups by their first member so output follows input order
+        let mut root_to_group: HashMap<usize, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for i in 0..preprocessed.len() {
+            let root = union_find.find(i);
+            let group_idx = *root_to_group.entry(root).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group_idx].push(i);
+        }
+
+        groups
     }
-    /// Static version of get_citation_year for use in static contexts
-    fn get_citation_year_static(citation: &Citation) -> Option<i32> {
-        citation.date.as_ref().map(|d| d.year)
+
+    /// Groups citations so that every member matches every other member.
+    fn mutual_match_groups(
+        &self,
+        preprocessed: &[PreprocessedCitation],
+        excluded: &ExcludedPairs,
+    ) -> Vec<Vec<usize>> {
+        self.greedy_groups(preprocessed, excluded, |group, j| {
+            group
+                .iter()
+                .all(|&member| self.is_duplicate(&preprocessed[member], &preprocessed[j]))
+        })
     }
 
-    fn convert_unicode_string(input: &str) -> String {
-        UNICODE_REGEX
-            .replace_all(input, |caps: &crate::regex::Captures| {
-                u32::from_str_radix(&caps[1], 16)
-                    .ok()
-                    .and_then(char::from_u32)
-                    .map(|c| c.to_string())
-                    .unwrap_or_else(|| caps[0].to_string())
-            })
-            .to_string()
+    /// Decides whether two preprocessed citations describe the same work, by the
+    /// built-in rules and then [`DeduplicatorConfig::custom_filter`].
+    fn is_duplicate(&self, current: &PreprocessedCitation, other: &PreprocessedCitation) -> bool {
+        // The same PubMed record needs no comparison unless a hook wants the context
+        if self.config.custom_filter.is_none() && Self::pubmed_ids_match(current, other) {
+            return true;
+        }
+        let context = self.match_context(current, other);
+        let Some(filter) = &self.config.custom_filter else {
+            return context.is_duplicate;
+        };
+        match filter(current.original, other.original, &context) {
+            MatchOverride::Accept => true,
+            MatchOverride::Reject => false,
+            MatchOverride::NoOpinion => context.is_duplicate,
+        }
     }
 
-    fn normalize_string(string: &str) -> Option<String> {
-        if string.is_empty() {
-            return None;
+    /// Compares two preprocessed citations field by field and applies the
+    /// built-in rules.
+    fn match_context(
+        &self,
+        current: &PreprocessedCitation,
+        other: &PreprocessedCitation,
+    ) -> MatchContext {
+        let journals_match = Self::journals_match(
+            &current.normalized_journal,
+            &current.normalized_journal_abbr,
+            &other.normalized_journal,
+            &other.normalized_journal_abbr,
+        );
+        let issns_match = Self::match_issns(&current.normalized_issn, &other.normalized_issn);
+        let volumes_match = !current.normalized_volume.is_empty()
+            && !other.normalized_volume.is_empty()
+            && current.normalized_volume == other.normalized_volume;
+        let pages_match = current.normalized_pages.is_some()
+            && current.normalized_pages == other.normalized_pages;
+        let years_match = Self::years_match(current, other);
+        let journal_match = journals_match || issns_match;
+
+        // Identical titles need no similarity, which would be 1.0
+        let exact_title = self.config.exact_title_fast_path && current.same_title(other);
+        let both_dois = current.normalized_doi.is_some() && other.normalized_doi.is_some();
+        let authors_match = self.authors_match(current, other);
+        let (title_similarity, dois_match, threshold) =
+            match (&current.normalized_doi, &other.normalized_doi) {
+                (Some(doi1), Some(doi2)) => (
+                    if exact_title {
+                        1.0
+                    } else {
+                        Self::title_similarity(current, other, jaro)
+                    },
+                    Self::dois_match(doi1, doi2),
+                    DOI_TITLE_SIMILARITY_THRESHOLD,
+                ),
+                _ => (
+                    if exact_title {
+                        1.0
+                    } else {
+                        Self::title_similarity(current, other, jaro_winkler)
+                    },
+                    false,
+                    NO_DOI_TITLE_SIMILARITY_THRESHOLD,
+                ),
+            };
+        let rules = |title_similarity: f64| {
+            if both_dois {
+                // With Journal/ISSN match
+                (dois_match && title_similarity >= DOI_TITLE_SIMILARITY_THRESHOLD && journal_match)
+                // Without Journal/ISSN match: only when we have same DOI (and we use volume/pages instead)
+                || (dois_match && title_similarity >= 0.99 && (volumes_match || pages_match))
+                // Without Journal/ISSN match: agreeing authors settle a DOI match
+                || (dois_match && title_similarity >= DOI_TITLE_SIMILARITY_THRESHOLD && authors_match == Some(true))
+                // Without DOI match: only when we have a very high title similarity and all other fields match
+                || (title_similarity >= 0.99 && years_match && (volumes_match || pages_match) && journal_match)
+            } else {
+                // With Journal/ISSN match
+                (title_similarity >= NO_DOI_TITLE_SIMILARITY_THRESHOLD && (volumes_match || pages_match) && journal_match)
+                // Without Journal/ISSN match: only when we have a very high title similarity and all other fields match
+                || (title_similarity >= 0.99 && years_match && (volumes_match && pages_match))
+            }
+        };
+        let mut is_duplicate = rules(title_similarity);
+
+        // Identical titles only need agreeing years and no contradicting DOIs or pages
+        if exact_title && !is_duplicate {
+            let no_years = current.compare_years
+                && other.compare_years
+                && [current.year, current.other_year, other.year, other.other_year]
+                    .iter()
+                    .all(Option::is_none);
+            let pages_contradict = match (&current.normalized_pages, &other.normalized_pages) {
+                (Some(a), Some(b)) => !pages_overlap(a, b),
+                _ => false,
+            };
+            is_duplicate =
+                (years_match || no_years) && (dois_match || !both_dois) && !pages_contradict;
         }
 
-        let mut result = String::with_capacity(string.len());
-        let mut s = string.trim().to_lowercase();
+        // Abstracts settle pairs with short titles or a borderline title similarity
+        let abstract_similarity = current
+            .abstract_shingles
+            .as_ref()
+            .zip(other.abstract_shingles.as_ref())
+            .map(|(a, b)| jaccard_similarity(a, b));
+        if let Some(similarity) = abstract_similarity {
+            let short_title = current
+                .normalized_title
+                .chars()
+                .count()
+                .min(other.normalized_title.chars().count())
+                < self.config.short_title_length;
+            let borderline = (title_similarity - threshold).abs() < BORDERLINE_TITLE_MARGIN;
+            if short_title || borderline {
+                is_duplicate = similarity >= self.config.abstract_similarity_threshold
+                    && (is_duplicate || rules(title_similarity + BORDERLINE_TITLE_MARGIN));
+            }
+        }
 
-        for replacement in HTML_REPLACEMENTS.iter() {
-            s = s.replace(replacement.0, replacement.1);
+        // Different authors tell apart works that share the rest, such as errata
+        if self.config.require_author_match && !dois_match && authors_match == Some(false) {
+            is_duplicate = false;
         }
 
-        s.chars()
-            .filter(|c| c.is_alphanumeric())
-            .for_each(|c| result.push(c));
+        let pubmed_ids_match = Self::pubmed_ids_match(current, other);
+        MatchContext {
+            title_similarity,
+            dois_match,
+            pubmed_ids_match,
+            journals_match,
+            issns_match,
+            volumes_match,
+            pages_match,
+            years_match,
+            authors_match: authors_match == Some(true),
+            abstract_similarity,
+            // A retraction notice never duplicates a work, whatever the titles say
+            is_duplicate: pubmed_ids_match
+                || (is_duplicate && current.retraction_notice == other.retraction_notice),
+        }
+    }
 
-        Some(result)
+    /// Lists the fields on which a duplicate agrees with the unique citation.
+    fn match_info(
+        &self,
+        unique: &PreprocessedCitation,
+        duplicate: &PreprocessedCitation,
+    ) -> MatchInfo {
+        let context = self.match_context(unique, duplicate);
+        let same = |a: Option<&str>, b: Option<&str>| a.is_some() && a == b;
+        let matched_on = [
+            (context.dois_match, MatchReason::DoiExact),
+            (same(unique.pmid, duplicate.pmid), MatchReason::PmidExact),
+            (same(unique.pmc_id, duplicate.pmc_id), MatchReason::PmcIdExact),
+            (true, MatchReason::TitleSimilarity(context.title_similarity)),
+            (context.journals_match, MatchReason::JournalMatch),
+            (context.issns_match, MatchReason::IssnMatch),
+            (context.volumes_match, MatchReason::VolumeMatch),
+            (context.pages_match, MatchReason::PagesMatch),
+            (context.years_match, MatchReason::YearMatch),
+            (context.authors_match, MatchReason::AuthorMatch),
+        ]
+        .into_iter()
+        .filter_map(|(matched, reason)| matched.then_some(reason))
+        .chain(context.abstract_similarity.map(MatchReason::AbstractSimilarity))
+        .collect();
+        MatchInfo {
+            score: context.title_similarity,
+            matched_on,
+        }
     }
 
-    fn normalize_volume(volume: &str) -> String {
-        if volume.is_empty() {
-            return String::new();
+    /// Scores how closely two citations match, as the title similarity used by
+    /// [`Deduplicator::is_duplicate`] for them.
+    fn match_score(current: &PreprocessedCitation, other: &PreprocessedCitation) -> f64 {
+        if current.normalized_doi.is_some() && other.normalized_doi.is_some() {
+            Self::title_similarity(current, other, jaro)
+        } else {
+            Self::title_similarity(current, other, jaro_winkler)
         }
+    }
 
-        // Find first sequence of numbers anywhere in the string
-        let numbers: String = volume
-            .chars()
-            .skip_while(|c| !c.is_numeric())
-            .take_while(|c| c.is_numeric())
-            .collect();
+    /// Scores the best comparable pair of titles, counting transliterated titles.
+    ///
+    /// Titles in different scripts are only compared through a transliterated
+    /// title, so a Latin title and a CJK title without one score `0.0`.
+    fn title_similarity(
+        current: &PreprocessedCitation,
+        other: &PreprocessedCitation,
+        metric: fn(&str, &str) -> f64,
+    ) -> f64 {
+        current
+            .titles()
+            .flat_map(|(a, a_script)| {
+                other.titles().filter_map(move |(b, b_script)| {
+                    script_aware_similarity(a, a_script, b, b_script, metric)
+                })
+            })
+            .fold(0.0, f64::max)
+    }
 
-        if numbers.is_empty() {
-            String::new()
-        } else {
-            numbers
+    /// Whether the citations are the same PubMed record, by PMID or PMC ID,
+    /// however their titles differ.
+    fn pubmed_ids_match(current: &PreprocessedCitation, other: &PreprocessedCitation) -> bool {
+        (current.pmid.is_some() && current.pmid == other.pmid)
+            || (current.pmc_id.is_some() && current.pmc_id == other.pmc_id)
+    }
+
+    /// The normalized family names of the first
+    /// [`DeduplicatorConfig::compared_authors`] authors followed by that of the
+    /// last author, leaving out names that normalize to nothing.
+    fn compared_author_names(&self, authors: &[crate::Author]) -> Vec<String> {
+        let mut names: Vec<String> = authors
+            .iter()
+            .map(|author| normalize_family_name(&author.name))
+            .filter(|name| !name.is_empty())
+            .collect();
+        if names.len() > self.config.compared_authors + 1 {
+            let last = names.pop();
+            names.truncate(self.config.compared_authors);
+            names.extend(last);
         }
+        names
     }
 
-    /// Check if two journals match by comparing both full name and abbreviation
+    /// Whether the authors agree as [`DeduplicatorConfig::require_author_match`]
+    /// describes, `None` when either citation lists no authors.
+    fn authors_match(
+        &self,
+        current: &PreprocessedCitation,
+        other: &PreprocessedCitation,
+    ) -> Option<bool> {
+        let (first, second) = (&current.author_names, &other.author_names);
+        if first.is_empty() || second.is_empty() {
+            return None;
+        }
+        if first[0] == second[0] {
+            return Some(true);
+        }
+        let (shorter, longer) = if first.len() <= second.len() {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let shared = shorter.iter().filter(|name| longer.contains(name)).count();
+        Some(shared as f64 >= self.config.author_overlap_threshold * shorter.len() as f64)
+    }
+
+    /// Whether the publication years match, counting the other year of either
+    /// citation under [`YearSource::Either`].
+    fn years_match(current: &PreprocessedCitation, other: &PreprocessedCitation) -> bool {
+        if !current.compare_years || !other.compare_years {
+            return false;
+        }
+        current.year == other.year
+            || current
+                .other_year
+                .is_some_and(|year| other.year == Some(year) || other.other_year == Some(year))
+            || other.other_year.is_some_and(|year| current.year == Some(year))
+    }
+
+    /// Indices of the citations of each year, citations without a year under 0.
+    fn year_blocks(&self, citations: &[Citation]) -> HashMap<i32, Vec<usize>> {
+        let mut year_map: HashMap<i32, Vec<usize>> = HashMap::new();
+
+        // TODO: handle citations without a year when grouping by year
+        for (index, citation) in citations.iter().enumerate() {
+            let (year, other_year) = self.citation_years(citation);
+            year_map.entry(year.unwrap_or(0)).or_default().push(index);
+            if let Some(other_year) = other_year {
+                year_map.entry(other_year).or_default().push(index);
+            }
+        }
+
+        year_map
+    }
+
+    /// The publication year of a citation under [`DeduplicatorConfig::year_source`],
+    /// `None` when it has no date, and under [`YearSource::Either`] the electronic
+    /// year when it differs from the print year.
+    fn citation_years(&self, citation: &Citation) -> (Option<i32>, Option<i32>) {
+        if self.config.ignore_fields.contains(&DedupeField::Year) {
+            return (None, None);
+        }
+        let print = citation.date.as_ref().map(|d| d.year);
+        let electronic = citation.electronic_date.as_ref().map(|d| d.year);
+        match self.config.year_source {
+            YearSource::PrintOnly => (print.or(electronic), None),
+            YearSource::ElectronicOnly => (electronic.or(print), None),
+            YearSource::Either => match (print, electronic) {
+                (Some(print), Some(electronic)) if print != electronic => {
+                    (Some(print), Some(electronic))
+                }
+                _ => (print.or(electronic), None),
+            },
+        }
+    }
+
+    /// Reduces a volume to its number, so "61 Suppl 1" matches "61".
+    fn normalize_volume(volume: &str) -> String {
+        crate::text::parse_volume(volume).number.unwrap_or_default()
+    }
+
+    /// Check if two journals match by comparing both full name and abbreviation
     fn journals_match(
         journal1: &Option<String>,
         journal_abbr1: &Option<String>,
@@ -784,42 +2261,58 @@ impl Deduplicator {
     }
 
     fn format_journal_name(full_name: Option<&str>) -> Option<String> {
-        full_name.map(|name| {
-            name.split(". Conference")
-                .next()
-                .unwrap_or(name)
-                .trim()
-                .to_lowercase()
-                .chars()
-                .filter(|c| c.is_alphanumeric())
-                .collect::<String>()
+        full_name.map(normalize_journal)
+    }
+
+    /// The ISSN values of a citation, followed by those of their counterparts in
+    /// the ISSN catalog, whatever their kind.
+    fn normalized_issns(&self, citation: &Citation) -> Vec<String> {
+        let issns = citation.issns();
+        let counterparts = issns
+            .iter()
+            .filter_map(|issn| self.issn_catalog.counterpart(issn))
+            .map(|issn| issn.value.clone())
+            .collect::<Vec<_>>();
+        let mut values: Vec<String> = issns.into_iter().map(|issn| issn.value).collect();
+        for value in counterparts {
+            if !values.contains(&value) {
+                values.push(value);
+            }
+        }
+        values
+    }
+
+    /// Normalizes a DOI for comparison with [`crate::utils::format_doi`]. Values
+    /// that do not contain a "10." prefix are only trimmed and lowercased.
+    fn normalize_doi(doi: &str) -> Option<String> {
+        crate::utils::format_doi(doi).or_else(|| {
+            let doi = doi.trim().to_lowercase();
+            (!doi.is_empty()).then_some(doi)
         })
     }
 
-    fn format_issn(issn_str: &str) -> Option<String> {
-        // Remove common suffixes and extra text
-        let clean_issn = issn_str
-            .trim()
-            .replace("(Electronic)", "")
-            .replace("(Linking)", "")
-            .replace("(Print)", "")
-            .replace(|c: char| !c.is_ascii_digit() && c != '-' && c != 'X', "")
-            .trim()
-            .to_string();
-
-        // Extract all digits and X
-        let digits: String = clean_issn
-            .chars()
-            .filter(|c| c.is_ascii_digit() || *c == 'X')
-            .collect();
+    /// Whether a normalized DOI is in [`DeduplicatorConfig::doi_denylist`].
+    fn is_denylisted_doi(&self, doi: &str) -> bool {
+        self.config
+            .doi_denylist
+            .iter()
+            .any(|entry| doi == entry || (entry.ends_with('/') && doi.starts_with(entry.as_str())))
+    }
 
-        // Validate format
-        match (clean_issn.len(), digits.len()) {
-            // Valid formats: "1234-5678" (9 chars with hyphen) or "12345678" (8 chars without hyphen)
-            (9, 8) if clean_issn.chars().nth(4) == Some('-') => Some(clean_issn),
-            (8, 8) => Some(format!("{}-{}", &digits[..4], &digits[4..])),
-            _ => None,
-        }
+    /// The number of a PMC ID, e.g. "7095418" for "PMC7095418" or "pmc7095418".
+    fn normalize_pmc_id(pmc_id: &str) -> Option<&str> {
+        let pmc_id = pmc_id.trim();
+        let number = match pmc_id.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("pmc") => pmc_id[3..].trim_start(),
+            _ => pmc_id,
+        };
+        (!number.is_empty()).then_some(number)
+    }
+
+    /// Checks whether two normalized DOIs identify the same work. DOIs are
+    /// case-insensitive, which normalization already accounts for.
+    fn dois_match(doi1: &str, doi2: &str) -> bool {
+        doi1 == doi2
     }
 
     fn match_issns(list1: &[String], list2: &[String]) -> bool {
@@ -829,6 +2322,278 @@ impl Deduplicator {
     }
 }
 
+/// Hashes of the word 3-shingles of the first [`ABSTRACT_COMPARED_CHARS`]
+/// normalized characters of an abstract, `None` when it has no words.
+///
+/// An abstract of fewer than three words is a single shingle.
+fn abstract_shingles(abstract_text: &str) -> Option<HashSet<u64>> {
+    let normalized: String = fold_typography(abstract_text)
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .take(ABSTRACT_COMPARED_CHARS)
+        .collect();
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+    let hash = |shingle: &[&str]| {
+        let mut hasher = DefaultHasher::new();
+        shingle.hash(&mut hasher);
+        hasher.finish()
+    };
+    if words.len() < 3 {
+        return Some(HashSet::from([hash(&words)]));
+    }
+    Some(words.windows(3).map(hash).collect())
+}
+
+/// Folds a family name to lowercase ASCII letters and digits, so "Müller-Lüdenscheidt"
+/// and "MULLER LUDENSCHEIDT" compare equal.
+fn normalize_family_name(name: &str) -> String {
+    name.chars()
+        .map(crate::short_key::fold_to_ascii)
+        .collect::<String>()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether two normalized page ranges share a page, e.g. "100-110" and "105".
+///
+/// Pages whose numbers cannot be read, such as "e0123", overlap only when equal.
+fn pages_overlap(a: &str, b: &str) -> bool {
+    // A page as its prefix, such as "S" in "S12", and its number
+    fn page(page: &str) -> Option<(&str, u64)> {
+        let page = page.trim();
+        let prefix = page.trim_end_matches(|c: char| c.is_ascii_digit());
+        Some((prefix, page[prefix.len()..].parse().ok()?))
+    }
+    fn range(pages: &str) -> Option<(&str, u64, u64)> {
+        let (first, last) = pages.split_once('-').unwrap_or((pages, pages));
+        let ((prefix, first), (last_prefix, last)) = (page(first)?, page(last)?);
+        (prefix == last_prefix && first <= last).then_some((prefix, first, last))
+    }
+
+    match (range(a), range(b)) {
+        (Some((a_prefix, a_first, a_last)), Some((b_prefix, b_first, b_last))) => {
+            a_prefix == b_prefix && a_first <= b_last && b_first <= a_last
+        }
+        _ => a == b,
+    }
+}
+
+/// Jaccard similarity of two sets, the share of their union they have in common.
+fn jaccard_similarity(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let common = a.intersection(b).count();
+    common as f64 / (a.len() + b.len() - common) as f64
+}
+
+/// Maps the id of every citation in `groups`, unique and duplicate alike, to
+/// the id of its group's unique citation.
+///
+/// Ids are read with [`Citation::id`]. Parsers leave them unset, so callers
+/// assign them with [`Citation::set_id`], or combine their exports with
+/// [`merge_datasets`](crate::merge_datasets), which gives every citation a
+/// distinct id. A unique citation maps to itself.
+///
+/// # Errors
+///
+/// Returns `DedupeError::InvalidCitation` if a citation has no id, or if an id
+/// occurs more than once, naming the id.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::dedupe::{Deduplicator, to_canonical_map};
+/// use biblib::Citation;
+///
+/// let citation = |id: &str| {
+///     let mut citation = Citation {
+///         title: "Early mobilisation after cardiac surgery".to_string(),
+///         doi: Some("10.1111/jocn.15600".to_string()),
+///         journal: Some("Journal of Clinical Nursing".to_string()),
+///         ..Default::default()
+///     };
+///     citation.set_id(id);
+///     citation
+/// };
+/// let groups = Deduplicator::new()
+///     .find_duplicates(&[citation("pubmed-1"), citation("embase-7")])
+///     .unwrap();
+///
+/// let map = to_canonical_map(&groups).unwrap();
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map["pubmed-1"], map["embase-7"]);
+/// ```
+pub fn to_canonical_map(groups: &[DuplicateGroup]) -> Result<HashMap<String, String>, DedupeError> {
+    let mut map = HashMap::new();
+    for group in groups {
+        let canonical = member_id(&group.unique)?;
+        for member in std::iter::once(&group.unique).chain(&group.duplicates) {
+            let id = member_id(member)?;
+            if map.insert(id.to_string(), canonical.to_string()).is_some() {
+                return Err(duplicate_id_error(id));
+            }
+        }
+    }
+    Ok(map)
+}
+
+/// Maps the id of each group's unique citation to the ids of all its members,
+/// the unique citation first and then its duplicates in order.
+///
+/// The inverse of [`to_canonical_map`].
+///
+/// # Errors
+///
+/// Returns `DedupeError::InvalidCitation` if a citation has no id, or if an id
+/// occurs more than once, naming the id.
+pub fn canonical_to_members(
+    groups: &[DuplicateGroup],
+) -> Result<HashMap<String, Vec<String>>, DedupeError> {
+    let mut seen = HashSet::new();
+    let mut map = HashMap::new();
+    for group in groups {
+        let mut members = Vec::with_capacity(group.duplicates.len() + 1);
+        for member in std::iter::once(&group.unique).chain(&group.duplicates) {
+            let id = member_id(member)?;
+            if !seen.insert(id) {
+                return Err(duplicate_id_error(id));
+            }
+            members.push(id.to_string());
+        }
+        map.insert(members[0].clone(), members);
+    }
+    Ok(map)
+}
+
+/// Record counts per source for the identification and screening boxes of a
+/// PRISMA 2020 flow diagram, from [`prisma_counts`].
+///
+/// Each record is counted once under its own source: in `kept` when it is the
+/// unique citation of its group, otherwise in `removed`. So for every source
+/// `identified = kept + removed`, and the `identified - removed` of all
+/// sources adds up to `unique`. A work found in several databases counts as
+/// identified by each of them, but as kept only by the source of its unique
+/// citation. `identified`, `removed` and `kept` have an entry for every
+/// source, zero where no record counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrismaCounts {
+    /// Records identified, per source
+    pub identified: HashMap<String, usize>,
+    /// Records removed as duplicates, per source
+    pub removed: HashMap<String, usize>,
+    /// Unique citations kept, per source
+    pub kept: HashMap<String, usize>,
+    /// Records removed as duplicates, per source of the unique citation that
+    /// replaced them and then per source of the removed record
+    pub removed_by_kept: HashMap<String, HashMap<String, usize>>,
+    /// Number of unique citations, one per group
+    pub unique: usize,
+}
+
+/// Counts the records identified and removed as duplicates per source, for a
+/// PRISMA 2020 flow diagram.
+///
+/// The source of a citation is looked up in `sources` by its [`Citation::id`],
+/// falling back to [`Citation::source`]. Every citation given to the
+/// deduplicator must be in `groups`, as it is in the result of
+/// [`Deduplicator::find_duplicates`]. See [`PrismaCounts`] for how the counts
+/// add up.
+///
+/// # Errors
+///
+/// Returns `DedupeError::InvalidCitation` if a citation has no source, naming
+/// the citation.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::dedupe::{Deduplicator, prisma_counts};
+/// use biblib::Citation;
+/// use std::collections::HashMap;
+///
+/// let citation = |id: &str| {
+///     let mut citation = Citation {
+///         title: "Early mobilisation after cardiac surgery".to_string(),
+///         doi: Some("10.1111/jocn.15600".to_string()),
+///         journal: Some("Journal of Clinical Nursing".to_string()),
+///         ..Default::default()
+///     };
+///     citation.set_id(id);
+///     citation
+/// };
+/// let groups = Deduplicator::new()
+///     .find_duplicates(&[citation("pubmed-1"), citation("embase-7")])
+///     .unwrap();
+/// let sources = HashMap::from([
+///     ("pubmed-1".to_string(), "PubMed".to_string()),
+///     ("embase-7".to_string(), "Embase".to_string()),
+/// ]);
+///
+/// let counts = prisma_counts(&groups, &sources).unwrap();
+/// assert_eq!(counts.identified["PubMed"] + counts.identified["Embase"], 2);
+/// assert_eq!(counts.unique, 1);
+/// ```
+pub fn prisma_counts(
+    groups: &[DuplicateGroup],
+    sources: &HashMap<String, String>,
+) -> Result<PrismaCounts, DedupeError> {
+    let mut counts = PrismaCounts {
+        unique: groups.len(),
+        ..Default::default()
+    };
+    for group in groups {
+        let kept = member_source(&group.unique, sources)?;
+        *counts.identified.entry(kept.to_string()).or_default() += 1;
+        *counts.kept.entry(kept.to_string()).or_default() += 1;
+        for duplicate in &group.duplicates {
+            let source = member_source(duplicate, sources)?;
+            *counts.identified.entry(source.to_string()).or_default() += 1;
+            *counts.removed.entry(source.to_string()).or_default() += 1;
+            *counts
+                .removed_by_kept
+                .entry(kept.to_string())
+                .or_default()
+                .entry(source.to_string())
+                .or_default() += 1;
+        }
+    }
+    for source in counts.identified.keys() {
+        counts.removed.entry(source.clone()).or_default();
+        counts.kept.entry(source.clone()).or_default();
+    }
+    Ok(counts)
+}
+
+/// The id of a group member, which the id maps require.
+fn member_id(citation: &Citation) -> Result<&str, DedupeError> {
+    citation.id().ok_or_else(|| {
+        DedupeError::InvalidCitation(format!("citation \"{}\" has no id", citation.title))
+    })
+}
+
+/// The source of a group member, by its id in `sources` or else its own.
+fn member_source<'a>(
+    citation: &'a Citation,
+    sources: &'a HashMap<String, String>,
+) -> Result<&'a str, DedupeError> {
+    citation
+        .id()
+        .and_then(|id| sources.get(id))
+        .map(String::as_str)
+        .or_else(|| citation.source())
+        .ok_or_else(|| {
+            DedupeError::InvalidCitation(format!("citation \"{}\" has no source", citation.title))
+        })
+}
+
+fn duplicate_id_error(id: &str) -> DedupeError {
+    DedupeError::InvalidCitation(format!("citation id '{}' occurs more than once", id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -864,7 +2629,7 @@ mod tests {
             },
         ];
 
-        let grouped = Deduplicator::group_by_year_with_indices(&citations);
+        let grouped = Deduplicator::new().year_blocks(&citations);
         assert_eq!(grouped.get(&2020).unwrap().len(), 1);
         assert_eq!(grouped.get(&0).unwrap().len(), 1);
     }
@@ -907,19 +2672,778 @@ mod tests {
             },
         ];
 
-        let deduplicator = Deduplicator::new();
-        let duplicate_groups = deduplicator.find_duplicates(&citations).unwrap();
+        let deduplicator = Deduplicator::new();
+        let duplicate_groups = deduplicator.find_duplicates(&citations).unwrap();
+
+        assert_eq!(duplicate_groups.len(), 2);
+        assert_eq!(
+            duplicate_groups
+                .iter()
+                .find(|g| g.unique.doi == Some("10.1234/abc".to_string()))
+                .unwrap()
+                .duplicates
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_notes_do_not_affect_matching() {
+        let citation = |title: &str, notes: Option<&str>| Citation {
+            title: title.to_string(),
+            date: Some(crate::Date {
+                year: 2020,
+                month: None,
+                day: None,
+            }),
+            journal: Some("Journal 1".to_string()),
+            volume: Some("24".to_string()),
+            notes: notes.map(str::to_string),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation("Prone positioning in ARDS", Some("Exclude: wrong population")),
+            citation("Prone positioning in ARDS", None),
+            citation("Sepsis bundles in low-resource settings", Some("Exclude: wrong population")),
+            citation("Early goal-directed therapy", Some("Exclude: wrong population")),
+        ];
+
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(groups.len(), 3);
+        assert!(groups.iter().all(|g| g.duplicates.len() <= 1));
+
+        let scores = std::sync::Mutex::new(Vec::new());
+        for notes in [None, Some("Include")] {
+            let mut with_notes = citations[..2].to_vec();
+            with_notes[1].notes = notes.map(str::to_string);
+            Deduplicator::new()
+                .for_each_duplicate_pair(&with_notes, |_, _, score| {
+                    scores.lock().unwrap().push(score)
+                })
+                .unwrap();
+        }
+        let scores = scores.into_inner().unwrap();
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0], scores[1]);
+    }
+
+    #[test]
+    fn test_placeholder_doi_is_treated_as_missing() {
+        let citation = |title: &str, volume: &str, pages: &str| Citation {
+            title: title.to_string(),
+            doi: Some("10.0000/unknown".to_string()),
+            journal: Some("Journal of Affective Disorders".to_string()),
+            volume: Some(volume.to_string()),
+            pages: Some(pages.to_string()),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation("Effects of exercise on depression in older adults", "310", "12-19"),
+            citation("Effects of exercise on anxiety in older adults", "298", "401-408"),
+        ];
+        let groups_with = |config: DeduplicatorConfig| {
+            let groups = Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(&citations)
+                .unwrap();
+            groups.iter().filter(|g| !g.duplicates.is_empty()).count()
+        };
+
+        let without_denylist = DeduplicatorConfig::builder()
+            .doi_denylist(Vec::<String>::new())
+            .build()
+            .unwrap();
+        assert_eq!(groups_with(without_denylist), 1);
+        assert_eq!(groups_with(DeduplicatorConfig::default()), 0);
+
+        let ignoring_doi = DeduplicatorConfig::builder()
+            .doi_denylist(Vec::<String>::new())
+            .ignore_fields([DedupeField::Doi])
+            .build()
+            .unwrap();
+        assert_eq!(groups_with(ignoring_doi), 0);
+        assert!(DeduplicatorConfig::builder().doi_denylist([" "]).build().is_err());
+    }
+
+    #[test]
+    fn test_ignored_pages_do_not_match() {
+        let citation = |title: &str, volume: &str| Citation {
+            title: title.to_string(),
+            journal: Some("BMJ Open".to_string()),
+            volume: Some(volume.to_string()),
+            pages: Some("1".to_string()),
+            date: Some(crate::Date {
+                year: 2022,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation("Exercise and depression in older adults: a cohort study", "12"),
+            citation("Exercise and depression in older women: a cohort study", "11"),
+            citation("Exercise and depression in older men: a cohort study", "10"),
+        ];
+        let duplicates_with = |fields: Vec<DedupeField>| {
+            let config = DeduplicatorConfig::builder().ignore_fields(fields).build().unwrap();
+            Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(&citations)
+                .unwrap()
+                .iter()
+                .map(|g| g.duplicates.len())
+                .sum::<usize>()
+        };
+
+        assert!(duplicates_with(vec![]) > 0);
+        assert_eq!(duplicates_with(vec![DedupeField::Pages]), 0);
+    }
+
+    #[test]
+    fn test_retraction_notice_is_not_a_duplicate() {
+        let citation = |status| Citation {
+            title: "Ivermectin for outpatient treatment of viral pneumonia".to_string(),
+            journal: Some("PLoS One".to_string()),
+            volume: Some("16".to_string()),
+            pages: Some("e0257001".to_string()),
+            retraction_status: status,
+            ..Default::default()
+        };
+        let duplicates = |citations: &[Citation]| {
+            Deduplicator::new()
+                .find_duplicates(citations)
+                .unwrap()
+                .iter()
+                .map(|g| g.duplicates.len())
+                .sum::<usize>()
+        };
+        let retracted = citation(Some(crate::RetractionStatus::Retracted { notice: None }));
+
+        assert_eq!(duplicates(&[retracted.clone(), citation(None)]), 1);
+        let notice = citation(Some(crate::RetractionStatus::RetractionNotice));
+        assert_eq!(duplicates(&[retracted, notice]), 0);
+    }
+
+    #[test]
+    fn test_max_group_size() {
+        let citations: Vec<Citation> = (0..50)
+            .map(|i| {
+                let mut citation = Citation {
+                    title: "Abstracts of the annual scientific meeting".to_string(),
+                    journal: Some("Critical Care".to_string()),
+                    volume: Some("27".to_string()),
+                    ..Default::default()
+                };
+                citation.set_id(&i.to_string());
+                citation
+            })
+            .collect();
+        let config = DeduplicatorConfig::builder()
+            .max_group_size(Some(10))
+            .build()
+            .unwrap();
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(&citations)
+            .unwrap();
+
+        assert_eq!(groups.len(), 41);
+        assert_eq!(groups[0].duplicates.len(), 9);
+        assert_eq!(groups[0].unique.extra_fields[SPILLED_DUPLICATES_FIELD], vec!["40"]);
+        assert!(groups[1..].iter().all(|g| g.duplicates.is_empty()
+            && !g.unique.extra_fields.contains_key(SPILLED_DUPLICATES_FIELD)));
+
+        let uncapped = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(uncapped.len(), 1);
+        assert!(!uncapped[0].unique.extra_fields.contains_key(SPILLED_DUPLICATES_FIELD));
+        assert!(DeduplicatorConfig::builder().max_group_size(Some(0)).build().is_err());
+    }
+
+    #[test]
+    fn test_warnings_of_capped_groups_and_denylisted_dois() {
+        let mut citations: Vec<Citation> = (0..5)
+            .map(|_| Citation {
+                title: "Abstracts of the annual scientific meeting".to_string(),
+                journal: Some("Critical Care".to_string()),
+                volume: Some("27".to_string()),
+                ..Default::default()
+            })
+            .collect();
+        citations[3].doi = Some("10.9999/placeholder".to_string());
+        let config = DeduplicatorConfig::builder()
+            .max_group_size(Some(2))
+            .build()
+            .unwrap();
+
+        let (groups, diagnostics) = Deduplicator::new()
+            .with_config(config.clone())
+            .find_duplicates_with_warnings(&citations, &[])
+            .unwrap();
+        assert_eq!(groups.len(), 4);
+        let reported: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.code, d.record_index))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                (crate::DiagnosticCode::DoiDenylisted, Some(3)),
+                (crate::DiagnosticCode::GroupCapped, Some(0)),
+            ]
+        );
+        assert!(diagnostics[1].message.contains("3 matching citations"));
+
+        let config = DeduplicatorConfig {
+            ignore_fields: HashSet::from([DedupeField::Doi]),
+            max_group_size: None,
+            ..config
+        };
+        let (_, diagnostics) = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates_with_warnings(&citations, &[])
+            .unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_custom_filter() {
+        let citation = |title: &str, source: &str, registry: Option<&str>| {
+            let mut citation = Citation {
+                title: title.to_string(),
+                journal: Some("Critical Care".to_string()),
+                volume: Some("27".to_string()),
+                pages: Some("101-110".to_string()),
+                date: Some(crate::Date {
+                    year: 2023,
+                    month: None,
+                    day: None,
+                }),
+                ..Default::default()
+            };
+            citation.set_source(source);
+            if let Some(registry) = registry {
+                citation
+                    .extra_fields
+                    .insert("registry".to_string(), vec![registry.to_string()]);
+            }
+            citation
+        };
+        let citations = vec![
+            citation("Early mobilisation after stroke", "PubMed", None),
+            citation("Early mobilisation after stroke", "Trials register", None),
+            citation("Protocol A", "PubMed", Some("NCT01234567")),
+            citation("Trial of early mobilisation", "Embase", Some("NCT01234567")),
+        ];
+        let registry = |c: &Citation| c.extra_fields.get("registry").cloned();
+        let grouped = |filter: Option<CustomMatchFilter>, run_in_parallel: bool| {
+            let mut config = DeduplicatorConfig::builder()
+                .run_in_parallel(run_in_parallel)
+                .build()
+                .unwrap();
+            config.custom_filter = filter;
+            Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(&citations)
+                .unwrap()
+                .iter()
+                .map(|g| g.duplicates.len())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(grouped(None, false), vec![1, 0, 0]);
+
+        let filter: CustomMatchFilter = Arc::new(move |a, b, context| {
+            if [a.source(), b.source()].contains(&Some("Trials register")) {
+                if a.title == b.title {
+                    assert!(context.is_duplicate && context.journals_match && context.pages_match);
+                }
+                MatchOverride::Reject
+            } else if registry(a).is_some() && registry(a) == registry(b) {
+                assert!(!context.is_duplicate && context.title_similarity < 0.99);
+                MatchOverride::Accept
+            } else {
+                MatchOverride::NoOpinion
+            }
+        });
+        for run_in_parallel in [false, true] {
+            assert_eq!(
+                grouped(Some(filter.clone()), run_in_parallel),
+                vec![0, 0, 1]
+            );
+        }
+
+        let config = DeduplicatorConfig::builder()
+            .custom_filter(|_, _, _| MatchOverride::NoOpinion)
+            .build()
+            .unwrap();
+        assert!(format!("{config:?}").contains("custom_filter: Some"));
+    }
+
+    #[test]
+    fn test_abstract_similarity() {
+        let letter = |abstract_text: &str| Citation {
+            title: "Letter to the editor".to_string(),
+            journal: Some("Critical Care Medicine".to_string()),
+            volume: Some("51".to_string()),
+            date: Some(crate::Date {
+                year: 2023,
+                month: None,
+                day: None,
+            }),
+            abstract_text: Some(abstract_text.to_string()),
+            ..Default::default()
+        };
+        let sepsis = "We read with interest the recent trial of early antibiotics in septic \
+                      shock and question whether the timing of the first dose was recorded \
+                      consistently across the participating centres.";
+        let delirium = "The authors report a reduction in delirium with light sedation, but \
+                        the assessment tool they used has not been validated in patients \
+                        receiving mechanical ventilation.";
+        let groups = |citations: &[Citation], enabled: bool| {
+            let config = DeduplicatorConfig::builder()
+                .use_abstract_similarity(enabled)
+                .build()
+                .unwrap();
+            Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(citations)
+                .unwrap()
+                .len()
+        };
+
+        // Titles, journal and volume alone merge unrelated letters
+        let different = [letter(sepsis), letter(delirium)];
+        assert_eq!(groups(&different, false), 1);
+        assert_eq!(groups(&different, true), 2);
+
+        let near_identical = [
+            letter(sepsis),
+            letter(&sepsis.replace("centres", "centers")),
+        ];
+        assert_eq!(groups(&near_identical, true), 1);
+
+        // A long title is decided by the title rules alone
+        let mut long = different.clone();
+        for citation in &mut long {
+            citation.title = "Early antibiotics in septic shock: a letter to the editor".to_string();
+        }
+        assert_eq!(groups(&long, true), 1);
+
+        // Without abstracts the title rules decide
+        let mut bare = different.clone();
+        for citation in &mut bare {
+            citation.abstract_text = None;
+        }
+        assert_eq!(groups(&bare, true), 1);
+
+        assert!(DeduplicatorConfig::builder()
+            .abstract_similarity_threshold(1.5)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_exact_title_fast_path() {
+        let citation = |year: Option<i32>, doi: Option<&str>, pages: Option<&str>| Citation {
+            title: "Mindfulness-based stress reduction for chronic low back pain".to_string(),
+            date: year.map(|year| crate::Date {
+                year,
+                month: None,
+                day: None,
+            }),
+            doi: doi.map(str::to_string),
+            pages: pages.map(str::to_string),
+            ..Default::default()
+        };
+        let group_count = |citations: &[Citation], fast_path: bool| {
+            let config = DeduplicatorConfig::builder()
+                .exact_title_fast_path(fast_path)
+                .build()
+                .unwrap();
+            Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(citations)
+                .unwrap()
+                .len()
+        };
+
+        // Sparse records sharing only a title and a year, or missing both years
+        let sparse = [citation(Some(2016), None, None), citation(Some(2016), None, None)];
+        assert_eq!(group_count(&sparse, true), 1);
+        assert_eq!(group_count(&sparse, false), 2);
+        let undated = [citation(None, None, None), citation(None, None, Some("1257-68"))];
+        assert_eq!(group_count(&undated, true), 1);
+
+        // Differing years, DOIs or page ranges keep identical titles apart
+        let years = [citation(Some(2016), None, None), citation(Some(2018), None, None)];
+        assert_eq!(group_count(&years, true), 2);
+        let dois = [
+            citation(Some(2016), Some("10.1001/jama.2016.2323"), None),
+            citation(Some(2016), Some("10.1001/jama.2016.2324"), None),
+        ];
+        assert_eq!(group_count(&dois, true), 2);
+        let pages = [
+            citation(Some(2016), None, Some("1240-9")),
+            citation(Some(2016), None, Some("1257-68")),
+        ];
+        assert_eq!(group_count(&pages, true), 2);
+
+        // However short, identical titles follow the same rules
+        let mut editorials = sparse.clone();
+        for citation in &mut editorials {
+            citation.title = "Editorial".to_string();
+        }
+        assert_eq!(group_count(&editorials, true), 1);
+        let mut editorials = pages.clone();
+        for citation in &mut editorials {
+            citation.title = "Editorial".to_string();
+        }
+        assert_eq!(group_count(&editorials, true), 2);
+    }
+
+    #[test]
+    fn test_author_match() {
+        let erratum = |doi: Option<&str>, authors: &[&str]| Citation {
+            title: "Erratum".to_string(),
+            authors: authors
+                .iter()
+                .map(|name| crate::Author {
+                    name: name.to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                })
+                .collect(),
+            journal: Some("Intensive Care Medicine".to_string()),
+            volume: Some("49".to_string()),
+            doi: doi.map(str::to_string),
+            date: Some(crate::Date {
+                year: 2023,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        };
+        let group_count = |citations: &[Citation], config: DeduplicatorConfig| {
+            Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(citations)
+                .unwrap()
+                .len()
+        };
+        let required = |threshold: f64| {
+            DeduplicatorConfig::builder()
+                .author_overlap_threshold(threshold)
+                .build()
+                .unwrap()
+        };
+        let not_required = DeduplicatorConfig::builder()
+            .require_author_match(false)
+            .build()
+            .unwrap();
+
+        // Errata of different articles in the same volume
+        let errata = [erratum(None, &["Nguyen"]), erratum(None, &["Okafor", "Schmidt"])];
+        assert_eq!(group_count(&errata, required(0.5)), 2);
+        assert_eq!(group_count(&errata, not_required.clone()), 1);
+
+        // Family names are compared without case, punctuation or diacritics
+        let same = [
+            erratum(None, &["Müller-Lüdenscheidt"]),
+            erratum(None, &["MULLER LUDENSCHEIDT"]),
+        ];
+        assert_eq!(group_count(&same, required(0.5)), 1);
+
+        // Shared authors count when the first authors differ
+        let reordered = [
+            erratum(None, &["Okafor", "Schmidt", "Tanaka"]),
+            erratum(None, &["Schmidt", "Okafor"]),
+        ];
+        assert_eq!(group_count(&reordered, required(0.5)), 1);
+        let partly = [
+            erratum(None, &["Okafor", "Schmidt", "Tanaka"]),
+            erratum(None, &["Schmidt", "Nguyen"]),
+        ];
+        assert_eq!(group_count(&partly, required(0.5)), 1);
+        assert_eq!(group_count(&partly, required(1.0)), 2);
+
+        // A citation without authors is matched on the other fields
+        let anonymous = [erratum(None, &["Nguyen"]), erratum(None, &[])];
+        assert_eq!(group_count(&anonymous, required(0.5)), 1);
+
+        // Matching DOIs override different authors
+        let doi = Some("10.1007/s00134-023-07001-1");
+        let misattributed = [erratum(doi, &["Nguyen"]), erratum(doi, &["Okafor"])];
+        assert_eq!(group_count(&misattributed, required(0.5)), 1);
+
+        // With the same DOI, agreeing authors stand in for the journal
+        let mut sparse = [erratum(doi, &["Nguyen"]), erratum(doi, &["Nguyen"])];
+        for citation in &mut sparse {
+            citation.journal = None;
+            citation.volume = None;
+        }
+        sparse[0].title = "Erratum to: surviving sepsis campaign guidelines".to_string();
+        sparse[1].title = "Erratum: surviving sepsis campaign guidelines".to_string();
+        assert_eq!(group_count(&sparse, required(0.5)), 1);
+        sparse[1].authors.clear();
+        assert_eq!(group_count(&sparse, required(0.5)), 2);
+
+        // Only the leading authors and the last author are compared
+        let long = [
+            erratum(None, &["Okafor", "Schmidt", "Tanaka", "Nguyen"]),
+            erratum(None, &["Schmidt", "Tanaka", "Lee"]),
+        ];
+        assert_eq!(group_count(&long, required(0.5)), 1);
+        let first_only = DeduplicatorConfig::builder()
+            .compared_authors(1)
+            .build()
+            .unwrap();
+        assert_eq!(group_count(&long, first_only), 2);
+
+        assert!(DeduplicatorConfig::builder()
+            .author_overlap_threshold(-0.1)
+            .build()
+            .is_err());
+        assert!(DeduplicatorConfig::builder()
+            .compared_authors(0)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_compared_author_names() {
+        let authors: Vec<crate::Author> = (0..2000)
+            .map(|i| crate::Author {
+                name: format!("Author{i}"),
+                given_name: None,
+                middle_name: None,
+                suffix: None,
+                affiliations: Vec::new(),
+            })
+            .collect();
+        let deduplicator = Deduplicator::new();
+        assert_eq!(
+            deduplicator.compared_author_names(&authors),
+            ["author0", "author1", "author2", "author3", "author4", "author1999"]
+        );
+        assert_eq!(
+            deduplicator.compared_author_names(&authors[..6]),
+            ["author0", "author1", "author2", "author3", "author4", "author5"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_family_name() {
+        assert_eq!(normalize_family_name("Müller-Lüdenscheidt"), "mullerludenscheidt");
+        assert_eq!(normalize_family_name("O'Brien"), "obrien");
+        assert_eq!(normalize_family_name("Ørsted"), "orsted");
+        assert_eq!(normalize_family_name("WHO"), "who");
+        assert_eq!(normalize_family_name(" - "), "");
+    }
+
+    #[test]
+    fn test_pubmed_ids_match_whatever_the_titles() {
+        let citation = |title: &str, pmid: Option<&str>, pmc_id: Option<&str>| Citation {
+            title: title.to_string(),
+            pmid: pmid.map(str::to_string),
+            pmc_id: pmc_id.map(str::to_string),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation("Efficacy of remdesivir in COVID-19", Some("32445440"), None),
+            citation("[Efficacy of remdesivir]. Translated title", Some(" 32445440 "), None),
+            citation("Telehealth for diabetes", None, Some("PMC7095418")),
+            citation("Diabetes care via telemedicine", None, Some("pmc7095418")),
+            citation("Unrelated work on asthma", Some("31234567"), Some("PMC1234567")),
+        ];
+
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].unique.title, citations[0].title);
+        assert_eq!(groups[0].duplicates[0].title, citations[1].title);
+        assert_eq!(groups[1].unique.title, citations[2].title);
+        assert_eq!(groups[1].duplicates[0].title, citations[3].title);
+        assert!(groups[2].duplicates.is_empty());
+
+        // A custom filter still sees, and can overturn, the decision
+        let config = DeduplicatorConfig::builder()
+            .custom_filter(|_, _, context| {
+                assert!(context.pubmed_ids_match && context.is_duplicate);
+                assert!(context.title_similarity < NO_DOI_TITLE_SIMILARITY_THRESHOLD);
+                MatchOverride::Reject
+            })
+            .build()
+            .unwrap();
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(&citations[..2])
+            .unwrap();
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn test_match_info_of_duplicates() {
+        let citation = |title: &str, doi: Option<&str>, pmid: Option<&str>| Citation {
+            title: title.to_string(),
+            doi: doi.map(str::to_string),
+            pmid: pmid.map(str::to_string),
+            journal: Some("BMJ".to_string()),
+            volume: Some("372".to_string()),
+            pages: Some("n71".to_string()),
+            date: Some(crate::Date {
+                year: 2021,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation("The PRISMA 2020 statement", Some("10.1136/bmj.n71"), Some("33782057")),
+            citation("The PRISMA 2020 statement.", Some("10.1136/BMJ.N71"), None),
+            citation("PRISMA 2020 statement: an updated guideline", None, Some("33782057")),
+        ];
+
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.match_info.len(), group.duplicates.len());
+
+        let info: Vec<_> = group.duplicates_with_info().collect();
+        assert_eq!(info[0].0.title, citations[1].title);
+        let first = info[0].1.unwrap();
+        assert_eq!(first.score, 1.0);
+        assert_eq!(
+            first.matched_on,
+            vec![
+                MatchReason::DoiExact,
+                MatchReason::TitleSimilarity(1.0),
+                MatchReason::JournalMatch,
+                MatchReason::VolumeMatch,
+                MatchReason::PagesMatch,
+                MatchReason::YearMatch,
+            ]
+        );
+        let second = info[1].1.unwrap();
+        assert!(second.score < NO_DOI_TITLE_SIMILARITY_THRESHOLD);
+        assert_eq!(second.matched_on[0], MatchReason::PmidExact);
+        assert!(!second.matched_on.contains(&MatchReason::DoiExact));
+
+        let json = serde_json::to_value(group).unwrap();
+        assert_eq!(json["match_info"][0]["matched_on"][0], "DoiExact");
+        assert_eq!(json["match_info"][0]["matched_on"][1]["TitleSimilarity"], 1.0);
+        let reloaded: DuplicateGroup = serde_json::from_value(json).unwrap();
+        assert_eq!(reloaded.match_info, group.match_info);
+        let older: DuplicateGroup =
+            serde_json::from_str(r#"{"unique": {}, "duplicates": [{}]}"#).unwrap();
+        assert_eq!(older.duplicates_with_info().next().unwrap().1, None);
+    }
+
+    #[test]
+    fn test_pages_overlap() {
+        assert!(pages_overlap("100-110", "105"));
+        assert!(pages_overlap("100-110", "110-120"));
+        assert!(pages_overlap("S12-S14", "S14"));
+        assert!(pages_overlap("e0123", "e0123"));
+        assert!(!pages_overlap("100-110", "111-120"));
+        assert!(!pages_overlap("S12-S14", "12-14"));
+        assert!(!pages_overlap("e0123", "e0124"));
+    }
+
+    #[test]
+    fn test_year_source() {
+        let year = |year| {
+            Some(crate::Date {
+                year,
+                month: None,
+                day: None,
+            })
+        };
+        let citation = |title: &str, print: i32, electronic: Option<i32>| Citation {
+            title: title.to_string(),
+            date: year(print),
+            electronic_date: electronic.and_then(year),
+            journal: Some("Critical Care".to_string()),
+            volume: Some("28".to_string()),
+            pages: Some("101-110".to_string()),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation("Sepsis bundles in low-resource settings", 2024, Some(2023)),
+            citation("Sepsis bundles in low-resource settings", 2023, None),
+            citation("Sepsis bundles in low-resource settings", 2024, None),
+            citation("Prone positioning in ARDS", 2023, Some(2023)),
+            citation("Early goal-directed therapy", 2024, Some(2023)),
+        ];
+
+        let groups_with = |year_source, group_by_year| {
+            let config = DeduplicatorConfig::builder()
+                .year_source(year_source)
+                .group_by_year(group_by_year)
+                .build()
+                .unwrap();
+            let mut groups = Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(&citations)
+                .unwrap();
+            // Every citation is reported exactly once
+            let titles = groups.iter().map(|g| g.duplicates.len() + 1).sum::<usize>();
+            assert_eq!(titles, citations.len());
+            groups.retain(|g| !g.duplicates.is_empty());
+            groups
+        };
 
-        assert_eq!(duplicate_groups.len(), 2);
-        assert_eq!(
-            duplicate_groups
-                .iter()
-                .find(|g| g.unique.doi == Some("10.1234/abc".to_string()))
-                .unwrap()
-                .duplicates
-                .len(),
-            1
-        );
+        // The epub ahead of print matches the 2023 record, and the 2024 record
+        // through the print year; seeded in 2023, it does not join the 2024 record
+        for group_by_year in [true, false] {
+            let groups = groups_with(YearSource::Either, group_by_year);
+            assert_eq!(groups.len(), 1);
+            let mut years: Vec<_> = std::iter::once(&groups[0].unique)
+                .chain(&groups[0].duplicates)
+                .map(|c| (c.date.as_ref().unwrap().year, c.electronic_date.is_some()))
+                .collect();
+            years.sort();
+            if group_by_year {
+                assert_eq!(years, vec![(2023, false), (2024, true)]);
+            } else {
+                assert_eq!(years, vec![(2023, false), (2024, false), (2024, true)]);
+            }
+        }
+
+        let groups = groups_with(YearSource::PrintOnly, true);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].duplicates.len(), 1);
+        assert!(groups[0].unique.date.as_ref().is_some_and(|d| d.year == 2024));
+        assert!(std::iter::once(&groups[0].unique)
+            .chain(&groups[0].duplicates)
+            .all(|c| c.date.as_ref().unwrap().year == 2024));
+
+        let groups = groups_with(YearSource::ElectronicOnly, true);
+        assert_eq!(groups.len(), 1);
+        assert!(std::iter::once(&groups[0].unique)
+            .chain(&groups[0].duplicates)
+            .all(|c| c.date.as_ref().unwrap().year == 2023 || c.electronic_date.is_some()));
+    }
+
+    #[test]
+    fn test_doi_case_and_trailing_slash() {
+        let variants = ["10.1234/ABC.DEF", "10.1234/abc.def", "10.1234/abc.def/"];
+        let citation = |doi: &str| Citation {
+            title: "Title 1".to_string(),
+            doi: Some(doi.to_string()),
+            journal: Some("Journal 1".to_string()),
+            ..Default::default()
+        };
+
+        for (i, first) in variants.iter().enumerate() {
+            for second in &variants[i + 1..] {
+                let groups = Deduplicator::new()
+                    .find_duplicates(&[citation(first), citation(second)])
+                    .unwrap();
+                assert_eq!(groups.len(), 1, "{first} and {second}");
+                assert_eq!(groups[0].duplicates.len(), 1, "{first} and {second}");
+            }
+        }
     }
 
     #[test]
@@ -968,63 +3492,6 @@ mod tests {
         assert_eq!(duplicate_groups.len(), 2);
     }
 
-    #[test]
-    fn test_normalize_string() {
-        assert_eq!(
-            Deduplicator::normalize_string("Machine Learning! (2<sup>nd</sup> Edition)"),
-            Some("machinelearning2ndedition".to_string())
-        );
-        assert_eq!(
-            Deduplicator::normalize_string("[&lt;sup&gt;11&lt;/sup&gt;C] benzo"),
-            Some("11cbenzo".to_string())
-        );
-    }
-
-    #[test]
-    fn test_convert_unicode_string() {
-        // Test basic conversion
-        assert_eq!(
-            Deduplicator::convert_unicode_string("2<U+0391>-amino-4<U+0391>"),
-            "2Α-amino-4Α",
-            "Failed to convert basic Alpha Unicode sequences"
-        );
-
-        // Test multiple different Unicode sequences
-        assert_eq!(
-            Deduplicator::convert_unicode_string("Hello <U+03A9>orld <U+03A3>cience"),
-            "Hello Ωorld Σcience",
-            "Failed to convert multiple Unicode sequences"
-        );
-
-        // Test string with no Unicode sequences
-        assert_eq!(
-            Deduplicator::convert_unicode_string("Normal String"),
-            "Normal String",
-            "Incorrectly modified string with no Unicode sequences"
-        );
-
-        // Test empty string
-        assert_eq!(
-            Deduplicator::convert_unicode_string(""),
-            "",
-            "Failed to handle empty string"
-        );
-
-        // Test mixed content
-        assert_eq!(
-            Deduplicator::convert_unicode_string("Mixed <U+0394> Unicode <U+03A9> Test"),
-            "Mixed Δ Unicode Ω Test",
-            "Failed to handle mixed content with Unicode sequences"
-        );
-
-        // Test consecutive Unicode sequences
-        assert_eq!(
-            Deduplicator::convert_unicode_string("<U+0391><U+0392><U+0393>"),
-            "ΑΒΓ",
-            "Failed to convert consecutive Unicode sequences"
-        );
-    }
-
     #[test]
     fn test_normalize_volume() {
         assert_eq!(Deduplicator::normalize_volume("61"), "61");
@@ -1040,6 +3507,8 @@ mod tests {
             Deduplicator::normalize_volume("9 (FEB) (no pagination)"),
             "9"
         );
+        assert_eq!(Deduplicator::normalize_volume(""), "");
+        assert_eq!(Deduplicator::normalize_volume("Suppl 1"), "");
     }
 
     #[test]
@@ -1054,7 +3523,7 @@ mod tests {
             Deduplicator::format_journal_name(Some(
                 "The FASEB Journal. Conference: Experimental Biology"
             )),
-            Some("thefasebjournal".to_string())
+            Some("fasebjournal".to_string())
         );
         assert_eq!(
             Deduplicator::format_journal_name(Some(
@@ -1108,33 +3577,34 @@ mod tests {
 
     #[test]
     fn test_format_issn() {
+        let format_issn = |issn: &str| crate::Issn::parse(issn).map(|issn| issn.value);
         assert_eq!(
-            Deduplicator::format_issn("1234-5678"),
+            format_issn("1234-5678"),
             Some("1234-5678".to_string())
         );
         assert_eq!(
-            Deduplicator::format_issn("12345678"),
+            format_issn("12345678"),
             Some("1234-5678".to_string())
         );
         assert_eq!(
-            Deduplicator::format_issn("1234-567X"),
+            format_issn("1234-567X"),
             Some("1234-567X".to_string())
         );
         assert_eq!(
-            Deduplicator::format_issn("1234-567X (Electronic)"),
+            format_issn("1234-567X (Electronic)"),
             Some("1234-567X".to_string())
         );
         assert_eq!(
-            Deduplicator::format_issn("1234-5678 (Print)"),
+            format_issn("1234-5678 (Print)"),
             Some("1234-5678".to_string())
         );
         assert_eq!(
-            Deduplicator::format_issn("1234-5678 (Linking)"),
+            format_issn("1234-5678 (Linking)"),
             Some("1234-5678".to_string())
         );
-        assert_eq!(Deduplicator::format_issn("invalid"), None);
-        assert_eq!(Deduplicator::format_issn("1234-56789"), None);
-        assert_eq!(Deduplicator::format_issn("123-45678"), None);
+        assert_eq!(format_issn("invalid"), None);
+        assert_eq!(format_issn("1234-56789"), None);
+        assert_eq!(format_issn("123-45678"), None);
     }
 
     #[test]
@@ -1182,6 +3652,41 @@ mod tests {
         assert!(duplicate_groups.iter().all(|g| g.duplicates.is_empty()));
     }
 
+    #[test]
+    fn test_config_builder() {
+        let config = DeduplicatorConfig::builder()
+            .group_by_year(true)
+            .run_in_parallel(true)
+            .source_preferences(["PubMed", "Embase"])
+            .grouping_mode(GroupingMode::MutualMatch)
+            .build()
+            .unwrap();
+        assert!(config.group_by_year);
+        assert!(config.run_in_parallel);
+        assert_eq!(config.source_preferences, vec!["PubMed", "Embase"]);
+        assert_eq!(config.grouping_mode, GroupingMode::MutualMatch);
+
+        let default = DeduplicatorConfig::builder().build().unwrap();
+        assert!(!default.group_by_year && !default.run_in_parallel);
+        assert!(default.source_preferences.is_empty());
+        assert_eq!(default.grouping_mode, GroupingMode::SeedMatch);
+    }
+
+    #[test]
+    fn test_config_builder_validation() {
+        for preferences in [vec!["PubMed", ""], vec!["  "]] {
+            let result = DeduplicatorConfig::builder()
+                .source_preferences(preferences)
+                .build();
+            assert!(matches!(result, Err(DedupeError::ConfigError(message)) if message.contains("blank")));
+        }
+
+        let result = DeduplicatorConfig::builder()
+            .source_preferences(["PubMed", "Embase", "PubMed"])
+            .build();
+        assert!(matches!(result, Err(DedupeError::ConfigError(message)) if message.contains("'PubMed'")));
+    }
+
     #[test]
     fn test_source_preferences() {
         let citations = vec![
@@ -1225,6 +3730,118 @@ mod tests {
         // The second citation should be selected as unique because source1 (PubMed)
         // has higher priority than source2 (Embase) in our preferences
         assert_eq!(duplicate_groups[0].duplicates.len(), 1);
+
+        // Both members expose the source passed for them
+        assert_eq!(duplicate_groups[0].unique.source(), Some("source1"));
+        assert_eq!(duplicate_groups[0].duplicates[0].source(), Some("source2"));
+        assert!(citations.iter().all(|c| c.source().is_none()));
+    }
+
+    #[test]
+    fn test_source_preferences_from_extra_fields() {
+        let citation = |source: Option<&str>| {
+            let mut citation = Citation {
+                title: "Title 1".to_string(),
+                doi: Some("10.1234/abc".to_string()),
+                journal: Some("Journal 1".to_string()),
+                ..Default::default()
+            };
+            if let Some(source) = source {
+                citation.set_source(source);
+            }
+            citation
+        };
+        let config = DeduplicatorConfig {
+            source_preferences: vec!["PubMed".to_string(), "Embase".to_string()],
+            ..Default::default()
+        };
+        let unique_source = |citations: &[Citation], sources: &[&str]| {
+            let groups = Deduplicator::new()
+                .with_config(config.clone())
+                .find_duplicates_with_sources(citations, sources)
+                .unwrap();
+            assert_eq!(groups.len(), 1);
+            groups[0].unique.source().map(str::to_string)
+        };
+
+        let citations = vec![citation(Some("Embase")), citation(Some("PubMed"))];
+        assert_eq!(unique_source(&citations, &[]).as_deref(), Some("PubMed"));
+
+        // Explicit sources take precedence over the stored ones and replace them
+        // in the result
+        let sources = ["PubMed", "Embase"];
+        assert_eq!(unique_source(&citations, &sources).as_deref(), Some("PubMed"));
+
+        // Citations without a stored source have none
+        let citations = vec![citation(Some("PubMed")), citation(None)];
+        assert_eq!(unique_source(&citations, &[]).as_deref(), Some("PubMed"));
+    }
+
+    #[test]
+    fn test_source_preference_ties_fall_through_to_quality() {
+        let citation = |abstract_text: Option<&str>| Citation {
+            title: "Title 1".to_string(),
+            abstract_text: abstract_text.map(str::to_string),
+            doi: Some("10.1234/abc".to_string()),
+            journal: Some("Journal 1".to_string()),
+            ..Default::default()
+        };
+        let citations = vec![
+            citation(None),
+            citation(Some("Embase abstract")),
+            citation(Some("PubMed abstract")),
+        ];
+        let config = DeduplicatorConfig::builder()
+            .source_preferences(["PubMed", "Embase"])
+            .build()
+            .unwrap();
+
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates_with_sources(&citations, &["PubMed", "Embase", "PubMed"])
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].unique.abstract_text.as_deref(), Some("PubMed abstract"));
+        assert_eq!(groups[0].unique.source(), Some("PubMed"));
+    }
+
+    #[test]
+    fn test_identical_citations_select_lowest_index() {
+        let citations: Vec<Citation> = (0..4)
+            .map(|i| {
+                let mut citation = Citation {
+                    title: "Title 1".to_string(),
+                    doi: Some("10.1234/abc".to_string()),
+                    journal: Some("Journal 1".to_string()),
+                    ..Default::default()
+                };
+                citation.set_id(&format!("record-{i}"));
+                citation
+            })
+            .collect();
+        let sources = ["Embase", "PubMed", "PubMed", "PubMed"];
+
+        for grouping_mode in [
+            GroupingMode::SeedMatch,
+            GroupingMode::TransitiveClosure,
+            GroupingMode::MutualMatch,
+        ] {
+            let config = DeduplicatorConfig::builder()
+                .source_preferences(["PubMed"])
+                .grouping_mode(grouping_mode)
+                .group_by_year(true)
+                .run_in_parallel(true)
+                .build()
+                .unwrap();
+            for _ in 0..10 {
+                let groups = Deduplicator::new()
+                    .with_config(config.clone())
+                    .find_duplicates_with_sources(&citations, &sources)
+                    .unwrap();
+                assert_eq!(groups.len(), 1);
+                assert_eq!(groups[0].unique.id(), Some("record-1"), "{grouping_mode:?}");
+            }
+        }
     }
 
     #[test]
@@ -1265,6 +3882,41 @@ mod tests {
         assert_eq!(duplicate_groups[0].duplicates.len(), 1);
     }
 
+    #[test]
+    fn test_truncated_abstract_counts_as_present() {
+        let year_2020 = crate::Date {
+            year: 2020,
+            month: None,
+            day: None,
+        };
+        let mut with_abstract = Citation {
+            title: "Title 1".to_string(),
+            abstract_text: Some("A long abstract that was cut short".to_string()),
+            doi: Some("10.1234/abc".to_string()),
+            journal: Some("Journal 1".to_string()),
+            date: Some(year_2020.clone()),
+            ..Default::default()
+        };
+        assert!(with_abstract.truncate_abstract(6));
+        let citations = vec![
+            Citation {
+                title: "Title 1".to_string(),
+                doi: Some("10.1234/abc".to_string()),
+                journal: Some("Journal 1".to_string()),
+                date: Some(year_2020),
+                ..Default::default()
+            },
+            with_abstract,
+        ];
+
+        let duplicate_groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(duplicate_groups.len(), 1);
+        assert_eq!(
+            duplicate_groups[0].unique.abstract_text.as_deref(),
+            Some("A long…")
+        );
+    }
+
     #[test]
     fn test_source_preferences_with_year_grouping() {
         // Create citations from different years to test year grouping with source preferences
@@ -1322,6 +3974,19 @@ mod tests {
             group_by_year: true, // This is the key - enable year grouping
             run_in_parallel: false,
             source_preferences: vec!["PubMed".to_string(), "Embase".to_string()],
+            grouping_mode: GroupingMode::SeedMatch,
+            year_source: YearSource::Either,
+            ignore_fields: HashSet::new(),
+            doi_denylist: Vec::new(),
+            max_group_size: None,
+            use_abstract_similarity: false,
+            short_title_length: 30,
+            abstract_similarity_threshold: 0.5,
+            exact_title_fast_path: true,
+            require_author_match: true,
+            author_overlap_threshold: 0.5,
+            compared_authors: 5,
+            custom_filter: None,
         };
 
         let deduplicator = Deduplicator::new().with_config(config);
@@ -1347,4 +4012,471 @@ mod tests {
             assert_eq!(group.duplicates.len(), 1);
         }
     }
+
+    /// Builds citations where A matches B (same DOI, similar title) and B matches
+    /// C (identical title, volume and journal) but A and C do not match.
+    fn chain_citation(label: &str, title: &str, doi: &str) -> Citation {
+        Citation {
+            pmid: Some(label.to_string()),
+            title: title.to_string(),
+            doi: Some(doi.to_string()),
+            journal: Some("Journal of Sleep Research".to_string()),
+            volume: Some("12".to_string()),
+            date: Some(crate::Date {
+                year: 2021,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn cjk_citation(label: &str, title: &str) -> Citation {
+        Citation {
+            pmid: Some(label.to_string()),
+            title: title.to_string(),
+            journal: Some("中华医学杂志".to_string()),
+            volume: Some("100".to_string()),
+            pages: Some("1021-1025".to_string()),
+            date: Some(crate::Date {
+                year: 2020,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cjk_titles() {
+        let original = cjk_citation("A", "新型冠状病毒肺炎患者的临床特征分析");
+        let same = cjk_citation("B", "新型冠状病毒肺炎患者的临床特征分析。");
+        let different = cjk_citation("C", "新型冠状病毒肺炎患者的心理状态分析");
+
+        let groups = dedupe_with_mode(&[original, same, different], GroupingMode::SeedMatch);
+        assert_eq!(groups, vec![vec!["A", "B"], vec!["C"]]);
+    }
+
+    #[test]
+    fn test_latin_and_cjk_titles() {
+        let chinese = cjk_citation("A", "新型冠状病毒肺炎患者的临床特征分析");
+        let mut english = cjk_citation(
+            "B",
+            "Analysis of clinical characteristics of COVID-19 patients",
+        );
+        english.doi = Some("10.1234/cmj.2020.1021".to_string());
+        let mut translated = chinese.clone();
+        translated.pmid = Some("C".to_string());
+        translated.doi = english.doi.clone();
+
+        // Same DOI, journal and pages, but titles in different scripts never match
+        let groups = dedupe_with_mode(
+            &[english.clone(), translated.clone()],
+            GroupingMode::SeedMatch,
+        );
+        assert_eq!(groups, vec![vec!["B"], vec!["C"]]);
+
+        // A transliterated title makes the comparison possible
+        english.extra_fields.insert(
+            "TT".to_string(),
+            vec!["新型冠状病毒肺炎患者的临床特征分析".to_string()],
+        );
+        let groups = dedupe_with_mode(&[english, translated, chinese], GroupingMode::SeedMatch);
+        assert_eq!(groups, vec![vec!["A", "B", "C"]]);
+    }
+
+    fn dedupe_with_mode(citations: &[Citation], mode: GroupingMode) -> Vec<Vec<String>> {
+        let config = DeduplicatorConfig {
+            grouping_mode: mode,
+            ..Default::default()
+        };
+        let groups = Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(citations)
+            .unwrap();
+
+        let mut ids: Vec<Vec<String>> = groups
+            .iter()
+            .map(|g| {
+                let mut members: Vec<String> = std::iter::once(&g.unique)
+                    .chain(&g.duplicates)
+                    .filter_map(|c| c.pmid.clone())
+                    .collect();
+                members.sort_unstable();
+                members
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_grouping_modes_on_chain() {
+        let a = chain_citation(
+            "A",
+            "Effects of exercise on sleep quality in adults",
+            "10.1234/sleep.1",
+        );
+        let b = chain_citation(
+            "B",
+            "Effects of exercise on sleep quality in older adults",
+            "10.1234/sleep.1",
+        );
+        let c = chain_citation(
+            "C",
+            "Effects of exercise on sleep quality in older adult",
+            "10.1234/sleep.2",
+        );
+
+        let in_order = vec![a.clone(), b.clone(), c.clone()];
+        assert_eq!(
+            dedupe_with_mode(&in_order, GroupingMode::SeedMatch),
+            vec![vec!["A", "B"], vec!["C"]]
+        );
+        assert_eq!(
+            dedupe_with_mode(&in_order, GroupingMode::TransitiveClosure),
+            vec![vec!["A", "B", "C"]]
+        );
+        assert_eq!(
+            dedupe_with_mode(&in_order, GroupingMode::MutualMatch),
+            vec![vec!["A", "B"], vec!["C"]]
+        );
+
+        // With the middle link first, a seed reaches both ends but the ends still
+        // do not match each other
+        let middle_first = vec![b, a, c];
+        assert_eq!(
+            dedupe_with_mode(&middle_first, GroupingMode::SeedMatch),
+            vec![vec!["A", "B", "C"]]
+        );
+        assert_eq!(
+            dedupe_with_mode(&middle_first, GroupingMode::TransitiveClosure),
+            vec![vec!["A", "B", "C"]]
+        );
+        assert_eq!(
+            dedupe_with_mode(&middle_first, GroupingMode::MutualMatch),
+            vec![vec!["A", "B"], vec!["C"]]
+        );
+    }
+
+    #[test]
+    fn test_title_buckets_settled_first() {
+        // B and C share a title and match each other; A only matches B
+        let citations = vec![
+            chain_citation(
+                "A",
+                "Effects of exercise on sleep quality in adults",
+                "10.1234/sleep.1",
+            ),
+            chain_citation(
+                "B",
+                "Effects of exercise on sleep quality in older adults",
+                "10.1234/sleep.1",
+            ),
+            chain_citation(
+                "C",
+                "Effects of exercise on sleep quality in older adults",
+                "10.1234/sleep.2",
+            ),
+        ];
+
+        // A seed no longer claims B before its identical title is grouped
+        for mode in [GroupingMode::SeedMatch, GroupingMode::MutualMatch] {
+            assert_eq!(
+                dedupe_with_mode(&citations, mode),
+                vec![vec!["A"], vec!["B", "C"]]
+            );
+        }
+        assert_eq!(
+            dedupe_with_mode(&citations, GroupingMode::TransitiveClosure),
+            vec![vec!["A", "B", "C"]]
+        );
+
+        // Led by B, the settled group takes in A like any seed would
+        let reordered = vec![citations[1].clone(), citations[0].clone(), citations[2].clone()];
+        assert_eq!(
+            dedupe_with_mode(&reordered, GroupingMode::SeedMatch),
+            vec![vec!["A", "B", "C"]]
+        );
+    }
+
+    #[test]
+    fn test_issn_catalog_pairs_print_and_electronic() {
+        // Without a shared journal name or ISSN, volume and pages would both have to
+        // match; the pages differ so that the identical titles alone do not match
+        let citation = |journal: Option<&str>, pages: Option<&str>, issn: &str| Citation {
+            title: "Statin therapy after acute coronary syndrome".to_string(),
+            journal: journal.map(str::to_string),
+            date: Some(crate::Date {
+                year: 2019,
+                month: None,
+                day: None,
+            }),
+            volume: Some("380".to_string()),
+            pages: pages.map(str::to_string),
+            issn: vec![issn.to_string()],
+            ..Default::default()
+        };
+        let citations = vec![
+            citation(Some("New England Journal of Medicine"), Some("11-22"), "0028-4793 (Print)"),
+            citation(None, Some("23-34"), "1533-4406 (Electronic)"),
+        ];
+
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let mut catalog = IssnCatalog::new();
+        catalog.add_pair("0028-4793", "1533-4406");
+        let groups = Deduplicator::new()
+            .with_issn_catalog(catalog)
+            .find_duplicates(&citations)
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].duplicates.len(), 1);
+    }
+
+    #[test]
+    fn test_exclusions() {
+        let title = "Effects of exercise on sleep quality in adults";
+        let mut a = chain_citation("A", title, "10.1234/sleep.1");
+        let mut b = chain_citation("B", title, "10.1234/sleep.1");
+        let mut c = chain_citation("C", title, "10.1234/sleep.1");
+        a.set_id("a");
+        b.set_id("b");
+        c.set_id("c");
+        let exclusions = vec![("b".to_string(), "a".to_string())];
+
+        for mode in [
+            GroupingMode::SeedMatch,
+            GroupingMode::TransitiveClosure,
+            GroupingMode::MutualMatch,
+        ] {
+            let config = DeduplicatorConfig {
+                grouping_mode: mode,
+                ..Default::default()
+            };
+            let dedupe = |citations: &[Citation]| {
+                let groups = Deduplicator::new()
+                    .with_config(config.clone())
+                    .with_exclusions(exclusions.clone())
+                    .find_duplicates(citations)
+                    .unwrap();
+                let mut sizes: Vec<usize> = groups.iter().map(|g| g.duplicates.len() + 1).collect();
+                sizes.sort_unstable();
+                (groups, sizes)
+            };
+
+            let (_, sizes) = dedupe(&[a.clone(), b.clone()]);
+            assert_eq!(sizes, vec![1, 1], "{:?}", mode);
+
+            // The third record joins exactly one of the excluded pair
+            let (groups, sizes) = dedupe(&[a.clone(), b.clone(), c.clone()]);
+            assert_eq!(sizes, vec![1, 2], "{:?}", mode);
+            let pair = groups.iter().find(|g| g.duplicates.len() == 1).unwrap();
+            let mut ids = vec![pair.unique.id().unwrap(), pair.duplicates[0].id().unwrap()];
+            ids.sort_unstable();
+            assert!(ids == ["a", "c"] || ids == ["b", "c"], "{:?}", mode);
+
+            // Without the exclusion everything groups
+            let groups = Deduplicator::new()
+                .with_config(config)
+                .find_duplicates(&[a.clone(), b.clone(), c.clone()])
+                .unwrap();
+            assert_eq!(groups.len(), 1, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn test_default_grouping_mode_is_seed_match() {
+        assert_eq!(GroupingMode::default(), GroupingMode::SeedMatch);
+        assert_eq!(
+            DeduplicatorConfig::default().grouping_mode,
+            GroupingMode::SeedMatch
+        );
+    }
+
+    #[test]
+    fn test_canonical_maps() {
+        let citation = |id: &str, title: &str| {
+            let mut citation = Citation {
+                title: title.to_string(),
+                ..Default::default()
+            };
+            citation.set_id(id);
+            citation
+        };
+        let groups = vec![
+            DuplicateGroup {
+                unique: citation("a", "First"),
+                duplicates: vec![citation("b", "First"), citation("c", "First")],
+                match_info: Vec::new(),
+            },
+            DuplicateGroup {
+                unique: citation("d", "Second"),
+                duplicates: vec![],
+                match_info: Vec::new(),
+            },
+        ];
+        let total: usize = groups.iter().map(|g| g.duplicates.len() + 1).sum();
+
+        let map = to_canonical_map(&groups).unwrap();
+        assert_eq!(map.len(), total);
+        assert_eq!(map["a"], "a");
+        assert_eq!(map["b"], map["a"]);
+        assert_eq!(map["c"], "a");
+        assert_eq!(map["d"], "d");
+
+        let members = canonical_to_members(&groups).unwrap();
+        assert_eq!(members.len(), groups.len());
+        assert_eq!(members["a"], vec!["a", "b", "c"]);
+        assert_eq!(members["d"], vec!["d"]);
+        assert_eq!(members.values().map(Vec::len).sum::<usize>(), total);
+        for (canonical, ids) in &members {
+            assert!(ids.iter().all(|id| &map[id] == canonical));
+        }
+
+        let json = serde_json::to_value(&map).unwrap();
+        assert_eq!(json["c"], "a");
+        let json = serde_json::to_value(&members).unwrap();
+        assert_eq!(json["a"][2], "c");
+    }
+
+    #[test]
+    fn test_canonical_maps_reject_colliding_ids() {
+        let mut first = Citation::new();
+        first.set_id("rec-1");
+        let mut second = Citation::new();
+        second.set_id("rec-1");
+        let groups = vec![
+            DuplicateGroup {
+                unique: first.clone(),
+                duplicates: vec![],
+                match_info: Vec::new(),
+            },
+            DuplicateGroup {
+                unique: second,
+                duplicates: vec![],
+                match_info: Vec::new(),
+            },
+        ];
+
+        for result in [
+            to_canonical_map(&groups).map(|_| ()),
+            canonical_to_members(&groups).map(|_| ()),
+        ] {
+            let err = result.unwrap_err();
+            assert!(matches!(&err, DedupeError::InvalidCitation(message) if message.contains("'rec-1'")));
+        }
+
+        let missing = vec![DuplicateGroup {
+            unique: first,
+            duplicates: vec![Citation::new()],
+            match_info: Vec::new(),
+        }];
+        assert!(to_canonical_map(&missing).is_err());
+        assert!(canonical_to_members(&missing).is_err());
+    }
+
+    /// Three sources whose overlaps are known: each work is identified by
+    /// the sources listed for it.
+    fn three_source_corpus() -> (Vec<Citation>, Vec<&'static str>, HashMap<String, String>) {
+        let works: [(&str, &[&str]); 6] = [
+            ("Early mobilisation and delirium in intensive care", &["PubMed", "Embase", "Scopus"]),
+            ("Vitamin D supplementation in older adults", &["PubMed", "Embase"]),
+            ("Machine learning for sepsis prediction", &["PubMed", "Scopus"]),
+            ("Hip protectors in nursing homes", &["PubMed"]),
+            ("Dietary fibre and the adolescent microbiome", &["Embase", "Scopus"]),
+            ("Falls detection with wearable accelerometers", &["Scopus"]),
+        ];
+        let mut citations = Vec::new();
+        let mut source_list = Vec::new();
+        let mut sources = HashMap::new();
+        for (work, (title, found_in)) in works.iter().enumerate() {
+            for source in *found_in {
+                let id = format!("{source}-{work}");
+                let mut citation = Citation {
+                    title: title.to_string(),
+                    doi: Some(format!("10.5555/work.{work}")),
+                    journal: Some("Journal of Example Studies".to_string()),
+                    date: Some(crate::Date {
+                        year: 2021,
+                        month: None,
+                        day: None,
+                    }),
+                    ..Default::default()
+                };
+                citation.set_id(&id);
+                sources.insert(id, source.to_string());
+                source_list.push(*source);
+                citations.push(citation);
+            }
+        }
+        (citations, source_list, sources)
+    }
+
+    #[test]
+    fn test_prisma_counts_three_sources() {
+        let (citations, source_list, sources) = three_source_corpus();
+        let groups = Deduplicator::new()
+            .with_config(
+                DeduplicatorConfig::builder()
+                    .source_preferences(["PubMed", "Embase", "Scopus"])
+                    .build()
+                    .unwrap(),
+            )
+            .find_duplicates_with_sources(&citations, &source_list)
+            .unwrap();
+        let counts = prisma_counts(&groups, &sources).unwrap();
+
+        let expected = |pairs: &[(&str, usize)]| -> HashMap<String, usize> {
+            pairs.iter().map(|(source, count)| (source.to_string(), *count)).collect()
+        };
+        assert_eq!(counts.unique, 6);
+        assert_eq!(counts.identified, expected(&[("PubMed", 4), ("Embase", 3), ("Scopus", 4)]));
+        assert_eq!(counts.removed, expected(&[("PubMed", 0), ("Embase", 2), ("Scopus", 3)]));
+        assert_eq!(counts.kept, expected(&[("PubMed", 4), ("Embase", 1), ("Scopus", 1)]));
+        assert_eq!(counts.removed_by_kept.len(), 2);
+        assert_eq!(counts.removed_by_kept["PubMed"], expected(&[("Embase", 2), ("Scopus", 2)]));
+        assert_eq!(counts.removed_by_kept["Embase"], expected(&[("Scopus", 1)]));
+
+        let remaining: usize = counts
+            .identified
+            .iter()
+            .map(|(source, identified)| identified - counts.removed[source])
+            .sum();
+        assert_eq!(remaining, counts.unique);
+        assert_eq!(counts.identified.values().sum::<usize>(), citations.len());
+    }
+
+    #[test]
+    fn test_prisma_counts_source_fallback_and_errors() {
+        let (citations, _, mut sources) = three_source_corpus();
+        let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+
+        // A citation's own source is used when the map has none for it
+        sources.remove("Scopus-5");
+        assert!(prisma_counts(&groups, &sources).is_err());
+        let mut groups = groups;
+        for group in &mut groups {
+            for member in std::iter::once(&mut group.unique).chain(&mut group.duplicates) {
+                if member.id() == Some("Scopus-5") {
+                    member.set_source("Scopus");
+                }
+            }
+        }
+        let counts = prisma_counts(&groups, &sources).unwrap();
+        assert_eq!(counts.identified["Scopus"], 4);
+        assert!(prisma_counts(&groups, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut union_find = UnionFind::new(5);
+        union_find.union(0, 1);
+        union_find.union(3, 4);
+        union_find.union(1, 4);
+
+        assert_eq!(union_find.find(0), union_find.find(3));
+        assert_ne!(union_find.find(0), union_find.find(2));
+    }
 }
@@ -0,0 +1,340 @@
+//! Non-fatal diagnostics reported by the parsers and the deduplicator.
+//!
+//! A [`Diagnostic`] records something that did not stop a parse or a
+//! deduplication run but that a caller may want to log or act on: lines that
+//! were skipped, records that failed to convert, data that was shortened, or
+//! inputs the deduplicator set aside. [`CitationParser::parse_lenient`] returns
+//! them in [`LenientParse::diagnostics`], and
+//! [`Deduplicator::find_duplicates_with_warnings`] alongside its groups.
+//!
+//! # Stability
+//!
+//! Each diagnostic carries a [`DiagnosticCode`], whose string form from
+//! [`DiagnosticCode::as_str`] is also its serialized form. Codes are stable:
+//! once released, a code keeps its name and meaning, and is only removed in a
+//! major release. New codes may be added in any release, so matches on
+//! [`DiagnosticCode`] need a wildcard arm. Messages are for people and may
+//! change wording at any time; key behavior off codes, not messages.
+//!
+//! [`CitationParser::parse_lenient`]: crate::CitationParser::parse_lenient
+//! [`LenientParse::diagnostics`]: crate::LenientParse::diagnostics
+//! [`Deduplicator::find_duplicates_with_warnings`]: crate::dedupe::Deduplicator::find_duplicates_with_warnings
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::diagnostics::DiagnosticCode;
+//! use biblib::{CitationParser, LenientOptions, RisParser};
+//!
+//! let input = "TY  - JOUR\nTI  - Example\nER  - \n<div>pasted</div>\n";
+//! let result = RisParser::new()
+//!     .parse_lenient(input, LenientOptions::default())
+//!     .unwrap();
+//!
+//! let diagnostic = &result.diagnostics[0];
+//! assert_eq!(diagnostic.code, DiagnosticCode::LineIgnored);
+//! assert_eq!(diagnostic.line, Some(4));
+//! assert_eq!(diagnostic.code.as_str(), "line_ignored");
+//! ```
+
+use crate::error::ParseError;
+pub use crate::validation::Severity;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The diagnostics of one parse or deduplication run, in the order found.
+pub type Diagnostics = Vec<Diagnostic>;
+
+/// What a [`Diagnostic`] reports, as a stable machine-readable code.
+///
+/// See the [module documentation](self) for the stability guarantees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    /// A non-blank line outside any record, or not in the parsed format, was skipped
+    LineIgnored,
+    /// A record failed to convert and was left out of the citations
+    RecordSkipped,
+    /// An abstract was shortened to the parser's maximum abstract length
+    AbstractTruncated,
+    /// An author list was shortened to the parser's maximum number of authors
+    AuthorsTruncated,
+    /// A duplicate group was capped at the deduplicator's maximum group size
+    GroupCapped,
+    /// A DOI in the deduplicator's DOI denylist was treated as missing
+    DoiDenylisted,
+    /// The input ended inside the last record, which was kept and marked with
+    /// [`TRUNCATED_FIELD`](crate::TRUNCATED_FIELD)
+    RecordTruncated,
+    /// A date could not be read, e.g. a year outside 1400 to 2100, and was kept
+    /// as written in `extra_fields` unless another date tag could be read
+    DateUnreadable,
+    /// Month and day of a date could be read in either order, so only its year
+    /// was kept
+    DateAmbiguous,
+    /// The input holds several documents, as when exports are joined with
+    /// `cat`, and the records of all were read
+    DocumentsConcatenated,
+    /// An export header before the first record, such as the search strategy
+    /// of an Ovid export, was skipped
+    HeaderSkipped,
+}
+
+impl DiagnosticCode {
+    /// The code as written in serialized diagnostics, e.g. "line_ignored".
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCode::LineIgnored => "line_ignored",
+            DiagnosticCode::RecordSkipped => "record_skipped",
+            DiagnosticCode::AbstractTruncated => "abstract_truncated",
+            DiagnosticCode::AuthorsTruncated => "authors_truncated",
+            DiagnosticCode::GroupCapped => "group_capped",
+            DiagnosticCode::DoiDenylisted => "doi_denylisted",
+            DiagnosticCode::RecordTruncated => "record_truncated",
+            DiagnosticCode::DateUnreadable => "date_unreadable",
+            DiagnosticCode::DateAmbiguous => "date_ambiguous",
+            DiagnosticCode::DocumentsConcatenated => "documents_concatenated",
+            DiagnosticCode::HeaderSkipped => "header_skipped",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A non-fatal problem found while parsing or deduplicating.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// [`Severity::Error`] when data was lost, such as a skipped record,
+    /// otherwise [`Severity::Warning`]
+    pub severity: Severity,
+    /// What happened, stable across releases
+    pub code: DiagnosticCode,
+    /// A description of what happened for people, e.g. for logs
+    pub message: String,
+    /// Index of the citation concerned in the returned citations, or in the
+    /// citations passed to the deduplicator
+    pub record_index: Option<usize>,
+    /// 1-based line of the input concerned
+    pub line: Option<usize>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic that points at no record or line.
+    pub fn new(severity: Severity, code: DiagnosticCode, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            record_index: None,
+            line: None,
+        }
+    }
+
+    pub(crate) fn line_ignored(line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::LineIgnored,
+                format!("line {line} is not part of any record"),
+            )
+        }
+    }
+
+    pub(crate) fn record_skipped(error: &ParseError) -> Self {
+        Self {
+            line: error.line,
+            ..Self::new(
+                Severity::Error,
+                DiagnosticCode::RecordSkipped,
+                format!("record skipped: {error}"),
+            )
+        }
+    }
+
+    pub(crate) fn abstract_truncated(index: usize) -> Self {
+        Self {
+            record_index: Some(index),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::AbstractTruncated,
+                format!("abstract of citation {index} was truncated"),
+            )
+        }
+    }
+
+    pub(crate) fn authors_truncated(index: usize, kept: usize, count: usize) -> Self {
+        Self {
+            record_index: Some(index),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::AuthorsTruncated,
+                format!("citation {index} keeps {kept} of {count} authors"),
+            )
+        }
+    }
+
+    pub(crate) fn record_truncated(index: usize) -> Self {
+        Self {
+            record_index: Some(index),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::RecordTruncated,
+                format!("input ends inside citation {index}, which may be incomplete"),
+            )
+        }
+    }
+
+    #[cfg(feature = "ris")]
+    pub(crate) fn date_unreadable(line: usize, tag: &str, date: &str) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::DateUnreadable,
+                format!("{tag} date \"{date}\" on line {line} could not be read"),
+            )
+        }
+    }
+
+    #[cfg(feature = "ris")]
+    pub(crate) fn date_ambiguous(line: usize, tag: &str, date: &str) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::DateAmbiguous,
+                format!(
+                    "{tag} date \"{date}\" on line {line} could be month/day or day/month, \
+                     only the year was kept"
+                ),
+            )
+        }
+    }
+
+    #[cfg(feature = "xml")]
+    pub(crate) fn documents_concatenated(line: usize) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::DocumentsConcatenated,
+                format!("another document starts on line {line}"),
+            )
+        }
+    }
+
+    #[cfg(feature = "ris")]
+    pub(crate) fn header_skipped(lines: usize) -> Self {
+        Self {
+            line: Some(1),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::HeaderSkipped,
+                format!("the export header on lines 1 to {lines} was skipped"),
+            )
+        }
+    }
+
+    #[cfg(feature = "dedupe")]
+    pub(crate) fn group_capped(index: usize, spilled: usize) -> Self {
+        Self {
+            record_index: Some(index),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::GroupCapped,
+                format!(
+                    "duplicate group of citation {index} was capped, \
+                     {spilled} matching citations left out"
+                ),
+            )
+        }
+    }
+
+    #[cfg(feature = "dedupe")]
+    pub(crate) fn doi_denylisted(index: usize, doi: &str) -> Self {
+        Self {
+            record_index: Some(index),
+            ..Self::new(
+                Severity::Warning,
+                DiagnosticCode::DoiDenylisted,
+                format!("DOI \"{doi}\" of citation {index} is denylisted and was ignored"),
+            )
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    /// Writes the diagnostic as e.g. `warning[line_ignored]: line 4 is not part of any record`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}[{}]: {}", self.code, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CitationFormat;
+    use crate::error::ValueError;
+
+    #[test]
+    fn test_display_and_serialization() {
+        let diagnostic = Diagnostic::line_ignored(4);
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning[line_ignored]: line 4 is not part of any record"
+        );
+
+        let json = serde_json::to_value(&diagnostic).unwrap();
+        assert_eq!(json["code"], "line_ignored");
+        assert_eq!(json["severity"], "Warning");
+        assert_eq!(json["line"], 4);
+        assert_eq!(json["record_index"], serde_json::Value::Null);
+        assert_eq!(
+            serde_json::from_value::<Diagnostic>(json).unwrap(),
+            diagnostic
+        );
+    }
+
+    #[test]
+    fn test_codes_serialize_as_their_names() {
+        for code in [
+            DiagnosticCode::LineIgnored,
+            DiagnosticCode::RecordSkipped,
+            DiagnosticCode::AbstractTruncated,
+            DiagnosticCode::AuthorsTruncated,
+            DiagnosticCode::GroupCapped,
+            DiagnosticCode::DoiDenylisted,
+            DiagnosticCode::RecordTruncated,
+            DiagnosticCode::DateUnreadable,
+            DiagnosticCode::DateAmbiguous,
+            DiagnosticCode::DocumentsConcatenated,
+            DiagnosticCode::HeaderSkipped,
+        ] {
+            let json = serde_json::to_string(&code).unwrap();
+            assert_eq!(json, format!("\"{}\"", code.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_record_skipped_keeps_the_error_line() {
+        let error = ParseError::at_line(
+            7,
+            CitationFormat::Ris,
+            ValueError::Syntax("bad".to_string()),
+        );
+        let diagnostic = Diagnostic::record_skipped(&error);
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.line, Some(7));
+        assert_eq!(diagnostic.record_index, None);
+    }
+}
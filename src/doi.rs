@@ -0,0 +1,211 @@
+//! Validation of DOIs at several levels of strictness.
+//!
+//! The parsers read DOI fields leniently, accepting anything containing
+//! "10.", as DOI fields hold DOIs written in many ways. DOIs taken from other
+//! text, such as a `doi.org` link in a URL field, are only kept when they pass
+//! [`DoiValidation::Strict`], so text such as "10.5 mg" is never read as a DOI.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::doi::{DoiValidation, validate};
+//!
+//! assert!(validate("https://doi.org/10.1000/xyz123", DoiValidation::Strict));
+//! assert!(validate("10.5 mg", DoiValidation::Lenient));
+//! assert!(!validate("10.5 mg", DoiValidation::Strict));
+//! assert!(!validate("10.1000/xyz123)", DoiValidation::Strict));
+//! ```
+
+/// How strictly [`validate`] checks a DOI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DoiValidation {
+    /// Any value containing "10.", as the parsers read DOI fields
+    #[default]
+    Lenient,
+    /// "10.", a registrant code of 4 to 9 digits, "/" and a suffix of at least
+    /// 3 characters, which is not all digits unless it has at least 6. Values
+    /// with whitespace, unbalanced parentheses or typographic dashes such as
+    /// "–", left by copying from formatted citations, are rejected.
+    Strict,
+    /// [`DoiValidation::Strict`], with a registrant code from 1000 to 99999,
+    /// the range registration agencies assign from
+    Checked,
+}
+
+/// Whether `doi` is a DOI at the given level of strictness.
+///
+/// A resolver such as `https://doi.org/`, a "doi:" prefix and a " \[doi\]"
+/// suffix are allowed around the DOI, as is whitespace at either end.
+#[must_use]
+pub fn validate(doi: &str, validation: DoiValidation) -> bool {
+    match validation {
+        DoiValidation::Lenient => crate::utils::format_doi(doi).is_some(),
+        DoiValidation::Strict => registrant(bare(doi)).is_some(),
+        DoiValidation::Checked => registrant(bare(doi))
+            .and_then(|registrant| registrant.parse::<u32>().ok())
+            .is_some_and(|registrant| (1000..=99999).contains(&registrant)),
+    }
+}
+
+/// Takes a DOI from text, from its first "10." on, and normalizes it like the
+/// parsers do when it passes `validation`.
+#[cfg(any(feature = "ris", feature = "xml", feature = "bibtex"))]
+pub(crate) fn extract(text: &str, validation: DoiValidation) -> Option<String> {
+    if validation == DoiValidation::Lenient {
+        return crate::utils::format_doi(text);
+    }
+    let doi = &text[text.find("10.")?..];
+    validate(doi, validation)
+        .then(|| crate::utils::format_doi(doi))
+        .flatten()
+}
+
+/// Strips whitespace, a resolver or "doi:" prefix and a "[doi]" suffix.
+fn bare(doi: &str) -> &str {
+    let doi = doi.trim();
+    let doi = doi.strip_suffix("[doi]").map_or(doi, str::trim_end);
+    for prefix in [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ] {
+        if doi
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        {
+            return doi[prefix.len()..].trim_start();
+        }
+    }
+    doi
+}
+
+/// The registrant code of a DOI passing [`DoiValidation::Strict`].
+fn registrant(doi: &str) -> Option<&str> {
+    let (registrant, suffix) = doi.strip_prefix("10.")?.split_once('/')?;
+    let suffix_chars = suffix.chars().count();
+    let valid = (4..=9).contains(&registrant.len())
+        && registrant.bytes().all(|b| b.is_ascii_digit())
+        && suffix_chars >= 3
+        && (suffix_chars >= 6 || !suffix.bytes().all(|b| b.is_ascii_digit()))
+        && !suffix
+            .chars()
+            .any(|c| c.is_whitespace() || is_typographic_dash(c))
+        && balanced_parentheses(suffix);
+    valid.then_some(registrant)
+}
+
+/// Whether `c` is a dash other than the ASCII hyphen-minus.
+fn is_typographic_dash(c: char) -> bool {
+    matches!(
+        c,
+        '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE58}' | '\u{FE63}' | '\u{FF0D}'
+    )
+}
+
+/// Whether every `(` in `text` is closed by a later `)` and every `)` closes one.
+fn balanced_parentheses(text: &str) -> bool {
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(open) => depth = open,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+    depth == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    // Plain DOIs and the ways parsers see them written
+    #[case("10.1000/xyz123", true, true, true)]
+    #[case("10.1038/nature12373", true, true, true)]
+    #[case("10.5555/JCCR.2021.045", true, true, true)]
+    #[case("  10.1000/xyz123  ", true, true, true)]
+    #[case("https://doi.org/10.1000/xyz123", true, true, true)]
+    #[case("HTTP://DX.DOI.ORG/10.1000/xyz123", true, true, true)]
+    #[case("doi: 10.1000/xyz123", true, true, true)]
+    #[case("10.1000/xyz123 [doi]", true, true, true)]
+    // Balanced parentheses and other punctuation in the suffix
+    #[case(
+        "10.1002/(SICI)1097-4636(199604)30:4<1::AID-JBM1>3.0.CO;2-M",
+        true,
+        true,
+        true
+    )]
+    #[case("10.1016/S0140-6736(20)30183-5", true, true, true)]
+    #[case("10.1000/abc.", true, true, true)]
+    // Suffixes of digits need six of them
+    #[case("10.1000/123456", true, true, true)]
+    #[case("10.1000/12345", true, false, false)]
+    #[case("10.1000/a1", true, false, false)]
+    #[case("10.1000/ab1", true, true, true)]
+    // Registrant codes
+    #[case("10.123/abcdef", true, false, false)]
+    #[case("10.123456789/abcdef", true, true, false)]
+    #[case("10.1234567890/abcdef", true, false, false)]
+    #[case("10.0999/abcdef", true, true, false)]
+    #[case("10.99999/abcdef", true, true, true)]
+    #[case("10.100000/abcdef", true, true, false)]
+    #[case("10.12a4/abcdef", true, false, false)]
+    #[case("10.1000.10/abcdef", true, false, false)]
+    // Free text that happens to contain "10."
+    #[case("10.5 mg", true, false, false)]
+    #[case("dose 10.5 mg/kg daily", true, false, false)]
+    #[case("10.1000", true, false, false)]
+    #[case("10.1000/", true, false, false)]
+    #[case("10.1000/ab cd", true, false, false)]
+    #[case("10.1000/ab\tcd", true, false, false)]
+    // Citation-style copy-paste
+    #[case("10.1000/xyz123)", true, false, false)]
+    #[case("(10.1000/xyz123)", true, false, false)]
+    #[case("10.1016/S0140-6736(20)30183-5)", true, false, false)]
+    #[case("10.1016/S0140-6736)20(30183-5", true, false, false)]
+    #[case("10.1016/S0140\u{2013}6736(20)30183-5", true, false, false)]
+    #[case("10.1000/abc\u{2010}def", true, false, false)]
+    #[case("10.1000/abc\u{2212}def", true, false, false)]
+    #[case("10.1000/abc\u{00a0}def", true, false, false)]
+    // Not DOIs at all
+    #[case("", false, false, false)]
+    #[case("not a doi", false, false, false)]
+    #[case("11.1000/xyz123", false, false, false)]
+    #[case("https://example.org/10.1000/xyz123", true, false, false)]
+    fn test_validate(
+        #[case] doi: &str,
+        #[case] lenient: bool,
+        #[case] strict: bool,
+        #[case] checked: bool,
+    ) {
+        assert_eq!(validate(doi, DoiValidation::Lenient), lenient, "lenient");
+        assert_eq!(validate(doi, DoiValidation::Strict), strict, "strict");
+        assert_eq!(validate(doi, DoiValidation::Checked), checked, "checked");
+    }
+
+    #[test]
+    fn test_extract() {
+        let strict = DoiValidation::Strict;
+        assert_eq!(
+            extract("https://doi.org/10.5555/JCCR.2021.045", strict).as_deref(),
+            Some("10.5555/jccr.2021.045")
+        );
+        assert_eq!(
+            extract("https://dx.doi.org/10.1000/xyz123.", strict).as_deref(),
+            Some("10.1000/xyz123")
+        );
+        assert_eq!(extract("https://doi.org/10.1000/xyz123)", strict), None);
+        assert_eq!(extract("https://doi.org/10.5", strict), None);
+        assert_eq!(
+            extract("10.5 mg", DoiValidation::Lenient).as_deref(),
+            Some("10.5mg")
+        );
+    }
+}
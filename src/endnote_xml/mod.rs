@@ -3,6 +3,16 @@
 //! Provides functionality to parse EndNote XML formatted citations with improved structure
 //! and error handling. EndNote XML is an export format from EndNote reference management software.
 //!
+//! Several exports concatenated into one file, each with its own XML declaration
+//! and `<xml>` root, are read as one document: records from every part are
+//! returned in file order.
+//!
+//! The EndNote library and application each record was exported from, given by
+//! its `<database>` and `<source-app>` elements, are kept in `extra_fields`
+//! under [`DATABASE_FIELD`], [`DATABASE_PATH_FIELD`], [`SOURCE_APP_FIELD`] and
+//! [`SOURCE_APP_VERSION_FIELD`], and written back by [`EndNoteXmlWriter`].
+//! Records of older exports, without these elements, have none of the keys.
+//!
 //! # Example
 //!
 //! ```
@@ -47,17 +57,66 @@
 //! ```
 
 mod parse;
+mod write;
 
 use crate::error::ParseError;
-use crate::{Citation, CitationParser};
-use parse::parse_endnote_xml;
+use crate::{Citation, CitationFormat, CitationParser, LenientOptions, LenientParse};
+use serde::{Deserialize, Serialize};
+pub(crate) use parse::endnote_xml_coverage;
+use parse::{DEFAULT_MAX_DEPTH, endnote_xml_diagnostics, parse_endnote_xml_into};
+pub use write::EndNoteXmlWriter;
+
+/// [`Citation::extra_fields`] key holding the name of the EndNote library a
+/// record was exported from, e.g. `["My EndNote Library.enl"]`, from the
+/// record's `<database>` element.
+pub const DATABASE_FIELD: &str = "endnote_database";
+
+/// [`Citation::extra_fields`] key holding the file path of the EndNote library
+/// a record was exported from, from the `path` attribute of `<database>`.
+pub const DATABASE_PATH_FIELD: &str = "endnote_database_path";
+
+/// [`Citation::extra_fields`] key holding the application a record was
+/// exported by, e.g. `["EndNote"]`, from the record's `<source-app>` element.
+pub const SOURCE_APP_FIELD: &str = "source_app";
+
+/// [`Citation::extra_fields`] key holding the version of the application a
+/// record was exported by, e.g. `["20.1"]`, from the `version` attribute of
+/// `<source-app>`.
+pub const SOURCE_APP_VERSION_FIELD: &str = "source_app_version";
 
 /// Parser for EndNote XML format citations.
 ///
 /// EndNote XML is an export format from EndNote reference management software
 /// that stores bibliographic data in a structured XML format.
-#[derive(Debug, Clone, Default)]
-pub struct EndNoteXmlParser;
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndNoteXmlParser {
+    dedupe_keywords: bool,
+    max_abstract_length: Option<usize>,
+    max_authors: Option<usize>,
+    stamp_ingestion_time: bool,
+    fold_typography: bool,
+    sentence_case_titles: bool,
+    raw_abstracts: bool,
+    max_depth: usize,
+    archival: bool,
+}
+
+impl Default for EndNoteXmlParser {
+    fn default() -> Self {
+        Self {
+            dedupe_keywords: false,
+            max_abstract_length: None,
+            max_authors: None,
+            stamp_ingestion_time: false,
+            fold_typography: false,
+            sentence_case_titles: false,
+            raw_abstracts: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            archival: false,
+        }
+    }
+}
 
 impl EndNoteXmlParser {
     /// Creates a new EndNote XML parser instance.
@@ -70,7 +129,158 @@ impl EndNoteXmlParser {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Sets whether to remove case-insensitive duplicate keywords from each citation.
+    ///
+    /// See [`Citation::dedupe_keywords`]. Disabled by default.
+    pub fn set_dedupe_keywords(&mut self, enabled: bool) -> &mut Self {
+        self.dedupe_keywords = enabled;
+        self
+    }
+
+    /// Sets the maximum abstract length in characters.
+    ///
+    /// Longer abstracts are shortened with [`Citation::truncate_abstract`] and
+    /// marked with [`crate::ABSTRACT_TRUNCATED_FIELD`]. `None`, the default,
+    /// keeps abstracts in full.
+    pub fn set_max_abstract_length(&mut self, max_chars: Option<usize>) -> &mut Self {
+        self.max_abstract_length = max_chars;
+        self
+    }
+
+    /// Sets the maximum number of authors kept per citation.
+    ///
+    /// Longer author lists, such as those of consortium papers, are shortened
+    /// with [`Citation::truncate_authors`], which records the full count in
+    /// `author_count` and marks the citation with
+    /// [`crate::AUTHORS_TRUNCATED_FIELD`]. `None`, the default, keeps all authors.
+    pub fn set_max_authors(&mut self, max_authors: Option<usize>) -> &mut Self {
+        self.max_authors = max_authors;
+        self
+    }
+
+    /// Sets whether each citation records when it was parsed in `ingested_at`.
+    ///
+    /// All citations of one parse get the same UTC time, as RFC 3339. Disabled by
+    /// default, so parsing the same input always gives the same output.
+    pub fn set_stamp_ingestion_time(&mut self, enabled: bool) -> &mut Self {
+        self.stamp_ingestion_time = enabled;
+        self
+    }
+
+    /// Sets whether typographic characters in the text fields are replaced with
+    /// ASCII equivalents.
+    ///
+    /// See [`Citation::fold_typography`]. Disabled by default, so stored fields
+    /// keep the characters of the source.
+    pub fn set_fold_typography(&mut self, enabled: bool) -> &mut Self {
+        self.fold_typography = enabled;
+        self
+    }
+
+    /// Sets whether all-caps titles are converted to sentence case.
+    ///
+    /// See [`Citation::sentence_case_title`]. Disabled by default, so titles
+    /// keep the case of the source.
+    pub fn set_sentence_case_titles(&mut self, enabled: bool) -> &mut Self {
+        self.sentence_case_titles = enabled;
+        self
+    }
+
+    /// Sets whether abstracts are cleaned of format-specific line breaks and
+    /// spacing.
+    ///
+    /// See [`Citation::clean_abstract`]. Enabled by default; disable it to keep
+    /// abstracts exactly as written in the source.
+    pub fn set_clean_abstracts(&mut self, enabled: bool) -> &mut Self {
+        self.raw_abstracts = !enabled;
+        self
+    }
+
+    /// Sets whether values are stored exactly as the file gives them.
+    ///
+    /// Archival mode is for collections that must keep what the file said. It
+    /// disables the normalization this parser applies by default:
+    ///
+    /// - `<pages>` is stored without completing a shortened end page
+    /// - `<electronic-resource-num>` is the DOI as written, without removing a
+    ///   resolver prefix or stray text, and no DOI is taken from `<url>`
+    /// - the `<year>` and `<pub-dates>` text read into `date` is also kept in
+    ///   `extra_fields` under [`crate::RAW_FIELD_PREFIX`], as "raw:year" and
+    ///   "raw:pub-dates"
+    /// - abstracts are not cleaned, whatever
+    ///   [`EndNoteXmlParser::set_clean_abstracts`] says
+    ///
+    /// Options that are disabled by default, such as typography folding, still
+    /// apply when enabled. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, EndNoteXmlParser};
+    ///
+    /// let xml = "<xml><records><record><titles><title>Example</title></titles>\
+    ///            <pages>1234-45</pages><dates><year> 2023</year></dates>\
+    ///            <electronic-resource-num>DOI: 10.1000/xyz</electronic-resource-num>\
+    ///            </record></records></xml>";
+    /// let mut parser = EndNoteXmlParser::new();
+    /// parser.set_archival(true);
+    /// let citation = &parser.parse(xml).unwrap()[0];
+    /// assert_eq!(citation.pages.as_deref(), Some("1234-45"));
+    /// assert_eq!(citation.doi.as_deref(), Some("DOI: 10.1000/xyz"));
+    /// assert_eq!(citation.extra_fields["raw:year"], vec!["2023"]);
+    /// ```
+    pub fn set_archival(&mut self, enabled: bool) -> &mut Self {
+        self.archival = enabled;
+        self
+    }
+
+    /// Sets the maximum nesting depth of elements within a record.
+    ///
+    /// EndNote nests fields a few elements deep, e.g.
+    /// `<titles><title><style>`. A record nesting deeper, as in a corrupt or
+    /// crafted file, fails with an error naming the depth and line, and lenient
+    /// parsing skips it. Defaults to 64.
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Parses `input` into `out`, as [`CitationParser::parse_into`] when `errors`
+    /// is `None`; otherwise records that fail to convert are pushed to `errors`.
+    fn parse_records(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        crate::utils::check_format(input, CitationFormat::EndNoteXml)?;
+        let count = parse_endnote_xml_into(input, out, errors, self.max_depth, self.archival)?;
+        crate::utils::set_parsed_from_of_last(out, count, CitationFormat::EndNoteXml);
+        if self.dedupe_keywords {
+            crate::utils::dedupe_keywords_of_last(out, count);
+        }
+        if self.fold_typography {
+            crate::utils::fold_typography_of_last(out, count);
+        }
+        if self.sentence_case_titles {
+            crate::utils::sentence_case_titles_of_last(out, count);
+        }
+        if !self.raw_abstracts && !self.archival {
+            crate::utils::clean_abstracts_of_last(out, count);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            crate::utils::truncate_abstracts_of_last(out, count, max_chars);
+        }
+        if let Some(max_authors) = self.max_authors {
+            crate::utils::truncate_authors_of_last(out, count, max_authors);
+        }
+        if self.stamp_ingestion_time {
+            crate::utils::stamp_ingestion_time_of_last(out, count);
+        }
+        Ok(count)
     }
 }
 
@@ -105,12 +315,73 @@ impl CitationParser for EndNoteXmlParser {
             return Ok(Vec::new());
         }
 
-        parse_endnote_xml(input)
+        crate::utils::check_format(input, CitationFormat::EndNoteXml)?;
+        let mut citations = Vec::new();
+        let count =
+            parse_endnote_xml_into(input, &mut citations, None, self.max_depth, self.archival)?;
+        crate::utils::set_parsed_from_of_last(&mut citations, count, CitationFormat::EndNoteXml);
+        if self.dedupe_keywords {
+            citations.iter_mut().for_each(Citation::dedupe_keywords);
+        }
+        if self.fold_typography {
+            citations.iter_mut().for_each(Citation::fold_typography);
+        }
+        if self.sentence_case_titles {
+            for citation in &mut citations {
+                citation.sentence_case_title();
+            }
+        }
+        if !self.raw_abstracts && !self.archival {
+            citations.iter_mut().for_each(Citation::clean_abstract);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            for citation in &mut citations {
+                citation.truncate_abstract(max_chars);
+            }
+        }
+        if let Some(max_authors) = self.max_authors {
+            for citation in &mut citations {
+                citation.truncate_authors(max_authors);
+            }
+        }
+        if self.stamp_ingestion_time {
+            let count = citations.len();
+            crate::utils::stamp_ingestion_time_of_last(&mut citations, count);
+        }
+        Ok(citations)
+    }
+
+    /// Parse EndNote XML content, appending the citations to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the XML is malformed; `out` is left unchanged
+    fn parse_into(&self, input: &str, out: &mut Vec<Citation>) -> Result<usize, ParseError> {
+        self.parse_records(input, out, None)
+    }
+
+    /// Parses EndNote XML content, skipping records that fail to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed or the failures exceed `options`
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> Result<LenientParse, ParseError> {
+        let mut citations = Vec::new();
+        let mut errors = Vec::new();
+        self.parse_records(input, &mut citations, Some(&mut errors))?;
+        let mut result = options.check(citations, errors, endnote_xml_coverage(input).unparsed)?;
+        result.diagnostics.extend(endnote_xml_diagnostics(input));
+        Ok(result)
     }
 }
 
 #[cfg(test)]
 mod integration_tests {
+    use super::parse::parse_endnote_xml;
     use super::*;
 
     #[test]
@@ -245,6 +516,28 @@ mod integration_tests {
         assert_eq!(citations[0].title, "Minimal Citation");
     }
 
+    #[test]
+    fn test_doi_from_url_must_be_strictly_valid() {
+        let record = |url: &str| {
+            format!(
+                "<record><titles><title>Linked</title></titles>\
+                 <urls><related-urls><url>{url}</url></related-urls></urls></record>"
+            )
+        };
+        let xml = format!(
+            "<xml><records>{}{}</records></xml>",
+            record("https://doi.org/10.1016/S0140-6736(20)30183-5"),
+            record("https://doi.org/10.1016/S0140-6736(20)30183-5)")
+        );
+
+        let citations = parse_endnote_xml(&xml).unwrap();
+        assert_eq!(
+            citations[0].doi.as_deref(),
+            Some("10.1016/s0140-6736(20)30183-5")
+        );
+        assert_eq!(citations[1].doi, None);
+    }
+
     #[test]
     fn test_author_only_citation() {
         let xml = r#"
@@ -269,6 +562,18 @@ mod integration_tests {
         assert_eq!(citations[0].title, ""); // Empty title since none provided
     }
 
+    #[test]
+    fn test_placeholder_authors() {
+        let xml = r#"<xml><records><record>
+            <contributors><authors><author>Anonymous</author><author></author></authors></contributors>
+            <titles><title>Editorial</title></titles>
+        </record></records></xml>"#;
+
+        let citations = parse_endnote_xml(xml).unwrap();
+        assert!(citations[0].authors.is_empty());
+        assert_eq!(citations[0].extra_fields[crate::ANONYMOUS_FIELD], vec!["true"]);
+    }
+
     #[test]
     fn test_complex_nesting() {
         let xml = r#"
@@ -310,6 +615,14 @@ mod integration_tests {
         assert_eq!(citation.keywords, vec!["keyword1", "keyword2"]);
     }
 
+    #[test]
+    fn test_style_runs_keep_spacing() {
+        let xml = r#"<xml><records><record><titles><title><style face="normal" font="default" size="100%">Effects of </style><style face="italic" font="default" size="100%">Escherichia coli</style><style face="normal" font="default" size="100%"> on gut health</style></title></titles></record></records></xml>"#;
+
+        let citations = parse_endnote_xml(xml).unwrap();
+        assert_eq!(citations[0].title, "Effects of Escherichia coli on gut health");
+    }
+
     #[test]
     fn test_malformed_xml_error() {
         let xml = r#"
@@ -431,6 +744,83 @@ mod integration_tests {
         // This should produce an error with line number information
     }
 
+    #[test]
+    fn test_error_position_inside_multi_byte_character() {
+        // The reader can stop inside the byte order mark of unreadable text
+        let xml = "\u{feff}Y <record> - JOUR\nTI  - x\nPY١  - 2😀020///  - 1\n";
+        let err = parse_endnote_xml(xml).unwrap_err();
+        assert_eq!(err.line, Some(1));
+    }
+
+    #[test]
+    fn test_pub_dates_range() {
+        let xml = r#"<xml><records>
+            <record><titles><title>Quarterly</title></titles>
+              <dates><year>2019</year><pub-dates><date>Nov-Jan</date></pub-dates></dates></record>
+            <record><titles><title>Dated</title></titles>
+              <dates><year>2021</year><pub-dates><date>Mar 15</date></pub-dates></dates></record>
+        </records></xml>"#;
+
+        let citations = parse_endnote_xml(xml).unwrap();
+
+        let start = citations[0].date.as_ref().unwrap();
+        assert_eq!((start.year, start.month), (2019, Some(11)));
+        let end = citations[0].date_end.as_ref().unwrap();
+        assert_eq!((end.year, end.month), (2020, Some(1)));
+
+        let date = citations[1].date.as_ref().unwrap();
+        assert_eq!((date.month, date.day), (Some(3), Some(15)));
+        assert_eq!(citations[1].date_end, None);
+    }
+
+    #[test]
+    fn test_notes_and_research_notes() {
+        let xml = r#"<xml><records>
+            <record><titles><title>Noted</title></titles>
+              <research-notes><style face="normal" font="default" size="100%">Check the CONSORT diagram</style></research-notes>
+              <notes>Screening: include</notes><notes> </notes></record>
+            <record><titles><title>Plain</title></titles></record>
+        </records></xml>"#;
+
+        let citations = parse_endnote_xml(xml).unwrap();
+        assert_eq!(
+            citations[0].notes.as_deref(),
+            Some("Check the CONSORT diagram\n\nScreening: include")
+        );
+        assert_eq!(citations[1].notes, None);
+    }
+
+    #[test]
+    fn test_ref_type_name_and_code() {
+        let xml = r#"<xml><records>
+            <record><ref-type name="Book Section"/><titles><title>Name only</title></titles></record>
+            <record><ref-type>32</ref-type><titles><title>Code only</title></titles></record>
+            <record><ref-type>99</ref-type><titles><title>Unknown code</title></titles></record>
+            <record><ref-type name="Journal Article">17</ref-type><titles><title>Both</title></titles></record>
+            <record><ref-type name="Report">13</ref-type><titles><title>Disagree</title></titles></record>
+        </records></xml>"#;
+
+        let citations = parse_endnote_xml(xml).unwrap();
+        let types: Vec<_> = citations
+            .iter()
+            .map(|c| c.citation_type.join(";"))
+            .collect();
+        assert_eq!(
+            types,
+            ["Book Section", "Thesis", "Other(99)", "Journal Article", "Report"]
+        );
+
+        let codes: Vec<_> = citations
+            .iter()
+            .map(|c| c.extra_fields.get("ref-type").cloned())
+            .collect();
+        assert_eq!(codes[0], None);
+        assert_eq!(codes[1], Some(vec!["32".to_string()]));
+        assert_eq!(codes[2], Some(vec!["99".to_string()]));
+        assert_eq!(codes[3], Some(vec!["17".to_string()]));
+        assert_eq!(codes[4], Some(vec!["13".to_string()]));
+    }
+
     #[test]
     fn test_detailed_line_tracking() {
         // XML with specific content to test line tracking precision
@@ -455,7 +845,7 @@ mod integration_tests {
             println!("Error at line {}: {}", line, parse_error.error);
             // The empty record starts around line 4, buffer position captured earlier
             assert!(
-                line >= 3 && line <= 7,
+                (3..=7).contains(&line),
                 "Line number should be around line 3-7, got {}",
                 line
             );
@@ -496,4 +886,148 @@ mod integration_tests {
         let result = parser.parse(xml).unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn test_parse_into_appends() {
+        let xml = "<xml><records>\
+            <record><titles><title>First</title></titles></record>\
+            <record><titles><title>Second</title></titles></record>\
+            </records></xml>";
+        let parser = EndNoteXmlParser::new();
+        let mut citations = parser
+            .parse("<xml><records><record><titles><title>Existing</title></titles></record></records></xml>")
+            .unwrap();
+
+        let count = parser.parse_into(xml, &mut citations).unwrap();
+        assert_eq!(count, parser.parse(xml).unwrap().len());
+        assert_eq!(count, 2);
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].title, "Existing");
+
+        // The first record is read before the second one fails validation
+        let invalid = "<xml><records>\
+            <record><titles><title>Valid</title></titles></record>\
+            <record><volume>1</volume></record>\
+            </records></xml>";
+        assert!(parser.parse_into(invalid, &mut citations).is_err());
+        assert_eq!(citations.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let xml = "<xml><records>\
+            <record><titles><title>Kept</title></titles></record>\
+            <record><volume>1</volume></record>\
+            </records></xml>";
+        let parser = EndNoteXmlParser::new();
+
+        let result = parser
+            .parse_lenient(xml, crate::LenientOptions::default())
+            .unwrap();
+        assert_eq!(result.citations[0].title, "Kept");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.stats.attempted, 2);
+
+        // Malformed XML still fails the whole parse
+        let malformed = "<xml><records><record><titles><title>Cut</title></records>";
+        assert!(
+            parser
+                .parse_lenient(malformed, crate::LenientOptions::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_max_abstract_length() {
+        let xml = "<xml><records><record><titles><title>Long</title></titles>\
+            <abstract>Éléments prognostiques</abstract></record></records></xml>";
+
+        let mut parser = EndNoteXmlParser::new();
+        parser.set_max_abstract_length(Some(2));
+        let mut citations = parser.parse(xml).unwrap();
+        assert_eq!(citations[0].abstract_text.as_deref(), Some("Él…"));
+        assert_eq!(citations[0].extra_fields[crate::ABSTRACT_TRUNCATED_FIELD], vec!["true"]);
+
+        parser.parse_into(xml, &mut citations).unwrap();
+        assert_eq!(citations[1].abstract_text.as_deref(), Some("Él…"));
+    }
+
+    #[test]
+    fn test_conference_metadata() {
+        use crate::CitationWriter;
+
+        let xml = r#"<xml><records>
+            <record><ref-type name="Conference Proceedings">10</ref-type>
+            <contributors><authors><author>Vaswani, Ashish</author></authors></contributors>
+            <titles><title>Attention is all you need</title>
+            <secondary-title>Advances in Neural Information Processing Systems</secondary-title></titles>
+            <dates><year>2017</year><pub-dates><date>4-9 December 2017</date></pub-dates></dates>
+            <pub-location>Long Beach, CA, USA</pub-location></record>
+            <record><ref-type name="Book">6</ref-type><titles><title>A book</title></titles>
+            <pub-location>Oxford</pub-location></record>
+            </records></xml>"#;
+        let citations = EndNoteXmlParser::new().parse(xml).unwrap();
+
+        let conference = citations[0].conference.as_ref().unwrap();
+        assert_eq!(
+            conference.name.as_deref(),
+            Some("Advances in Neural Information Processing Systems")
+        );
+        assert_eq!(conference.location.as_deref(), Some("Long Beach, CA, USA"));
+        assert_eq!(conference.date.as_deref(), Some("4-9 December 2017"));
+
+        assert!(citations[1].conference.is_none());
+        assert_eq!(citations[1].extra_fields["pub-location"], vec!["Oxford"]);
+
+        let written = crate::EndNoteXmlWriter::new()
+            .write_to_string(&citations)
+            .unwrap();
+        let reparsed = EndNoteXmlParser::new().parse(&written).unwrap();
+        assert_eq!(reparsed[0].conference, citations[0].conference);
+        assert_eq!(reparsed[1].extra_fields["pub-location"], vec!["Oxford"]);
+    }
+
+    #[test]
+    fn test_library_and_application() {
+        use crate::CitationWriter;
+
+        let xml = r#"<xml><records>
+            <record><database name="Team &amp; Co.enl" path="/srv/Team.enl">Team.enl</database>
+            <source-app name="EndNote" version="20.1">EndNote</source-app>
+            <titles><title>With attributes</title></titles></record>
+            <record><database name="Shared.enl"/><source-app>EndNote</source-app>
+            <titles><title>Self-closing and text only</title></titles></record>
+            <record><database>  </database><titles><title>Blank</title></titles></record>
+            </records></xml>"#;
+        let citations = EndNoteXmlParser::new().parse(xml).unwrap();
+        let field = |index: usize, key: &str| {
+            let values = citations[index].extra_fields.get(key);
+            values.map(|values| values[0].as_str())
+        };
+
+        assert_eq!(field(0, DATABASE_FIELD), Some("Team & Co.enl"));
+        assert_eq!(field(0, DATABASE_PATH_FIELD), Some("/srv/Team.enl"));
+        assert_eq!(field(0, SOURCE_APP_FIELD), Some("EndNote"));
+        assert_eq!(field(0, SOURCE_APP_VERSION_FIELD), Some("20.1"));
+        assert_eq!(field(1, DATABASE_FIELD), Some("Shared.enl"));
+        assert_eq!(field(1, DATABASE_PATH_FIELD), None);
+        assert_eq!(field(1, SOURCE_APP_FIELD), Some("EndNote"));
+        assert_eq!(field(1, SOURCE_APP_VERSION_FIELD), None);
+        assert!(citations[2].extra_fields.is_empty());
+
+        let written = crate::EndNoteXmlWriter::new()
+            .write_to_string(&citations)
+            .unwrap();
+        assert!(written.contains(
+            r#"<record><database name="Team &amp; Co.enl" path="/srv/Team.enl">Team &amp; Co.enl"#
+        ));
+        let reparsed = EndNoteXmlParser::new().parse(&written).unwrap();
+        let provenance = |citation: &Citation| {
+            [DATABASE_FIELD, DATABASE_PATH_FIELD, SOURCE_APP_FIELD, SOURCE_APP_VERSION_FIELD]
+                .map(|key| citation.extra_fields.get(key).cloned())
+        };
+        for (reparsed, citation) in reparsed.iter().zip(&citations) {
+            assert_eq!(provenance(reparsed), provenance(citation));
+        }
+    }
 }
@@ -2,8 +2,13 @@
 //!
 //! This module provides the core parsing logic for EndNote XML format.
 
+use super::{DATABASE_FIELD, DATABASE_PATH_FIELD, SOURCE_APP_FIELD, SOURCE_APP_VERSION_FIELD};
 use crate::error::{ParseError, ValueError};
-use crate::{Author, Citation, CitationFormat};
+use crate::utils::Coverage;
+use crate::{
+    Author, Citation, CitationFormat, Contributor, ContributorRole, Diagnostic, Diagnostics,
+    LinkKind, NameOrder,
+};
 use quick_xml::Reader;
 use quick_xml::events::Event;
 use quick_xml::name::QName;
@@ -14,13 +19,74 @@ fn buffer_position_to_line_number(content: &str, pos: usize) -> usize {
     if pos >= content.len() {
         return content.lines().count();
     }
-    content[..pos].lines().count()
+    // The position may fall inside a multi-byte character, so count by bytes
+    let before = &content.as_bytes()[..pos];
+    let newlines = before.iter().filter(|&&b| b == b'\n').count();
+    newlines + usize::from(!before.is_empty() && !before.ends_with(b"\n"))
+}
+
+/// Default maximum nesting depth of elements within a record.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// Tracks how deeply elements are nested within the record being read, so a
+/// corrupt or crafted file cannot nest elements without bound.
+pub(crate) struct Nesting<'a> {
+    content: &'a str,
+    max_depth: usize,
+    depth: usize,
+    exceeded: bool,
+}
+
+impl<'a> Nesting<'a> {
+    /// Starts tracking at the start of a record in `content`.
+    fn new(content: &'a str, max_depth: usize) -> Self {
+        Self {
+            content,
+            max_depth,
+            depth: 0,
+            exceeded: false,
+        }
+    }
+
+    /// Reads the next event, failing once elements nest deeper than the maximum.
+    ///
+    /// Every event of a record is read through here, whichever loop handles it,
+    /// so the depth stays exact.
+    fn read_event<'b, B: BufRead>(
+        &mut self,
+        reader: &mut Reader<B>,
+        buf: &'b mut Vec<u8>,
+    ) -> Result<quick_xml::Result<Event<'b>>, ParseError> {
+        let event = reader.read_event_into(buf);
+        match &event {
+            Ok(Event::Start(_)) => {
+                self.depth += 1;
+                if self.depth > self.max_depth {
+                    self.exceeded = true;
+                    let line_num =
+                        buffer_position_to_line_number(self.content, reader.buffer_position() as usize);
+                    return Err(ParseError::at_line(
+                        line_num,
+                        CitationFormat::EndNoteXml,
+                        ValueError::Syntax(format!(
+                            "Elements nested more than {} deep in a record",
+                            self.max_depth
+                        )),
+                    ));
+                }
+            }
+            Ok(Event::End(_)) => self.depth = self.depth.saturating_sub(1),
+            _ => (),
+        }
+        Ok(event)
+    }
 }
 
 /// Enhanced extract_text function that tracks line numbers for better error reporting
 fn extract_text_with_position<B: BufRead>(
     reader: &mut Reader<B>,
     buf: &mut Vec<u8>,
+    nesting: &mut Nesting,
     closing_tag: &[u8],
     content: &str,
     _start_pos: usize,
@@ -30,7 +96,7 @@ fn extract_text_with_position<B: BufRead>(
 
     loop {
         let current_pos = reader.buffer_position() as usize;
-        match reader.read_event_into(buf) {
+        match nesting.read_event(reader, buf)? {
             Ok(Event::Text(e)) => {
                 text.push_str(&e.unescape().map_err(|e| {
                     let line_num = buffer_position_to_line_number(content, current_pos);
@@ -69,6 +135,31 @@ fn extract_text_with_position<B: BufRead>(
     Ok(text.trim().to_string())
 }
 
+/// Count the records of an EndNote XML document. XML has no unread lines: the
+/// parser either reads a document or rejects it.
+pub(crate) fn endnote_xml_coverage(content: &str) -> Coverage {
+    Coverage {
+        lines: content.lines().filter(|line| !line.trim().is_empty()).count(),
+        unparsed: Vec::new(),
+        records: content.matches("<record>").count() + content.matches("<record ").count(),
+    }
+}
+
+/// Report each `<xml>` root after the first, which starts another export of a
+/// file made by concatenating several.
+pub(crate) fn endnote_xml_diagnostics(content: &str) -> Diagnostics {
+    let mut roots: Vec<usize> = ["<xml>", "<xml "]
+        .iter()
+        .flat_map(|root| content.match_indices(root).map(|(pos, _)| pos))
+        .collect();
+    roots.sort_unstable();
+    roots
+        .into_iter()
+        .skip(1)
+        .map(|pos| Diagnostic::documents_concatenated(buffer_position_to_line_number(content, pos)))
+        .collect()
+}
+
 /// Parse EndNote XML content into citations.
 ///
 /// This function parses EndNote XML format and returns a vector of citations.
@@ -81,24 +172,81 @@ fn extract_text_with_position<B: BufRead>(
 /// # Returns
 ///
 /// A Result containing either a vector of citations or a parsing error.
-///
-/// ```
+#[cfg(test)]
 pub(crate) fn parse_endnote_xml(content: &str) -> Result<Vec<Citation>, ParseError> {
+    let mut citations = Vec::new();
+    parse_endnote_xml_into(content, &mut citations, None, DEFAULT_MAX_DEPTH, false)?;
+    Ok(citations)
+}
+
+/// Parse EndNote XML content, appending the citations to `out`.
+///
+/// Records are pushed as they are read. If a later record fails to parse, the
+/// citations appended by this call are removed again so `out` is unchanged.
+/// When `errors` is given, records that fail are pushed to it instead, and only
+/// malformed XML fails the call. A record nesting elements deeper than
+/// `max_depth` fails like a record missing its title, and is skipped when
+/// `errors` is given. With `archival`, values are kept as written, see
+/// [`crate::EndNoteXmlParser::set_archival`].
+///
+/// # Returns
+///
+/// A Result containing the number of citations appended or a parsing error.
+pub(crate) fn parse_endnote_xml_into(
+    content: &str,
+    out: &mut Vec<Citation>,
+    errors: Option<&mut Vec<ParseError>>,
+    max_depth: usize,
+    archival: bool,
+) -> Result<usize, ParseError> {
+    let start = out.len();
+    let result = read_records(content, out, errors, max_depth, archival);
+    if result.is_err() {
+        out.truncate(start);
+    }
+    result.map(|()| out.len() - start)
+}
+
+/// Reads every record element in `content` into `citations`.
+fn read_records(
+    content: &str,
+    citations: &mut Vec<Citation>,
+    mut errors: Option<&mut Vec<ParseError>>,
+    max_depth: usize,
+    archival: bool,
+) -> Result<(), ParseError> {
     if content.trim().is_empty() {
-        return Ok(Vec::new());
+        return Ok(());
     }
 
+    // Text is not trimmed per event: EndNote splits formatted fields into
+    // several <style> runs and the whitespace between runs is significant.
+    // Collected field text is trimmed as a whole in extract_text instead.
+    // The reader does not require a single root element, so files made by
+    // concatenating exports are read record by record across every part.
     let mut reader = Reader::from_str(content);
-    reader.config_mut().trim_text(true);
 
-    let mut citations = Vec::new();
     let mut buf = Vec::new();
 
     loop {
         let pos = reader.buffer_position() as usize;
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) if e.name() == QName(b"record") => {
-                citations.push(parse_record(&mut reader, &mut buf, content, pos)?);
+                let mut nesting = Nesting::new(content, max_depth);
+                let citation = match parse_record(&mut reader, &mut buf, &mut nesting, archival) {
+                    Ok(citation) => check_record(citation, content, pos),
+                    Err(e) if nesting.exceeded && errors.is_some() => {
+                        buf.clear();
+                        reader.read_to_end_into(QName(b"record"), &mut buf)?;
+                        Err(e)
+                    }
+                    Err(e) => return Err(e),
+                };
+                match (citation, errors.as_deref_mut()) {
+                    (Ok(citation), _) => citations.push(citation),
+                    (Err(e), Some(errors)) => errors.push(e),
+                    (Err(e), None) => return Err(e),
+                }
             }
             Ok(Event::Eof) => break,
             Err(e) => return Err(ParseError::from(e)),
@@ -107,21 +255,21 @@ pub(crate) fn parse_endnote_xml(content: &str) -> Result<Vec<Citation>, ParseErr
         buf.clear();
     }
 
-    // Return empty vector instead of error for empty but valid XML
-    Ok(citations)
+    Ok(())
 }
 
 /// Extracts text content from XML events until the closing tag is found
 fn extract_text<B: BufRead>(
     reader: &mut Reader<B>,
     buf: &mut Vec<u8>,
+    nesting: &mut Nesting,
     closing_tag: &[u8],
 ) -> Result<String, ParseError> {
     let mut text = String::new();
     let closing_tag_str = String::from_utf8_lossy(closing_tag);
 
     loop {
-        match reader.read_event_into(buf) {
+        match nesting.read_event(reader, buf)? {
             Ok(Event::Text(e)) => {
                 text.push_str(&e.unescape().map_err(|e| {
                     ParseError::without_position(
@@ -149,12 +297,17 @@ fn extract_text<B: BufRead>(
     Ok(text.trim().to_string())
 }
 
-/// Extracts date components (year, month, day) from a year element
+/// Date components (year, month, day) read from a year element
+type DateParts = (Option<i32>, Option<u8>, Option<u8>);
+
+/// Extracts date components (year, month, day) from a year element, with the
+/// element's text as written
 fn extract_date_from_year_element<B: BufRead>(
     reader: &mut Reader<B>,
     e: &quick_xml::events::BytesStart,
     content: &str,
-) -> Result<(Option<i32>, Option<u8>, Option<u8>), ParseError> {
+    nesting: &mut Nesting,
+) -> Result<(DateParts, String), ParseError> {
     let mut year_val = None;
     let mut month_val = None;
     let mut day_val = None;
@@ -193,83 +346,199 @@ fn extract_date_from_year_element<B: BufRead>(
         }
     }
 
+    let mut local_buf = Vec::new();
+    let start_pos = reader.buffer_position() as usize;
+    let text =
+        extract_text_with_position(reader, &mut local_buf, nesting, b"year", content, start_pos)?;
+
     // If no year attribute, try to get year from text content
     if year_val.is_none() {
-        let mut local_buf = Vec::new();
-        let start_pos = reader.buffer_position() as usize;
-        if let Ok(year) =
-            extract_text_with_position(reader, &mut local_buf, b"year", content, start_pos)?
-                .parse::<i32>()
-        {
-            year_val = Some(year);
+        year_val = text.parse::<i32>().ok();
+    }
+
+    Ok(((year_val, month_val, day_val), text))
+}
+
+/// Extra field holding the numeric EndNote reference type code.
+const REF_TYPE_CODE_FIELD: &str = "ref-type";
+
+/// Extra field holding the place published of records other than proceedings.
+pub(super) const PUB_LOCATION_FIELD: &str = "pub-location";
+
+/// Maps an EndNote numeric reference type code to its name.
+///
+/// Covers the types EndNote exports most often; the names match those used by
+/// the EndNote XML writer.
+fn ref_type_name(code: u16) -> Option<&'static str> {
+    let name = match code {
+        5 => "Book Section",
+        6 => "Book",
+        10 => "Conference Proceedings",
+        12 => "Web Page",
+        13 => "Generic",
+        17 => "Journal Article",
+        19 => "Magazine Article",
+        23 => "Newspaper Article",
+        25 => "Patent",
+        27 => "Report",
+        28 => "Edited Book",
+        32 => "Thesis",
+        47 => "Conference Paper",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Reads the `name` attribute of a ref-type element, if present.
+fn ref_type_name_attribute(
+    e: &quick_xml::events::BytesStart,
+) -> Result<Option<String>, ParseError> {
+    attribute(e, b"name")
+}
+
+/// Reads an attribute of an element, if present and not blank.
+fn attribute(
+    e: &quick_xml::events::BytesStart,
+    key: &[u8],
+) -> Result<Option<String>, ParseError> {
+    for attr in e.attributes() {
+        let attr = attr.map_err(|e| {
+            ParseError::without_position(
+                CitationFormat::EndNoteXml,
+                ValueError::Syntax(format!("Invalid attribute: {}", e)),
+            )
+        })?;
+        if attr.key.as_ref() == key {
+            let value = attr.unescape_value().map_err(|e| {
+                ParseError::without_position(
+                    CitationFormat::EndNoteXml,
+                    ValueError::Syntax(format!("Invalid attribute value: {}", e)),
+                )
+            })?;
+            let value = value.trim();
+            if !value.is_empty() {
+                return Ok(Some(value.to_string()));
+            }
         }
-    } else {
-        // Still need to consume the text content
-        let mut local_buf = Vec::new();
-        let start_pos = reader.buffer_position() as usize;
-        let _ = extract_text_with_position(reader, &mut local_buf, b"year", content, start_pos)?;
+    }
+    Ok(None)
+}
+
+/// Keeps the library and application a record was exported from, given by the
+/// attributes of its `<database>` or `<source-app>` element and, for the name,
+/// its text.
+fn push_provenance(
+    citation: &mut Citation,
+    e: &quick_xml::events::BytesStart,
+    text: Option<String>,
+) -> Result<(), ParseError> {
+    let (name_key, detail_key, detail) = match e.name().as_ref() {
+        b"database" => (DATABASE_FIELD, DATABASE_PATH_FIELD, attribute(e, b"path")?),
+        _ => (SOURCE_APP_FIELD, SOURCE_APP_VERSION_FIELD, attribute(e, b"version")?),
+    };
+    let name = attribute(e, b"name")?.or(text.filter(|text| !text.trim().is_empty()));
+    for (key, value) in [(name_key, name), (detail_key, detail)] {
+        if let Some(value) = value {
+            citation.extra_fields.insert(key.to_string(), vec![value]);
+        }
+    }
+    Ok(())
+}
+
+/// Sets the citation type from a ref-type element's name attribute and text.
+///
+/// The name is preferred. Without one, a numeric code is looked up in the
+/// reference type table and unknown codes become `Other(<code>)`. Numeric codes
+/// are always kept in extra_fields under [`REF_TYPE_CODE_FIELD`].
+fn push_ref_type(citation: &mut Citation, name: Option<String>, text: String) {
+    let code = text.parse::<u16>().ok();
+    if code.is_some() {
+        citation
+            .extra_fields
+            .entry(REF_TYPE_CODE_FIELD.to_string())
+            .or_default()
+            .push(text.clone());
     }
 
-    Ok((year_val, month_val, day_val))
+    let citation_type = match (name, code) {
+        (Some(name), _) => name,
+        (None, Some(code)) => ref_type_name(code)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("Other({})", code)),
+        (None, None) if !text.is_empty() => text,
+        (None, None) => return,
+    };
+    citation.citation_type.push(citation_type);
 }
 
 /// Parse a single record element into a Citation
+///
+/// Errors are XML errors that leave the reader unusable; missing fields are
+/// reported by [`check_record`].
 fn parse_record<B: BufRead>(
     reader: &mut Reader<B>,
     buf: &mut Vec<u8>,
-    content: &str,
-    start_pos: usize,
+    nesting: &mut Nesting,
+    archival: bool,
 ) -> Result<Citation, ParseError> {
+    let content = nesting.content;
     let mut citation = Citation::new();
+    let mut year_text = None;
+    let mut anonymous = false;
+    let mut location = None;
+    let mut date_text = None;
+    let mut notes = Vec::new();
+    // Kind of the links in the current `<urls>` group, from its element name
+    let mut url_kind = None;
+    // Role of the people in the current `<contributors>` group
+    let mut author_role = ContributorRole::Author;
 
     loop {
-        match reader.read_event_into(buf) {
+        match nesting.read_event(reader, buf)? {
             Ok(Event::Start(ref e)) => match e.name().as_ref() {
                 b"ref-type" => {
-                    for attr in e.attributes() {
-                        let attr = attr.map_err(|e| {
-                            ParseError::without_position(
-                                CitationFormat::EndNoteXml,
-                                ValueError::Syntax(format!("Invalid attribute: {}", e)),
-                            )
-                        })?;
-                        if attr.key.as_ref() == b"name" {
-                            citation.citation_type.push(
-                                attr.unescape_value()
-                                    .map_err(|e| {
-                                        ParseError::without_position(
-                                            CitationFormat::EndNoteXml,
-                                            ValueError::Syntax(format!(
-                                                "Invalid attribute value: {}",
-                                                e
-                                            )),
-                                        )
-                                    })?
-                                    .into_owned(),
-                            );
-                        }
-                    }
+                    let name = ref_type_name_attribute(e)?;
+                    let code = extract_text(reader, buf, nesting, b"ref-type")?;
+                    push_ref_type(&mut citation, name, code);
                 }
                 b"title" => {
-                    citation.title = extract_text(reader, buf, b"title")?;
+                    citation.title = extract_text(reader, buf, nesting, b"title")?;
                 }
                 b"author" => {
-                    let author_str = extract_text(reader, buf, b"author")?;
-                    let (family, given) = crate::utils::parse_author_name(&author_str);
+                    let author_str = extract_text(reader, buf, nesting, b"author")?;
+                    if crate::utils::is_placeholder_author(&author_str) {
+                        anonymous |=
+                            author_role == ContributorRole::Author && !author_str.is_empty();
+                        buf.clear();
+                        continue;
+                    }
+                    let (family, given, suffix) =
+                        crate::utils::parse_author_name(&author_str, NameOrder::FamilyFirst);
                     let (given_opt, middle_opt) = if given.is_empty() {
                         (None, None)
                     } else {
                         crate::utils::split_given_and_middle(&given)
                     };
-                    citation.authors.push(Author {
+                    let person = Author {
                         name: family,
                         given_name: given_opt,
                         middle_name: middle_opt,
+                        suffix,
                         affiliations: Vec::new(),
-                    });
+                    };
+                    if author_role == ContributorRole::Author {
+                        citation.authors.push(person);
+                    } else {
+                        citation.contributors.push(Contributor::new(person, author_role));
+                    }
+                }
+                b"authors" => author_role = ContributorRole::Author,
+                b"secondary-authors" | b"tertiary-authors" => author_role = ContributorRole::Editor,
+                b"subsidiary-authors" | b"translated-authors" => {
+                    author_role = ContributorRole::Translator;
                 }
                 b"secondary-title" => {
-                    let sec_title = extract_text(reader, buf, b"secondary-title")?;
+                    let sec_title = extract_text(reader, buf, nesting, b"secondary-title")?;
                     // If no primary title, use secondary-title as title
                     if citation.title.is_empty() {
                         citation.title = sec_title;
@@ -278,7 +547,7 @@ fn parse_record<B: BufRead>(
                     }
                 }
                 b"alt-title" => {
-                    let alt_title = extract_text(reader, buf, b"alt-title")?;
+                    let alt_title = extract_text(reader, buf, nesting, b"alt-title")?;
                     // If no primary title or journal is set, use alt-title as title
                     if citation.title.is_empty() && citation.journal.is_none() {
                         citation.title = alt_title;
@@ -289,49 +558,69 @@ fn parse_record<B: BufRead>(
                     }
                 }
                 b"custom2" => {
-                    let text = extract_text(reader, buf, b"custom2")?;
+                    let text = extract_text(reader, buf, nesting, b"custom2")?;
                     // Check for PMC ID patterns
                     if text.to_lowercase().contains("pmc") || text.starts_with("PMC") {
                         citation.pmc_id = Some(text);
                     }
                 }
                 b"volume" => {
-                    citation.volume = Some(extract_text(reader, buf, b"volume")?);
+                    citation.volume = Some(extract_text(reader, buf, nesting, b"volume")?);
                 }
                 b"number" => {
-                    citation.issue = Some(extract_text(reader, buf, b"number")?);
+                    citation.issue = Some(extract_text(reader, buf, nesting, b"number")?);
                 }
                 b"pages" => {
-                    let pages = extract_text(reader, buf, b"pages")?;
-                    citation.pages = Some(crate::utils::format_page_numbers(&pages));
+                    let pages = extract_text(reader, buf, nesting, b"pages")?;
+                    citation.pages = if archival {
+                        Some(pages)
+                    } else {
+                        Some(crate::utils::format_page_numbers(&pages))
+                    };
                 }
                 b"electronic-resource-num" => {
-                    let doi = extract_text(reader, buf, b"electronic-resource-num")?;
-                    citation.doi = crate::utils::format_doi(&doi);
+                    let doi = extract_text(reader, buf, nesting, b"electronic-resource-num")?;
+                    citation.doi = if archival {
+                        Some(doi).filter(|doi| !doi.is_empty())
+                    } else {
+                        crate::utils::format_doi(&doi)
+                    };
                 }
+                b"pdf-urls" => url_kind = Some(LinkKind::Pdf),
+                b"text-urls" => url_kind = Some(LinkKind::FullText),
+                b"related-urls" | b"web-urls" | b"image-urls" => url_kind = None,
                 b"url" => {
-                    let url = extract_text(reader, buf, b"url")?;
-                    if citation.doi.is_none() && url.contains("doi.org") {
-                        citation.doi = crate::utils::format_doi(&url);
+                    let url = extract_text(reader, buf, nesting, b"url")?;
+                    if citation.doi.is_none() && !archival && url.contains("doi.org") {
+                        citation.doi = crate::doi::extract(&url, crate::doi::DoiValidation::Strict);
+                    }
+                    if let Some(kind) = url_kind {
+                        citation.link_kinds.entry(url.clone()).or_insert(kind);
                     }
                     citation.urls.push(url);
                 }
                 b"year" => {
-                    let (year_val, month_val, day_val) =
-                        extract_date_from_year_element(reader, e, content)?;
+                    let ((year_val, month_val, day_val), text) =
+                        extract_date_from_year_element(reader, e, content, nesting)?;
                     citation.date = crate::utils::parse_endnote_date(year_val, month_val, day_val);
+                    year_text = Some(text);
                 }
                 b"dates" => {
                     // Handle the dates element - we'll look for year sub-element
                     // This is a more complex structure but we'll process it
                     loop {
-                        match reader.read_event_into(buf) {
+                        match nesting.read_event(reader, buf)? {
                             Ok(Event::Start(ref inner_e)) if inner_e.name() == QName(b"year") => {
                                 // Parse year element within dates
-                                let (year_val, month_val, day_val) =
-                                    extract_date_from_year_element(reader, inner_e, content)?;
+                                let ((year_val, month_val, day_val), text) =
+                                    extract_date_from_year_element(reader, inner_e, content, nesting)?;
                                 citation.date =
                                     crate::utils::parse_endnote_date(year_val, month_val, day_val);
+                                year_text = Some(text);
+                            }
+                            Ok(Event::Start(ref inner_e)) if inner_e.name() == QName(b"date") => {
+                                // Free text within pub-dates, e.g. "Mar 15" or "Jan-Mar"
+                                date_text = Some(extract_text(reader, buf, nesting, b"date")?);
                             }
                             Ok(Event::End(ref inner_e)) if inner_e.name() == QName(b"dates") => {
                                 break;
@@ -342,27 +631,61 @@ fn parse_record<B: BufRead>(
                         }
                         buf.clear();
                     }
+                    if let Some(date) = citation.date.as_mut()
+                        && let Some(text) = &date_text
+                    {
+                        let (month, day, end) =
+                            crate::utils::parse_month_day_range(date.year, text);
+                        if date.month.is_none() {
+                            date.month = month;
+                            date.day = day;
+                        }
+                        citation.date_end = end;
+                    }
                 }
                 b"abstract" => {
-                    citation.abstract_text = Some(extract_text(reader, buf, b"abstract")?);
+                    citation.abstract_text = Some(extract_text(reader, buf, nesting, b"abstract")?);
+                }
+                b"notes" => {
+                    notes.push(extract_text(reader, buf, nesting, b"notes")?);
+                }
+                b"research-notes" => {
+                    notes.push(extract_text(reader, buf, nesting, b"research-notes")?);
                 }
                 b"keyword" => {
                     citation
                         .keywords
-                        .push(extract_text(reader, buf, b"keyword")?);
+                        .push(extract_text(reader, buf, nesting, b"keyword")?);
                 }
                 b"language" => {
-                    citation.language = Some(extract_text(reader, buf, b"language")?);
+                    citation.language = Some(extract_text(reader, buf, nesting, b"language")?);
                 }
                 b"publisher" => {
-                    citation.publisher = Some(extract_text(reader, buf, b"publisher")?);
+                    citation.publisher = Some(extract_text(reader, buf, nesting, b"publisher")?);
+                }
+                b"pub-location" => {
+                    location = Some(extract_text(reader, buf, nesting, b"pub-location")?);
                 }
                 b"isbn" => {
-                    let issns = extract_text(reader, buf, b"isbn")?;
+                    let issns = extract_text(reader, buf, nesting, b"isbn")?;
                     citation.issn.extend(crate::utils::split_issns(&issns));
                 }
+                b"database" | b"source-app" => {
+                    let e = e.to_owned();
+                    let text = extract_text(reader, buf, nesting, e.name().as_ref())?;
+                    push_provenance(&mut citation, &e, Some(text))?;
+                }
                 _ => (),
             },
+            Ok(Event::Empty(ref e)) if e.name() == QName(b"ref-type") => {
+                let name = ref_type_name_attribute(e)?;
+                push_ref_type(&mut citation, name, String::new());
+            }
+            Ok(Event::Empty(ref e))
+                if matches!(e.name().as_ref(), b"database" | b"source-app") =>
+            {
+                push_provenance(&mut citation, e, None)?;
+            }
             Ok(Event::End(ref e)) if e.name() == QName(b"record") => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(ParseError::from(e)),
@@ -371,7 +694,46 @@ fn parse_record<B: BufRead>(
         buf.clear();
     }
 
-    // Validate that we have at least a title or author
+    crate::utils::mark_anonymous(&mut citation, anonymous);
+    citation.retraction_status = crate::RetractionStatus::from_title(&citation.title);
+
+    // The date is read from the year and pub-dates text, kept as written in
+    // archival mode
+    if archival && citation.date.is_some() {
+        for (element, text) in [("year", year_text), ("pub-dates", date_text.clone())] {
+            if let Some(text) = text.filter(|text| !text.is_empty()) {
+                let key = format!("{}{}", crate::RAW_FIELD_PREFIX, element);
+                citation.extra_fields.insert(key, vec![text]);
+            }
+        }
+    }
+
+    notes.retain(|note| !note.trim().is_empty());
+    citation.notes = (!notes.is_empty()).then(|| notes.join("\n\n"));
+
+    // In proceedings the secondary title, place and date describe the conference
+    if crate::utils::is_conference_type(&citation.citation_type) {
+        let conference = crate::ConferenceInfo {
+            name: citation.journal.clone(),
+            location,
+            date: date_text,
+        };
+        citation.conference = (conference != crate::ConferenceInfo::default()).then_some(conference);
+    } else if let Some(location) = location {
+        citation
+            .extra_fields
+            .insert(PUB_LOCATION_FIELD.to_string(), vec![location]);
+    }
+
+    Ok(citation)
+}
+
+/// Checks that a record read from `start_pos` has at least a title or author.
+fn check_record(
+    citation: Citation,
+    content: &str,
+    start_pos: usize,
+) -> Result<Citation, ParseError> {
     if citation.title.is_empty() && citation.authors.is_empty() {
         let line_num = buffer_position_to_line_number(content, start_pos);
         return Err(ParseError::at_line(
@@ -0,0 +1,489 @@
+//! EndNote XML writer implementation.
+//!
+//! The document header is written before the first record and the footer after
+//! the last, with records streamed in between.
+
+use super::parse::PUB_LOCATION_FIELD;
+use super::{DATABASE_FIELD, DATABASE_PATH_FIELD, SOURCE_APP_FIELD, SOURCE_APP_VERSION_FIELD};
+use crate::{Author, Citation, CitationError, CitationWriter, ContributorRole, LinkKind};
+use crate::writer::sanitize::xml_text;
+use std::io::Write;
+
+/// The `<contributors>` groups written, with the roles of the people in each.
+const CONTRIBUTOR_GROUPS: [(&str, &[ContributorRole]); 3] = [
+    ("authors", &[ContributorRole::Author, ContributorRole::CorporateAuthor]),
+    ("secondary-authors", &[ContributorRole::Editor]),
+    ("subsidiary-authors", &[ContributorRole::Translator]),
+];
+
+const HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" ?><xml><records>"#;
+const FOOTER: &str = "</records></xml>\n";
+
+/// Writer for EndNote XML format citations.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Citation, CitationWriter, EndNoteXmlWriter};
+///
+/// let citation = Citation {
+///     title: "Test Title".to_string(),
+///     ..Default::default()
+/// };
+///
+/// let xml = EndNoteXmlWriter::new().write_to_string([&citation]).unwrap();
+/// assert!(xml.contains("<titles><title>Test Title</title></titles>"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EndNoteXmlWriter;
+
+impl EndNoteXmlWriter {
+    /// Creates a new EndNote XML writer instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::EndNoteXmlWriter;
+    /// let writer = EndNoteXmlWriter::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Writes a single citation as one `<record>` element on its own line.
+    fn write_record<W: Write>(&self, citation: &Citation, w: &mut W) -> std::io::Result<()> {
+        w.write_all(b"<record>")?;
+        write_provenance(w, citation, "database", DATABASE_FIELD, ("path", DATABASE_PATH_FIELD))?;
+        write_provenance(
+            w,
+            citation,
+            "source-app",
+            SOURCE_APP_FIELD,
+            ("version", SOURCE_APP_VERSION_FIELD),
+        )?;
+
+        let (type_name, type_number) = ref_type(citation.citation_type.first());
+        write!(
+            w,
+            r#"<ref-type name="{}">{}</ref-type>"#,
+            xml_text(type_name),
+            type_number
+        )?;
+
+        // EndNote has no group for investigators; corporate authors go with the authors
+        let groups = CONTRIBUTOR_GROUPS.map(|(group, roles)| {
+            let people: Vec<&Author> = roles
+                .iter()
+                .flat_map(|&role| citation.contributors_with_role(role))
+                .collect();
+            (group, people)
+        });
+        if groups.iter().any(|(_, people)| !people.is_empty()) {
+            w.write_all(b"<contributors>")?;
+            for (group, people) in groups.iter().filter(|(_, people)| !people.is_empty()) {
+                write!(w, "<{group}>")?;
+                for person in people {
+                    write_element(w, "author", &crate::utils::format_author_name(person))?;
+                }
+                write!(w, "</{group}>")?;
+            }
+            w.write_all(b"</contributors>")?;
+        }
+
+        w.write_all(b"<titles>")?;
+        write_element(w, "title", &citation.title)?;
+        write_optional_element(w, "secondary-title", citation.journal.as_deref())?;
+        write_optional_element(w, "alt-title", citation.journal_abbr.as_deref())?;
+        w.write_all(b"</titles>")?;
+
+        write_optional_element(w, "pages", citation.pages.as_deref())?;
+        write_optional_element(w, "volume", citation.volume.as_deref())?;
+        write_optional_element(w, "number", citation.issue.as_deref())?;
+
+        if !citation.keywords.is_empty() {
+            w.write_all(b"<keywords>")?;
+            for keyword in &citation.keywords {
+                write_element(w, "keyword", keyword)?;
+            }
+            w.write_all(b"</keywords>")?;
+        }
+
+        if let Some(date) = &citation.date {
+            w.write_all(b"<dates>")?;
+            write_element(w, "year", &date.year.to_string())?;
+            // The conference date stands in for the publication date of proceedings
+            let conference_date = citation.conference.as_ref().and_then(|c| c.date.clone());
+            if let Some(text) = conference_date.or_else(|| {
+                crate::utils::format_month_day_range(date, citation.date_end.as_ref())
+            }) {
+                w.write_all(b"<pub-dates>")?;
+                write_element(w, "date", &text)?;
+                w.write_all(b"</pub-dates>")?;
+            }
+            w.write_all(b"</dates>")?;
+        }
+
+        write_optional_element(w, "publisher", citation.publisher.as_deref())?;
+        let location = match &citation.conference {
+            Some(conference) => conference.location.as_deref(),
+            None => citation
+                .extra_fields
+                .get(PUB_LOCATION_FIELD)
+                .and_then(|values| values.first())
+                .map(String::as_str),
+        };
+        write_optional_element(w, "pub-location", location)?;
+        if !citation.issn.is_empty() {
+            // EndNote separates multiple ISSNs with carriage returns
+            write!(
+                w,
+                "<isbn>{}</isbn>",
+                citation
+                    .issn
+                    .iter()
+                    .map(|issn| xml_text(issn))
+                    .collect::<Vec<_>>()
+                    .join("&#xD;")
+            )?;
+        }
+        write_optional_element(w, "abstract", citation.abstract_text.as_deref())?;
+        write_optional_element(w, "notes", citation.notes.as_deref())?;
+        write_optional_element(w, "custom2", citation.pmc_id.as_deref())?;
+
+        if !citation.urls.is_empty() {
+            w.write_all(b"<urls>")?;
+            for (group, kind) in [
+                ("pdf-urls", Some(LinkKind::Pdf)),
+                ("text-urls", Some(LinkKind::FullText)),
+                ("related-urls", None),
+            ] {
+                let urls: Vec<&String> = citation
+                    .urls
+                    .iter()
+                    .filter(|url| {
+                        let url_kind = citation.link_kinds.get(*url).copied();
+                        match kind {
+                            Some(_) => url_kind == kind,
+                            None => !matches!(url_kind, Some(LinkKind::Pdf | LinkKind::FullText)),
+                        }
+                    })
+                    .collect();
+                if urls.is_empty() {
+                    continue;
+                }
+                write!(w, "<{group}>")?;
+                for url in urls {
+                    write_element(w, "url", url)?;
+                }
+                write!(w, "</{group}>")?;
+            }
+            w.write_all(b"</urls>")?;
+        }
+
+        write_optional_element(w, "electronic-resource-num", citation.doi.as_deref())?;
+        write_optional_element(w, "language", citation.language.as_deref())?;
+
+        w.write_all(b"</record>\n")
+    }
+}
+
+impl CitationWriter for EndNoteXmlWriter {
+    /// Writes citations as an EndNote XML document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CitationError::Io` if the underlying writer fails
+    fn write_to<'a, W: Write>(
+        &self,
+        citations: impl IntoIterator<Item = &'a Citation>,
+        mut writer: W,
+    ) -> Result<(), CitationError> {
+        writeln!(writer, "{}", HEADER)?;
+        for citation in citations {
+            self.write_record(citation, &mut writer)?;
+        }
+        writer.write_all(FOOTER.as_bytes())?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn write_element<W: Write>(w: &mut W, name: &str, text: &str) -> std::io::Result<()> {
+    write!(w, "<{name}>{}</{name}>", xml_text(text))
+}
+
+/// Writes the `<database>` or `<source-app>` element of a record exported from
+/// EndNote, from the name under `name_key` and the attribute under the key
+/// paired with it, when the citation has a name.
+fn write_provenance<W: Write>(
+    w: &mut W,
+    citation: &Citation,
+    element: &str,
+    name_key: &str,
+    (attribute, attribute_key): (&str, &str),
+) -> std::io::Result<()> {
+    let first = |key| citation.extra_fields.get(key).and_then(|values| values.first());
+    let Some(name) = first(name_key) else {
+        return Ok(());
+    };
+    let name = xml_text(name);
+    write!(w, r#"<{element} name="{name}""#)?;
+    if let Some(value) = first(attribute_key) {
+        write!(w, r#" {attribute}="{}""#, xml_text(value))?;
+    }
+    write!(w, ">{name}</{element}>")
+}
+
+fn write_optional_element<W: Write>(
+    w: &mut W,
+    name: &str,
+    text: Option<&str>,
+) -> std::io::Result<()> {
+    match text {
+        Some(text) => write_element(w, name, text),
+        None => Ok(()),
+    }
+}
+
+/// Maps a citation type to an EndNote reference type name and number.
+///
+/// RIS type codes are translated to their EndNote names; other names are kept
+/// and written as the Generic type number.
+fn ref_type(citation_type: Option<&String>) -> (&str, u8) {
+    let Some(citation_type) = citation_type else {
+        return ("Journal Article", 17);
+    };
+
+    match citation_type.as_str() {
+        "JOUR" | "Journal Article" => ("Journal Article", 17),
+        "BOOK" | "Book" => ("Book", 6),
+        "CHAP" | "Book Section" => ("Book Section", 5),
+        "CONF" | "CPAPER" | "Conference Proceedings" => ("Conference Proceedings", 10),
+        "THES" | "Thesis" => ("Thesis", 32),
+        "RPRT" | "Report" => ("Report", 27),
+        "ELEC" | "Web Page" => ("Web Page", 12),
+        other => (other, 13),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CountingWriter;
+    use crate::{Author, CitationParser, Date, EndNoteXmlParser};
+    use pretty_assertions::assert_eq;
+
+    fn synthetic_citation(i: usize) -> Citation {
+        Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: format!("Synthetic <citation> & number {}", i),
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: None,
+                suffix: None,
+                affiliations: Vec::new(),
+            }],
+            journal: Some("Journal of Testing".to_string()),
+            date: Some(Date {
+                year: 2000 + (i % 25) as i32,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let citation = Citation {
+            citation_type: vec!["Book Section".to_string()],
+            title: "Tom & Jerry's \"guide\" to <XML>".to_string(),
+            authors: vec![Author {
+                name: "García-López".to_string(),
+                given_name: Some("María".to_string()),
+                middle_name: Some("J.".to_string()),
+                suffix: Some("Jr.".to_string()),
+                affiliations: Vec::new(),
+            }],
+            journal: Some("Handbook of Testing".to_string()),
+            journal_abbr: Some("Handb Test".to_string()),
+            date: Some(Date {
+                year: 2021,
+                month: Some(3),
+                day: Some(15),
+            }),
+            date_end: Some(Date {
+                year: 2021,
+                month: Some(3),
+                day: Some(21),
+            }),
+            volume: Some("9".to_string()),
+            issue: Some("1".to_string()),
+            pages: Some("133-150".to_string()),
+            issn: vec!["0029-6643".to_string(), "1753-4887".to_string()],
+            doi: Some("10.5555/test.2021".to_string()),
+            pmc_id: Some("PMC8123456".to_string()),
+            abstract_text: Some("An abstract.".to_string()),
+            keywords: vec!["one".to_string(), "two".to_string()],
+            urls: vec!["https://example.org/?a=1&b=2".to_string()],
+            language: Some("eng".to_string()),
+            publisher: Some("Example Press".to_string()),
+            notes: Some("First note\n\nSecond note".to_string()),
+            ..Default::default()
+        };
+
+        let xml = EndNoteXmlWriter::new().write_to_string([&citation]).unwrap();
+        assert!(xml.starts_with(HEADER));
+        assert!(xml.ends_with(FOOTER));
+        assert!(xml.contains(r#"<ref-type name="Book Section">5</ref-type>"#));
+
+        let parsed = EndNoteXmlParser::new().parse(&xml).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let parsed = &parsed[0];
+        assert_eq!(parsed.citation_type, citation.citation_type);
+        assert_eq!(parsed.title, citation.title);
+        assert_eq!(parsed.authors, citation.authors);
+        assert_eq!(parsed.journal, citation.journal);
+        assert_eq!(parsed.journal_abbr, citation.journal_abbr);
+        assert_eq!(parsed.date, citation.date);
+        assert_eq!(parsed.date_end, citation.date_end);
+        assert_eq!(parsed.volume, citation.volume);
+        assert_eq!(parsed.issue, citation.issue);
+        assert_eq!(parsed.pages, citation.pages);
+        assert_eq!(parsed.issn, citation.issn);
+        assert_eq!(parsed.doi, citation.doi);
+        assert_eq!(parsed.pmc_id, citation.pmc_id);
+        assert_eq!(parsed.abstract_text, citation.abstract_text);
+        assert_eq!(parsed.keywords, citation.keywords);
+        assert_eq!(parsed.urls, citation.urls);
+        assert_eq!(parsed.language, citation.language);
+        assert_eq!(parsed.publisher, citation.publisher);
+        assert_eq!(parsed.notes, citation.notes);
+    }
+
+    #[test]
+    fn test_write_link_kinds() {
+        let pdf = "https://example.org/download".to_string();
+        let full_text = "https://example.org/fulltext".to_string();
+        let web = "https://example.org/article".to_string();
+        let citation = Citation {
+            title: "Links".to_string(),
+            urls: vec![web.clone(), pdf.clone(), full_text.clone()],
+            link_kinds: [
+                (pdf.clone(), LinkKind::Pdf),
+                (full_text.clone(), LinkKind::FullText),
+            ]
+            .into(),
+            ..Default::default()
+        };
+
+        let xml = EndNoteXmlWriter::new().write_to_string([&citation]).unwrap();
+        assert!(xml.contains(&format!(
+            "<urls><pdf-urls><url>{pdf}</url></pdf-urls><text-urls><url>{full_text}</url>\
+             </text-urls><related-urls><url>{web}</url></related-urls></urls>"
+        )));
+
+        let parsed = &EndNoteXmlParser::new().parse(&xml).unwrap()[0];
+        assert_eq!(parsed.urls, vec![pdf, full_text, web]);
+        assert_eq!(parsed.link_kinds, citation.link_kinds);
+        assert_eq!(parsed.best_url(), Some("https://example.org/fulltext"));
+    }
+
+    #[test]
+    fn test_write_values_containing_xml_syntax() {
+        let dangerous = |field: &str| {
+            format!("{} </title></record><x a='1' b=\"2\">&amp; &lt;!--]]>", field)
+        };
+        let citation = Citation {
+            citation_type: vec![dangerous("type")],
+            title: dangerous("title"),
+            authors: vec![Author {
+                name: dangerous("name"),
+                given_name: None,
+                middle_name: None,
+                suffix: None,
+                affiliations: Vec::new(),
+            }],
+            journal: Some(dangerous("journal")),
+            journal_abbr: Some(dangerous("abbr")),
+            volume: Some(dangerous("volume")),
+            issue: Some(dangerous("issue")),
+            pages: Some(dangerous("pages")),
+            doi: Some("10.1002/(sici)1097-4636<36::aid>&'2'".to_string()),
+            abstract_text: Some(dangerous("abstract")),
+            keywords: vec![dangerous("keyword")],
+            urls: vec![dangerous("url")],
+            language: Some(dangerous("language")),
+            publisher: Some(dangerous("publisher")),
+            ..Default::default()
+        };
+
+        let xml = EndNoteXmlWriter::new().write_to_string([&citation]).unwrap();
+        let parsed = EndNoteXmlParser::new().parse(&xml).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let parsed = &parsed[0];
+        assert_eq!(parsed.citation_type, citation.citation_type);
+        assert_eq!(parsed.title, citation.title);
+        assert_eq!(parsed.authors.len(), 1);
+        assert_eq!(parsed.journal, citation.journal);
+        assert_eq!(parsed.journal_abbr, citation.journal_abbr);
+        assert_eq!(parsed.volume, citation.volume);
+        assert_eq!(parsed.issue, citation.issue);
+        assert_eq!(parsed.pages, citation.pages);
+        assert_eq!(parsed.doi, citation.doi);
+        assert_eq!(parsed.abstract_text, citation.abstract_text);
+        assert_eq!(parsed.keywords, citation.keywords);
+        assert_eq!(parsed.urls, citation.urls);
+        assert_eq!(parsed.language, citation.language);
+        assert_eq!(parsed.publisher, citation.publisher);
+    }
+
+    #[test]
+    fn test_write_drops_characters_not_allowed_in_xml() {
+        let citation = Citation {
+            title: "Form\u{c} feed".to_string(),
+            ..Default::default()
+        };
+        let xml = EndNoteXmlWriter::new().write_to_string([&citation]).unwrap();
+        assert_eq!(EndNoteXmlParser::new().parse(&xml).unwrap()[0].title, "Form feed");
+    }
+
+    #[test]
+    fn test_write_empty_document() {
+        let xml = EndNoteXmlWriter::new()
+            .write_to_string(std::iter::empty())
+            .unwrap();
+        assert_eq!(xml, format!("{}\n{}", HEADER, FOOTER));
+        assert!(EndNoteXmlParser::new().parse(&xml).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_write_streams_large_collection() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::default();
+        EndNoteXmlWriter::new()
+            .write_to(&citations, &mut counter)
+            .unwrap();
+
+        let expected = EndNoteXmlWriter::new().write_to_string(&citations).unwrap();
+        assert_eq!(counter.bytes, expected.len());
+        assert_eq!(
+            EndNoteXmlParser::new().parse(&expected).unwrap().len(),
+            10_000
+        );
+    }
+
+    #[test]
+    fn test_write_propagates_io_errors() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::failing_after(4096);
+        let result = EndNoteXmlWriter::new().write_to(&citations, &mut counter);
+
+        assert!(matches!(result, Err(CitationError::Io(_))));
+        assert!(counter.bytes <= 4096);
+    }
+}
@@ -0,0 +1,596 @@
+//! EndNote tagged (`.enw`) format parser implementation.
+//!
+//! The `.enw` format, also known as Refer/EndNote tagged format, is exported by
+//! EndNote and by Google Scholar's "EndNote" link. Each line starts with a `%`
+//! followed by a one-character tag and a space, records are separated by blank
+//! lines, and a `%0` line gives the reference type.
+//!
+//! Lines that do not start with a tag continue the value of the previous tag.
+//! `%K` values hold one keyword per line.
+//!
+//! # Example
+//!
+//! ```
+//! use biblib::{CitationParser, EnwParser};
+//!
+//! let input = "%0 Journal Article\n%T Example Title\n%A Smith, John\n%D 2023\n";
+//!
+//! let citations = EnwParser::new().parse(input).unwrap();
+//! assert_eq!(citations[0].title, "Example Title");
+//! assert_eq!(citations[0].citation_type, vec!["Journal Article"]);
+//! assert_eq!(citations[0].authors[0].name, "Smith");
+//! ```
+
+use crate::error::{ParseError, ValueError};
+use crate::utils::Coverage;
+use crate::{
+    Author, Citation, CitationFormat, CitationParser, Contributor, ContributorRole,
+    LenientOptions, LenientParse, NameOrder,
+};
+use serde::{Deserialize, Serialize};
+
+/// Parser for EndNote tagged (`.enw`) format citations.
+///
+/// Editors (`%E`, and series editors under `%Y`) and translators (`%?`) are read
+/// into [`Citation::contributors`]. Tags without a [`Citation`] field of their
+/// own, such as `%M` (accession number), are kept in `extra_fields` under the
+/// tag, e.g. `"%M"`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnwParser {
+    dedupe_keywords: bool,
+    max_abstract_length: Option<usize>,
+    max_authors: Option<usize>,
+    stamp_ingestion_time: bool,
+    fold_typography: bool,
+    sentence_case_titles: bool,
+    raw_abstracts: bool,
+    archival: bool,
+}
+
+impl EnwParser {
+    /// Creates a new `.enw` parser instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::EnwParser;
+    /// let parser = EnwParser::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to remove case-insensitive duplicate keywords from each citation.
+    ///
+    /// See [`Citation::dedupe_keywords`]. Disabled by default.
+    pub fn set_dedupe_keywords(&mut self, enabled: bool) -> &mut Self {
+        self.dedupe_keywords = enabled;
+        self
+    }
+
+    /// Sets the maximum abstract length in characters.
+    ///
+    /// Longer abstracts are shortened with [`Citation::truncate_abstract`] and
+    /// marked with [`crate::ABSTRACT_TRUNCATED_FIELD`]. `None`, the default,
+    /// keeps abstracts in full.
+    pub fn set_max_abstract_length(&mut self, max_chars: Option<usize>) -> &mut Self {
+        self.max_abstract_length = max_chars;
+        self
+    }
+
+    /// Sets the maximum number of authors kept per citation.
+    ///
+    /// Longer author lists, such as those of consortium papers, are shortened
+    /// with [`Citation::truncate_authors`], which records the full count in
+    /// `author_count` and marks the citation with
+    /// [`crate::AUTHORS_TRUNCATED_FIELD`]. `None`, the default, keeps all authors.
+    pub fn set_max_authors(&mut self, max_authors: Option<usize>) -> &mut Self {
+        self.max_authors = max_authors;
+        self
+    }
+
+    /// Sets whether each citation records when it was parsed in `ingested_at`.
+    ///
+    /// All citations of one parse get the same UTC time, as RFC 3339. Disabled by
+    /// default, so parsing the same input always gives the same output.
+    pub fn set_stamp_ingestion_time(&mut self, enabled: bool) -> &mut Self {
+        self.stamp_ingestion_time = enabled;
+        self
+    }
+
+    /// Sets whether typographic characters in the text fields are replaced with
+    /// ASCII equivalents.
+    ///
+    /// See [`Citation::fold_typography`]. Disabled by default, so stored fields
+    /// keep the characters of the source.
+    pub fn set_fold_typography(&mut self, enabled: bool) -> &mut Self {
+        self.fold_typography = enabled;
+        self
+    }
+
+    /// Sets whether all-caps titles are converted to sentence case.
+    ///
+    /// See [`Citation::sentence_case_title`]. Disabled by default, so titles
+    /// keep the case of the source.
+    pub fn set_sentence_case_titles(&mut self, enabled: bool) -> &mut Self {
+        self.sentence_case_titles = enabled;
+        self
+    }
+
+    /// Sets whether abstracts are cleaned of format-specific line breaks and
+    /// spacing.
+    ///
+    /// See [`Citation::clean_abstract`]. Enabled by default; disable it to keep
+    /// abstracts exactly as written in the source.
+    pub fn set_clean_abstracts(&mut self, enabled: bool) -> &mut Self {
+        self.raw_abstracts = !enabled;
+        self
+    }
+
+    /// Sets whether values are stored exactly as the file gives them.
+    ///
+    /// Archival mode is for collections that must keep what the file said. It
+    /// disables the normalization this parser applies by default:
+    ///
+    /// - values continued on further lines keep their line breaks and spacing
+    ///   instead of being joined into one line with single spaces
+    /// - `%P` is stored without completing a shortened end page
+    /// - `%R` is the DOI as written, without removing a resolver prefix or stray
+    ///   text
+    /// - the `%D` value read into `date` is also kept in `extra_fields` under
+    ///   [`crate::RAW_FIELD_PREFIX`], as "raw:%D"
+    /// - abstracts are not cleaned, whatever [`EnwParser::set_clean_abstracts`]
+    ///   says
+    ///
+    /// Options that are disabled by default, such as typography folding, still
+    /// apply when enabled. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, EnwParser};
+    ///
+    /// let input = "%0 Journal Article\n%T Example\n%D 2023\n%P 1234-45\n%R doi:10.1000/xyz\n";
+    /// let mut parser = EnwParser::new();
+    /// parser.set_archival(true);
+    /// let citation = &parser.parse(input).unwrap()[0];
+    /// assert_eq!(citation.pages.as_deref(), Some("1234-45"));
+    /// assert_eq!(citation.doi.as_deref(), Some("doi:10.1000/xyz"));
+    /// assert_eq!(citation.extra_fields["raw:%D"], vec!["2023"]);
+    /// ```
+    pub fn set_archival(&mut self, enabled: bool) -> &mut Self {
+        self.archival = enabled;
+        self
+    }
+
+    /// Parses `input` into `out`, as [`CitationParser::parse_into`] when `errors`
+    /// is `None`; otherwise records that fail to convert are pushed to `errors`.
+    fn parse_records(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        crate::utils::check_format(input, CitationFormat::Enw)?;
+        let mut records = RawEnwRecord::read_all(input);
+        for record in &mut records {
+            record.archival = self.archival;
+        }
+        let count = crate::utils::append_citations(
+            out,
+            records.into_iter().map(Citation::try_from),
+            errors,
+        )?;
+        crate::utils::set_parsed_from_of_last(out, count, CitationFormat::Enw);
+        if self.dedupe_keywords {
+            crate::utils::dedupe_keywords_of_last(out, count);
+        }
+        if self.fold_typography {
+            crate::utils::fold_typography_of_last(out, count);
+        }
+        if self.sentence_case_titles {
+            crate::utils::sentence_case_titles_of_last(out, count);
+        }
+        if !self.raw_abstracts && !self.archival {
+            crate::utils::clean_abstracts_of_last(out, count);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            crate::utils::truncate_abstracts_of_last(out, count, max_chars);
+        }
+        if let Some(max_authors) = self.max_authors {
+            crate::utils::truncate_authors_of_last(out, count, max_authors);
+        }
+        if self.stamp_ingestion_time {
+            crate::utils::stamp_ingestion_time_of_last(out, count);
+        }
+        Ok(count)
+    }
+}
+
+impl CitationParser for EnwParser {
+    /// Parses a string containing one or more citations in `.enw` format.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if a record has neither a title nor an author
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        let mut citations = Vec::new();
+        self.parse_into(input, &mut citations)?;
+        Ok(citations)
+    }
+
+    /// Parses `.enw` formatted content, appending the citations to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if a record has neither a title nor an author; `out`
+    /// is left unchanged
+    fn parse_into(&self, input: &str, out: &mut Vec<Citation>) -> Result<usize, ParseError> {
+        self.parse_records(input, out, None)
+    }
+
+    /// Parses `.enw` content, skipping records that fail to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed or the failures exceed `options`
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> Result<LenientParse, ParseError> {
+        let mut citations = Vec::new();
+        let mut errors = Vec::new();
+        self.parse_records(input, &mut citations, Some(&mut errors))?;
+        options.check(citations, errors, enw_coverage(input).unparsed)
+    }
+}
+
+/// Splits a tag line into its tag character and value.
+///
+/// Returns `None` for lines that are not tag lines, which continue the previous
+/// value.
+fn split_tag_line(line: &str) -> Option<(char, &str)> {
+    let rest = line.strip_prefix('%')?;
+    let mut chars = rest.chars();
+    let tag = chars.next().filter(|c| c.is_ascii_graphic())?;
+    let value = chars.as_str();
+    if value.is_empty() {
+        return Some((tag, ""));
+    }
+    value.strip_prefix(' ').map(|value| (tag, value.trim()))
+}
+
+/// Count the lines of an `.enw` formatted text that [`EnwParser`] reads, skips
+/// and starts records at.
+///
+/// Continuation lines outside a record, such as text before the first tag, are
+/// the only lines the parser skips.
+pub(crate) fn enw_coverage(text: &str) -> Coverage {
+    let mut coverage = Coverage::default();
+    let mut in_record = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            in_record = false;
+            continue;
+        }
+        coverage.lines += 1;
+
+        match split_tag_line(line) {
+            Some((tag, _)) => {
+                if !in_record || tag == '0' {
+                    coverage.records += 1;
+                }
+                in_record = true;
+            }
+            None if !in_record => coverage.unparsed.push(index + 1),
+            None => {}
+        }
+    }
+
+    coverage
+}
+
+/// The tags and values of one record, in file order.
+#[derive(Debug, Default)]
+struct RawEnwRecord {
+    /// 1-based line number of the record's first tag
+    line: usize,
+    fields: Vec<(char, String)>,
+    /// Whether values are kept as written, see [`EnwParser::set_archival`]
+    archival: bool,
+}
+
+impl RawEnwRecord {
+    /// Reads every record in `text`.
+    ///
+    /// A record ends at a blank line or at the next `%0` line.
+    fn read_all(text: &str) -> Vec<Self> {
+        let mut records = Vec::new();
+        let mut current = RawEnwRecord::default();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.trim().is_empty() {
+                if !current.fields.is_empty() {
+                    records.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+
+            match split_tag_line(line) {
+                Some((tag, value)) => {
+                    if tag == '0' && !current.fields.is_empty() {
+                        records.push(std::mem::take(&mut current));
+                    }
+                    if current.fields.is_empty() {
+                        current.line = index + 1;
+                    }
+                    current.fields.push((tag, value.to_string()));
+                }
+                None => {
+                    // Continuation of the previous value; lines outside a record are skipped
+                    if let Some((_, value)) = current.fields.last_mut() {
+                        value.push('\n');
+                        value.push_str(line.trim());
+                    }
+                }
+            }
+        }
+
+        if !current.fields.is_empty() {
+            records.push(current);
+        }
+        records
+    }
+}
+
+impl TryFrom<RawEnwRecord> for Citation {
+    type Error = ParseError;
+
+    fn try_from(record: RawEnwRecord) -> Result<Self, Self::Error> {
+        let mut citation = Citation::default();
+        let mut anonymous = false;
+        let mut book_title = None;
+
+        let archival = record.archival;
+        for (tag, value) in record.fields {
+            let single_line = || {
+                if archival {
+                    value.clone()
+                } else {
+                    value.split_whitespace().collect::<Vec<_>>().join(" ")
+                }
+            };
+            match tag {
+                '0' => citation.citation_type.push(single_line()),
+                'A' => {
+                    if crate::utils::is_placeholder_author(&value) {
+                        anonymous |= !value.trim().is_empty();
+                    } else {
+                        citation.authors.push(parse_author(&single_line()));
+                    }
+                }
+                'E' | 'Y' | '?' => {
+                    let role = match tag {
+                        '?' => ContributorRole::Translator,
+                        _ => ContributorRole::Editor,
+                    };
+                    if !crate::utils::is_placeholder_author(&value) {
+                        let person = parse_author(&single_line());
+                        citation.contributors.push(Contributor::new(person, role));
+                    }
+                }
+                'T' if citation.title.is_empty() => citation.title = single_line(),
+                'J' if citation.journal.is_none() => citation.journal = Some(single_line()),
+                'B' if book_title.is_none() => book_title = Some(single_line()),
+                'D' if citation.date.is_none() => {
+                    citation.date = crate::utils::parse_year_only(&value);
+                    if citation.date.is_none() {
+                        insert_extra(&mut citation, tag, value);
+                    } else if archival {
+                        let key = format!("{}%{}", crate::RAW_FIELD_PREFIX, tag);
+                        citation.extra_fields.insert(key, vec![value]);
+                    }
+                }
+                'V' if citation.volume.is_none() => citation.volume = Some(single_line()),
+                'N' if citation.issue.is_none() => citation.issue = Some(single_line()),
+                'P' if citation.pages.is_none() && archival => citation.pages = Some(value),
+                'P' if citation.pages.is_none() => {
+                    citation.pages = Some(crate::utils::format_page_numbers(&value));
+                }
+                'R' if citation.doi.is_none() && archival => citation.doi = Some(value),
+                'R' if citation.doi.is_none() => {
+                    citation.doi = crate::utils::format_doi(&value);
+                    if citation.doi.is_none() {
+                        insert_extra(&mut citation, tag, value);
+                    }
+                }
+                'X' if citation.abstract_text.is_none() => {
+                    citation.abstract_text = Some(single_line());
+                }
+                'K' => citation.keywords.extend(
+                    value
+                        .lines()
+                        .map(str::trim)
+                        .filter(|keyword| !keyword.is_empty())
+                        .map(String::from),
+                ),
+                '@' => citation.issn.extend(crate::utils::split_issns(&value)),
+                'I' if citation.publisher.is_none() => citation.publisher = Some(single_line()),
+                'U' => citation.urls.extend(value.split_whitespace().map(String::from)),
+                'G' if citation.language.is_none() => citation.language = Some(single_line()),
+                'Z' => match &mut citation.notes {
+                    Some(notes) => {
+                        notes.push_str("\n\n");
+                        notes.push_str(&value);
+                    }
+                    None => citation.notes = Some(value),
+                },
+                _ => insert_extra(&mut citation, tag, value),
+            }
+        }
+
+        // Book sections give the book under %B
+        if citation.journal.is_none() {
+            citation.journal = book_title;
+        } else if let Some(book_title) = book_title {
+            insert_extra(&mut citation, 'B', book_title);
+        }
+
+        crate::utils::mark_anonymous(&mut citation, anonymous);
+
+        if citation.title.is_empty() && citation.authors.is_empty() {
+            return Err(ParseError::at_line(
+                record.line,
+                CitationFormat::Enw,
+                ValueError::MissingValue {
+                    field: "title or author",
+                    key: "%T/%A",
+                },
+            ));
+        }
+
+        citation.retraction_status = crate::RetractionStatus::from_title(&citation.title);
+        Ok(citation)
+    }
+}
+
+/// Keeps a value in `extra_fields` under its tag, e.g. `"%M"`.
+fn insert_extra(citation: &mut Citation, tag: char, value: String) {
+    citation
+        .extra_fields
+        .entry(format!("%{}", tag))
+        .or_default()
+        .push(value);
+}
+
+/// Parses an `%A` value, written family name first.
+fn parse_author(name: &str) -> Author {
+    let (family, given, suffix) = crate::utils::parse_author_name(name, NameOrder::FamilyFirst);
+    let (given_name, middle_name) = crate::utils::split_given_and_middle(&given);
+    Author {
+        name: family,
+        given_name,
+        middle_name,
+        suffix,
+        affiliations: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_simple_record() {
+        let input = "\
+%0 Journal Article
+%T Effects of exercise on sleep quality
+%A Smith, John A.
+%A Doe, Jane
+%J Sleep Medicine
+%D 2021
+%V 12
+%N 4
+%P 100-10
+%R 10.1016/j.sleep.2021.01.001
+%X Regular exercise
+improves sleep.
+%K exercise
+sleep quality
+%M 33445566
+";
+        let citations = EnwParser::new().parse(input).unwrap();
+        assert_eq!(citations.len(), 1);
+
+        let citation = &citations[0];
+        assert_eq!(citation.citation_type, vec!["Journal Article"]);
+        assert_eq!(citation.title, "Effects of exercise on sleep quality");
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.authors[0].name, "Smith");
+        assert_eq!(citation.authors[0].given_name.as_deref(), Some("John"));
+        assert_eq!(citation.authors[0].middle_name.as_deref(), Some("A."));
+        assert_eq!(citation.authors[1].name, "Doe");
+        assert_eq!(citation.journal.as_deref(), Some("Sleep Medicine"));
+        assert_eq!(citation.date.as_ref().unwrap().year, 2021);
+        assert_eq!(citation.volume.as_deref(), Some("12"));
+        assert_eq!(citation.issue.as_deref(), Some("4"));
+        assert_eq!(citation.pages.as_deref(), Some("100-110"));
+        assert_eq!(citation.doi.as_deref(), Some("10.1016/j.sleep.2021.01.001"));
+        assert_eq!(
+            citation.abstract_text.as_deref(),
+            Some("Regular exercise improves sleep.")
+        );
+        assert_eq!(citation.keywords, vec!["exercise", "sleep quality"]);
+        assert_eq!(citation.extra_fields["%M"], vec!["33445566"]);
+    }
+
+    #[test]
+    fn test_records_split_at_blank_lines_and_type_lines() {
+        let input = "\
+%0 Journal Article
+%T First
+
+%0 Book
+%T Second
+%0 Book Section
+%T Third
+%B The Book
+";
+        let citations = EnwParser::new().parse(input).unwrap();
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[1].citation_type, vec!["Book"]);
+        assert_eq!(citations[2].title, "Third");
+        assert_eq!(citations[2].journal.as_deref(), Some("The Book"));
+    }
+
+    #[test]
+    fn test_placeholder_authors() {
+        let input = "%0 Journal Article\n%T Editorial\n%A Anonymous\n";
+        let citations = EnwParser::new().parse(input).unwrap();
+        assert!(citations[0].authors.is_empty());
+        assert_eq!(citations[0].extra_fields[crate::ANONYMOUS_FIELD], vec!["true"]);
+    }
+
+    #[test]
+    fn test_editors_and_translators() {
+        let input = "%0 Book Section\n%T Sepsis\n%A Okafor, Chidi\n%E Tanaka, Hiroshi\n\
+                     %Y Moreau, Claire\n%? Novak, Jan\n%B Emergency Medicine\n";
+        let citation = &EnwParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.authors.len(), 1);
+        let editors: Vec<&str> = citation.editors().map(|e| e.name.as_str()).collect();
+        assert_eq!(editors, vec!["Tanaka", "Moreau"]);
+        assert_eq!(citation.contributors[2].role, ContributorRole::Translator);
+        assert_eq!(citation.contributors[2].person.given_name.as_deref(), Some("Jan"));
+        assert!(!citation.extra_fields.contains_key("%E"));
+    }
+
+    #[test]
+    fn test_record_without_title_or_author() {
+        let input = "%0 Journal Article\n%T First\n\n%0 Journal Article\n%D 2020\n";
+        let err = EnwParser::new().parse(input).unwrap_err();
+        assert_eq!(err.line, Some(4));
+        assert_eq!(err.format, CitationFormat::Enw);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(EnwParser::new().parse("").unwrap().is_empty());
+        assert!(EnwParser::new().parse("\n\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_enw_coverage() {
+        let input = "stray text\n%0 Journal Article\n%T First\ncontinued\n\n%0 Book\n%T Second\n";
+        let coverage = enw_coverage(input);
+        assert_eq!(coverage.lines, 6);
+        assert_eq!(coverage.unparsed, vec![1]);
+        assert_eq!(coverage.records, 2);
+    }
+}
@@ -39,6 +39,9 @@ pub enum CitationError {
 
     #[error(transparent)]
     Parse(#[from] ParseError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 /// Parse error with detailed location and context information.
@@ -126,6 +129,9 @@ pub enum ValueError {
         second_row: Option<usize>,
         second_col: Option<usize>,
     },
+
+    #[error("Input looks like {detected} rather than this format; parse it with the {detected} parser")]
+    WrongFormat { detected: CitationFormat },
 }
 
 // Conversion implementations for external error types
@@ -252,6 +258,14 @@ mod tests {
         assert_eq!(format!("{}", CitationFormat::Csv), "CSV");
     }
 
+    #[test]
+    fn test_io_error_conversion() {
+        let io_err = std::io::Error::other("disk full");
+        let error: CitationError = io_err.into();
+        assert!(matches!(error, CitationError::Io(_)));
+        assert_eq!(format!("{}", error), "I/O error: disk full");
+    }
+
     #[cfg(feature = "csv")]
     #[test]
     fn test_csv_error_conversion() {
@@ -0,0 +1,249 @@
+//! C ABI for parsing and deduplicating citations from other languages.
+//!
+//! The functions take and return NUL-terminated UTF-8 strings, exchanging
+//! citations and duplicate groups as JSON in the shape serde gives [`Citation`]
+//! and [`DuplicateGroup`](crate::DuplicateGroup); the `schema` feature has its
+//! JSON Schema. Every returned string is allocated by biblib and must be
+//! released with [`biblib_free_string`]. The declarations for C are in `include/biblib.h`.
+//!
+//! Failures never cross the boundary as panics or null pointers: a function
+//! that fails returns a JSON object such as
+//! `{"error": {"kind": "parse", "message": "..."}}`, where `kind` is one of
+//! `"null_argument"`, `"invalid_utf8"`, `"invalid_json"`, `"invalid_config"`,
+//! `"unknown_format"`, `"parse"`, `"dedupe"` and `"panic"`. Successful results
+//! are JSON arrays, so checking for an object is enough to tell them apart.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::ffi::{biblib_free_string, biblib_parse};
+//! use std::ffi::{CStr, CString};
+//!
+//! let content = CString::new("TY  - JOUR\nTI  - Example Title\nER  - \n").unwrap();
+//! let format = CString::new("ris").unwrap();
+//! unsafe {
+//!     let result = biblib_parse(content.as_ptr(), format.as_ptr());
+//!     let json = CStr::from_ptr(result).to_str().unwrap().to_string();
+//!     biblib_free_string(result);
+//!     assert!(json.starts_with("[{"));
+//!     assert!(json.contains("\"title\":\"Example Title\""));
+//! }
+//! ```
+
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+use crate::CitationParser;
+use crate::dedupe::{Deduplicator, DeduplicatorConfig};
+use crate::{Citation, CitationError};
+use serde_json::{Value, json};
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// A failure reported to the caller as a JSON error object.
+struct FfiError {
+    kind: &'static str,
+    message: String,
+}
+
+impl FfiError {
+    fn new(kind: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        json!({"error": {"kind": self.kind, "message": self.message}}).to_string()
+    }
+}
+
+impl From<CitationError> for FfiError {
+    fn from(error: CitationError) -> Self {
+        match error {
+            CitationError::UnknownFormat => FfiError::new("unknown_format", error.to_string()),
+            error => FfiError::new("parse", error.to_string()),
+        }
+    }
+}
+
+/// Parses citation content, returning the citations as a JSON array.
+///
+/// `format_hint` names the format: "ris", "pubmed" (or "nbib"), "endnote_xml"
+/// (or "xml"), "enw", "bibtex" (or "bib") or "csv", in any case. A null
+/// pointer, an empty string or "auto" detects the format as
+/// [`crate::detect_and_parse`] does.
+///
+/// # Safety
+///
+/// `content` must be null or point to a NUL-terminated string, and so must
+/// `format_hint`; both must stay valid for the duration of the call. The
+/// returned string must be released with [`biblib_free_string`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn biblib_parse(
+    content: *const c_char,
+    format_hint: *const c_char,
+) -> *mut c_char {
+    guarded(|| {
+        // SAFETY: the caller guarantees both pointers are null or valid C strings
+        let content = unsafe { required_str(content, "content") }?;
+        let format_hint = unsafe { optional_str(format_hint, "format_hint") }?;
+        let citations = parse(content, format_hint.unwrap_or(""))?;
+        to_json(&citations)
+    })
+}
+
+/// Deduplicates citations given as a JSON array, returning the duplicate
+/// groups as a JSON array.
+///
+/// `json_config` is null, or a JSON object of [`DeduplicatorConfig`] options as
+/// it deserializes them, e.g. `{"grouping_mode": "transitive_closure"}`;
+/// missing keys keep the defaults of [`Deduplicator::new`], and unknown keys or
+/// invalid values are rejected.
+///
+/// # Safety
+///
+/// `json_citations` must be null or point to a NUL-terminated string, and so
+/// must `json_config`; both must stay valid for the duration of the call. The
+/// returned string must be released with [`biblib_free_string`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn biblib_dedupe(
+    json_citations: *const c_char,
+    json_config: *const c_char,
+) -> *mut c_char {
+    guarded(|| {
+        // SAFETY: the caller guarantees both pointers are null or valid C strings
+        let citations = unsafe { required_str(json_citations, "json_citations") }?;
+        let config = unsafe { optional_str(json_config, "json_config") }?;
+
+        let citations: Vec<Citation> = serde_json::from_str(citations)
+            .map_err(|e| FfiError::new("invalid_json", format!("json_citations: {}", e)))?;
+        let mut deduplicator = Deduplicator::new();
+        if let Some(config) = config.filter(|c| !c.trim().is_empty()) {
+            let config: Value = serde_json::from_str(config)
+                .map_err(|e| FfiError::new("invalid_json", format!("json_config: {}", e)))?;
+            deduplicator = deduplicator.with_config(dedupe_config(config)?);
+        }
+
+        let groups = deduplicator
+            .find_duplicates(&citations)
+            .map_err(|e| FfiError::new("dedupe", e.to_string()))?;
+        to_json(&groups)
+    })
+}
+
+/// Releases a string returned by [`biblib_parse`] or [`biblib_dedupe`].
+///
+/// Passing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that has not been
+/// released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn biblib_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees `s` came from `CString::into_raw` here
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Runs `f`, turning its error or a panic into a JSON error object, and hands
+/// the result to the caller as a C string.
+fn guarded(f: impl FnOnce() -> Result<String, FfiError>) -> *mut c_char {
+    let json = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(json)) => json,
+        Ok(Err(error)) => error.to_json(),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            FfiError::new("panic", message).to_json()
+        }
+    };
+    // serde_json escapes NUL characters, so the JSON never contains one
+    CString::new(json).map_or(std::ptr::null_mut(), CString::into_raw)
+}
+
+/// Reads a C string argument that must be given.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn required_str<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, FfiError> {
+    // SAFETY: forwarded from the caller
+    unsafe { optional_str(ptr, name) }?
+        .ok_or_else(|| FfiError::new("null_argument", format!("{} is null", name)))
+}
+
+/// Reads a C string argument that may be null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn optional_str<'a>(ptr: *const c_char, name: &str) -> Result<Option<&'a str>, FfiError> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: forwarded from the caller
+    let bytes = unsafe { CStr::from_ptr(ptr) };
+    bytes
+        .to_str()
+        .map(Some)
+        .map_err(|e| FfiError::new("invalid_utf8", format!("{}: {}", name, e)))
+}
+
+/// Parses `content` in the format named by `hint`.
+fn parse(content: &str, hint: &str) -> Result<Vec<Citation>, FfiError> {
+    let parsed = match hint.trim().to_ascii_lowercase().as_str() {
+        "" | "auto" => crate::detect_and_parse(content).map(|(citations, _)| citations),
+        #[cfg(feature = "ris")]
+        "ris" => crate::RisParser::new().parse(content).map_err(CitationError::Parse),
+        #[cfg(feature = "pubmed")]
+        "pubmed" | "nbib" => {
+            crate::PubMedParser::new().parse(content).map_err(CitationError::Parse)
+        }
+        #[cfg(feature = "xml")]
+        "endnote_xml" | "xml" => {
+            crate::EndNoteXmlParser::new().parse(content).map_err(CitationError::Parse)
+        }
+        #[cfg(feature = "enw")]
+        "enw" => crate::EnwParser::new().parse(content).map_err(CitationError::Parse),
+        #[cfg(feature = "bibtex")]
+        "bibtex" | "bib" => crate::BibTexParser::new().parse(content).map_err(CitationError::Parse),
+        #[cfg(feature = "csv")]
+        "csv" => crate::CsvParser::new().parse(content).map_err(CitationError::Parse),
+        _ => {
+            return Err(FfiError::new(
+                "unknown_format",
+                format!("Unsupported format hint '{}'", hint),
+            ));
+        }
+    };
+    parsed.map_err(FfiError::from)
+}
+
+/// Serializes a result for the caller.
+fn to_json(value: &impl serde::Serialize) -> Result<String, FfiError> {
+    serde_json::to_string(value).map_err(|e| FfiError::new("invalid_json", e.to_string()))
+}
+
+/// Builds a deduplicator configuration from its JSON form, on top of the
+/// settings of [`Deduplicator::new`].
+fn dedupe_config(value: Value) -> Result<DeduplicatorConfig, FfiError> {
+    let invalid = |message: String| FfiError::new("invalid_config", message);
+    let Value::Object(mut object) = value else {
+        return Err(invalid("json_config must be an object".to_string()));
+    };
+    // Deduplicator::new groups by year, unlike the default configuration
+    object.entry("group_by_year").or_insert(Value::Bool(true));
+    serde_json::from_value(Value::Object(object)).map_err(|e| invalid(e.to_string()))
+}
@@ -0,0 +1,229 @@
+//! Selecting citations by date.
+//!
+//! Alert-based screening processes the records added since the last search.
+//! It goes by the date a record entered the database, such as the PubMed
+//! Entrez date, rather than the publication date, which often lies months
+//! earlier. [`filter_citations`] keeps the citations whose dates fall in the
+//! ranges of a [`CitationFilter`].
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::filter::{CitationFilter, DateRange, filter_citations};
+//! use biblib::{Citation, Date, RecordDateKind};
+//!
+//! let added = |day: u8| {
+//!     let mut citation = Citation::new();
+//!     let date = Date { year: 2023, month: Some(5), day: Some(day) };
+//!     citation.record_dates.insert(RecordDateKind::Entrez, date);
+//!     citation
+//! };
+//! let citations = vec![added(2), added(12), Citation::new()];
+//!
+//! let last_run = Date { year: 2023, month: Some(5), day: Some(10) };
+//! let filter = CitationFilter::default().entrez_dates(DateRange::since(last_run));
+//! let new = filter_citations(&citations, &filter);
+//! assert_eq!(new.len(), 1);
+//! assert_eq!(new[0].entrez_date().unwrap().day, Some(12));
+//! ```
+
+use crate::{Citation, Date, RecordDateKind};
+use std::cmp::Ordering;
+
+/// An inclusive range of dates, open at either end when a bound is `None`.
+///
+/// A bound without a month or day covers the whole year or month, so a range
+/// ending in "2023" contains "2023 Dec 31". A date less precise than a bound
+/// is compared as if it came before the missing parts, so "2023" is not in a
+/// range starting at "2023 May".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateRange {
+    /// First date in the range
+    pub start: Option<Date>,
+    /// Last date in the range
+    pub end: Option<Date>,
+}
+
+impl DateRange {
+    /// The range from `start` to `end`, both included.
+    #[must_use]
+    pub fn new(start: Option<Date>, end: Option<Date>) -> Self {
+        Self { start, end }
+    }
+
+    /// The range of `start` and every later date.
+    #[must_use]
+    pub fn since(start: Date) -> Self {
+        Self::new(Some(start), None)
+    }
+
+    /// The range of `end` and every earlier date.
+    #[must_use]
+    pub fn until(end: Date) -> Self {
+        Self::new(None, Some(end))
+    }
+
+    /// Whether `date` lies in this range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Date;
+    /// use biblib::filter::DateRange;
+    ///
+    /// let date = |year, month, day| Date { year, month, day };
+    /// let year_2023 = DateRange::new(Some(date(2023, None, None)), Some(date(2023, None, None)));
+    /// assert!(year_2023.contains(&date(2023, Some(12), Some(31))));
+    /// assert!(!year_2023.contains(&date(2024, Some(1), Some(1))));
+    /// ```
+    #[must_use]
+    pub fn contains(&self, date: &Date) -> bool {
+        self.start
+            .as_ref()
+            .is_none_or(|start| compare_to_bound(date, start) != Ordering::Less)
+            && self
+                .end
+                .as_ref()
+                .is_none_or(|end| compare_to_bound(date, end) != Ordering::Greater)
+    }
+}
+
+/// Compares `date` with `bound` at the precision of `bound`.
+fn compare_to_bound(date: &Date, bound: &Date) -> Ordering {
+    let truncated = Date {
+        year: date.year,
+        month: bound.month.and(date.month),
+        day: bound.day.and(date.day),
+    };
+    truncated.cmp(bound)
+}
+
+/// Date ranges a citation must fall in to be kept by [`filter_citations`].
+///
+/// A citation without the date a range applies to is left out. The default
+/// filter keeps every citation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CitationFilter {
+    /// Range of the publication date, [`Citation::date`]
+    pub published: Option<DateRange>,
+    /// Ranges of [`Citation::record_dates`], all of which must hold
+    pub record_dates: Vec<(RecordDateKind, DateRange)>,
+}
+
+impl CitationFilter {
+    /// Keeps citations published in `range`.
+    #[must_use]
+    pub fn published(mut self, range: DateRange) -> Self {
+        self.published = Some(range);
+        self
+    }
+
+    /// Keeps citations whose record date of the given kind is in `range`.
+    #[must_use]
+    pub fn record_dates(mut self, kind: RecordDateKind, range: DateRange) -> Self {
+        self.record_dates.push((kind, range));
+        self
+    }
+
+    /// Keeps citations added to the database in `range`, see
+    /// [`Citation::entrez_date`].
+    #[must_use]
+    pub fn entrez_dates(self, range: DateRange) -> Self {
+        self.record_dates(RecordDateKind::Entrez, range)
+    }
+
+    /// Whether `citation` falls in every range of this filter.
+    #[must_use]
+    pub fn matches(&self, citation: &Citation) -> bool {
+        let within =
+            |date: Option<&Date>, range: &DateRange| date.is_some_and(|d| range.contains(d));
+        self.published
+            .as_ref()
+            .is_none_or(|range| within(citation.date.as_ref(), range))
+            && self
+                .record_dates
+                .iter()
+                .all(|(kind, range)| within(citation.record_date(*kind), range))
+    }
+}
+
+/// Returns the citations matching `filter`, in their original order.
+#[must_use]
+pub fn filter_citations<'a>(
+    citations: &'a [Citation],
+    filter: &CitationFilter,
+) -> Vec<&'a Citation> {
+    citations
+        .iter()
+        .filter(|citation| filter.matches(citation))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn date(year: i32, month: Option<u8>, day: Option<u8>) -> Date {
+        Date { year, month, day }
+    }
+
+    fn citation(published: Option<Date>, entrez: Option<Date>) -> Citation {
+        let mut citation = Citation {
+            date: published,
+            ..Default::default()
+        };
+        if let Some(entrez) = entrez {
+            citation.record_dates.insert(RecordDateKind::Entrez, entrez);
+        }
+        citation
+    }
+
+    #[test]
+    fn test_date_range_bounds() {
+        let range = DateRange::new(
+            Some(date(2023, Some(5), None)),
+            Some(date(2023, Some(6), Some(15))),
+        );
+        assert!(range.contains(&date(2023, Some(5), Some(1))));
+        assert!(range.contains(&date(2023, Some(6), Some(15))));
+        assert!(range.contains(&date(2023, Some(6), None)));
+        assert!(!range.contains(&date(2023, Some(4), Some(30))));
+        assert!(!range.contains(&date(2023, Some(6), Some(16))));
+        assert!(!range.contains(&date(2023, None, None)));
+        assert!(DateRange::default().contains(&date(1900, None, None)));
+    }
+
+    #[test]
+    fn test_filter_by_entrez_date() {
+        let citations = vec![
+            // Published long before it was added
+            citation(
+                Some(date(2022, Some(11), None)),
+                Some(date(2023, Some(5), Some(12))),
+            ),
+            citation(
+                Some(date(2023, Some(5), None)),
+                Some(date(2023, Some(5), Some(2))),
+            ),
+            citation(Some(date(2023, Some(6), None)), None),
+        ];
+        let since = DateRange::since(date(2023, Some(5), Some(10)));
+
+        let filter = CitationFilter::default().entrez_dates(since.clone());
+        let kept = filter_citations(&citations, &filter);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].date, Some(date(2022, Some(11), None)));
+
+        // Publication and record date ranges must both hold
+        let filter = CitationFilter::default()
+            .published(DateRange::since(date(2023, None, None)))
+            .entrez_dates(since);
+        assert!(filter_citations(&citations, &filter).is_empty());
+
+        assert_eq!(
+            filter_citations(&citations, &CitationFilter::default()).len(),
+            3
+        );
+    }
+}
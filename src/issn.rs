@@ -0,0 +1,293 @@
+//! Structured ISSNs and print/electronic pairings.
+//!
+//! [`Citation::issn`] keeps ISSNs as written in the source, often with an
+//! annotation naming the medium, e.g. "1234-5678 (Print)" in PubMed `IS` lines.
+//! [`Issn::parse`] reads such a string into the ISSN and its [`IssnKind`], and
+//! [`Citation::issns`] does so for every ISSN of a citation.
+//!
+//! A journal usually has a print and an electronic ISSN, and records of the same
+//! article may carry only one of them each. An [`IssnCatalog`] of known pairings
+//! fills in the missing counterpart, and the deduplicator compares the ISSNs of
+//! citations together with their counterparts when given one (see
+//! `Deduplicator::with_issn_catalog`).
+
+use crate::Citation;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The medium an ISSN is assigned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IssnKind {
+    /// The print edition, e.g. "(Print)" or "pISSN"
+    Print,
+    /// The electronic edition, e.g. "(Electronic)", "(Online)" or "eISSN"
+    Electronic,
+    /// The linking ISSN shared by all editions, e.g. "(Linking)" or "ISSN-L"
+    Linking,
+}
+
+impl IssnKind {
+    /// The annotation written after an ISSN of this kind, as in PubMed.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssnKind::Print => "Print",
+            IssnKind::Electronic => "Electronic",
+            IssnKind::Linking => "Linking",
+        }
+    }
+}
+
+/// An ISSN with the medium it is assigned to, if known.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Issn, IssnKind};
+///
+/// let issn = Issn::parse("1234-567x (Electronic)").unwrap();
+/// assert_eq!(issn.value, "1234-567X");
+/// assert_eq!(issn.kind, Some(IssnKind::Electronic));
+/// assert_eq!(issn.to_string(), "1234-567X (Electronic)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Issn {
+    /// The ISSN as "1234-567X", with the hyphen and an uppercase check digit
+    pub value: String,
+    /// The medium, when the source names it
+    pub kind: Option<IssnKind>,
+}
+
+impl Issn {
+    /// Parses an ISSN and its annotation, e.g. "1234-5678 (Print)",
+    /// "eISSN: 1234-5678" or "12345678".
+    ///
+    /// The kind is read from "Print", "Electronic", "Online" and "Linking" in any
+    /// case and with or without parentheses, and from "pISSN", "eISSN" and
+    /// "ISSN-L" prefixes, also hyphenated ("e-ISSN"). Returns `None` when the
+    /// string holds no ISSN: seven digits and a digit or "X", with an optional
+    /// hyphen after the fourth.
+    #[must_use]
+    pub fn parse(text: &str) -> Option<Issn> {
+        Some(Issn {
+            value: find_issn(text)?,
+            kind: issn_kind(text),
+        })
+    }
+}
+
+impl fmt::Display for Issn {
+    /// Writes the ISSN with its kind in parentheses, e.g. "1234-5678 (Print)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Some(kind) => write!(f, "{} ({})", self.value, kind.as_str()),
+            None => f.write_str(&self.value),
+        }
+    }
+}
+
+/// Finds the first ISSN in `text`, formatted as "1234-567X".
+fn find_issn(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    (0..bytes.len())
+        .filter(|&i| bytes[i].is_ascii_digit() && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric()))
+        .find_map(|i| {
+            let mut digits = Vec::with_capacity(8);
+            let mut j = i;
+            while digits.len() < 8 && j < bytes.len() {
+                match bytes[j] {
+                    b'-' if digits.len() == 4 && j > i && bytes[j - 1] != b'-' => {}
+                    b if b.is_ascii_digit() => digits.push(b),
+                    b'X' | b'x' if digits.len() == 7 => digits.push(b'X'),
+                    _ => break,
+                }
+                j += 1;
+            }
+            let ends = bytes.get(j).is_none_or(|b| !b.is_ascii_alphanumeric());
+            (digits.len() == 8 && ends).then(|| {
+                let digits = String::from_utf8(digits).expect("ASCII digits");
+                format!("{}-{}", &digits[..4], &digits[4..])
+            })
+        })
+}
+
+/// Reads the kind of an ISSN from its annotation or prefix.
+fn issn_kind(text: &str) -> Option<IssnKind> {
+    let text = text.to_lowercase().replace("-issn", "issn");
+    if text.contains("linking") || text.contains("issn-l") || text.contains("issnl") {
+        Some(IssnKind::Linking)
+    } else if text.contains("electronic") || text.contains("online") || text.contains("eissn") {
+        Some(IssnKind::Electronic)
+    } else if text.contains("print") || text.contains("pissn") {
+        Some(IssnKind::Print)
+    } else {
+        None
+    }
+}
+
+/// Known pairings of the print and electronic ISSNs of journals.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Citation, IssnCatalog};
+///
+/// let mut catalog = IssnCatalog::new();
+/// catalog.add_pair("0028-4793", "1533-4406");
+///
+/// let mut citation = Citation {
+///     issn: vec!["0028-4793 (Print)".to_string()],
+///     ..Default::default()
+/// };
+/// assert_eq!(catalog.fill_counterparts(&mut citation), 1);
+/// assert_eq!(citation.issn, vec!["0028-4793 (Print)", "1533-4406 (Electronic)"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IssnCatalog {
+    /// The counterpart of each ISSN in a pairing, keyed by ISSN value
+    counterparts: HashMap<String, Issn>,
+}
+
+impl IssnCatalog {
+    /// Creates an empty catalog.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `print` and `electronic` are the ISSNs of the same journal.
+    ///
+    /// Both are read with [`Issn::parse`], ignoring any annotation; the pairing is
+    /// not recorded when either holds no ISSN.
+    pub fn add_pair(&mut self, print: &str, electronic: &str) -> &mut Self {
+        if let (Some(print), Some(electronic)) = (find_issn(print), find_issn(electronic)) {
+            self.counterparts.insert(
+                print.clone(),
+                Issn {
+                    value: electronic.clone(),
+                    kind: Some(IssnKind::Electronic),
+                },
+            );
+            self.counterparts.insert(
+                electronic,
+                Issn {
+                    value: print,
+                    kind: Some(IssnKind::Print),
+                },
+            );
+        }
+        self
+    }
+
+    /// Returns whether the catalog holds no pairings.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.counterparts.is_empty()
+    }
+
+    /// Returns the other ISSN of the journal, with its kind.
+    ///
+    /// A linking ISSN has no counterpart, as it names every edition.
+    #[must_use]
+    pub fn counterpart(&self, issn: &Issn) -> Option<&Issn> {
+        if issn.kind == Some(IssnKind::Linking) {
+            return None;
+        }
+        self.counterparts.get(&issn.value)
+    }
+
+    /// Adds the counterparts of a citation's ISSNs it does not already have, as
+    /// display strings such as "1533-4406 (Electronic)".
+    ///
+    /// Returns the number of ISSNs added.
+    pub fn fill_counterparts(&self, citation: &mut Citation) -> usize {
+        let issns = citation.issns();
+        let mut added = 0;
+        for issn in &issns {
+            if let Some(counterpart) = self.counterpart(issn)
+                && !citation.issns().iter().any(|i| i.value == counterpart.value)
+            {
+                citation.issn.push(counterpart.to_string());
+                added += 1;
+            }
+        }
+        added
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1234-5678", "1234-5678", None)]
+    #[case("12345678", "1234-5678", None)]
+    #[case("1234-567x", "1234-567X", None)]
+    #[case("1234-5678 (Print)", "1234-5678", Some(IssnKind::Print))]
+    #[case("1234-5678 (print)", "1234-5678", Some(IssnKind::Print))]
+    #[case("1234-5678 Print", "1234-5678", Some(IssnKind::Print))]
+    #[case("pISSN: 1234-5678", "1234-5678", Some(IssnKind::Print))]
+    #[case("p-ISSN 1234-5678", "1234-5678", Some(IssnKind::Print))]
+    #[case("1234-5678 (Electronic)", "1234-5678", Some(IssnKind::Electronic))]
+    #[case("1234-5678 [electronic]", "1234-5678", Some(IssnKind::Electronic))]
+    #[case("1234-5678 (Online)", "1234-5678", Some(IssnKind::Electronic))]
+    #[case("eISSN 1234-5678", "1234-5678", Some(IssnKind::Electronic))]
+    #[case("e-ISSN: 1234-5678", "1234-5678", Some(IssnKind::Electronic))]
+    #[case("1234-5678 (Linking)", "1234-5678", Some(IssnKind::Linking))]
+    #[case("ISSN-L 1234-5678", "1234-5678", Some(IssnKind::Linking))]
+    #[case("ISSN: 1234-5678", "1234-5678", None)]
+    fn test_parse(#[case] text: &str, #[case] value: &str, #[case] kind: Option<IssnKind>) {
+        assert_eq!(
+            Issn::parse(text),
+            Some(Issn {
+                value: value.to_string(),
+                kind,
+            }),
+            "{text}"
+        );
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("(Print)")]
+    #[case("1234-567")]
+    #[case("123-45678")]
+    #[case("1234--5678")]
+    #[case("123456789")]
+    #[case("12X4-5678")]
+    #[case("978-0-12-345678-9")]
+    fn test_parse_rejects(#[case] text: &str) {
+        assert_eq!(Issn::parse(text), None, "{text}");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Issn::parse("1234-5678 (Online)").unwrap().to_string(), "1234-5678 (Electronic)");
+        assert_eq!(Issn::parse("12345678").unwrap().to_string(), "1234-5678");
+    }
+
+    #[test]
+    fn test_catalog() {
+        let mut catalog = IssnCatalog::new();
+        catalog
+            .add_pair("0028-4793 (Print)", "1533-4406")
+            .add_pair("not an issn", "1234-5678");
+        assert!(!catalog.is_empty());
+
+        let print = Issn::parse("00284793").unwrap();
+        assert_eq!(catalog.counterpart(&print).unwrap().to_string(), "1533-4406 (Electronic)");
+        let electronic = Issn::parse("1533-4406").unwrap();
+        assert_eq!(catalog.counterpart(&electronic).unwrap().to_string(), "0028-4793 (Print)");
+        assert_eq!(catalog.counterpart(&Issn::parse("1234-5678").unwrap()), None);
+        assert_eq!(catalog.counterpart(&Issn::parse("0028-4793 (Linking)").unwrap()), None);
+
+        let mut citation = Citation {
+            issn: vec!["1533-4406 (Electronic)".to_string(), "0028-4793".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(catalog.fill_counterparts(&mut citation), 0);
+        assert_eq!(citation.issn.len(), 2);
+    }
+}
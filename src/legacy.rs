@@ -0,0 +1,270 @@
+//! Deserialization of citations serialized by earlier releases.
+//!
+//! [`Citation`] and [`Author`] deserialize through the representations here,
+//! which accept both the current shape and the 0.2 shape:
+//!
+//! - A top-level `year` fills `date` when `date` is missing.
+//! - `id` and `source` move to `extra_fields` under [`crate::ID_FIELD`] and
+//!   [`crate::SOURCE_FIELD`].
+//! - Author `family_name` is read as `name`, an empty `given_name` as `None`,
+//!   and a single `affiliation` as `affiliations`.
+//! - Fields added since, such as `date_end`, `notes` or `author_count`, may be
+//!   missing.
+
+use crate::{
+    Author, Citation, CitationFormat, ConferenceInfo, Contributor, Date, LinkKind, RecordDateKind,
+    RetractionStatus,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Deserialized form of [`Citation`].
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct CitationRepr {
+    citation_type: Vec<String>,
+    title: String,
+    authors: Vec<Author>,
+    author_count: Option<usize>,
+    contributors: Vec<Contributor>,
+    journal: Option<String>,
+    journal_abbr: Option<String>,
+    date: Option<Date>,
+    date_end: Option<Date>,
+    electronic_date: Option<Date>,
+    record_dates: HashMap<RecordDateKind, Date>,
+    volume: Option<String>,
+    issue: Option<String>,
+    pages: Option<String>,
+    issn: Vec<String>,
+    doi: Option<String>,
+    pmid: Option<String>,
+    pmc_id: Option<String>,
+    abstract_text: Option<String>,
+    keywords: Vec<String>,
+    urls: Vec<String>,
+    link_kinds: HashMap<String, LinkKind>,
+    language: Option<String>,
+    mesh_terms: Vec<String>,
+    publisher: Option<String>,
+    notes: Option<String>,
+    conference: Option<ConferenceInfo>,
+    times_cited: Option<u32>,
+    retraction_status: Option<RetractionStatus>,
+    ingested_at: Option<String>,
+    parsed_from: Option<CitationFormat>,
+    extra_fields: HashMap<String, Vec<String>>,
+    /// Publication year, replaced by `date` in 0.3
+    year: Option<i32>,
+    /// Citation id, removed in 0.3
+    id: Option<String>,
+    /// Citation source, removed in 0.3
+    source: Option<String>,
+}
+
+impl From<CitationRepr> for Citation {
+    fn from(repr: CitationRepr) -> Self {
+        let date = repr.date.or_else(|| {
+            repr.year.map(|year| Date {
+                year,
+                month: None,
+                day: None,
+            })
+        });
+
+        let mut citation = Citation {
+            citation_type: repr.citation_type,
+            title: repr.title,
+            authors: repr.authors,
+            author_count: repr.author_count,
+            contributors: repr.contributors,
+            journal: repr.journal,
+            journal_abbr: repr.journal_abbr,
+            date,
+            date_end: repr.date_end,
+            electronic_date: repr.electronic_date,
+            record_dates: repr.record_dates,
+            volume: repr.volume,
+            issue: repr.issue,
+            pages: repr.pages,
+            issn: repr.issn,
+            doi: repr.doi,
+            pmid: repr.pmid,
+            pmc_id: repr.pmc_id,
+            abstract_text: repr.abstract_text,
+            keywords: repr.keywords,
+            urls: repr.urls,
+            link_kinds: repr.link_kinds,
+            language: repr.language,
+            mesh_terms: repr.mesh_terms,
+            publisher: repr.publisher,
+            notes: repr.notes,
+            conference: repr.conference,
+            times_cited: repr.times_cited,
+            retraction_status: repr.retraction_status,
+            ingested_at: repr.ingested_at,
+            parsed_from: repr.parsed_from,
+            extra_fields: repr.extra_fields,
+        };
+
+        // Values already stored under the field keys take precedence
+        for (key, value) in [(crate::ID_FIELD, repr.id), (crate::SOURCE_FIELD, repr.source)] {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                citation
+                    .extra_fields
+                    .entry(key.to_string())
+                    .or_insert_with(|| vec![value]);
+            }
+        }
+        citation
+    }
+}
+
+/// Deserialized form of [`Author`].
+#[derive(Deserialize)]
+pub(crate) struct AuthorRepr {
+    #[serde(alias = "family_name")]
+    name: String,
+    #[serde(default)]
+    given_name: Option<String>,
+    #[serde(default)]
+    middle_name: Option<String>,
+    #[serde(default)]
+    suffix: Option<String>,
+    #[serde(default)]
+    affiliations: Vec<String>,
+    /// Single affiliation, replaced by `affiliations` in 0.3
+    #[serde(default)]
+    affiliation: Option<String>,
+}
+
+impl From<AuthorRepr> for Author {
+    fn from(repr: AuthorRepr) -> Self {
+        let mut affiliations = repr.affiliations;
+        if affiliations.is_empty() {
+            affiliations.extend(repr.affiliation.filter(|a| !a.is_empty()));
+        }
+
+        Author {
+            name: repr.name,
+            given_name: repr.given_name.filter(|g| !g.is_empty()),
+            middle_name: repr.middle_name,
+            suffix: repr.suffix,
+            affiliations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// A citation as serialized by biblib 0.2.4.
+    const V02_CITATION: &str = r#"{
+        "id": "ckx1q2w3e0000abcd",
+        "citation_type": ["Journal Article"],
+        "title": "Effect of early mobilisation after cardiac surgery",
+        "authors": [
+            {"family_name": "Nguyen", "given_name": "Thi", "affiliation": "University of Sydney"},
+            {"family_name": "WHO", "given_name": "", "affiliation": null}
+        ],
+        "journal": "Journal of Clinical Nursing",
+        "journal_abbr": "J Clin Nurs",
+        "year": 2021,
+        "date": null,
+        "volume": "30",
+        "issue": "5-6",
+        "pages": "700-710",
+        "issn": ["0962-1067"],
+        "doi": "10.1111/jocn.15600",
+        "pmid": "33350000",
+        "pmc_id": null,
+        "abstract_text": "Background: ...",
+        "keywords": ["mobilisation", "cardiac surgery"],
+        "urls": [],
+        "language": "eng",
+        "mesh_terms": [],
+        "publisher": null,
+        "extra_fields": {"DB": ["Medline"]},
+        "source": "PubMed"
+    }"#;
+
+    #[test]
+    fn test_v02_citation() {
+        let citation: Citation = serde_json::from_str(V02_CITATION).unwrap();
+
+        assert_eq!(citation.title, "Effect of early mobilisation after cardiac surgery");
+        assert_eq!(citation.date.as_ref().map(|d| d.year), Some(2021));
+        assert_eq!(citation.date_end, None);
+        assert_eq!(citation.electronic_date, None);
+        assert_eq!(citation.notes, None);
+        assert_eq!(citation.author_count, None);
+        assert_eq!(citation.ingested_at, None);
+        assert_eq!(citation.parsed_from, None);
+        assert_eq!(citation.id(), Some("ckx1q2w3e0000abcd"));
+        assert_eq!(citation.source(), Some("PubMed"));
+        assert_eq!(citation.extra_fields["DB"], vec!["Medline"]);
+
+        assert_eq!(citation.authors[0].name, "Nguyen");
+        assert_eq!(citation.authors[0].given_name.as_deref(), Some("Thi"));
+        assert_eq!(citation.authors[0].affiliations, vec!["University of Sydney"]);
+        assert_eq!(citation.authors[1].given_name, None);
+        assert!(citation.authors[1].affiliations.is_empty());
+    }
+
+    #[test]
+    fn test_v02_date_takes_precedence_over_year() {
+        let json = r#"{"title": "T", "year": 2020, "date": {"year": 2019, "month": 12, "day": null}}"#;
+        let citation: Citation = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            citation.date,
+            Some(Date {
+                year: 2019,
+                month: Some(12),
+                day: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut citation: Citation = serde_json::from_str(V02_CITATION).unwrap();
+        citation.date_end = Some(Date {
+            year: 2021,
+            month: Some(6),
+            day: None,
+        });
+        citation.electronic_date = Some(Date {
+            year: 2020,
+            month: Some(12),
+            day: Some(21),
+        });
+        citation.notes = Some("A note".to_string());
+        citation.author_count = Some(2000);
+        citation.times_cited = Some(31);
+        citation.ingested_at = Some("2026-10-14T09:30:00Z".to_string());
+        citation.parsed_from = Some(CitationFormat::EndNoteXml);
+
+        let json = serde_json::to_string(&citation).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value.get("year").is_none());
+        let reparsed: Citation = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(reparsed.date, citation.date);
+        assert_eq!(reparsed.date_end, citation.date_end);
+        assert_eq!(reparsed.electronic_date, citation.electronic_date);
+        assert_eq!(reparsed.authors, citation.authors);
+        assert_eq!(reparsed.notes, citation.notes);
+        assert_eq!(reparsed.author_count, Some(2000));
+        assert_eq!(reparsed.times_cited, Some(31));
+        assert_eq!(reparsed.ingested_at, citation.ingested_at);
+        assert_eq!(reparsed.parsed_from, Some(CitationFormat::EndNoteXml));
+        assert_eq!(reparsed.extra_fields, citation.extra_fields);
+    }
+
+    #[test]
+    fn test_author_requires_name() {
+        assert!(serde_json::from_str::<Author>(r#"{"given_name": "John"}"#).is_err());
+    }
+}
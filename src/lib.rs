@@ -10,6 +10,7 @@
 //! - `csv` - Enable CSV format support (enabled by default)
 //! - `pubmed` - Enable PubMed/MEDLINE format support (enabled by default)  
 //! - `xml` - Enable EndNote XML support (enabled by default)
+//! - `enw` - Enable EndNote tagged (`.enw`) support (enabled by default)
 //! - `ris` - Enable RIS format support (enabled by default)
 //! - `dedupe` - Enable citation deduplication (enabled by default)
 //!
@@ -26,6 +27,7 @@
 //!   - RIS (Research Information Systems)
 //!   - PubMed/MEDLINE
 //!   - EndNote XML
+//!   - EndNote tagged (`.enw`)
 //!   - CSV with configurable mappings
 //!
 //! - **Rich Metadata Support**:
@@ -91,11 +93,11 @@
 //! use biblib::dedupe::{Deduplicator, DeduplicatorConfig};
 //!
 //! // Configure deduplication
-//! let config = DeduplicatorConfig {
-//!     group_by_year: true,
-//!     run_in_parallel: true,
-//!     ..Default::default()
-//! };
+//! let config = DeduplicatorConfig::builder()
+//!     .group_by_year(true)
+//!     .run_in_parallel(true)
+//!     .build()
+//!     .unwrap();
 //!
 //! let deduplicator = Deduplicator::new().with_config(config);
 //! let duplicate_groups = deduplicator.find_duplicates(&citations).unwrap();
@@ -131,7 +133,9 @@
 //!
 //! # Thread Safety
 //!
-//! All parser implementations are thread-safe and can be shared between threads.
+//! All parsers, writers and their configurations, the deduplicator and its
+//! configuration are `Send + Sync`, so one instance can be shared by reference
+//! between threads. This is checked at compile time by the test suite.
 //! The deduplicator supports parallel processing through the `run_in_parallel` option.
 
 use serde::{Deserialize, Serialize};
@@ -140,39 +144,85 @@ use std::collections::HashMap;
 #[cfg(feature = "csv")]
 extern crate csv as csv_crate;
 
+#[cfg(feature = "bibtex")]
+pub mod bibtex;
+pub mod content;
 #[cfg(feature = "csv")]
 pub mod csv;
+pub mod dataset;
 #[cfg(feature = "dedupe")]
 pub mod dedupe;
+pub mod diagnostics;
+pub mod doi;
 #[cfg(feature = "xml")]
 pub mod endnote_xml;
+#[cfg(feature = "enw")]
+pub mod enw;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filter;
+pub mod issn;
+#[cfg(feature = "migrate")]
+pub mod migrate;
+pub mod prelude;
 #[cfg(feature = "pubmed")]
 pub mod pubmed;
+pub mod report;
 #[cfg(feature = "ris")]
 pub mod ris;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "dedupe")]
+pub mod search;
+pub mod short_key;
+pub mod sort;
+pub mod text;
+pub mod transform;
+pub mod validation;
 
 // Reexports
+#[cfg(feature = "bibtex")]
+pub use bibtex::BibTexParser;
 #[cfg(feature = "csv")]
-pub use csv::CsvParser;
+pub use csv::{CsvParser, CsvWriter};
+pub use content::{ContentHashedCitation, ContentKey};
+pub use dataset::{MergedDataset, merge_datasets};
+pub use diagnostics::{Diagnostic, DiagnosticCode, Diagnostics};
 #[cfg(feature = "xml")]
-pub use endnote_xml::EndNoteXmlParser;
+pub use endnote_xml::{EndNoteXmlParser, EndNoteXmlWriter};
+#[cfg(feature = "enw")]
+pub use enw::EnwParser;
 pub use error::{CitationError, ParseError, ValueError};
+pub use issn::{Issn, IssnCatalog, IssnKind};
 #[cfg(feature = "pubmed")]
-pub use pubmed::PubMedParser;
+pub use pubmed::{PmidListParser, PubMedParser, PubMedWriter};
 #[cfg(feature = "ris")]
-pub use ris::RisParser;
+pub use ris::{RisParser, RisWriter};
+pub use validation::CitationType;
 
+mod legacy;
 mod regex;
 mod utils;
+#[cfg(any(feature = "csv", feature = "ris", feature = "xml", feature = "pubmed"))]
+mod writer;
 
 /// Citation format types supported by the library.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CitationFormat {
     Ris,
     PubMed,
     EndNoteXml,
+    /// EndNote tagged format (`.enw`)
+    Enw,
     Csv,
+    /// BibTeX format (`.bib`)
+    BibTex,
+    /// Bare list of PubMed IDs
+    PmidList,
+    /// Content made of sections in different formats, e.g. RIS followed by PubMed
+    Mixed,
     Unknown,
 }
 
@@ -183,7 +233,11 @@ impl CitationFormat {
             CitationFormat::Ris => "RIS",
             CitationFormat::PubMed => "PubMed",
             CitationFormat::EndNoteXml => "EndNote XML",
+            CitationFormat::Enw => "EndNote Tagged",
             CitationFormat::Csv => "CSV",
+            CitationFormat::BibTex => "BibTeX",
+            CitationFormat::PmidList => "PMID List",
+            CitationFormat::Mixed => "Mixed",
             CitationFormat::Unknown => "Unknown",
         }
     }
@@ -196,7 +250,11 @@ impl std::fmt::Display for CitationFormat {
 }
 
 /// Represents a publication date with required year and optional month/day components.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Dates are ordered by year, then month, then day, a missing month or day
+/// coming before any given one, so "2023" sorts before "2023 Jan".
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Date {
     /// Publication year (required)
     pub year: i32,
@@ -207,7 +265,12 @@ pub struct Date {
 }
 
 /// Represents an author of a citation.
+///
+/// Deserialization also accepts authors serialized by biblib 0.2, with
+/// `family_name` and a single `affiliation`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "legacy::AuthorRepr")]
 pub struct Author {
     /// The primary name of the person. This can be the family name or full name for mononyms.
     pub name: String,
@@ -218,12 +281,345 @@ pub struct Author {
     /// Optional middle name(s), when available.
     pub middle_name: Option<String>,
 
+    /// Optional generational suffix such as "Jr." or "III", as written.
+    pub suffix: Option<String>,
+
     /// List of affiliation strings associated with the author.
     pub affiliations: Vec<String>,
 }
 
+/// The part a person or group played in a work, see [`Citation::contributors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ContributorRole {
+    /// An author; parsers put authors in [`Citation::authors`] rather than in
+    /// `contributors`
+    Author,
+    /// An editor of the work or of the book or series it appears in: RIS `A2`,
+    /// `A3` and `ED`, PubMed `ED` and `FED`, EndNote secondary and tertiary
+    /// authors and `.enw` `%E` and `%Y`
+    Editor,
+    /// A translator: RIS `A4`, EndNote subsidiary authors and `.enw` `%?`
+    Translator,
+    /// An investigator or collaborator credited apart from the authors, PubMed
+    /// `IR` and `FIR`
+    Investigator,
+    /// A group or organization with authorship responsibility, PubMed `CN`
+    CorporateAuthor,
+}
+
+/// A person or group credited with a role in a work, see
+/// [`Citation::contributors`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Contributor {
+    /// The name, split into parts like an author's; a group has its whole name
+    /// in [`Author::name`]
+    pub person: Author,
+    /// What the person or group did
+    pub role: ContributorRole,
+}
+
+impl Contributor {
+    /// A contributor with the given role.
+    #[must_use]
+    pub fn new(person: Author, role: ContributorRole) -> Self {
+        Self { person, role }
+    }
+}
+
+/// Order of the parts of an author name written without a comma.
+///
+/// Names with a comma are always read as "Family, Given", and a generational
+/// suffix ("Jr.", "Sr.", "II", "III", "IV", "2nd", "3rd") is split off into
+/// [`Author::suffix`] wherever it appears. Cells show the family name, then the
+/// given names and any suffix:
+///
+/// | Name               | `FamilyFirst`     | `GivenFirst`      | `Auto`            |
+/// |--------------------|-------------------|-------------------|-------------------|
+/// | "Smith JA"         | Smith / JA        | JA / Smith        | Smith / JA        |
+/// | "John Smith"       | John / Smith      | Smith / John      | Smith / John      |
+/// | "J. Smith"         | J. / Smith        | Smith / J.        | Smith / J.        |
+/// | "John Smith Jr"    | John / Smith, Jr  | Smith / John, Jr  | Smith / John, Jr  |
+/// | "Garcia Lopez"     | Garcia / Lopez    | Lopez / Garcia    | Garcia / Lopez    |
+///
+/// `Auto` reads a name given-first only when its first word is an initial or a
+/// common given name and its last word is not an initial; anything else is
+/// read family-first, as the tagged formats write names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameOrder {
+    /// Family name first: "Smith John", "Smith JA"
+    #[default]
+    FamilyFirst,
+    /// Given names first, family name last: "John Smith"
+    GivenFirst,
+    /// Detect the order of each name with heuristics
+    Auto,
+}
+
+/// What a parser does with a record that has no title.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingTitle {
+    /// Fail with a `MissingValue` error
+    Error,
+    /// Leave the record out
+    Skip,
+    /// Use a placeholder naming the record, e.g. "[PMID 12345678]"
+    Placeholder,
+}
+
+/// Prefix for [`Citation::extra_fields`] keys that would otherwise repeat a
+/// field name the source format maps to a standard field.
+///
+/// For example, the second `TI` tag of an RIS record is kept under "x-TI".
+pub const EXTRA_FIELD_PREFIX: &str = "x-";
+
+/// Prefix for [`Citation::extra_fields`] keys holding a date as written, kept
+/// next to the parsed date by parsers in archival mode.
+///
+/// For example, [`RisParser::set_archival`] keeps the `PY` value read into
+/// [`Citation::date`] under "raw:PY".
+pub const RAW_FIELD_PREFIX: &str = "raw:";
+
+/// [`Citation::extra_fields`] key holding the name of the database or file a
+/// citation came from, e.g. `["PubMed"]`.
+///
+/// Parsers never set it; applications that track sources on the citation itself
+/// can use it in place of the `source` field removed in 0.3. The deduplicator
+/// reads it for [`dedupe::DeduplicatorConfig::source_preferences`] when no source
+/// is passed explicitly.
+pub const SOURCE_FIELD: &str = "source";
+
+/// [`Citation::extra_fields`] key holding an application-assigned identifier
+/// for a citation, e.g. a database primary key.
+///
+/// Parsers never set it. The deduplicator reads it to apply
+/// [`dedupe::Deduplicator::with_exclusions`].
+pub const ID_FIELD: &str = "id";
+
+/// [`Citation::extra_fields`] key holding database accession numbers, such as a
+/// Web of Science "WOS:" number, a Scopus EID or an Embase number.
+///
+/// The RIS parser keeps `AN` values here, and `ID` values that look like
+/// accession numbers. Keys other exports use can be moved here with
+/// [`transform::promote_extra_field`].
+pub const ACCESSION_FIELD: &str = "AN";
+
+/// [`Citation::extra_fields`] key set to `["true"]` on a record cut off by the
+/// end of the input, such as a final RIS record without an `ER` tag.
+///
+/// Parsers can drop such records instead, e.g. with
+/// [`RisParser::set_drop_truncated`].
+pub const TRUNCATED_FIELD: &str = "truncated";
+
+/// [`Citation::extra_fields`] key set to `["true"]` when the abstract was
+/// shortened by [`Citation::truncate_abstract`], e.g. through
+/// [`RisParser::set_max_abstract_length`].
+pub const ABSTRACT_TRUNCATED_FIELD: &str = "abstract_truncated";
+
+/// [`Citation::extra_fields`] key set to `["true"]` when the author list was
+/// shortened by [`Citation::truncate_authors`], e.g. through
+/// [`RisParser::set_max_authors`].
+pub const AUTHORS_TRUNCATED_FIELD: &str = "authors_truncated";
+
+/// [`Citation::extra_fields`] key set to `["true"]` when a record's authors were
+/// all placeholders, such as "Anonymous" or PubMed's "[No authors listed]".
+///
+/// Parsers drop such placeholders, and blank author entries, so `authors` is
+/// empty for these records.
+pub const ANONYMOUS_FIELD: &str = "anonymous";
+
+/// [`Citation::extra_fields`] key holding links to local attachment files,
+/// such as `file://` and `zotero://` links moved out of `urls` by
+/// [`RisParser::set_split_attachments`].
+pub const ATTACHMENTS_FIELD: &str = "attachments";
+
+/// The conference a proceedings record or conference abstract was presented at.
+///
+/// Parsers fill it for records whose type names a conference, such as RIS
+/// `CONF`/`CPAPER` or EndNote "Conference Proceedings", from RIS `T2`, `CY` and
+/// `Y2` or EndNote `secondary-title`, `pub-location` and `pub-dates`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConferenceInfo {
+    /// Conference name, e.g. "2016 IEEE Conference on Computer Vision and Pattern Recognition"
+    pub name: Option<String>,
+    /// Where the conference took place, e.g. "Las Vegas, NV, USA"
+    pub location: Option<String>,
+    /// When the conference took place, as written in the source, e.g. "27-30 June 2016"
+    pub date: Option<String>,
+}
+
+/// Whether a work was retracted or corrected, see [`Citation::retraction_status`].
+///
+/// Serialized as `{"Retracted": {"notice": ...}}`, `"RetractionNotice"` or
+/// `"HasErratum"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum RetractionStatus {
+    /// The work was retracted or withdrawn
+    Retracted {
+        /// Reference to the retraction notice, e.g. PubMed `RIN`
+        /// "Lancet. 2010 Feb 6;375(9713):445. PMID: 20137807"
+        notice: Option<String>,
+    },
+    /// The record is the retraction notice of another work
+    RetractionNotice,
+    /// An erratum to the work was published
+    HasErratum,
+}
+
+impl RetractionStatus {
+    /// Title prefixes marking a retracted or withdrawn work, lowercase.
+    const RETRACTED_PREFIXES: &[&str] = &[
+        "retracted:",
+        "retracted article:",
+        "[retracted]",
+        "withdrawn:",
+    ];
+
+    /// Title prefixes marking a retraction notice, lowercase.
+    const NOTICE_PREFIXES: &[&str] = &[
+        "retraction:",
+        "retraction notice",
+        "retraction note",
+        "retraction of",
+    ];
+
+    /// Reads the status from a title prefix, such as "Retracted:" or Elsevier's
+    /// "WITHDRAWN:" for a retracted work and "Retraction notice to:" for the
+    /// notice, in any case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::RetractionStatus;
+    ///
+    /// assert_eq!(
+    ///     RetractionStatus::from_title("WITHDRAWN: Exercise for depression"),
+    ///     Some(RetractionStatus::Retracted { notice: None })
+    /// );
+    /// assert_eq!(
+    ///     RetractionStatus::from_title("Retraction notice to: Exercise for depression"),
+    ///     Some(RetractionStatus::RetractionNotice)
+    /// );
+    /// assert_eq!(RetractionStatus::from_title("Exercise for depression"), None);
+    /// ```
+    #[must_use]
+    pub fn from_title(title: &str) -> Option<Self> {
+        let title = title.trim_start();
+        let has_prefix = |prefixes: &[&str]| {
+            prefixes.iter().any(|prefix| {
+                title
+                    .get(..prefix.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+            })
+        };
+        if has_prefix(Self::RETRACTED_PREFIXES) {
+            Some(RetractionStatus::Retracted { notice: None })
+        } else if has_prefix(Self::NOTICE_PREFIXES) {
+            Some(RetractionStatus::RetractionNotice)
+        } else {
+            None
+        }
+    }
+}
+
+/// A date in the life of a database record rather than of the work, see
+/// [`Citation::record_dates`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum RecordDateKind {
+    /// When the record was created, PubMed `CRDT`
+    Created,
+    /// When the record was added to the database, PubMed `EDAT`. This is the
+    /// date "new since the last search" alerts go by, as the publication date
+    /// often lies months before a record appears.
+    Entrez,
+    /// When MeSH terms were added to the record, PubMed `MHDA`; the same as the
+    /// Entrez date until the record is indexed
+    Mesh,
+}
+
+/// What a link in [`Citation::urls`] points to, see [`Citation::links`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LinkKind {
+    /// A web page, such as the publisher's landing page; RIS `UR` and `LK`
+    Web,
+    /// The full text of the work as a web page, RIS `L2`
+    FullText,
+    /// A PDF of the work, RIS `L1` or a URL ending in ".pdf"
+    Pdf,
+    /// A DOI resolver link such as `https://doi.org/10.1000/xyz`
+    DoiResolver,
+    /// A file on the computer that exported the record, a `file://` URL
+    LocalFile,
+}
+
+impl LinkKind {
+    /// The kind of `url` judging from the URL alone, if it shows one.
+    ///
+    /// `file://` URLs are local files, URLs on `doi.org` DOI resolvers and
+    /// URLs whose path ends in ".pdf" PDFs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::LinkKind;
+    ///
+    /// assert_eq!(LinkKind::from_url("https://doi.org/10.1000/xyz"), Some(LinkKind::DoiResolver));
+    /// assert_eq!(LinkKind::from_url("https://example.org/paper.PDF?dl=1"), Some(LinkKind::Pdf));
+    /// assert_eq!(LinkKind::from_url("https://example.org/article"), None);
+    /// ```
+    #[must_use]
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url.trim().to_ascii_lowercase();
+        if url.starts_with("file://") {
+            return Some(Self::LocalFile);
+        }
+        let rest = url.split_once("://").map_or(url.as_str(), |(_, rest)| rest);
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if host == "doi.org" || host.ends_with(".doi.org") {
+            return Some(Self::DoiResolver);
+        }
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        path.ends_with(".pdf").then_some(Self::Pdf)
+    }
+}
+
+/// A URL of a citation with what it points to, see [`Citation::links`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// The URL as stored in [`Citation::urls`]
+    pub url: String,
+    /// What the URL points to
+    pub kind: LinkKind,
+}
+
 /// Represents a single citation with its metadata.
+///
+/// # Extra fields
+///
+/// `extra_fields` is keyed by the field name in the source (an RIS tag, a CSV
+/// header, ...). Parsers never store data under a name they map to a standard
+/// field: values that do not fit a standard field, such as a repeated RIS `TI`
+/// or a second CSV column mapped to `doi`, are kept under the name prefixed
+/// with [`EXTRA_FIELD_PREFIX`]. Writers never let an extra field replace or
+/// duplicate a standard field in their output.
+///
+/// # Serialization
+///
+/// Fields missing from serialized input get their default value, so data
+/// written before a field was added still deserializes. Citations serialized
+/// by biblib 0.2 are accepted too: their `year` fills a missing `date`, and
+/// their `id` and `source` are moved to [`ID_FIELD`] and [`SOURCE_FIELD`].
+/// With the `migrate` feature, `migrate::from_v02_json` also converts 0.2 JSON
+/// whose values are not quite of the expected types.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(from = "legacy::CitationRepr")]
 pub struct Citation {
     /// Type of the citation
     pub citation_type: Vec<String>,
@@ -231,19 +627,36 @@ pub struct Citation {
     pub title: String,
     /// List of authors
     pub authors: Vec<Author>,
+    /// Number of authors in the source when `authors` holds only the first of
+    /// them (see [`Citation::truncate_authors`]); `None` when `authors` is complete
+    pub author_count: Option<usize>,
+    /// People and groups credited other than the authors, such as editors,
+    /// translators and investigators, with their roles; see
+    /// [`Citation::contributors_with_role`]
+    pub contributors: Vec<Contributor>,
     /// Journal name
     pub journal: Option<String>,
     /// Journal abbreviation
     pub journal_abbr: Option<String>,
     /// Publication date with year, month, and day
     pub date: Option<Date>,
+    /// End of the publication date when it is a range (e.g. the "Mar" of "2020 Jan-Mar");
+    /// `date` holds the start of the range
+    pub date_end: Option<Date>,
+    /// Date of electronic publication when the source gives it apart from
+    /// `date`, e.g. PubMed `DEP` for articles published online ahead of print
+    pub electronic_date: Option<Date>,
+    /// Dates of the database record, e.g. PubMed `EDAT`, without their time of
+    /// day; see [`Citation::entrez_date`]
+    pub record_dates: HashMap<RecordDateKind, Date>,
     /// Volume number
     pub volume: Option<String>,
     /// Issue number
     pub issue: Option<String>,
     /// Page range
     pub pages: Option<String>,
-    /// ISSN of the journal
+    /// ISSNs of the journal as written in the source, e.g. "1234-5678 (Print)";
+    /// see [`Citation::issns`] for the parsed values
     pub issn: Vec<String>,
     /// Digital Object Identifier
     pub doi: Option<String>,
@@ -257,12 +670,36 @@ pub struct Citation {
     pub keywords: Vec<String>,
     /// URLs
     pub urls: Vec<String>,
+    /// Kinds of URLs in `urls` known from the field they were read from, such
+    /// as RIS `L1` for [`LinkKind::Pdf`]; see [`Citation::links`]
+    pub link_kinds: HashMap<String, LinkKind>,
     /// Language
     pub language: Option<String>,
     /// MeSH Terms
     pub mesh_terms: Vec<String>,
     /// Publisher
     pub publisher: Option<String>,
+    /// Free-text notes, from RIS `N1` and `RN`, EndNote XML `<notes>` and
+    /// `<research-notes>` and `.enw` `%Z`. Separate notes from one record are
+    /// joined with a blank line ("\n\n"), general notes before research notes.
+    ///
+    /// Notes often hold screening decisions, so deduplication never compares them.
+    pub notes: Option<String>,
+    /// Name, location and dates of the conference, for conference records
+    pub conference: Option<ConferenceInfo>,
+    /// Number of times the work has been cited, as reported by the source database
+    pub times_cited: Option<u32>,
+    /// Whether the work was retracted or corrected, from PubMed `PT`, `RIN`,
+    /// `ROF` and `EIN` or a title prefix such as "Retracted:" or "WITHDRAWN:";
+    /// `None` when the record shows neither
+    pub retraction_status: Option<RetractionStatus>,
+    /// When the citation was parsed, as an RFC 3339 UTC timestamp such as
+    /// "2026-10-14T09:30:00Z"; set only when a parser is asked to
+    pub ingested_at: Option<String>,
+    /// The format of the record the citation was parsed from, set by every
+    /// parser, e.g. [`CitationFormat::PubMed`] for a PubMed section of mixed
+    /// input. Never [`CitationFormat::Mixed`] or [`CitationFormat::Unknown`].
+    pub parsed_from: Option<CitationFormat>,
     /// Additional fields not covered by standard fields
     pub extra_fields: HashMap<String, Vec<String>>,
 }
@@ -272,15 +709,937 @@ impl Citation {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// The source recorded under [`SOURCE_FIELD`] in `extra_fields`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut citation = Citation::new();
+    /// assert_eq!(citation.source(), None);
+    ///
+    /// citation.set_source("PubMed");
+    /// assert_eq!(citation.source(), Some("PubMed"));
+    /// ```
+    #[must_use]
+    pub fn source(&self) -> Option<&str> {
+        self.extra_fields
+            .get(SOURCE_FIELD)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    /// Record the source of this citation under [`SOURCE_FIELD`] in `extra_fields`,
+    /// replacing any previous source.
+    pub fn set_source(&mut self, source: &str) -> &mut Self {
+        self.extra_fields
+            .insert(SOURCE_FIELD.to_string(), vec![source.to_string()]);
+        self
+    }
+
+    /// The identifier recorded under [`ID_FIELD`] in `extra_fields`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut citation = Citation::new();
+    /// assert_eq!(citation.id(), None);
+    ///
+    /// citation.set_id("rec-42");
+    /// assert_eq!(citation.id(), Some("rec-42"));
+    /// ```
+    #[must_use]
+    pub fn id(&self) -> Option<&str> {
+        self.extra_fields
+            .get(ID_FIELD)
+            .and_then(|values| values.first())
+            .map(String::as_str)
+    }
+
+    /// Record an identifier for this citation under [`ID_FIELD`] in `extra_fields`,
+    /// replacing any previous identifier.
+    pub fn set_id(&mut self, id: &str) -> &mut Self {
+        self.extra_fields
+            .insert(ID_FIELD.to_string(), vec![id.to_string()]);
+        self
+    }
+
+    /// What identifies the work the citation describes, leaving out the id,
+    /// source and other fields that differ between records of one work.
+    ///
+    /// See [`ContentKey`] for the fields used, and [`ContentHashedCitation`]
+    /// to put citations in sets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, ContentKey};
+    ///
+    /// let citation = Citation {
+    ///     doi: Some("https://doi.org/10.1111/JOCN.15600".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(citation.content_key(), ContentKey::Doi("10.1111/jocn.15600".to_string()));
+    /// ```
+    #[must_use]
+    pub fn content_key(&self) -> ContentKey {
+        ContentKey::of(self)
+    }
+
+    /// A short, human-readable key such as "Smith2023JClinOncol": the family name
+    /// of the first author, the year and the journal abbreviation, folded to
+    /// ASCII, with title words standing in for missing pieces.
+    ///
+    /// Keys are not unique; see [`short_key::assign_unique_short_keys`] to
+    /// key a whole dataset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let citation = Citation {
+    ///     title: "Delirium in cardiac surgery".to_string(),
+    ///     journal_abbr: Some("J Clin Nurs".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(citation.short_key(), "DeliriumCardiacJClinNurs");
+    /// ```
+    #[must_use]
+    pub fn short_key(&self) -> String {
+        short_key::key_of(self, true)
+    }
+
+    /// Whether the work was retracted or withdrawn, see [`Citation::retraction_status`].
+    ///
+    /// A retraction notice is not itself retracted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, RetractionStatus};
+    ///
+    /// let mut citation = Citation::new();
+    /// assert!(!citation.is_retracted());
+    ///
+    /// citation.retraction_status = Some(RetractionStatus::Retracted { notice: None });
+    /// assert!(citation.is_retracted());
+    /// ```
+    #[must_use]
+    pub fn is_retracted(&self) -> bool {
+        matches!(self.retraction_status, Some(RetractionStatus::Retracted { .. }))
+    }
+
+    /// The date of the database record of the given kind, if the source gives it.
+    #[must_use]
+    pub fn record_date(&self, kind: RecordDateKind) -> Option<&Date> {
+        self.record_dates.get(&kind)
+    }
+
+    /// When the record was added to the database, e.g. PubMed `EDAT`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, Date, PubMedParser};
+    ///
+    /// let input = "PMID- 37171234\nTI  - Example\nEDAT- 2023/05/12 06:00\n";
+    /// let citation = &PubMedParser::new().parse(input).unwrap()[0];
+    /// assert_eq!(
+    ///     citation.entrez_date(),
+    ///     Some(&Date { year: 2023, month: Some(5), day: Some(12) })
+    /// );
+    /// ```
+    #[must_use]
+    pub fn entrez_date(&self) -> Option<&Date> {
+        self.record_date(RecordDateKind::Entrez)
+    }
+
+    /// The URLs of the citation with what each points to.
+    ///
+    /// A `file://` URL is a [`LinkKind::LocalFile`] and a `doi.org` URL a
+    /// [`LinkKind::DoiResolver`] wherever it was read from. Other URLs take the
+    /// kind in [`Citation::link_kinds`], set by parsers from the field they read
+    /// the URL from, then [`LinkKind::Pdf`] when the path ends in ".pdf" and
+    /// [`LinkKind::Web`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, LinkKind, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - Example\nL1  - https://example.org/download\n\
+    ///              UR  - https://doi.org/10.1000/xyz\nER  - \n";
+    /// let citation = &RisParser::new().parse(input).unwrap()[0];
+    /// let kinds: Vec<LinkKind> = citation.links().into_iter().map(|link| link.kind).collect();
+    /// assert_eq!(kinds, vec![LinkKind::Pdf, LinkKind::DoiResolver]);
+    /// ```
+    #[must_use]
+    pub fn links(&self) -> Vec<Link> {
+        self.urls
+            .iter()
+            .map(|url| {
+                let guessed = LinkKind::from_url(url);
+                let kind = match guessed {
+                    Some(kind @ (LinkKind::LocalFile | LinkKind::DoiResolver)) => kind,
+                    _ => self
+                        .link_kinds
+                        .get(url)
+                        .copied()
+                        .or(guessed)
+                        .unwrap_or(LinkKind::Web),
+                };
+                Link {
+                    url: url.clone(),
+                    kind,
+                }
+            })
+            .collect()
+    }
+
+    /// The URL best suited for opening the work, if there is one.
+    ///
+    /// Links are preferred by kind, see [`Citation::links`]: full text first,
+    /// then a PDF, a DOI resolver and any other web page. Among links of the
+    /// same kind the first one wins. Local files are never returned, as they
+    /// only exist on the computer that exported the record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let citation = Citation {
+    ///     urls: vec![
+    ///         "https://example.org/article".to_string(),
+    ///         "https://doi.org/10.1000/xyz".to_string(),
+    ///         "file:///home/me/paper.pdf".to_string(),
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(citation.best_url(), Some("https://doi.org/10.1000/xyz"));
+    /// ```
+    #[must_use]
+    pub fn best_url(&self) -> Option<&str> {
+        let rank = |kind: LinkKind| match kind {
+            LinkKind::FullText => Some(0),
+            LinkKind::Pdf => Some(1),
+            LinkKind::DoiResolver => Some(2),
+            LinkKind::Web => Some(3),
+            LinkKind::LocalFile => None,
+        };
+        let best = self
+            .links()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, link)| rank(link.kind).map(|rank| (rank, index)))
+            .min()?;
+        Some(self.urls[best.1].as_str())
+    }
+
+    /// Returns the ISSNs parsed with [`Issn::parse`], skipping values that hold
+    /// no ISSN.
+    ///
+    /// PubMed lists the print ISSN first, so when a citation parsed from PubMed
+    /// has several ISSNs and none names its medium, the first is returned as
+    /// [`IssnKind::Print`]; `issn` itself is left as written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, IssnKind};
+    ///
+    /// let citation = Citation {
+    ///     issn: vec!["1234-5678 (Print)".to_string(), "n/a".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let issns = citation.issns();
+    /// assert_eq!(issns.len(), 1);
+    /// assert_eq!(issns[0].value, "1234-5678");
+    /// assert_eq!(issns[0].kind, Some(IssnKind::Print));
+    /// ```
+    #[must_use]
+    pub fn issns(&self) -> Vec<Issn> {
+        let mut issns: Vec<Issn> = self.issn.iter().filter_map(|issn| Issn::parse(issn)).collect();
+        let unannotated = issns.len() == self.issn.len() && issns.iter().all(|i| i.kind.is_none());
+        if self.parsed_from == Some(CitationFormat::PubMed) && issns.len() > 1 && unannotated {
+            issns[0].kind = Some(IssnKind::Print);
+        }
+        issns
+    }
+
+    /// Remove duplicate entries from `keywords` and `mesh_terms`.
+    ///
+    /// Entries are compared case-insensitively, ignoring surrounding whitespace,
+    /// repeated inner whitespace and trailing periods. The first occurrence of each
+    /// entry is kept unchanged and the original order is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut citation = Citation {
+    ///     keywords: vec!["Humans".to_string(), "humans".to_string(), "Neoplasms.".to_string(), "neoplasms".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// citation.dedupe_keywords();
+    /// assert_eq!(citation.keywords, vec!["Humans", "Neoplasms."]);
+    /// ```
+    pub fn dedupe_keywords(&mut self) {
+        dedupe_terms(&mut self.keywords);
+        dedupe_terms(&mut self.mesh_terms);
+    }
+
+    /// Replace typographic characters in the text fields with ASCII equivalents.
+    ///
+    /// Applies [`text::fold_typography`] to the title, journal names, abstract,
+    /// notes, publisher, keywords and author names. Parsers do this when asked to,
+    /// e.g. with [`RisParser::set_fold_typography`]; the deduplicator always
+    /// compares folded titles, whether or not the stored fields are folded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut citation = Citation {
+    ///     title: "Alzheimer\u{2019}s disease \u{2013} a review".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// citation.fold_typography();
+    /// assert_eq!(citation.title, "Alzheimer's disease - a review");
+    /// ```
+    pub fn fold_typography(&mut self) {
+        let fold = |value: &mut String| {
+            if !value.is_ascii() {
+                *value = text::fold_typography(value);
+            }
+        };
+
+        fold(&mut self.title);
+        for value in [
+            &mut self.journal,
+            &mut self.journal_abbr,
+            &mut self.abstract_text,
+            &mut self.notes,
+            &mut self.publisher,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            fold(value);
+        }
+        self.keywords.iter_mut().for_each(fold);
+        for author in &mut self.authors {
+            fold(&mut author.name);
+            for value in [&mut author.given_name, &mut author.middle_name]
+                .into_iter()
+                .flatten()
+            {
+                fold(value);
+            }
+        }
+    }
+
+    /// Remove format-specific line breaks and spacing from the abstract.
+    ///
+    /// Applies [`text::clean_abstract`], and removes an abstract left empty.
+    /// Parsers do this unless told not to, e.g. with
+    /// [`RisParser::set_clean_abstracts`], after joining the lines of multi-line
+    /// fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut citation = Citation {
+    ///     abstract_text: Some("Background:\\par sepsis is\r\ncommon. ".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// citation.clean_abstract();
+    /// assert_eq!(citation.abstract_text.as_deref(), Some("Background: sepsis is common."));
+    /// ```
+    pub fn clean_abstract(&mut self) {
+        if let Some(text) = &self.abstract_text {
+            let cleaned = text::clean_abstract(text);
+            self.abstract_text = (!cleaned.is_empty()).then_some(cleaned);
+        }
+    }
+
+    /// Convert an all-caps title to sentence case.
+    ///
+    /// Applies [`text::to_sentence_case`] when [`text::is_all_caps`] holds for
+    /// the title, as in older MEDLINE records; other titles are left alone.
+    /// Parsers do this when asked to, e.g. with
+    /// [`RisParser::set_sentence_case_titles`]. Returns whether the title changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut citation = Citation {
+    ///     title: "DNA REPAIR IN E. COLI: A REVIEW".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(citation.sentence_case_title());
+    /// assert_eq!(citation.title, "DNA repair in E. coli: A review");
+    /// ```
+    pub fn sentence_case_title(&mut self) -> bool {
+        if !text::is_all_caps(&self.title) {
+            return false;
+        }
+        let recased = text::to_sentence_case(&self.title);
+        let changed = recased != self.title;
+        self.title = recased;
+        changed
+    }
+
+    /// Shorten the abstract to at most `max_chars` characters.
+    ///
+    /// A longer abstract is cut at a character boundary, trailing whitespace is
+    /// removed and "…" is appended, and [`ABSTRACT_TRUNCATED_FIELD`] is set in
+    /// `extra_fields`. Returns whether the abstract was shortened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{ABSTRACT_TRUNCATED_FIELD, Citation};
+    ///
+    /// let mut citation = Citation {
+    ///     abstract_text: Some("Background: sepsis is common.".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(citation.truncate_abstract(11));
+    /// assert_eq!(citation.abstract_text.as_deref(), Some("Background:…"));
+    /// assert!(citation.extra_fields.contains_key(ABSTRACT_TRUNCATED_FIELD));
+    /// ```
+    pub fn truncate_abstract(&mut self, max_chars: usize) -> bool {
+        let Some(text) = self.abstract_text.as_mut() else {
+            return false;
+        };
+        let Some((end, _)) = text.char_indices().nth(max_chars) else {
+            return false;
+        };
+        text.truncate(end);
+        text.truncate(text.trim_end().len());
+        text.push('…');
+        self.extra_fields
+            .insert(ABSTRACT_TRUNCATED_FIELD.to_string(), vec!["true".to_string()]);
+        true
+    }
+
+    /// Keeps the first `max_authors` authors, for consortium papers listing
+    /// thousands.
+    ///
+    /// When authors are dropped, `author_count` is set to the number of authors
+    /// before truncation (or kept, when the list was already truncated) and
+    /// [`AUTHORS_TRUNCATED_FIELD`] is set in `extra_fields`. Returns whether
+    /// authors were dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{AUTHORS_TRUNCATED_FIELD, Author, Citation};
+    ///
+    /// let author = |name: &str| Author {
+    ///     name: name.to_string(),
+    ///     given_name: None,
+    ///     middle_name: None,
+    ///     suffix: None,
+    ///     affiliations: Vec::new(),
+    /// };
+    /// let mut citation = Citation {
+    ///     authors: vec![author("Okafor"), author("Lindqvist"), author("Tanaka")],
+    ///     ..Default::default()
+    /// };
+    /// assert!(citation.truncate_authors(2));
+    /// assert_eq!(citation.authors.len(), 2);
+    /// assert_eq!(citation.author_count, Some(3));
+    /// assert!(citation.extra_fields.contains_key(AUTHORS_TRUNCATED_FIELD));
+    /// ```
+    pub fn truncate_authors(&mut self, max_authors: usize) -> bool {
+        if self.authors.len() <= max_authors {
+            return false;
+        }
+        self.author_count = Some(self.author_count.unwrap_or(0).max(self.authors.len()));
+        self.authors.truncate(max_authors);
+        self.extra_fields
+            .insert(AUTHORS_TRUNCATED_FIELD.to_string(), vec!["true".to_string()]);
+        true
+    }
+
+    /// The people and groups credited with `role`, in source order.
+    ///
+    /// For [`ContributorRole::Author`] these are [`Citation::authors`] followed by
+    /// any contributors with that role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Author, Citation, Contributor, ContributorRole};
+    ///
+    /// let person = |name: &str| Author {
+    ///     name: name.to_string(),
+    ///     given_name: None,
+    ///     middle_name: None,
+    ///     suffix: None,
+    ///     affiliations: Vec::new(),
+    /// };
+    /// let chapter = Citation {
+    ///     authors: vec![person("Okafor")],
+    ///     contributors: vec![
+    ///         Contributor::new(person("Lindqvist"), ContributorRole::Editor),
+    ///         Contributor::new(person("Tanaka"), ContributorRole::Translator),
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// let names = |role| {
+    ///     chapter.contributors_with_role(role).map(|a| a.name.as_str()).collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(names(ContributorRole::Author), vec!["Okafor"]);
+    /// assert_eq!(names(ContributorRole::Editor), vec!["Lindqvist"]);
+    /// assert!(names(ContributorRole::Investigator).is_empty());
+    /// ```
+    pub fn contributors_with_role(&self, role: ContributorRole) -> impl Iterator<Item = &Author> {
+        let authors = match role {
+            ContributorRole::Author => self.authors.as_slice(),
+            _ => &[],
+        };
+        authors.iter().chain(
+            self.contributors
+                .iter()
+                .filter(move |contributor| contributor.role == role)
+                .map(|contributor| &contributor.person),
+        )
+    }
+
+    /// The editors of the work, in source order; see
+    /// [`Citation::contributors_with_role`].
+    pub fn editors(&self) -> impl Iterator<Item = &Author> {
+        self.contributors_with_role(ContributorRole::Editor)
+    }
+
+    /// The type of this citation, read from the first label of `citation_type`
+    /// that names one; see [`CitationType::from_labels`].
+    #[must_use]
+    pub fn normalized_type(&self) -> CitationType {
+        CitationType::from_labels(&self.citation_type)
+    }
+
+    /// Checks that this citation has the fields its type calls for, by the default
+    /// [`validation::ValidationRules`]; use [`validation::validate`] to pass other
+    /// rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let book = Citation {
+    ///     citation_type: vec!["BOOK".to_string()],
+    ///     title: "Clinical Epidemiology".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let issues = book.validate();
+    /// assert_eq!(issues[0].to_string(), "error: publisher missing, required for a book");
+    /// ```
+    #[must_use]
+    pub fn validate(&self) -> Vec<validation::ValidationIssue> {
+        validation::validate(self, &validation::ValidationRules::default())
+    }
+
+    /// Whether this citation looks like a conference abstract rather than a full
+    /// article.
+    ///
+    /// Embase indexes conference abstracts from journal supplements, so they carry
+    /// a journal, volume and pages like the full paper published later. The
+    /// heuristic adds up signals from the parsed fields: a citation type naming a
+    /// conference (two points) or an abstract, a journal name naming a conference
+    /// or congress, "Suppl" in the volume or issue, pages that span at most two
+    /// pages or look like an abstract number ("S123", "OP-12"), and a missing DOI.
+    /// A score of three or more classifies the citation as a conference abstract.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let abstract_in_supplement = Citation {
+    ///     citation_type: vec!["JOUR".to_string(), "Conference Abstract".to_string()],
+    ///     journal: Some("Intensive Care Medicine Experimental".to_string()),
+    ///     volume: Some("9".to_string()),
+    ///     issue: Some("Suppl 1".to_string()),
+    ///     pages: Some("S112".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(abstract_in_supplement.is_conference_abstract());
+    ///
+    /// let article = Citation {
+    ///     citation_type: vec!["JOUR".to_string()],
+    ///     journal: Some("Intensive Care Medicine Experimental".to_string()),
+    ///     pages: Some("210-218".to_string()),
+    ///     doi: Some("10.5555/icme.2022.0042".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(!article.is_conference_abstract());
+    /// ```
+    #[must_use]
+    pub fn is_conference_abstract(&self) -> bool {
+        let mentions = |text: &str, words: &[&str]| {
+            let text = text.to_lowercase();
+            words.iter().any(|word| text.contains(word))
+        };
+
+        let mut score = 0;
+        if utils::is_conference_type(&self.citation_type) {
+            score += 2;
+        }
+        if self
+            .citation_type
+            .iter()
+            .any(|t| mentions(t, &["abstract"]) || t.eq_ignore_ascii_case("ABST"))
+        {
+            score += 1;
+        }
+        if [&self.journal, &self.journal_abbr]
+            .into_iter()
+            .flatten()
+            .any(|journal| mentions(journal, &["conference", "congress"]))
+        {
+            score += 1;
+        }
+        if [&self.volume, &self.issue]
+            .into_iter()
+            .flatten()
+            .any(|value| text::parse_volume(value).is_supplement())
+        {
+            score += 1;
+        }
+        if self.pages.as_deref().is_some_and(utils::is_abstract_pages) {
+            score += 1;
+        }
+        if self.doi.as_deref().is_none_or(|doi| doi.trim().is_empty()) {
+            score += 1;
+        }
+
+        score >= 3
+    }
+
+    /// Fill in fields that are missing from this citation with values from `other`.
+    ///
+    /// Scalar fields are copied only when they are `None` or empty, and the title,
+    /// citation type, authors and contributors only when empty. `issn` and `urls`
+    /// are extended with values not already present, `keywords` and `mesh_terms`
+    /// with terms not already present case-insensitively (see
+    /// [`Citation::dedupe_keywords`]), and `extra_fields` gains the keys it lacks
+    /// without changing existing ones.
+    /// `date` and `date_end` are copied together, and only when `date` is missing,
+    /// so a range is never assembled from two records. `record_dates` gains the
+    /// kinds it lacks and `link_kinds` the URLs it lacks. `notes` gains the notes of
+    /// `other` it lacks, joined with a blank line. `times_cited` takes the
+    /// larger of the two counts, as databases update them at different times,
+    /// `retraction_status` that of `other` when it is missing or `other` is
+    /// retracted, and `ingested_at` the earlier timestamp, compared as text.
+    /// `parsed_from` is never changed, so a merged record keeps its own format.
+    ///
+    /// Returns the number of fields that changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    ///
+    /// let mut local = Citation {
+    ///     title: "Example".to_string(),
+    ///     keywords: vec!["Sepsis".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let fetched = Citation {
+    ///     title: "Example (fetched)".to_string(),
+    ///     doi: Some("10.1000/example".to_string()),
+    ///     keywords: vec!["sepsis".to_string(), "Shock".to_string()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(local.merge_missing_from(&fetched), 2);
+    /// assert_eq!(local.title, "Example");
+    /// assert_eq!(local.doi.as_deref(), Some("10.1000/example"));
+    /// assert_eq!(local.keywords, vec!["Sepsis", "Shock"]);
+    /// ```
+    pub fn merge_missing_from(&mut self, other: &Citation) -> usize {
+        let mut changed = 0;
+        let mut count = |field_changed: bool| changed += usize::from(field_changed);
+
+        count(fill_vec(&mut self.citation_type, &other.citation_type));
+        if self.title.trim().is_empty() && !other.title.trim().is_empty() {
+            self.title.clone_from(&other.title);
+            count(true);
+        }
+        if fill_vec(&mut self.authors, &other.authors) {
+            self.author_count = other.author_count;
+            count(true);
+        }
+        count(fill_vec(&mut self.contributors, &other.contributors));
+        count(fill_option(&mut self.journal, &other.journal));
+        count(fill_option(&mut self.journal_abbr, &other.journal_abbr));
+        if self.date.is_none() && other.date.is_some() {
+            self.date.clone_from(&other.date);
+            count(true);
+            count(self.date_end != other.date_end);
+            self.date_end.clone_from(&other.date_end);
+        }
+        if self.electronic_date.is_none() && other.electronic_date.is_some() {
+            self.electronic_date.clone_from(&other.electronic_date);
+            count(true);
+        }
+        let record_dates = self.record_dates.len();
+        for (&kind, date) in &other.record_dates {
+            self.record_dates.entry(kind).or_insert_with(|| date.clone());
+        }
+        count(self.record_dates.len() != record_dates);
+        count(fill_option(&mut self.volume, &other.volume));
+        count(fill_option(&mut self.issue, &other.issue));
+        count(fill_option(&mut self.pages, &other.pages));
+        count(extend_missing(&mut self.issn, &other.issn));
+        count(fill_option(&mut self.doi, &other.doi));
+        count(fill_option(&mut self.pmid, &other.pmid));
+        count(fill_option(&mut self.pmc_id, &other.pmc_id));
+        count(fill_option(&mut self.abstract_text, &other.abstract_text));
+        count(extend_missing_terms(&mut self.keywords, &other.keywords));
+        count(extend_missing(&mut self.urls, &other.urls));
+        let link_kinds = self.link_kinds.len();
+        for (url, &kind) in &other.link_kinds {
+            self.link_kinds.entry(url.clone()).or_insert(kind);
+        }
+        count(self.link_kinds.len() != link_kinds);
+        count(fill_option(&mut self.language, &other.language));
+        count(extend_missing_terms(&mut self.mesh_terms, &other.mesh_terms));
+        count(fill_option(&mut self.publisher, &other.publisher));
+        count(merge_notes(&mut self.notes, &other.notes));
+        if self.conference.is_none() && other.conference.is_some() {
+            self.conference.clone_from(&other.conference);
+            count(true);
+        }
+        if other.times_cited > self.times_cited {
+            self.times_cited = other.times_cited;
+            count(true);
+        }
+        if other.retraction_status.is_some()
+            && (self.retraction_status.is_none() || other.is_retracted() && !self.is_retracted())
+        {
+            self.retraction_status.clone_from(&other.retraction_status);
+            count(true);
+        }
+        if let Some(ingested_at) = &other.ingested_at
+            && self.ingested_at.as_ref().is_none_or(|own| ingested_at < own)
+        {
+            self.ingested_at = Some(ingested_at.clone());
+            count(true);
+        }
+
+        let mut extra_changed = false;
+        for (key, values) in &other.extra_fields {
+            if !self.extra_fields.contains_key(key) {
+                self.extra_fields.insert(key.clone(), values.clone());
+                extra_changed = true;
+            }
+        }
+        count(extra_changed);
+
+        changed
+    }
+}
+
+/// Copies `other` into `value` if `value` is `None` or blank and `other` is not.
+fn fill_option(value: &mut Option<String>, other: &Option<String>) -> bool {
+    let is_blank = |v: &Option<String>| v.as_deref().is_none_or(|s| s.trim().is_empty());
+    if is_blank(value) && !is_blank(other) {
+        value.clone_from(other);
+        true
+    } else {
+        false
+    }
+}
+
+/// Appends the notes of `other` that `notes` lacks, each note being a block
+/// separated by a blank line.
+fn merge_notes(notes: &mut Option<String>, other: &Option<String>) -> bool {
+    let Some(other) = other else {
+        return false;
+    };
+    let mut merged: Vec<&str> = notes.as_deref().map_or_else(Vec::new, split_notes);
+    let before = merged.len();
+    for note in split_notes(other) {
+        if !merged.contains(&note) {
+            merged.push(note);
+        }
+    }
+    if merged.len() == before {
+        return false;
+    }
+    *notes = Some(merged.join("\n\n"));
+    true
+}
+
+fn split_notes(notes: &str) -> Vec<&str> {
+    notes
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|note| !note.is_empty())
+        .collect()
+}
+
+/// Copies `other` into `values` if `values` is empty.
+fn fill_vec<T: Clone>(values: &mut Vec<T>, other: &[T]) -> bool {
+    if values.is_empty() && !other.is_empty() {
+        values.extend_from_slice(other);
+        true
+    } else {
+        false
+    }
+}
+
+/// Appends the entries of `other` that are not already in `values`.
+fn extend_missing(values: &mut Vec<String>, other: &[String]) -> bool {
+    let before = values.len();
+    for value in other {
+        if !values.contains(value) {
+            values.push(value.clone());
+        }
+    }
+    values.len() != before
+}
+
+/// Appends the terms of `other` that are not already in `terms`, compared as in
+/// [`Citation::dedupe_keywords`]. Duplicates already in `terms` are left alone.
+fn extend_missing_terms(terms: &mut Vec<String>, other: &[String]) -> bool {
+    let mut seen: std::collections::HashSet<String> = terms.iter().map(|t| term_key(t)).collect();
+    let before = terms.len();
+    for term in other {
+        if seen.insert(term_key(term)) {
+            terms.push(term.clone());
+        }
+    }
+    terms.len() != before
+}
+
+/// Removes case-insensitive duplicates from a list of terms, keeping the first occurrence.
+fn dedupe_terms(terms: &mut Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+    terms.retain(|term| seen.insert(term_key(term)));
+}
+
+/// The comparison key of a keyword or MeSH term.
+fn term_key(term: &str) -> String {
+    term.trim()
+        .trim_end_matches('.')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
 }
 
 /// Represents a group of duplicate citations with one unique citation
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct DuplicateGroup {
     /// The unique (original) citation
     pub unique: Citation,
     /// The duplicate citations
     pub duplicates: Vec<Citation>,
+    /// How each duplicate compares with the unique citation, at the same
+    /// position as in `duplicates`. Empty for groups made by hand or read from
+    /// JSON written without it.
+    #[serde(default)]
+    pub match_info: Vec<MatchInfo>,
+}
+
+/// How a duplicate compares with the unique citation of its [`DuplicateGroup`],
+/// to justify the merge.
+///
+/// Under [`GroupingMode::TransitiveClosure`](crate::dedupe::GroupingMode), a
+/// duplicate may only match the unique citation through other members, so its
+/// reasons need not satisfy the matching rules on their own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MatchInfo {
+    /// Similarity of the titles from 0.0 to 1.0, the score passed to
+    /// `Deduplicator::for_each_duplicate_pair`
+    pub score: f64,
+    /// The fields the two citations agree on, always including the title
+    /// similarity
+    pub matched_on: Vec<MatchReason>,
+}
+
+/// A field on which a duplicate agrees with the unique citation, see [`MatchInfo`].
+///
+/// Fields are compared as the deduplicator compares them: normalized, and never
+/// matching when missing from either citation or ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum MatchReason {
+    /// The DOIs are equal
+    DoiExact,
+    /// The PMIDs are equal
+    PmidExact,
+    /// The PMC IDs are equal
+    PmcIdExact,
+    /// The similarity of the titles from 0.0 to 1.0
+    TitleSimilarity(f64),
+    /// The journal names or abbreviations are equal
+    JournalMatch,
+    /// The citations share an ISSN
+    IssnMatch,
+    /// The volume numbers are equal
+    VolumeMatch,
+    /// The pages are equal
+    PagesMatch,
+    /// The publication years are equal
+    YearMatch,
+    /// The first authors, or enough of the authors, have the same family name
+    AuthorMatch,
+    /// The similarity of the abstracts from 0.0 to 1.0, when abstracts are compared
+    AbstractSimilarity(f64),
+}
+
+impl DuplicateGroup {
+    /// Returns the unique citation with missing fields filled in from the duplicates.
+    ///
+    /// Duplicates are merged in order with [`Citation::merge_missing_from`], so
+    /// earlier duplicates take precedence over later ones.
+    #[must_use]
+    pub fn merged(&self) -> Citation {
+        let mut merged = self.unique.clone();
+        for duplicate in &self.duplicates {
+            merged.merge_missing_from(duplicate);
+        }
+        merged
+    }
+
+    /// Returns the duplicates with how each compares with the unique citation,
+    /// `None` when the group has no [`MatchInfo`] for it.
+    pub fn duplicates_with_info(&self) -> impl Iterator<Item = (&Citation, Option<&MatchInfo>)> {
+        self.duplicates
+            .iter()
+            .enumerate()
+            .map(|(i, duplicate)| (duplicate, self.match_info.get(i)))
+    }
+
+    /// Returns the format each duplicate was parsed from, in the order of
+    /// `duplicates`; [`DuplicateGroup::merged`] keeps that of the unique citation.
+    pub fn duplicate_formats(&self) -> impl Iterator<Item = Option<&CitationFormat>> {
+        self.duplicates
+            .iter()
+            .map(|duplicate| duplicate.parsed_from.as_ref())
+    }
 }
 
 /// Trait for implementing citation parsers.
@@ -299,6 +1658,143 @@ pub trait CitationParser {
     ///
     /// Returns `ParseError` if the input is malformed
     fn parse(&self, input: &str) -> std::result::Result<Vec<Citation>, crate::error::ParseError>;
+
+    /// Parse a string and append the citations to an existing vector.
+    ///
+    /// This avoids allocating a fresh vector per input when many files are parsed
+    /// into one collection. Existing elements of `out` are kept; on error, `out` is
+    /// left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The string containing citation data
+    /// * `out` - The vector to append parsed citations to
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of citations appended or a ParseError
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let parser = RisParser::new();
+    /// let mut citations = Vec::new();
+    /// for input in ["TY  - JOUR\nTI  - First\nER  -", "TY  - JOUR\nTI  - Second\nER  -"] {
+    ///     parser.parse_into(input, &mut citations).unwrap();
+    /// }
+    /// assert_eq!(citations.len(), 2);
+    /// ```
+    fn parse_into(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+    ) -> std::result::Result<usize, crate::error::ParseError> {
+        let citations = self.parse(input)?;
+        let count = citations.len();
+        out.extend(citations);
+        Ok(count)
+    }
+
+    /// Parse a string, skipping records that fail instead of failing the whole input.
+    ///
+    /// The errors of skipped records are returned alongside the citations, with
+    /// the record and line counts in [`LenientParse::stats`]. Errors that make the
+    /// rest of the input unreadable, such as malformed XML, still fail the parse.
+    /// The default implementation has no per-record recovery and parses like
+    /// [`CitationParser::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is unreadable, or if more records failed
+    /// than `options` allows; the message then gives the counts and the first
+    /// few failures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, LenientOptions, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - Kept\nER  - \n\nTY  - JOUR\nPY  - 2020\nER  - \n";
+    ///
+    /// let result = RisParser::new().parse_lenient(input, LenientOptions::default()).unwrap();
+    /// assert_eq!(result.citations.len(), 1);
+    /// assert_eq!(result.errors.len(), 1);
+    /// assert_eq!(result.stats.attempted, 2);
+    ///
+    /// let options = LenientOptions {
+    ///     max_errors: Some(0),
+    ///     ..Default::default()
+    /// };
+    /// assert!(RisParser::new().parse_lenient(input, options).is_err());
+    /// ```
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> std::result::Result<LenientParse, crate::error::ParseError> {
+        let citations = self.parse(input)?;
+        options.check(citations, Vec::new(), Vec::new())
+    }
+}
+
+/// Trait for implementing citation writers.
+///
+/// Writers stream their output to any [`std::io::Write`], so large collections
+/// can be written to a file or socket without building the whole output in memory.
+pub trait CitationWriter {
+    /// Write citations to `writer`.
+    ///
+    /// Records are written one at a time as the iterator is consumed. Wrap files
+    /// in a [`std::io::BufWriter`] to avoid one system call per field.
+    ///
+    /// # Arguments
+    ///
+    /// * `citations` - The citations to write
+    /// * `writer` - The destination for the formatted output
+    ///
+    /// # Errors
+    ///
+    /// Returns `CitationError::Io` if the underlying writer fails
+    fn write_to<'a, W: std::io::Write>(
+        &self,
+        citations: impl IntoIterator<Item = &'a Citation>,
+        writer: W,
+    ) -> std::result::Result<(), CitationError>;
+
+    /// Write citations to a new string.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CitationError::Io` if formatting fails
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{Citation, CitationWriter, RisWriter};
+    ///
+    /// let citation = Citation {
+    ///     title: "Example Title".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// let output = RisWriter::new().write_to_string([&citation]).unwrap();
+    /// assert!(output.contains("TI  - Example Title"));
+    /// ```
+    fn write_to_string<'a>(
+        &self,
+        citations: impl IntoIterator<Item = &'a Citation>,
+    ) -> std::result::Result<String, CitationError> {
+        let mut buf = Vec::new();
+        self.write_to(citations, &mut buf)?;
+        String::from_utf8(buf).map_err(|e| {
+            CitationError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })
+    }
 }
 
 /// Format detection and automatic parsing of citation files
@@ -312,6 +1808,11 @@ pub trait CitationParser {
 /// A Result containing a vector of parsed Citations and the detected format,
 /// or a CitationError if parsing fails
 ///
+/// Content that contains both RIS (`TY  -`) and PubMed (`PMID-`) records, such as
+/// an nbib export pasted after an RIS export, is split into contiguous sections.
+/// Each section is parsed with its own parser, the citations are concatenated in
+/// file order and the format is reported as [`CitationFormat::Mixed`].
+///
 /// # Examples
 ///
 /// ```
@@ -347,6 +1848,35 @@ pub fn detect_and_parse(
         return Err(CitationError::UnknownFormat);
     }
 
+    // Check for BibTeX format (starts with an entry such as @article{)
+    #[cfg(feature = "bibtex")]
+    if bibtex::starts_with_entry(content) {
+        return BibTexParser::new()
+            .parse(content)
+            .map(|citations| (citations, CitationFormat::BibTex))
+            .map_err(CitationError::Parse);
+    }
+
+    #[cfg(all(feature = "ris", feature = "pubmed"))]
+    {
+        let sections = format_sections(content);
+        if sections.len() > 1 {
+            let mut citations = Vec::new();
+            for section in sections {
+                let result = match section.format {
+                    CitationFormat::Ris => RisParser::new().parse_into(section.text, &mut citations),
+                    _ => PubMedParser::new().parse_into(section.text, &mut citations),
+                };
+                result.map_err(|mut e| {
+                    // Report lines relative to the whole input, not the section
+                    e.line = e.line.map(|line| line + section.first_line);
+                    CitationError::Parse(e)
+                })?;
+            }
+            return Ok((citations, CitationFormat::Mixed));
+        }
+    }
+
     // Check for RIS format (starts with TY or has TY  - pattern)
     if trimmed.starts_with("TY  -") || trimmed.contains("\nTY  -") {
         #[cfg(feature = "ris")]
@@ -375,29 +1905,695 @@ pub fn detect_and_parse(
         return Err(CitationError::UnknownFormat);
     }
 
+    // Check for EndNote tagged format (has a %0 reference type line)
+    if trimmed.starts_with("%0 ") || trimmed.contains("\n%0 ") {
+        #[cfg(feature = "enw")]
+        {
+            return EnwParser::new()
+                .parse(content)
+                .map(|citations| (citations, CitationFormat::Enw))
+                .map_err(CitationError::Parse);
+        }
+        #[cfg(not(feature = "enw"))]
+        return Err(CitationError::UnknownFormat);
+    }
+
+    // Check for a bare list of PMIDs, one or more per line
+    #[cfg(feature = "pubmed")]
+    if pubmed::is_pmid_list(content) {
+        return PmidListParser::new()
+            .parse(content)
+            .map(|citations| (citations, CitationFormat::PmidList))
+            .map_err(CitationError::Parse);
+    }
+
     Err(CitationError::UnknownFormat)
 }
 
+/// Limits on how much of a file [`detect_and_parse_strict`] may leave unparsed.
+///
+/// A file fails the check when it exceeds either limit. The default allows no
+/// unparsed lines at all.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::CoverageLimits;
+///
+/// // Tolerate a handful of stray lines, but never more than 1% of the file
+/// let limits = CoverageLimits {
+///     max_unparsed_lines: 5,
+///     max_unparsed_ratio: 0.01,
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CoverageLimits {
+    /// Maximum number of non-blank lines the parser may skip
+    pub max_unparsed_lines: usize,
+    /// Maximum share of non-blank lines the parser may skip, from 0.0 to 1.0
+    pub max_unparsed_ratio: f64,
+}
+
+impl CoverageLimits {
+    /// Number of unparsed line numbers listed in a coverage error.
+    const LISTED_LINES: usize = 5;
+
+    /// Checks the coverage of a parse that produced `parsed` citations.
+    fn check(
+        &self,
+        coverage: &utils::Coverage,
+        parsed: usize,
+        format: CitationFormat,
+    ) -> std::result::Result<(), ParseError> {
+        let unparsed = coverage.unparsed.len();
+        let ratio = if coverage.lines == 0 {
+            0.0
+        } else {
+            unparsed as f64 / coverage.lines as f64
+        };
+
+        if unparsed > self.max_unparsed_lines || ratio > self.max_unparsed_ratio {
+            let listed = coverage
+                .unparsed
+                .iter()
+                .take(Self::LISTED_LINES)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let more = if unparsed > Self::LISTED_LINES { ", ..." } else { "" };
+            return Err(ParseError::at_line(
+                coverage.unparsed[0],
+                format,
+                ValueError::Syntax(format!(
+                    "{} of {} lines were not parsed (lines {}{})",
+                    unparsed, coverage.lines, listed, more
+                )),
+            ));
+        }
+
+        if parsed != coverage.records {
+            return Err(ParseError::without_position(
+                format,
+                ValueError::Syntax(format!(
+                    "found {} record starts but parsed {} citations",
+                    coverage.records, parsed
+                )),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Limits on how many records [`CitationParser::parse_lenient`] may skip.
+///
+/// A parse fails when it exceeds either limit. The default sets no limits, so
+/// every readable record that converts is returned however many others fail.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::LenientOptions;
+///
+/// // Reject files where more than half of the records, or over 100 in all, fail
+/// let options = LenientOptions {
+///     max_error_ratio: Some(0.5),
+///     max_errors: Some(100),
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LenientOptions {
+    /// Maximum share of attempted records that may fail, from 0.0 to 1.0
+    pub max_error_ratio: Option<f64>,
+    /// Maximum number of records that may fail
+    pub max_errors: Option<usize>,
+}
+
+impl LenientOptions {
+    /// Number of failures listed in the error of a rejected parse.
+    const LISTED_ERRORS: usize = 3;
+
+    /// Builds the result of a lenient parse, or an error if it exceeds the limits.
+    ///
+    /// `ignored_lines` are the numbers of the non-blank lines the parser skipped.
+    pub(crate) fn check(
+        &self,
+        citations: Vec<Citation>,
+        errors: Vec<ParseError>,
+        ignored_lines: Vec<usize>,
+    ) -> std::result::Result<LenientParse, ParseError> {
+        let stats = ParseStats {
+            attempted: citations.len() + errors.len(),
+            succeeded: citations.len(),
+            failed: errors.len(),
+            lines_ignored: ignored_lines.len(),
+        };
+
+        let ratio = if stats.attempted == 0 {
+            0.0
+        } else {
+            stats.failed as f64 / stats.attempted as f64
+        };
+        let too_many = self.max_errors.is_some_and(|max| stats.failed > max)
+            || self.max_error_ratio.is_some_and(|max| ratio > max);
+
+        if too_many {
+            let listed = errors
+                .iter()
+                .take(Self::LISTED_ERRORS)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ");
+            let more = if stats.failed > Self::LISTED_ERRORS { "; ..." } else { "" };
+            return Err(ParseError::new(
+                errors[0].line,
+                None,
+                errors[0].format.clone(),
+                ValueError::Syntax(format!(
+                    "{} of {} records failed to parse ({}{})",
+                    stats.failed, stats.attempted, listed, more
+                )),
+            ));
+        }
+
+        let mut diagnostics: Diagnostics =
+            ignored_lines.into_iter().map(Diagnostic::line_ignored).collect();
+        diagnostics.extend(errors.iter().map(Diagnostic::record_skipped));
+        for (index, citation) in citations.iter().enumerate() {
+            if citation.extra_fields.contains_key(ABSTRACT_TRUNCATED_FIELD) {
+                diagnostics.push(Diagnostic::abstract_truncated(index));
+            }
+            if citation.extra_fields.contains_key(AUTHORS_TRUNCATED_FIELD) {
+                let count = citation.author_count.unwrap_or(citation.authors.len());
+                diagnostics.push(Diagnostic::authors_truncated(
+                    index,
+                    citation.authors.len(),
+                    count,
+                ));
+            }
+            if citation.extra_fields.contains_key(TRUNCATED_FIELD) {
+                diagnostics.push(Diagnostic::record_truncated(index));
+            }
+        }
+
+        Ok(LenientParse {
+            citations,
+            errors,
+            stats,
+            diagnostics,
+        })
+    }
+}
+
+/// Record and line counts of a lenient parse, for logging ingest quality.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Number of records the parser tried to convert
+    pub attempted: usize,
+    /// Number of records converted into citations
+    pub succeeded: usize,
+    /// Number of records skipped because they failed to convert
+    pub failed: usize,
+    /// Number of non-blank lines outside any record, or not in the format
+    pub lines_ignored: usize,
+}
+
+/// Result of [`CitationParser::parse_lenient`].
+#[derive(Debug)]
+pub struct LenientParse {
+    /// Citations of the records that parsed, in input order
+    pub citations: Vec<Citation>,
+    /// Errors of the records that failed, in input order
+    pub errors: Vec<ParseError>,
+    /// Record and line counts
+    pub stats: ParseStats,
+    /// Ignored lines, then skipped records, then shortened abstracts, author
+    /// lists and truncated records in citation order, then those particular to
+    /// the format such as unreadable dates; see [`diagnostics`]
+    pub diagnostics: Diagnostics,
+}
+
+/// Format detection and parsing that verifies the whole file matched the
+/// detected format.
+///
+/// Parses like [`detect_and_parse`], then cross-checks the result: the number of
+/// non-blank lines the parser skipped must be within `limits`, and the number of
+/// citations must equal the number of record start lines (`TY  -`, `PMID-`,
+/// `<record>` or `%0`). This catches files that are only partly in the detected format,
+/// such as an RIS export with pasted-in HTML, which [`detect_and_parse`] accepts.
+///
+/// # Errors
+///
+/// Returns the errors of [`detect_and_parse`], or a `CitationError::Parse` whose
+/// message lists the first few unparsed line numbers when a check fails.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{CoverageLimits, detect_and_parse, detect_and_parse_strict};
+///
+/// let content = "TY  - JOUR\nTI  - Example Title\n<div>pasted</div>\nER  - \n";
+///
+/// assert!(detect_and_parse(content).is_ok());
+/// let err = detect_and_parse_strict(content, CoverageLimits::default()).unwrap_err();
+/// assert!(err.to_string().contains("lines 3"));
+/// ```
+pub fn detect_and_parse_strict(
+    content: &str,
+    limits: CoverageLimits,
+) -> std::result::Result<(Vec<Citation>, CitationFormat), CitationError> {
+    let (citations, format) = detect_and_parse(content)?;
+
+    let coverage = match format {
+        #[cfg(feature = "ris")]
+        CitationFormat::Ris => Some(ris::ris_coverage(content)),
+        #[cfg(feature = "pubmed")]
+        CitationFormat::PubMed => Some(pubmed::pubmed_coverage(content)),
+        #[cfg(feature = "xml")]
+        CitationFormat::EndNoteXml => Some(endnote_xml::endnote_xml_coverage(content)),
+        #[cfg(feature = "enw")]
+        CitationFormat::Enw => Some(enw::enw_coverage(content)),
+        #[cfg(feature = "bibtex")]
+        CitationFormat::BibTex => Some(bibtex::bibtex_coverage(content)),
+        #[cfg(all(feature = "ris", feature = "pubmed"))]
+        CitationFormat::Mixed => {
+            let mut coverage = utils::Coverage::default();
+            for section in format_sections(content) {
+                let section_coverage = match section.format {
+                    CitationFormat::Ris => ris::ris_coverage(section.text),
+                    _ => pubmed::pubmed_coverage(section.text),
+                };
+                coverage.extend(section_coverage, section.first_line);
+            }
+            Some(coverage)
+        }
+        _ => None,
+    };
+
+    if let Some(coverage) = coverage {
+        limits
+            .check(&coverage, citations.len(), format.clone())
+            .map_err(CitationError::Parse)?;
+    }
+    Ok((citations, format))
+}
+
+/// A contiguous run of lines in a single tagged format.
+#[cfg(all(feature = "ris", feature = "pubmed"))]
+#[derive(Debug)]
+struct FormatSection<'a> {
+    format: CitationFormat,
+    /// Number of lines in the input before this section
+    first_line: usize,
+    text: &'a str,
+}
+
+/// Splits content into sections at every switch between RIS and PubMed records.
+///
+/// A section starts at a record start line (`TY  -` for RIS, `PMID-` for PubMed)
+/// whose format differs from the previous record's. Lines before the first record
+/// belong to the first section. Content in a single format yields one section.
+#[cfg(all(feature = "ris", feature = "pubmed"))]
+fn format_sections(content: &str) -> Vec<FormatSection<'_>> {
+    let mut sections = Vec::new();
+    let mut current: Option<(CitationFormat, usize, usize)> = None;
+    let mut offset = 0;
+
+    for (line_number, line) in content.split_inclusive('\n').enumerate() {
+        let format = if line.starts_with("TY  -") {
+            Some(CitationFormat::Ris)
+        } else if line.starts_with("PMID-") {
+            Some(CitationFormat::PubMed)
+        } else {
+            None
+        };
+
+        if let Some(format) = format {
+            match current.take() {
+                Some((current_format, first_line, start)) if current_format != format => {
+                    sections.push(FormatSection {
+                        format: current_format,
+                        first_line,
+                        text: &content[start..offset],
+                    });
+                    current = Some((format, line_number, offset));
+                }
+                Some(same) => current = Some(same),
+                None => current = Some((format, 0, 0)),
+            }
+        }
+
+        offset += line.len();
+    }
+
+    if let Some((format, first_line, start)) = current {
+        sections.push(FormatSection {
+            format,
+            first_line,
+            text: &content[start..],
+        });
+    }
+
+    sections
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_types_are_send_sync() {
+        assert_send_sync::<Citation>();
+        assert_send_sync::<DuplicateGroup>();
+        #[cfg(feature = "ris")]
+        assert_send_sync::<RisParser>();
+        #[cfg(feature = "ris")]
+        assert_send_sync::<RisWriter>();
+        #[cfg(feature = "pubmed")]
+        assert_send_sync::<PubMedParser>();
+        #[cfg(feature = "pubmed")]
+        assert_send_sync::<PmidListParser>();
+        #[cfg(feature = "pubmed")]
+        assert_send_sync::<PubMedWriter>();
+        #[cfg(feature = "xml")]
+        assert_send_sync::<EndNoteXmlParser>();
+        #[cfg(feature = "xml")]
+        assert_send_sync::<EndNoteXmlWriter>();
+        #[cfg(feature = "enw")]
+        assert_send_sync::<EnwParser>();
+        #[cfg(feature = "bibtex")]
+        assert_send_sync::<BibTexParser>();
+        #[cfg(feature = "csv")]
+        assert_send_sync::<CsvParser>();
+        #[cfg(feature = "csv")]
+        assert_send_sync::<CsvWriter>();
+        #[cfg(feature = "csv")]
+        assert_send_sync::<csv::CsvConfig>();
+        #[cfg(feature = "dedupe")]
+        assert_send_sync::<dedupe::Deduplicator>();
+        #[cfg(feature = "dedupe")]
+        assert_send_sync::<dedupe::DeduplicatorConfig>();
+        #[cfg(feature = "dedupe")]
+        assert_send_sync::<dedupe::DeduplicatorConfigBuilder>();
+    }
+
     #[test]
     fn test_author_equality() {
         let author1 = Author {
             name: "Smith".to_string(),
             given_name: Some("John".to_string()),
             middle_name: None,
+            suffix: None,
             affiliations: Vec::new(),
         };
         let author2 = Author {
             name: "Smith".to_string(),
             given_name: Some("John".to_string()),
             middle_name: None,
+            suffix: None,
             affiliations: Vec::new(),
         };
         assert_eq!(author1, author2);
     }
 
+    #[test]
+    fn test_dedupe_keywords() {
+        let mut citation = Citation {
+            keywords: vec![
+                "Humans".to_string(),
+                "humans".to_string(),
+                "HUMANS".to_string(),
+                "Neoplasms".to_string(),
+                " neoplasms. ".to_string(),
+                "Breast  Neoplasms".to_string(),
+                "breast neoplasms".to_string(),
+                "Animals".to_string(),
+            ],
+            mesh_terms: vec![
+                "Adult".to_string(),
+                "Aged".to_string(),
+                "adult.".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        citation.dedupe_keywords();
+        assert_eq!(
+            citation.keywords,
+            vec!["Humans", "Neoplasms", "Breast  Neoplasms", "Animals"]
+        );
+        assert_eq!(citation.mesh_terms, vec!["Adult", "Aged"]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "ris", feature = "pubmed"))]
+    fn test_merge_missing_from() {
+        let mut local = RisParser::new()
+            .parse(
+                "TY  - JOUR\nTI  - Vitamin D and respiratory infections\nAU  - Brennan, S.\n\
+                 PY  - 2019\nVL  - 77\nKW  - Vitamin D\nDB  - Embase\nER  - \n",
+            )
+            .unwrap()
+            .remove(0);
+        let fetched = PubMedParser::new()
+            .parse(
+                "PMID- 31000001\nTI  - Vitamin D supplementation and respiratory infections.\n\
+                 FAU - Brennan, Sarah\nFAU - Haddad, Omar\nDP  - 2019 Aug\nVI  - 78\n\
+                 IP  - 8\nPG  - 560-571\nLID - 10.5555/nrl.2019.077 [doi]\nOT  - vitamin d\n\
+                 OT  - Aged\nMH  - Humans\nLA  - eng\nJT  - Nutrition Reviews Letters\n\n",
+            )
+            .unwrap()
+            .remove(0);
+
+        let before = local.clone();
+        let changed = local.merge_missing_from(&fetched);
+
+        // journal, issue, pages, doi, pmid, language, mesh_terms and extra_fields
+        assert_eq!(changed, 8);
+        assert_eq!(local.title, before.title);
+        assert_eq!(local.authors, before.authors);
+        assert_eq!(local.date, before.date);
+        assert_eq!(local.volume.as_deref(), Some("77"));
+        assert_eq!(local.journal.as_deref(), Some("Nutrition Reviews Letters"));
+        assert_eq!(local.issue.as_deref(), Some("8"));
+        assert_eq!(local.pages.as_deref(), Some("560-571"));
+        assert_eq!(local.doi.as_deref(), Some("10.5555/nrl.2019.077"));
+        assert_eq!(local.pmid.as_deref(), Some("31000001"));
+        assert_eq!(local.language.as_deref(), Some("eng"));
+        assert_eq!(local.mesh_terms, vec!["Humans"]);
+        assert_eq!(local.extra_fields["DB"], vec!["Embase"]);
+        assert_eq!(local.extra_fields["OT"], vec!["vitamin d", "Aged"]);
+        assert_eq!(local.keywords, vec!["Vitamin D"]);
+
+        // Merging again changes nothing
+        assert_eq!(local.merge_missing_from(&fetched), 0);
+    }
+
+    #[test]
+    fn test_merge_missing_from_dates_and_terms() {
+        let date = |year, month| Date {
+            year,
+            month,
+            day: None,
+        };
+        let other = Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: "Other".to_string(),
+            date: Some(date(2020, Some(1))),
+            date_end: Some(date(2020, Some(3))),
+            keywords: vec!["sepsis.".to_string(), "Shock".to_string()],
+            urls: vec!["https://a.example".to_string(), "https://b.example".to_string()],
+            doi: Some("10.1000/other".to_string()),
+            ..Default::default()
+        };
+
+        let mut citation = Citation {
+            date: Some(date(2020, None)),
+            keywords: vec!["Sepsis".to_string()],
+            urls: vec!["https://a.example".to_string()],
+            doi: Some(String::new()),
+            ..Default::default()
+        };
+        // citation_type, title, doi, keywords and urls
+        assert_eq!(citation.merge_missing_from(&other), 5);
+        assert_eq!(citation.date, Some(date(2020, None)));
+        assert_eq!(citation.date_end, None);
+        assert_eq!(citation.keywords, vec!["Sepsis", "Shock"]);
+        assert_eq!(citation.urls.len(), 2);
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/other"));
+
+        let mut undated = Citation::default();
+        undated.merge_missing_from(&other);
+        assert_eq!(undated.date, other.date);
+        assert_eq!(undated.date_end, other.date_end);
+    }
+
+    #[test]
+    fn test_merge_concatenates_distinct_notes() {
+        let mut citation = Citation {
+            notes: Some("Include\n\nChecked by AB".to_string()),
+            ..Default::default()
+        };
+        let other = Citation {
+            notes: Some("Checked by AB\n\nFull text ordered".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(citation.merge_missing_from(&other), 1);
+        assert_eq!(
+            citation.notes.as_deref(),
+            Some("Include\n\nChecked by AB\n\nFull text ordered")
+        );
+        assert_eq!(citation.merge_missing_from(&other), 0);
+
+        let mut empty = Citation::default();
+        assert_eq!(empty.merge_missing_from(&other), 1);
+        assert_eq!(empty.notes, other.notes);
+    }
+
+    #[test]
+    fn test_truncate_abstract() {
+        // "é" and "…" are multi-byte, so byte offsets would split them
+        let mut citation = Citation {
+            abstract_text: Some("Résumé des études".to_string()),
+            ..Default::default()
+        };
+        assert!(!citation.truncate_abstract(17));
+        assert!(citation.extra_fields.is_empty());
+
+        assert!(citation.truncate_abstract(5));
+        assert_eq!(citation.abstract_text.as_deref(), Some("Résum…"));
+        assert_eq!(citation.extra_fields[ABSTRACT_TRUNCATED_FIELD], vec!["true"]);
+
+        // Cutting right after a space drops it before the ellipsis
+        let mut citation = Citation {
+            abstract_text: Some("Résumé des études".to_string()),
+            ..Default::default()
+        };
+        assert!(citation.truncate_abstract(7));
+        assert_eq!(citation.abstract_text.as_deref(), Some("Résumé…"));
+
+        assert!(!Citation::default().truncate_abstract(0));
+    }
+
+    #[test]
+    fn test_truncate_authors() {
+        let author = |name: &str| Author {
+            name: name.to_string(),
+            given_name: None,
+            middle_name: None,
+            suffix: None,
+            affiliations: Vec::new(),
+        };
+        let mut citation = Citation {
+            authors: ["A", "B", "C", "D"].into_iter().map(author).collect(),
+            ..Default::default()
+        };
+        assert!(!citation.truncate_authors(4));
+        assert_eq!(citation.author_count, None);
+        assert!(citation.extra_fields.is_empty());
+
+        assert!(citation.truncate_authors(3));
+        assert!(citation.truncate_authors(1));
+        assert_eq!(citation.authors, vec![author("A")]);
+        // The count stays that of the source
+        assert_eq!(citation.author_count, Some(4));
+        assert_eq!(citation.extra_fields[AUTHORS_TRUNCATED_FIELD], vec!["true"]);
+
+        // The count follows the authors when merged
+        let mut merged = Citation::default();
+        merged.merge_missing_from(&citation);
+        assert_eq!(merged.author_count, Some(4));
+    }
+
+    #[test]
+    fn test_duplicate_group_merged() {
+        let group = DuplicateGroup {
+            unique: Citation {
+                title: "Unique".to_string(),
+                ..Default::default()
+            },
+            duplicates: vec![
+                Citation {
+                    title: "First".to_string(),
+                    volume: Some("1".to_string()),
+                    ..Default::default()
+                },
+                Citation {
+                    title: "Second".to_string(),
+                    volume: Some("2".to_string()),
+                    issue: Some("3".to_string()),
+                    ..Default::default()
+                },
+            ],
+            match_info: Vec::new(),
+        };
+
+        let merged = group.merged();
+        assert_eq!(merged.title, "Unique");
+        assert_eq!(merged.volume.as_deref(), Some("1"));
+        assert_eq!(merged.issue.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn test_duplicate_group_formats() {
+        let parsed = |title: &str, format| Citation {
+            title: title.to_string(),
+            parsed_from: Some(format),
+            ..Default::default()
+        };
+        let group = DuplicateGroup {
+            unique: Citation {
+                volume: Some("1".to_string()),
+                ..parsed("Unique", CitationFormat::PubMed)
+            },
+            duplicates: vec![
+                parsed("First", CitationFormat::Ris),
+                Citation::new(),
+                parsed("Third", CitationFormat::Csv),
+            ],
+            match_info: Vec::new(),
+        };
+
+        assert_eq!(group.merged().parsed_from, Some(CitationFormat::PubMed));
+        assert_eq!(
+            group.duplicate_formats().collect::<Vec<_>>(),
+            vec![Some(&CitationFormat::Ris), None, Some(&CitationFormat::Csv)]
+        );
+    }
+
+    #[test]
+    fn test_merge_takes_max_times_cited() {
+        let cited = |times_cited| Citation {
+            title: "Cited".to_string(),
+            times_cited,
+            ..Default::default()
+        };
+        let group = DuplicateGroup {
+            unique: cited(Some(12)),
+            duplicates: vec![cited(Some(40)), cited(None), cited(Some(7))],
+            match_info: Vec::new(),
+        };
+        assert_eq!(group.merged().times_cited, Some(40));
+
+        let stamped = |ingested_at: &str| Citation {
+            ingested_at: Some(ingested_at.to_string()),
+            ..Default::default()
+        };
+        let mut citation = stamped("2026-03-02T10:00:00Z");
+        assert_eq!(citation.merge_missing_from(&stamped("2026-01-15T08:30:00Z")), 1);
+        assert_eq!(citation.merge_missing_from(&stamped("2026-05-01T00:00:00Z")), 0);
+        assert_eq!(citation.ingested_at.as_deref(), Some("2026-01-15T08:30:00Z"));
+
+        let mut uncounted = cited(None);
+        assert_eq!(uncounted.merge_missing_from(&cited(Some(3))), 1);
+        assert_eq!(uncounted.times_cited, Some(3));
+        assert_eq!(uncounted.merge_missing_from(&cited(None)), 0);
+        assert_eq!(uncounted.times_cited, Some(3));
+    }
+
     #[test]
     fn test_detect_and_parse_ris() {
         let content = r#"TY  - JOUR
@@ -433,6 +2629,104 @@ FAU - Smith, John"#;
         assert_eq!(citations[0].title, "Test Title");
     }
 
+    #[cfg(feature = "pubmed")]
+    #[test]
+    fn test_detect_and_parse_pmid_list() {
+        let content = "PMID: 12345678
+23456789, 34567890
+
+45678901
+";
+
+        let (citations, format) = detect_and_parse(content).unwrap();
+        assert_eq!(format, CitationFormat::PmidList);
+        assert_eq!(citations.len(), 4);
+        assert_eq!(citations[3].pmid.as_deref(), Some("45678901"));
+    }
+
+    #[cfg(all(feature = "ris", feature = "pubmed"))]
+    #[test]
+    fn test_detect_and_parse_mixed() {
+        let content = "TY  - JOUR\nTI  - First RIS\nER  - \n\nTY  - JOUR\nTI  - Second RIS\nER  - \n\n\
+            PMID- 1\nTI  - First PubMed\n\nPMID- 2\nTI  - Second PubMed\n\n\
+            TY  - JOUR\nTI  - Third RIS\nER  - \n";
+
+        let (citations, format) = detect_and_parse(content).unwrap();
+        assert_eq!(format, CitationFormat::Mixed);
+        assert_eq!(
+            citations.iter().map(|c| c.title.as_str()).collect::<Vec<_>>(),
+            vec!["First RIS", "Second RIS", "First PubMed", "Second PubMed", "Third RIS"]
+        );
+    }
+
+    #[cfg(all(feature = "ris", feature = "pubmed"))]
+    #[test]
+    fn test_format_sections() {
+        let content = "preamble\nTY  - JOUR\nER  - \nTY  - JOUR\nER  - \nPMID- 1\nTI  - x\n";
+        let sections = format_sections(content);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].format, CitationFormat::Ris);
+        assert_eq!(sections[0].first_line, 0);
+        assert!(sections[0].text.starts_with("preamble\n"));
+        assert_eq!(sections[1].format, CitationFormat::PubMed);
+        assert_eq!(sections[1].first_line, 5);
+        assert_eq!(sections[1].text, "PMID- 1\nTI  - x\n");
+
+        assert_eq!(format_sections("TY  - JOUR\nER  - \nTY  - JOUR\n").len(), 1);
+        assert!(format_sections("no records").is_empty());
+    }
+
+    #[cfg(feature = "ris")]
+    #[test]
+    fn test_detect_and_parse_strict() {
+        // 8 RIS lines and 2 garbage lines
+        let content = "TY  - JOUR\nTI  - First\nAU  - Smith, John\nER  - \n\
+            <html><body>\n\
+            TY  - JOUR\nTI  - Second\nPY  - 2020\nER  - \n\
+            ##### corrupted #####\n";
+
+        let (citations, _) = detect_and_parse(content).unwrap();
+        assert_eq!(citations.len(), 2);
+
+        let err = detect_and_parse_strict(content, CoverageLimits::default()).unwrap_err();
+        let CitationError::Parse(err) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(err.line, Some(5));
+        assert_eq!(err.format, CitationFormat::Ris);
+        assert!(err.to_string().contains("2 of 10 lines were not parsed (lines 5, 10)"));
+
+        let tolerant = CoverageLimits {
+            max_unparsed_lines: 2,
+            max_unparsed_ratio: 0.2,
+        };
+        let (citations, format) = detect_and_parse_strict(content, tolerant).unwrap();
+        assert_eq!(citations.len(), 2);
+        assert_eq!(format, CitationFormat::Ris);
+
+        let ratio_only = CoverageLimits {
+            max_unparsed_lines: 2,
+            max_unparsed_ratio: 0.1,
+        };
+        assert!(detect_and_parse_strict(content, ratio_only).is_err());
+    }
+
+    #[cfg(all(feature = "ris", feature = "pubmed"))]
+    #[test]
+    fn test_detect_and_parse_strict_mixed() {
+        let content = "TY  - JOUR\nTI  - RIS\nER  - \n\nPMID- 1\nTI  - PubMed\n      continued\n\
+            garbage line\n      with continuation\n";
+
+        assert_eq!(detect_and_parse(content).unwrap().0.len(), 2);
+        let err = detect_and_parse_strict(content, CoverageLimits::default()).unwrap_err();
+        assert!(err.to_string().contains("lines 8, 9"), "{err}");
+
+        let clean = &content[..content.find("garbage").unwrap()];
+        let (citations, format) = detect_and_parse_strict(clean, CoverageLimits::default()).unwrap();
+        assert_eq!(citations.len(), 2);
+        assert_eq!(format, CitationFormat::Mixed);
+    }
+
     #[test]
     fn test_detect_and_parse_empty() {
         let result = detect_and_parse("");
@@ -447,4 +2741,27 @@ FAU - Smith, John"#;
         let result = detect_and_parse(content);
         assert!(matches!(result, Err(CitationError::UnknownFormat)));
     }
+
+    #[test]
+    fn test_parse_into_default_implementation() {
+        struct LineParser;
+
+        impl CitationParser for LineParser {
+            fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+                Ok(input
+                    .lines()
+                    .map(|line| Citation {
+                        title: line.to_string(),
+                        ..Default::default()
+                    })
+                    .collect())
+            }
+        }
+
+        let mut citations = vec![Citation::new()];
+        let count = LineParser.parse_into("one\ntwo", &mut citations).unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[2].title, "two");
+    }
 }
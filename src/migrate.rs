@@ -0,0 +1,417 @@
+//! Migration of citations serialized by biblib 0.2.
+//!
+//! [`Citation`] already deserializes the 0.2 shape (see [`Citation`]'s
+//! documentation), but only when every value has the type it expects. Stores
+//! written over several releases, or edited by other tools, also hold single
+//! values where lists are expected, numbers where strings are expected, and
+//! `null` where 0.2 wrote nothing. [`from_v02_json`] converts those before
+//! deserializing, and reports what cannot be converted with the path of the
+//! field at fault. [`is_v02_shape`] tells the old shape from the current one,
+//! for stores that hold both.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::migrate::{from_v02_json, is_v02_shape};
+//! use serde_json::json;
+//!
+//! let value = json!({
+//!     "id": "ckx1q2w3e0000abcd",
+//!     "title": "Effect of early mobilisation after cardiac surgery",
+//!     "authors": [{"family_name": "Nguyen", "given_name": "Thi", "affiliation": null}],
+//!     "year": 2021,
+//!     "pmid": 33350000,
+//!     "keywords": "mobilisation",
+//!     "extra_fields": {"DB": "Medline"},
+//!     "source": "PubMed"
+//! });
+//! assert!(is_v02_shape(&value));
+//!
+//! let citation = from_v02_json(value).unwrap();
+//! assert_eq!(citation.date.as_ref().map(|d| d.year), Some(2021));
+//! assert_eq!(citation.pmid.as_deref(), Some("33350000"));
+//! assert_eq!(citation.keywords, vec!["mobilisation"]);
+//! assert_eq!(citation.extra_fields["DB"], vec!["Medline"]);
+//! assert_eq!(citation.source(), Some("PubMed"));
+//! ```
+
+use crate::Citation;
+use serde_json::{Map, Value};
+
+/// Top-level fields of the 0.2 shape that later releases no longer write.
+const V02_ONLY_FIELDS: [&str; 3] = ["year", "id", "source"];
+
+/// Author fields of the 0.2 shape that later releases no longer write.
+const V02_ONLY_AUTHOR_FIELDS: [&str; 2] = ["family_name", "affiliation"];
+
+/// Fields holding a list of strings, which may be stored as a single string.
+const LIST_FIELDS: [&str; 5] = ["citation_type", "issn", "keywords", "urls", "mesh_terms"];
+
+/// Fields holding a string, which may be stored as a number.
+const STRING_FIELDS: [&str; 15] = [
+    "title",
+    "journal",
+    "journal_abbr",
+    "volume",
+    "issue",
+    "pages",
+    "doi",
+    "pmid",
+    "pmc_id",
+    "abstract_text",
+    "language",
+    "publisher",
+    "notes",
+    "id",
+    "source",
+];
+
+/// A value that cannot be converted to its field in [`Citation`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Cannot migrate field `{field}`: {reason}")]
+pub struct MigrationError {
+    /// Path of the field, such as `"date"`, `"authors[2]"` or `"extra_fields.DB"`
+    pub field: String,
+    /// Why the value was rejected
+    pub reason: String,
+}
+
+impl MigrationError {
+    fn new(field: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Whether `value` is a citation in the shape written by biblib 0.2.
+///
+/// The shape is recognized by the fields later releases no longer write: a
+/// top-level `year`, `id` or `source`, or an author with `family_name` or
+/// `affiliation`. A citation that has none of them deserializes the same way
+/// in either shape.
+#[must_use]
+pub fn is_v02_shape(value: &Value) -> bool {
+    let Some(object) = value.as_object() else {
+        return false;
+    };
+    V02_ONLY_FIELDS
+        .iter()
+        .any(|field| object.contains_key(*field))
+        || object
+            .get("authors")
+            .and_then(Value::as_array)
+            .is_some_and(|authors| {
+                authors.iter().filter_map(Value::as_object).any(|author| {
+                    V02_ONLY_AUTHOR_FIELDS
+                        .iter()
+                        .any(|field| author.contains_key(*field))
+                })
+            })
+}
+
+/// Converts a citation serialized by biblib 0.2 into a [`Citation`].
+///
+/// On top of what deserializing [`Citation`] accepts, this:
+///
+/// - reads a `year` given as a string, such as `"2021"`
+/// - reads a single string as a one-item list in `citation_type`, `issn`,
+///   `keywords`, `urls` and `mesh_terms`, and `null` as an empty list
+/// - reads numbers in string fields such as `pmid` or `volume` as strings
+/// - reads `extra_fields` values given as a single string, number or boolean
+///   as one-item lists, and drops `null` values
+/// - reads a `null` `title`, `authors` or `extra_fields` as empty
+///
+/// Citations in the current shape are returned as they would deserialize.
+///
+/// # Errors
+///
+/// Returns a [`MigrationError`] naming the first field whose value cannot be
+/// converted, such as an author without a name or a `date` without a year.
+pub fn from_v02_json(value: Value) -> Result<Citation, MigrationError> {
+    let Value::Object(mut object) = value else {
+        return Err(MigrationError::new(
+            "",
+            format!("expected a JSON object, found {}", kind(&value)),
+        ));
+    };
+
+    normalize_year(&mut object)?;
+    for field in LIST_FIELDS {
+        normalize_list(&mut object, field)?;
+    }
+    for field in STRING_FIELDS {
+        normalize_string(&mut object, field)?;
+    }
+    normalize_extra_fields(&mut object)?;
+    if object.get("authors").is_some_and(Value::is_null) {
+        object.remove("authors");
+    }
+
+    // Deserializing field by field is only needed to find the one at fault
+    serde_json::from_value(Value::Object(object.clone())).map_err(|error| {
+        locate_error(&object).unwrap_or_else(|| MigrationError::new("", error.to_string()))
+    })
+}
+
+/// Reads a numeric string in `year` as a number.
+fn normalize_year(object: &mut Map<String, Value>) -> Result<(), MigrationError> {
+    match object.get("year") {
+        Some(Value::Null) => {
+            object.remove("year");
+        }
+        Some(Value::String(year)) => {
+            let year = year.trim();
+            if year.is_empty() {
+                object.remove("year");
+            } else {
+                let year: i32 = year.parse().map_err(|_| {
+                    MigrationError::new("year", format!("\"{year}\" is not a year"))
+                })?;
+                object.insert("year".to_string(), Value::from(year));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Reads a single value or `null` in a list-of-strings field as a list.
+fn normalize_list(object: &mut Map<String, Value>, field: &str) -> Result<(), MigrationError> {
+    let Some(value) = object.get_mut(field) else {
+        return Ok(());
+    };
+    let items = match value.take() {
+        Value::Null => Vec::new(),
+        Value::Array(items) => items,
+        single => vec![single],
+    };
+    let mut list = Vec::with_capacity(items.len());
+    for (index, item) in items.into_iter().enumerate() {
+        if let Some(item) = scalar_to_string(&item) {
+            list.push(Value::String(item));
+        } else if !item.is_null() {
+            return Err(MigrationError::new(
+                format!("{field}[{index}]"),
+                format!("expected a string, found {}", kind(&item)),
+            ));
+        }
+    }
+    *value = Value::Array(list);
+    Ok(())
+}
+
+/// Reads a number or boolean in a string field as a string.
+fn normalize_string(object: &mut Map<String, Value>, field: &str) -> Result<(), MigrationError> {
+    let Some(value) = object.get_mut(field) else {
+        return Ok(());
+    };
+    match value {
+        Value::Null | Value::String(_) => {}
+        Value::Number(_) | Value::Bool(_) => {
+            *value = Value::String(scalar_to_string(value).unwrap_or_default());
+        }
+        other => {
+            return Err(MigrationError::new(
+                field,
+                format!("expected a string, found {}", kind(other)),
+            ));
+        }
+    }
+    // `title` is not optional
+    if field == "title" && value.is_null() {
+        object.remove(field);
+    }
+    Ok(())
+}
+
+/// Reads `extra_fields` values given as single values as lists.
+fn normalize_extra_fields(object: &mut Map<String, Value>) -> Result<(), MigrationError> {
+    let Some(value) = object.get_mut("extra_fields") else {
+        return Ok(());
+    };
+    let fields = match value.take() {
+        Value::Null => Map::new(),
+        Value::Object(fields) => fields,
+        other => {
+            return Err(MigrationError::new(
+                "extra_fields",
+                format!("expected an object, found {}", kind(&other)),
+            ));
+        }
+    };
+
+    let mut normalized = Map::with_capacity(fields.len());
+    for (key, field_value) in fields {
+        let items = match field_value {
+            Value::Null => continue,
+            Value::Array(items) => items,
+            single => vec![single],
+        };
+        let mut values = Vec::with_capacity(items.len());
+        for (index, item) in items.into_iter().enumerate() {
+            if let Some(item) = scalar_to_string(&item) {
+                values.push(Value::String(item));
+            } else if !item.is_null() {
+                return Err(MigrationError::new(
+                    format!("extra_fields.{key}[{index}]"),
+                    format!("expected a string, found {}", kind(&item)),
+                ));
+            }
+        }
+        if !values.is_empty() {
+            normalized.insert(key, Value::Array(values));
+        }
+    }
+    *value = Value::Object(normalized);
+    Ok(())
+}
+
+/// Finds the field of `object` that fails to deserialize.
+fn locate_error(object: &Map<String, Value>) -> Option<MigrationError> {
+    let fails = |value: Value| serde_json::from_value::<Citation>(value).err();
+
+    object.iter().find_map(|(field, value)| {
+        if let (Some(authors), "authors") = (value.as_array(), field.as_str()) {
+            return authors.iter().enumerate().find_map(|(index, author)| {
+                let single = Value::Object(Map::from_iter([(
+                    "authors".to_string(),
+                    Value::Array(vec![author.clone()]),
+                )]));
+                fails(single).map(|error| {
+                    MigrationError::new(format!("authors[{index}]"), error.to_string())
+                })
+            });
+        }
+        let single = Value::Object(Map::from_iter([(field.clone(), value.clone())]));
+        fails(single).map(|error| MigrationError::new(field.as_str(), error.to_string()))
+    })
+}
+
+/// A string, number or boolean as a string.
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// The kind of a JSON value, for error messages.
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Date;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_is_v02_shape() {
+        assert!(is_v02_shape(&json!({"title": "T", "year": 2020})));
+        assert!(is_v02_shape(&json!({"title": "T", "source": null})));
+        assert!(is_v02_shape(
+            &json!({"title": "T", "authors": [{"family_name": "Smith"}]})
+        ));
+        assert!(!is_v02_shape(
+            &json!({"title": "T", "authors": [{"name": "Smith"}], "date": {"year": 2020}})
+        ));
+        assert!(!is_v02_shape(&json!(["year"])));
+
+        let current = serde_json::to_value(Citation::new()).unwrap();
+        assert!(!is_v02_shape(&current));
+    }
+
+    #[test]
+    fn test_lenient_values() {
+        let citation = from_v02_json(json!({
+            "title": null,
+            "authors": null,
+            "year": " 1998 ",
+            "citation_type": "JOUR",
+            "issn": ["0962-1067", null],
+            "urls": null,
+            "volume": 30,
+            "pmid": 9999999,
+            "extra_fields": {"DB": "Medline", "AN": 123, "N2": null, "KW": ["a", null]}
+        }))
+        .unwrap();
+
+        assert_eq!(citation.title, "");
+        assert!(citation.authors.is_empty());
+        assert_eq!(
+            citation.date,
+            Some(Date {
+                year: 1998,
+                month: None,
+                day: None,
+            })
+        );
+        assert_eq!(citation.citation_type, vec!["JOUR"]);
+        assert_eq!(citation.issn, vec!["0962-1067"]);
+        assert!(citation.urls.is_empty());
+        assert_eq!(citation.volume.as_deref(), Some("30"));
+        assert_eq!(citation.pmid.as_deref(), Some("9999999"));
+        assert_eq!(citation.extra_fields["DB"], vec!["Medline"]);
+        assert_eq!(citation.extra_fields["AN"], vec!["123"]);
+        assert_eq!(citation.extra_fields["KW"], vec!["a"]);
+        assert!(!citation.extra_fields.contains_key("N2"));
+    }
+
+    #[test]
+    fn test_field_level_errors() {
+        let error = |value: Value| from_v02_json(value).unwrap_err();
+
+        assert_eq!(
+            error(json!({"year": "c. 1998"})),
+            MigrationError::new("year", "\"c. 1998\" is not a year")
+        );
+        assert_eq!(error(json!({"keywords": [{"a": 1}]})).field, "keywords[0]");
+        assert_eq!(error(json!({"doi": ["10.1/a"]})).field, "doi");
+        assert_eq!(
+            error(json!({"extra_fields": {"DB": {"name": "Medline"}}})).field,
+            "extra_fields.DB[0]"
+        );
+        assert_eq!(error(json!({"date": {"month": 5}})).field, "date");
+
+        let err = error(json!({
+            "title": "T",
+            "authors": [{"family_name": "Smith"}, {"given_name": "John"}]
+        }));
+        assert_eq!(err.field, "authors[1]");
+        assert_eq!(err.reason, "missing field `name`");
+        assert_eq!(
+            err.to_string(),
+            "Cannot migrate field `authors[1]`: missing field `name`"
+        );
+
+        assert_eq!(error(json!("title")).field, "");
+    }
+
+    #[test]
+    fn test_current_shape_unchanged() {
+        let mut citation = Citation::new();
+        citation.title = "Title".to_string();
+        citation.date = Some(Date {
+            year: 2024,
+            month: Some(2),
+            day: None,
+        });
+        citation.set_source("Embase");
+        let value = serde_json::to_value(&citation).unwrap();
+        let migrated = from_v02_json(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&migrated).unwrap(), value);
+    }
+}
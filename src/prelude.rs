@@ -0,0 +1,37 @@
+//! Commonly used items, for glob importing.
+//!
+//! Items re-exported here keep their names across releases even when the modules
+//! that define them are reorganized, so downstream code can rely on a single
+//! import.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::prelude::*;
+//!
+//! let (citations, format) = detect_and_parse("TY  - JOUR\nTI  - Example\nER  - ").unwrap();
+//! assert_eq!(format, CitationFormat::Ris);
+//! assert_eq!(citations[0].title, "Example");
+//! ```
+
+pub use crate::error::{CitationError, ParseError};
+pub use crate::sort::{SortKey, sort_citations};
+pub use crate::{
+    Author, Citation, CitationFormat, CitationParser, CitationType, CitationWriter,
+    ConferenceInfo, Contributor, ContributorRole, Date, DuplicateGroup, detect_and_parse,
+};
+
+#[cfg(feature = "bibtex")]
+pub use crate::bibtex::BibTexParser;
+#[cfg(feature = "csv")]
+pub use crate::csv::{CsvParser, CsvWriter};
+#[cfg(feature = "dedupe")]
+pub use crate::dedupe::{Deduplicator, DeduplicatorConfig};
+#[cfg(feature = "xml")]
+pub use crate::endnote_xml::{EndNoteXmlParser, EndNoteXmlWriter};
+#[cfg(feature = "enw")]
+pub use crate::enw::EnwParser;
+#[cfg(feature = "pubmed")]
+pub use crate::pubmed::{PmidListParser, PubMedParser, PubMedWriter};
+#[cfg(feature = "ris")]
+pub use crate::ris::{RisParser, RisWriter};
@@ -1,6 +1,6 @@
-//! PubMed format parser implementation.
+//! PubMed format parser and writer implementation.
 //!
-//! Provides functionality to parse PubMed formatted citations.
+//! Provides functionality to parse and write PubMed formatted citations.
 //!
 //! # Example
 //!
@@ -21,22 +21,39 @@
 
 mod author;
 mod parse;
+mod pmid_list;
 mod split;
 mod structure;
 mod tags;
 mod whole_lines;
+mod write;
 
 use crate::error::ParseError;
+pub(crate) use crate::pubmed::parse::pubmed_coverage;
 use crate::pubmed::parse::pubmed_parse;
-use crate::{Citation, CitationParser};
-use itertools::Itertools;
+pub use crate::pubmed::pmid_list::PmidListParser;
+pub(crate) use crate::pubmed::pmid_list::is_pmid_list;
+pub use crate::pubmed::write::PubMedWriter;
+use crate::{Citation, CitationFormat, CitationParser, LenientOptions, LenientParse};
+use serde::{Deserialize, Serialize};
 
 /// Parser for PubMed format citations.
 ///
 /// PubMed format is commonly used by PubMed and the National Library of Medicine
 /// for bibliographic citations.
-#[derive(Debug, Clone, Default)]
-pub struct PubMedParser {}
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PubMedParser {
+    dedupe_keywords: bool,
+    drop_truncated: bool,
+    max_abstract_length: Option<usize>,
+    max_authors: Option<usize>,
+    stamp_ingestion_time: bool,
+    fold_typography: bool,
+    sentence_case_titles: bool,
+    raw_abstracts: bool,
+    archival: bool,
+}
 
 impl PubMedParser {
     /// Creates a new PubMed parser instance.
@@ -51,6 +68,247 @@ impl PubMedParser {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Sets whether to remove case-insensitive duplicate keywords and MeSH terms
+    /// from each citation.
+    ///
+    /// See [`Citation::dedupe_keywords`]. Disabled by default.
+    pub fn set_dedupe_keywords(&mut self, enabled: bool) -> &mut Self {
+        self.dedupe_keywords = enabled;
+        self
+    }
+
+    /// Sets the maximum abstract length in characters.
+    ///
+    /// Longer abstracts are shortened with [`Citation::truncate_abstract`] and
+    /// marked with [`crate::ABSTRACT_TRUNCATED_FIELD`]. `None`, the default,
+    /// keeps abstracts in full.
+    pub fn set_max_abstract_length(&mut self, max_chars: Option<usize>) -> &mut Self {
+        self.max_abstract_length = max_chars;
+        self
+    }
+
+    /// Sets the maximum number of authors kept per citation.
+    ///
+    /// Longer author lists, such as those of consortium papers, are shortened
+    /// with [`Citation::truncate_authors`], which records the full count in
+    /// `author_count` and marks the citation with
+    /// [`crate::AUTHORS_TRUNCATED_FIELD`]. `None`, the default, keeps all authors.
+    pub fn set_max_authors(&mut self, max_authors: Option<usize>) -> &mut Self {
+        self.max_authors = max_authors;
+        self
+    }
+
+    /// Sets whether each citation records when it was parsed in `ingested_at`.
+    ///
+    /// All citations of one parse get the same UTC time, as RFC 3339. Disabled by
+    /// default, so parsing the same input always gives the same output.
+    pub fn set_stamp_ingestion_time(&mut self, enabled: bool) -> &mut Self {
+        self.stamp_ingestion_time = enabled;
+        self
+    }
+
+    /// Sets whether typographic characters in the text fields are replaced with
+    /// ASCII equivalents.
+    ///
+    /// See [`Citation::fold_typography`]. Disabled by default, so stored fields
+    /// keep the characters of the source.
+    pub fn set_fold_typography(&mut self, enabled: bool) -> &mut Self {
+        self.fold_typography = enabled;
+        self
+    }
+
+    /// Sets whether all-caps titles are converted to sentence case.
+    ///
+    /// See [`Citation::sentence_case_title`]. Disabled by default, so titles
+    /// keep the case of the source.
+    pub fn set_sentence_case_titles(&mut self, enabled: bool) -> &mut Self {
+        self.sentence_case_titles = enabled;
+        self
+    }
+
+    /// Sets whether abstracts are cleaned of format-specific line breaks and
+    /// spacing.
+    ///
+    /// See [`Citation::clean_abstract`]. Enabled by default; disable it to keep
+    /// abstracts exactly as written in the source.
+    pub fn set_clean_abstracts(&mut self, enabled: bool) -> &mut Self {
+        self.raw_abstracts = !enabled;
+        self
+    }
+
+    /// Sets whether values are stored exactly as the file gives them.
+    ///
+    /// Archival mode is for collections that must keep what the file said. It
+    /// disables the normalization this parser applies by default:
+    ///
+    /// - the `DP`, `DEP`, `CRDT`, `EDAT` and `MHDA` values read into `date`,
+    ///   `electronic_date` and `record_dates` are also kept in `extra_fields`
+    ///   under [`crate::RAW_FIELD_PREFIX`], e.g. "raw:DP"
+    /// - abstracts are not cleaned, whatever [`PubMedParser::set_clean_abstracts`]
+    ///   says
+    ///
+    /// Pages and DOIs are never rewritten by this parser; the DOI is read from
+    /// `LID` or `AID` without its "\[doi\]" marker in either mode. Options that
+    /// are disabled by default, such as typography folding, still apply when
+    /// enabled. Disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, PubMedParser};
+    ///
+    /// let input = "PMID- 1\nTI  - Example\nDP  - 2023 Spring\nIS  - 1234-5678\nIS  - 2345-6789\n";
+    /// let mut parser = PubMedParser::new();
+    /// parser.set_archival(true);
+    /// let citation = &parser.parse(input).unwrap()[0];
+    /// assert_eq!(citation.date.as_ref().unwrap().year, 2023);
+    /// assert_eq!(citation.extra_fields["raw:DP"], vec!["2023 Spring"]);
+    /// assert_eq!(citation.issn, vec!["1234-5678", "2345-6789"]);
+    /// ```
+    pub fn set_archival(&mut self, enabled: bool) -> &mut Self {
+        self.archival = enabled;
+        self
+    }
+
+    /// Sets whether to drop a final record that is cut off by the end of the input.
+    ///
+    /// The last record is considered truncated when its final line is not a
+    /// complete tag line, e.g. a tag cut off mid-way or an indented continuation
+    /// line with no tag before it. A missing trailing blank line is not truncation.
+    /// By default such a record is kept and marked with
+    /// [`crate::TRUNCATED_FIELD`] in `extra_fields`; when enabled it is dropped.
+    pub fn set_drop_truncated(&mut self, enabled: bool) -> &mut Self {
+        self.drop_truncated = enabled;
+        self
+    }
+
+    /// Parses `input` into `out`, as [`CitationParser::parse_into`] when `errors`
+    /// is `None`; otherwise records that fail to convert are pushed to `errors`.
+    fn parse_records(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        crate::utils::check_format(input, CitationFormat::PubMed)?;
+        let line_break = crate::utils::newline_delimiter_of(input);
+        self.parse_section(input, line_break, true, out, errors)
+    }
+
+    /// Parses a run of whole records of a larger input as [`PubMedParser::parse_records`]
+    /// does, with lines ending in `line_break`; see [`pubmed_parse`].
+    fn parse_section(
+        &self,
+        input: &str,
+        line_break: &str,
+        ends_input: bool,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        // Handle empty input by appending nothing
+        if input.trim().is_empty() {
+            return Ok(0);
+        }
+
+        let mut records = pubmed_parse(input, line_break, ends_input);
+        if self.drop_truncated {
+            records.retain(|record| !record.truncated);
+        }
+        for record in &mut records {
+            record.archival = self.archival;
+        }
+        let converted = records.into_iter().map(|record| {
+            let line = record.line;
+            Citation::try_from(record).map_err(|mut e| {
+                e.line.get_or_insert(line);
+                e
+            })
+        });
+        let count = crate::utils::append_citations(out, converted, errors)?;
+        crate::utils::set_parsed_from_of_last(out, count, CitationFormat::PubMed);
+        if self.dedupe_keywords {
+            crate::utils::dedupe_keywords_of_last(out, count);
+        }
+        if self.fold_typography {
+            crate::utils::fold_typography_of_last(out, count);
+        }
+        if self.sentence_case_titles {
+            crate::utils::sentence_case_titles_of_last(out, count);
+        }
+        if !self.raw_abstracts && !self.archival {
+            crate::utils::clean_abstracts_of_last(out, count);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            crate::utils::truncate_abstracts_of_last(out, count, max_chars);
+        }
+        if let Some(max_authors) = self.max_authors {
+            crate::utils::truncate_authors_of_last(out, count, max_authors);
+        }
+        if self.stamp_ingestion_time {
+            crate::utils::stamp_ingestion_time_of_last(out, count);
+        }
+        Ok(count)
+    }
+
+    /// Parses PubMed content on the rayon thread pool, giving the same result as
+    /// [`CitationParser::parse`].
+    ///
+    /// The input is split at `PMID-` lines after a blank line into chunks of
+    /// many records, which are parsed in parallel and concatenated in input
+    /// order. This pays off for large exports; for small ones it is no faster
+    /// than [`CitationParser::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the `ParseError` of the first record that fails to convert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, PubMedParser};
+    ///
+    /// let input = "PMID- 1\nTI  - First\n\nPMID- 2\nTI  - Second\n";
+    /// let parser = PubMedParser::new();
+    /// let citations = parser.parse_parallel(input).unwrap();
+    /// assert_eq!(citations.len(), 2);
+    /// assert_eq!(citations[1].title, "Second");
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn parse_parallel(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        self.parse_parallel_in_chunks(input, crate::utils::PARALLEL_CHUNK_BYTES)
+    }
+
+    /// [`PubMedParser::parse_parallel`] with chunks of at least `min_bytes`.
+    #[cfg(feature = "parallel")]
+    fn parse_parallel_in_chunks(
+        &self,
+        input: &str,
+        min_bytes: usize,
+    ) -> Result<Vec<Citation>, ParseError> {
+        crate::utils::check_format(input, CitationFormat::PubMed)?;
+        let line_break = crate::utils::newline_delimiter_of(input);
+        let mut after_blank = false;
+        let chunks = crate::utils::record_chunks(input, min_bytes, |line| {
+            let starts_record = after_blank && line.starts_with("PMID-");
+            after_blank = line == line_break;
+            starts_record
+        });
+
+        // All citations of one parse share one ingestion time
+        let mut parser = self.clone();
+        parser.stamp_ingestion_time = false;
+        let mut citations = crate::utils::parse_chunks_parallel(&chunks, |chunk| {
+            let mut citations = Vec::new();
+            parser.parse_section(chunk.text, line_break, chunk.is_last, &mut citations, None)?;
+            Ok(citations)
+        })?;
+        if self.stamp_ingestion_time {
+            let count = citations.len();
+            crate::utils::stamp_ingestion_time_of_last(&mut citations, count);
+        }
+        Ok(citations)
+    }
 }
 
 impl CitationParser for PubMedParser {
@@ -68,21 +326,41 @@ impl CitationParser for PubMedParser {
     ///
     /// Returns `ParseError` if the input is malformed
     fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
-        // Handle empty input by returning empty vector
-        if input.trim().is_empty() {
-            return Ok(Vec::new());
-        }
+        let mut citations = Vec::new();
+        self.parse_into(input, &mut citations)?;
+        Ok(citations)
+    }
 
-        pubmed_parse(input)
-            .into_iter()
-            .map(|x| x.try_into())
-            .try_collect()
+    /// Parses PubMed formatted content, appending the citations to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed; `out` is left unchanged
+    fn parse_into(&self, input: &str, out: &mut Vec<Citation>) -> Result<usize, ParseError> {
+        self.parse_records(input, out, None)
+    }
+
+    /// Parses PubMed content, skipping records that fail to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed or the failures exceed `options`
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> Result<LenientParse, ParseError> {
+        let mut citations = Vec::new();
+        let mut errors = Vec::new();
+        self.parse_records(input, &mut citations, Some(&mut errors))?;
+        options.check(citations, errors, pubmed_coverage(input).unparsed)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use itertools::Itertools;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -134,6 +412,24 @@ TI- Three
         assert_eq!(pmids, &[Some("123"), Some("456"), Some("789")])
     }
 
+    #[test]
+    fn test_errors_report_the_line_of_the_record() {
+        let input = "PMID- 123\nTI  - One\n\n\nPMID- 456\nJT  - No title\n\nPMID- 789\nDP  - 20x\n";
+        let parser = PubMedParser::new();
+        let error = parser.parse(input).unwrap_err();
+        assert_eq!(error.line, Some(5));
+        assert_eq!(
+            error.to_string(),
+            "Error in PubMed format at line 5: Missing value for TI"
+        );
+
+        let result = parser
+            .parse_lenient(input, crate::LenientOptions::default())
+            .unwrap();
+        let lines = result.errors.iter().map(|e| e.line).collect_vec();
+        assert_eq!(lines, &[Some(5), Some(8)]);
+    }
+
     #[test]
     fn test_parse_citation_with_affiliation() {
         let input = r#"PMID- 12345678
@@ -191,6 +487,91 @@ IS  - 8765-4321
         let parser = PubMedParser::new();
         let result = parser.parse(input).unwrap();
         assert_eq!(result[0].issn, vec!["1234-5678", "8765-4321"]);
+        // Without annotations the first ISSN is the print one
+        let kinds = |citation: &Citation| -> Vec<_> {
+            citation.issns().into_iter().map(|issn| issn.kind).collect()
+        };
+        assert_eq!(kinds(&result[0]), vec![Some(crate::IssnKind::Print), None]);
+
+        let input = "PMID- 1\nTI  - Annotated\nIS  - 1234-5678 (Electronic)\nIS  - 8765-4321\n\n\
+                     PMID- 2\nTI  - Single\nIS  - 1234-5678\n\n";
+        let result = parser.parse(input).unwrap();
+        assert_eq!(result[0].issn, vec!["1234-5678 (Electronic)", "8765-4321"]);
+        assert_eq!(kinds(&result[0]), vec![Some(crate::IssnKind::Electronic), None]);
+        assert_eq!(result[1].issn, vec!["1234-5678"]);
+        assert_eq!(kinds(&result[1]), vec![None]);
+    }
+
+    #[test]
+    fn test_electronic_date() {
+        let input = "PMID- 1\nTI  - Ahead of print\nDP  - 2024 Jan\nDEP - 20231215\n\n\
+                     PMID- 2\nTI  - Bad DEP\nDP  - 2024\nDEP - 2023\n\n";
+        let result = PubMedParser::new().parse(input).unwrap();
+
+        assert_eq!(
+            result[0].electronic_date,
+            Some(crate::Date {
+                year: 2023,
+                month: Some(12),
+                day: Some(15),
+            })
+        );
+        assert!(!result[0].extra_fields.contains_key("DEP"));
+        assert_eq!(result[1].electronic_date, None);
+        assert_eq!(result[1].extra_fields["DEP"], vec!["2023"]);
+    }
+
+    #[test]
+    fn test_record_dates() {
+        let input = "PMID- 37171234\nOWN - NLM\nSTAT- MEDLINE\nDCOM- 20230615\n\
+                     LR  - 20230616\nIS  - 1532-8198 (Electronic)\nVI  - 45\nDP  - 2022 Nov\n\
+                     TI  - Early mobilisation in the intensive care unit.\nLA  - eng\n\
+                     PT  - Journal Article\nDEP - 20221102\nTA  - J Crit Care\n\
+                     JT  - Journal of critical care\nMH  - Humans\n\
+                     EDAT- 2023/05/12 06:00\nMHDA- 2023/06/16 06:42\nCRDT- 2023/05/11 19:03\n\
+                     PHST- 2022/08/14 00:00 [received]\nPHST- 2023/05/12 06:00 [pubmed]\n\
+                     AID - 10.5555/jcc.2022.045 [doi]\nPST - ppublish\n\n\
+                     PMID- 2\nTI  - Unreadable\nEDAT- 2023/13/01 06:00\n";
+        let result = PubMedParser::new().parse(input).unwrap();
+        let date = |year, month, day| crate::Date {
+            year,
+            month: Some(month),
+            day: Some(day),
+        };
+
+        let citation = &result[0];
+        assert_eq!(citation.entrez_date(), Some(&date(2023, 5, 12)));
+        assert_eq!(
+            citation.record_date(crate::RecordDateKind::Created),
+            Some(&date(2023, 5, 11))
+        );
+        assert_eq!(
+            citation.record_date(crate::RecordDateKind::Mesh),
+            Some(&date(2023, 6, 16))
+        );
+        assert_eq!(citation.date.as_ref().map(|d| (d.year, d.month)), Some((2022, Some(11))));
+        for tag in ["EDAT", "MHDA", "CRDT"] {
+            assert!(!citation.extra_fields.contains_key(tag), "{tag}");
+        }
+        assert_eq!(citation.extra_fields["PHST"].len(), 2);
+
+        assert!(result[1].record_dates.is_empty());
+        assert_eq!(result[1].extra_fields["EDAT"], vec!["2023/13/01 06:00"]);
+    }
+
+    #[test]
+    fn test_doi_from_article_identifiers() {
+        let input = "PMID- 1\nTI  - Unmarked\nAID - S0140-6736(21)00001-1\nAID - 10.1016/S0140-6736(21)00001-1\n\n\
+                     PMID- 2\nTI  - Marked\nAID - 10.1000/marked [doi]\nAID - S0000-0000(21)00002-2 [pii]\n\n\
+                     PMID- 3\nTI  - No DOI\nAID - 10.1000/first\nAID - S0000-0000(21)00003-3\n\n\
+                     PMID- 4\nTI  - LID first\nLID - 10.1000/lid [doi]\nAID - 10.1000/aid [doi]\n\n";
+        let result = PubMedParser::new().parse(input).unwrap();
+
+        assert_eq!(result[0].doi.as_deref(), Some("10.1016/S0140-6736(21)00001-1"));
+        assert_eq!(result[1].doi.as_deref(), Some("10.1000/marked"));
+        assert_eq!(result[2].doi, None);
+        assert_eq!(result[3].doi.as_deref(), Some("10.1000/lid"));
+        assert_eq!(result[0].extra_fields["AID"].len(), 2);
     }
 
     #[test]
@@ -229,6 +610,26 @@ AU  - Zhang H
     assert_eq!(result[0].authors[1].given_name.as_deref(), Some("Huajun"));
     }
 
+    #[test]
+    fn test_editors() {
+        let input = "PMID- 30000001\nTI  - Sepsis\nFAU - Okafor, Chidi\nAU  - Okafor C\n\
+                     ED  - Tanaka H\nFED - Tanaka, Hiroshi\nED  - Moreau C\nFED - Moreau, Claire\n\
+                     BTI - Emergency Medicine\n\n\
+                     PMID- 30000002\nTI  - Without full names\nED  - Novak J\n";
+        let result = PubMedParser::new().parse(input).unwrap();
+        assert_eq!(result[0].authors.len(), 1);
+        let editors: Vec<_> = result[0].editors().collect();
+        assert_eq!(editors.len(), 2);
+        assert_eq!(editors[0].name, "Tanaka");
+        assert_eq!(editors[0].given_name.as_deref(), Some("Hiroshi"));
+        assert!(!result[0].extra_fields.contains_key("ED"));
+        assert!(!result[0].extra_fields.contains_key("FED"));
+
+        let editors: Vec<_> = result[1].editors().collect();
+        assert_eq!(editors[0].name, "Novak");
+        assert_eq!(editors[0].given_name.as_deref(), Some("J"));
+    }
+
     #[test]
     fn test_crlf_endings() {
         let input = "PMID- 123\r\nTI- Windows\r\nFAU- Gates, Bill\r\nFAU- Cutler, Dave";
@@ -242,6 +643,21 @@ AU  - Zhang H
     assert_eq!(result[0].authors[1].name, "Cutler");
     }
 
+    #[test]
+    fn test_date_range() {
+        let input = "PMID- 1\nTI  - Quarterly\nDP  - 2019 Dec-2020 Feb\n\nPMID- 2\nTI  - Monthly\nDP  - 2020 Mar\n";
+        let parser = PubMedParser::new();
+        let result = parser.parse(input).unwrap();
+
+        let start = result[0].date.as_ref().unwrap();
+        assert_eq!((start.year, start.month), (2019, Some(12)));
+        let end = result[0].date_end.as_ref().unwrap();
+        assert_eq!((end.year, end.month), (2020, Some(2)));
+
+        assert_eq!(result[1].date.as_ref().unwrap().month, Some(3));
+        assert_eq!(result[1].date_end, None);
+    }
+
     #[test]
     fn test_continued_line() {
         let input = r#"PMID- 31181385
@@ -282,4 +698,115 @@ AU  - Van Dyke MCC
         let result = parser.parse("   \n  \t  ").unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[test]
+    fn test_dedupe_keywords_flag() {
+        let input = "PMID- 1\nTI  - Merged\nMH  - Humans\nMH  - humans\nMH  - Neoplasms\nMH  - HUMANS\n";
+
+        let parser = PubMedParser::new();
+        let result = parser.parse(input).unwrap();
+        assert_eq!(result[0].mesh_terms.len(), 4);
+
+        let mut parser = PubMedParser::new();
+        parser.set_dedupe_keywords(true);
+        let result = parser.parse(input).unwrap();
+        assert_eq!(result[0].mesh_terms, vec!["Humans", "Neoplasms"]);
+    }
+
+    #[test]
+    fn test_max_abstract_length() {
+        let input = "PMID- 1\nTI  - Long\nAB  - Éléments prognostiques\n";
+
+        let mut parser = PubMedParser::new();
+        parser.set_max_abstract_length(Some(2));
+        let mut citations = parser.parse(input).unwrap();
+        assert_eq!(citations[0].abstract_text.as_deref(), Some("Él…"));
+        assert_eq!(citations[0].extra_fields[crate::ABSTRACT_TRUNCATED_FIELD], vec!["true"]);
+
+        parser.parse_into(input, &mut citations).unwrap();
+        assert_eq!(citations[1].abstract_text.as_deref(), Some("Él…"));
+    }
+
+    #[test]
+    fn test_no_authors_listed() {
+        let input = "PMID- 1\nTI  - Editorial\nAU  - [No authors listed]\n";
+        let citation = &PubMedParser::new().parse(input).unwrap()[0];
+
+        assert!(citation.authors.is_empty());
+        assert_eq!(citation.extra_fields[crate::ANONYMOUS_FIELD], vec!["true"]);
+    }
+
+    #[test]
+    fn test_parse_into_appends() {
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nTI  - Second\n";
+        let parser = PubMedParser::new();
+        let mut citations = parser.parse("PMID- 3\nTI  - Existing\n").unwrap();
+
+        let count = parser.parse_into(input, &mut citations).unwrap();
+        assert_eq!(count, parser.parse(input).unwrap().len());
+        assert_eq!(count, 2);
+        assert_eq!(
+            citations.iter().map(|c| c.title.as_str()).collect::<Vec<_>>(),
+            vec!["Existing", "First", "Second"]
+        );
+
+        assert_eq!(parser.parse_into("  \n", &mut citations).unwrap(), 0);
+        assert_eq!(citations.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nJT  - No title\n\nPMID- 3\nTI  - Third\n";
+        let parser = PubMedParser::new();
+        assert!(parser.parse(input).is_err());
+
+        let result = parser
+            .parse_lenient(input, crate::LenientOptions::default())
+            .unwrap();
+        assert_eq!(
+            result.citations.iter().map(|c| c.title.as_str()).collect::<Vec<_>>(),
+            vec!["First", "Third"]
+        );
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.stats.attempted, 3);
+    }
+
+    #[test]
+    fn test_parse_abstract_with_many_continuation_lines() {
+        let mut input = String::from("PMID- 12345678\nTI  - Long abstract\nAB  - Start");
+        for i in 0..5_000 {
+            input.push_str(&format!("\n      continuation line number {i} of a very long abstract"));
+        }
+
+        let start = std::time::Instant::now();
+        let citations = PubMedParser::new().parse(&input).unwrap();
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+
+        let abstract_text = citations[0].abstract_text.as_deref().unwrap();
+        assert!(abstract_text.len() > 200_000);
+        assert!(abstract_text.ends_with("line number 4999 of a very long abstract"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parse_parallel_in_small_chunks() {
+        let input = "PMID- 1\r\nTI  - First\r\n\r\n\r\nPMID- 2\r\nTI  - Second, with an\r\n      \
+                     unparsable last line\r\nFA\r\n\r\nPMID- 3\r\nTI  - Cut off\r\nFA";
+        let parser = PubMedParser::new();
+        let json = |citations: &[Citation]| serde_json::to_value(citations).unwrap();
+
+        let citations = parser.parse_parallel_in_chunks(input, 1).unwrap();
+        assert_eq!(json(&citations), json(&parser.parse(input).unwrap()));
+        let truncated = citations
+            .iter()
+            .map(|c| c.extra_fields.contains_key(crate::TRUNCATED_FIELD))
+            .collect_vec();
+        assert_eq!(truncated, vec![false, false, true]);
+
+        let input = "PMID- 1\nTI  - First\n\nPMID- 2\nAB  - No title\n";
+        assert_eq!(
+            parser.parse_parallel_in_chunks(input, 1).unwrap_err().to_string(),
+            parser.parse(input).unwrap_err().to_string()
+        );
+    }
 }
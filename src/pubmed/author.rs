@@ -25,6 +25,11 @@ impl AuthorName {
         AuthorName { name, full: true }
     }
 
+    /// Whether the name is a placeholder such as "[No authors listed]".
+    pub(crate) fn is_placeholder(&self) -> bool {
+        crate::utils::is_placeholder_author(&self.name)
+    }
+
     /// Get the author's last (family) name.
     pub fn last_name(&self) -> &str {
         let parts = if self.full {
@@ -58,9 +63,8 @@ impl AuthorName {
         }
     }
 
-    // not used, consider deleting?
     /// Get the name as an `AU`.
-    pub fn as_au(&self) -> Cow<str> {
+    pub fn as_au(&self) -> Cow<'_, str> {
         if self.full {
             let initials = self.first_initials();
             if initials.is_empty() {
@@ -105,7 +109,8 @@ fn au_initials(au: &str) -> CompactString {
 /// Get the first two initials from a `FAU` value.
 fn fau_initials(fau: &str) -> CompactString {
     if let Some((_, r)) = fau.split_once(", ") {
-        let chars = r.split(' ').map_while(|s| s.chars().next());
+        // hyphenated given names contribute one initial per part, e.g. "Anna-Lena" is "AL"
+        let chars = r.split([' ', '-']).map_while(|s| s.chars().next());
         CompactString::from_iter(chars)
     } else {
         CompactString::const_new("")
@@ -220,6 +225,13 @@ mod tests {
         "FHC",
         Some("Francis Harry Compton")
     )]
+    #[case(
+        "Schmidt AL",
+        "Schmidt, Anna-Lena",
+        "Schmidt",
+        "AL",
+        Some("Anna-Lena")
+    )]
     // Complicated name from https://pubmed.ncbi.nlm.nih.gov/27206507/
     #[case(
         "van der Valk JPM",
@@ -258,7 +270,7 @@ mod tests {
     #[case(&["Watson JD", "Watson JD"])]
     fn test_resolve_author_consecutive_au(#[case] names: &[&str]) {
         let data = names
-            .into_iter()
+            .iter()
             .map(|s| (ConsecutiveTag::Author, s.to_string()))
             .collect();
         let (authors, _) = resolve_authors(data);
@@ -333,7 +345,7 @@ mod tests {
     ])]
     fn test_resolve_author_deduplication(#[case] names: &[(ConsecutiveTag, &str)]) {
         let data = names
-            .into_iter()
+            .iter()
             .map(|(t, n)| (*t, n.to_string()))
             .collect();
         let (authors, _) = resolve_authors(data);
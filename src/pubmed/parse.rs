@@ -3,7 +3,7 @@ use crate::pubmed::split::BlankLineSplit;
 use crate::pubmed::structure::RawPubmedData;
 use crate::pubmed::tags::PubmedTag;
 use crate::pubmed::whole_lines::WholeLinesIter;
-use crate::utils::newline_delimiter_of;
+use crate::utils::Coverage;
 use either::{Either, Left, Right};
 use itertools::Itertools;
 use std::collections::HashMap;
@@ -11,18 +11,67 @@ use std::collections::HashMap;
 /// Parse the content of a PubMed formatted .nbib file, returning its key-value pairs
 /// in a [HashMap] (with the order of duplicate values preserved in the [Vec] values)
 /// alongside any unparsable lines.
-pub fn pubmed_parse<S: AsRef<str>>(nbib_text: S) -> Vec<RawPubmedData> {
-    let text = nbib_text.as_ref();
-    let line_break = newline_delimiter_of(text);
-    BlankLineSplit::new(text, line_break)
-        .map(|(_line_number, chunk)| pubmed_parse_one(chunk, line_break))
-        .collect() // TODO do not collect, return an Iterator instead
+///
+/// `text` may be a run of whole records of a larger input with lines ending in
+/// `line_break`; only when `ends_input` is set can its last record be marked
+/// as truncated.
+pub fn pubmed_parse(text: &str, line_break: &str, ends_input: bool) -> Vec<RawPubmedData> {
+    let mut chunks = BlankLineSplit::new(text, line_break).peekable();
+    let mut records = Vec::new(); // TODO do not collect, return an Iterator instead
+    while let Some((line_number, chunk)) = chunks.next() {
+        let is_last = ends_input && chunks.peek().is_none();
+        records.push(pubmed_parse_one(chunk, line_number, line_break, is_last));
+    }
+    records
+}
+
+/// Count the lines of a PubMed formatted text that [`pubmed_parse`] reads, skips
+/// and starts records at. Continuation lines share the status of the line they
+/// continue.
+pub(crate) fn pubmed_coverage(text: &str) -> Coverage {
+    let mut coverage = Coverage::default();
+    let mut unparsed = true;
+
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            // A continuation line right after a blank line has nothing to continue
+            unparsed = true;
+            continue;
+        }
+        coverage.lines += 1;
+
+        if !line.starts_with(' ') {
+            let entry = split_on_dash(line).and_then(|(k, v)| match_pubmed_key(k, v));
+            if matches!(entry, Some((PubmedTag::PubmedUniqueIdentifier, _))) {
+                coverage.records += 1;
+            }
+            unparsed = entry.is_none();
+        }
+        if unparsed {
+            coverage.unparsed.push(index + 1);
+        }
+    }
+
+    coverage
 }
 
-fn pubmed_parse_one(text: &str, line_break: &str) -> RawPubmedData {
+/// Parse one record, starting at line `line`. The last record of the input is
+/// marked as truncated if its final line is not a complete key-value pair, e.g.
+/// a tag cut off mid-way or a continuation line without a tag before it.
+fn pubmed_parse_one(text: &str, line: usize, line_break: &str, is_last: bool) -> RawPubmedData {
+    let lines: Vec<String> = WholeLinesIter::new(text.split(line_break)).collect();
+    let truncated = is_last
+        && lines
+            .iter()
+            .rfind(|line| !line.trim().is_empty())
+            .is_some_and(|line| {
+                split_on_dash(line)
+                    .and_then(|(k, v)| match_pubmed_key(k, v))
+                    .is_none()
+            });
     let (mut ignored_lines, pairs): (Vec<_>, Vec<_>) =
-        WholeLinesIter::new(text.split(line_break)).partition_map(parse_complete_entry);
-    let (data, others) = separate_stateless_entries(pairs);
+        lines.into_iter().partition_map(parse_complete_entry);
+    let (data, order, others) = separate_stateless_entries(pairs);
     let (authors, leading_affiliations) = resolve_authors(others);
     ignored_lines.extend(
         leading_affiliations
@@ -31,19 +80,25 @@ fn pubmed_parse_one(text: &str, line_break: &str) -> RawPubmedData {
     );
     RawPubmedData {
         data,
+        order,
         authors,
         ignored_lines,
+        line,
+        truncated,
+        archival: false,
     }
 }
 
 /// Collect the data: tags which can be parsed statelessly are stored in a [HashMap],
-/// with duplicates kept in a [Vec] with order preserved, while other tags that require
-/// context to parse are stored in a vec with order preserved.
+/// with duplicates kept in a [Vec] with order preserved and the tag of each entry
+/// listed in input order, while other tags that require context to parse are
+/// stored in a vec with order preserved.
 #[allow(clippy::type_complexity)]
 fn separate_stateless_entries<V>(
     v: Vec<(PubmedTag, V)>,
-) -> (HashMap<PubmedTag, Vec<V>>, Vec<(ConsecutiveTag, V)>) {
+) -> (HashMap<PubmedTag, Vec<V>>, Vec<PubmedTag>, Vec<(ConsecutiveTag, V)>) {
     let mut map = HashMap::with_capacity(v.len());
+    let mut order = Vec::with_capacity(v.len());
     let mut other = Vec::with_capacity(v.len());
     for (k, v) in v {
         if let Some(tag) = ConsecutiveTag::from_tag(k) {
@@ -51,9 +106,10 @@ fn separate_stateless_entries<V>(
         } else {
             let bucket = map.entry(k).or_insert_with(Vec::new);
             bucket.push(v);
+            order.push(k);
         }
     }
-    (map, other)
+    (map, order, other)
 }
 
 /// Parse the string as a key-value pair from a PubMed formatted .nbib file.
@@ -80,6 +136,38 @@ mod tests {
     use super::*;
     use rstest::*;
 
+    #[rstest]
+    #[case("PMID- 1\nTI  - Complete\n\nPMID- 2\nTI  - Also complete", false)]
+    #[case("PMID- 1\nTI  - Complete\n\nPMID- 2\nTI  - Also complete\n\n", false)]
+    #[case("PMID- 1\nTI  - Complete\n\nPMID- 2\nTI  - Cut off\nFA", true)]
+    #[case("PMID- 1\nTI  - Complete\n\n      pathogens.", true)]
+    fn test_truncated_last_record(#[case] input: &str, #[case] truncated: bool) {
+        let records = pubmed_parse(input, "\n", true);
+        assert!(!records[0].truncated);
+        assert_eq!(records.last().unwrap().truncated, truncated);
+    }
+
+    #[test]
+    fn test_entries_keep_input_order() {
+        let records = pubmed_parse(
+            "PMID- 1\nIS  - 1234-5678\nAID - S0000-0000(21)00001-1\nIS  - 8765-4321\n\
+             FAU - Smith, John\nAID - 10.1000/test\n",
+            "\n",
+            true,
+        );
+        let entries: Vec<_> = records[0].entries().collect();
+        assert_eq!(
+            entries,
+            vec![
+                (PubmedTag::PubmedUniqueIdentifier, "1"),
+                (PubmedTag::Issn, "1234-5678"),
+                (PubmedTag::ArticleIdentifier, "S0000-0000(21)00001-1"),
+                (PubmedTag::Issn, "8765-4321"),
+                (PubmedTag::ArticleIdentifier, "10.1000/test"),
+            ]
+        );
+    }
+
     #[rstest]
     #[case("", Left(""))]
     #[case("DNE - tag does not exist", Left("DNE - tag does not exist"))]
@@ -95,7 +183,7 @@ mod tests {
         assert_eq!(
             actual
                 .as_ref()
-                .map_either(|s| s.as_str(), |(t, s)| (t.clone(), s.as_str())),
+                .map_either(|s| s.as_str(), |(t, s)| (*t, s.as_str())),
             expected
         )
     }
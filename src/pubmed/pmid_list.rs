@@ -0,0 +1,209 @@
+//! Parser for bare lists of PubMed IDs.
+//!
+//! PMIDs may be separated by newlines, commas or spaces, and may carry a
+//! `PMID:` prefix. Each PMID becomes a citation with only `pmid` and, by
+//! default, a placeholder title set.
+
+use crate::error::{ParseError, ValueError, fields};
+use crate::{Citation, CitationFormat, CitationParser, MissingTitle};
+
+/// Share of non-blank lines that must look like PMIDs for a list to be detected.
+const MIN_PMID_LINE_RATIO: f64 = 0.95;
+
+/// Parser for lists of PubMed IDs, such as those copied from a search result.
+///
+/// The citations have no title of their own; what is done about that is set with
+/// [`PmidListParser::set_missing_title`]. By default each gets the placeholder
+/// title "[PMID 12345678]".
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{CitationParser, PmidListParser};
+///
+/// let citations = PmidListParser::new().parse("PMID: 12345678\n23456789, 34567890").unwrap();
+/// assert_eq!(citations.len(), 3);
+/// assert_eq!(citations[0].pmid.as_deref(), Some("12345678"));
+/// assert_eq!(citations[0].title, "[PMID 12345678]");
+/// ```
+#[derive(Debug, Clone)]
+pub struct PmidListParser {
+    missing_title: MissingTitle,
+}
+
+impl Default for PmidListParser {
+    fn default() -> Self {
+        Self {
+            missing_title: MissingTitle::Placeholder,
+        }
+    }
+}
+
+impl PmidListParser {
+    /// Creates a new PMID list parser instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::PmidListParser;
+    /// let parser = PmidListParser::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what is done about the missing titles of the citations.
+    ///
+    /// Defaults to [`MissingTitle::Placeholder`].
+    pub fn set_missing_title(&mut self, policy: MissingTitle) -> &mut Self {
+        self.missing_title = policy;
+        self
+    }
+}
+
+impl CitationParser for PmidListParser {
+    /// Parses a list of PMIDs into citations, one per PMID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if an entry is not a number of 1 to 8 digits, or if
+    /// the missing title policy is [`MissingTitle::Error`] and the list is not empty
+    fn parse(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        let mut citations = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            for pmid in pmids_of_line(line) {
+                if !is_pmid(pmid) {
+                    return Err(ParseError::at_line(
+                        index + 1,
+                        CitationFormat::PmidList,
+                        ValueError::BadValue {
+                            field: fields::PMID,
+                            key: "PMID",
+                            value: pmid.to_string(),
+                            reason: "expected a number of 1 to 8 digits".to_string(),
+                        },
+                    ));
+                }
+
+                let title = match self.missing_title {
+                    MissingTitle::Error => {
+                        return Err(ParseError::at_line(
+                            index + 1,
+                            CitationFormat::PmidList,
+                            ValueError::MissingValue {
+                                field: fields::TITLE,
+                                key: "title",
+                            },
+                        ));
+                    }
+                    MissingTitle::Skip => continue,
+                    MissingTitle::Placeholder => format!("[PMID {}]", pmid),
+                };
+                citations.push(Citation {
+                    title,
+                    pmid: Some(pmid.to_string()),
+                    parsed_from: Some(CitationFormat::PmidList),
+                    ..Default::default()
+                });
+            }
+        }
+
+        Ok(citations)
+    }
+}
+
+/// Splits a line into its entries, dropping `PMID:` prefixes.
+fn pmids_of_line(line: &str) -> impl Iterator<Item = &str> {
+    line.split(|c: char| c == ',' || c.is_whitespace())
+        .map(|entry| {
+            entry
+                .get(..5)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("PMID:"))
+                .map_or(entry, |_| &entry[5..])
+        })
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Whether an entry is a PMID: a number of 1 to 8 digits.
+fn is_pmid(entry: &str) -> bool {
+    (1..=8).contains(&entry.len()) && entry.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Whether at least 95% of the non-blank lines of `text` hold only PMIDs.
+pub(crate) fn is_pmid_list(text: &str) -> bool {
+    let mut lines = 0;
+    let mut pmid_lines = 0;
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        lines += 1;
+        let mut entries = pmids_of_line(line).peekable();
+        if entries.peek().is_some() && entries.all(is_pmid) {
+            pmid_lines += 1;
+        }
+    }
+    lines > 0 && pmid_lines as f64 / lines as f64 >= MIN_PMID_LINE_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_mixed_separators() {
+        let input = "12345678\n\nPMID: 23456789, 34567890 pmid:45678901\n  5  \n";
+        let citations = PmidListParser::new().parse(input).unwrap();
+
+        assert_eq!(
+            citations
+                .iter()
+                .map(|c| c.pmid.as_deref().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["12345678", "23456789", "34567890", "45678901", "5"]
+        );
+        assert_eq!(citations[4].title, "[PMID 5]");
+        assert!(citations[0].authors.is_empty());
+        assert_eq!(citations[0].parsed_from, Some(CitationFormat::PmidList));
+    }
+
+    #[test]
+    fn test_rejects_non_numeric_entries() {
+        let parser = PmidListParser::new();
+
+        let err = parser.parse("12345678\n2345x789\n").unwrap_err();
+        assert_eq!(err.line, Some(2));
+        assert!(err.to_string().contains("2345x789"));
+
+        assert!(parser.parse("123456789").is_err());
+        assert!(parser.parse("PMC1234567").is_err());
+    }
+
+    #[test]
+    fn test_missing_title_policy() {
+        let mut parser = PmidListParser::new();
+
+        parser.set_missing_title(MissingTitle::Skip);
+        assert!(parser.parse("12345678\n23456789").unwrap().is_empty());
+
+        parser.set_missing_title(MissingTitle::Error);
+        let err = parser.parse("12345678").unwrap_err();
+        assert!(matches!(err.error, ValueError::MissingValue { .. }));
+        assert!(parser.parse("\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_is_pmid_list() {
+        assert!(is_pmid_list("12345678\n23456789, 34567890\n\nPMID: 45678901\n"));
+
+        let mut mostly = "12345678\n".repeat(19);
+        mostly.push_str("see attached\n");
+        assert!(is_pmid_list(&mostly));
+        mostly.push_str("and more\n");
+        assert!(!is_pmid_list(&mostly));
+
+        assert!(!is_pmid_list("TY  - JOUR\nTI  - Title\nER  - "));
+        assert!(!is_pmid_list("2020\nTitle, 2021"));
+        assert!(!is_pmid_list(""));
+    }
+}
@@ -1,40 +1,111 @@
 use crate::error::{ParseError, ValueError, fields};
-use crate::pubmed::author::PubmedAuthor;
+use crate::pubmed::author::{AuthorName, PubmedAuthor};
 use crate::pubmed::tags::PubmedTag;
-use crate::utils::parse_pubmed_date;
-use crate::{CitationFormat, Date};
+use crate::utils::parse_pubmed_date_range;
+use crate::{CitationFormat, Contributor, ContributorRole, Date, RecordDateKind, RetractionStatus};
 use std::collections::HashMap;
 
 /// Structured raw data from a PubMed formatted .nbib file.
 pub(crate) struct RawPubmedData {
     /// Key-value pair data from the .nbib file data.
     pub(crate) data: HashMap<PubmedTag, Vec<String>>,
+    /// The tag of each value in `data`, in input order.
+    pub(crate) order: Vec<PubmedTag>,
     /// Authors of the cited work.
     pub(crate) authors: Vec<PubmedAuthor>,
     /// Invalid lines found in the .nbib file data, which were skipped by the parser.
+    #[allow(dead_code)]
     pub(crate) ignored_lines: Vec<String>,
+    /// Line number the record starts at, reported with conversion errors.
+    pub(crate) line: usize,
+    /// Whether this is the last record and the input ended mid-line.
+    pub(crate) truncated: bool,
+    /// Whether values are kept as written, see [`crate::PubMedParser::set_archival`].
+    pub(crate) archival: bool,
+}
+
+impl RawPubmedData {
+    /// Returns the values of `data` with their tags, in input order.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (PubmedTag, &str)> {
+        let mut seen: HashMap<PubmedTag, usize> = HashMap::new();
+        self.order.iter().filter_map(move |&tag| {
+            let index = seen.entry(tag).or_default();
+            let value = self.data.get(&tag)?.get(*index)?;
+            *index += 1;
+            Some((tag, value.as_str()))
+        })
+    }
+
+    /// Returns the DOI among the `AID` values: the one marked `[doi]`, or when
+    /// no value carries a suffix, the last one if it looks like a DOI.
+    fn article_identifier_doi(&self) -> Option<String> {
+        let ids: Vec<&str> = self
+            .entries()
+            .filter(|(tag, _)| *tag == PubmedTag::ArticleIdentifier)
+            .map(|(_, value)| value.trim())
+            .collect();
+        if ids.iter().any(|id| id.ends_with(']')) {
+            return ids
+                .iter()
+                .find_map(|id| id.strip_suffix("[doi]").map(|doi| doi.trim().to_string()));
+        }
+        ids.last()
+            .filter(|id| id.starts_with("10.") && id.contains('/'))
+            .map(|id| id.to_string())
+    }
 }
 
 impl TryFrom<RawPubmedData> for crate::Citation {
     type Error = ParseError;
-    fn try_from(
-        RawPubmedData {
+    fn try_from(raw: RawPubmedData) -> Result<Self, Self::Error> {
+        let article_doi = raw.article_identifier_doi();
+        let RawPubmedData {
             mut data,
+            order: _,
             authors,
             ignored_lines: _,
-        }: RawPubmedData,
-    ) -> Result<Self, Self::Error> {
+            line: _,
+            truncated,
+            archival,
+        } = raw;
+        // Dates read into fields, kept as written in archival mode
+        let mut raw_dates = Vec::new();
+
         // unresolved question: what should we do if multiple values are found for
         // a field where one value is expected?
         // https://github.com/AliAzlanDev/biblib/pull/7#issuecomment-2984871452
         // current solution: join multiple values on hard-coded string " AND "
         // alternative solutions:
-        let date = data
-            .remove(&PubmedTag::PublicationDate)
+        let anonymous = authors.iter().any(|author| author.name.is_placeholder());
+        let authors: Vec<crate::Author> = authors
+            .into_iter()
+            .filter(|author| !author.name.is_placeholder())
+            .map(Into::into)
+            .collect();
+        let anonymous = anonymous && authors.is_empty();
+        let contributors = contributors(&mut data);
+
+        let publication_date = data.remove(&PubmedTag::PublicationDate);
+        let (date, date_end) = publication_date
+            .as_ref()
             // multiple values ignored
-            .and_then(|v| v.into_iter().next())
+            .and_then(|v| v.first())
             .map(parse_pubmed_date_err)
-            .transpose()?;
+            .transpose()?
+            .map_or((None, None), |(start, end)| (Some(start), end));
+        raw_dates.extend(publication_date.map(|v| (PubmedTag::PublicationDate, v)));
+
+        let electronic_date = data
+            .get(&PubmedTag::DateOfElectronicPublication)
+            .and_then(|v| v.first())
+            .and_then(|dep| parse_electronic_date(dep));
+        if electronic_date.is_some() {
+            let tag = PubmedTag::DateOfElectronicPublication;
+            raw_dates.extend(data.remove(&tag).map(|v| (tag, v)));
+        }
+
+        let record_dates = record_dates(&mut data, &mut raw_dates);
+        let retraction_status = retraction_status(&data);
 
         Ok(Self {
             citation_type: data
@@ -52,7 +123,9 @@ impl TryFrom<RawPubmedData> for crate::Citation {
                         },
                     )
                 })?,
-            authors: authors.into_iter().map(|a| a.into()).collect(),
+            authors,
+            author_count: None,
+            contributors,
             journal: data
                 .remove(&PubmedTag::FullJournalTitle)
                 .and_then(join_if_some),
@@ -60,6 +133,9 @@ impl TryFrom<RawPubmedData> for crate::Citation {
                 .remove(&PubmedTag::JournalTitleAbbreviation)
                 .and_then(join_if_some),
             date,
+            date_end,
+            electronic_date,
+            record_dates,
             volume: data.remove(&PubmedTag::Volume).and_then(join_if_some),
             issue: data.remove(&PubmedTag::Issue).and_then(join_if_some),
             pages: data.remove(&PubmedTag::Pagination).and_then(join_if_some),
@@ -69,7 +145,8 @@ impl TryFrom<RawPubmedData> for crate::Citation {
                 .unwrap_or_else(Vec::new)
                 .into_iter()
                 .filter_map(parse_doi_from_lid)
-                .next(),
+                .next()
+                .or(article_doi),
             pmid: data
                 .remove(&PubmedTag::PubmedUniqueIdentifier)
                 .and_then(join_if_some),
@@ -79,17 +156,69 @@ impl TryFrom<RawPubmedData> for crate::Citation {
             abstract_text: data.remove(&PubmedTag::Abstract).and_then(join_if_some),
             keywords: Vec::new(),
             urls: Vec::new(),
+            link_kinds: HashMap::new(),
             language: data.remove(&PubmedTag::Language).and_then(join_if_some),
             mesh_terms: data.remove(&PubmedTag::MeshTerms).unwrap_or_else(Vec::new),
             publisher: data.remove(&PubmedTag::Publisher).and_then(join_if_some),
+            notes: None,
+            conference: None,
+            times_cited: None,
+            retraction_status,
+            ingested_at: None,
+            parsed_from: None,
             extra_fields: data
                 .into_iter()
                 .map(|(k, v)| (k.as_tag().to_string(), v))
+                .chain(
+                    truncated
+                        .then(|| (crate::TRUNCATED_FIELD.to_string(), vec!["true".to_string()])),
+                )
+                .chain(
+                    anonymous
+                        .then(|| (crate::ANONYMOUS_FIELD.to_string(), vec!["true".to_string()])),
+                )
+                .chain(raw_dates.into_iter().filter(|_| archival).map(|(tag, v)| {
+                    (format!("{}{}", crate::RAW_FIELD_PREFIX, tag.as_tag()), v)
+                }))
                 .collect(),
         })
     }
 }
 
+/// Reads the retraction status from the publication types and the `RIN`,
+/// `RRI`, `ROF` and `EIN` references, falling back to the title prefix for
+/// records such as withdrawn Cochrane reviews.
+///
+/// The tags stay in `data`, so they are still kept in `extra_fields`.
+fn retraction_status(data: &HashMap<PubmedTag, Vec<String>>) -> Option<RetractionStatus> {
+    let values = |tag| data.get(&tag).map_or(&[][..], Vec::as_slice);
+    let has_type = |name: &str| {
+        values(PubmedTag::PublicationType)
+            .iter()
+            .any(|t| t.trim().eq_ignore_ascii_case(name))
+    };
+
+    let notice = values(PubmedTag::RetractionIn)
+        .iter()
+        .chain(values(PubmedTag::RetractedAndRepublishedIn))
+        .map(|v| v.trim())
+        .find(|v| !v.is_empty());
+    if notice.is_some() || has_type("Retracted Publication") {
+        return Some(RetractionStatus::Retracted {
+            notice: notice.map(str::to_string),
+        });
+    }
+    if !values(PubmedTag::RetractionOf).is_empty() || has_type("Retraction of Publication") {
+        return Some(RetractionStatus::RetractionNotice);
+    }
+    if !values(PubmedTag::ErratumIn).is_empty() {
+        return Some(RetractionStatus::HasErratum);
+    }
+    values(PubmedTag::Title)
+        .first()
+        .and_then(|title| RetractionStatus::from_title(title))
+}
+
 // FIXME when `CitationError::MultipleValues` is implemented.
 // https://github.com/AliAzlanDev/biblib/pull/7#issuecomment-2989915130
 fn join_if_some(v: Vec<String>) -> Option<String> {
@@ -100,10 +229,10 @@ fn join_if_some(v: Vec<String>) -> Option<String> {
     }
 }
 
-/// Wraps [parse_pubmed_date] to change its types.
-fn parse_pubmed_date_err<S: AsRef<str>>(date: S) -> Result<Date, ParseError> {
+/// Wraps [parse_pubmed_date_range] to change its types.
+fn parse_pubmed_date_err<S: AsRef<str>>(date: S) -> Result<(Date, Option<Date>), ParseError> {
     let s = date.as_ref();
-    parse_pubmed_date(s).ok_or_else(|| {
+    parse_pubmed_date_range(s).ok_or_else(|| {
         ParseError::without_position(
             CitationFormat::PubMed,
             ValueError::BadValue {
@@ -116,20 +245,127 @@ fn parse_pubmed_date_err<S: AsRef<str>>(date: S) -> Result<Date, ParseError> {
     })
 }
 
+/// Parses a `DEP` date, e.g. "20230115"; other values stay in `extra_fields`.
+fn parse_electronic_date(dep: &str) -> Option<Date> {
+    let dep = dep.trim();
+    if dep.len() != 8 || !dep.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let month: u8 = dep[4..6].parse().ok()?;
+    let day: u8 = dep[6..].parse().ok()?;
+    ((1..=12).contains(&month) && (1..=31).contains(&day)).then(|| Date {
+        year: dep[..4].parse().expect("ASCII digits"),
+        month: Some(month),
+        day: Some(day),
+    })
+}
+
+/// Takes the `CRDT`, `EDAT` and `MHDA` dates out of `data`; values that cannot
+/// be read stay in `extra_fields`. The values of the dates read are moved to
+/// `raw_dates`.
+fn record_dates(
+    data: &mut HashMap<PubmedTag, Vec<String>>,
+    raw_dates: &mut Vec<(PubmedTag, Vec<String>)>,
+) -> HashMap<RecordDateKind, Date> {
+    let mut dates = HashMap::new();
+    for (tag, kind) in [
+        (PubmedTag::CreateDate, RecordDateKind::Created),
+        (PubmedTag::EntryDate, RecordDateKind::Entrez),
+        (PubmedTag::MeshDate, RecordDateKind::Mesh),
+    ] {
+        let date = data
+            .get(&tag)
+            .and_then(|v| v.first())
+            .and_then(|value| parse_record_date(value));
+        if let Some(date) = date {
+            raw_dates.extend(data.remove(&tag).map(|v| (tag, v)));
+            dates.insert(kind, date);
+        }
+    }
+    dates
+}
+
+/// Parses a record date such as "2023/05/12 06:00", dropping the time of day.
+fn parse_record_date(value: &str) -> Option<Date> {
+    let date = value.split_whitespace().next()?;
+    let mut parts = date.split('/');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || year.len() != 4 {
+        return None;
+    }
+    let month: u8 = month.parse().ok()?;
+    let day: u8 = day.parse().ok()?;
+    ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some(Date {
+        year: year.parse().ok()?,
+        month: Some(month),
+        day: Some(day),
+    })
+}
+
 fn parse_doi_from_lid(s: String) -> Option<String> {
     s.strip_suffix(" [doi]").map(|s| s.to_string())
 }
 
+/// Takes the editors (`FED`, or `ED` without them), investigators (`FIR`, or
+/// `IR` without them) and corporate authors (`CN`) out of `data`.
+fn contributors(data: &mut HashMap<PubmedTag, Vec<String>>) -> Vec<Contributor> {
+    let mut contributors = Vec::new();
+    for (short, full, role) in [
+        (PubmedTag::Editor, PubmedTag::FullEditorName, ContributorRole::Editor),
+        (
+            PubmedTag::Investigator,
+            PubmedTag::FullInvestigatorName,
+            ContributorRole::Investigator,
+        ),
+    ] {
+        let short = data.remove(&short).unwrap_or_default();
+        let names = match data.remove(&full) {
+            Some(full) if !full.is_empty() => full.into_iter().map(AuthorName::fau).collect(),
+            _ => short.into_iter().map(AuthorName::au).collect::<Vec<_>>(),
+        };
+        contributors.extend(
+            names
+                .into_iter()
+                .filter(|name| !name.is_placeholder())
+                .map(|name| {
+                    let person = PubmedAuthor {
+                        name,
+                        affiliations: Vec::new(),
+                    };
+                    Contributor::new(person.into(), role)
+                }),
+        );
+    }
+    let groups = data.remove(&PubmedTag::CorporateAuthor).unwrap_or_default();
+    contributors.extend(
+        groups
+            .into_iter()
+            .filter(|group| !group.trim().is_empty())
+            .map(|group| {
+                let person = crate::Author {
+                    name: group,
+                    given_name: None,
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                };
+                Contributor::new(person, ContributorRole::CorporateAuthor)
+            }),
+    );
+    contributors
+}
+
 impl From<PubmedAuthor> for crate::Author {
     fn from(PubmedAuthor { name, affiliations }: PubmedAuthor) -> Self {
         let (given_name_opt, middle_name_opt) = name
             .given_name()
-            .map(|g| crate::utils::split_given_and_middle(g))
+            .map(crate::utils::split_given_and_middle)
             .unwrap_or((None, None));
         Self {
             name: name.last_name().to_string(),
             given_name: given_name_opt,
             middle_name: middle_name_opt,
+            suffix: None,
             affiliations,
         }
     }
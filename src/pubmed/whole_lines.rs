@@ -1,5 +1,3 @@
-use std::ops::Add;
-
 /// An [Iterator] which yields lines containing whole values from a PubMed .nbib formatted string.
 ///
 /// The PubMed .nbib file format consists of key-value pairs e.g.
@@ -60,15 +58,18 @@ impl<'a, I: Iterator<Item = &'a str>> WholeLinesIter<'a, I> {
 }
 
 /// Join strings on space, except for hyphen-terminated or blank items which are joined without a space.
+///
+/// The capacity is reserved up front, so a value split over thousands of lines is
+/// joined without repeated reallocation.
 fn join_lines(v: Vec<&str>) -> String {
-    v.into_iter().fold(String::new(), |acc, e| {
-        if acc.ends_with('-') || acc.ends_with(' ') || acc.is_empty() {
-            acc
-        } else {
-            acc.add(" ")
+    let mut joined = String::with_capacity(v.iter().map(|e| e.len() + 1).sum());
+    for e in v {
+        if !(joined.ends_with('-') || joined.ends_with(' ') || joined.is_empty()) {
+            joined.push(' ');
         }
-        .add(e)
-    })
+        joined.push_str(e);
+    }
+    joined
 }
 
 impl<'a, I: Iterator<Item = &'a str>> Iterator for WholeLinesIter<'a, I> {
@@ -113,4 +114,33 @@ LAST- line
         let actual: Vec<_> = WholeLinesIter::new(text.split('\n')).collect();
         assert_eq!(&actual.iter().map(|s| s.as_str()).collect_vec(), expected)
     }
+
+    #[rstest]
+    #[case(&[], "")]
+    #[case(&["one"], "one")]
+    #[case(&["one", "two"], "one two")]
+    #[case(&["Self-", "assembled"], "Self-assembled")]
+    #[case(&["trailing ", "space"], "trailing space")]
+    #[case(&["", "blank first"], "blank first")]
+    #[case(&["blank", "", "middle"], "blank middle")]
+    #[case(&["a-", "-b", "c"], "a--b c")]
+    fn test_join_lines(#[case] parts: &[&str], #[case] expected: &str) {
+        assert_eq!(join_lines(parts.to_vec()), expected);
+    }
+
+    #[test]
+    fn test_many_continuation_lines() {
+        let mut text = String::from("AB  - Start");
+        for i in 0..5_000 {
+            text.push_str(&format!("\n      continuation line number {i} of a very long abstract"));
+        }
+
+        let start = std::time::Instant::now();
+        let lines: Vec<_> = WholeLinesIter::new(text.split('\n')).collect();
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("AB  - Start continuation line number 0 of"));
+        assert!(lines[0].ends_with("continuation line number 4999 of a very long abstract"));
+    }
 }
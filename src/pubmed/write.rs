@@ -0,0 +1,679 @@
+//! PubMed format writer implementation.
+//!
+//! Citations are written one record at a time so arbitrarily large collections
+//! can be streamed to a file. Long values are wrapped like PubMed exports, on
+//! continuation lines indented by six spaces, which the parser joins back.
+
+use crate::pubmed::author::AuthorName;
+use crate::pubmed::tags::PubmedTag;
+use crate::writer::sanitize::pubmed_value;
+use crate::{Author, Citation, CitationError, CitationWriter, ContributorRole, Date};
+use crate::{RecordDateKind, utils};
+use std::io::Write;
+
+/// Lines are wrapped to this many characters, including the tag or indent.
+const LINE_WIDTH: usize = 80;
+
+/// Indent of continuation lines, as wide as a tag and its `- ` separator.
+const INDENT: &str = "      ";
+
+/// Tags written from the standard fields of a citation, never from extra fields.
+const FIELD_TAGS: [PubmedTag; 24] = [
+    PubmedTag::PubmedUniqueIdentifier,
+    PubmedTag::Title,
+    PubmedTag::FullAuthorName,
+    PubmedTag::Author,
+    PubmedTag::Affiliation,
+    PubmedTag::FullEditorName,
+    PubmedTag::Editor,
+    PubmedTag::FullInvestigatorName,
+    PubmedTag::Investigator,
+    PubmedTag::CorporateAuthor,
+    PubmedTag::FullJournalTitle,
+    PubmedTag::JournalTitleAbbreviation,
+    PubmedTag::PublicationDate,
+    PubmedTag::Volume,
+    PubmedTag::Issue,
+    PubmedTag::Pagination,
+    PubmedTag::LocationId,
+    PubmedTag::Abstract,
+    PubmedTag::MeshTerms,
+    PubmedTag::Issn,
+    PubmedTag::Language,
+    PubmedTag::PublicationType,
+    PubmedTag::PubmedCentralIdentifier,
+    PubmedTag::Publisher,
+];
+
+/// Record dates and the tags they are written as.
+const RECORD_DATE_TAGS: [(RecordDateKind, PubmedTag); 3] = [
+    (RecordDateKind::Entrez, PubmedTag::EntryDate),
+    (RecordDateKind::Mesh, PubmedTag::MeshDate),
+    (RecordDateKind::Created, PubmedTag::CreateDate),
+];
+
+/// Writer for PubMed (MEDLINE `.nbib`) format citations.
+///
+/// Each author is written as `FAU` and `AU` followed by an `AD` line per
+/// affiliation, editors and investigators as `FED`/`ED` and `FIR`/`IR`, and
+/// corporate authors as `CN`. Extra fields whose keys are PubMed tags, such as
+/// `AID` or `PHST`, are written back, so parsing the output gives the citations
+/// that were written. Keywords are written as `OT`, which the parser keeps in
+/// `extra_fields["OT"]`, and fields PubMed has no tag for, such as URLs or
+/// notes, are left out. Dates read in archival mode are written as they were
+/// read, see [`crate::PubMedParser::set_archival`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Citation, CitationWriter, PubMedWriter};
+///
+/// let citation = Citation {
+///     title: "Example Title".to_string(),
+///     pmid: Some("12345678".to_string()),
+///     ..Default::default()
+/// };
+///
+/// let output = PubMedWriter::new().write_to_string([&citation]).unwrap();
+/// assert_eq!(output, "PMID- 12345678\nTI  - Example Title\n\n");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PubMedWriter;
+
+impl PubMedWriter {
+    /// Creates a new PubMed writer instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::PubMedWriter;
+    /// let writer = PubMedWriter::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Writes a single citation as one record, followed by a blank line.
+    fn write_record<W: Write>(&self, citation: &Citation, w: &mut W) -> std::io::Result<()> {
+        write_optional_tag(
+            w,
+            PubmedTag::PubmedUniqueIdentifier,
+            citation.pmid.as_deref(),
+        )?;
+        write_tag(w, PubmedTag::Title, &citation.title)?;
+
+        for author in &citation.authors {
+            write_person(w, PubmedTag::FullAuthorName, PubmedTag::Author, author)?;
+            for affiliation in &author.affiliations {
+                write_tag(w, PubmedTag::Affiliation, affiliation)?;
+            }
+        }
+        if citation.authors.is_empty() && citation.extra_fields.contains_key(crate::ANONYMOUS_FIELD)
+        {
+            write_tag(w, PubmedTag::Author, "[No authors listed]")?;
+        }
+        for (role, full, short) in [
+            (
+                ContributorRole::Editor,
+                PubmedTag::FullEditorName,
+                PubmedTag::Editor,
+            ),
+            (
+                ContributorRole::Investigator,
+                PubmedTag::FullInvestigatorName,
+                PubmedTag::Investigator,
+            ),
+        ] {
+            for person in citation.contributors_with_role(role) {
+                write_person(w, full, short, person)?;
+            }
+        }
+        for group in citation.contributors_with_role(ContributorRole::CorporateAuthor) {
+            write_tag(w, PubmedTag::CorporateAuthor, &group.name)?;
+        }
+
+        write_optional_tag(w, PubmedTag::FullJournalTitle, citation.journal.as_deref())?;
+        write_optional_tag(
+            w,
+            PubmedTag::JournalTitleAbbreviation,
+            citation.journal_abbr.as_deref(),
+        )?;
+        if let Some(date) = &citation.date
+            && !write_raw_date(w, citation, PubmedTag::PublicationDate)?
+        {
+            let value = format_date(date, citation.date_end.as_ref());
+            write_tag(w, PubmedTag::PublicationDate, &value)?;
+        }
+        write_optional_tag(w, PubmedTag::Volume, citation.volume.as_deref())?;
+        write_optional_tag(w, PubmedTag::Issue, citation.issue.as_deref())?;
+        write_optional_tag(w, PubmedTag::Pagination, citation.pages.as_deref())?;
+        if let Some(doi) = &citation.doi {
+            write_tag(w, PubmedTag::LocationId, &format!("{} [doi]", doi))?;
+        }
+        write_optional_tag(w, PubmedTag::Abstract, citation.abstract_text.as_deref())?;
+        for term in &citation.mesh_terms {
+            write_tag(w, PubmedTag::MeshTerms, term)?;
+        }
+        for keyword in &citation.keywords {
+            write_tag(w, PubmedTag::OtherTerm, keyword)?;
+        }
+        for issn in &citation.issn {
+            write_tag(w, PubmedTag::Issn, issn)?;
+        }
+        write_optional_tag(w, PubmedTag::Language, citation.language.as_deref())?;
+        for citation_type in &citation.citation_type {
+            write_tag(w, PubmedTag::PublicationType, citation_type)?;
+        }
+        write_optional_tag(
+            w,
+            PubmedTag::PubmedCentralIdentifier,
+            citation.pmc_id.as_deref(),
+        )?;
+        write_optional_tag(w, PubmedTag::Publisher, citation.publisher.as_deref())?;
+
+        // Dates without a month and day cannot be written as `DEP`
+        if let Some(Date {
+            year,
+            month: Some(month),
+            day: Some(day),
+        }) = citation.electronic_date
+            && !write_raw_date(w, citation, PubmedTag::DateOfElectronicPublication)?
+        {
+            let value = format!("{:04}{:02}{:02}", year, month, day);
+            write_tag(w, PubmedTag::DateOfElectronicPublication, &value)?;
+        }
+        for (kind, tag) in RECORD_DATE_TAGS {
+            if let Some(Date {
+                year,
+                month: Some(month),
+                day: Some(day),
+            }) = citation.record_dates.get(&kind)
+                && !write_raw_date(w, citation, tag)?
+            {
+                write_tag(
+                    w,
+                    tag,
+                    &format!("{:04}/{:02}/{:02} 00:00", year, month, day),
+                )?;
+            }
+        }
+        self.write_extra_fields(citation, w)?;
+
+        writeln!(w)
+    }
+
+    /// Writes extra fields whose keys are PubMed tags, sorted by tag.
+    ///
+    /// Tags written from a standard field are skipped, as are `DEP`, `CRDT`,
+    /// `EDAT` and `MHDA` when the citation has the date, and `OT` terms that
+    /// are also keywords, so an extra field never duplicates a standard field.
+    fn write_extra_fields<W: Write>(&self, citation: &Citation, w: &mut W) -> std::io::Result<()> {
+        let mut extra: Vec<(PubmedTag, &Vec<String>)> = citation
+            .extra_fields
+            .iter()
+            .filter_map(|(key, values)| Some((PubmedTag::from_tag(key)?, values)))
+            .filter(|(tag, _)| !FIELD_TAGS.contains(tag))
+            .filter(|(tag, _)| {
+                let written = match tag {
+                    PubmedTag::DateOfElectronicPublication => citation.electronic_date.as_ref(),
+                    _ => RECORD_DATE_TAGS
+                        .iter()
+                        .find(|(_, date_tag)| date_tag == tag)
+                        .and_then(|(kind, _)| citation.record_dates.get(kind)),
+                };
+                written.is_none_or(|date| date.month.is_none() || date.day.is_none())
+            })
+            .collect();
+        extra.sort_by_key(|(tag, _)| tag.as_tag());
+
+        for (tag, values) in extra {
+            for value in values {
+                if tag == PubmedTag::OtherTerm && citation.keywords.contains(value) {
+                    continue;
+                }
+                write_tag(w, tag, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CitationWriter for PubMedWriter {
+    /// Writes citations in PubMed format, separating records with a blank line.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CitationError::Io` if the underlying writer fails
+    fn write_to<'a, W: Write>(
+        &self,
+        citations: impl IntoIterator<Item = &'a Citation>,
+        mut writer: W,
+    ) -> Result<(), CitationError> {
+        for citation in citations {
+            self.write_record(citation, &mut writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes one tag, with its value wrapped onto continuation lines.
+fn write_tag<W: Write>(w: &mut W, tag: PubmedTag, value: &str) -> std::io::Result<()> {
+    let value = pubmed_value(value);
+    let mut lines = wrap(&value).into_iter();
+    writeln!(
+        w,
+        "{:<4}- {}",
+        tag.as_tag(),
+        lines.next().unwrap_or_default()
+    )?;
+    for line in lines {
+        writeln!(w, "{}{}", INDENT, line)?;
+    }
+    Ok(())
+}
+
+fn write_optional_tag<W: Write>(
+    w: &mut W,
+    tag: PubmedTag,
+    value: Option<&str>,
+) -> std::io::Result<()> {
+    match value {
+        Some(value) => write_tag(w, tag, value),
+        None => Ok(()),
+    }
+}
+
+/// Writes the values of a date tag as written in the input, which archival
+/// mode keeps under [`crate::RAW_FIELD_PREFIX`]. Returns whether there were any.
+fn write_raw_date<W: Write>(
+    w: &mut W,
+    citation: &Citation,
+    tag: PubmedTag,
+) -> std::io::Result<bool> {
+    let key = format!("{}{}", crate::RAW_FIELD_PREFIX, tag.as_tag());
+    let values = citation
+        .extra_fields
+        .get(&key)
+        .map_or(&[][..], Vec::as_slice);
+    for value in values {
+        write_tag(w, tag, value)?;
+    }
+    Ok(!values.is_empty())
+}
+
+/// Writes a person as a full name, e.g. "Watson, James Dewey", and the short
+/// name with initials, e.g. "Watson JD".
+///
+/// A name without given names is only written in full, as the parser would
+/// take its last word for initials.
+fn write_person<W: Write>(
+    w: &mut W,
+    full: PubmedTag,
+    short: PubmedTag,
+    person: &Author,
+) -> std::io::Result<()> {
+    let given = [person.given_name.as_deref(), person.middle_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if given.is_empty() {
+        return write_tag(w, full, &person.name);
+    }
+    let name = AuthorName::fau(format!("{}, {}", person.name, given));
+    write_tag(w, full, &format!("{}, {}", person.name, given))?;
+    write_tag(w, short, &name.as_au())
+}
+
+/// Formats a date as a PubMed `DP` value, e.g. "2023", "2023 Jan 23",
+/// "2020 Jan-Mar" or "2018-2019".
+fn format_date(date: &Date, date_end: Option<&Date>) -> String {
+    match utils::format_month_day_range(date, date_end) {
+        Some(month_day) => format!("{} {}", date.year, month_day),
+        None => match date_end {
+            Some(end) if end.year != date.year => format!("{}-{}", date.year, end.year),
+            _ => date.year.to_string(),
+        },
+    }
+}
+
+/// Splits a value into lines of at most [`LINE_WIDTH`] characters with the
+/// tag or indent, breaking at spaces.
+///
+/// The parser joins continuation lines with a space, except after a line
+/// ending in `-`, and drops the indent, so the value only breaks at a single
+/// space that does not follow a `-`. A word longer than a line is not broken.
+fn wrap(value: &str) -> Vec<&str> {
+    let width = LINE_WIDTH - INDENT.len();
+    let mut lines = Vec::new();
+    let mut rest = value;
+    while rest.chars().nth(width).is_some() {
+        let mut fitting = None;
+        let mut overlong = None;
+        for (chars, (i, c)) in rest.char_indices().enumerate() {
+            if chars > width && fitting.is_some() {
+                break;
+            }
+            if c == ' ' && is_break(rest, i) {
+                if chars <= width {
+                    fitting = Some(i);
+                } else {
+                    overlong = Some(i);
+                    break;
+                }
+            }
+        }
+        let Some(i) = fitting.or(overlong) else {
+            break;
+        };
+        lines.push(&rest[..i]);
+        rest = &rest[i + 1..];
+    }
+    lines.push(rest);
+    lines
+}
+
+/// Whether the space at byte `i` of `value` can end a line.
+fn is_break(value: &str, i: usize) -> bool {
+    let before = value[..i].chars().next_back();
+    let after = value[i + 1..].chars().next();
+    before.is_some_and(|c| !c.is_whitespace() && c != '-')
+        && after.is_some_and(|c| !c.is_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CountingWriter;
+    use crate::{CitationFormat, CitationParser, Contributor, PubMedParser};
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn author(name: &str, given: Option<&str>, middle: Option<&str>) -> Author {
+        Author {
+            name: name.to_string(),
+            given_name: given.map(str::to_string),
+            middle_name: middle.map(str::to_string),
+            suffix: None,
+            affiliations: Vec::new(),
+        }
+    }
+
+    fn date(year: i32, month: Option<u8>, day: Option<u8>) -> Date {
+        Date { year, month, day }
+    }
+
+    fn synthetic_citation(i: usize) -> Citation {
+        Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: format!("Synthetic citation number {}", i),
+            authors: vec![author("Smith", Some("John"), Some("A"))],
+            journal: Some("Journal of Testing".to_string()),
+            date: Some(date(2000 + (i % 25) as i32, Some(3), None)),
+            volume: Some("12".to_string()),
+            pages: Some("100-110".to_string()),
+            doi: Some(format!("10.1234/test.{}", i)),
+            pmid: Some((30_000_000 + i).to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn full_citation() -> Citation {
+        let mut okafor = author("Okafor", Some("Chidinma"), Some("A"));
+        okafor.affiliations = vec![
+            "Department of Critical Care, Example University Hospital, Springfield, USA.".into(),
+            "Institute of Clinical Research, Example University, Springfield, USA.".into(),
+        ];
+        let mut extra_fields = HashMap::new();
+        extra_fields.insert("OWN".to_string(), vec!["NLM".to_string()]);
+        extra_fields.insert(
+            "AID".to_string(),
+            vec!["S0883-9441(21)00045-1 [pii]".to_string()],
+        );
+        Citation {
+            citation_type: vec![
+                "Journal Article".to_string(),
+                "Randomized Controlled Trial".to_string(),
+            ],
+            title: "Early mobilisation and delirium in critically ill adults: a randomised \
+                    controlled trial of a nurse-led, protocol-driven intervention in \
+                    three intensive care units."
+                .to_string(),
+            authors: vec![
+                okafor,
+                author("Schmidt", Some("Anna-Lena"), None),
+                author("van der Valk", Some("J"), Some("P M")),
+                author("Tanaka", None, None),
+            ],
+            contributors: vec![
+                Contributor::new(
+                    author("Moreau", Some("Claire"), None),
+                    ContributorRole::Editor,
+                ),
+                Contributor::new(
+                    author("Haddad", Some("Omar"), None),
+                    ContributorRole::Investigator,
+                ),
+                Contributor::new(
+                    author("ICU Mobility Trial Group", None, None),
+                    ContributorRole::CorporateAuthor,
+                ),
+            ],
+            journal: Some("Journal of critical care research".to_string()),
+            journal_abbr: Some("J Crit Care Res".to_string()),
+            date: Some(date(2021, Some(3), Some(15))),
+            date_end: Some(date(2021, Some(3), Some(21))),
+            electronic_date: Some(date(2021, Some(2), Some(1))),
+            record_dates: HashMap::from([
+                (RecordDateKind::Created, date(2021, Some(2), Some(1))),
+                (RecordDateKind::Entrez, date(2021, Some(2), Some(2))),
+            ]),
+            volume: Some("45".to_string()),
+            issue: Some("3".to_string()),
+            pages: Some("210-218".to_string()),
+            issn: vec![
+                "1557-8615 (Electronic)".to_string(),
+                "0883-9441 (Linking)".to_string(),
+            ],
+            doi: Some("10.5555/jccr.2021.045".to_string()),
+            pmid: Some("33510001".to_string()),
+            pmc_id: Some("PMC7890123".to_string()),
+            abstract_text: Some(
+                "BACKGROUND: Delirium is common in the intensive care unit and is \
+                 associated with worse outcomes. METHODS: We randomly assigned 312 \
+                 mechanically ventilated adults to early mobilisation or usual care. \
+                 RESULTS: Delirium-free days were higher with early mobilisation (mean \
+                 difference 1.8 days; 95% CI 0.9 to 2.7). CONCLUSIONS: Early mobilisation \
+                 reduced delirium in critically ill adults. Well- and poorly-staffed units \
+                 alike benefited."
+                    .to_string(),
+            ),
+            language: Some("eng".to_string()),
+            mesh_terms: vec![
+                "Adult".to_string(),
+                "*Delirium/prevention & control".to_string(),
+            ],
+            publisher: Some("Example Press".to_string()),
+            extra_fields,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let citation = full_citation();
+        let output = PubMedWriter::new().write_to_string([&citation]).unwrap();
+        assert!(output.starts_with("PMID- 33510001\nTI  - Early mobilisation"));
+        assert!(output.contains("FAU - Okafor, Chidinma A\nAU  - Okafor CA\nAD  - "));
+        assert!(output.contains("FAU - Schmidt, Anna-Lena\nAU  - Schmidt AL\n"));
+        assert!(output.contains("FAU - Tanaka\nFED - Moreau, Claire\nED  - Moreau C\n"));
+        assert!(output.contains("DP  - 2021 Mar 15-21\n"));
+        assert!(output.contains("LID - 10.5555/jccr.2021.045 [doi]\n"));
+        assert!(output.contains("DEP - 20210201\n"));
+        assert!(output.ends_with("\n\n"));
+        for line in output.lines() {
+            assert!(line.chars().count() <= LINE_WIDTH, "{line}");
+        }
+
+        let parsed = PubMedParser::new().parse(&output).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let expected = Citation {
+            parsed_from: Some(CitationFormat::PubMed),
+            ..citation
+        };
+        assert_eq!(
+            serde_json::to_value(&parsed[0]).unwrap(),
+            serde_json::to_value(&expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keywords_are_read_back_as_other_terms() {
+        let mut citation = full_citation();
+        citation.keywords = vec!["delirium".to_string(), "mobilisation".to_string()];
+        citation.extra_fields.insert(
+            "OT".to_string(),
+            vec!["mobilisation".to_string(), "nursing".to_string()],
+        );
+
+        let output = PubMedWriter::new().write_to_string([&citation]).unwrap();
+        assert_eq!(output.matches("OT  - mobilisation\n").count(), 1);
+        let parsed = PubMedParser::new().parse(&output).unwrap().remove(0);
+        assert!(parsed.keywords.is_empty());
+        assert_eq!(
+            parsed.extra_fields["OT"],
+            vec!["delirium", "mobilisation", "nursing"]
+        );
+    }
+
+    #[test]
+    fn test_extra_fields_never_override_standard_fields() {
+        let mut citation = synthetic_citation(1);
+        citation
+            .record_dates
+            .insert(RecordDateKind::Mesh, date(2021, Some(6), Some(16)));
+        for (key, value) in [
+            ("TI", "Shadow title"),
+            ("MHDA", "2021/06/17 06:00"),
+            ("DEP", "Spring 2021"),
+            ("anonymous", "true"),
+            ("lowercase", "ignored"),
+            ("SB", "IM"),
+        ] {
+            citation
+                .extra_fields
+                .insert(key.to_string(), vec![value.to_string()]);
+        }
+
+        let output = PubMedWriter::new().write_to_string([&citation]).unwrap();
+        assert!(!output.contains("Shadow title"));
+        assert!(!output.contains("ignored"));
+        assert!(!output.contains("[No authors listed]"));
+        assert!(output.contains("MHDA- 2021/06/16 00:00\n"));
+        assert!(!output.contains("2021/06/17"));
+        // Without an electronic date the unparsed value is kept
+        assert!(output.contains("DEP - Spring 2021\nSB  - IM\n"));
+    }
+
+    #[test]
+    fn test_write_values_containing_pubmed_syntax() {
+        let citation = Citation {
+            title: "A title\n\nPMID- 2\nTI  - Injected".to_string(),
+            abstract_text: Some("First line\r\n      indented\rlast".to_string()),
+            ..Default::default()
+        };
+        let output = PubMedWriter::new().write_to_string([&citation]).unwrap();
+        let parsed = PubMedParser::new()
+            .set_clean_abstracts(false)
+            .parse(&output)
+            .unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].title, "A title  PMID- 2 TI  - Injected");
+        assert_eq!(
+            parsed[0].abstract_text.as_deref(),
+            Some("First line       indented last")
+        );
+    }
+
+    #[test]
+    fn test_anonymous_citation_round_trip() {
+        let parsed = PubMedParser::new()
+            .parse("PMID- 1\nTI  - Editorial\nAU  - [No authors listed]\n\n")
+            .unwrap();
+        let output = PubMedWriter::new().write_to_string(&parsed).unwrap();
+        assert!(output.contains("AU  - [No authors listed]\n"));
+        let reparsed = PubMedParser::new().parse(&output).unwrap();
+        assert_eq!(reparsed[0].extra_fields, parsed[0].extra_fields);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("Short value"), vec!["Short value"]);
+        assert_eq!(wrap(""), vec![""]);
+
+        let words = "word ".repeat(40);
+        let lines = wrap(words.trim_end());
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.chars().count() <= 74));
+        assert_eq!(lines.join(" "), words.trim_end());
+
+        // Never after a hyphen, nor at a double space
+        let value = format!("{}well- known  x", "a".repeat(68));
+        assert_eq!(wrap(&value), vec![value.as_str()]);
+        let value = format!("{} {} tail", "a".repeat(70), "b".repeat(90));
+        assert_eq!(
+            wrap(&value),
+            vec!["a".repeat(70).as_str(), "b".repeat(90).as_str(), "tail"]
+        );
+        // Characters are counted rather than bytes
+        let value = format!("{} é", "é".repeat(72));
+        assert_eq!(wrap(&value), vec![value.as_str()]);
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date(&date(2023, None, None), None), "2023");
+        assert_eq!(
+            format_date(&date(2023, Some(1), Some(23)), None),
+            "2023 Jan 23"
+        );
+        assert_eq!(
+            format_date(
+                &date(2019, Some(12), None),
+                Some(&date(2020, Some(1), None))
+            ),
+            "2019 Dec-2020 Jan"
+        );
+        assert_eq!(
+            format_date(&date(2018, None, None), Some(&date(2019, None, None))),
+            "2018-2019"
+        );
+    }
+
+    #[test]
+    fn test_write_streams_large_collection() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::default();
+        PubMedWriter::new()
+            .write_to(&citations, &mut counter)
+            .unwrap();
+
+        let expected = PubMedWriter::new().write_to_string(&citations).unwrap();
+        assert_eq!(counter.bytes, expected.len());
+        assert_eq!(PubMedParser::new().parse(&expected).unwrap().len(), 10_000);
+    }
+
+    #[test]
+    fn test_write_propagates_io_errors() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::failing_after(4096);
+        let result = PubMedWriter::new().write_to(&citations, &mut counter);
+
+        assert!(matches!(result, Err(CitationError::Io(_))));
+        assert!(counter.bytes <= 4096);
+    }
+}
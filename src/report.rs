@@ -0,0 +1,244 @@
+//! Counts of citations by journal, year and type for reporting.
+//!
+//! [`count_by_journal`] groups journals by [`normalize_journal`], the
+//! normalization the deduplicator compares journals with, so "The Lancet" and
+//! "LANCET" are counted together. [`count_by_year`] and [`count_by_type`] count
+//! publication years and [`CitationType`]s. The counts serialize with serde for
+//! use in reports and dashboards.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::Citation;
+//! use biblib::report::count_by_journal;
+//!
+//! let citation = |journal: &str| Citation {
+//!     journal: Some(journal.to_string()),
+//!     ..Default::default()
+//! };
+//! let citations = vec![citation("Lancet"), citation("The Lancet"), citation("Lancet")];
+//!
+//! let counts = count_by_journal(&citations);
+//! assert_eq!(counts.len(), 1);
+//! assert_eq!(counts[0].name, "Lancet");
+//! assert_eq!(counts[0].key, "lancet");
+//! assert_eq!(counts[0].count, 3);
+//! ```
+
+use crate::text::normalize_journal;
+use crate::{Citation, CitationType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The number of citations from one journal.
+///
+/// The spellings that give `name` leave out the conference name that Embase
+/// appends to journals after ". Conference".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalCount {
+    /// The most frequent spelling of the journal name, the first seen on ties
+    pub name: String,
+    /// The normalized name the citations were grouped by
+    pub key: String,
+    /// Number of citations from the journal
+    pub count: usize,
+    /// ISSNs given by the citations, in the order first seen
+    pub issns: Vec<String>,
+}
+
+/// The number of citations published in one year.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearCount {
+    /// Publication year, or `None` for citations without a date
+    pub year: Option<i32>,
+    /// Number of citations from the year
+    pub count: usize,
+}
+
+/// The number of citations of one type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeCount {
+    /// Type of the citations, from [`Citation::normalized_type`]
+    pub citation_type: CitationType,
+    /// Number of citations of the type
+    pub count: usize,
+}
+
+/// Journal counts being collected, with the count of each spelling.
+struct JournalTally {
+    key: String,
+    spellings: Vec<(String, usize)>,
+    count: usize,
+    issns: Vec<String>,
+}
+
+/// Counts citations by journal, most frequent first, then by key.
+///
+/// The journal is taken from [`Citation::journal`], or from
+/// [`Citation::journal_abbr`] when there is no full name. Citations without a
+/// journal, or whose journal name has no letters or digits, are not counted.
+#[must_use]
+pub fn count_by_journal(citations: &[Citation]) -> Vec<JournalCount> {
+    let mut tallies: Vec<JournalTally> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for citation in citations {
+        let Some(name) = citation
+            .journal
+            .as_deref()
+            .or(citation.journal_abbr.as_deref())
+        else {
+            continue;
+        };
+        let key = normalize_journal(name);
+        if key.is_empty() {
+            continue;
+        }
+        let position = *positions.entry(key.clone()).or_insert_with(|| {
+            tallies.push(JournalTally {
+                key,
+                spellings: Vec::new(),
+                count: 0,
+                issns: Vec::new(),
+            });
+            tallies.len() - 1
+        });
+        let tally = &mut tallies[position];
+        tally.count += 1;
+        let name = name.split(". Conference").next().unwrap_or(name).trim();
+        match tally
+            .spellings
+            .iter_mut()
+            .find(|(spelling, _)| spelling == name)
+        {
+            Some((_, count)) => *count += 1,
+            None => tally.spellings.push((name.to_string(), 1)),
+        }
+        for issn in citation.issns() {
+            if !tally.issns.contains(&issn.value) {
+                tally.issns.push(issn.value);
+            }
+        }
+    }
+
+    let mut counts: Vec<JournalCount> = tallies
+        .into_iter()
+        .map(|tally| {
+            // max_by_key returns the last maximum, so search from the end to get the first
+            let name = tally
+                .spellings
+                .into_iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .map(|(spelling, _)| spelling)
+                .unwrap_or_default();
+            JournalCount {
+                name,
+                key: tally.key,
+                count: tally.count,
+                issns: tally.issns,
+            }
+        })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    counts
+}
+
+/// Counts citations by publication year, earliest first, with citations
+/// without a date last.
+#[must_use]
+pub fn count_by_year(citations: &[Citation]) -> Vec<YearCount> {
+    let mut counts: HashMap<Option<i32>, usize> = HashMap::new();
+    for citation in citations {
+        *counts
+            .entry(citation.date.as_ref().map(|date| date.year))
+            .or_default() += 1;
+    }
+    let mut counts: Vec<YearCount> = counts
+        .into_iter()
+        .map(|(year, count)| YearCount { year, count })
+        .collect();
+    counts.sort_by_key(|count| (count.year.is_none(), count.year));
+    counts
+}
+
+/// Counts citations by [`Citation::normalized_type`], most frequent first, with
+/// ties in the order the types first appear.
+#[must_use]
+pub fn count_by_type(citations: &[Citation]) -> Vec<TypeCount> {
+    let mut counts: Vec<TypeCount> = Vec::new();
+    for citation in citations {
+        let citation_type = citation.normalized_type();
+        match counts
+            .iter_mut()
+            .find(|count| count.citation_type == citation_type)
+        {
+            Some(count) => count.count += 1,
+            None => counts.push(TypeCount {
+                citation_type,
+                count: 1,
+            }),
+        }
+    }
+    counts.sort_by_key(|count| std::cmp::Reverse(count.count));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Date;
+
+    fn citation(journal: Option<&str>, abbr: Option<&str>, issn: &[&str]) -> Citation {
+        Citation {
+            journal: journal.map(str::to_string),
+            journal_abbr: abbr.map(str::to_string),
+            issn: issn.iter().map(|issn| issn.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_journal_spelling_ties_keep_first_seen() {
+        let citations = vec![
+            citation(Some("The Lancet"), None, &["0140-6736"]),
+            citation(Some("LANCET"), None, &["0140-6736 (Print)", "1474-547X"]),
+        ];
+        let counts = count_by_journal(&citations);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].name, "The Lancet");
+        assert_eq!(counts[0].issns, vec!["0140-6736", "1474-547X"]);
+    }
+
+    #[test]
+    fn test_journal_falls_back_to_abbreviation() {
+        let citations = vec![
+            citation(None, Some("BMJ"), &[]),
+            citation(Some("BMJ"), Some("Br Med J"), &[]),
+            citation(None, None, &[]),
+            citation(Some(" - "), None, &[]),
+        ];
+        let counts = count_by_journal(&citations);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].key, "bmj");
+        assert_eq!(counts[0].count, 2);
+    }
+
+    #[test]
+    fn test_years_sort_with_undated_last() {
+        let dated = |year| Citation {
+            date: Some(Date {
+                year,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        };
+        let citations = vec![Citation::new(), dated(2021), dated(2019), dated(2021)];
+        let years: Vec<_> = count_by_year(&citations)
+            .into_iter()
+            .map(|count| (count.year, count.count))
+            .collect();
+        assert_eq!(years, vec![(Some(2019), 1), (Some(2021), 2), (None, 1)]);
+    }
+}
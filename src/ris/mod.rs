@@ -19,19 +19,44 @@
 //! assert_eq!(citations[0].title, "Example Title");
 //! ```
 
+mod ovid;
 mod parse;
 mod structure;
 mod tags;
+mod write;
 
-use crate::{Citation, CitationParser};
-use parse::ris_parse;
+pub(crate) use parse::is_embedded_tag_at;
+
+use crate::error::ParseError;
+use crate::{Citation, CitationFormat, CitationParser, LenientOptions, LenientParse};
+use serde::{Deserialize, Serialize};
+pub(crate) use parse::ris_coverage;
+use parse::{ris_diagnostics, ris_parse};
+use tags::RisTag;
+pub use ovid::{OvidHeader, SearchLine, ovid_header};
+pub use write::RisWriter;
 
 /// Parser for RIS format citations.
 ///
 /// RIS is a standardized format for bibliographic citations that uses two-letter
 /// tags at the start of each line to denote different citation fields.
-#[derive(Debug, Clone, Default)]
-pub struct RisParser;
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RisParser {
+    dedupe_keywords: bool,
+    tag_mappings: Vec<(String, String)>,
+    times_cited_tag: Option<String>,
+    drop_truncated: bool,
+    max_abstract_length: Option<usize>,
+    max_authors: Option<usize>,
+    stamp_ingestion_time: bool,
+    fold_typography: bool,
+    sentence_case_titles: bool,
+    raw_abstracts: bool,
+    strip_note_html: bool,
+    split_attachments: bool,
+    archival: bool,
+}
 
 impl RisParser {
     /// Creates a new RIS parser instance.
@@ -44,7 +69,403 @@ impl RisParser {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Sets whether to remove case-insensitive duplicate keywords from each citation.
+    ///
+    /// See [`Citation::dedupe_keywords`]. Disabled by default.
+    pub fn set_dedupe_keywords(&mut self, enabled: bool) -> &mut Self {
+        self.dedupe_keywords = enabled;
+        self
+    }
+
+    /// Sets the maximum abstract length in characters.
+    ///
+    /// Longer abstracts are shortened with [`Citation::truncate_abstract`] and
+    /// marked with [`crate::ABSTRACT_TRUNCATED_FIELD`]. `None`, the default,
+    /// keeps abstracts in full.
+    pub fn set_max_abstract_length(&mut self, max_chars: Option<usize>) -> &mut Self {
+        self.max_abstract_length = max_chars;
+        self
+    }
+
+    /// Sets the maximum number of authors kept per citation.
+    ///
+    /// Longer author lists, such as those of consortium papers, are shortened
+    /// with [`Citation::truncate_authors`], which records the full count in
+    /// `author_count` and marks the citation with
+    /// [`crate::AUTHORS_TRUNCATED_FIELD`]. `None`, the default, keeps all authors.
+    pub fn set_max_authors(&mut self, max_authors: Option<usize>) -> &mut Self {
+        self.max_authors = max_authors;
+        self
+    }
+
+    /// Sets whether each citation records when it was parsed in `ingested_at`.
+    ///
+    /// All citations of one parse get the same UTC time, as RFC 3339. Disabled by
+    /// default, so parsing the same input always gives the same output.
+    pub fn set_stamp_ingestion_time(&mut self, enabled: bool) -> &mut Self {
+        self.stamp_ingestion_time = enabled;
+        self
+    }
+
+    /// Sets whether typographic characters in the text fields are replaced with
+    /// ASCII equivalents.
+    ///
+    /// See [`Citation::fold_typography`]. Disabled by default, so stored fields
+    /// keep the characters of the source.
+    pub fn set_fold_typography(&mut self, enabled: bool) -> &mut Self {
+        self.fold_typography = enabled;
+        self
+    }
+
+    /// Sets whether all-caps titles are converted to sentence case.
+    ///
+    /// See [`Citation::sentence_case_title`]. Disabled by default, so titles
+    /// keep the case of the source.
+    pub fn set_sentence_case_titles(&mut self, enabled: bool) -> &mut Self {
+        self.sentence_case_titles = enabled;
+        self
+    }
+
+    /// Sets whether abstracts are cleaned of format-specific line breaks and
+    /// spacing.
+    ///
+    /// See [`Citation::clean_abstract`]. Enabled by default; disable it to keep
+    /// abstracts exactly as written in the source.
+    pub fn set_clean_abstracts(&mut self, enabled: bool) -> &mut Self {
+        self.raw_abstracts = !enabled;
+        self
+    }
+
+    /// Sets whether values are stored exactly as the file gives them.
+    ///
+    /// Archival mode is for collections that must keep what the file said. It
+    /// disables the normalization this parser applies by default:
+    ///
+    /// - `SP` and `EP` are joined into `pages` without completing a shortened
+    ///   end page, so "1234" and "45" give "1234-45" rather than "1234-1245"
+    /// - the `DO` value is the DOI as written, without removing a resolver
+    ///   prefix or stray text, and no DOI is taken from `UR` or link tags
+    /// - the `PY` and `Y1` values read into `date` are also kept in
+    ///   `extra_fields` under [`crate::RAW_FIELD_PREFIX`], e.g. "raw:PY"
+    /// - abstracts are not cleaned, whatever [`RisParser::set_clean_abstracts`]
+    ///   says
+    ///
+    /// Options that are disabled by default, such as typography folding, still
+    /// apply when enabled. The deduplicator normalizes DOIs and pages when
+    /// comparing, so archival citations are matched like others. Disabled by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - Example\nPY  - 2023///\nSP  - 1234\nEP  - 45\n\
+    ///              DO  - https://doi.org/10.1000/XYZ\nER  - \n";
+    /// let mut parser = RisParser::new();
+    /// parser.set_archival(true);
+    /// let citation = &parser.parse(input).unwrap()[0];
+    /// assert_eq!(citation.pages.as_deref(), Some("1234-45"));
+    /// assert_eq!(citation.doi.as_deref(), Some("https://doi.org/10.1000/XYZ"));
+    /// assert_eq!(citation.date.as_ref().unwrap().year, 2023);
+    /// assert_eq!(citation.extra_fields["raw:PY"], vec!["2023///"]);
+    /// ```
+    pub fn set_archival(&mut self, enabled: bool) -> &mut Self {
+        self.archival = enabled;
+        self
+    }
+
+    /// Sets whether to drop a final record that is cut off by the end of the input.
+    ///
+    /// A last record without an `ER` tag usually means the file was truncated, for
+    /// example by an interrupted download. By default it is kept and marked with
+    /// [`crate::TRUNCATED_FIELD`] in `extra_fields`; when enabled it is dropped, so
+    /// a partial record without a title does not fail the whole parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - Complete\nER  - \n\nTY  - JOUR\nAB  - This study investiga";
+    /// assert!(RisParser::new().parse(input).is_err());
+    ///
+    /// let mut parser = RisParser::new();
+    /// parser.set_drop_truncated(true);
+    /// assert_eq!(parser.parse(input).unwrap().len(), 1);
+    /// ```
+    pub fn set_drop_truncated(&mut self, enabled: bool) -> &mut Self {
+        self.drop_truncated = enabled;
+        self
+    }
+
+    /// Sets whether HTML markup is removed from notes.
+    ///
+    /// Zotero writes each note as an `N1` tag holding the note's HTML. When
+    /// enabled, tags are dropped and character references decoded, with
+    /// paragraphs kept on separate lines. Disabled by default.
+    pub fn set_strip_note_html(&mut self, enabled: bool) -> &mut Self {
+        self.strip_note_html = enabled;
+        self
+    }
+
+    /// Sets whether links to local attachments are moved out of `urls`.
+    ///
+    /// When enabled, `file://` and `zotero://` links (such as the PDF paths Zotero
+    /// writes under `L1`) are kept in `extra_fields` under
+    /// [`crate::ATTACHMENTS_FIELD`] instead. Disabled by default.
+    pub fn set_split_attachments(&mut self, enabled: bool) -> &mut Self {
+        self.split_attachments = enabled;
+        self
+    }
+
+    /// Reads values of the `from` tag as if they had been given under the `to` tag.
+    ///
+    /// Useful for exporters that put standard data under non-standard tags. Mapped
+    /// values are added after any values already present for `to`, so for fields
+    /// that keep only the first value (such as `DO`) an existing value wins and
+    /// the mapped one is kept in `extra_fields` (see [`crate::EXTRA_FIELD_PREFIX`]).
+    /// Mappings are applied in the order they were added. Author tags are read
+    /// while parsing and cannot be remapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let mut parser = RisParser::new();
+    /// parser.set_tag_mapping("M3", "DO");
+    ///
+    /// let input = "TY  - JOUR\nTI  - Example\nM3  - 10.1000/example\nER  -";
+    /// let citations = parser.parse(input).unwrap();
+    /// assert_eq!(citations[0].doi.as_deref(), Some("10.1000/example"));
+    /// ```
+    pub fn set_tag_mapping(&mut self, from: &str, to: &str) -> &mut Self {
+        self.tag_mappings.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Sets the tag holding the number of times a record has been cited.
+    ///
+    /// RIS has no standard tag for the count, so exporters that write one put it
+    /// under a custom tag. Its first numeric value, with thousands separators
+    /// dropped, is read as `times_cited`; non-numeric values are ignored. The tag
+    /// is read after [`RisParser::set_tag_mapping`] mappings are applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let mut parser = RisParser::new();
+    /// parser.set_times_cited_tag("C8");
+    ///
+    /// let input = "TY  - JOUR\nTI  - Example\nC8  - 1,204\nER  -";
+    /// let citations = parser.parse(input).unwrap();
+    /// assert_eq!(citations[0].times_cited, Some(1204));
+    /// ```
+    pub fn set_times_cited_tag(&mut self, tag: &str) -> &mut Self {
+        self.times_cited_tag = Some(tag.to_string());
+        self
+    }
+
+    /// Creates a parser for Google Scholar RIS exports.
+    ///
+    /// Google Scholar uses `T1`/`A1` for titles and authors and `Y1` dates with
+    /// trailing slashes (`1998///`), all of which the default parser reads, so this
+    /// is equivalent to [`RisParser::new`]. It is provided so callers can select a
+    /// preset per source without special-casing.
+    #[must_use]
+    pub fn google_scholar() -> Self {
+        Self::new()
+    }
+
+    /// Creates a parser for Ovid Embase RIS exports.
+    ///
+    /// The publication type Ovid writes under `M3` (e.g. "Conference Abstract")
+    /// is added to `citation_type` after the `TY` value, and case-insensitive
+    /// duplicate keywords from Emtree and author keywords are removed. The `AN`
+    /// accession number stays in `extra_fields`.
+    #[must_use]
+    pub fn ovid_embase() -> Self {
+        let mut parser = Self::new();
+        parser.set_tag_mapping("M3", "TY").set_dedupe_keywords(true);
+        parser
+    }
+
+    /// Creates a parser for Zotero RIS exports.
+    ///
+    /// HTML is stripped from `N1` notes, attachment links are moved from `urls` to
+    /// [`crate::ATTACHMENTS_FIELD`], and the Zotero item key Zotero writes under
+    /// `ID` is kept in `extra_fields` under "zotero_key" rather than read as a
+    /// PMID. The library catalog under `DB` stays in `extra_fields`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{ATTACHMENTS_FIELD, CitationParser, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - Example\nID  - 8XKQ2M3T\n\
+    ///              L1  - file:///home/user/Zotero/storage/8XKQ2M3T/example.pdf\n\
+    ///              N1  - <p>Include at <b>full text</b></p>\nER  - ";
+    /// let citation = &RisParser::zotero().parse(input).unwrap()[0];
+    ///
+    /// assert_eq!(citation.notes.as_deref(), Some("Include at full text"));
+    /// assert!(citation.urls.is_empty());
+    /// assert_eq!(citation.extra_fields[ATTACHMENTS_FIELD].len(), 1);
+    /// assert_eq!(citation.extra_fields["zotero_key"], vec!["8XKQ2M3T"]);
+    /// assert_eq!(citation.pmid, None);
+    /// ```
+    #[must_use]
+    pub fn zotero() -> Self {
+        let mut parser = Self::new();
+        parser
+            .set_tag_mapping("ID", "zotero_key")
+            .set_strip_note_html(true)
+            .set_split_attachments(true);
+        parser
+    }
+
+    /// Creates a parser for Scopus RIS exports.
+    ///
+    /// Scopus writes the PubMed ID under `C7` and sometimes the DOI under `M3`;
+    /// these are read as `ID` (the PMID) and `DO`.
+    #[must_use]
+    pub fn scopus() -> Self {
+        let mut parser = Self::new();
+        parser.set_tag_mapping("C7", "ID").set_tag_mapping("M3", "DO");
+        parser
+    }
+
+    /// Parses `input` into `out`, as [`CitationParser::parse_into`] when `errors`
+    /// is `None`; otherwise records that fail to convert are pushed to `errors`.
+    fn parse_records(
+        &self,
+        input: &str,
+        out: &mut Vec<Citation>,
+        errors: Option<&mut Vec<ParseError>>,
+    ) -> Result<usize, ParseError> {
+        crate::utils::check_format(input, CitationFormat::Ris)?;
+        let mut raw_citations = ris_parse(input)?;
+        if self.drop_truncated {
+            raw_citations.retain(|raw| !raw.truncated);
+        }
+        for (from, to) in &self.tag_mappings {
+            let from = RisTag::from_tag(from);
+            for raw in &mut raw_citations {
+                raw.remap_tag(&from, RisTag::from_tag(to));
+            }
+        }
+        let times_cited_tag = self.times_cited_tag.as_deref().map(RisTag::from_tag);
+        let count = crate::utils::append_citations(
+            out,
+            raw_citations.into_iter().map(|mut raw| {
+                raw.archival = self.archival;
+                let times_cited = times_cited_tag
+                    .as_ref()
+                    .and_then(|tag| raw.remove(tag))
+                    .and_then(|values| values.iter().find_map(|v| crate::utils::parse_count(v)));
+                Citation::try_from(raw).map(|mut citation| {
+                    citation.times_cited = times_cited;
+                    citation
+                })
+            }),
+            errors,
+        )?;
+        crate::utils::set_parsed_from_of_last(out, count, CitationFormat::Ris);
+        if self.dedupe_keywords {
+            crate::utils::dedupe_keywords_of_last(out, count);
+        }
+        if self.fold_typography {
+            crate::utils::fold_typography_of_last(out, count);
+        }
+        if self.sentence_case_titles {
+            crate::utils::sentence_case_titles_of_last(out, count);
+        }
+        if !self.raw_abstracts && !self.archival {
+            crate::utils::clean_abstracts_of_last(out, count);
+        }
+        if let Some(max_chars) = self.max_abstract_length {
+            crate::utils::truncate_abstracts_of_last(out, count, max_chars);
+        }
+        if let Some(max_authors) = self.max_authors {
+            crate::utils::truncate_authors_of_last(out, count, max_authors);
+        }
+        if self.stamp_ingestion_time {
+            crate::utils::stamp_ingestion_time_of_last(out, count);
+        }
+        let start = out.len() - count;
+        for citation in &mut out[start..] {
+            if self.strip_note_html {
+                strip_note_html(citation);
+            }
+            if self.split_attachments {
+                split_attachments(citation);
+            }
+        }
+        Ok(count)
+    }
+
+    /// Parses RIS content on the rayon thread pool, giving the same result as
+    /// [`CitationParser::parse`].
+    ///
+    /// The input is split at `TY` lines right after an `ER` line into chunks of
+    /// many records, which are parsed in parallel and concatenated in input
+    /// order. This pays off for large exports; for small ones it is no faster
+    /// than [`CitationParser::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the `ParseError` of the first record that fails to convert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::{CitationParser, RisParser};
+    ///
+    /// let input = "TY  - JOUR\nTI  - First\nER  - \nTY  - JOUR\nTI  - Second\nER  - \n";
+    /// let parser = RisParser::new();
+    /// let citations = parser.parse_parallel(input).unwrap();
+    /// assert_eq!(citations.len(), 2);
+    /// assert_eq!(citations[1].title, "Second");
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn parse_parallel(&self, input: &str) -> Result<Vec<Citation>, ParseError> {
+        self.parse_parallel_in_chunks(input, crate::utils::PARALLEL_CHUNK_BYTES)
+    }
+
+    /// [`RisParser::parse_parallel`] with chunks of at least `min_bytes`.
+    #[cfg(feature = "parallel")]
+    fn parse_parallel_in_chunks(
+        &self,
+        input: &str,
+        min_bytes: usize,
+    ) -> Result<Vec<Citation>, ParseError> {
+        // Only a record closed by `ER` may end a chunk, as an unclosed last
+        // record counts as truncated
+        let mut after_end = false;
+        let chunks = crate::utils::record_chunks(input, min_bytes, |line| {
+            let starts_record = after_end && line.starts_with("TY  -");
+            if !line.trim().is_empty() {
+                after_end = line.starts_with("ER  -")
+                    || (after_end && ovid::is_record_number(line.trim()));
+            }
+            starts_record
+        });
+
+        // All citations of one parse share one ingestion time
+        let mut parser = self.clone();
+        parser.stamp_ingestion_time = false;
+        let mut citations =
+            crate::utils::parse_chunks_parallel(&chunks, |chunk| parser.parse(chunk.text))?;
+        if self.stamp_ingestion_time {
+            let count = citations.len();
+            crate::utils::stamp_ingestion_time_of_last(&mut citations, count);
+        }
+        Ok(citations)
     }
 }
 
@@ -63,15 +484,71 @@ impl CitationParser for RisParser {
     ///
     /// Returns `ParseError` if the input is malformed or contains no valid citations
     fn parse(&self, input: &str) -> std::result::Result<Vec<Citation>, crate::error::ParseError> {
-        let raw_citations = ris_parse(input)?;
+        let mut citations = Vec::new();
+        self.parse_into(input, &mut citations)?;
+        Ok(citations)
+    }
 
-        let mut citations = Vec::with_capacity(raw_citations.len());
-        for raw in raw_citations {
-            let citation = raw.try_into()?;
-            citations.push(citation);
-        }
+    /// Parses RIS formatted content, appending the citations to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed; `out` is left unchanged
+    fn parse_into(&self, input: &str, out: &mut Vec<Citation>) -> Result<usize, ParseError> {
+        self.parse_records(input, out, None)
+    }
 
-        Ok(citations)
+    /// Parses RIS content, skipping records that fail to convert.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the input is malformed or the failures exceed `options`
+    fn parse_lenient(
+        &self,
+        input: &str,
+        options: LenientOptions,
+    ) -> Result<LenientParse, ParseError> {
+        let mut citations = Vec::new();
+        let mut errors = Vec::new();
+        self.parse_records(input, &mut citations, Some(&mut errors))?;
+        let mut result = options.check(citations, errors, ris_coverage(input).unparsed)?;
+        result.diagnostics.extend(ris_diagnostics(input));
+        Ok(result)
+    }
+}
+
+/// Removes HTML markup from each note, dropping notes left empty.
+fn strip_note_html(citation: &mut Citation) {
+    let Some(notes) = &citation.notes else {
+        return;
+    };
+    let notes: Vec<String> = notes
+        .split("\n\n")
+        .map(crate::utils::strip_html)
+        .filter(|note| !note.is_empty())
+        .collect();
+    citation.notes = (!notes.is_empty()).then(|| notes.join("\n\n"));
+}
+
+/// Moves `file://` and `zotero://` links from `urls` to [`crate::ATTACHMENTS_FIELD`].
+fn split_attachments(citation: &mut Citation) {
+    let is_attachment = |url: &str| {
+        let url = url.trim_start().to_ascii_lowercase();
+        url.starts_with("file://") || url.starts_with("zotero://")
+    };
+    let (attachments, urls): (Vec<String>, Vec<String>) = std::mem::take(&mut citation.urls)
+        .into_iter()
+        .partition(|url| is_attachment(url));
+    citation.urls = urls;
+    for attachment in &attachments {
+        citation.link_kinds.remove(attachment);
+    }
+    if !attachments.is_empty() {
+        citation
+            .extra_fields
+            .entry(crate::ATTACHMENTS_FIELD.to_string())
+            .or_default()
+            .extend(attachments);
     }
 }
 
@@ -79,6 +556,7 @@ impl CitationParser for RisParser {
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use crate::{CitationWriter, ContributorRole, Date, LinkKind};
 
     #[test]
     fn test_parse_simple_ris() {
@@ -114,6 +592,21 @@ ER  -
         assert_eq!(citation.keywords.len(), 2);
     }
 
+    #[test]
+    fn test_date_range() {
+        let input = "TY  - JOUR\nTI  - Quarterly\nPY  - 2020///Jan-Mar\nER  -\n\nTY  - JOUR\nTI  - Single\nPY  - 2021/04/02\nER  -";
+        let parser = RisParser::new();
+        let result = parser.parse(input).unwrap();
+
+        let start = result[0].date.as_ref().unwrap();
+        assert_eq!((start.year, start.month), (2020, Some(1)));
+        let end = result[0].date_end.as_ref().unwrap();
+        assert_eq!((end.year, end.month), (2020, Some(3)));
+
+        assert_eq!(result[1].date.as_ref().unwrap().day, Some(2));
+        assert_eq!(result[1].date_end, None);
+    }
+
     #[test]
     fn test_parse_gs_format() {
         let input = r#"TY  - JOUR
@@ -148,7 +641,7 @@ ER  -
 
 "#;
         let parser = RisParser::new();
-        let citations = parser.parse(&input).unwrap();
+        let citations = parser.parse(input).unwrap();
         assert_eq!(
             citations.len(),
             2,
@@ -182,4 +675,394 @@ ER  -"#;
         );
         assert_eq!(result[0].doi, Some("10.1000/test".to_string()));
     }
+
+    #[test]
+    fn test_doi_from_url_must_be_strictly_valid() {
+        let input = "TY  - JOUR\nTI  - Copied link\nUR  - https://doi.org/10.1000/xyz123)\n\
+                     UR  - https://doi.org/10.1000/xyz\u{2013}123\n\
+                     UR  - https://dx.doi.org/10.1000/XYZ124\nER  - \n\
+                     TY  - JOUR\nTI  - Only a broken link\nUR  - https://doi.org/10.5\nER  - \n";
+        let result = RisParser::new().parse(input).unwrap();
+        assert_eq!(result[0].doi.as_deref(), Some("10.1000/xyz124"));
+        assert_eq!(result[0].urls.len(), 3);
+        assert_eq!(result[1].doi, None);
+
+        // The DOI field itself is still read leniently
+        let input = "TY  - JOUR\nTI  - Lenient field\nDO  - 10.1000/xyz123)\nER  - \n";
+        let result = RisParser::new().parse(input).unwrap();
+        assert_eq!(result[0].doi.as_deref(), Some("10.1000/xyz123)"));
+    }
+
+    #[test]
+    fn test_parse_into_appends() {
+        let input = "TY  - JOUR\nTI  - First\nER  -\n\nTY  - BOOK\nTI  - Second\nER  -";
+        let parser = RisParser::new();
+        let mut citations = parser.parse("TY  - JOUR\nTI  - Existing\nER  -").unwrap();
+
+        let count = parser.parse_into(input, &mut citations).unwrap();
+        assert_eq!(count, parser.parse(input).unwrap().len());
+        assert_eq!(count, 2);
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0].title, "Existing");
+        assert_eq!(citations[2].title, "Second");
+
+        // A record without a title fails and nothing is appended
+        let invalid = "TY  - JOUR\nTI  - Valid\nER  -\n\nTY  - JOUR\nAU  - Smith\nER  -";
+        assert!(parser.parse_into(invalid, &mut citations).is_err());
+        assert_eq!(citations.len(), 3);
+    }
+
+    #[test]
+    fn test_truncated_final_record() {
+        let input = "TY  - JOUR\nTI  - Complete\nER  - \n\nTY  - JOUR\nTI  - Partial\nAB  - This study investiga";
+
+        let citations = RisParser::new().parse(input).unwrap();
+        assert_eq!(citations.len(), 2);
+        assert!(!citations[0].extra_fields.contains_key(crate::TRUNCATED_FIELD));
+        assert_eq!(citations[1].extra_fields[crate::TRUNCATED_FIELD], vec!["true"]);
+        assert_eq!(
+            citations[1].abstract_text.as_deref(),
+            Some("This study investiga")
+        );
+
+        let mut parser = RisParser::new();
+        parser.set_drop_truncated(true);
+        let citations = parser.parse(input).unwrap();
+        assert_eq!(citations.len(), 1);
+        assert_eq!(citations[0].title, "Complete");
+
+        // A record missing only its ER before the next TY is not truncated
+        let input = "TY  - JOUR\nTI  - First\nTY  - JOUR\nTI  - Second\nER  -";
+        let citations = parser.parse(input).unwrap();
+        assert_eq!(citations.len(), 2);
+        assert!(citations.iter().all(|c| c.extra_fields.is_empty()));
+    }
+
+    #[test]
+    fn test_tag_mapping() {
+        let input = "TY  - JOUR\nTI  - Mapped\nDO  - 10.1000/first\nM3  - 10.1000/second\nC7  - 12345678\nER  -";
+
+        let mut parser = RisParser::new();
+        parser.set_tag_mapping("C7", "ID").set_tag_mapping("M3", "DO");
+        let citation = &parser.parse(input).unwrap()[0];
+        assert_eq!(citation.pmid.as_deref(), Some("12345678"));
+        assert_eq!(citation.doi.as_deref(), Some("10.1000/first"));
+        assert_eq!(citation.extra_fields["x-DO"], vec!["10.1000/second"]);
+
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.pmid, None);
+        assert_eq!(citation.extra_fields["M3"], vec!["10.1000/second"]);
+    }
+
+    #[test]
+    fn test_reference_id() {
+        let parse = |id: &str| {
+            let input = format!("TY  - JOUR\nTI  - Identified\nID  - {}\nER  -", id);
+            RisParser::new().parse(&input).unwrap().remove(0)
+        };
+
+        // PubMed-derived RIS
+        let citation = parse("33510001");
+        assert_eq!(citation.pmid.as_deref(), Some("33510001"));
+        assert!(citation.extra_fields.is_empty());
+
+        // EndNote record number
+        let citation = parse("1432");
+        assert_eq!(citation.pmid, None);
+        assert_eq!(citation.extra_fields["reference_id"], vec!["1432"]);
+
+        // Zotero item key
+        let citation = parse("ABC123XYZ");
+        assert_eq!(citation.pmid, None);
+        assert_eq!(citation.extra_fields["reference_id"], vec!["ABC123XYZ"]);
+
+        // Accession numbers
+        for accession in ["2013510001", "L636118453", "WOS:000123456700001", "2-s2.0-85012345678"] {
+            let citation = parse(accession);
+            assert_eq!(citation.pmid, None);
+            assert_eq!(citation.extra_fields["AN"], vec![accession]);
+        }
+    }
+
+    #[test]
+    fn test_dedupe_keywords_flag() {
+        let input = "TY  - JOUR\nTI  - Merged\nKW  - Sepsis\nKW  - sepsis.\nKW  - Critical Care\nER  - \n";
+
+        let mut parser = RisParser::new();
+        parser.set_dedupe_keywords(true);
+        assert_eq!(
+            parser.parse(input).unwrap()[0].keywords,
+            vec!["Sepsis", "Critical Care"]
+        );
+
+        // Citations already in the vector are left alone
+        let mut citations = RisParser::new().parse(input).unwrap();
+        parser.parse_into(input, &mut citations).unwrap();
+        assert_eq!(citations[0].keywords.len(), 3);
+        assert_eq!(citations[1].keywords, vec!["Sepsis", "Critical Care"]);
+    }
+
+    #[test]
+    fn test_max_abstract_length() {
+        let input = "TY  - JOUR\nTI  - Long\nAB  - Éléments prognostiques\nER  - \n";
+
+        let mut parser = RisParser::new();
+        parser.set_max_abstract_length(Some(2));
+        let citation = &parser.parse(input).unwrap()[0];
+        assert_eq!(citation.abstract_text.as_deref(), Some("Él…"));
+        assert_eq!(citation.extra_fields[crate::ABSTRACT_TRUNCATED_FIELD], vec!["true"]);
+
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.abstract_text.as_deref(), Some("Éléments prognostiques"));
+    }
+
+    #[test]
+    fn test_max_authors() {
+        let input = "TY  - JOUR\nTI  - Consortium\nAU  - Okafor, C\nAU  - Tanaka, H\nAU  - Smith, J\nER  - \n";
+
+        let mut parser = RisParser::new();
+        parser.set_max_authors(Some(2));
+        let citation = &parser.parse(input).unwrap()[0];
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(citation.author_count, Some(3));
+        assert_eq!(citation.extra_fields[crate::AUTHORS_TRUNCATED_FIELD], vec!["true"]);
+
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.authors.len(), 3);
+        assert_eq!(citation.author_count, None);
+    }
+
+    #[test]
+    fn test_times_cited_tag() {
+        let input = "TY  - JOUR\nTI  - Counted\nN1  - 2 345\nER  - \n\
+                     TY  - JOUR\nTI  - Unknown\nN1  - not yet indexed\nER  - \n";
+
+        let mut parser = RisParser::new();
+        parser.set_times_cited_tag("N1");
+        let citations = parser.parse(input).unwrap();
+        assert_eq!(citations[0].times_cited, Some(2345));
+        assert_eq!(citations[0].notes, None);
+        assert_eq!(citations[1].times_cited, None);
+
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.times_cited, None);
+        assert_eq!(citation.notes.as_deref(), Some("2 345"));
+    }
+
+    #[test]
+    fn test_research_notes() {
+        let input = "TY  - JOUR\nTI  - Noted\nRN  - Check the CONSORT diagram\n\
+                     N1  - Screening: include\nN1  - \nER  - \n";
+
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        assert_eq!(
+            citation.notes.as_deref(),
+            Some("Screening: include\n\nCheck the CONSORT diagram")
+        );
+        assert!(citation.extra_fields.is_empty());
+    }
+
+    #[test]
+    fn test_dates_written_by_other_tools() {
+        let input = "TY  - JOUR\nTI  - Two-digit year\nY1  - 98///\nER  - \n\
+                     TY  - JOUR\nTI  - Unreadable year\nPY  - 0003\nY1  - 03/15/2021\nER  - \n\
+                     TY  - JOUR\nTI  - No readable date\nPY  - In press\nER  - \n";
+
+        let citations = RisParser::new().parse(input).unwrap();
+        let date = |year, month, day| Some(Date { year, month, day });
+        assert_eq!(citations[0].date, date(1998, None, None));
+        assert_eq!(citations[1].date, date(2021, Some(3), Some(15)));
+        assert!(citations[1].extra_fields.is_empty());
+        assert_eq!(citations[2].date, None);
+        assert_eq!(citations[2].extra_fields["PY"], vec!["In press"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parse_parallel_in_small_chunks() {
+        let input = "Provider: Example\n\nTY  - JOUR\nTI  - First\nER  - \n\n\
+                     TY  - JOUR\nTI  - Unclosed\n\
+                     TY  - JOUR\nTI  - Third\nER  - \n\
+                     TY  - JOUR\nTI  - Cut off\nAB  - This study investiga";
+        let parser = RisParser::new();
+        let json = |citations: &[Citation]| serde_json::to_value(citations).unwrap();
+
+        let citations = parser.parse_parallel_in_chunks(input, 1).unwrap();
+        assert_eq!(json(&citations), json(&parser.parse(input).unwrap()));
+        assert_eq!(citations.len(), 4);
+        assert!(!citations[1].extra_fields.contains_key(crate::TRUNCATED_FIELD));
+        assert!(citations[3].extra_fields.contains_key(crate::TRUNCATED_FIELD));
+
+        let input = "TY  - JOUR\nTI  - First\nER  - \nTY  - JOUR\nAU  - Smith, J\nER  - \n";
+        assert_eq!(
+            parser.parse_parallel_in_chunks(input, 1).unwrap_err().to_string(),
+            parser.parse(input).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_withdrawn_title() {
+        let input = "TY  - JOUR\nTI  - WITHDRAWN: Statin therapy after acute stroke\nT2  - The Lancet\nER  - \n\
+                     TY  - JOUR\nTI  - Withdrawal of statin therapy after acute stroke\nER  - \n";
+
+        let citations = RisParser::new().parse(input).unwrap();
+        assert_eq!(
+            citations[0].retraction_status,
+            Some(crate::RetractionStatus::Retracted { notice: None })
+        );
+        assert_eq!(citations[0].title, "WITHDRAWN: Statin therapy after acute stroke");
+        assert_eq!(citations[1].retraction_status, None);
+    }
+
+    #[test]
+    fn test_fold_typography() {
+        let input = "TY  - JOUR\nTI  - Alzheimer\u{2019}s disease \u{2013} a review\n\
+                     AU  - O\u{2019}Brien, Ann\nKW  - \u{FB01}brosis\nER  - \n";
+
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        assert_eq!(citation.title, "Alzheimer\u{2019}s disease \u{2013} a review");
+        assert_eq!(citation.authors[0].name, "O\u{2019}Brien");
+
+        let mut parser = RisParser::new();
+        parser.set_fold_typography(true);
+        let citation = &parser.parse(input).unwrap()[0];
+        assert_eq!(citation.title, "Alzheimer's disease - a review");
+        assert_eq!(citation.authors[0].name, "O'Brien");
+        assert_eq!(citation.keywords, vec!["fibrosis"]);
+    }
+
+    #[test]
+    fn test_sentence_case_titles() {
+        let input = "TY  - JOUR\nTI  - DNA REPAIR IN E. COLI: A REVIEW\nER  - \n\n\
+                     TY  - JOUR\nTI  - The Role of NF-kB in Sepsis\nER  - \n";
+
+        let citations = RisParser::new().parse(input).unwrap();
+        assert_eq!(citations[0].title, "DNA REPAIR IN E. COLI: A REVIEW");
+
+        let mut parser = RisParser::new();
+        parser.set_sentence_case_titles(true);
+        let citations = parser.parse(input).unwrap();
+        assert_eq!(citations[0].title, "DNA repair in E. coli: A review");
+        assert_eq!(citations[1].title, "The Role of NF-kB in Sepsis");
+    }
+
+    #[test]
+    fn test_link_kinds() {
+        let input = "TY  - JOUR\nTI  - Links\n\
+                     L1  - https://example.org/download?id=1\n\
+                     L2  - https://example.org/fulltext\n\
+                     UR  - https://example.org/article\n\
+                     LK  - https://example.org/landing\n\
+                     UR  - https://example.org/files/paper.pdf\n\
+                     UR  - https://dx.doi.org/10.1000/xyz\n\
+                     L1  - file:///home/me/paper.pdf\nER  - \n";
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        let kinds: Vec<_> = citation.links().iter().map(|link| link.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LinkKind::Pdf,
+                LinkKind::LocalFile,
+                LinkKind::FullText,
+                LinkKind::Web,
+                LinkKind::Pdf,
+                LinkKind::DoiResolver,
+                LinkKind::Web,
+            ]
+        );
+        assert_eq!(citation.best_url(), Some("https://example.org/fulltext"));
+
+        // The writer keeps the tags the kinds came from
+        let output = RisWriter::new().write_to_string([citation]).unwrap();
+        assert!(output.contains("L1  - https://example.org/download?id=1"));
+        assert!(output.contains("L2  - https://example.org/fulltext"));
+        assert!(output.contains("UR  - https://example.org/landing"));
+        let reparsed = &RisParser::new().parse(&output).unwrap()[0];
+        assert_eq!(reparsed.links(), citation.links());
+
+        let mut parser = RisParser::new();
+        parser.set_split_attachments(true);
+        let citation = &parser.parse(input).unwrap()[0];
+        assert!(!citation.link_kinds.contains_key("file:///home/me/paper.pdf"));
+    }
+
+    #[test]
+    fn test_book_chapter_contributors() {
+        let input = "TY  - CHAP\nTI  - Sepsis in the emergency department\n\
+                     AU  - Okafor, Chidi\nAU  - Lindqvist, Anna\n\
+                     A2  - Tanaka, Hiroshi\nED  - Moreau, Claire\n\
+                     A4  - Novak, Jan\nBT  - Emergency Medicine\nPY  - 2020\nER  - \n";
+        let citation = &RisParser::new().parse(input).unwrap()[0];
+        let names = |citation: &Citation, role| {
+            citation
+                .contributors_with_role(role)
+                .map(|person| person.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(citation.authors.len(), 2);
+        assert_eq!(names(citation, ContributorRole::Author), vec!["Okafor", "Lindqvist"]);
+        assert_eq!(names(citation, ContributorRole::Editor), vec!["Tanaka", "Moreau"]);
+        assert_eq!(citation.editors().count(), 2);
+        assert_eq!(names(citation, ContributorRole::Translator), vec!["Novak"]);
+        assert!(!citation.extra_fields.contains_key("ED"));
+
+        // Editors are written as A2 and translators as A4, after the authors
+        let output = RisWriter::new().write_to_string([citation]).unwrap();
+        assert!(output.contains(
+            "AU  - Okafor, Chidi\nAU  - Lindqvist, Anna\n\
+             A2  - Tanaka, Hiroshi\nA2  - Moreau, Claire\nA4  - Novak, Jan\n"
+        ));
+        let reparsed = &RisParser::new().parse(&output).unwrap()[0];
+        assert_eq!(reparsed.authors, citation.authors);
+        assert_eq!(reparsed.contributors, citation.contributors);
+
+        #[cfg(feature = "xml")]
+        {
+            let xml = crate::EndNoteXmlWriter::new().write_to_string([citation]).unwrap();
+            assert!(xml.contains(
+                "<secondary-authors><author>Tanaka, Hiroshi</author>\
+                 <author>Moreau, Claire</author></secondary-authors>"
+            ));
+            let reparsed = &crate::EndNoteXmlParser::new().parse(&xml).unwrap()[0];
+            assert_eq!(reparsed.authors, citation.authors);
+            assert_eq!(reparsed.contributors, citation.contributors);
+        }
+    }
+
+    #[test]
+    fn test_conference_metadata() {
+        let input = "TY  - CONF\nTI  - Deep Residual Learning for Image Recognition\n\
+                     AU  - He, Kaiming\n\
+                     T2  - 2016 IEEE Conference on Computer Vision and Pattern Recognition (CVPR)\n\
+                     CY  - Las Vegas, NV, USA\nPY  - 2016\nY2  - 27-30 June 2016\nSP  - 770\n\
+                     EP  - 778\nER  - \n\n\
+                     TY  - JOUR\nTI  - Journal article\nCY  - London\nY2  - 2024/01/05\nER  - \n";
+        let citations = RisParser::new().parse(input).unwrap();
+
+        let conference = citations[0].conference.as_ref().unwrap();
+        assert_eq!(
+            conference.name.as_deref(),
+            Some("2016 IEEE Conference on Computer Vision and Pattern Recognition (CVPR)")
+        );
+        assert_eq!(conference.location.as_deref(), Some("Las Vegas, NV, USA"));
+        assert_eq!(conference.date.as_deref(), Some("27-30 June 2016"));
+        assert_eq!(citations[0].journal, conference.name);
+        assert_eq!(citations[0].date.as_ref().unwrap().year, 2016);
+        assert!(!citations[0].extra_fields.contains_key("CY"));
+
+        assert!(citations[1].conference.is_none());
+        assert_eq!(citations[1].extra_fields["CY"], vec!["London"]);
+    }
+
+    #[test]
+    fn test_placeholder_authors() {
+        let input = "TY  - JOUR\nTI  - Editorial\nAU  - Anonymous\nER  - \n\n\
+                     TY  - JOUR\nTI  - Letter\nAU  - \nAU  - Smith, John\nER  - \n";
+        let citations = RisParser::new().parse(input).unwrap();
+
+        assert!(citations[0].authors.is_empty());
+        assert_eq!(citations[0].extra_fields[crate::ANONYMOUS_FIELD], vec!["true"]);
+        assert_eq!(citations[1].authors.len(), 1);
+        assert!(!citations[1].extra_fields.contains_key(crate::ANONYMOUS_FIELD));
+    }
 }
@@ -0,0 +1,192 @@
+//! The header and record numbers of Ovid exports.
+//!
+//! Ovid writes a header before the first record, with the databases searched
+//! and the search strategy, and numbers each record with a line such as
+//! `<1. >`. The RIS parser skips the header and ends a record at each record
+//! number as at `ER`; [`ovid_header`] reads the header instead.
+
+/// One line of an Ovid search strategy, such as `3     1 and 2 (89)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchLine {
+    /// Number of the search line
+    pub number: u32,
+    /// Search as written, without the number and result count
+    pub query: String,
+    /// Number of records the search found, when given
+    pub results: Option<u64>,
+}
+
+/// The header Ovid writes before the first record of an export.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OvidHeader {
+    /// Databases searched, with their coverage, e.g. "Embase <1974 to 2024 March 01>"
+    pub databases: Vec<String>,
+    /// The lines of the search strategy, in order
+    pub search_strategy: Vec<SearchLine>,
+}
+
+/// Reads the header of an Ovid export, which the RIS parser skips.
+///
+/// Returns `None` when `input` does not start with a `Database:` line.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::ris::ovid_header;
+///
+/// let input = "Database: Embase <1974 to 2024 March 01>\n\
+///              Search Strategy:\n\
+///              --------------------------------------------------------------\n\
+///              1     delirium/ (45678)\n\
+///              2     early mobilization/ (1234)\n\
+///              3     1 and 2 (89)\n\
+///              \n\
+///              ***************************\n\
+///              \n\
+///              <1. >\n\
+///              TY  - JOUR\n\
+///              TI  - Early mobilisation and delirium\n\
+///              ER  - \n";
+///
+/// let header = ovid_header(input).unwrap();
+/// assert_eq!(header.databases, vec!["Embase <1974 to 2024 March 01>"]);
+/// assert_eq!(header.search_strategy.len(), 3);
+/// assert_eq!(header.search_strategy[2].query, "1 and 2");
+/// assert_eq!(header.search_strategy[2].results, Some(89));
+/// ```
+#[must_use]
+pub fn ovid_header(input: &str) -> Option<OvidHeader> {
+    let header_lines = header_lines(input);
+    if header_lines == 0 {
+        return None;
+    }
+
+    let mut header = OvidHeader::default();
+    for line in input.lines().take(header_lines).map(trim) {
+        if let Some(databases) = line.strip_prefix("Database:") {
+            header.databases.extend(split_databases(databases));
+        } else if let Some(search_line) = parse_search_line(line) {
+            header.search_strategy.push(search_line);
+        }
+    }
+    Some(header)
+}
+
+/// The number of lines of the Ovid header at the start of `text`, or 0 when
+/// the first line that is not blank is not a `Database:` line.
+///
+/// The header ends at the first record number or `TY` line.
+pub(crate) fn header_lines(text: &str) -> usize {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .skip_while(|(_, line)| trim(line).is_empty());
+    if !lines
+        .next()
+        .is_some_and(|(_, line)| trim(line).starts_with("Database:"))
+    {
+        return 0;
+    }
+    lines
+        .find(|(_, line)| {
+            let line = trim(line);
+            is_record_number(line) || line.starts_with("TY  -")
+        })
+        .map_or_else(|| text.lines().count(), |(index, _)| index)
+}
+
+/// Whether `line` is an Ovid record number such as `<1. >` or `<12>`.
+pub(crate) fn is_record_number(line: &str) -> bool {
+    line.strip_prefix('<')
+        .and_then(|line| line.strip_suffix('>'))
+        .map(|number| number.trim_end().trim_end_matches('.'))
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Trims whitespace and a byte order mark from a line.
+fn trim(line: &str) -> &str {
+    line.trim_start_matches('\u{feff}').trim()
+}
+
+/// Splits the databases of a `Database:` line, each followed by its coverage
+/// in angle brackets.
+fn split_databases(databases: &str) -> Vec<String> {
+    let mut split = Vec::new();
+    let mut rest = databases.trim();
+    while let Some(end) = rest.find(">,") {
+        split.push(rest[..=end].trim().to_string());
+        rest = rest[end + 2..].trim_start();
+    }
+    if !rest.is_empty() {
+        split.push(rest.to_string());
+    }
+    split
+}
+
+/// Reads a numbered search line, with the result count in parentheses at the end.
+fn parse_search_line(line: &str) -> Option<SearchLine> {
+    let (number, query) = line.split_once(char::is_whitespace)?;
+    let number = number.parse().ok()?;
+    let query = query.trim();
+    let (query, results) = match query
+        .strip_suffix(')')
+        .and_then(|query| query.rsplit_once('('))
+        .and_then(|(query, count)| Some((query.trim_end(), count.parse().ok()?)))
+    {
+        Some((query, results)) => (query, Some(results)),
+        None => (query, None),
+    };
+    Some(SearchLine {
+        number,
+        query: query.to_string(),
+        results,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("<1. >", true)]
+    #[case("<12>", true)]
+    #[case("<3.>", true)]
+    #[case("<. >", false)]
+    #[case("<i>", false)]
+    #[case("1. ", false)]
+    fn test_is_record_number(#[case] line: &str, #[case] expected: bool) {
+        assert_eq!(is_record_number(line), expected);
+    }
+
+    #[test]
+    fn test_search_lines_and_databases() {
+        let line = parse_search_line("4     limit 3 to yr=\"2019 -Current\" (45)").unwrap();
+        assert_eq!(line.number, 4);
+        assert_eq!(line.query, "limit 3 to yr=\"2019 -Current\"");
+        assert_eq!(line.results, Some(45));
+
+        let line = parse_search_line("5     (delirium or confusion).ti,ab.").unwrap();
+        assert_eq!(line.query, "(delirium or confusion).ti,ab.");
+        assert_eq!(line.results, None);
+        assert_eq!(parse_search_line("Search Strategy:"), None);
+
+        assert_eq!(
+            split_databases(" Embase <1974 to 2024 March 01>, Ovid MEDLINE(R) ALL <1946 to 2024>"),
+            vec![
+                "Embase <1974 to 2024 March 01>",
+                "Ovid MEDLINE(R) ALL <1946 to 2024>"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_ends_at_first_record() {
+        assert_eq!(
+            header_lines("\nDatabase: Embase\n1  a (2)\n<1. >\nTY  - JOUR\n"),
+            3
+        );
+        assert_eq!(header_lines("Database: Embase\n1  a (2)\nTY  - JOUR\n"), 2);
+        assert_eq!(header_lines("TY  - JOUR\nER  - \n"), 0);
+    }
+}
@@ -2,11 +2,12 @@
 //!
 //! This module handles the low-level parsing of RIS formatted text.
 
+use crate::ris::ovid;
 use crate::ris::structure::RawRisData;
 use crate::ris::tags::RisTag;
-use crate::utils::parse_author_name;
+use crate::utils::{Coverage, parse_author_name};
 use crate::{
-    Author, CitationFormat,
+    Author, CitationFormat, Contributor, ContributorRole, Diagnostic, Diagnostics, NameOrder,
     error::{ParseError, ValueError},
 };
 
@@ -20,58 +21,82 @@ pub(crate) fn ris_parse<S: AsRef<str>>(ris_text: S) -> Result<Vec<RawRisData>, P
 
     let mut citations = Vec::new();
     let mut current_citation = RawRisData::new();
-    let mut line_number = 0;
+    let header_lines = ovid::header_lines(text);
+    let mut line_number = header_lines;
 
-    for line in text.lines() {
+    for line in text.lines().skip(header_lines) {
         line_number += 1;
-        let line = line.trim();
 
-        // Skip empty lines
-        if line.is_empty() {
+        // Ovid numbers records with lines such as "<1. >", which end the
+        // previous record like an `ER` tag
+        if ovid::is_record_number(line.trim()) {
+            if current_citation.has_content() {
+                citations.push(current_citation);
+                current_citation = RawRisData::new();
+            }
             continue;
         }
 
-        // Skip metadata lines
-        if is_metadata_line(line) {
-            continue;
-        }
+        // A physical line may hold several tag lines when newlines were lost
+        for line in split_glued_lines(trim_line(line)) {
+            // Skip empty lines
+            if line.is_empty() {
+                continue;
+            }
+
+            // Skip metadata lines
+            if is_metadata_line(line) {
+                continue;
+            }
 
-        match parse_ris_line(line, line_number) {
-            Ok((tag, content)) => {
-                match tag {
-                    RisTag::Type => {
-                        // Start of new citation
-                        if current_citation.has_content() {
-                            citations.push(current_citation);
-                            current_citation = RawRisData::new();
+            match parse_ris_line(line, line_number) {
+                Ok((tag, content)) => {
+                    match tag {
+                        RisTag::Type => {
+                            // Start of new citation
+                            if current_citation.has_content() {
+                                citations.push(current_citation);
+                                current_citation = RawRisData::new();
+                            }
+                            current_citation.add_data(tag, content);
                         }
-                        current_citation.add_data(tag, content);
-                    }
-                    RisTag::EndOfReference => {
-                        // End of current citation
-                        if current_citation.has_content() {
-                            citations.push(current_citation);
-                            current_citation = RawRisData::new();
+                        RisTag::EndOfReference => {
+                            // End of current citation
+                            if current_citation.has_content() {
+                                citations.push(current_citation);
+                                current_citation = RawRisData::new();
+                            }
+                        }
+                        tag if tag.is_author_tag() => {
+                            let role = tag.contributor_role().unwrap_or(ContributorRole::Author);
+                            if crate::utils::is_placeholder_author(&content) {
+                                if role == ContributorRole::Author {
+                                    current_citation.anonymous |= !content.trim().is_empty();
+                                }
+                            } else if role == ContributorRole::Author {
+                                current_citation.add_author(parse_author(&content));
+                            } else {
+                                let person = parse_author(&content);
+                                current_citation.add_contributor(Contributor::new(person, role));
+                            }
+                        }
+                        _ => {
+                            current_citation.add_data(tag, content);
                         }
-                    }
-                    tag if tag.is_author_tag() => {
-                        let author = parse_author(&content);
-                        current_citation.add_author(author);
-                    }
-                    _ => {
-                        current_citation.add_data(tag, content);
                     }
                 }
-            }
-            Err(_) => {
-                // Add invalid lines to ignored lines with context
-                current_citation.add_ignored_line(line_number, line.to_string());
+                Err(_) => {
+                    // Add invalid lines to ignored lines with context
+                    current_citation.add_ignored_line(line_number, line.to_string());
+                }
             }
         }
     }
 
-    // Add the last citation if it has content
+    // Add the last citation if it has content; without an `ER` tag the input
+    // ended mid-record
     if current_citation.has_content() {
+        current_citation.truncated = true;
         citations.push(current_citation);
     }
 
@@ -82,6 +107,108 @@ pub(crate) fn ris_parse<S: AsRef<str>>(ris_text: S) -> Result<Vec<RawRisData>, P
     Ok(citations)
 }
 
+/// Count the lines of a RIS formatted text that [`ris_parse`] reads, skips and
+/// starts records at.
+pub(crate) fn ris_coverage(text: &str) -> Coverage {
+    let mut coverage = Coverage::default();
+    let header_lines = ovid::header_lines(text);
+
+    for (index, line) in text.lines().enumerate().skip(header_lines) {
+        let line = trim_line(line);
+        if line.is_empty() {
+            continue;
+        }
+        coverage.lines += 1;
+        if ovid::is_record_number(line) {
+            continue;
+        }
+
+        let mut unparsed = false;
+        for line in split_glued_lines(line) {
+            if line.is_empty() || is_metadata_line(line) {
+                continue;
+            }
+            match parse_ris_line(line, index + 1) {
+                Ok((RisTag::Type, _)) => coverage.records += 1,
+                Ok(_) => {}
+                Err(_) => unparsed = true,
+            }
+        }
+        if unparsed {
+            coverage.unparsed.push(index + 1);
+        }
+    }
+
+    coverage
+}
+
+/// Find what [`ris_parse`] and the conversion of its records read leniently: a
+/// skipped Ovid header, then `PY` and `Y1` dates that cannot be read or whose
+/// month and day can be read in either order, in line order.
+pub(crate) fn ris_diagnostics(text: &str) -> Diagnostics {
+    let header_lines = ovid::header_lines(text);
+    let mut diagnostics = Diagnostics::new();
+    if header_lines > 0 {
+        diagnostics.push(Diagnostic::header_skipped(header_lines));
+    }
+
+    for (index, line) in text.lines().enumerate().skip(header_lines) {
+        for line in split_glued_lines(trim_line(line)) {
+            let Ok((tag @ (RisTag::PublicationYear | RisTag::DatePrimary), date)) =
+                parse_ris_line(line, index + 1)
+            else {
+                continue;
+            };
+            let date = date.trim();
+            if date.is_empty() {
+                continue;
+            }
+            if crate::utils::parse_ris_date_range(date).is_none() {
+                diagnostics.push(Diagnostic::date_unreadable(index + 1, tag.as_tag(), date));
+            } else if crate::utils::ris_date_is_ambiguous(date) {
+                diagnostics.push(Diagnostic::date_ambiguous(index + 1, tag.as_tag(), date));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Split a line at every embedded tag pattern such as the `TY  - ` in
+/// `ER  -TY  - JOUR`, returning the logical lines.
+///
+/// Only known tags count, and only when not preceded by a letter or digit, so
+/// ordinary text containing "  - " is left alone.
+fn split_glued_lines(line: &str) -> Vec<&str> {
+    let mut starts = vec![0];
+    starts.extend((1..line.len()).filter(|&i| is_embedded_tag_at(line, i)));
+
+    starts
+        .iter()
+        .zip(starts.iter().skip(1).chain([&line.len()]))
+        .map(|(&start, &end)| trim_line(&line[start..end]))
+        .collect()
+}
+
+/// Trim a line, keeping trailing tabs so that a tab-separated tag without
+/// content, such as the `ER\t` of Reference Manager exports, stays a tag line.
+fn trim_line(line: &str) -> &str {
+    line.trim_start()
+        .trim_end_matches(|c: char| c.is_whitespace() && c != '\t')
+}
+
+/// Whether a known tag pattern such as `TY  -` starts at byte `i` of `line`,
+/// at the start of the line or after a character other than a letter or digit.
+pub(crate) fn is_embedded_tag_at(line: &str, i: usize) -> bool {
+    let bytes = line.as_bytes();
+    bytes.len() >= i + 5
+        && bytes[i].is_ascii_uppercase()
+        && (bytes[i + 1].is_ascii_uppercase() || bytes[i + 1].is_ascii_digit())
+        && &bytes[i + 2..i + 5] == b"  -"
+        && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric())
+        && !matches!(RisTag::from_tag(&line[i..i + 2]), RisTag::Unknown(_))
+}
+
 /// Parse a single RIS line into a tag and content.
 fn parse_ris_line(line: &str, line_number: usize) -> Result<(RisTag, String), ParseError> {
     // Validate minimum line length
@@ -96,18 +223,26 @@ fn parse_ris_line(line: &str, line_number: usize) -> Result<(RisTag, String), Pa
         ));
     }
 
-    let tag_str = &line[..2];
-
-    // Validate tag format
-    if !tag_str.chars().all(|c| c.is_ascii_alphanumeric()) {
+    // Validate tag format; checking bytes keeps the slicing below on character
+    // boundaries when the line starts with a multi-byte character
+    let Some(tag_str) = line
+        .get(..2)
+        .filter(|tag| tag.bytes().all(|b| b.is_ascii_alphanumeric()))
+    else {
+        let tag: String = line.chars().take(2).collect();
         return Err(ParseError::at_line(
             line_number,
             CitationFormat::Ris,
-            ValueError::Syntax(format!("Invalid RIS tag format: '{}'", tag_str)),
+            ValueError::Syntax(format!("Invalid RIS tag format: '{}'", tag)),
         ));
-    }
+    };
 
-    let tag = RisTag::from_tag(tag_str);
+    // Reference Manager writes tags in lowercase before a tab, e.g. "ty\tJOUR"
+    let tag = if line[2..].starts_with('\t') {
+        RisTag::from_tag(&tag_str.to_ascii_uppercase())
+    } else {
+        RisTag::from_tag(tag_str)
+    };
 
     // Extract content
     let content = extract_ris_content(line, line_number)?;
@@ -116,33 +251,42 @@ fn parse_ris_line(line: &str, line_number: usize) -> Result<(RisTag, String), Pa
 }
 
 /// Extract content from a RIS line, handling various format patterns.
+///
+/// The line starts with a two-character ASCII tag; separators are compared as
+/// bytes, so a multi-byte character after the tag is never sliced.
 fn extract_ris_content(line: &str, line_number: usize) -> Result<String, ParseError> {
+    let separator = |len: usize| line.as_bytes().get(2..2 + len);
+
     // Standard format: "TY  - JOUR"
-    if line.len() >= 6 && &line[2..6] == "  - " {
+    if separator(4) == Some(b"  - ") {
         return Ok(line[6..].trim().to_string());
     }
 
     // Format without space after dash: "ER  -"
-    if line.len() >= 5 && &line[2..5] == "  -" {
+    if separator(3) == Some(b"  -") {
         return Ok(line[5..].trim().to_string());
     }
 
     // Format without spaces before dash: "TY- JOUR"
-    if line.len() >= 4 && &line[2..4] == "- " {
+    if separator(2) == Some(b"- ") {
         return Ok(line[4..].trim().to_string());
     }
 
     // Minimal format: "TY-JOUR"
-    if line.len() >= 3 && &line[2..3] == "-" {
+    if separator(1) == Some(b"-") {
         return Ok(line[3..].trim().to_string());
     }
 
-    // Require proper separator (space or dash) after tag
-    if line.len() > 2 {
-        let third_char = line.chars().nth(2).unwrap();
-        if third_char == ' ' || third_char == '-' {
-            return Ok(line[2..].trim().to_string());
-        }
+    // Tab-separated formats from Reference Manager: "TY\tJOUR", "TY\t- JOUR"
+    if separator(1) == Some(b"\t") {
+        let content = line[3..].trim();
+        let content = content.strip_prefix('-').unwrap_or(content);
+        return Ok(content.trim().to_string());
+    }
+
+    // Require proper separator (space, dash or tab) after tag
+    if separator(1) == Some(b" ") {
+        return Ok(line[2..].trim().to_string());
     }
 
     // If we reach here, the line doesn't have a proper separator
@@ -150,7 +294,7 @@ fn extract_ris_content(line: &str, line_number: usize) -> Result<String, ParseEr
         line_number,
         CitationFormat::Ris,
         ValueError::Syntax(format!(
-            "RIS line missing proper separator (space or dash) after tag: '{}'",
+            "RIS line missing proper separator (space, dash or tab) after tag: '{}'",
             line
         )),
     ))
@@ -158,13 +302,13 @@ fn extract_ris_content(line: &str, line_number: usize) -> Result<String, ParseEr
 
 /// Parse an author string into an Author struct.
 fn parse_author(author_str: &str) -> Author {
-    let (family, given) = parse_author_name(author_str);
+    let (family, given, suffix) = parse_author_name(author_str, NameOrder::FamilyFirst);
     let (given_opt, middle_opt) = if given.is_empty() {
         (None, None)
     } else {
         crate::utils::split_given_and_middle(&given)
     };
-    Author { name: family, given_name: given_opt, middle_name: middle_opt, affiliations: Vec::new() }
+    Author { name: family, given_name: given_opt, middle_name: middle_opt, suffix, affiliations: Vec::new() }
 }
 
 /// Check if a line is RIS metadata that should be ignored.
@@ -180,6 +324,20 @@ mod tests {
     use super::*;
     use rstest::*;
 
+    #[rstest]
+    #[case("ER  -TY  - JOUR", vec!["ER  -", "TY  - JOUR"])]
+    #[case("ER  - TY  - JOUR", vec!["ER  - ", "TY  - JOUR"])]
+    #[case("ER  - end of record", vec!["ER  - end of record"])]
+    #[case("TI  - A trial.AU  - Smith, J", vec!["TI  - A trial.", "AU  - Smith, J"])]
+    #[case("AB  - Effects in the UK  - and abroad", vec!["AB  - Effects in the UK  - and abroad"])]
+    #[case("AB  - Cases ZZ  - unknown tag", vec!["AB  - Cases ZZ  - unknown tag"])]
+    #[case("TY  - JOUR", vec!["TY  - JOUR"])]
+    #[case("", vec![""])]
+    fn test_split_glued_lines(#[case] line: &str, #[case] expected: Vec<&str>) {
+        let expected: Vec<&str> = expected.iter().map(|s| s.trim()).collect();
+        assert_eq!(split_glued_lines(line), expected);
+    }
+
     #[rstest]
     #[case("TY  - JOUR", RisTag::Type, "JOUR")]
     #[case("TI  - Test Title", RisTag::Title, "Test Title")]
@@ -188,6 +346,14 @@ mod tests {
     #[case("DO  - 10.1000/test", RisTag::Doi, "10.1000/test")]
     #[case("TY Content", RisTag::Type, "Content")]
     #[case("TY-Content", RisTag::Type, "Content")]
+    #[case("TY\tJOUR", RisTag::Type, "JOUR")]
+    #[case("TY\t- JOUR", RisTag::Type, "JOUR")]
+    #[case("TY\t-JOUR", RisTag::Type, "JOUR")]
+    #[case("ti\tTest Title", RisTag::Title, "Test Title")]
+    #[case("ER\t", RisTag::EndOfReference, "")]
+    #[case("er\t-", RisTag::EndOfReference, "")]
+    #[case("TI  - Café", RisTag::Title, "Café")]
+    #[case("TI é", RisTag::Title, "é")]
     fn test_parse_ris_line_valid(
         #[case] line: &str,
         #[case] expected_tag: RisTag,
@@ -204,6 +370,14 @@ mod tests {
     #[case("!!  - Invalid tag")]
     #[case("TYNoSeparator")]
     #[case("TYBAD")]
+    #[case("TY")]
+    #[case("TY:\tJOUR")]
+    // Multi-byte characters where the tag or separator would be sliced
+    #[case("é")]
+    #[case("éTY  - JOUR")]
+    #[case("Aé - Kowalski")]
+    #[case("TY😀- JOUR")]
+    #[case("TYé")]
     fn test_parse_ris_line_invalid(#[case] line: &str) {
         let result = parse_ris_line(line, 1);
         assert!(result.is_err());
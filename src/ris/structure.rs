@@ -10,7 +10,7 @@
 //! - **Two-pass**: DOI extraction checks dedicated fields first, then URLs
 //! - **Validation**: Date parsing includes error logging for invalid formats
 
-use crate::Author;
+use crate::{Author, Contributor, LinkKind};
 use crate::ris::tags::RisTag;
 use std::collections::HashMap;
 
@@ -21,8 +21,16 @@ pub(crate) struct RawRisData {
     pub(crate) data: HashMap<RisTag, Vec<String>>,
     /// Authors of the cited work.
     pub(crate) authors: Vec<Author>,
+    /// Editors and translators of the cited work.
+    pub(crate) contributors: Vec<Contributor>,
     /// Invalid lines found in the RIS file data with line number context for error reporting.
     pub(crate) ignored_lines: Vec<(usize, String)>,
+    /// Whether the input ended inside this record, before its `ER` tag.
+    pub(crate) truncated: bool,
+    /// Whether an author tag held a placeholder such as "Anonymous".
+    pub(crate) anonymous: bool,
+    /// Whether values are kept as written, see [`crate::RisParser::set_archival`].
+    pub(crate) archival: bool,
 }
 
 impl RawRisData {
@@ -31,7 +39,11 @@ impl RawRisData {
         Self {
             data: HashMap::new(),
             authors: Vec::new(),
+            contributors: Vec::new(),
             ignored_lines: Vec::new(),
+            truncated: false,
+            anonymous: false,
+            archival: false,
         }
     }
 
@@ -45,6 +57,11 @@ impl RawRisData {
         self.authors.push(author);
     }
 
+    /// Add a contributor other than an author.
+    pub(crate) fn add_contributor(&mut self, contributor: Contributor) {
+        self.contributors.push(contributor);
+    }
+
     /// Add an ignored line with context.
     pub(crate) fn add_ignored_line(&mut self, line_number: usize, line: String) {
         self.ignored_lines.push((line_number, line));
@@ -60,9 +77,44 @@ impl RawRisData {
         self.data.remove(tag)
     }
 
+    /// Move all values of the `from` tag onto the `to` tag.
+    ///
+    /// Moved values are appended after any values already present for `to`.
+    pub(crate) fn remap_tag(&mut self, from: &RisTag, to: RisTag) {
+        if let Some(values) = self.data.remove(from) {
+            self.data.entry(to).or_default().extend(values);
+        }
+    }
+
+    /// Remove all values for a tag, returning the first.
+    ///
+    /// Any further values are kept as escaped extra fields.
+    pub(crate) fn take_first(&mut self, tag: &RisTag) -> Option<String> {
+        let mut values = self.data.remove(tag)?.into_iter();
+        let first = values.next();
+        self.retain_extra(tag, values);
+        first
+    }
+
+    /// Keep values of a standard tag that were not used for the citation field.
+    ///
+    /// They are stored under the tag with [`crate::EXTRA_FIELD_PREFIX`] prepended
+    /// (e.g. "x-TI"), so they end up in `extra_fields` without reusing the
+    /// standard key. Empty values are dropped.
+    pub(crate) fn retain_extra(&mut self, tag: &RisTag, values: impl IntoIterator<Item = String>) {
+        let values: Vec<String> = values
+            .into_iter()
+            .filter(|value| !value.trim().is_empty())
+            .collect();
+        if !values.is_empty() {
+            let key = RisTag::Unknown(format!("{}{}", crate::EXTRA_FIELD_PREFIX, tag.as_tag()));
+            self.data.entry(key).or_default().extend(values);
+        }
+    }
+
     /// Check if the data contains any content (not just metadata).
     pub(crate) fn has_content(&self) -> bool {
-        !self.data.is_empty() || !self.authors.is_empty()
+        !self.data.is_empty() || !self.authors.is_empty() || !self.contributors.is_empty()
     }
 
     /// Generic helper method to select the best value based on tag priority.
@@ -77,15 +129,13 @@ impl RawRisData {
         let mut best_priority = u8::MAX;
 
         for (tag, values) in &self.data {
-            if let Some(priority) = priority_fn(tag) {
-                if priority < best_priority && !values.is_empty() {
-                    if let Some(first_value) = values.first() {
-                        if !first_value.trim().is_empty() {
-                            best_priority = priority;
-                            best_value = Some(first_value.clone());
-                        }
-                    }
-                }
+            if let Some(priority) = priority_fn(tag)
+                && priority < best_priority
+                && let Some(first_value) = values.first()
+                && !first_value.trim().is_empty()
+            {
+                best_priority = priority;
+                best_value = Some(first_value.clone());
             }
         }
 
@@ -108,25 +158,33 @@ impl TryFrom<RawRisData> for crate::Citation {
 
     fn try_from(mut raw: RawRisData) -> Result<Self, Self::Error> {
         let citation_type = raw.remove(&RisTag::Type).unwrap_or_default();
-        let title = Self::extract_title(&mut raw)?;
+        let title = Self::extract_title(&mut raw, &citation_type)?;
+        let conference = Self::extract_conference(&mut raw, &citation_type);
+        let retraction_status = crate::RetractionStatus::from_title(&title);
         let (journal, journal_abbr) = Self::extract_journal_info(&mut raw);
-        let date = Self::extract_date(&mut raw);
+        let (date, date_end) = Self::extract_date(&mut raw);
         let (volume, issue, pages) = Self::extract_publication_details(&mut raw);
-        let (doi, urls) = Self::extract_doi_and_urls(&mut raw);
+        let (doi, urls, link_kinds) = Self::extract_doi_and_urls(&mut raw);
         let (pmid, pmc_id) = Self::extract_identifiers(&mut raw);
         let abstract_text = Self::extract_abstract(&mut raw);
+        let notes = Self::extract_notes(&mut raw);
         let keywords = raw.remove(&RisTag::Keywords).unwrap_or_default();
         let issn = raw.remove(&RisTag::SerialNumber).unwrap_or_default();
         let (language, publisher) = Self::extract_metadata(&mut raw);
         let extra_fields = Self::extract_extra_fields(&mut raw);
 
-        Ok(crate::Citation {
+        let mut citation = crate::Citation {
             citation_type,
             title,
             authors: raw.authors,
+            author_count: None,
+            contributors: raw.contributors,
             journal,
             journal_abbr,
-            date: date.clone(),
+            date,
+            date_end,
+            electronic_date: None,
+            record_dates: HashMap::new(),
             volume,
             issue,
             pages,
@@ -137,42 +195,89 @@ impl TryFrom<RawRisData> for crate::Citation {
             abstract_text,
             keywords,
             urls,
+            link_kinds,
             language,
             mesh_terms: Vec::new(), // RIS doesn't typically have MeSH terms
             publisher,
+            notes,
+            conference,
+            times_cited: None,
+            retraction_status,
+            ingested_at: None,
+            parsed_from: None,
             extra_fields,
-        })
+        };
+        crate::utils::mark_anonymous(&mut citation, raw.anonymous);
+        Ok(citation)
     }
 }
 
 impl crate::Citation {
     /// Extract title from RIS data, trying primary title first, then alternative.
-    fn extract_title(raw: &mut RawRisData) -> Result<String, crate::error::ParseError> {
-        let title = raw
-            .get_first(&RisTag::Title)
-            .filter(|s| !s.trim().is_empty())
-            .or_else(|| {
-                raw.get_first(&RisTag::TitleAlternative)
-                    .filter(|s| !s.trim().is_empty())
-            })
-            .cloned()
-            .ok_or_else(|| {
-                crate::error::ParseError::without_position(
-                    crate::CitationFormat::Ris,
-                    crate::error::ValueError::MissingValue {
-                        field: crate::error::fields::TITLE,
-                        key: "TI",
-                    },
-                )
-            })?;
-
-        // Remove title data after extraction
-        raw.remove(&RisTag::Title);
-        raw.remove(&RisTag::TitleAlternative);
+    ///
+    /// Title values other than the one used are kept as escaped extra fields.
+    fn extract_title(
+        raw: &mut RawRisData,
+        citation_type: &[String],
+    ) -> Result<String, crate::error::ParseError> {
+        let mut titles = raw.remove(&RisTag::Title).unwrap_or_default();
+        let mut alternatives = raw.remove(&RisTag::TitleAlternative).unwrap_or_default();
+
+        let is_present = |values: &[String]| values.first().is_some_and(|s| !s.trim().is_empty());
+        let title = if is_present(&titles) {
+            titles.remove(0)
+        } else if is_present(&alternatives) {
+            alternatives.remove(0)
+        } else if is_whole_book(citation_type)
+            && let Some(title) = Self::take_book_title(raw)
+        {
+            title
+        } else {
+            return Err(crate::error::ParseError::without_position(
+                crate::CitationFormat::Ris,
+                crate::error::ValueError::MissingValue {
+                    field: crate::error::fields::TITLE,
+                    key: "TI",
+                },
+            ));
+        };
+
+        raw.retain_extra(&RisTag::Title, titles);
+        raw.retain_extra(&RisTag::TitleAlternative, alternatives);
 
         Ok(title)
     }
 
+    /// Take the title of a whole book without `TI` or `T1`: the `BT` book title,
+    /// or the `T2` secondary title when there is no `BT`.
+    fn take_book_title(raw: &mut RawRisData) -> Option<String> {
+        [RisTag::BookTitle, RisTag::SecondaryTitle]
+            .iter()
+            .find(|tag| raw.get_first(tag).is_some_and(|s| !s.trim().is_empty()))
+            .and_then(|tag| raw.take_first(tag))
+    }
+
+    /// Extract the conference name, location and date of a conference record.
+    ///
+    /// The name is the `T2` secondary title, which also serves as the journal
+    /// name. `CY` and `Y2` are taken as the location and date; in other records
+    /// `CY` stays in `extra_fields` and `Y2`, the access date, is dropped.
+    fn extract_conference(
+        raw: &mut RawRisData,
+        citation_type: &[String],
+    ) -> Option<crate::ConferenceInfo> {
+        if !crate::utils::is_conference_type(citation_type) {
+            return None;
+        }
+
+        let conference = crate::ConferenceInfo {
+            name: raw.get_first(&RisTag::SecondaryTitle).cloned(),
+            location: raw.take_first(&RisTag::PlacePublished),
+            date: raw.take_first(&RisTag::DateAccess),
+        };
+        (conference != crate::ConferenceInfo::default()).then_some(conference)
+    }
+
     /// Extract journal information using priority-based selection.
     fn extract_journal_info(raw: &mut RawRisData) -> (Option<String>, Option<String>) {
         let journal = raw.get_best_journal();
@@ -184,70 +289,85 @@ impl crate::Citation {
         raw.remove(&RisTag::JournalAbbreviation);
         raw.remove(&RisTag::JournalAbbreviationAlternative);
         raw.remove(&RisTag::SecondaryTitle);
+        raw.remove(&RisTag::BookTitle);
 
         (journal, journal_abbr)
     }
 
-    /// Extract date from RIS data with validation.
-    fn extract_date(raw: &mut RawRisData) -> Option<crate::Date> {
-        // Parse date from available date fields with validation
-        let date = raw
-            .get_first(&RisTag::PublicationYear)
-            .or_else(|| raw.get_first(&RisTag::DatePrimary))
-            .and_then(|date_str| {
-                crate::utils::parse_ris_date(date_str)
-                // Note: Invalid dates are silently ignored to avoid breaking parsing
-                // TODO: Collect warnings
+    /// Extract the date, and the end of the date range if any, from RIS data with validation.
+    ///
+    /// `Y1` is used when `PY` is missing or cannot be read. When neither can,
+    /// both stay in `extra_fields` as written. In archival mode both are also kept
+    /// when the date is read, under [`crate::RAW_FIELD_PREFIX`], e.g. "raw:PY".
+    fn extract_date(raw: &mut RawRisData) -> (Option<crate::Date>, Option<crate::Date>) {
+        let date = [RisTag::PublicationYear, RisTag::DatePrimary]
+            .iter()
+            .find_map(|tag| {
+                raw.get_first(tag)
+                    .and_then(|date_str| crate::utils::parse_ris_date_range(date_str))
             });
 
-        raw.remove(&RisTag::PublicationYear);
-        raw.remove(&RisTag::DatePrimary);
+        if date.is_some() {
+            for tag in [RisTag::PublicationYear, RisTag::DatePrimary] {
+                if let Some(values) = raw.remove(&tag)
+                    && raw.archival
+                {
+                    let key = format!("{}{}", crate::RAW_FIELD_PREFIX, tag.as_tag());
+                    raw.data.insert(RisTag::Unknown(key), values);
+                }
+            }
+        }
         raw.remove(&RisTag::DateAccess);
 
-        date
+        date.map_or((None, None), |(start, end)| (Some(start), end))
     }
 
     /// Extract publication details: volume, issue, and formatted pages.
+    ///
+    /// In archival mode the pages are joined as written, without completing a
+    /// shortened end page.
     fn extract_publication_details(
         raw: &mut RawRisData,
     ) -> (Option<String>, Option<String>, Option<String>) {
-        let volume = raw
-            .remove(&RisTag::Volume)
-            .and_then(|v| v.into_iter().next());
-        let issue = raw
-            .remove(&RisTag::Issue)
-            .and_then(|v| v.into_iter().next());
+        let volume = raw.take_first(&RisTag::Volume);
+        let issue = raw.take_first(&RisTag::Issue);
 
         // Handle pages
-        let start_page = raw
-            .remove(&RisTag::StartPage)
-            .and_then(|v| v.into_iter().next());
-        let end_page = raw
-            .remove(&RisTag::EndPage)
-            .and_then(|v| v.into_iter().next());
+        let start_page = raw.take_first(&RisTag::StartPage);
+        let end_page = raw.take_first(&RisTag::EndPage);
         let pages = match (start_page, end_page) {
-            (Some(start), Some(end)) => Some(crate::utils::format_page_numbers(&format!(
-                "{}-{}",
-                start, end
-            ))),
-            (Some(start), None) => Some(crate::utils::format_page_numbers(&start)),
+            (Some(start), Some(end)) => Some(format!("{}-{}", start, end)),
+            (start, None) => start,
             (None, Some(end)) => Some(end),
-            (None, None) => None,
+        };
+        let pages = if raw.archival {
+            pages
+        } else {
+            pages.map(|pages| crate::utils::format_page_numbers(&pages))
         };
 
         (volume, issue, pages)
     }
 
     /// Extract DOI and URLs with two-pass DOI extraction strategy.
-    fn extract_doi_and_urls(raw: &mut RawRisData) -> (Option<String>, Vec<String>) {
+    ///
+    /// URLs from `L1` are marked as PDFs and those from `L2` as full text. A DOI
+    /// taken from a URL must pass [`crate::doi::DoiValidation::Strict`]. In
+    /// archival mode the DOI is only taken from `DO`, as written.
+    fn extract_doi_and_urls(
+        raw: &mut RawRisData,
+    ) -> (Option<String>, Vec<String>, HashMap<String, LinkKind>) {
         // First pass: Extract DOI from dedicated DOI field
-        let mut doi = raw
-            .remove(&RisTag::Doi)
-            .and_then(|v| v.into_iter().next())
-            .and_then(|doi_str| crate::utils::format_doi(&doi_str));
+        let doi = raw.take_first(&RisTag::Doi);
+        let mut doi = if raw.archival {
+            doi
+        } else {
+            doi.and_then(|doi_str| crate::utils::format_doi(&doi_str))
+        };
 
         // Collect URLs from various link fields and extract DOI if not already found
         let mut urls = Vec::new();
+        let mut link_kinds = HashMap::new();
         for tag in [
             RisTag::LinkPdf,
             RisTag::LinkFullText,
@@ -258,32 +378,56 @@ impl crate::Citation {
         ] {
             if let Some(mut tag_urls) = raw.remove(&tag) {
                 // Second pass: Extract DOI from URL fields if not already found
-                if doi.is_none() {
+                if doi.is_none() && !raw.archival {
                     for url in &tag_urls {
-                        if url.contains("doi.org") {
-                            if let Some(extracted_doi) = crate::utils::format_doi(url) {
-                                doi = Some(extracted_doi);
-                                break;
-                            }
+                        if url.contains("doi.org")
+                            && let Some(extracted_doi) =
+                                crate::doi::extract(url, crate::doi::DoiValidation::Strict)
+                        {
+                            doi = Some(extracted_doi);
+                            break;
                         }
                     }
                 }
+                let kind = match tag {
+                    RisTag::LinkPdf => Some(LinkKind::Pdf),
+                    RisTag::LinkFullText => Some(LinkKind::FullText),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    for url in &tag_urls {
+                        link_kinds.entry(url.clone()).or_insert(kind);
+                    }
+                }
                 urls.append(&mut tag_urls);
             }
         }
 
-        (doi, urls)
+        (doi, urls, link_kinds)
     }
 
     /// Extract PMID and PMC ID identifiers.
+    ///
+    /// `ID` is a generic reference identifier: EndNote writes its record number
+    /// there and Zotero its item key. It is read as the PMID only when it looks
+    /// like one; otherwise it is kept in `extra_fields` under "AN" when it looks
+    /// like a database accession number, or under "reference_id".
     fn extract_identifiers(raw: &mut RawRisData) -> (Option<String>, Option<String>) {
-        let pmid = raw
-            .remove(&RisTag::ReferenceId)
-            .and_then(|v| v.into_iter().next());
+        let pmid = raw.take_first(&RisTag::ReferenceId).and_then(|id| {
+            if is_pmid(&id) {
+                return Some(id);
+            }
+            let key = if is_accession_number(&id) {
+                crate::ACCESSION_FIELD
+            } else {
+                "reference_id"
+            };
+            raw.data.entry(RisTag::Unknown(key.to_string())).or_default().push(id);
+            None
+        });
 
         let pmc_id = raw
-            .remove(&RisTag::PmcId)
-            .and_then(|v| v.into_iter().next())
+            .take_first(&RisTag::PmcId)
             .filter(|s| s.contains("PMC"));
 
         (pmid, pmc_id)
@@ -302,14 +446,21 @@ impl crate::Citation {
         abstract_text
     }
 
+    /// Extract notes, joining the values of `N1` tags and then `RN` research
+    /// notes with a blank line.
+    fn extract_notes(raw: &mut RawRisData) -> Option<String> {
+        let notes: Vec<String> = [RisTag::Notes, RisTag::ResearchNotes]
+            .iter()
+            .flat_map(|tag| raw.remove(tag).unwrap_or_default())
+            .filter(|note| !note.trim().is_empty())
+            .collect();
+        (!notes.is_empty()).then(|| notes.join("\n\n"))
+    }
+
     /// Extract language and publisher metadata.
     fn extract_metadata(raw: &mut RawRisData) -> (Option<String>, Option<String>) {
-        let language = raw
-            .remove(&RisTag::Language)
-            .and_then(|v| v.into_iter().next());
-        let publisher = raw
-            .remove(&RisTag::Publisher)
-            .and_then(|v| v.into_iter().next());
+        let language = raw.take_first(&RisTag::Language);
+        let publisher = raw.take_first(&RisTag::Publisher);
 
         (language, publisher)
     }
@@ -320,13 +471,42 @@ impl crate::Citation {
         raw.remove(&RisTag::EndOfReference);
 
         // Collect remaining fields as extra_fields
-        raw.data
+        let mut extra_fields: HashMap<String, Vec<String>> = raw
+            .data
             .drain()
             .map(|(tag, values)| (tag.as_tag().to_string(), values))
-            .collect()
+            .collect();
+        if raw.truncated {
+            extra_fields.insert(crate::TRUNCATED_FIELD.to_string(), vec!["true".to_string()]);
+        }
+        extra_fields
     }
 }
 
+/// Checks whether a reference id looks like a PMID: 5 to 8 digits. Shorter
+/// numbers are far more often EndNote record numbers than the earliest PMIDs.
+fn is_pmid(id: &str) -> bool {
+    (5..=8).contains(&id.len()) && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Checks whether a record type is a whole book, whose title may be given only
+/// as the book title.
+fn is_whole_book(citation_type: &[String]) -> bool {
+    citation_type
+        .first()
+        .is_some_and(|t| matches!(t.as_str(), "BOOK" | "EDBOOK" | "EBOOK"))
+}
+
+/// Checks whether a reference id looks like a database accession number: a Web
+/// of Science "WOS:" number, a Scopus "2-s2.0-" EID, or an Embase number of nine
+/// or more digits with an optional "L" prefix.
+fn is_accession_number(id: &str) -> bool {
+    let embase = id.strip_prefix('L').unwrap_or(id);
+    id.starts_with("WOS:")
+        || id.starts_with("2-s2.0-")
+        || (embase.len() >= 9 && embase.bytes().all(|b| b.is_ascii_digit()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -354,6 +534,24 @@ mod tests {
         assert!(raw.has_content());
     }
 
+    #[test]
+    fn test_remap_tag() {
+        let mut raw = RawRisData::new();
+        raw.add_data(RisTag::Doi, "10.1000/first".to_string());
+        raw.add_data(RisTag::Unknown("M3".to_string()), "10.1000/second".to_string());
+
+        raw.remap_tag(&RisTag::Unknown("M3".to_string()), RisTag::Doi);
+        assert_eq!(
+            raw.data[&RisTag::Doi],
+            vec!["10.1000/first", "10.1000/second"]
+        );
+        assert!(!raw.data.contains_key(&RisTag::Unknown("M3".to_string())));
+
+        // Remapping a missing tag is a no-op
+        raw.remap_tag(&RisTag::Unknown("M1".to_string()), RisTag::Abstract);
+        assert!(!raw.data.contains_key(&RisTag::Abstract));
+    }
+
     #[test]
     fn test_journal_priority() {
         let mut raw = RawRisData::new();
@@ -373,6 +571,7 @@ mod tests {
             name: "Smith".to_string(),
             given_name: Some("John".to_string()),
             middle_name: None,
+            suffix: None,
             affiliations: Vec::new(),
         });
 
@@ -451,6 +650,44 @@ mod tests {
         assert_eq!(citation3.title, "Fallback Title");
     }
 
+    #[test]
+    fn test_book_title_extraction() {
+        // A whole book may carry its title only as BT
+        let mut raw = RawRisData::new();
+        raw.add_data(RisTag::Type, "BOOK".to_string());
+        raw.add_data(RisTag::BookTitle, "Clinical Epidemiology".to_string());
+        raw.add_data(RisTag::Publisher, "Wolters Kluwer".to_string());
+
+        let citation: crate::Citation = raw.try_into().unwrap();
+        assert_eq!(citation.title, "Clinical Epidemiology");
+        assert_eq!(citation.journal, None);
+
+        // ... or as T2 when there is no BT
+        let mut raw = RawRisData::new();
+        raw.add_data(RisTag::Type, "EDBOOK".to_string());
+        raw.add_data(RisTag::SecondaryTitle, "Handbook of Meta-analysis".to_string());
+
+        let citation: crate::Citation = raw.try_into().unwrap();
+        assert_eq!(citation.title, "Handbook of Meta-analysis");
+
+        // A chapter keeps TI as its title and BT as the containing book
+        let mut raw = RawRisData::new();
+        raw.add_data(RisTag::Type, "CHAP".to_string());
+        raw.add_data(RisTag::Title, "Assessing risk of bias".to_string());
+        raw.add_data(RisTag::BookTitle, "Cochrane Handbook".to_string());
+
+        let citation: crate::Citation = raw.try_into().unwrap();
+        assert_eq!(citation.title, "Assessing risk of bias");
+        assert_eq!(citation.journal.as_deref(), Some("Cochrane Handbook"));
+        assert!(!citation.extra_fields.contains_key("BT"));
+
+        // Only whole books fall back to the book title
+        let mut raw = RawRisData::new();
+        raw.add_data(RisTag::Type, "CHAP".to_string());
+        raw.add_data(RisTag::BookTitle, "Cochrane Handbook".to_string());
+        assert!(crate::Citation::try_from(raw).is_err());
+    }
+
     #[test]
     fn test_complex_doi_extraction_scenarios() {
         let mut raw = RawRisData::new();
@@ -3,6 +3,8 @@
 //! This module defines all the standard RIS tags used in bibliographic citations.
 //! See: http://en.wikipedia.org/wiki/RIS_(file_format)
 
+use crate::ContributorRole;
+
 /// RIS format tags.
 ///
 /// RIS (Research Information Systems) is a standardized tag format developed by
@@ -22,12 +24,14 @@ pub enum RisTag {
     Author,
     /// A1 - Primary author
     AuthorPrimary,
-    /// A2 - Secondary author (Editor, if any)
+    /// A2 - Secondary author (editor)
     AuthorSecondary,
-    /// A3 - Tertiary author
+    /// A3 - Tertiary author (series editor)
     AuthorTertiary,
-    /// A4 - Subsidiary author
+    /// A4 - Subsidiary author (translator)
     AuthorSubsidiary,
+    /// ED - Editor
+    Editor,
     /// JF - Journal/Periodical name: full format
     JournalFull,
     /// JO - Journal/Periodical name: full format (alternative)
@@ -38,11 +42,13 @@ pub enum RisTag {
     JournalAbbreviationAlternative,
     /// T2 - Secondary title (journal title alternative)
     SecondaryTitle,
+    /// BT - Book title; the title itself for whole books
+    BookTitle,
     /// PY - Publication year
     PublicationYear,
     /// Y1 - Primary date
     DatePrimary,
-    /// Y2 - Access date
+    /// Y2 - Access date; the conference date for conference records
     DateAccess,
     /// VL - Volume number
     Volume,
@@ -60,6 +66,10 @@ pub enum RisTag {
     Abstract,
     /// N2 - Abstract (alternative)
     AbstractAlternative,
+    /// N1 - Notes
+    Notes,
+    /// RN - Research notes, written by EndNote
+    ResearchNotes,
     /// KW - Keywords
     Keywords,
     /// SN - ISSN/ISBN
@@ -80,6 +90,8 @@ pub enum RisTag {
     Language,
     /// PB - Publisher
     Publisher,
+    /// CY - Place published; the conference location for conference records
+    PlacePublished,
     /// C2 - PMCID
     PmcId,
     /// ER - End of reference
@@ -100,11 +112,13 @@ impl RisTag {
             "A2" => RisTag::AuthorSecondary,
             "A3" => RisTag::AuthorTertiary,
             "A4" => RisTag::AuthorSubsidiary,
+            "ED" => RisTag::Editor,
             "JF" => RisTag::JournalFull,
             "JO" => RisTag::JournalFullAlternative,
             "JA" => RisTag::JournalAbbreviation,
             "J2" => RisTag::JournalAbbreviationAlternative,
             "T2" => RisTag::SecondaryTitle,
+            "BT" => RisTag::BookTitle,
             "PY" => RisTag::PublicationYear,
             "Y1" => RisTag::DatePrimary,
             "Y2" => RisTag::DateAccess,
@@ -116,6 +130,8 @@ impl RisTag {
             "ID" => RisTag::ReferenceId,
             "AB" => RisTag::Abstract,
             "N2" => RisTag::AbstractAlternative,
+            "N1" => RisTag::Notes,
+            "RN" => RisTag::ResearchNotes,
             "KW" => RisTag::Keywords,
             "SN" => RisTag::SerialNumber,
             "L1" => RisTag::LinkPdf,
@@ -126,6 +142,7 @@ impl RisTag {
             "LK" => RisTag::Link,
             "LA" => RisTag::Language,
             "PB" => RisTag::Publisher,
+            "CY" => RisTag::PlacePublished,
             "C2" => RisTag::PmcId,
             "ER" => RisTag::EndOfReference,
             _ => RisTag::Unknown(tag.to_string()),
@@ -143,11 +160,13 @@ impl RisTag {
             RisTag::AuthorSecondary => "A2",
             RisTag::AuthorTertiary => "A3",
             RisTag::AuthorSubsidiary => "A4",
+            RisTag::Editor => "ED",
             RisTag::JournalFull => "JF",
             RisTag::JournalFullAlternative => "JO",
             RisTag::JournalAbbreviation => "JA",
             RisTag::JournalAbbreviationAlternative => "J2",
             RisTag::SecondaryTitle => "T2",
+            RisTag::BookTitle => "BT",
             RisTag::PublicationYear => "PY",
             RisTag::DatePrimary => "Y1",
             RisTag::DateAccess => "Y2",
@@ -159,6 +178,8 @@ impl RisTag {
             RisTag::ReferenceId => "ID",
             RisTag::Abstract => "AB",
             RisTag::AbstractAlternative => "N2",
+            RisTag::Notes => "N1",
+            RisTag::ResearchNotes => "RN",
             RisTag::Keywords => "KW",
             RisTag::SerialNumber => "SN",
             RisTag::LinkPdf => "L1",
@@ -169,6 +190,7 @@ impl RisTag {
             RisTag::Link => "LK",
             RisTag::Language => "LA",
             RisTag::Publisher => "PB",
+            RisTag::PlacePublished => "CY",
             RisTag::PmcId => "C2",
             RisTag::EndOfReference => "ER",
             RisTag::Unknown(tag) => tag,
@@ -184,9 +206,22 @@ impl RisTag {
                 | RisTag::AuthorSecondary
                 | RisTag::AuthorTertiary
                 | RisTag::AuthorSubsidiary
+                | RisTag::Editor
         )
     }
 
+    /// The role of the people named by this tag, if it is an author tag.
+    pub fn contributor_role(&self) -> Option<ContributorRole> {
+        match self {
+            RisTag::Author | RisTag::AuthorPrimary => Some(ContributorRole::Author),
+            RisTag::AuthorSecondary | RisTag::AuthorTertiary | RisTag::Editor => {
+                Some(ContributorRole::Editor)
+            }
+            RisTag::AuthorSubsidiary => Some(ContributorRole::Translator),
+            _ => None,
+        }
+    }
+
     /// Get the priority of this tag for journal name selection.
     /// Lower numbers have higher priority.
     ///
@@ -194,11 +229,13 @@ impl RisTag {
     /// 1. JF (Journal Full) - primary full journal name
     /// 2. T2 (Secondary Title) - alternative journal title
     /// 3. JO (Journal Full Alternative) - alternative full name
+    /// 4. BT (Book Title) - the containing book of a chapter
     pub fn journal_priority(&self) -> Option<u8> {
         match self {
             RisTag::JournalFull => Some(1),
             RisTag::SecondaryTitle => Some(2),
             RisTag::JournalFullAlternative => Some(3),
+            RisTag::BookTitle => Some(4),
             _ => None,
         }
     }
@@ -250,4 +287,18 @@ mod tests {
     fn test_is_author_tag(#[case] tag: RisTag, #[case] expected: bool) {
         assert_eq!(tag.is_author_tag(), expected);
     }
+
+    #[rstest]
+    #[case("AU", Some(ContributorRole::Author))]
+    #[case("A1", Some(ContributorRole::Author))]
+    #[case("A2", Some(ContributorRole::Editor))]
+    #[case("A3", Some(ContributorRole::Editor))]
+    #[case("ED", Some(ContributorRole::Editor))]
+    #[case("A4", Some(ContributorRole::Translator))]
+    #[case("TI", None)]
+    fn test_contributor_role(#[case] tag: &str, #[case] expected: Option<ContributorRole>) {
+        let tag = RisTag::from_tag(tag);
+        assert_eq!(tag.contributor_role(), expected);
+        assert_eq!(tag.is_author_tag(), expected.is_some());
+    }
 }
@@ -0,0 +1,523 @@
+//! RIS format writer implementation.
+//!
+//! Citations are written one record at a time so arbitrarily large collections
+//! can be streamed to a file.
+
+use crate::ris::tags::RisTag;
+use crate::writer::sanitize::ris_value;
+use crate::{Citation, CitationError, CitationWriter, ContributorRole, Date, LinkKind};
+use std::io::Write;
+
+/// Writer for RIS format citations.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::{Citation, CitationWriter, RisWriter};
+///
+/// let citation = Citation {
+///     title: "Example Title".to_string(),
+///     ..Default::default()
+/// };
+///
+/// let mut output = Vec::new();
+/// RisWriter::new().write_to([&citation], &mut output).unwrap();
+/// assert!(String::from_utf8(output).unwrap().starts_with("TY  - JOUR\n"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RisWriter;
+
+impl RisWriter {
+    /// Creates a new RIS writer instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::RisWriter;
+    /// let writer = RisWriter::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Writes a single citation as one RIS record, terminated by `ER`.
+    fn write_record<W: Write>(&self, citation: &Citation, w: &mut W) -> std::io::Result<()> {
+        write_tag(w, RisTag::Type, ris_type(citation.citation_type.first()))?;
+        write_tag(w, RisTag::Title, &citation.title)?;
+
+        // RIS has no tag for investigators; corporate authors are written as authors
+        for (role, tag) in [
+            (ContributorRole::Author, RisTag::Author),
+            (ContributorRole::CorporateAuthor, RisTag::Author),
+            (ContributorRole::Editor, RisTag::AuthorSecondary),
+            (ContributorRole::Translator, RisTag::AuthorSubsidiary),
+        ] {
+            for person in citation.contributors_with_role(role) {
+                write_tag(w, tag.clone(), &crate::utils::format_author_name(person))?;
+            }
+        }
+
+        write_optional_tag(w, RisTag::JournalFull, citation.journal.as_deref())?;
+        write_optional_tag(w, RisTag::JournalAbbreviation, citation.journal_abbr.as_deref())?;
+        if let Some(date) = &citation.date {
+            write_tag(
+                w,
+                RisTag::PublicationYear,
+                &format_date(date, citation.date_end.as_ref()),
+            )?;
+        }
+        write_optional_tag(w, RisTag::Volume, citation.volume.as_deref())?;
+        write_optional_tag(w, RisTag::Issue, citation.issue.as_deref())?;
+
+        if let Some(pages) = &citation.pages {
+            match pages.split_once('-') {
+                Some((start, end)) => {
+                    write_tag(w, RisTag::StartPage, start)?;
+                    write_tag(w, RisTag::EndPage, end)?;
+                }
+                None => write_tag(w, RisTag::StartPage, pages)?,
+            }
+        }
+
+        for issn in &citation.issn {
+            write_tag(w, RisTag::SerialNumber, issn)?;
+        }
+        write_optional_tag(w, RisTag::Doi, citation.doi.as_deref())?;
+        // Identifiers the parser keeps under "reference_id" came from `ID` too
+        let reference_id = citation.extra_fields.get("reference_id").and_then(|ids| ids.first());
+        write_optional_tag(
+            w,
+            RisTag::ReferenceId,
+            citation.pmid.as_deref().or(reference_id.map(String::as_str)),
+        )?;
+        write_optional_tag(w, RisTag::PmcId, citation.pmc_id.as_deref())?;
+        write_optional_tag(w, RisTag::Abstract, citation.abstract_text.as_deref())?;
+        for keyword in &citation.keywords {
+            write_tag(w, RisTag::Keywords, keyword)?;
+        }
+        for url in &citation.urls {
+            let tag = match citation.link_kinds.get(url) {
+                Some(LinkKind::Pdf) => RisTag::LinkPdf,
+                Some(LinkKind::FullText) => RisTag::LinkFullText,
+                _ => RisTag::Url,
+            };
+            write_tag(w, tag, url)?;
+        }
+        write_optional_tag(w, RisTag::Language, citation.language.as_deref())?;
+        write_optional_tag(w, RisTag::Publisher, citation.publisher.as_deref())?;
+        match &citation.conference {
+            Some(conference) => {
+                write_optional_tag(w, RisTag::SecondaryTitle, conference.name.as_deref())?;
+                write_optional_tag(w, RisTag::PlacePublished, conference.location.as_deref())?;
+                write_optional_tag(w, RisTag::DateAccess, conference.date.as_deref())?;
+            }
+            // Outside conference records the place published is kept as an extra field
+            None => {
+                let places = citation.extra_fields.get(RisTag::PlacePublished.as_tag());
+                for place in places.into_iter().flatten() {
+                    write_tag(w, RisTag::PlacePublished, place)?;
+                }
+            }
+        }
+        if let Some(notes) = &citation.notes {
+            for note in notes.split("\n\n") {
+                write_tag(w, RisTag::Notes, note)?;
+            }
+        }
+        self.write_extra_fields(citation, w)?;
+
+        writeln!(w, "{}  - ", RisTag::EndOfReference.as_tag())?;
+        writeln!(w)
+    }
+
+    /// Writes extra fields whose keys are RIS tags with no standard meaning,
+    /// sorted by tag.
+    ///
+    /// Keys naming a tag the parser maps to a standard field (e.g. "TI" or "JO"),
+    /// escaped keys such as "x-TI", and keys that are not valid tags are skipped,
+    /// so an extra field can never duplicate or override a standard field.
+    fn write_extra_fields<W: Write>(&self, citation: &Citation, w: &mut W) -> std::io::Result<()> {
+        let mut keys: Vec<&String> = citation
+            .extra_fields
+            .keys()
+            .filter(|key| is_extra_tag(key))
+            .collect();
+        keys.sort();
+
+        for key in keys {
+            for value in &citation.extra_fields[key] {
+                writeln!(w, "{}  - {}", key, ris_value(value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether an extra field key can be written as an RIS tag of its own.
+fn is_extra_tag(key: &str) -> bool {
+    key.len() == 2
+        && key.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && matches!(RisTag::from_tag(key), RisTag::Unknown(_))
+}
+
+impl CitationWriter for RisWriter {
+    /// Writes citations in RIS format, separating records with a blank line.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CitationError::Io` if the underlying writer fails
+    fn write_to<'a, W: Write>(
+        &self,
+        citations: impl IntoIterator<Item = &'a Citation>,
+        mut writer: W,
+    ) -> Result<(), CitationError> {
+        for citation in citations {
+            self.write_record(citation, &mut writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes one tag line, with newlines in the value replaced so it stays on the line.
+fn write_tag<W: Write>(w: &mut W, tag: RisTag, value: &str) -> std::io::Result<()> {
+    writeln!(w, "{}  - {}", tag.as_tag(), ris_value(value))
+}
+
+fn write_optional_tag<W: Write>(w: &mut W, tag: RisTag, value: Option<&str>) -> std::io::Result<()> {
+    match value {
+        Some(value) => write_tag(w, tag, value),
+        None => Ok(()),
+    }
+}
+
+/// Maps a citation type to an RIS reference type, defaulting to `JOUR`.
+///
+/// Types that already look like RIS codes (e.g. from RIS input) are kept as-is.
+fn ris_type(citation_type: Option<&String>) -> &str {
+    let Some(citation_type) = citation_type else {
+        return "JOUR";
+    };
+
+    match citation_type.to_lowercase().as_str() {
+        "journal article" | "article" => "JOUR",
+        "book" => "BOOK",
+        "book section" | "book chapter" => "CHAP",
+        "conference proceedings" | "conference paper" => "CONF",
+        "conference abstract" => "ABST",
+        "thesis" | "dissertation" => "THES",
+        "report" => "RPRT",
+        "web page" => "ELEC",
+        _ if (2..=6).contains(&citation_type.len())
+            && citation_type.bytes().all(|b| b.is_ascii_uppercase()) =>
+        {
+            citation_type
+        }
+        _ => "GEN",
+    }
+}
+
+/// Formats a date as an RIS `PY` value, e.g. "2023", "2023/01/23/" or "2020/01//Jan-Mar".
+///
+/// Year-only ranges are written as "2018-2019", which the parser reads back as a range.
+fn format_date(date: &Date, date_end: Option<&Date>) -> String {
+    if date.month.is_none() {
+        return match date_end {
+            Some(end) => format!("{}-{}", date.year, end.year),
+            None => date.year.to_string(),
+        };
+    }
+
+    let two_digits = |part: Option<u8>| part.map_or(String::new(), |p| format!("{:02}", p));
+    let other = date_end
+        .and_then(|end| crate::utils::format_month_day_range(date, Some(end)))
+        .unwrap_or_default();
+
+    format!(
+        "{}/{}/{}/{}",
+        date.year,
+        two_digits(date.month),
+        two_digits(date.day),
+        other
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::CountingWriter;
+    use crate::{Author, CitationParser, RisParser};
+    use pretty_assertions::assert_eq;
+
+    fn synthetic_citation(i: usize) -> Citation {
+        Citation {
+            citation_type: vec!["Journal Article".to_string()],
+            title: format!("Synthetic citation number {}", i),
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("John".to_string()),
+                middle_name: Some("A".to_string()),
+                suffix: None,
+                affiliations: Vec::new(),
+            }],
+            journal: Some("Journal of Testing".to_string()),
+            date: Some(Date {
+                year: 2000 + (i % 25) as i32,
+                month: Some(3),
+                day: None,
+            }),
+            volume: Some("12".to_string()),
+            pages: Some("100-110".to_string()),
+            doi: Some(format!("10.1234/test.{}", i)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let citation = Citation {
+            citation_type: vec!["JOUR".to_string()],
+            title: "Round trip".to_string(),
+            authors: vec![
+                Author {
+                    name: "Smith".to_string(),
+                    given_name: Some("John".to_string()),
+                    middle_name: None,
+                    suffix: Some("Jr.".to_string()),
+                    affiliations: Vec::new(),
+                },
+                Author {
+                    name: "WHO".to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                },
+            ],
+            journal: Some("Journal of Testing".to_string()),
+            journal_abbr: Some("J Test".to_string()),
+            date: Some(Date {
+                year: 2020,
+                month: Some(1),
+                day: None,
+            }),
+            date_end: Some(Date {
+                year: 2020,
+                month: Some(3),
+                day: None,
+            }),
+            volume: Some("10".to_string()),
+            issue: Some("2".to_string()),
+            pages: Some("100-110".to_string()),
+            issn: vec!["1234-5678".to_string()],
+            doi: Some("10.1234/test".to_string()),
+            pmc_id: Some("PMC123456".to_string()),
+            abstract_text: Some("An abstract.".to_string()),
+            keywords: vec!["one".to_string(), "two".to_string()],
+            urls: vec!["https://example.org".to_string()],
+            language: Some("eng".to_string()),
+            publisher: Some("Publisher".to_string()),
+            notes: Some("First note\n\nSecond note".to_string()),
+            ..Default::default()
+        };
+
+        let output = RisWriter::new().write_to_string([&citation]).unwrap();
+        assert!(output.contains("PY  - 2020/01//Jan-Mar\n"));
+        assert!(output.contains("SP  - 100\nEP  - 110\n"));
+        assert!(output.ends_with("ER  - \n\n"));
+
+        let parsed = RisParser::new().parse(&output).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let parsed = &parsed[0];
+        assert_eq!(parsed.citation_type, citation.citation_type);
+        assert_eq!(parsed.title, citation.title);
+        assert_eq!(parsed.authors, citation.authors);
+        assert_eq!(parsed.journal, citation.journal);
+        assert_eq!(parsed.journal_abbr, citation.journal_abbr);
+        assert_eq!(parsed.date, citation.date);
+        assert_eq!(parsed.date_end, citation.date_end);
+        assert_eq!(parsed.volume, citation.volume);
+        assert_eq!(parsed.issue, citation.issue);
+        assert_eq!(parsed.pages, citation.pages);
+        assert_eq!(parsed.issn, citation.issn);
+        assert_eq!(parsed.doi, citation.doi);
+        assert_eq!(parsed.pmc_id, citation.pmc_id);
+        assert_eq!(parsed.abstract_text, citation.abstract_text);
+        assert_eq!(parsed.keywords, citation.keywords);
+        assert_eq!(parsed.urls, citation.urls);
+        assert_eq!(parsed.language, citation.language);
+        assert_eq!(parsed.publisher, citation.publisher);
+        assert_eq!(parsed.notes, citation.notes);
+    }
+
+    #[test]
+    fn test_parse_write_parse_simple_ris() {
+        let input = "TY  - JOUR\nTI  - Test Article Title\nAU  - Smith, John\n\
+                     JO  - Test Journal\nPY  - 2023/12/25/Christmas edition\nVL  - 10\n\
+                     IS  - 2\nSP  - 100\nEP  - 110\nDO  - 10.1000/test\n\
+                     AB  - This is a test abstract.\nKW  - Keyword1\nKW  - Keyword2\n\
+                     ID  - 31000001\nDB  - Embase\nER  -\n";
+        let parsed = RisParser::new().parse(input).unwrap();
+
+        let output = RisWriter::new().write_to_string(&parsed).unwrap();
+        assert!(output.contains("AU  - Smith, John\nJF  - Test Journal\nPY  - 2023/12/25/\n"));
+        assert!(output.contains("ID  - 31000001\n"));
+        assert!(output.contains("DB  - Embase\n"));
+
+        let reparsed = RisParser::new().parse(&output).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&parsed).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_values_containing_ris_syntax() {
+        let dangerous = |field: &str| format!("{} start\nER  - \r\nTY  - BOOK\rTI  - end", field);
+        let mut citation = Citation {
+            title: dangerous("title"),
+            authors: vec![Author {
+                name: dangerous("name"),
+                given_name: None,
+                middle_name: None,
+                suffix: None,
+                affiliations: Vec::new(),
+            }],
+            journal: Some(dangerous("journal")),
+            journal_abbr: Some(dangerous("abbr")),
+            volume: Some(dangerous("volume")),
+            issue: Some(dangerous("issue")),
+            abstract_text: Some(dangerous("abstract")),
+            keywords: vec![dangerous("keyword")],
+            urls: vec![dangerous("url")],
+            language: Some(dangerous("language")),
+            publisher: Some(dangerous("publisher")),
+            notes: Some(dangerous("notes")),
+            ..Default::default()
+        };
+        citation
+            .extra_fields
+            .insert("ZZ".to_string(), vec![dangerous("extra")]);
+
+        let output = RisWriter::new().write_to_string([&citation]).unwrap();
+        let parsed = RisParser::new()
+            .set_clean_abstracts(false)
+            .parse(&output)
+            .unwrap();
+        assert_eq!(parsed.len(), 1);
+        let parsed = &parsed[0];
+
+        let expected = |field: &str| Some(format!("{} start ER -  TY - BOOK TI - end", field));
+        assert_eq!(Some(parsed.title.clone()), expected("title"));
+        // The name is split into parts, but stays one author
+        assert_eq!(parsed.authors.len(), 1);
+        assert_eq!(parsed.journal, expected("journal"));
+        assert_eq!(parsed.journal_abbr, expected("abbr"));
+        assert_eq!(parsed.volume, expected("volume"));
+        assert_eq!(parsed.issue, expected("issue"));
+        assert_eq!(parsed.abstract_text, expected("abstract"));
+        assert_eq!(parsed.keywords.first().cloned(), expected("keyword"));
+        assert_eq!(parsed.urls.first().cloned(), expected("url"));
+        assert_eq!(parsed.language, expected("language"));
+        assert_eq!(parsed.publisher, expected("publisher"));
+        assert_eq!(parsed.notes, expected("notes"));
+        assert_eq!(parsed.extra_fields["ZZ"].first().cloned(), expected("extra"));
+    }
+
+    #[test]
+    fn test_extra_fields_never_override_standard_fields() {
+        let input = "TY  - JOUR\nTI  - Primary title\nTI  - Vendor title\nDB  - Embase\nER  - \n";
+        let parsed = RisParser::new().parse(input).unwrap();
+        let mut citation = parsed.into_iter().next().unwrap();
+        assert_eq!(citation.title, "Primary title");
+        assert_eq!(citation.extra_fields["x-TI"], vec!["Vendor title"]);
+        assert!(!citation.extra_fields.contains_key("TI"));
+
+        // Hand-built keys colliding with standard tags are not written either
+        citation
+            .extra_fields
+            .insert("JO".to_string(), vec!["Shadow journal".to_string()]);
+        citation
+            .extra_fields
+            .insert("lowercase".to_string(), vec!["ignored".to_string()]);
+
+        let output = RisWriter::new().write_to_string([&citation]).unwrap();
+        assert_eq!(output.matches("TI  - ").count(), 1);
+        assert!(!output.contains("Vendor title"));
+        assert!(!output.contains("Shadow journal"));
+        assert!(output.contains("DB  - Embase\n"));
+
+        let reparsed = &RisParser::new().parse(&output).unwrap()[0];
+        assert_eq!(reparsed.title, "Primary title");
+        assert_eq!(reparsed.journal, None);
+        assert_eq!(reparsed.extra_fields["DB"], vec!["Embase"]);
+    }
+
+    #[test]
+    fn test_write_conference_metadata() {
+        let input = "TY  - CONF\nTI  - A paper\nT2  - Proc. Test Conf.\nCY  - Lisbon, Portugal\n\
+                     Y2  - 3-5 May 2023\nER  - \n\n\
+                     TY  - JOUR\nTI  - An article\nCY  - London\nER  - \n";
+        let parsed = RisParser::new().parse(input).unwrap();
+
+        let output = RisWriter::new().write_to_string(&parsed).unwrap();
+        assert_eq!(output.matches("CY  - ").count(), 2);
+
+        let reparsed = RisParser::new().parse(&output).unwrap();
+        assert_eq!(reparsed[0].conference, parsed[0].conference);
+        assert_eq!(reparsed[0].journal.as_deref(), Some("Proc. Test Conf."));
+        assert_eq!(reparsed[1].extra_fields["CY"], vec!["London"]);
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = |year, month, day| Date { year, month, day };
+
+        assert_eq!(format_date(&date(2023, None, None), None), "2023");
+        assert_eq!(
+            format_date(&date(2023, Some(1), Some(23)), None),
+            "2023/01/23/"
+        );
+        assert_eq!(
+            format_date(&date(2019, Some(12), None), Some(&date(2020, Some(2), None))),
+            "2019/12//Dec-2020 Feb"
+        );
+        assert_eq!(
+            format_date(&date(2018, None, None), Some(&date(2019, None, None))),
+            "2018-2019"
+        );
+    }
+
+    #[test]
+    fn test_ris_type() {
+        assert_eq!(ris_type(None), "JOUR");
+        assert_eq!(ris_type(Some(&"Journal Article".to_string())), "JOUR");
+        assert_eq!(ris_type(Some(&"Book Section".to_string())), "CHAP");
+        assert_eq!(ris_type(Some(&"CPAPER".to_string())), "CPAPER");
+        assert_eq!(ris_type(Some(&"Something else".to_string())), "GEN");
+    }
+
+    #[test]
+    fn test_write_streams_large_collection() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::default();
+        RisWriter::new().write_to(&citations, &mut counter).unwrap();
+
+        let expected = RisWriter::new().write_to_string(&citations).unwrap();
+        assert_eq!(counter.bytes, expected.len());
+        assert_eq!(RisParser::new().parse(&expected).unwrap().len(), 10_000);
+    }
+
+    #[test]
+    fn test_write_propagates_io_errors() {
+        let citations: Vec<Citation> = (0..10_000).map(synthetic_citation).collect();
+
+        let mut counter = CountingWriter::failing_after(4096);
+        let result = RisWriter::new().write_to(&citations, &mut counter);
+
+        assert!(matches!(result, Err(CitationError::Io(_))));
+        assert!(counter.bytes <= 4096);
+    }
+}
@@ -0,0 +1,36 @@
+//! JSON Schema of the citation JSON written by biblib.
+//!
+//! [`citation_schema`] returns a [JSON Schema](https://json-schema.org) (draft 7)
+//! describing [`Citation`](crate::Citation) as serde serializes it, with
+//! [`Author`](crate::Author), [`Date`](crate::Date) and the other types it
+//! holds under `definitions`. The schema is derived with `schemars` from the
+//! same types and serde attributes that serialization uses, so the two cannot
+//! diverge. [`DuplicateGroup`](crate::DuplicateGroup) and the types of the
+//! match details implement `schemars::JsonSchema` too, for schemas of
+//! deduplication output.
+//!
+//! A snapshot of the schema is checked in as `schema/citation.schema.json`
+//! for tools outside Rust, and the test suite fails when it no longer matches,
+//! so field changes show up in review.
+//!
+//! Fields holding an `Option` are not listed as required, although
+//! serialization writes them all, as `null` when unset. Deserialization is
+//! more lenient still: any field may be missing, and the 0.2 shape is accepted
+//! too (see [`Citation`](crate::Citation)).
+//!
+//! # Examples
+//!
+//! ```
+//! let schema = biblib::schema::citation_schema();
+//! let citation = schema.schema.object.as_ref().unwrap();
+//! assert!(citation.properties.contains_key("electronic_date"));
+//! assert!(schema.definitions.contains_key("Author"));
+//! ```
+
+use schemars::schema::RootSchema;
+
+/// Returns the JSON Schema of a serialized [`Citation`](crate::Citation).
+#[must_use]
+pub fn citation_schema() -> RootSchema {
+    schemars::schema_for!(crate::Citation)
+}
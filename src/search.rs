@@ -0,0 +1,379 @@
+//! Looking up citations by title while the user types.
+//!
+//! [`TitleIndex`] keeps the titles of a list of citations, normalized with
+//! [`normalize_title`], in sorted order. Titles starting with a given text are
+//! then found by binary search instead of normalizing and scanning every title
+//! on each keystroke, and [`TitleIndex::find_similar_titles`] only scores the
+//! titles whose shared prefix and length leave room for a match.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::Citation;
+//! use biblib::search::TitleIndex;
+//!
+//! let citation = |title: &str| Citation {
+//!     title: title.to_string(),
+//!     ..Default::default()
+//! };
+//! let citations = vec![
+//!     citation("Sepsis in children"),
+//!     citation("Machine learning in radiology"),
+//!     citation("Machine Learning for sepsis prediction"),
+//! ];
+//!
+//! let index = TitleIndex::new(&citations);
+//! assert_eq!(index.find_by_title_prefix("machine learning f", 10), vec![2]);
+//! assert_eq!(index.find_by_title_prefix("Machine", 10), vec![2, 1]);
+//! ```
+
+use crate::Citation;
+use crate::text::normalize_title;
+use rayon::prelude::*;
+use std::ops::Range;
+
+/// A normalized title and the citation it belongs to.
+#[derive(Debug, Clone)]
+struct Entry {
+    title: String,
+    /// Length of `title` in characters
+    chars: usize,
+    /// Position of the citation in the indexed list
+    index: usize,
+}
+
+/// Sorted index of normalized citation titles.
+///
+/// Citations whose title is empty once normalized are left out. The index
+/// refers to citations by their position in the list it was built from, and
+/// does not change when that list does; rebuild it after adding or removing
+/// citations.
+#[derive(Debug, Clone, Default)]
+pub struct TitleIndex {
+    /// Entries sorted by title, then by citation index
+    entries: Vec<Entry>,
+    /// Positions in `entries`, sorted by title length
+    by_length: Vec<usize>,
+}
+
+impl TitleIndex {
+    /// Builds the index of the titles of `citations`.
+    ///
+    /// Titles are normalized in parallel.
+    #[must_use]
+    pub fn new(citations: &[Citation]) -> Self {
+        let entry = |(index, citation): (usize, &Citation)| {
+            let title = normalize_title(&citation.title);
+            (!title.is_empty()).then(|| Entry {
+                chars: title.chars().count(),
+                title,
+                index,
+            })
+        };
+
+        let mut entries: Vec<Entry> = citations.par_iter().enumerate().filter_map(entry).collect();
+
+        // Entries are collected in citation order, so a stable sort keeps equal
+        // titles in that order
+        entries.sort_by(|a, b| a.title.cmp(&b.title));
+        let mut by_length: Vec<usize> = (0..entries.len()).collect();
+        by_length.sort_by_key(|&position| entries[position].chars);
+
+        Self { entries, by_length }
+    }
+
+    /// Number of indexed titles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when no title was indexed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the positions of up to `limit` citations whose normalized title
+    /// starts with the normalized `prefix`.
+    ///
+    /// Citations are returned in the order of their normalized titles, and
+    /// citations with the same title in their original order. An empty prefix
+    /// matches every title.
+    #[must_use]
+    pub fn find_by_title_prefix(&self, prefix: &str, limit: usize) -> Vec<usize> {
+        let prefix = normalize_title(prefix);
+        self.entries[self.prefix_range(&prefix)]
+            .iter()
+            .take(limit)
+            .map(|entry| entry.index)
+            .collect()
+    }
+
+    /// Returns up to `limit` citations whose title has a Jaro-Winkler similarity
+    /// of at least `min_similarity` to `title`, with their similarity.
+    ///
+    /// Titles are compared once normalized, as in [`find_by_title_prefix`], and
+    /// the results are sorted by descending similarity, then by position.
+    /// Titles too short or too long to reach `min_similarity` given the prefix
+    /// they share with `title` are skipped without being scored, so the results
+    /// are those of scoring every title.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    /// use biblib::search::TitleIndex;
+    ///
+    /// let citations: Vec<Citation> = [
+    ///     "Effects of beta-blockers on heart failure",
+    ///     "Effect of β-blockers in heart failure",
+    ///     "Statins for primary prevention",
+    /// ]
+    /// .iter()
+    /// .map(|title| Citation { title: title.to_string(), ..Default::default() })
+    /// .collect();
+    ///
+    /// let index = TitleIndex::new(&citations);
+    /// let similar = index.find_similar_titles("Effects of β-blockers on heart failure", 0.9, 5);
+    /// assert_eq!(similar.len(), 2);
+    /// assert_eq!(similar[0], (0, 1.0));
+    /// assert_eq!(similar[1].0, 1);
+    /// ```
+    ///
+    /// [`find_by_title_prefix`]: TitleIndex::find_by_title_prefix
+    #[must_use]
+    pub fn find_similar_titles(
+        &self,
+        title: &str,
+        min_similarity: f64,
+        limit: usize,
+    ) -> Vec<(usize, f64)> {
+        let query = normalize_title(title);
+        let query_chars = query.chars().count();
+        if query_chars == 0 || limit == 0 {
+            return Vec::new();
+        }
+
+        let mut matches = Vec::new();
+        let mut score = |entry: &Entry| {
+            let similarity = strsim::jaro_winkler(&query, &entry.title);
+            if similarity >= min_similarity {
+                matches.push((entry.index, similarity));
+            }
+        };
+
+        // Titles of any prefix whose length allows a match without a prefix bonus
+        let unprefixed = length_band(query_chars, min_similarity, 0);
+        let start = self
+            .by_length
+            .partition_point(|&position| self.entries[position].chars < unprefixed.start);
+        let end = self
+            .by_length
+            .partition_point(|&position| self.entries[position].chars < unprefixed.end);
+        for &position in &self.by_length[start..end] {
+            score(&self.entries[position]);
+        }
+
+        // Titles sharing their first characters with the query get a bonus of up
+        // to four characters, which admits a wider band of lengths
+        let boundaries: Vec<usize> = query
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .skip(1)
+            .chain([query.len()])
+            .take(4)
+            .collect();
+        for (shared, &offset) in boundaries.iter().enumerate() {
+            let band = length_band(query_chars, min_similarity, shared + 1);
+            let range = self.prefix_range(&query[..offset]);
+            // Titles sharing one character more are scored in the next round
+            let longer = match boundaries.get(shared + 1) {
+                Some(&next) => self.prefix_range(&query[..next]),
+                None => range.end..range.end,
+            };
+            for entry in self.entries[range.start..longer.start]
+                .iter()
+                .chain(&self.entries[longer.end..range.end])
+            {
+                if band.contains(&entry.chars) && !unprefixed.contains(&entry.chars) {
+                    score(entry);
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Positions in `entries` of the titles starting with `prefix`.
+    fn prefix_range(&self, prefix: &str) -> Range<usize> {
+        let start = self
+            .entries
+            .partition_point(|entry| entry.title.as_str() < prefix);
+        let len = self.entries[start..].partition_point(|entry| entry.title.starts_with(prefix));
+        start..start + len
+    }
+}
+
+/// Lengths in characters a title must have to reach a Jaro-Winkler similarity of
+/// `min_similarity` to a title of `chars` characters, when the two share their
+/// first `shared` characters.
+///
+/// The Jaro similarity of titles whose lengths have the ratio `r` (shorter by
+/// longer) is at most `(2 + r) / 3`, and the Winkler bonus adds at most a tenth
+/// of the remainder per shared character.
+fn length_band(chars: usize, min_similarity: f64, shared: usize) -> Range<usize> {
+    // Leaves room for rounding, so no title that reaches the threshold is missed
+    const TOLERANCE: f64 = 1e-9;
+
+    let bonus = 0.1 * shared.min(4) as f64;
+    let min_jaro = (min_similarity - bonus) / (1.0 - bonus);
+    let min_ratio = 3.0 * min_jaro - 2.0 - TOLERANCE;
+    if min_ratio <= 0.0 {
+        return 1..usize::MAX;
+    }
+    let shortest = (chars as f64 * min_ratio).ceil().max(1.0) as usize;
+    let longest = (chars as f64 / min_ratio).floor() as usize;
+    shortest..longest.saturating_add(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn citations(titles: &[&str]) -> Vec<Citation> {
+        titles
+            .iter()
+            .map(|title| Citation {
+                title: title.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    const TITLES: [&str; 12] = [
+        "Machine learning in radiology",
+        "Machine Learning for sepsis prediction",
+        "Machine learning in radiology.",
+        "Deep learning in radiology",
+        "Sepsis in children",
+        "Sepsis in childhood",
+        "",
+        "<i>In vitro</i> effects of β-blockers",
+        "In vitro effects of beta blockers",
+        "Ma",
+        "Mach",
+        "Effects of exercise on sleep quality in older adults",
+    ];
+
+    #[test]
+    fn test_find_by_title_prefix() {
+        let index = TitleIndex::new(&citations(&TITLES));
+        assert_eq!(index.len(), 11);
+        assert_eq!(
+            index.find_by_title_prefix("machine learning in", 10),
+            vec![0, 2]
+        );
+        assert_eq!(index.find_by_title_prefix("MACHINE", 10), vec![1, 0, 2]);
+        assert_eq!(index.find_by_title_prefix("Mach", 10), vec![10, 1, 0, 2]);
+        assert_eq!(index.find_by_title_prefix("Mach", 2), vec![10, 1]);
+        assert_eq!(
+            index.find_by_title_prefix("in vitro effects of β", 10),
+            vec![7, 8]
+        );
+        assert_eq!(
+            index.find_by_title_prefix("zebrafish", 10),
+            Vec::<usize>::new()
+        );
+        assert_eq!(index.find_by_title_prefix("", 100).len(), 11);
+        assert_eq!(index.find_by_title_prefix("Sepsis", 0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_find_by_title_prefix_matches_scan() {
+        let citations = citations(&TITLES);
+        let index = TitleIndex::new(&citations);
+        for prefix in [
+            "m",
+            "machine l",
+            "s",
+            "sepsis in child",
+            "in",
+            "e",
+            "d",
+            "x",
+            "ma",
+        ] {
+            let normalized = normalize_title(prefix);
+            let mut expected: Vec<(String, usize)> = citations
+                .iter()
+                .enumerate()
+                .map(|(i, citation)| (normalize_title(&citation.title), i))
+                .filter(|(title, _)| !title.is_empty() && title.starts_with(&normalized))
+                .collect();
+            expected.sort();
+            let expected: Vec<usize> = expected.into_iter().map(|(_, i)| i).collect();
+            assert_eq!(
+                index.find_by_title_prefix(prefix, usize::MAX),
+                expected,
+                "{prefix}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_similar_titles_matches_scan() {
+        let citations = citations(&TITLES);
+        let index = TitleIndex::new(&citations);
+        let queries = [
+            "Machine learning in radiology",
+            "Machine learning",
+            "Sepsis in children",
+            "In vitro effects of beta-blockers",
+            "Mac",
+            "M",
+            "Learning in radiology",
+            "Effects of exercise",
+        ];
+        for query in queries {
+            let normalized = normalize_title(query);
+            for min_similarity in [0.0, 0.5, 0.7, 0.8, 0.85, 0.9, 0.95, 1.0] {
+                let mut expected: Vec<(usize, f64)> = citations
+                    .iter()
+                    .enumerate()
+                    .map(|(i, citation)| (i, normalize_title(&citation.title)))
+                    .filter(|(_, title)| !title.is_empty())
+                    .map(|(i, title)| (i, strsim::jaro_winkler(&normalized, &title)))
+                    .filter(|(_, similarity)| *similarity >= min_similarity)
+                    .collect();
+                expected.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+                assert_eq!(
+                    index.find_similar_titles(query, min_similarity, usize::MAX),
+                    expected,
+                    "{query} at {min_similarity}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_similar_titles_limit() {
+        let index = TitleIndex::new(&citations(&TITLES));
+        let similar = index.find_similar_titles("Machine learning in radiology", 0.8, 2);
+        assert_eq!(similar, vec![(0, 1.0), (2, 1.0)]);
+        assert!(index.find_similar_titles("", 0.0, 10).is_empty());
+        assert!(index.find_similar_titles("Sepsis", 0.0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_length_band() {
+        assert_eq!(length_band(20, 0.5, 0), 1..usize::MAX);
+        assert_eq!(length_band(20, 1.0, 0), 20..21);
+        assert_eq!(length_band(20, 0.9, 0), 14..29);
+        // A shared prefix admits titles further from the query's length
+        assert!(length_band(20, 0.9, 4).start < 14);
+    }
+}
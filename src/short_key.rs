@@ -0,0 +1,300 @@
+//! Short, human-readable citation keys such as "Smith2023JClinOncol".
+//!
+//! [`Citation::short_key`] joins the family name of the first author, the
+//! publication year and the journal abbreviation, folded to ASCII letters and
+//! digits. A piece the citation lacks is replaced by the next word of the
+//! title, so every citation gets a key. Keys are not unique by themselves;
+//! [`assign_unique_short_keys`] tells apart the citations of a dataset that
+//! share one with letter suffixes.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::{Author, Citation, Date};
+//! use biblib::short_key::{SHORT_KEY_FIELD, assign_unique_short_keys};
+//!
+//! let citation = |title: &str| Citation {
+//!     title: title.to_string(),
+//!     authors: vec![Author {
+//!         name: "Müller".to_string(),
+//!         given_name: Some("Anna".to_string()),
+//!         middle_name: None,
+//!         suffix: None,
+//!         affiliations: Vec::new(),
+//!     }],
+//!     date: Some(Date { year: 2023, month: None, day: None }),
+//!     journal_abbr: Some("J Clin Oncol".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let mut citations = vec![citation("First trial"), citation("Second trial")];
+//! assert_eq!(citations[0].short_key(), "Muller2023JClinOncol");
+//!
+//! let keys = assign_unique_short_keys(&mut citations);
+//! assert_eq!(keys["Muller2023JClinOncola"], 0);
+//! assert_eq!(citations[1].extra_fields[SHORT_KEY_FIELD], vec!["Muller2023JClinOncolb"]);
+//! ```
+
+use crate::Citation;
+use std::collections::{HashMap, HashSet};
+
+/// [`Citation::extra_fields`] key holding the key set by [`assign_unique_short_keys`].
+pub const SHORT_KEY_FIELD: &str = "short_key";
+
+/// Title words left out when title words stand in for missing pieces.
+const STOP_WORDS: [&str; 12] = [
+    "a", "an", "and", "at", "by", "for", "in", "of", "on", "the", "to", "with",
+];
+
+/// Sets a unique short key on every citation, under [`SHORT_KEY_FIELD`] in
+/// `extra_fields`, and returns the position of each key's citation.
+///
+/// Citations that share a [`Citation::short_key`] all get a letter suffix, "a",
+/// "b" and so on in input order, continuing with "aa" after "z". A suffix that
+/// would give a key another citation already has is skipped.
+pub fn assign_unique_short_keys(citations: &mut [Citation]) -> HashMap<String, usize> {
+    let keys = unique_keys(citations.iter().map(|citation| key_of(citation, true)));
+    let mut positions = HashMap::with_capacity(keys.len());
+    for (index, (citation, key)) in citations.iter_mut().zip(keys).enumerate() {
+        citation
+            .extra_fields
+            .insert(SHORT_KEY_FIELD.to_string(), vec![key.clone()]);
+        positions.insert(key, index);
+    }
+    positions
+}
+
+/// The key of a citation: the first author's family name, the year and, when
+/// `with_journal` is set, the journal abbreviation or name, each replaced by
+/// the next title word when missing. "Untitled" when nothing is left.
+pub(crate) fn key_of(citation: &Citation, with_journal: bool) -> String {
+    let mut title_words = citation
+        .title
+        .split_whitespace()
+        .filter(|word| !STOP_WORDS.contains(&word.to_lowercase().as_str()))
+        .map(key_part)
+        .filter(|word| !word.is_empty());
+
+    let author = citation
+        .authors
+        .first()
+        .map(|author| key_part(&author.name))
+        .filter(|name| !name.is_empty());
+    let year = citation.date.as_ref().map(|date| date.year.to_string());
+    let journal = [&citation.journal_abbr, &citation.journal]
+        .into_iter()
+        .flatten()
+        .map(|journal| key_part(journal))
+        .find(|journal| !journal.is_empty());
+
+    let mut key = String::new();
+    for piece in [Some(author), Some(year), with_journal.then_some(journal)]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(piece) = piece.or_else(|| title_words.next()) {
+            key.push_str(&piece);
+        }
+    }
+    if key.is_empty() {
+        key.push_str("Untitled");
+    }
+    key
+}
+
+/// Makes `keys` unique by suffixing every key that repeats with "a", "b" and so
+/// on in order, skipping suffixed keys that are already taken.
+pub(crate) fn unique_keys(keys: impl IntoIterator<Item = String>) -> Vec<String> {
+    let keys: Vec<String> = keys.into_iter().collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for key in &keys {
+        *counts.entry(key).or_default() += 1;
+    }
+    let mut taken: HashSet<String> = counts
+        .iter()
+        .filter(|(_, count)| **count == 1)
+        .map(|(key, _)| key.to_string())
+        .collect();
+
+    let mut suffixes: HashMap<&str, usize> = HashMap::new();
+    let mut unique = Vec::with_capacity(keys.len());
+    for key in &keys {
+        if counts[key.as_str()] == 1 {
+            unique.push(key.clone());
+            continue;
+        }
+        let suffix = suffixes.entry(key).or_default();
+        let candidate = loop {
+            *suffix += 1;
+            let candidate = format!("{key}{}", letter_suffix(*suffix));
+            if taken.insert(candidate.clone()) {
+                break candidate;
+            }
+        };
+        unique.push(candidate);
+    }
+    unique
+}
+
+/// The `n`th letter suffix, counting from 1: "a" to "z", then "aa", "ab" and so on.
+fn letter_suffix(mut n: usize) -> String {
+    let mut letters = Vec::new();
+    while n > 0 {
+        n -= 1;
+        letters.push(b'a' + (n % 26) as u8);
+        n /= 26;
+    }
+    letters.iter().rev().map(|&b| b as char).collect()
+}
+
+/// Folds `text` to ASCII and joins its words, each starting with a capital, so
+/// "J Clin Oncol" gives "JClinOncol" and "van der Berg" gives "VanDerBerg".
+fn key_part(text: &str) -> String {
+    let folded: String = text.chars().map(fold_to_ascii).collect();
+    folded
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let (first, rest) = word.split_at(1);
+            format!("{}{rest}", first.to_ascii_uppercase())
+        })
+        .collect()
+}
+
+/// Folds an accented Latin letter to its ASCII letters, keeping its case.
+/// Other characters are returned as they are.
+pub(crate) fn fold_to_ascii(c: char) -> String {
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let folded = match lower {
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'þ' => "th",
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return c.to_string(),
+    };
+    if c.is_uppercase() {
+        folded.to_ascii_uppercase()
+    } else {
+        folded.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, Date};
+    use rstest::rstest;
+
+    fn citation(author: Option<&str>, year: Option<i32>, journal: Option<&str>) -> Citation {
+        Citation {
+            title: "The effect of early mobilisation on delirium".to_string(),
+            authors: author
+                .map(|name| Author {
+                    name: name.to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                })
+                .into_iter()
+                .collect(),
+            date: year.map(|year| Date {
+                year,
+                month: None,
+                day: None,
+            }),
+            journal_abbr: journal.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(Some("Smith"), Some(2023), Some("J Clin Oncol"), "Smith2023JClinOncol")]
+    #[case(Some("Ødegård"), Some(2023), Some("Crit Care"), "Odegard2023CritCare")]
+    #[case(Some("van der Berg"), Some(2019), Some("BMJ"), "VanDerBerg2019BMJ")]
+    #[case(Some("O'Brien"), Some(2020), Some("Lancet"), "OBrien2020Lancet")]
+    #[case(None, Some(2023), Some("J Clin Oncol"), "Effect2023JClinOncol")]
+    #[case(Some("Smith"), None, Some("J Clin Oncol"), "SmithEffectJClinOncol")]
+    #[case(Some("Smith"), Some(2023), None, "Smith2023Effect")]
+    #[case(None, None, None, "EffectEarlyMobilisation")]
+    #[case(Some("  "), Some(2023), Some("--"), "Effect2023Early")]
+    fn test_short_key(
+        #[case] author: Option<&str>,
+        #[case] year: Option<i32>,
+        #[case] journal: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(citation(author, year, journal).short_key(), expected);
+    }
+
+    #[test]
+    fn test_journal_name_and_empty_citations() {
+        let mut with_name = citation(Some("Smith"), Some(2023), None);
+        with_name.journal = Some("Journal of Critical Care".to_string());
+        assert_eq!(with_name.short_key(), "Smith2023JournalOfCriticalCare");
+        assert_eq!(Citation::new().short_key(), "Untitled");
+        assert_eq!(
+            key_of(&citation(Some("Smith"), Some(2023), None), false),
+            "Smith2023"
+        );
+    }
+
+    #[test]
+    fn test_collisions_get_suffixes_in_input_order() {
+        let mut citations = vec![
+            citation(Some("Smith"), Some(2023), Some("BMJ")),
+            citation(Some("Jones"), Some(2023), Some("BMJ")),
+            citation(Some("Smith"), Some(2023), Some("BMJ")),
+            citation(Some("Smith"), Some(2023), Some("BMJ")),
+        ];
+        let keys = assign_unique_short_keys(&mut citations);
+        let assigned: Vec<&str> = citations
+            .iter()
+            .map(|c| c.extra_fields[SHORT_KEY_FIELD][0].as_str())
+            .collect();
+        assert_eq!(
+            assigned,
+            vec![
+                "Smith2023BMJa",
+                "Jones2023BMJ",
+                "Smith2023BMJb",
+                "Smith2023BMJc"
+            ]
+        );
+        assert_eq!(keys.len(), 4);
+        assert_eq!(keys["Smith2023BMJc"], 3);
+        assert_eq!(keys["Jones2023BMJ"], 1);
+    }
+
+    #[test]
+    fn test_suffixes_skip_taken_keys() {
+        let keys = ["Smith2023", "Smith2023a", "Smith2023"].map(str::to_string);
+        assert_eq!(
+            unique_keys(keys),
+            vec!["Smith2023b", "Smith2023a", "Smith2023c"]
+        );
+
+        let many = unique_keys(std::iter::repeat_n("Key".to_string(), 28));
+        assert_eq!(&many[25..], ["Keyz", "Keyaa", "Keyab"]);
+        assert_eq!(letter_suffix(26 * 27 + 1), "aaa");
+    }
+}
@@ -0,0 +1,282 @@
+//! Sorting of citation lists for display.
+//!
+//! [`sort_citations`] sorts by several [`SortKey`]s in turn, and
+//! [`SortKey::compare`] exposes each comparison for use in other sorts.
+//!
+//! Text is compared case-insensitively by Unicode lowercase mapping, which does
+//! not depend on the locale. Citations missing the value of a key, such as a
+//! date or authors, always sort after those that have it, in ascending and
+//! descending order alike.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::sort::{SortKey, sort_citations};
+//! use biblib::{Citation, Date};
+//!
+//! let citation = |title: &str, year: Option<i32>| Citation {
+//!     title: title.to_string(),
+//!     date: year.map(|year| Date { year, month: None, day: None }),
+//!     ..Default::default()
+//! };
+//! let mut citations = vec![
+//!     citation("b", Some(2019)),
+//!     citation("c", None),
+//!     citation("a", Some(2021)),
+//! ];
+//!
+//! sort_citations(&mut citations, &[SortKey::Year.descending()]);
+//! let titles: Vec<&str> = citations.iter().map(|c| c.title.as_str()).collect();
+//! assert_eq!(titles, vec!["a", "b", "c"]);
+//! ```
+
+use crate::Citation;
+use std::cmp::Ordering;
+
+/// A key to sort citations by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SortKey {
+    /// Family name of the first author
+    FirstAuthorFamily,
+    /// Publication year
+    Year,
+    /// Publication year, then family name of the first author
+    YearThenAuthor,
+    /// Title
+    Title,
+    /// Journal name, or its abbreviation when the name is missing
+    Journal,
+    /// Order of [`Citation::id`], for ids assigned as citations are added.
+    /// Numeric ids are compared as numbers.
+    DateAdded,
+    /// The reverse order of a key, e.g. the most recent year first; missing
+    /// values still sort last
+    Descending(Box<SortKey>),
+}
+
+impl SortKey {
+    /// Returns the descending order of this key.
+    #[must_use]
+    pub fn descending(self) -> Self {
+        SortKey::Descending(Box::new(self))
+    }
+
+    /// Compares two citations by this key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::Citation;
+    /// use biblib::sort::SortKey;
+    /// use std::cmp::Ordering;
+    ///
+    /// let a = Citation { title: "alpha".to_string(), ..Default::default() };
+    /// let b = Citation { title: "Beta".to_string(), ..Default::default() };
+    /// assert_eq!(SortKey::Title.compare(&a, &b), Ordering::Less);
+    /// ```
+    #[must_use]
+    pub fn compare(&self, a: &Citation, b: &Citation) -> Ordering {
+        self.compare_in(a, b, false)
+    }
+
+    /// Compares two citations, reversing the order of present values when
+    /// `descending` is set.
+    fn compare_in(&self, a: &Citation, b: &Citation, descending: bool) -> Ordering {
+        match self {
+            SortKey::FirstAuthorFamily => {
+                compare_present(first_author(a), first_author(b), descending, compare_text)
+            }
+            SortKey::Year => compare_present(year(a), year(b), descending, Ord::cmp),
+            SortKey::YearThenAuthor => SortKey::Year
+                .compare_in(a, b, descending)
+                .then_with(|| SortKey::FirstAuthorFamily.compare_in(a, b, descending)),
+            SortKey::Title => compare_present(title(a), title(b), descending, compare_text),
+            SortKey::Journal => compare_present(journal(a), journal(b), descending, compare_text),
+            SortKey::DateAdded => compare_present(a.id(), b.id(), descending, compare_ids),
+            SortKey::Descending(key) => key.compare_in(a, b, !descending),
+        }
+    }
+}
+
+/// Sorts citations by `keys`, each breaking the ties of the previous one.
+///
+/// The sort is stable: citations equal under every key keep their order.
+pub fn sort_citations(citations: &mut [Citation], keys: &[SortKey]) {
+    citations.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+/// Orders present values with `cmp`, and missing values after present ones.
+fn compare_present<T>(
+    a: Option<T>,
+    b: Option<T>,
+    descending: bool,
+    cmp: impl Fn(&T, &T) -> Ordering,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => cmp(&b, &a),
+        (Some(a), Some(b)) => cmp(&a, &b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compares text case-insensitively, without allocating.
+fn compare_text(a: &&str, b: &&str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+/// Compares ids as numbers when both are numeric, and as text otherwise.
+fn compare_ids(a: &&str, b: &&str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => compare_text(a, b),
+    }
+}
+
+/// Treats blank text as missing.
+fn non_blank(text: &str) -> Option<&str> {
+    let text = text.trim();
+    (!text.is_empty()).then_some(text)
+}
+
+fn first_author(citation: &Citation) -> Option<&str> {
+    citation.authors.first().and_then(|a| non_blank(&a.name))
+}
+
+fn year(citation: &Citation) -> Option<i32> {
+    citation.date.as_ref().map(|date| date.year)
+}
+
+fn title(citation: &Citation) -> Option<&str> {
+    non_blank(&citation.title)
+}
+
+fn journal(citation: &Citation) -> Option<&str> {
+    citation
+        .journal
+        .as_deref()
+        .and_then(non_blank)
+        .or_else(|| citation.journal_abbr.as_deref().and_then(non_blank))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, Date};
+    use pretty_assertions::assert_eq;
+
+    fn citation(title: &str, author: Option<&str>, year: Option<i32>) -> Citation {
+        Citation {
+            title: title.to_string(),
+            authors: author
+                .map(|name| Author {
+                    name: name.to_string(),
+                    given_name: None,
+                    middle_name: None,
+                    suffix: None,
+                    affiliations: Vec::new(),
+                })
+                .into_iter()
+                .collect(),
+            date: year.map(|year| Date {
+                year,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn titles(citations: &[Citation]) -> Vec<&str> {
+        citations.iter().map(|c| c.title.as_str()).collect()
+    }
+
+    /// Citations in no particular order.
+    fn shuffled() -> Vec<Citation> {
+        vec![
+            citation("1", Some("Okafor"), Some(2019)),
+            citation("2", None, None),
+            citation("3", Some("nguyen"), Some(2021)),
+            citation("4", Some("Álvarez"), None),
+            citation("5", Some("Larsen"), Some(2021)),
+            citation("6", Some("Nguyen"), Some(2019)),
+            citation("7", None, Some(2021)),
+            citation("8", Some("abbott"), Some(2019)),
+        ]
+    }
+
+    #[test]
+    fn test_year_descending_then_first_author() {
+        let mut citations = shuffled();
+        sort_citations(
+            &mut citations,
+            &[SortKey::Year.descending(), SortKey::FirstAuthorFamily],
+        );
+
+        // Missing authors sort last within a year, missing years last overall,
+        // and "nguyen" ties with "Nguyen" case-insensitively
+        assert_eq!(titles(&citations), vec!["5", "3", "7", "8", "6", "1", "4", "2"]);
+    }
+
+    #[test]
+    fn test_year_then_author() {
+        let mut citations = shuffled();
+        sort_citations(&mut citations, &[SortKey::YearThenAuthor]);
+        assert_eq!(titles(&citations), vec!["8", "6", "1", "5", "3", "7", "4", "2"]);
+
+        let mut citations = shuffled();
+        sort_citations(&mut citations, &[SortKey::YearThenAuthor.descending()]);
+        assert_eq!(titles(&citations), vec!["3", "5", "7", "1", "6", "8", "4", "2"]);
+    }
+
+    #[test]
+    fn test_title_and_journal() {
+        let mut citations = vec![
+            citation("beta", None, None),
+            citation("", None, None),
+            citation("Alpha", None, None),
+        ];
+        citations[0].journal_abbr = Some("Z J".to_string());
+        citations[2].journal = Some("a journal".to_string());
+
+        sort_citations(&mut citations, &[SortKey::Title]);
+        assert_eq!(titles(&citations), vec!["Alpha", "beta", ""]);
+
+        citations.reverse();
+        sort_citations(&mut citations, &[SortKey::Journal.descending()]);
+        assert_eq!(titles(&citations), vec!["beta", "Alpha", ""]);
+    }
+
+    #[test]
+    fn test_date_added() {
+        let mut citations: Vec<Citation> = ["10", "9", "x", ""]
+            .iter()
+            .map(|id| {
+                let mut c = citation(id, None, None);
+                if !id.is_empty() {
+                    c.set_id(id);
+                }
+                c
+            })
+            .collect();
+
+        sort_citations(&mut citations, &[SortKey::DateAdded]);
+        assert_eq!(titles(&citations), vec!["9", "10", "x", ""]);
+    }
+
+    #[test]
+    fn test_compare_text() {
+        assert_eq!(compare_text(&"ÉCOLE", &"école"), Ordering::Equal);
+        assert_eq!(compare_text(&"apple", &"Banana"), Ordering::Less);
+        assert_eq!(compare_ids(&"9", &"10"), Ordering::Less);
+        assert_eq!(compare_ids(&"a9", &"a10"), Ordering::Greater);
+    }
+}
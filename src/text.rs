@@ -0,0 +1,1102 @@
+//! Text normalization and comparison helpers for citation titles, journal names,
+//! volumes and issues.
+//!
+//! These are the same routines the deduplicator uses to decide whether two titles
+//! refer to the same work, exposed so that other code (search, retrieval, manual
+//! matching) can apply an identical tolerance. [`parse_volume`] and [`parse_issue`]
+//! split volume and issue strings into the number the deduplicator compares and
+//! the supplement and part markers it ignores.
+//!
+//! Normalization is deliberately lossy: case, punctuation, whitespace, inline
+//! HTML markup and escaped unicode sequences are all removed or folded, and Greek
+//! letters are transliterated to a single Latin letter.
+//!
+//! Typographic characters (curly quotes, dashes, ligatures, ellipses) are folded to
+//! their ASCII equivalents by [`fold_typography`] before any other step, whatever
+//! the script.
+//!
+//! Titles written mostly in a non-Latin script (see [`dominant_script`]) keep
+//! their letters as-is, and CJK titles are compared by character bigrams rather
+//! than edit distance.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::text::normalize_title;
+//!
+//! assert_eq!(
+//!     normalize_title("The <i>TNF-α</i> pathway: a review."),
+//!     "thetnfapathwayareview"
+//! );
+//! ```
+
+use crate::regex::Regex;
+use std::sync::LazyLock;
+
+static UNICODE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<U\+([0-9A-Fa-f]+)>").unwrap());
+
+/// Typographic characters and their ASCII equivalents, applied by [`fold_typography`].
+const TYPOGRAPHY: [(char, &str); 25] = [
+    // Quotes and primes
+    ('\u{2018}', "'"),
+    ('\u{2019}', "'"),
+    ('\u{201B}', "'"),
+    ('\u{2032}', "'"),
+    ('\u{201C}', "\""),
+    ('\u{201D}', "\""),
+    ('\u{201E}', "\""),
+    ('\u{2033}', "\""),
+    // Hyphens, dashes and the minus sign
+    ('\u{2010}', "-"),
+    ('\u{2011}', "-"),
+    ('\u{2012}', "-"),
+    ('\u{2013}', "-"),
+    ('\u{2014}', "-"),
+    ('\u{2015}', "-"),
+    ('\u{2212}', "-"),
+    // Ligatures
+    ('\u{FB00}', "ff"),
+    ('\u{FB01}', "fi"),
+    ('\u{FB02}', "fl"),
+    ('\u{FB03}', "ffi"),
+    ('\u{FB04}', "ffl"),
+    ('\u{FB05}', "st"),
+    ('\u{FB06}', "st"),
+    // Ellipsis and spaces
+    ('\u{2026}', "..."),
+    ('\u{00A0}', " "),
+    ('\u{202F}', " "),
+];
+
+/// Replacements applied to lowercased text before non-alphanumeric characters are dropped.
+const REPLACEMENTS: [(&str, &str); 46] = [
+    // Escaped and inline HTML markup
+    ("&lt;", "<"),
+    ("&gt;", ">"),
+    ("<sup>", ""),
+    ("</sup>", ""),
+    ("<sub>", ""),
+    ("</sub>", ""),
+    ("<inf>", ""),
+    ("</inf>", ""),
+    ("<i>", ""),
+    ("</i>", ""),
+    ("<b>", ""),
+    ("</b>", ""),
+    // Non-breaking and soft hyphens
+    ("\u{2011}", "-"),
+    ("\u{00ad}", ""),
+    // Spelled-out Greek letters
+    ("beta", "b"),
+    ("alpha", "a"),
+    // Greek alphabet, plus the common lookalikes for beta and mu
+    ("α", "a"),
+    ("β", "b"),
+    ("ß", "b"),
+    ("γ", "g"),
+    ("δ", "d"),
+    ("ε", "e"),
+    ("ζ", "z"),
+    ("η", "e"),
+    ("θ", "t"),
+    ("ι", "i"),
+    ("κ", "k"),
+    ("λ", "l"),
+    ("μ", "m"),
+    ("µ", "m"),
+    ("ν", "n"),
+    ("ξ", "x"),
+    ("ο", "o"),
+    ("π", "p"),
+    ("ρ", "r"),
+    ("σ", "s"),
+    ("ς", "s"),
+    ("τ", "t"),
+    ("υ", "u"),
+    ("φ", "p"),
+    ("ϕ", "p"),
+    ("χ", "c"),
+    ("ψ", "p"),
+    ("ω", "o"),
+    ("ϵ", "e"),
+    ("ϑ", "t"),
+];
+
+/// The writing system most of a title's letters belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Script {
+    /// Latin letters, including accented forms
+    Latin,
+    /// Greek letters
+    Greek,
+    /// Cyrillic letters
+    Cyrillic,
+    /// Chinese, Japanese and Korean characters
+    Cjk,
+    /// Any other alphabetic script
+    Other,
+}
+
+impl Script {
+    /// Classifies a single character, returning `None` for non-letters.
+    fn of(c: char) -> Option<Self> {
+        match c as u32 {
+            _ if c.is_ascii_alphabetic() => Some(Script::Latin),
+            _ if !c.is_alphabetic() => None,
+            0x00C0..=0x024F | 0x1E00..=0x1EFF => Some(Script::Latin),
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Some(Script::Greek),
+            0x0400..=0x052F => Some(Script::Cyrillic),
+            0x1100..=0x11FF
+            | 0x3040..=0x30FF
+            | 0x3130..=0x318F
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0x20000..=0x2FA1F => Some(Script::Cjk),
+            _ => Some(Script::Other),
+        }
+    }
+}
+
+/// Detects the script most letters of `text` are written in.
+///
+/// Digits, punctuation and markup are ignored. Text without letters, and ties,
+/// count as [`Script::Latin`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::{Script, dominant_script};
+///
+/// assert_eq!(dominant_script("TNF-α signalling"), Script::Latin);
+/// assert_eq!(dominant_script("新型冠状病毒肺炎的临床特征"), Script::Cjk);
+/// assert_eq!(dominant_script("Лечение сепсиса"), Script::Cyrillic);
+/// ```
+#[must_use]
+pub fn dominant_script(text: &str) -> Script {
+    const SCRIPTS: [Script; 5] = [
+        Script::Latin,
+        Script::Greek,
+        Script::Cyrillic,
+        Script::Cjk,
+        Script::Other,
+    ];
+
+    let mut counts = [0usize; SCRIPTS.len()];
+    for script in text.chars().filter_map(Script::of) {
+        counts[SCRIPTS.iter().position(|&s| s == script).unwrap()] += 1;
+    }
+
+    // Keep the first script on ties, so Latin wins them
+    let mut dominant = 0;
+    for (i, &count) in counts.iter().enumerate() {
+        if count > counts[dominant] {
+            dominant = i;
+        }
+    }
+    SCRIPTS[dominant]
+}
+
+/// Replaces typographic characters with their ASCII equivalents.
+///
+/// Curly quotes and primes become `'` and `"`, hyphens, en and em dashes and the
+/// minus sign become `-`, ligatures such as `ﬁ` are spelled out, an ellipsis
+/// becomes `...` and non-breaking spaces become plain spaces. Everything else is
+/// kept as it is.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::fold_typography;
+///
+/// assert_eq!(
+///     fold_typography("Alzheimer\u{2019}s disease \u{2013} a \u{FB01}eld review\u{2026}"),
+///     "Alzheimer's disease - a field review..."
+/// );
+/// ```
+#[must_use]
+pub fn fold_typography(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match TYPOGRAPHY.iter().find(|(from, _)| *from == c) {
+            Some((_, to)) => folded.push_str(to),
+            None => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// RTF control words replaced by a space in [`clean_abstract`].
+const RTF_CONTROL_WORDS: [&str; 4] = ["pard", "par", "tab", "line"];
+
+/// Removes format-specific line breaks and spacing from an abstract.
+///
+/// Line breaks of any kind become spaces: CR, LF and CRLF, the literal escape
+/// sequences `\n`, `\r` and `\r\n` some exports write instead, and the RTF
+/// control words `\par`, `\pard`, `\tab` and `\line`. Runs of whitespace are
+/// then collapsed to a single space and the ends trimmed, so an abstract has
+/// the same text whichever format it was read from.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::clean_abstract;
+///
+/// assert_eq!(
+///     clean_abstract("Background:\\par Sepsis is\r\ncommon.\\nMethods:  we   enrolled "),
+///     "Background: Sepsis is common. Methods: we enrolled"
+/// );
+/// ```
+#[must_use]
+pub fn clean_abstract(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    let mut space = false;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let skip = if c.is_whitespace() {
+            Some(c.len_utf8())
+        } else if c == '\\' {
+            escaped_break_len(&rest[1..]).map(|len| len + 1)
+        } else {
+            None
+        };
+
+        match skip {
+            Some(len) => {
+                space = true;
+                rest = &rest[len..];
+            }
+            None => {
+                if space && !cleaned.is_empty() {
+                    cleaned.push(' ');
+                }
+                space = false;
+                cleaned.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    cleaned
+}
+
+/// Returns the length of the line break escape or RTF control word at the start
+/// of `text`, which follows a backslash.
+fn escaped_break_len(text: &str) -> Option<usize> {
+    if text.starts_with("r\\n") {
+        return Some(3);
+    }
+    if text.starts_with(['n', 'r']) {
+        return Some(1);
+    }
+    RTF_CONTROL_WORDS
+        .iter()
+        .find(|word| {
+            text.starts_with(**word)
+                && !text[word.len()..].starts_with(|c: char| c.is_ascii_alphabetic())
+        })
+        .map(|word| word.len())
+}
+
+/// Share of uppercase letters above which [`is_all_caps`] treats text as shouted.
+const ALL_CAPS_SHARE: f64 = 0.9;
+
+/// Words lowercased inside a title by [`to_title_case`].
+const SMALL_WORDS: [&str; 22] = [
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "or", "per", "the", "to", "via", "vs", "with", "yet",
+];
+
+/// Acronyms with vowels kept in all-caps text, where case cannot tell them
+/// apart from ordinary words.
+const SHOUTED_ACRONYMS: [&str; 22] = [
+    "ADHD", "AIDS", "ATP", "BMI", "COPD", "COVID", "DNA", "ECG", "EEG", "EU", "HIV", "HPV",
+    "ICU", "IVF", "MERS", "MRI", "NICU", "OECD", "RNA", "SARS", "UK", "USA",
+];
+
+/// Whether more than 90% of the cased letters in `text` are uppercase.
+///
+/// Text without any cased letter is not all caps.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::is_all_caps;
+///
+/// assert!(is_all_caps("DNA REPAIR IN E. COLI: A REVIEW"));
+/// assert!(!is_all_caps("DNA repair in E. coli"));
+/// assert!(!is_all_caps("2023"));
+/// ```
+#[must_use]
+pub fn is_all_caps(text: &str) -> bool {
+    let (upper, cased) = text
+        .chars()
+        .filter(|c| c.is_uppercase() || c.is_lowercase())
+        .fold((0, 0), |(upper, cased), c| {
+            (upper + usize::from(c.is_uppercase()), cased + 1)
+        });
+    cased > 0 && upper as f64 > ALL_CAPS_SHARE * cased as f64
+}
+
+/// Converts a title to sentence case.
+///
+/// Every word is lowercased except the first word of the title and the first
+/// word after a colon, question mark or exclamation mark, which are
+/// capitalized, and acronyms, which are kept as they are. An acronym is a
+/// word that contains a digit, a single capital letter other than "A" such as
+/// the initial in "E. coli", or a word with capitals after its first letter.
+/// In mixed-case text that includes all-caps words of up to five letters; in
+/// all-caps text, see [`is_all_caps`], only words without vowels and a short
+/// list of well-known acronyms such as "HIV" and "COVID" are kept. The parts
+/// of hyphenated words are treated separately.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::to_sentence_case;
+///
+/// assert_eq!(
+///     to_sentence_case("DNA REPAIR IN E. COLI: A REVIEW"),
+///     "DNA repair in E. coli: A review"
+/// );
+/// assert_eq!(
+///     to_sentence_case("The Role of mRNA Vaccines in COVID-19"),
+///     "The role of mRNA vaccines in COVID-19"
+/// );
+/// ```
+#[must_use]
+pub fn to_sentence_case(text: &str) -> String {
+    recase(text, false)
+}
+
+/// Converts a title to title case.
+///
+/// Words are capitalized except small words such as "of", "the" and "and",
+/// which are lowercased unless they are the first or last word of the title
+/// or follow a colon, question mark or exclamation mark. Each part of a
+/// hyphenated word is cased on its own, and acronyms are kept as described
+/// for [`to_sentence_case`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::to_title_case;
+///
+/// assert_eq!(
+///     to_title_case("the burden of out-of-hospital cardiac arrest: a review"),
+///     "The Burden of Out-of-Hospital Cardiac Arrest: A Review"
+/// );
+/// assert_eq!(to_title_case("DNA REPAIR IN E. COLI"), "DNA Repair in E. Coli");
+/// ```
+#[must_use]
+pub fn to_title_case(text: &str) -> String {
+    recase(text, true)
+}
+
+/// Shared implementation of [`to_sentence_case`] and [`to_title_case`].
+fn recase(text: &str, title_case: bool) -> String {
+    let shouted = is_all_caps(text);
+    let words: Vec<&str> = text.split_inclusive(char::is_whitespace).collect();
+    let last = words
+        .iter()
+        .rposition(|word| !word.trim().is_empty())
+        .unwrap_or(0);
+
+    let mut recased = String::with_capacity(text.len());
+    let mut starts_sentence = true;
+    for (index, token) in words.iter().enumerate() {
+        let word = token.trim_end_matches(char::is_whitespace);
+        if word.is_empty() {
+            recased.push_str(token);
+            continue;
+        }
+
+        let parts: Vec<&str> = word.split('-').collect();
+        for (part_index, part) in parts.iter().enumerate() {
+            if part_index > 0 {
+                recased.push('-');
+            }
+            let before_number = parts
+                .get(part_index + 1)
+                .is_some_and(|next| next.starts_with(|c: char| c.is_ascii_digit()));
+            if is_acronym(part, shouted, before_number) {
+                recased.push_str(part);
+                continue;
+            }
+
+            let lower = part.to_lowercase();
+            let capitalize = if title_case {
+                let boundary = (starts_sentence && part_index == 0)
+                    || (index == last && part_index == parts.len() - 1);
+                let bare = lower.trim_matches(|c: char| !c.is_alphanumeric());
+                boundary || !SMALL_WORDS.contains(&bare)
+            } else {
+                starts_sentence && part_index == 0
+            };
+            if capitalize {
+                recased.push_str(&capitalize_first(&lower));
+            } else {
+                recased.push_str(&lower);
+            }
+        }
+        recased.push_str(&token[word.len()..]);
+        starts_sentence = word.ends_with([':', '?', '!']);
+    }
+    recased
+}
+
+/// Whether a word keeps its case in [`to_sentence_case`] and [`to_title_case`].
+///
+/// `shouted` is set for words of an all-caps title, and `before_number` for
+/// the part of a hyphenated word that precedes a number, as in "IL-6".
+fn is_acronym(word: &str, shouted: bool, before_number: bool) -> bool {
+    let letters: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.is_empty() || word.chars().any(|c| c.is_ascii_digit()) {
+        return true;
+    }
+
+    let upper = letters.iter().filter(|c| c.is_uppercase()).count();
+    if letters.len() == 1 {
+        return upper == 1 && letters[0] != 'A';
+    }
+    if upper < letters.len() {
+        // Inner capitals, as in "mRNA" and "IgG"
+        return !shouted && letters[1..].iter().any(|c| c.is_uppercase());
+    }
+    if shouted {
+        let letters: String = letters.into_iter().collect();
+        (before_number && letters.len() <= 5)
+            || !letters.chars().any(|c| "AEIOUY".contains(c))
+            || SHOUTED_ACRONYMS.contains(&letters.as_str())
+    } else {
+        letters.len() <= 5
+    }
+}
+
+/// Uppercases the first letter of `word`.
+fn capitalize_first(word: &str) -> String {
+    match word.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((start, c)) => {
+            let mut capitalized = String::with_capacity(word.len());
+            capitalized.push_str(&word[..start]);
+            capitalized.extend(c.to_uppercase());
+            capitalized.push_str(&word[start + c.len_utf8()..]);
+            capitalized
+        }
+        None => word.to_string(),
+    }
+}
+
+/// Normalizes a title for comparison.
+///
+/// Typographic characters are folded with [`fold_typography`], escaped unicode
+/// sequences such as `<U+03B1>` are decoded, the text is lowercased,
+/// HTML markup and Greek letters are folded, and everything that is not alphanumeric
+/// is removed. Markup and Greek letters are only folded in titles whose
+/// [`dominant_script`] is Latin; other titles keep every letter.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::normalize_title;
+///
+/// assert_eq!(
+///     normalize_title("Machine Learning! (2<sup>nd</sup> Edition)"),
+///     "machinelearning2ndedition"
+/// );
+/// assert_eq!(normalize_title("<U+03B2>-blockers"), "bblockers");
+/// assert_eq!(normalize_title("Θεραπεία της σήψης"), "θεραπείατηςσήψης");
+/// ```
+#[must_use]
+pub fn normalize_title(title: &str) -> String {
+    normalize_plain_ascii(title).unwrap_or_else(|| normalize_marked_up(title))
+}
+
+/// Normalizes an ASCII title in a single pass when no step of [`normalize_title`]
+/// but dropping punctuation and case applies to it.
+///
+/// Returns `None` for non-ASCII text, for `<` and `&`, which start markup and
+/// escapes, and for spelled-out "alpha" and "beta", in any case.
+fn normalize_plain_ascii(title: &str) -> Option<String> {
+    let bytes = title.as_bytes();
+    let mut normalized = String::with_capacity(bytes.len());
+    for (i, &byte) in bytes.iter().enumerate() {
+        let rest = &bytes[i..];
+        let spells = |word: &[u8]| {
+            rest.len() >= word.len() && rest[..word.len()].eq_ignore_ascii_case(word)
+        };
+        match byte {
+            b'<' | b'&' => return None,
+            b'a' | b'A' if spells(b"alpha") => return None,
+            b'b' | b'B' if spells(b"beta") => return None,
+            _ if byte.is_ascii_alphanumeric() => {
+                normalized.push(byte.to_ascii_lowercase() as char);
+            }
+            _ if !byte.is_ascii() => return None,
+            _ => {}
+        }
+    }
+    Some(normalized)
+}
+
+/// The full [`normalize_title`], with escapes, markup and Greek letters.
+fn normalize_marked_up(title: &str) -> String {
+    let mut s = convert_unicode_escapes(&fold_typography(title.trim())).to_lowercase();
+
+    if dominant_script(&s) == Script::Latin {
+        for (from, to) in REPLACEMENTS.iter() {
+            if s.contains(from) {
+                s = s.replace(from, to);
+            }
+        }
+    }
+
+    s.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+/// Normalizes a journal name the way the deduplicator compares journals.
+///
+/// The conference name that Embase appends after ". Conference" is dropped,
+/// along with a leading "The", case and everything that is not alphanumeric.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::normalize_journal;
+///
+/// assert_eq!(normalize_journal("The Lancet"), "lancet");
+/// assert_eq!(normalize_journal("LANCET."), "lancet");
+/// assert_eq!(
+///     normalize_journal("Heart. Conference: British Cardiovascular Society"),
+///     "heart"
+/// );
+/// ```
+#[must_use]
+pub fn normalize_journal(name: &str) -> String {
+    let name = name.split(". Conference").next().unwrap_or(name).trim().to_lowercase();
+    let name = name.strip_prefix("the ").unwrap_or(&name);
+    name.chars().filter(|c| c.is_alphanumeric()).collect()
+}
+
+/// Computes the similarity of two titles after normalization.
+///
+/// Returns the Jaro-Winkler similarity of the normalized titles, in the range
+/// `0.0` (nothing in common) to `1.0` (identical once normalized). Two CJK titles
+/// are compared with the Sørensen-Dice coefficient of their character bigrams
+/// instead, and a CJK title never matches a title in another script (`0.0`).
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::title_similarity;
+///
+/// let score = title_similarity(
+///     "Effects of β-blockers on heart failure",
+///     "Effects of beta-blockers on heart failure.",
+/// );
+/// assert_eq!(score, 1.0);
+/// ```
+#[cfg(feature = "dedupe")]
+#[must_use]
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize_title(a), normalize_title(b));
+    script_aware_similarity(&a, dominant_script(&a), &b, dominant_script(&b), strsim::jaro_winkler)
+        .unwrap_or(0.0)
+}
+
+/// Compares two normalized titles with `metric`, or by character bigrams when both
+/// are CJK.
+///
+/// Returns `None` when exactly one of the titles is CJK, as the two cannot be
+/// meaningfully compared.
+#[cfg(feature = "dedupe")]
+pub(crate) fn script_aware_similarity(
+    a: &str,
+    a_script: Script,
+    b: &str,
+    b_script: Script,
+    metric: fn(&str, &str) -> f64,
+) -> Option<f64> {
+    match (a_script == Script::Cjk, b_script == Script::Cjk) {
+        (true, true) => Some(strsim::sorensen_dice(a, b)),
+        (false, false) => Some(metric(a, b)),
+        _ => None,
+    }
+}
+
+/// A volume or issue string split into its number and supplement or part markers.
+///
+/// Returned by [`parse_volume`] and [`parse_issue`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VolumeInfo {
+    /// The first number outside the markers, e.g. `"61"` in `"61 Suppl 1"`
+    pub number: Option<String>,
+    /// The supplement after a "Suppl" or "Supplement" marker, e.g. `"1"`; empty
+    /// when the marker has no number of its own
+    pub supplement: Option<String>,
+    /// The part after a "Pt" or "Part" marker, e.g. `"2"` in `"4 Pt 2"`; empty
+    /// when the marker has no number of its own
+    pub part: Option<String>,
+    /// The string as given
+    pub raw: String,
+}
+
+impl VolumeInfo {
+    /// Returns `true` when the string names a supplement.
+    #[must_use]
+    pub fn is_supplement(&self) -> bool {
+        self.supplement.is_some()
+    }
+}
+
+/// Parses a volume string such as `"61 Suppl 1"` or `"Part A. 242"`.
+///
+/// "Suppl", "Supplement", "Pt" and "Part" markers are recognized in any case, also
+/// when written together with their number (`"Suppl1"`). The number the marker
+/// applies to, a digit or a single letter, is kept as the supplement or part. The
+/// first remaining number is the volume number, with trailing letters dropped
+/// (`"161A"` is `"161"`); the deduplicator compares volumes by this number only.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::parse_volume;
+///
+/// let volume = parse_volume("55 (10 SUPPL 1)");
+/// assert_eq!(volume.number.as_deref(), Some("55"));
+/// assert_eq!(volume.supplement.as_deref(), Some("1"));
+/// assert!(volume.is_supplement());
+///
+/// let volume = parse_volume("Part A. 242");
+/// assert_eq!(volume.number.as_deref(), Some("242"));
+/// assert_eq!(volume.part.as_deref(), Some("A"));
+/// ```
+#[must_use]
+pub fn parse_volume(volume: &str) -> VolumeInfo {
+    let mut info = VolumeInfo {
+        raw: volume.to_string(),
+        ..Default::default()
+    };
+
+    let mut tokens = volume
+        .split(|c: char| {
+            c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | ',' | '.' | ':' | ';')
+        })
+        .filter(|token| !token.is_empty())
+        .peekable();
+
+    while let Some(token) = tokens.next() {
+        if let Some((marker, rest)) = split_marker(token) {
+            let value = if rest.is_empty() {
+                tokens
+                    .next_if(|next| split_marker(next).is_none() && is_marker_value(next))
+                    .unwrap_or_default()
+            } else {
+                rest
+            };
+            let slot = match marker {
+                Marker::Supplement => &mut info.supplement,
+                Marker::Part => &mut info.part,
+            };
+            slot.get_or_insert_with(|| value.to_string());
+        } else if info.number.is_none() {
+            let digits: String = token
+                .chars()
+                .skip_while(|c| !c.is_ascii_digit())
+                .take_while(char::is_ascii_digit)
+                .collect();
+            if !digits.is_empty() {
+                info.number = Some(digits);
+            }
+        }
+    }
+
+    info
+}
+
+/// Parses an issue string such as `"4 Pt 2"` or `"Suppl 1"`.
+///
+/// Issues use the same markers as volumes; see [`parse_volume`].
+///
+/// # Examples
+///
+/// ```
+/// use biblib::text::parse_issue;
+///
+/// let issue = parse_issue("4 Pt 2");
+/// assert_eq!(issue.number.as_deref(), Some("4"));
+/// assert_eq!(issue.part.as_deref(), Some("2"));
+///
+/// let issue = parse_issue("Suppl 1");
+/// assert_eq!(issue.number, None);
+/// assert_eq!(issue.supplement.as_deref(), Some("1"));
+/// ```
+#[must_use]
+pub fn parse_issue(issue: &str) -> VolumeInfo {
+    parse_volume(issue)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Marker {
+    Supplement,
+    Part,
+}
+
+/// Splits a supplement or part marker from the start of a token, returning the
+/// marker and any number written together with it.
+fn split_marker(token: &str) -> Option<(Marker, &str)> {
+    let lower = token.to_ascii_lowercase();
+    let (marker, len) = if lower.starts_with("supplement") {
+        (Marker::Supplement, "supplement".len())
+    } else if lower.starts_with("suppl") {
+        (Marker::Supplement, "suppl".len())
+    } else if lower.starts_with("part") {
+        (Marker::Part, "part".len())
+    } else if lower.starts_with("pt") {
+        (Marker::Part, "pt".len())
+    } else {
+        return None;
+    };
+
+    let rest = token[len..].trim_start_matches(|c: char| !c.is_alphanumeric());
+    // "Parts", "Ptosis" and the like are words, not markers
+    (rest.is_empty() || rest.chars().all(|c| c.is_ascii_digit())).then_some((marker, rest))
+}
+
+/// Returns `true` for tokens that can follow a marker as its number or letter.
+fn is_marker_value(token: &str) -> bool {
+    token.chars().any(|c| c.is_ascii_digit())
+        || (token.chars().count() == 1 && token.chars().all(char::is_alphabetic))
+}
+
+/// Decodes `<U+XXXX>` escape sequences, leaving invalid ones untouched.
+fn convert_unicode_escapes(input: &str) -> String {
+    UNICODE_REGEX
+        .replace_all(input, |caps: &crate::regex::Captures| {
+            u32::from_str_radix(&caps[1], 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "Machine Learning! (2<sup>nd</sup> Edition)",
+        "machinelearning2ndedition"
+    )]
+    #[case("[&lt;sup&gt;11&lt;/sup&gt;C] benzo", "11cbenzo")]
+    #[case("The <i>E. coli</i> genome", "theecoligenome")]
+    #[case("&lt;b&gt;Bold&lt;/b&gt; claims", "boldclaims")]
+    #[case("Non\u{2011}breaking hyphen", "nonbreakinghyphen")]
+    #[case("Soft\u{00ad}hyphen", "softhyphen")]
+    #[case("TNF-α and IL-1β", "tnfaandil1b")]
+    #[case("TNF-alpha and IL-1beta", "tnfaandil1b")]
+    #[case(
+        "All twenty four Greek letters ΑΒΓΔΕΖΗΘΙΚΛΜΝΞΟΠΡΣΤΥΦΧΨΩ in one title",
+        "alltwentyfourgreeklettersabgdezetiklmnxoprstupcpoinonetitle"
+    )]
+    #[case(
+        "All twenty four Greek letters αβγδεζηθικλμνξοπρστυφχψω in one title",
+        "alltwentyfourgreeklettersabgdezetiklmnxoprstupcpoinonetitle"
+    )]
+    #[case("Θεραπεία της σήψης", "θεραπείατηςσήψης")]
+    #[case("Лечение <i>сепсиса</i>", "лечениеiсепсисаi")]
+    #[case("新型冠状病毒肺炎的临床特征", "新型冠状病毒肺炎的临床特征")]
+    #[case("10 µg doses", "10mgdoses")]
+    #[case("2<U+0391>-amino", "2aamino")]
+    #[case("  Already normal  ", "alreadynormal")]
+    #[case("", "")]
+    fn test_normalize_title(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(normalize_title(input), expected);
+    }
+
+    /// A xorshift generator, so the random titles are the same on every run.
+    fn random_titles(count: usize, alphabet: &[u8]) -> Vec<String> {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..count)
+            .map(|_| {
+                let len = (next() % 40) as usize;
+                (0..len)
+                    .map(|_| alphabet[(next() % alphabet.len() as u64) as usize] as char)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii_fast_path_matches_full_normalization() {
+        let all_ascii: Vec<u8> = (0..128).collect();
+        // Letters of the spelled-out Greek letters and markup, so they come up often
+        let markers = b"alphbetALPHBET<>&;/isupbgtU+03B9 -.";
+        let mut fast = 0;
+        for alphabet in [&all_ascii[..], &markers[..]] {
+            for title in random_titles(20_000, alphabet) {
+                if let Some(normalized) = normalize_plain_ascii(&title) {
+                    assert_eq!(normalized, normalize_marked_up(&title), "{title:?}");
+                    fast += 1;
+                }
+                assert_eq!(normalize_title(&title), normalize_marked_up(&title), "{title:?}");
+            }
+        }
+        assert!(fast > 1_000);
+    }
+
+    #[rstest]
+    #[case("TNF-Alpha levels")]
+    #[case("IL-1BETA")]
+    #[case("a &amp; b")]
+    #[case("2<U+0391>-amino")]
+    #[case("café")]
+    fn test_ascii_fast_path_defers_to_full_normalization(#[case] input: &str) {
+        assert_eq!(normalize_plain_ascii(input), None);
+    }
+
+    #[rstest]
+    #[case("DNA REPAIR IN E. COLI: A REVIEW", "DNA repair in E. coli: A review")]
+    #[case(
+        "EFFECT OF HIV ON ICU OUTCOMES IN THE USA",
+        "Effect of HIV on ICU outcomes in the USA"
+    )]
+    #[case(
+        "COVID-19-ASSOCIATED COAGULOPATHY: WHAT DO WE KNOW?",
+        "COVID-19-associated coagulopathy: What do we know?"
+    )]
+    #[case("IL-6 AND TNF LEVELS IN SEPSIS", "IL-6 and TNF levels in sepsis")]
+    #[case("VITAMIN D AND H1N1 INFLUENZA", "Vitamin D and H1N1 influenza")]
+    #[case(
+        "The Role of mRNA Vaccines in COVID-19",
+        "The role of mRNA vaccines in COVID-19"
+    )]
+    #[case(
+        "Machine Learning for NLP: A Systematic Review",
+        "Machine learning for NLP: A systematic review"
+    )]
+    #[case("IgG Levels After Vaccination", "IgG levels after vaccination")]
+    #[case("Does It Work? Yes!", "Does it work? Yes!")]
+    #[case("A Study of Out-Of-Hospital Arrest", "A study of out-of-hospital arrest")]
+    #[case("(SEPSIS) IN  CHILDREN", "(Sepsis) in  children")]
+    #[case("already in sentence case", "Already in sentence case")]
+    #[case("", "")]
+    fn test_to_sentence_case(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(to_sentence_case(input), expected);
+    }
+
+    #[rstest]
+    #[case("DNA REPAIR IN E. COLI: A REVIEW", "DNA Repair in E. Coli: A Review")]
+    #[case(
+        "the burden of out-of-hospital cardiac arrest",
+        "The Burden of Out-of-Hospital Cardiac Arrest"
+    )]
+    #[case("a review of the evidence", "A Review of the Evidence")]
+    #[case("what are they looking for", "What Are They Looking For")]
+    #[case("sepsis: the silent killer", "Sepsis: The Silent Killer")]
+    #[case("follow-up of mRNA vaccine trials", "Follow-Up of mRNA Vaccine Trials")]
+    #[case("RISK OF COPD WITH E-CIGARETTES", "Risk of COPD with E-Cigarettes")]
+    #[case("", "")]
+    fn test_to_title_case(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(to_title_case(input), expected);
+    }
+
+    #[rstest]
+    #[case("DNA REPAIR IN E. COLI: A REVIEW", true)]
+    #[case("A TITLE WITH ONE mRNA", true)]
+    #[case("DNA Repair In E. Coli", false)]
+    #[case("dna repair", false)]
+    #[case("2023 - 45", false)]
+    #[case("", false)]
+    fn test_is_all_caps(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(is_all_caps(input), expected);
+    }
+
+    #[rstest]
+    #[case("", Script::Latin)]
+    #[case("2023: 45-67", Script::Latin)]
+    #[case("TNF-α and IL-1β", Script::Latin)]
+    #[case("Café au lait", Script::Latin)]
+    #[case("Θεραπεία της σήψης", Script::Greek)]
+    #[case("Лечение сепсиса у детей", Script::Cyrillic)]
+    #[case("COVID-19患者的临床特征", Script::Cjk)]
+    #[case("ひらがなとカタカナ", Script::Cjk)]
+    #[case("한국어 제목", Script::Cjk)]
+    #[case("עברית", Script::Other)]
+    fn test_dominant_script(#[case] input: &str, #[case] expected: Script) {
+        assert_eq!(dominant_script(input), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "dedupe")]
+    fn test_title_similarity_cjk() {
+        let title = "新型冠状病毒肺炎患者的临床特征分析";
+        assert_eq!(title_similarity(title, "新型冠状病毒肺炎患者的临床特征分析。"), 1.0);
+
+        // Different studies sharing common characters are far apart
+        let other = "糖尿病患者的血糖控制与并发症分析";
+        assert!(title_similarity(title, other) < 0.5);
+
+        // A CJK title never matches a Latin one
+        assert_eq!(
+            title_similarity(title, "Clinical characteristics of COVID-19 pneumonia"),
+            0.0
+        );
+    }
+
+    #[rstest]
+    #[case("Plain abstract.", "Plain abstract.")]
+    #[case("  Line one\nline two\r\nline three\rend  ", "Line one line two line three end")]
+    #[case("Escaped\\nbreaks\\r\\nhere", "Escaped breaks here")]
+    #[case("RTF\\par remnants\\tab and\\pard\\line more", "RTF remnants and more")]
+    #[case("Keeps \\partial and C:\\temp", "Keeps \\partial and C:\\temp")]
+    #[case("Self-assembled  structures\t are\u{A0}important", "Self-assembled structures are important")]
+    #[case(" \\par \n ", "")]
+    fn test_clean_abstract(#[case] text: &str, #[case] cleaned: &str) {
+        assert_eq!(clean_abstract(text), cleaned);
+    }
+
+    #[test]
+    fn test_fold_typography() {
+        assert_eq!(fold_typography("plain ASCII"), "plain ASCII");
+        assert_eq!(
+            fold_typography("\u{201C}Quoted\u{201D} \u{2014} pre\u{2011}op \u{2212}5"),
+            "\"Quoted\" - pre-op -5"
+        );
+        assert_eq!(fold_typography("e\u{FB03}cacy of \u{FB02}uids"), "efficacy of fluids");
+        assert_eq!(fold_typography("Θεραπεία\u{2026}"), "Θεραπεία...");
+    }
+
+    #[test]
+    #[cfg(feature = "dedupe")]
+    fn test_title_similarity() {
+        assert_eq!(
+            title_similarity("Machine Learning Basics", "machine learning basics."),
+            1.0
+        );
+        assert_eq!(
+            title_similarity("<i>In vivo</i> imaging", "In vivo imaging"),
+            1.0
+        );
+
+        let close = title_similarity(
+            "Cardiovascular outcomes of SGLT2 inhibitors",
+            "Cardiovascular outcome of SGLT2 inhibitors",
+        );
+        assert!(close > 0.95 && close < 1.0);
+
+        let far = title_similarity("Machine learning basics", "Deep sea ecology");
+        assert!(far < 0.7);
+
+        assert_eq!(title_similarity("", ""), 1.0);
+
+        assert_eq!(
+            title_similarity(
+                "Alzheimer\u{2019}s disease \u{2013} a review",
+                "Alzheimer's disease - a review"
+            ),
+            1.0
+        );
+        assert_eq!(
+            title_similarity("\u{FB01}brosis and e\u{FB03}cacy", "fibrosis and efficacy"),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_convert_unicode_escapes() {
+        // Test basic conversion
+        assert_eq!(
+            convert_unicode_escapes("2<U+0391>-amino-4<U+0391>"),
+            "2Α-amino-4Α",
+            "Failed to convert basic Alpha Unicode sequences"
+        );
+
+        // Test multiple different Unicode sequences
+        assert_eq!(
+            convert_unicode_escapes("Hello <U+03A9>orld <U+03A3>cience"),
+            "Hello Ωorld Σcience",
+            "Failed to convert multiple Unicode sequences"
+        );
+
+        // Test string with no Unicode sequences
+        assert_eq!(
+            convert_unicode_escapes("Normal String"),
+            "Normal String",
+            "Incorrectly modified string with no Unicode sequences"
+        );
+
+        // Test empty string
+        assert_eq!(
+            convert_unicode_escapes(""),
+            "",
+            "Failed to handle empty string"
+        );
+
+        // Test mixed content
+        assert_eq!(
+            convert_unicode_escapes("Mixed <U+0394> Unicode <U+03A9> Test"),
+            "Mixed Δ Unicode Ω Test",
+            "Failed to handle mixed content with Unicode sequences"
+        );
+
+        // Test consecutive Unicode sequences
+        assert_eq!(
+            convert_unicode_escapes("<U+0391><U+0392><U+0393>"),
+            "ΑΒΓ",
+            "Failed to convert consecutive Unicode sequences"
+        );
+
+        // Invalid code points are left as-is
+        assert_eq!(convert_unicode_escapes("<U+D800>"), "<U+D800>");
+    }
+
+    #[rstest]
+    #[case("61", Some("61"), None, None)]
+    #[case("61 (Supplement 1)", Some("61"), Some("1"), None)]
+    #[case("9 (8) (no pagination)", Some("9"), None, None)]
+    #[case("3)", Some("3"), None, None)]
+    #[case("Part A. 242", Some("242"), None, Some("A"))]
+    #[case("55 (10 SUPPL 1)", Some("55"), Some("1"), None)]
+    #[case("161A", Some("161"), None, None)]
+    #[case("74 Suppl 1", Some("74"), Some("1"), None)]
+    #[case("20 (2)", Some("20"), None, None)]
+    #[case("9 (FEB) (no pagination)", Some("9"), None, None)]
+    #[case("12 Suppl", Some("12"), Some(""), None)]
+    #[case("Suppl1", None, Some("1"), None)]
+    #[case("30 Suppl 2 Pt 1", Some("30"), Some("2"), Some("1"))]
+    #[case("4 Pt 2", Some("4"), None, Some("2"))]
+    #[case("Ptosis 7", Some("7"), None, None)]
+    #[case("", None, None, None)]
+    fn test_parse_volume(
+        #[case] input: &str,
+        #[case] number: Option<&str>,
+        #[case] supplement: Option<&str>,
+        #[case] part: Option<&str>,
+    ) {
+        let info = parse_volume(input);
+        assert_eq!(info.number.as_deref(), number);
+        assert_eq!(info.supplement.as_deref(), supplement);
+        assert_eq!(info.part.as_deref(), part);
+        assert_eq!(info.raw, input);
+        assert_eq!(parse_issue(input), info);
+    }
+}
@@ -0,0 +1,383 @@
+//! Dataset-wide clean-up of [`Citation::extra_fields`].
+//!
+//! Files from different databases keep the same datum under different extra
+//! field keys, e.g. an accession number under "AN", "UT" or "Accession
+//! Number". [`remap_extra_fields`] renames such keys to one, and
+//! [`promote_extra_field`] moves the values of a key into a standard field.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::transform::{FieldTarget, PromotePolicy, promote_extra_field, remap_extra_fields};
+//! use biblib::{ACCESSION_FIELD, Citation};
+//! use std::collections::HashMap;
+//!
+//! let mut citations = vec![Citation::new(), Citation::new()];
+//! citations[0].extra_fields.insert("UT".to_string(), vec!["WOS:000123456700001".to_string()]);
+//! citations[1].extra_fields.insert("DOI".to_string(), vec!["https://doi.org/10.1000/XYZ".to_string()]);
+//!
+//! let mapping = HashMap::from([("UT".to_string(), ACCESSION_FIELD.to_string())]);
+//! assert_eq!(remap_extra_fields(&mut citations, &mapping), 1);
+//! assert_eq!(citations[0].extra_fields[ACCESSION_FIELD], vec!["WOS:000123456700001"]);
+//!
+//! assert_eq!(promote_extra_field(&mut citations, "DOI", FieldTarget::Doi, PromotePolicy::Skip), 1);
+//! assert_eq!(citations[1].doi.as_deref(), Some("10.1000/xyz"));
+//! assert!(citations[1].extra_fields.is_empty());
+//! ```
+
+use crate::Citation;
+use std::collections::HashMap;
+
+/// A standard field that [`promote_extra_field`] can move values into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldTarget {
+    /// `doi`, normalized with the same rules as the parsers; values that are not
+    /// DOIs are left in place
+    Doi,
+    /// `pmid`; values other than a number of 1 to 8 digits, with an optional
+    /// "PMID:" prefix, are left in place
+    Pmid,
+    /// `pmc_id`, with a "PMC" prefix added to bare numbers; other values are
+    /// left in place
+    PmcId,
+    /// `keywords`, with values split on semicolons
+    Keywords,
+    /// `notes`, with separate values joined by a blank line
+    Notes,
+    /// Accession numbers under [`crate::ACCESSION_FIELD`]
+    Accession,
+}
+
+/// What [`promote_extra_field`] does for a citation whose target field already
+/// has a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromotePolicy {
+    /// Keep the existing value, and the extra field as it is
+    Skip,
+    /// Replace the existing value
+    Overwrite,
+    /// Add the values to list fields (keywords and accession numbers) and to the
+    /// notes; identifiers keep their existing value, as with `Skip`
+    AppendForLists,
+}
+
+/// Renames extra field keys of every citation, from each key of `mapping` to
+/// its value.
+///
+/// Values of a key renamed onto one that already exists are added after the
+/// existing values, skipping exact repeats. Keys are renamed once, so mapping
+/// "a" to "b" and "b" to "c" moves the values of "a" to "b" and those of "b" to
+/// "c". Returns the number of keys renamed.
+pub fn remap_extra_fields(citations: &mut [Citation], mapping: &HashMap<String, String>) -> usize {
+    let mut renamed = 0;
+    for citation in citations {
+        let moved: Vec<(&String, Vec<String>)> = mapping
+            .iter()
+            .filter(|(from, to)| from != to)
+            .filter_map(|(from, to)| Some((to, citation.extra_fields.remove(from)?)))
+            .collect();
+        renamed += moved.len();
+        for (to, values) in moved {
+            extend_missing(citation.extra_fields.entry(to.clone()).or_default(), values);
+        }
+    }
+    renamed
+}
+
+/// Moves the values of the extra field `key` of every citation into `target`.
+///
+/// Values are normalized for the target first; those that do not fit it, such
+/// as a value that is not a DOI, stay under `key`, as do values after the first
+/// for single-valued fields. For a citation whose target already has a value,
+/// `policy` decides whether the values are moved. The key is removed once none
+/// of its values are left. Returns the number of citations changed.
+pub fn promote_extra_field(
+    citations: &mut [Citation],
+    key: &str,
+    target: FieldTarget,
+    policy: PromotePolicy,
+) -> usize {
+    if target == FieldTarget::Accession && key == crate::ACCESSION_FIELD {
+        return 0;
+    }
+
+    let mut changed = 0;
+    for citation in citations {
+        let Some(values) = citation.extra_fields.get(key) else {
+            continue;
+        };
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+        for value in values {
+            match normalize(target, value) {
+                Some(normalized) => accepted.extend(normalized),
+                None => rejected.push(value.clone()),
+            }
+        }
+        if accepted.is_empty() {
+            continue;
+        }
+
+        let populated = is_populated(citation, target);
+        let append = populated && policy == PromotePolicy::AppendForLists;
+        if populated && !append && policy != PromotePolicy::Overwrite {
+            continue;
+        }
+        if append && matches!(target, FieldTarget::Doi | FieldTarget::Pmid | FieldTarget::PmcId) {
+            continue;
+        }
+
+        let mut left = rejected;
+        match target {
+            FieldTarget::Doi | FieldTarget::Pmid | FieldTarget::PmcId => {
+                left.extend(accepted.drain(1..));
+                let value = accepted.pop();
+                match target {
+                    FieldTarget::Doi => citation.doi = value,
+                    FieldTarget::Pmid => citation.pmid = value,
+                    _ => citation.pmc_id = value,
+                }
+            }
+            FieldTarget::Keywords => {
+                if !append {
+                    citation.keywords.clear();
+                }
+                extend_missing(&mut citation.keywords, accepted);
+            }
+            FieldTarget::Notes => {
+                let notes = accepted.join("\n\n");
+                citation.notes = match citation.notes.take().filter(|_| append) {
+                    Some(existing) => Some(format!("{}\n\n{}", existing, notes)),
+                    None => Some(notes),
+                };
+            }
+            FieldTarget::Accession => {
+                let existing = citation
+                    .extra_fields
+                    .entry(crate::ACCESSION_FIELD.to_string())
+                    .or_default();
+                if !append {
+                    existing.clear();
+                }
+                extend_missing(existing, accepted);
+            }
+        }
+
+        if left.is_empty() {
+            citation.extra_fields.remove(key);
+        } else {
+            citation.extra_fields.insert(key.to_string(), left);
+        }
+        changed += 1;
+    }
+    changed
+}
+
+/// Normalizes an extra field value for `target`, returning `None` when it does
+/// not fit the target. Keywords may split into several values.
+fn normalize(target: FieldTarget, value: &str) -> Option<Vec<String>> {
+    let value = value.trim();
+    let normalized = match target {
+        FieldTarget::Doi => crate::utils::format_doi(value)?,
+        FieldTarget::Pmid => {
+            let pmid = value
+                .get(..5)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("PMID:"))
+                .map_or(value, |_| value[5..].trim_start());
+            let is_pmid = (1..=8).contains(&pmid.len()) && pmid.bytes().all(|b| b.is_ascii_digit());
+            is_pmid.then(|| pmid.to_string())?
+        }
+        FieldTarget::PmcId => {
+            let digits = value
+                .get(..3)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("PMC"))
+                .map_or(value, |_| &value[3..]);
+            let is_pmc = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+            is_pmc.then(|| format!("PMC{}", digits))?
+        }
+        FieldTarget::Keywords => {
+            let keywords: Vec<String> = value
+                .split(';')
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .map(String::from)
+                .collect();
+            return (!keywords.is_empty()).then_some(keywords);
+        }
+        FieldTarget::Notes | FieldTarget::Accession if !value.is_empty() => value.to_string(),
+        FieldTarget::Notes | FieldTarget::Accession => return None,
+    };
+    Some(vec![normalized])
+}
+
+/// Whether the target field of a citation already has a value.
+fn is_populated(citation: &Citation, target: FieldTarget) -> bool {
+    match target {
+        FieldTarget::Doi => citation.doi.is_some(),
+        FieldTarget::Pmid => citation.pmid.is_some(),
+        FieldTarget::PmcId => citation.pmc_id.is_some(),
+        FieldTarget::Keywords => !citation.keywords.is_empty(),
+        FieldTarget::Notes => citation.notes.is_some(),
+        FieldTarget::Accession => citation
+            .extra_fields
+            .get(crate::ACCESSION_FIELD)
+            .is_some_and(|values| !values.is_empty()),
+    }
+}
+
+/// Appends the values not already in `existing`.
+fn extend_missing(existing: &mut Vec<String>, values: Vec<String>) {
+    for value in values {
+        if !existing.contains(&value) {
+            existing.push(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ACCESSION_FIELD;
+    use pretty_assertions::assert_eq;
+
+    fn with_extra(fields: &[(&str, &[&str])]) -> Citation {
+        let mut citation = Citation::new();
+        for (key, values) in fields {
+            citation
+                .extra_fields
+                .insert(key.to_string(), values.iter().map(|v| v.to_string()).collect());
+        }
+        citation
+    }
+
+    /// Records as read from Web of Science, Embase, Scopus and a hand-made CSV.
+    fn mixed_dataset() -> Vec<Citation> {
+        vec![
+            with_extra(&[("UT", &["WOS:000123456700001"])]),
+            with_extra(&[("AN", &["L636118453"]), ("UT", &["WOS:000765432100002"])]),
+            with_extra(&[("Accession Number", &["2-s2.0-85012345678"])]),
+            with_extra(&[("UT", &["  "])]),
+            Citation::new(),
+        ]
+    }
+
+    #[test]
+    fn test_remap_extra_fields() {
+        let mut citations = mixed_dataset();
+        let mapping = HashMap::from([
+            ("UT".to_string(), "AN".to_string()),
+            ("Accession Number".to_string(), "AN".to_string()),
+            ("AN".to_string(), "AN".to_string()),
+        ]);
+
+        assert_eq!(remap_extra_fields(&mut citations, &mapping), 4);
+        assert_eq!(citations[0].extra_fields["AN"], vec!["WOS:000123456700001"]);
+        // Values merge into an existing key
+        assert_eq!(
+            citations[1].extra_fields["AN"],
+            vec!["L636118453", "WOS:000765432100002"]
+        );
+        assert!(!citations[1].extra_fields.contains_key("UT"));
+        assert_eq!(citations[2].extra_fields["AN"], vec!["2-s2.0-85012345678"]);
+        assert!(citations[4].extra_fields.is_empty());
+    }
+
+    #[test]
+    fn test_remap_renames_each_key_once() {
+        let mut citations = vec![with_extra(&[("a", &["1"]), ("b", &["2"])])];
+        let mapping = HashMap::from([
+            ("a".to_string(), "b".to_string()),
+            ("b".to_string(), "c".to_string()),
+        ]);
+
+        remap_extra_fields(&mut citations, &mapping);
+        assert_eq!(citations[0].extra_fields["b"], vec!["1"]);
+        assert_eq!(citations[0].extra_fields["c"], vec!["2"]);
+        assert!(!citations[0].extra_fields.contains_key("a"));
+    }
+
+    #[test]
+    fn test_promote_accession_across_dataset() {
+        let mut citations = mixed_dataset();
+        assert_eq!(
+            promote_extra_field(&mut citations, "UT", FieldTarget::Accession, PromotePolicy::Skip),
+            1
+        );
+        assert_eq!(citations[0].extra_fields[ACCESSION_FIELD], vec!["WOS:000123456700001"]);
+        assert!(!citations[0].extra_fields.contains_key("UT"));
+        // The Embase number is kept and the Web of Science number left in place
+        assert_eq!(citations[1].extra_fields[ACCESSION_FIELD], vec!["L636118453"]);
+        assert_eq!(citations[1].extra_fields["UT"], vec!["WOS:000765432100002"]);
+        // Blank values are not promoted
+        assert_eq!(citations[3].extra_fields["UT"], vec!["  "]);
+
+        let mut citations = mixed_dataset();
+        promote_extra_field(
+            &mut citations,
+            "UT",
+            FieldTarget::Accession,
+            PromotePolicy::AppendForLists,
+        );
+        assert_eq!(
+            citations[1].extra_fields[ACCESSION_FIELD],
+            vec!["L636118453", "WOS:000765432100002"]
+        );
+
+        let mut citations = mixed_dataset();
+        promote_extra_field(&mut citations, "UT", FieldTarget::Accession, PromotePolicy::Overwrite);
+        assert_eq!(citations[1].extra_fields[ACCESSION_FIELD], vec!["WOS:000765432100002"]);
+        assert!(!citations[1].extra_fields.contains_key("UT"));
+    }
+
+    #[test]
+    fn test_promote_identifiers() {
+        let mut citations = vec![
+            with_extra(&[("DOI", &["not a doi", "https://doi.org/10.1000/ABC", "10.1000/def"])]),
+            with_extra(&[("PM", &["PMID: 12345678"]), ("PMC", &["4567890"])]),
+            with_extra(&[("PM", &["123456789"])]),
+        ];
+        citations[1].doi = Some("10.1000/kept".to_string());
+
+        assert_eq!(
+            promote_extra_field(&mut citations, "DOI", FieldTarget::Doi, PromotePolicy::Skip),
+            1
+        );
+        assert_eq!(citations[0].doi.as_deref(), Some("10.1000/abc"));
+        assert_eq!(citations[0].extra_fields["DOI"], vec!["not a doi", "10.1000/def"]);
+
+        promote_extra_field(&mut citations, "PM", FieldTarget::Pmid, PromotePolicy::Skip);
+        promote_extra_field(&mut citations, "PMC", FieldTarget::PmcId, PromotePolicy::Skip);
+        assert_eq!(citations[1].pmid.as_deref(), Some("12345678"));
+        assert_eq!(citations[1].pmc_id.as_deref(), Some("PMC4567890"));
+        assert!(citations[1].extra_fields.is_empty());
+        assert_eq!(citations[2].pmid, None);
+        assert_eq!(citations[2].extra_fields["PM"], vec!["123456789"]);
+
+        // Identifiers are never appended to
+        citations[1].extra_fields.insert("DO".to_string(), vec!["10.1000/new".to_string()]);
+        let policy = PromotePolicy::AppendForLists;
+        assert_eq!(promote_extra_field(&mut citations, "DO", FieldTarget::Doi, policy), 0);
+        let policy = PromotePolicy::Overwrite;
+        assert_eq!(promote_extra_field(&mut citations, "DO", FieldTarget::Doi, policy), 1);
+        assert_eq!(citations[1].doi.as_deref(), Some("10.1000/new"));
+    }
+
+    #[test]
+    fn test_promote_keywords_and_notes() {
+        let mut citation = with_extra(&[("DE", &["Sepsis; ICU", "sepsis"]), ("Comment", &["Screened"])]);
+        citation.keywords = vec!["ICU".to_string()];
+        citation.notes = Some("Imported".to_string());
+        let mut citations = vec![citation];
+
+        let policy = PromotePolicy::AppendForLists;
+        promote_extra_field(&mut citations, "DE", FieldTarget::Keywords, policy);
+        promote_extra_field(&mut citations, "Comment", FieldTarget::Notes, policy);
+        assert_eq!(citations[0].keywords, vec!["ICU", "Sepsis", "sepsis"]);
+        assert_eq!(citations[0].notes.as_deref(), Some("Imported\n\nScreened"));
+        assert!(citations[0].extra_fields.is_empty());
+
+        citations[0].extra_fields.insert("DE".to_string(), vec!["Shock".to_string()]);
+        promote_extra_field(&mut citations, "DE", FieldTarget::Keywords, PromotePolicy::Overwrite);
+        assert_eq!(citations[0].keywords, vec!["Shock"]);
+    }
+}
@@ -1,15 +1,41 @@
-use crate::Date;
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+use crate::error::{ParseError, ValueError};
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+use crate::{Citation, CitationFormat, Date};
+#[cfg(any(feature = "ris", feature = "xml", feature = "csv"))]
+use crate::Author;
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+use crate::NameOrder;
 use crate::regex::Regex;
 use std::sync::LazyLock;
 
 static DOI_URL_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^https?://(?:dx\.)?doi\.org/(.+)$").unwrap());
 
+#[cfg(any(feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
 static ISSN_SPLIT_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\d{4}-\d{3}[\dX](?:\s*\([^)]+\))?").unwrap());
 
 /// Formats page numbers consistently, handling partial end page numbers
 ///
+/// An end page that repeats the prefix of the start page, as in "S12-S14", is
+/// taken as written rather than completed. A range whose end comes before its
+/// start, such as "S12-S4" or "95-3", is returned unchanged.
+///
 /// # Arguments
 ///
 /// * `page_str` - The page string to format
@@ -48,9 +74,13 @@ pub fn format_page_numbers(page_range: &str) -> String {
         None => return page_range.to_string(),
     };
 
-    // If to number is shorter, use from's prefix/digits
-    let completed_to = if to_num.len() < from_num.len() {
-        format!("{}{}", &from_num[..from_num.len() - to_num.len()], to_num)
+    // If to number is shorter, use from's prefix/digits, unless to repeats the prefix
+    let completed_to = if to_num.len() < from_num.len() && to_prefix.is_empty() {
+        // The cut may fall inside a multi-byte character after the digits
+        let Some(head) = from_num.get(..from_num.len() - to_num.len()) else {
+            return page_range.to_string();
+        };
+        format!("{}{}", head, to_num)
     } else {
         to_num.to_string()
     };
@@ -60,6 +90,13 @@ pub fn format_page_numbers(page_range: &str) -> String {
         return format!("{}{}", from_prefix, from_num);
     }
 
+    // An end before the start is not a range this function can read
+    if let (Ok(start), Ok(end)) = (from_num.parse::<u64>(), completed_to.parse::<u64>())
+        && end < start
+    {
+        return page_range.to_string();
+    }
+
     // Reconstruct the page range
     format!(
         "{}{}-{}{}",
@@ -83,8 +120,48 @@ fn split_prefix_and_number(input: &str) -> (String, Option<String>) {
     }
 }
 
+/// Checks whether a page field looks like the pages of a conference abstract: a
+/// range of at most two pages, or an abstract number such as "S123", "OP-12" or
+/// "Abstract 112". Electronic article numbers such as "e071674" do not count.
+pub(crate) fn is_abstract_pages(pages: &str) -> bool {
+    let pages = format_page_numbers(pages.trim());
+    if pages.is_empty() {
+        return false;
+    }
+    if pages.to_lowercase().contains("abstract") {
+        return true;
+    }
+
+    let (from, to) = match pages.split_once('-') {
+        Some((from, to)) => (from.trim(), Some(to.trim())),
+        None => (pages.as_str(), None),
+    };
+    let leading_number = |text: &str| {
+        let digits: String = text.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse::<u32>().ok()
+    };
+
+    let (prefix, from_num) = split_prefix_and_number(from);
+    let Some(from_num) = from_num.as_deref().and_then(leading_number) else {
+        // A session code before the number, e.g. "OP-12"
+        return to.is_some_and(|to| leading_number(to).is_some());
+    };
+    match to {
+        None => prefix.is_empty() || !prefix.eq_ignore_ascii_case("e"),
+        Some(to) => split_prefix_and_number(to)
+            .1
+            .as_deref()
+            .and_then(leading_number)
+            .is_some_and(|to_num| to_num >= from_num && to_num - from_num <= 1),
+    }
+}
+
 /// Formats a DOI string by removing URL prefixes and [doi] suffixes
 ///
+/// DOIs are case-insensitive, so the result is lowercased. Trailing `.`, `,` and
+/// `;` and a single trailing slash, left over from URLs and citation text, are
+/// removed.
+///
 /// # Arguments
 ///
 /// * `doi_str` - The DOI string to format
@@ -102,21 +179,30 @@ pub fn format_doi(doi_str: &str) -> Option<String> {
     // Find the first occurrence of "10." which typically starts a DOI
     if let Some(pos) = doi.find("10.") {
         let doi = &doi[pos..];
-        if let Some(captures) = DOI_URL_REGEX.captures(doi) {
-            Some(captures[1].to_string())
-        } else {
-            Some(doi.to_string())
-        }
+        let doi = match DOI_URL_REGEX.captures(doi) {
+            Some(captures) => captures[1].to_string(),
+            None => doi.to_string(),
+        };
+        Some(trim_doi_end(&doi).to_string())
     } else {
         None
     }
 }
 
+/// Removes trailing punctuation and a single trailing slash from a DOI.
+fn trim_doi_end(doi: &str) -> &str {
+    let punctuation: &[char] = &['.', ',', ';'];
+    let doi = doi.trim_end_matches(punctuation);
+    doi.strip_suffix('/')
+        .map_or(doi, |doi| doi.trim_end_matches(punctuation))
+}
+
 /// Splits a string containing multiple ISSNs into a vector of individual ISSNs
 ///
 /// # Arguments
 ///
 /// * `issns` - String containing one or more ISSNs, possibly separated by newlines
+#[cfg(any(feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
 pub fn split_issns(issns: &str) -> Vec<String> {
     let normalized = issns
         .replace("\\r\\n", "\n")
@@ -142,28 +228,131 @@ pub fn split_issns(issns: &str) -> Vec<String> {
 }
 
 /// Helper function to parse author names in various formats
-pub fn parse_author_name(name: &str) -> (String, String) {
+///
+/// Returns the family name, the given names and any generational suffix. Names
+/// with a comma are read as "Family, Given"; `order` decides how names without
+/// one are split (see [`NameOrder`]).
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+pub fn parse_author_name(name: &str, order: NameOrder) -> (String, String, Option<String>) {
     // Handle formats like "Lastname, Firstname", "Lastname, FN", or "Lastname FN"
-    let parts: Vec<&str> = if name.contains(',') {
-        name.split(',').collect()
+    if !name.contains(',') {
+        return parse_name_without_comma(name, order);
+    }
+
+    let mut parts: Vec<&str> = name.split(',').map(str::trim).collect();
+    // A suffix in its own part: "Smith, John, Jr."
+    let mut suffix = None;
+    if parts.len() > 1 && is_name_suffix(parts[parts.len() - 1]) {
+        suffix = parts.pop().map(String::from);
+    }
+    if parts.len() == 1 {
+        // "John Smith, Jr."
+        let (family, given, inner) = parse_name_without_comma(parts[0], order);
+        return (family, given, suffix.or(inner));
+    }
+
+    // A suffix after the family name or the given names: "Smith Jr., John"
+    let (family, family_suffix) = take_trailing_suffix(parts[0]);
+    let given = parts[1..].join(" ");
+    let (given, given_suffix) = take_trailing_suffix(given.trim());
+    (
+        family.to_string(),
+        given.to_string(),
+        suffix.or(family_suffix).or(given_suffix),
+    )
+}
+
+/// Splits a name without a comma into family name, given names and suffix.
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+fn parse_name_without_comma(name: &str, order: NameOrder) -> (String, String, Option<String>) {
+    let mut tokens: Vec<&str> = name.split_whitespace().collect();
+    let suffix = if tokens.len() > 1 && is_name_suffix(tokens[tokens.len() - 1]) {
+        tokens.pop().map(String::from)
     } else {
-        name.split_whitespace().collect()
+        None
     };
 
-    match parts.len() {
-        0 => (String::new(), String::new()),
-        1 => (parts[0].trim().to_string(), String::new()),
-        2 => {
-            let family = parts[0].trim().to_string();
-            let given = parts[1].trim().to_string();
-            (family, given)
-        }
-        _ => {
-            let family = parts[0].trim().to_string();
-            let given = parts[1..].join(" ").trim().to_string();
-            (family, given)
-        }
+    let given_first = match order {
+        NameOrder::FamilyFirst => false,
+        NameOrder::GivenFirst => true,
+        NameOrder::Auto => looks_given_first(&tokens),
+    };
+    match tokens.as_slice() {
+        [] => (String::new(), String::new(), suffix),
+        [only] => (only.to_string(), String::new(), suffix),
+        [rest @ .., last] if given_first => (last.to_string(), rest.join(" "), suffix),
+        [first, rest @ ..] => (first.to_string(), rest.join(" "), suffix),
+    }
+}
+
+/// Splits a suffix off the end of a name part that has other words before it.
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+fn take_trailing_suffix(part: &str) -> (&str, Option<String>) {
+    match part.rsplit_once(char::is_whitespace) {
+        Some((rest, last)) if is_name_suffix(last) => (rest.trim_end(), Some(last.to_string())),
+        _ => (part, None),
+    }
+}
+
+/// Whether a word is a generational suffix such as "Jr." or "III". "V" is not
+/// counted, as it is far more often an initial.
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+fn is_name_suffix(word: &str) -> bool {
+    matches!(
+        word.trim_end_matches('.').to_lowercase().as_str(),
+        "jr" | "sr" | "ii" | "iii" | "iv" | "2nd" | "3rd"
+    )
+}
+
+/// Whether a word looks like initials: "J", "J.", "JA", "J.A." or "J.-P.".
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+fn is_initials(word: &str) -> bool {
+    let letters: Vec<char> = word.chars().filter(|c| !matches!(c, '.' | '-')).collect();
+    !letters.is_empty() && letters.len() <= 3 && letters.iter().all(|c| c.is_uppercase())
+}
+
+/// Common given names, used to recognize names written given-first.
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+const COMMON_GIVEN_NAMES: &[&str] = &[
+    "adam", "alice", "andrew", "anna", "anne", "brian", "carlos", "catherine", "charles",
+    "christopher", "daniel", "david", "elizabeth", "emily", "emma", "eric", "hannah", "helen",
+    "james", "jane", "jennifer", "jessica", "john", "jose", "joseph", "juan", "julia", "karen",
+    "kevin", "laura", "linda", "lisa", "maria", "mark", "mary", "matthew", "michael", "paul",
+    "peter", "rachel", "richard", "robert", "sarah", "stephen", "steven", "susan", "thomas",
+    "william",
+];
+
+/// Decides whether a name without a comma is written given-first, for
+/// [`NameOrder::Auto`]. Trailing initials mean family-first ("Smith JA"); a
+/// leading initial or common given name means given-first ("J. Smith",
+/// "John Smith").
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+fn looks_given_first(tokens: &[&str]) -> bool {
+    let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+        return false;
+    };
+    if tokens.len() < 2 || is_initials(last) {
+        return false;
     }
+    let capitalized = last.chars().next().is_some_and(char::is_uppercase);
+    capitalized
+        && (is_initials(first) || COMMON_GIVEN_NAMES.contains(&first.to_lowercase().as_str()))
+}
+
+/// Formats an author as "Family, Given Middle, Suffix", the form
+/// [`parse_author_name`] reads back. Missing parts are left out.
+#[cfg(any(feature = "ris", feature = "xml", feature = "csv"))]
+pub(crate) fn format_author_name(author: &Author) -> String {
+    let given = [author.given_name.as_deref(), author.middle_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    [Some(author.name.as_str()), (!given.is_empty()).then_some(given.as_str()), author.suffix.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 /// Split a full given name string into given name and middle name parts.
@@ -171,6 +360,14 @@ pub fn parse_author_name(name: &str) -> (String, String) {
 /// Returns a tuple of (given_name, middle_name), where each is Option<String>.
 /// The first token becomes the given_name; remaining tokens (if any) are joined
 /// with a single space and become the middle_name.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
 pub fn split_given_and_middle(full_given: &str) -> (Option<String>, Option<String>) {
     let trimmed = full_given.trim();
     if trimmed.is_empty() {
@@ -187,53 +384,67 @@ pub fn split_given_and_middle(full_given: &str) -> (Option<String>, Option<Strin
     (first, middle)
 }
 
-/// Parses PubMed format dates (e.g., "2020 Jun 9", "2023 May 30", "2023 Jan 3", "2023")
+/// Parses PubMed format dates (e.g., "2020 Jun 9", "2023 May 30", "2023"), which may
+/// describe a range (e.g., "2020 Jan-Mar", "2019 Dec-2020 Jan", "2021 Mar 15-21", "2018-2019")
+///
+/// Returns the start date, plus the end date if the string is a range.
 ///
 /// # Arguments
 ///
 /// * `date_str` - The date string to parse
-pub fn parse_pubmed_date(date_str: &str) -> Option<Date> {
+#[cfg(any(feature = "ris", feature = "pubmed"))]
+pub fn parse_pubmed_date_range(date_str: &str) -> Option<(Date, Option<Date>)> {
     let date_str = date_str.trim();
 
     if date_str.is_empty() {
         return None;
     }
 
-    // Split the date string into parts
-    let parts: Vec<&str> = date_str.split_whitespace().collect();
-
-    // First part should be year
-    let year = if let Some(year_str) = parts.first() {
-        year_str.parse::<i32>().ok()?
-    } else {
-        return None;
-    };
+    // First part should be year, the rest is month and day
+    let (year_str, rest) = date_str
+        .split_once(char::is_whitespace)
+        .unwrap_or((date_str, ""));
 
-    let mut month = None;
-    let mut day = None;
-
-    // Second part should be month (if present)
-    if let Some(month_str) = parts.get(1) {
-        month = parse_month_name(month_str);
+    // Year ranges such as "2018-2019"
+    if let Some((start_year, end_year)) = year_str.split_once('-') {
+        let start = parse_year_only(start_year)?;
+        let end = parse_year_only(end_year)?;
+        return Some((start, Some(end)));
     }
 
-    // Third part should be day (if present)
-    if let Some(day_str) = parts.get(2) {
-        if let Ok(parsed_day) = day_str.parse::<u8>() {
-            if (1..=31).contains(&parsed_day) {
-                day = Some(parsed_day);
-            }
-        }
-    }
+    let year = year_str.parse::<i32>().ok()?;
+    let (month, day, end) = parse_month_day_range(year, rest);
 
-    Some(Date { year, month, day })
+    Some((Date { year, month, day }, end))
 }
 
+/// Years accepted in RIS dates; anything else is taken for a misread date.
+#[cfg(feature = "ris")]
+const RIS_YEARS: std::ops::RangeInclusive<i32> = 1400..=2100;
+
+/// Two-digit years below this are read as 20xx, the others as 19xx.
+#[cfg(feature = "ris")]
+const TWO_DIGIT_YEAR_PIVOT: i32 = 30;
+
 /// Parses RIS format dates (e.g., "1999/12/25/Christmas edition", "2023/05/30", "2023")
 ///
+/// Besides the standard year/month/day order, dates written by other tools are
+/// read as follows:
+///
+/// - A two-digit year, as in Google Scholar's "98///", is 20xx below 30
+///   and 19xx otherwise, so "98" is 1998 and "03"
+///   is 2003.
+/// - A four-digit year last, as in "03/15/2021" or "15/03/2021", follows a
+///   month and day in either order when only one order gives a valid month.
+///   When both do, as in "05/06/2021", the order cannot be told and only the
+///   year is kept, unless month and day are the same number.
+///
+/// Returns `None` when the year is not a number or lies outside 1400 to 2100.
+///
 /// # Arguments
 ///
 /// * `date_str` - The date string to parse
+#[cfg(feature = "ris")]
 pub fn parse_ris_date(date_str: &str) -> Option<Date> {
     let date_str = date_str.trim();
 
@@ -243,44 +454,88 @@ pub fn parse_ris_date(date_str: &str) -> Option<Date> {
 
     // Split by '/' and take first 3 parts (year/month/day)
     let parts: Vec<&str> = date_str.split('/').collect();
+    let number = |i: usize| {
+        parts
+            .get(i)
+            .filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse::<u16>().ok())
+    };
 
-    // First part should be year
-    let year = if let Some(year_str) = parts.first() {
-        if !year_str.is_empty() {
-            year_str.parse::<i32>().ok()?
-        } else {
-            return None;
-        }
+    let year_last = parts[0].len() <= 2 && parts.get(2).is_some_and(|s| s.len() == 4);
+    let (year, month, day) = if year_last {
+        // Month and day in either order, then the year
+        let year = i32::from(number(2)?);
+        let (first, second) = (number(0), number(1));
+        let (month, day) = match (first, second) {
+            (Some(a), Some(b)) if a == b || (b > 12 && a <= 12) => (Some(a), Some(b)),
+            (Some(a), Some(b)) if a > 12 && b <= 12 => (Some(b), Some(a)),
+            _ => (None, None),
+        };
+        (year, month, day)
     } else {
+        let year = match number(0)? {
+            year if parts[0].len() == 2 && i32::from(year) < TWO_DIGIT_YEAR_PIVOT => 2000 + i32::from(year),
+            year if parts[0].len() == 2 => 1900 + i32::from(year),
+            year => i32::from(year),
+        };
+        (year, number(1), number(2))
+    };
+
+    if !RIS_YEARS.contains(&year) {
         return None;
+    }
+    let month = month.filter(|m| (1..=12).contains(m)).map(|m| m as u8);
+    let day = day.filter(|d| (1..=31).contains(d)).map(|d| d as u8);
+
+    Some(Date { year, month, day })
+}
+
+/// Whether month and day of a year-last RIS date such as "05/06/2021" can be
+/// read in either order, so that [`parse_ris_date`] keeps only the year.
+#[cfg(feature = "ris")]
+pub(crate) fn ris_date_is_ambiguous(date_str: &str) -> bool {
+    let parts: Vec<&str> = date_str.trim().split('/').collect();
+    let month = |i: usize| {
+        parts
+            .get(i)
+            .filter(|s| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse::<u16>().ok())
+            .filter(|n| (1..=12).contains(n))
     };
+    let year_last = parts[0].len() <= 2 && parts.get(2).is_some_and(|s| s.len() == 4);
+    year_last && matches!((month(0), month(1)), (Some(a), Some(b)) if a != b)
+}
 
-    let mut month = None;
-    let mut day = None;
+/// Parses RIS format dates which may describe a range
+///
+/// The "other information" part of a slash-separated date may carry a month range
+/// (e.g., "2020///Jan-Mar"); dates without slashes are parsed as PubMed style dates
+/// (e.g., "2020 Jan-Mar", "2019-2020").
+///
+/// Returns the start date, plus the end date if the string is a range.
+///
+/// # Arguments
+///
+/// * `date_str` - The date string to parse
+#[cfg(feature = "ris")]
+pub fn parse_ris_date_range(date_str: &str) -> Option<(Date, Option<Date>)> {
+    let date_str = date_str.trim();
 
-    // Second part should be month (if present and not empty)
-    if let Some(month_str) = parts.get(1) {
-        if !month_str.is_empty() {
-            if let Ok(parsed_month) = month_str.parse::<u8>() {
-                if (1..=12).contains(&parsed_month) {
-                    month = Some(parsed_month);
-                }
-            }
-        }
+    if !date_str.contains('/') {
+        return parse_pubmed_date_range(date_str).filter(|(start, end)| {
+            RIS_YEARS.contains(&start.year) && end.as_ref().is_none_or(|end| RIS_YEARS.contains(&end.year))
+        });
     }
 
-    // Third part should be day (if present and not empty)
-    if let Some(day_str) = parts.get(2) {
-        if !day_str.is_empty() {
-            if let Ok(parsed_day) = day_str.parse::<u8>() {
-                if (1..=31).contains(&parsed_day) {
-                    day = Some(parsed_day);
-                }
-            }
-        }
+    let mut start = parse_ris_date(date_str)?;
+    let other = date_str.splitn(4, '/').nth(3).unwrap_or("");
+    let (month, day, end) = parse_month_day_range(start.year, other);
+    if start.month.is_none() {
+        start.month = month;
+        start.day = day;
     }
 
-    Some(Date { year, month, day })
+    Some((start, end))
 }
 
 /// Parses EndNote XML format dates from year attributes
@@ -290,6 +545,7 @@ pub fn parse_ris_date(date_str: &str) -> Option<Date> {
 /// * `year` - Year value
 /// * `month` - Month value (optional)
 /// * `day` - Day value (optional)
+#[cfg(feature = "xml")]
 pub fn parse_endnote_date(year: Option<i32>, month: Option<u8>, day: Option<u8>) -> Option<Date> {
     let year = year?;
     Some(Date { year, month, day })
@@ -300,6 +556,13 @@ pub fn parse_endnote_date(year: Option<i32>, month: Option<u8>, day: Option<u8>)
 /// # Arguments
 ///
 /// * `year_str` - The year string to parse
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
 pub fn parse_year_only(year_str: &str) -> Option<Date> {
     let year_str = year_str.trim();
 
@@ -319,8 +582,122 @@ pub fn parse_year_only(year_str: &str) -> Option<Date> {
     })
 }
 
+/// Parses a count such as a times-cited number, e.g. "1,234" or " 56 ".
+///
+/// Thousands separators (commas and spaces) are dropped. Returns `None` for
+/// anything else that is not a number of digits, or that does not fit a `u32`.
+#[cfg(any(feature = "csv", feature = "ris"))]
+pub(crate) fn parse_count(text: &str) -> Option<u32> {
+    let digits: String = text
+        .chars()
+        .filter(|c| *c != ',' && !c.is_whitespace())
+        .collect();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Parses the month and day that follow the year of a date, which may be a range
+/// such as "Jan-Mar", "Dec-2020 Jan", "Mar 15-21" or "Jan 28-Feb 3".
+///
+/// Returns the start month and day, plus the end date if a range was given. An end
+/// month earlier than the start month without an explicit year rolls over into the
+/// following year.
+#[cfg(any(feature = "ris", feature = "pubmed", feature = "xml"))]
+pub(crate) fn parse_month_day_range(year: i32, text: &str) -> (Option<u8>, Option<u8>, Option<Date>) {
+    let parse_day = |s: &str| s.parse::<u8>().ok().filter(|d| (1..=31).contains(d));
+
+    let (start, end) = match text.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (text, None),
+    };
+
+    let mut tokens = start.split_whitespace();
+    let Some(month) = tokens.next().and_then(parse_month_name) else {
+        return (None, None, None);
+    };
+    let day = tokens.next().and_then(parse_day);
+
+    let end = end.and_then(|end| {
+        let mut tokens = end.split_whitespace().peekable();
+        let explicit_year = tokens
+            .peek()
+            .filter(|t| t.len() == 4)
+            .and_then(|t| t.parse::<i32>().ok());
+        if explicit_year.is_some() {
+            tokens.next();
+        }
+
+        let (end_month, end_day) = match tokens.next() {
+            Some(token) => match parse_month_name(token) {
+                Some(end_month) => (Some(end_month), tokens.next().and_then(parse_day)),
+                // A day range within the start month, e.g. "Mar 15-21"
+                None => (Some(month), Some(parse_day(token)?)),
+            },
+            None => (None, None),
+        };
+
+        let end_year = match (explicit_year, end_month) {
+            (Some(end_year), _) => end_year,
+            (None, Some(end_month)) if end_month < month => year + 1,
+            (None, Some(_)) => year,
+            (None, None) => return None,
+        };
+
+        Some(Date {
+            year: end_year,
+            month: end_month,
+            day: end_day,
+        })
+    });
+
+    (Some(month), day, end)
+}
+
+/// Three-letter month names as used in PubMed and EndNote date text.
+#[cfg(any(feature = "ris", feature = "pubmed", feature = "xml"))]
+const MONTH_ABBREVIATIONS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Formats the month and day of a date, plus the end of its range, in the form
+/// read by [`parse_month_day_range`] (e.g. "Mar 15", "Jan-Mar", "Dec-2020 Jan").
+///
+/// Returns `None` when the start date has no month.
+#[cfg(any(feature = "ris", feature = "pubmed", feature = "xml"))]
+pub(crate) fn format_month_day_range(start: &Date, end: Option<&Date>) -> Option<String> {
+    let month_name = |month: u8| MONTH_ABBREVIATIONS.get(usize::from(month).checked_sub(1)?);
+
+    let mut text = month_name(start.month?)?.to_string();
+    if let Some(day) = start.day {
+        text.push_str(&format!(" {}", day));
+    }
+
+    if let Some(end) = end
+        && let Some(end_month) = end.month.and_then(month_name)
+    {
+        if end.year != start.year {
+            text.push_str(&format!("-{} {}", end.year, end_month));
+        } else if end.month == start.month
+            && let Some(end_day) = end.day
+        {
+            text.push_str(&format!("-{}", end_day));
+            return Some(text);
+        } else {
+            text.push_str(&format!("-{}", end_month));
+        }
+        if let Some(end_day) = end.day {
+            text.push_str(&format!(" {}", end_day));
+        }
+    }
+
+    Some(text)
+}
+
 /// Helper function to parse month names to month numbers
-fn parse_month_name(month_str: &str) -> Option<u8> {
+#[cfg(any(feature = "ris", feature = "pubmed", feature = "xml", feature = "bibtex"))]
+pub(crate) fn parse_month_name(month_str: &str) -> Option<u8> {
     match month_str.to_lowercase().as_str() {
         "jan" | "january" => Some(1),
         "feb" | "february" => Some(2),
@@ -338,7 +715,133 @@ fn parse_month_name(month_str: &str) -> Option<u8> {
     }
 }
 
+/// The format whose records a line starts or ends, if it is one of the formats
+/// [`check_format`] looks for.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+fn format_marker(line: &str) -> Option<CitationFormat> {
+    let line = line.trim_start_matches('\u{feff}');
+    let ris_tag = line.trim_start().get(..2).zip(line.trim_start().get(2..));
+    if ris_tag.is_some_and(|(tag, rest)| {
+        // Reference Manager writes lowercase tags before a tab, e.g. "ty\tJOUR"
+        ((tag == "TY" || tag == "ER") && (rest.starts_with("  -") || rest.starts_with(" -")))
+            || ((tag.eq_ignore_ascii_case("TY") || tag.eq_ignore_ascii_case("ER"))
+                && rest.starts_with('\t'))
+    }) {
+        Some(CitationFormat::Ris)
+    } else if line.starts_with("PMID-") {
+        Some(CitationFormat::PubMed)
+    } else if line.starts_with("%0 ") {
+        Some(CitationFormat::Enw)
+    } else if is_bibtex_entry_start(line) {
+        Some(CitationFormat::BibTex)
+    } else if ["<?xml", "<xml>", "<record>"]
+        .iter()
+        .any(|start| line.trim_start().starts_with(start))
+    {
+        Some(CitationFormat::EndNoteXml)
+    } else {
+        None
+    }
+}
+
+/// Whether `line` starts a BibTeX entry: `@`, letters and an opening brace or parenthesis.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn is_bibtex_entry_start(line: &str) -> bool {
+    let Some(rest) = line.trim_start_matches('\u{feff}').trim_start().strip_prefix('@') else {
+        return false;
+    };
+    let rest = rest.trim_start();
+    let name_end = rest
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(rest.len());
+    name_end > 0 && rest[name_end..].trim_start().starts_with(['{', '('])
+}
+
+/// Checks that `input` does not look like a format other than `format`.
+///
+/// Parsing a file with the parser of another format can succeed, as tags such
+/// as `TI  - ` mean the same in RIS and PubMed, and give citations made of
+/// whichever tags happen to collide. The input is rejected when it has record
+/// markers of other formats (RIS `TY` and `ER` lines, PubMed `PMID`, `.enw`
+/// `%0`, BibTeX `@article{` or EndNote XML elements) but none of `format`, so
+/// messy files of the right format still parse. The error names the format with
+/// the most markers and points at its first one.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn check_format(input: &str, format: CitationFormat) -> Result<(), ParseError> {
+    // Format, number of markers and line of the first marker
+    let mut others: Vec<(CitationFormat, usize, usize)> = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let Some(marker) = format_marker(line) else {
+            continue;
+        };
+        if marker == format {
+            return Ok(());
+        }
+        match others.iter_mut().find(|(other, ..)| *other == marker) {
+            Some((_, count, _)) => *count += 1,
+            None => others.push((marker, 1, index + 1)),
+        }
+    }
+
+    match others
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)))
+    {
+        Some((detected, _, line)) => Err(ParseError::at_line(
+            line,
+            format,
+            ValueError::WrongFormat { detected },
+        )),
+        None => Ok(()),
+    }
+}
+
+/// How much of a tagged input the parser for its format reads, used by
+/// [`crate::detect_and_parse_strict`].
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Coverage {
+    /// Number of non-blank lines
+    pub(crate) lines: usize,
+    /// 1-based numbers of the non-blank lines the parser skips
+    pub(crate) unparsed: Vec<usize>,
+    /// Number of record start lines
+    pub(crate) records: usize,
+}
+
+impl Coverage {
+    /// Adds the coverage of a section that starts after `first_line` lines.
+    #[cfg(all(feature = "ris", feature = "pubmed"))]
+    pub(crate) fn extend(&mut self, other: Coverage, first_line: usize) {
+        self.lines += other.lines;
+        self.unparsed
+            .extend(other.unparsed.into_iter().map(|line| line + first_line));
+        self.records += other.records;
+    }
+}
+
 /// get the newline delimiter (e.g. CRLF for Windows, LF for Linux). of multi-line text.
+#[cfg(feature = "pubmed")]
 pub(crate) fn newline_delimiter_of(text: &str) -> &'static str {
     // find the first '\n', then check whether the character before it is '\r'
     if text
@@ -353,9 +856,461 @@ pub(crate) fn newline_delimiter_of(text: &str) -> &'static str {
     }
 }
 
+/// Whether a citation type names a conference record, such as RIS `CONF` or
+/// `CPAPER` or EndNote "Conference Proceedings".
+pub(crate) fn is_conference_type(citation_type: &[String]) -> bool {
+    citation_type.iter().any(|t| {
+        t.eq_ignore_ascii_case("CONF")
+            || t.eq_ignore_ascii_case("CPAPER")
+            || t.to_lowercase().contains("conference")
+    })
+}
+
+/// Whether an author name is a placeholder for a missing author, such as
+/// "Anonymous", "[No authors listed]", "et al", "N/A" or a blank name.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn is_placeholder_author(name: &str) -> bool {
+    let name = name.trim().trim_end_matches('.').trim();
+    let name = name
+        .strip_prefix('[')
+        .and_then(|name| name.strip_suffix(']'))
+        .unwrap_or(name)
+        .trim();
+    ["", "anonymous", "anon", "no authors listed", "et al", "n/a"]
+        .iter()
+        .any(|placeholder| name.eq_ignore_ascii_case(placeholder))
+}
+
+/// Sets [`crate::ANONYMOUS_FIELD`] when placeholder authors were dropped and no
+/// real authors remain.
+#[cfg(any(feature = "ris", feature = "xml", feature = "enw", feature = "bibtex", feature = "csv"))]
+pub(crate) fn mark_anonymous(citation: &mut Citation, placeholder_seen: bool) {
+    if placeholder_seen && citation.authors.is_empty() {
+        citation
+            .extra_fields
+            .insert(crate::ANONYMOUS_FIELD.to_string(), vec!["true".to_string()]);
+    }
+}
+
+/// Removes HTML markup from text such as a note exported by Zotero.
+///
+/// Tags are dropped, with paragraph, line break, list item and heading tags
+/// starting a new line, and character references like `&amp;` and `&#233;` are
+/// decoded. Lines are trimmed and empty lines removed, so the result never
+/// contains a blank line.
+#[cfg(feature = "ris")]
+pub(crate) fn strip_html(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find(['<', '&']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let tag_end = rest[1..]
+            .starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!')
+            .then(|| rest.find('>'))
+            .flatten()
+            .filter(|_| rest.starts_with('<'));
+        let entity = rest
+            .find(';')
+            .filter(|&end| rest.starts_with('&') && end <= 10)
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+
+        if let Some(end) = tag_end {
+            let name = rest[1..end]
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            if matches!(
+                name.as_str(),
+                "p" | "br" | "div" | "li" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+            ) {
+                text.push('\n');
+            }
+            rest = &rest[end + 1..];
+        } else if let Some((c, end)) = entity {
+            text.push(c);
+            rest = &rest[end + 1..];
+        } else {
+            // A literal '<' or '&'
+            text.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    text.push_str(rest);
+
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Decodes the name of an HTML character reference, without `&` and `;`.
+#[cfg(feature = "ris")]
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// Appends converted citations to `out`, stopping at the first error.
+///
+/// On error, every citation appended by this call is removed again so `out`
+/// is left exactly as it was. When `errors` is given, failed conversions are
+/// pushed to it instead and the remaining citations are still appended.
+/// Returns the number of citations appended.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn append_citations<I>(
+    out: &mut Vec<Citation>,
+    citations: I,
+    mut errors: Option<&mut Vec<ParseError>>,
+) -> Result<usize, ParseError>
+where
+    I: IntoIterator<Item = Result<Citation, ParseError>>,
+{
+    let start = out.len();
+    let citations = citations.into_iter();
+    out.reserve(citations.size_hint().0);
+    for citation in citations {
+        match (citation, errors.as_deref_mut()) {
+            (Ok(citation), _) => out.push(citation),
+            (Err(e), Some(errors)) => errors.push(e),
+            (Err(e), None) => {
+                out.truncate(start);
+                return Err(e);
+            }
+        }
+    }
+    Ok(out.len() - start)
+}
+
+/// Applies [`Citation::dedupe_keywords`] to the last `count` citations of `citations`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn dedupe_keywords_of_last(citations: &mut [Citation], count: usize) {
+    let start = citations.len() - count;
+    citations[start..]
+        .iter_mut()
+        .for_each(Citation::dedupe_keywords);
+}
+
+/// Applies [`Citation::fold_typography`] to the last `count` citations of `citations`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn fold_typography_of_last(citations: &mut [Citation], count: usize) {
+    let start = citations.len() - count;
+    citations[start..]
+        .iter_mut()
+        .for_each(Citation::fold_typography);
+}
+
+/// Applies [`Citation::sentence_case_title`] to the last `count` citations of `citations`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn sentence_case_titles_of_last(citations: &mut [Citation], count: usize) {
+    let start = citations.len() - count;
+    for citation in &mut citations[start..] {
+        citation.sentence_case_title();
+    }
+}
+
+/// Applies [`Citation::clean_abstract`] to the last `count` citations of `citations`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn clean_abstracts_of_last(citations: &mut [Citation], count: usize) {
+    let start = citations.len() - count;
+    citations[start..]
+        .iter_mut()
+        .for_each(Citation::clean_abstract);
+}
+
+/// Applies [`Citation::truncate_abstract`] to the last `count` citations of `citations`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn truncate_abstracts_of_last(citations: &mut [Citation], count: usize, max_chars: usize) {
+    let start = citations.len() - count;
+    for citation in &mut citations[start..] {
+        citation.truncate_abstract(max_chars);
+    }
+}
+
+/// Applies [`Citation::truncate_authors`] to the last `count` citations of `citations`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn truncate_authors_of_last(citations: &mut [Citation], count: usize, max_authors: usize) {
+    let start = citations.len() - count;
+    for citation in &mut citations[start..] {
+        citation.truncate_authors(max_authors);
+    }
+}
+
+/// Sets `ingested_at` of the last `count` citations of `citations` to the current time.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn stamp_ingestion_time_of_last(citations: &mut [Citation], count: usize) {
+    let now = rfc3339_now();
+    let start = citations.len() - count;
+    for citation in &mut citations[start..] {
+        citation.ingested_at = Some(now.clone());
+    }
+}
+
+/// Sets `parsed_from` of the last `count` citations of `citations` to `format`.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn set_parsed_from_of_last(
+    citations: &mut [Citation],
+    count: usize,
+    format: CitationFormat,
+) {
+    let start = citations.len() - count;
+    for citation in &mut citations[start..] {
+        citation.parsed_from = Some(format.clone());
+    }
+}
+
+/// Input bytes parsed together by one task of a parallel parse.
+#[cfg(all(feature = "parallel", any(feature = "ris", feature = "pubmed")))]
+pub(crate) const PARALLEL_CHUNK_BYTES: usize = 1 << 20;
+
+/// A run of whole records of a larger input, parsed by one task of a parallel parse.
+#[cfg(all(feature = "parallel", any(feature = "ris", feature = "pubmed")))]
+#[derive(Debug)]
+pub(crate) struct RecordChunk<'a> {
+    /// Number of lines in the input before this chunk
+    pub(crate) first_line: usize,
+    pub(crate) text: &'a str,
+    /// Whether the chunk runs to the end of the input
+    pub(crate) is_last: bool,
+}
+
+/// Splits `input` into chunks of at least `min_bytes`, except for the last one.
+///
+/// `is_boundary` is called with every line of the input in order, line break
+/// included, and tells whether a chunk may start at it; it only needs to scan
+/// for record starts, without parsing.
+#[cfg(all(feature = "parallel", any(feature = "ris", feature = "pubmed")))]
+pub(crate) fn record_chunks(
+    input: &str,
+    min_bytes: usize,
+    mut is_boundary: impl FnMut(&str) -> bool,
+) -> Vec<RecordChunk<'_>> {
+    let mut chunks = Vec::new();
+    let (mut start, mut first_line, mut offset) = (0, 0, 0);
+    for (line_number, line) in input.split_inclusive('\n').enumerate() {
+        if is_boundary(line) && offset - start >= min_bytes {
+            chunks.push(RecordChunk {
+                first_line,
+                text: &input[start..offset],
+                is_last: false,
+            });
+            start = offset;
+            first_line = line_number;
+        }
+        offset += line.len();
+    }
+    chunks.push(RecordChunk {
+        first_line,
+        text: &input[start..],
+        is_last: true,
+    });
+    chunks
+}
+
+/// Parses `chunks` on the rayon thread pool, concatenating the citations in
+/// input order.
+///
+/// Fails with the error of the first failing chunk, its line made relative to
+/// the whole input.
+#[cfg(all(feature = "parallel", any(feature = "ris", feature = "pubmed")))]
+pub(crate) fn parse_chunks_parallel<F>(
+    chunks: &[RecordChunk<'_>],
+    parse: F,
+) -> Result<Vec<Citation>, ParseError>
+where
+    F: Fn(&RecordChunk<'_>) -> Result<Vec<Citation>, ParseError> + Sync,
+{
+    use rayon::prelude::*;
+
+    let parsed: Vec<_> = chunks.par_iter().map(&parse).collect();
+    let mut citations = Vec::with_capacity(parsed.iter().flatten().map(Vec::len).sum());
+    for (chunk, result) in chunks.iter().zip(parsed) {
+        let chunk_citations = result.map_err(|mut e| {
+            e.line = e.line.map(|line| line + chunk.first_line);
+            e
+        })?;
+        citations.extend(chunk_citations);
+    }
+    Ok(citations)
+}
+
+/// The current UTC time as RFC 3339 with second precision, e.g. "2026-10-14T09:30:00Z".
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+pub(crate) fn rfc3339_now() -> String {
+    let seconds = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    format_rfc3339(seconds)
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp.
+#[cfg(any(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "bibtex",
+    feature = "csv"
+))]
+fn format_rfc3339(unix_seconds: u64) -> String {
+    let days = (unix_seconds / 86_400) as i64;
+    let seconds = unix_seconds % 86_400;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// A writer that counts the bytes and flushes it receives, optionally failing
+/// once a byte limit is reached. Used to test the streaming writers.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub(crate) struct CountingWriter {
+    pub(crate) bytes: usize,
+    pub(crate) flushes: usize,
+    pub(crate) fail_after: Option<usize>,
+}
+
+#[cfg(test)]
+impl CountingWriter {
+    pub(crate) fn failing_after(bytes: usize) -> Self {
+        Self {
+            fail_after: Some(bytes),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl std::io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.fail_after.is_some_and(|limit| self.bytes + buf.len() > limit) {
+            return Err(std::io::Error::other("byte limit reached"));
+        }
+        self.bytes += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn test_format_page_numbers() {
@@ -372,6 +1327,27 @@ mod tests {
         assert_eq!(format_page_numbers("01-Apr"), "01-Apr");
         assert_eq!(format_page_numbers("iii613-iii614"), "iii613-iii614");
         assert_eq!(format_page_numbers("101-101"), "101");
+        assert_eq!(format_page_numbers("S12-4"), "S12-S14");
+        assert_eq!(format_page_numbers("S12-S14"), "S12-S14");
+        assert_eq!(format_page_numbers("S12-S12"), "S12");
+        // Ends before the start are left alone
+        assert_eq!(format_page_numbers("S12-S4"), "S12-S4");
+        assert_eq!(format_page_numbers("95-3"), "95-3");
+        assert_eq!(format_page_numbers("iii614-iii613"), "iii614-iii613");
+        assert_eq!(format_page_numbers("xii-xv"), "xii-xv");
+        // Completing the end must not cut into a multi-byte character
+        assert_eq!(format_page_numbers("1é-3"), "1é-3");
+        assert_eq!(format_page_numbers("12€-3"), "12€-3");
+    }
+
+    #[test]
+    fn test_is_abstract_pages() {
+        for pages in ["S123", "A94-A95", "1234-5", "88", "OP-12", "Abstract 112", "iii613-iii614"] {
+            assert!(is_abstract_pages(pages), "{pages}");
+        }
+        for pages in ["", "210-218", "S1-S10", "e071674", "01-Apr"] {
+            assert!(!is_abstract_pages(pages), "{pages}");
+        }
     }
 
     #[test]
@@ -409,6 +1385,11 @@ mod tests {
                 "https://doi.org/10.1000/test [doi]",
                 Some("10.1000/test".to_string()),
             ),
+            ("10.1234/abc.def/", Some("10.1234/abc.def".to_string())),
+            ("10.1234/abc.def.", Some("10.1234/abc.def".to_string())),
+            ("10.1234/abc.def;", Some("10.1234/abc.def".to_string())),
+            ("10.1234/abc.def/,", Some("10.1234/abc.def".to_string())),
+            ("10.1234/abc.def//", Some("10.1234/abc.def/".to_string())),
             ("", None),
             ("invalid", None),
         ];
@@ -421,46 +1402,87 @@ mod tests {
     #[test]
     fn test_parse_author_name() {
         // Test standard format "LastName, FirstName"
-        let (family, given) = parse_author_name("Smith, John");
+        let (family, given, _) = parse_author_name("Smith, John", NameOrder::FamilyFirst);
         assert_eq!(family, "Smith");
         assert_eq!(given, "John");
 
         // Test format with initials "LastName, J.J."
-        let (family, given) = parse_author_name("Duan, J.J.");
+        let (family, given, _) = parse_author_name("Duan, J.J.", NameOrder::FamilyFirst);
         assert_eq!(family, "Duan");
         assert_eq!(given, "J.J.");
 
         // Test format without comma "LastName FirstName"
-        let (family, given) = parse_author_name("Smith John");
+        let (family, given, _) = parse_author_name("Smith John", NameOrder::FamilyFirst);
         assert_eq!(family, "Smith");
         assert_eq!(given, "John");
 
         // Test format with just initials "LastName JJ"
-        let (family, given) = parse_author_name("Duan JJ");
+        let (family, given, _) = parse_author_name("Duan JJ", NameOrder::FamilyFirst);
         assert_eq!(family, "Duan");
         assert_eq!(given, "JJ");
 
         // Test single name
-        let (family, given) = parse_author_name("Smith");
+        let (family, given, _) = parse_author_name("Smith", NameOrder::FamilyFirst);
         assert_eq!(family, "Smith");
         assert_eq!(given, "");
 
         // Test hyphenated names
-        let (family, given) = parse_author_name("Smith-Jones, John-Paul");
+        let (family, given, _) = parse_author_name("Smith-Jones, John-Paul", NameOrder::FamilyFirst);
         assert_eq!(family, "Smith-Jones");
         assert_eq!(given, "John-Paul");
 
         // Test empty string
-        let (family, given) = parse_author_name("");
+        let (family, given, _) = parse_author_name("", NameOrder::FamilyFirst);
         assert_eq!(family, "");
         assert_eq!(given, "");
 
         // Test with multiple spaces
-        let (family, given) = parse_author_name("von  Neumann,    John");
+        let (family, given, _) = parse_author_name("von  Neumann,    John", NameOrder::FamilyFirst);
         assert_eq!(family, "von  Neumann");
         assert_eq!(given, "John");
     }
 
+    #[test]
+    fn test_parse_author_name_order_and_suffix() {
+        use NameOrder::{Auto, FamilyFirst, GivenFirst};
+
+        // (input, order, family, given, suffix)
+        let cases = [
+            ("Smith, John, Jr.", FamilyFirst, "Smith", "John", Some("Jr.")),
+            ("Smith Jr., John", FamilyFirst, "Smith", "John", Some("Jr.")),
+            ("Smith, John Jr", FamilyFirst, "Smith", "John", Some("Jr")),
+            ("Smith, J. A., III", FamilyFirst, "Smith", "J. A.", Some("III")),
+            ("John Smith, Jr.", GivenFirst, "Smith", "John", Some("Jr.")),
+            ("Smith JA", FamilyFirst, "Smith", "JA", None),
+            ("Smith JA Jr", FamilyFirst, "Smith", "JA", Some("Jr")),
+            ("John Smith Jr", GivenFirst, "Smith", "John", Some("Jr")),
+            ("John Smith Jr", FamilyFirst, "John", "Smith", Some("Jr")),
+            ("John Paul Smith", GivenFirst, "Smith", "John Paul", None),
+            // Auto: trailing initials mean family-first
+            ("Smith JA", Auto, "Smith", "JA", None),
+            ("Smith J.-P.", Auto, "Smith", "J.-P.", None),
+            // Auto: a leading initial or common given name means given-first
+            ("J. Smith", Auto, "Smith", "J.", None),
+            ("John Smith", Auto, "Smith", "John", None),
+            ("John Smith III", Auto, "Smith", "John", Some("III")),
+            // Auto: anything else stays family-first, as tagged formats write it
+            ("Garcia Lopez", Auto, "Garcia", "Lopez", None),
+            ("Smith John", Auto, "Smith", "John", None),
+            // "V" is read as an initial, not a suffix
+            ("Duan V", Auto, "Duan", "V", None),
+            // A lone suffix-like word is the name
+            ("II", Auto, "II", "", None),
+        ];
+
+        for (input, order, family, given, suffix) in cases {
+            assert_eq!(
+                parse_author_name(input, order),
+                (family.to_string(), given.to_string(), suffix.map(String::from)),
+                "{input} ({order:?})"
+            );
+        }
+    }
+
     #[test]
     fn test_split_issns() {
         // Test single ISSN
@@ -513,27 +1535,73 @@ mod tests {
     #[test]
     fn test_parse_pubmed_date() {
         // Test full date
-        let date = parse_pubmed_date("2020 Jun 9").unwrap();
+        let date = parse_pubmed_date_range("2020 Jun 9").unwrap().0;
         assert_eq!(date.year, 2020);
         assert_eq!(date.month, Some(6));
         assert_eq!(date.day, Some(9));
 
         // Test year and month only
-        let date = parse_pubmed_date("2023 May").unwrap();
+        let date = parse_pubmed_date_range("2023 May").unwrap().0;
         assert_eq!(date.year, 2023);
         assert_eq!(date.month, Some(5));
         assert_eq!(date.day, None);
 
         // Test year only
-        let date = parse_pubmed_date("2023").unwrap();
+        let date = parse_pubmed_date_range("2023").unwrap().0;
         assert_eq!(date.year, 2023);
         assert_eq!(date.month, None);
         assert_eq!(date.day, None);
 
         // Test empty string
-        let date = parse_pubmed_date("");
+        let date = parse_pubmed_date_range("");
         assert!(date.is_none());
     }
+    #[test]
+    fn test_parse_pubmed_date_range() {
+        let date = |year, month, day| Date { year, month, day };
+
+        // Single dates have no end
+        assert_eq!(
+            parse_pubmed_date_range("2020 Jun 9"),
+            Some((date(2020, Some(6), Some(9)), None))
+        );
+        assert_eq!(parse_pubmed_date_range("2023"), Some((date(2023, None, None), None)));
+        assert_eq!(
+            parse_pubmed_date_range("2019 Winter"),
+            Some((date(2019, None, None), None))
+        );
+
+        // Month ranges
+        assert_eq!(
+            parse_pubmed_date_range("2020 Jan-Mar"),
+            Some((date(2020, Some(1), None), Some(date(2020, Some(3), None))))
+        );
+        assert_eq!(
+            parse_pubmed_date_range("2021 Mar 15-21"),
+            Some((date(2021, Some(3), Some(15)), Some(date(2021, Some(3), Some(21)))))
+        );
+        assert_eq!(
+            parse_pubmed_date_range("2021 Jan 28-Feb 3"),
+            Some((date(2021, Some(1), Some(28)), Some(date(2021, Some(2), Some(3)))))
+        );
+
+        // Cross-year ranges, with and without the end year
+        assert_eq!(
+            parse_pubmed_date_range("2019 Dec-2020 Jan"),
+            Some((date(2019, Some(12), None), Some(date(2020, Some(1), None))))
+        );
+        assert_eq!(
+            parse_pubmed_date_range("2019 Dec-Jan"),
+            Some((date(2019, Some(12), None), Some(date(2020, Some(1), None))))
+        );
+        assert_eq!(
+            parse_pubmed_date_range("2018-2019"),
+            Some((date(2018, None, None), Some(date(2019, None, None))))
+        );
+
+        assert_eq!(parse_pubmed_date_range("Spring 2020"), None);
+    }
+
     #[test]
     fn test_parse_ris_date() {
         // Test full date
@@ -565,6 +1633,98 @@ mod tests {
         assert!(date.is_none());
     }
 
+    #[rstest]
+    #[case("98///", Some((1998, None, None)))]
+    #[case("03/05/", Some((2003, Some(5), None)))]
+    #[case("29", Some((2029, None, None)))]
+    #[case("30/12/31", Some((1930, Some(12), Some(31))))]
+    #[case("03/15/2021", Some((2021, Some(3), Some(15))))]
+    #[case("15/03/2021", Some((2021, Some(3), Some(15))))]
+    #[case("3/15/2021/Spring", Some((2021, Some(3), Some(15))))]
+    // Either order gives a valid month, so only the year is kept
+    #[case("05/06/2021", Some((2021, None, None)))]
+    #[case("06/06/2021", Some((2021, Some(6), Some(6))))]
+    #[case("45/03/2021", Some((2021, Some(3), None)))]
+    #[case("2021/13/40", Some((2021, None, None)))]
+    #[case("1399///", None)]
+    #[case("2101", None)]
+    #[case("0098///", None)]
+    #[case("+2021//", None)]
+    #[case("Spring 2021", None)]
+    fn test_parse_ris_date_orders(#[case] text: &str, #[case] expected: Option<(i32, Option<u8>, Option<u8>)>) {
+        let expected = expected.map(|(year, month, day)| Date { year, month, day });
+        assert_eq!(parse_ris_date(text), expected, "{text}");
+    }
+
+    #[test]
+    fn test_parse_ris_date_range() {
+        let date = |year, month, day| Date { year, month, day };
+
+        assert_eq!(
+            parse_ris_date_range("2023/05/30/"),
+            Some((date(2023, Some(5), Some(30)), None))
+        );
+        assert_eq!(
+            parse_ris_date_range("1999/12/25/Christmas edition"),
+            Some((date(1999, Some(12), Some(25)), None))
+        );
+        assert_eq!(
+            parse_ris_date_range("2020///Jan-Mar"),
+            Some((date(2020, Some(1), None), Some(date(2020, Some(3), None))))
+        );
+        assert_eq!(
+            parse_ris_date_range("2020 Nov-Dec"),
+            Some((date(2020, Some(11), None), Some(date(2020, Some(12), None))))
+        );
+        assert_eq!(parse_ris_date_range("2023"), Some((date(2023, None, None), None)));
+        assert_eq!(parse_ris_date_range(""), None);
+        assert_eq!(parse_ris_date_range("98"), None);
+        assert_eq!(parse_ris_date_range("2020-3020"), None);
+    }
+
+    #[test]
+    fn test_parse_month_day_range() {
+        let date = |year, month, day| Date { year, month, day };
+
+        assert_eq!(parse_month_day_range(2020, ""), (None, None, None));
+        assert_eq!(parse_month_day_range(2020, "Mar 15"), (Some(3), Some(15), None));
+        assert_eq!(
+            parse_month_day_range(2020, "Nov-Feb"),
+            (Some(11), None, Some(date(2021, Some(2), None)))
+        );
+        assert_eq!(
+            parse_month_day_range(2020, "Dec-2021"),
+            (Some(12), None, Some(date(2021, None, None)))
+        );
+        assert_eq!(parse_month_day_range(2020, "Winter-Spring"), (None, None, None));
+    }
+
+    #[test]
+    fn test_format_month_day_range() {
+        let date = |year, month, day| Date { year, month, day };
+
+        assert_eq!(format_month_day_range(&date(2020, None, None), None), None);
+        assert_eq!(
+            format_month_day_range(&date(2020, Some(3), Some(15)), None).as_deref(),
+            Some("Mar 15")
+        );
+
+        // Every formatted range parses back to the same dates
+        for (start, end, expected) in [
+            (date(2020, Some(1), None), date(2020, Some(3), None), "Jan-Mar"),
+            (date(2019, Some(12), None), date(2020, Some(1), None), "Dec-2020 Jan"),
+            (date(2021, Some(3), Some(15)), date(2021, Some(3), Some(21)), "Mar 15-21"),
+            (date(2021, Some(1), Some(28)), date(2021, Some(2), Some(3)), "Jan 28-Feb 3"),
+        ] {
+            let text = format_month_day_range(&start, Some(&end)).unwrap();
+            assert_eq!(text, expected);
+            assert_eq!(
+                parse_month_day_range(start.year, &text),
+                (start.month, start.day, Some(end))
+            );
+        }
+    }
+
     #[test]
     fn test_parse_endnote_date() {
         // Add tests for EndNote date parsing
@@ -614,6 +1774,29 @@ mod tests {
         assert!(date.is_none());
     }
 
+    #[test]
+    #[cfg(any(feature = "csv", feature = "ris"))]
+    fn test_parse_count() {
+        assert_eq!(parse_count("14"), Some(14));
+        assert_eq!(parse_count(" 1,234 "), Some(1234));
+        assert_eq!(parse_count("12 345"), Some(12345));
+        assert_eq!(parse_count("0"), Some(0));
+        assert_eq!(parse_count(""), None);
+        assert_eq!(parse_count("n/a"), None);
+        assert_eq!(parse_count("-3"), None);
+        assert_eq!(parse_count("12.5"), None);
+        assert_eq!(parse_count("99999999999"), None);
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(format_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_rfc3339(1_792_000_000), "2026-10-14T17:46:40Z");
+        assert_eq!(format_rfc3339(4_102_444_799), "2099-12-31T23:59:59Z");
+        assert!(rfc3339_now().as_str() > "2026-01-01T00:00:00Z");
+    }
+
     #[test]
     fn test_parse_month_name() {
         assert_eq!(parse_month_name("Jan"), Some(1));
@@ -633,4 +1816,61 @@ mod tests {
         assert_eq!(newline_delimiter_of("hello\r\nworld"), "\r\n");
         assert_eq!(newline_delimiter_of("hello\r\nworld\r\n"), "\r\n");
     }
+
+    #[test]
+    fn test_is_placeholder_author() {
+        for name in ["Anonymous", "anonymous.", "[No authors listed]", "et al", "et al.", "N/A", "", "  "] {
+            assert!(is_placeholder_author(name), "{:?}", name);
+        }
+        for name in ["Smith, J", "Anonymi, Paolo", "Na, Minsoo", "Alvarez et al"] {
+            assert!(!is_placeholder_author(name), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_strip_html() {
+        assert_eq!(
+            strip_html(r#"<div data-schema-version="8"><p>First <strong>point</strong></p><p>Second&nbsp;&amp; last</p></div>"#),
+            "First point\nSecond & last"
+        );
+        assert_eq!(strip_html("Line one<br/>Line two"), "Line one\nLine two");
+        assert_eq!(strip_html("caf&#233; &#x2013; ok"), "café – ok");
+        // Text that only looks like markup is kept
+        assert_eq!(strip_html("p < 0.05 & n > 12"), "p < 0.05 & n > 12");
+        assert_eq!(strip_html("plain note"), "plain note");
+    }
+
+    #[test]
+    fn test_append_citations() {
+        let titled = |title: &str| Citation {
+            title: title.to_string(),
+            ..Default::default()
+        };
+        let mut out = vec![titled("existing")];
+
+        let appended = append_citations(&mut out, vec![Ok(titled("a")), Ok(titled("b"))], None);
+        assert_eq!(appended.unwrap(), 2);
+        assert_eq!(out.len(), 3);
+
+        let error = || {
+            ParseError::without_position(
+                crate::CitationFormat::Unknown,
+                crate::ValueError::Syntax("bad".to_string()),
+            )
+        };
+        let appended = append_citations(&mut out, vec![Ok(titled("c")), Err(error())], None);
+        assert!(appended.is_err());
+        assert_eq!(
+            out.iter().map(|c| c.title.as_str()).collect::<Vec<_>>(),
+            vec!["existing", "a", "b"]
+        );
+
+        // Collected errors do not stop the remaining citations
+        let mut errors = Vec::new();
+        let citations = vec![Err(error()), Ok(titled("d")), Err(error())];
+        let appended = append_citations(&mut out, citations, Some(&mut errors));
+        assert_eq!(appended.unwrap(), 1);
+        assert_eq!(out.len(), 4);
+        assert_eq!(errors.len(), 2);
+    }
 }
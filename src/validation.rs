@@ -0,0 +1,630 @@
+//! Checks that citations have the fields their type calls for.
+//!
+//! A journal article without a journal, a book without a publisher or a thesis
+//! without an institution usually means something was lost on the way in.
+//! [`validate`] reads the type of a citation as a [`CitationType`] and reports
+//! the fields missing by the [`ValidationRules`] for that type, along with
+//! fields that contradict the type, as [`ValidationIssue`]s. The citation is
+//! never changed.
+//!
+//! # Examples
+//!
+//! ```
+//! use biblib::Citation;
+//! use biblib::validation::{CitationField, Severity};
+//!
+//! let citation = Citation {
+//!     citation_type: vec!["JOUR".to_string()],
+//!     title: "Early mobilisation after stroke".to_string(),
+//!     pages: Some("12-19".to_string()),
+//!     ..Default::default()
+//! };
+//! let errors: Vec<_> = citation
+//!     .validate()
+//!     .into_iter()
+//!     .filter(|issue| issue.severity == Severity::Error)
+//!     .map(|issue| issue.field)
+//!     .collect();
+//! assert_eq!(errors, vec![CitationField::Journal]);
+//! ```
+
+use crate::Citation;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The kind of work a citation describes, normalized from the labels of
+/// [`Citation::citation_type`] that the formats use, such as RIS `JOUR`,
+/// PubMed "Journal Article" or EndNote "Book Section".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CitationType {
+    /// A journal article
+    JournalArticle,
+    /// A whole book, authored or edited
+    Book,
+    /// A chapter or section of a book
+    BookChapter,
+    /// A paper or abstract in conference proceedings
+    ConferencePaper,
+    /// A thesis or dissertation
+    Thesis,
+    /// A report
+    Report,
+    /// Any other type, or no type at all
+    Other,
+}
+
+impl CitationType {
+    /// Reads the type from the first of `labels` that names one, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use biblib::CitationType;
+    ///
+    /// let labels = ["Journal Article".to_string(), "Review".to_string()];
+    /// assert_eq!(CitationType::from_labels(&labels), CitationType::JournalArticle);
+    /// assert_eq!(CitationType::from_labels(&["CHAP".to_string()]), CitationType::BookChapter);
+    /// assert_eq!(CitationType::from_labels(&[]), CitationType::Other);
+    /// ```
+    #[must_use]
+    pub fn from_labels(labels: &[String]) -> Self {
+        labels
+            .iter()
+            .find_map(|label| Self::from_label(label))
+            .unwrap_or(CitationType::Other)
+    }
+
+    /// Reads the type from one label, e.g. "JOUR" or "Book Section".
+    fn from_label(label: &str) -> Option<Self> {
+        let label = label.trim().to_lowercase();
+        let citation_type = match label.as_str() {
+            "jour" | "jfull" | "ejour" | "journal article" | "journal-article" | "article"
+            | "electronic article" => CitationType::JournalArticle,
+            "book" | "ebook" | "edbook" | "whole book" | "edited book" | "electronic book" => {
+                CitationType::Book
+            }
+            "chap"
+            | "echap"
+            | "book section"
+            | "book chapter"
+            | "book-chapter"
+            | "chapter"
+            | "inbook"
+            | "incollection"
+            | "electronic book section" => CitationType::BookChapter,
+            "conf"
+            | "cpaper"
+            | "conference paper"
+            | "conference proceedings"
+            | "conference proceeding"
+            | "proceedings-article"
+            | "inproceedings"
+            | "conference" => CitationType::ConferencePaper,
+            "thes" | "thesis" | "dissertation" | "doctoral thesis" | "masters thesis"
+            | "phdthesis" | "mastersthesis" => CitationType::Thesis,
+            "rprt" | "report" | "techreport" => CitationType::Report,
+            _ => return None,
+        };
+        Some(citation_type)
+    }
+
+    /// The type as written in issue messages, e.g. "journal article".
+    fn description(self) -> &'static str {
+        match self {
+            CitationType::JournalArticle => "journal article",
+            CitationType::Book => "book",
+            CitationType::BookChapter => "book chapter",
+            CitationType::ConferencePaper => "conference paper",
+            CitationType::Thesis => "thesis",
+            CitationType::Report => "report",
+            CitationType::Other => "citation",
+        }
+    }
+}
+
+/// A standard field checked by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CitationField {
+    /// `title`
+    Title,
+    /// `authors`
+    Authors,
+    /// `journal`, or `journal_abbr` when the name is missing. For book chapters
+    /// this is the title of the book.
+    Journal,
+    /// `date`
+    Date,
+    /// `volume`
+    Volume,
+    /// `issue`
+    Issue,
+    /// `pages`
+    Pages,
+    /// `doi`
+    Doi,
+    /// `publisher`, which holds the institution of a thesis
+    Publisher,
+    /// `abstract_text`
+    Abstract,
+    /// `conference`
+    Conference,
+}
+
+impl CitationField {
+    /// Name of the [`Citation`] field, e.g. "abstract_text".
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            CitationField::Title => "title",
+            CitationField::Authors => "authors",
+            CitationField::Journal => "journal",
+            CitationField::Date => "date",
+            CitationField::Volume => "volume",
+            CitationField::Issue => "issue",
+            CitationField::Pages => "pages",
+            CitationField::Doi => "doi",
+            CitationField::Publisher => "publisher",
+            CitationField::Abstract => "abstract_text",
+            CitationField::Conference => "conference",
+        }
+    }
+
+    /// Whether `citation` has a value for this field, ignoring blank strings.
+    fn is_present(self, citation: &Citation) -> bool {
+        let filled =
+            |value: &Option<String>| value.as_deref().is_some_and(|v| !v.trim().is_empty());
+        match self {
+            CitationField::Title => !citation.title.trim().is_empty(),
+            CitationField::Authors => !citation.authors.is_empty(),
+            CitationField::Journal => filled(&citation.journal) || filled(&citation.journal_abbr),
+            CitationField::Date => citation.date.is_some(),
+            CitationField::Volume => filled(&citation.volume),
+            CitationField::Issue => filled(&citation.issue),
+            CitationField::Pages => filled(&citation.pages),
+            CitationField::Doi => filled(&citation.doi),
+            CitationField::Publisher => filled(&citation.publisher),
+            CitationField::Abstract => filled(&citation.abstract_text),
+            CitationField::Conference => citation.conference.is_some(),
+        }
+    }
+}
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// The citation is usable but probably incomplete or inconsistent
+    Warning,
+    /// The citation lacks a field its type requires
+    Error,
+}
+
+/// Why a field was reported by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssueReason {
+    /// A field in [`TypeRules::required`] is missing
+    MissingRequired,
+    /// A field in [`TypeRules::recommended`] is missing
+    MissingRecommended,
+    /// A journal article has pages but no volume
+    PagesWithoutVolume,
+    /// A journal article has conference details
+    ConferenceInJournalArticle,
+}
+
+/// A problem found by [`validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// [`Severity::Error`] for missing required fields, otherwise
+    /// [`Severity::Warning`]
+    pub severity: Severity,
+    /// The field that is missing or contradicts the type
+    pub field: CitationField,
+    /// What is wrong with the field
+    pub reason: IssueReason,
+    /// The type the citation was checked as
+    pub citation_type: CitationType,
+}
+
+impl fmt::Display for ValidationIssue {
+    /// Writes the issue as e.g. `error: journal missing, required for a journal article`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        let field = self.field.name();
+        let citation_type = self.citation_type.description();
+        match self.reason {
+            IssueReason::MissingRequired => {
+                write!(
+                    f,
+                    "{severity}: {field} missing, required for a {citation_type}"
+                )
+            }
+            IssueReason::MissingRecommended => {
+                write!(
+                    f,
+                    "{severity}: {field} missing, recommended for a {citation_type}"
+                )
+            }
+            IssueReason::PagesWithoutVolume => {
+                write!(f, "{severity}: {field} missing although pages are given")
+            }
+            IssueReason::ConferenceInJournalArticle => {
+                write!(f, "{severity}: {field} given for a {citation_type}")
+            }
+        }
+    }
+}
+
+/// The fields checked for one [`CitationType`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeRules {
+    /// Fields whose absence is an [`Severity::Error`]
+    pub required: Vec<CitationField>,
+    /// Fields whose absence is a [`Severity::Warning`]
+    pub recommended: Vec<CitationField>,
+}
+
+impl TypeRules {
+    /// Rules with the given required and recommended fields.
+    #[must_use]
+    pub fn new(required: &[CitationField], recommended: &[CitationField]) -> Self {
+        Self {
+            required: required.to_vec(),
+            recommended: recommended.to_vec(),
+        }
+    }
+}
+
+/// The rule table used by [`validate`].
+///
+/// The default table is:
+///
+/// | Type | Required | Recommended |
+/// |------|----------|-------------|
+/// | Journal article | journal | authors, date, volume, pages, DOI |
+/// | Book | publisher | authors, date |
+/// | Book chapter | journal (the book title) | authors, date, publisher, pages |
+/// | Conference paper | | authors, date, conference |
+/// | Thesis | publisher (the institution) | authors, date |
+/// | Report | | authors, date, publisher |
+///
+/// Types without rules, [`CitationType::Other`] by default, are not checked
+/// for missing fields.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::CitationType;
+/// use biblib::validation::{CitationField, TypeRules, ValidationRules};
+///
+/// // Also require a DOI for journal articles
+/// let mut rules = ValidationRules::default();
+/// rules.types.insert(
+///     CitationType::JournalArticle,
+///     TypeRules::new(
+///         &[CitationField::Journal, CitationField::Doi],
+///         &[CitationField::Authors, CitationField::Date],
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationRules {
+    /// Required and recommended fields by type
+    pub types: HashMap<CitationType, TypeRules>,
+    /// Whether to report fields that contradict each other or the type: a
+    /// journal article with pages but no volume, or with conference details
+    pub check_consistency: bool,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        use CitationField::*;
+
+        let types = HashMap::from([
+            (
+                CitationType::JournalArticle,
+                TypeRules::new(&[Journal], &[Authors, Date, Volume, Pages, Doi]),
+            ),
+            (
+                CitationType::Book,
+                TypeRules::new(&[Publisher], &[Authors, Date]),
+            ),
+            (
+                CitationType::BookChapter,
+                TypeRules::new(&[Journal], &[Authors, Date, Publisher, Pages]),
+            ),
+            (
+                CitationType::ConferencePaper,
+                TypeRules::new(&[], &[Authors, Date, Conference]),
+            ),
+            (
+                CitationType::Thesis,
+                TypeRules::new(&[Publisher], &[Authors, Date]),
+            ),
+            (
+                CitationType::Report,
+                TypeRules::new(&[], &[Authors, Date, Publisher]),
+            ),
+        ]);
+        Self {
+            types,
+            check_consistency: true,
+        }
+    }
+}
+
+impl ValidationRules {
+    /// A table without rules, for building one from scratch.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            types: HashMap::new(),
+            check_consistency: false,
+        }
+    }
+}
+
+/// Checks `citation` against the `rules` for its type, read with
+/// [`Citation::normalized_type`].
+///
+/// Missing required fields are reported first, then missing recommended fields,
+/// each in the order of the rules, then the consistency checks.
+///
+/// # Examples
+///
+/// ```
+/// use biblib::Citation;
+/// use biblib::validation::{CitationField, IssueReason, ValidationRules, validate};
+///
+/// let thesis = Citation {
+///     citation_type: vec!["THES".to_string()],
+///     title: "Frailty in older adults".to_string(),
+///     ..Default::default()
+/// };
+/// let issues = validate(&thesis, &ValidationRules::default());
+///
+/// assert_eq!(issues[0].field, CitationField::Publisher);
+/// assert_eq!(issues[0].reason, IssueReason::MissingRequired);
+/// ```
+#[must_use]
+pub fn validate(citation: &Citation, rules: &ValidationRules) -> Vec<ValidationIssue> {
+    let citation_type = citation.normalized_type();
+    let mut issues = Vec::new();
+    let mut report = |severity, field, reason| {
+        issues.push(ValidationIssue {
+            severity,
+            field,
+            reason,
+            citation_type,
+        });
+    };
+
+    if let Some(type_rules) = rules.types.get(&citation_type) {
+        for &field in &type_rules.required {
+            if !field.is_present(citation) {
+                report(Severity::Error, field, IssueReason::MissingRequired);
+            }
+        }
+        for &field in &type_rules.recommended {
+            if !field.is_present(citation) {
+                report(Severity::Warning, field, IssueReason::MissingRecommended);
+            }
+        }
+    }
+
+    if rules.check_consistency && citation_type == CitationType::JournalArticle {
+        if CitationField::Pages.is_present(citation) && !CitationField::Volume.is_present(citation)
+        {
+            report(
+                Severity::Warning,
+                CitationField::Volume,
+                IssueReason::PagesWithoutVolume,
+            );
+        }
+        if CitationField::Conference.is_present(citation) {
+            report(
+                Severity::Warning,
+                CitationField::Conference,
+                IssueReason::ConferenceInJournalArticle,
+            );
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, ConferenceInfo, Date};
+    use pretty_assertions::assert_eq;
+
+    fn citation(citation_type: &str) -> Citation {
+        Citation {
+            citation_type: vec![citation_type.to_string()],
+            title: "Early mobilisation after stroke".to_string(),
+            authors: vec![Author {
+                name: "Smith".to_string(),
+                given_name: Some("Jane".to_string()),
+                middle_name: None,
+                suffix: None,
+                affiliations: vec![],
+            }],
+            date: Some(Date {
+                year: 2021,
+                month: None,
+                day: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn issues(citation: &Citation, rules: &ValidationRules) -> Vec<(Severity, CitationField)> {
+        validate(citation, rules)
+            .into_iter()
+            .map(|issue| (issue.severity, issue.field))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_labels() {
+        let cases = [
+            (vec!["JOUR"], CitationType::JournalArticle),
+            (
+                vec!["Journal Article", "Review"],
+                CitationType::JournalArticle,
+            ),
+            (
+                vec!["Comment", "Journal Article"],
+                CitationType::JournalArticle,
+            ),
+            (vec!["Book Section"], CitationType::BookChapter),
+            (vec!["EDBOOK"], CitationType::Book),
+            (vec!["CPAPER"], CitationType::ConferencePaper),
+            (vec![" thesis "], CitationType::Thesis),
+            (vec!["RPRT"], CitationType::Report),
+            (vec!["Letter"], CitationType::Other),
+            (vec![], CitationType::Other),
+        ];
+        for (labels, expected) in cases {
+            let labels: Vec<String> = labels.into_iter().map(str::to_string).collect();
+            assert_eq!(CitationType::from_labels(&labels), expected, "{labels:?}");
+        }
+    }
+
+    #[test]
+    fn test_journal_article_rules() {
+        let rules = ValidationRules::default();
+        let mut article = citation("JOUR");
+        assert_eq!(
+            issues(&article, &rules),
+            vec![
+                (Severity::Error, CitationField::Journal),
+                (Severity::Warning, CitationField::Volume),
+                (Severity::Warning, CitationField::Pages),
+                (Severity::Warning, CitationField::Doi),
+            ]
+        );
+
+        article.journal_abbr = Some("Stroke".to_string());
+        article.volume = Some("52".to_string());
+        article.pages = Some("12-19".to_string());
+        article.doi = Some("10.1161/strokeaha.120.032".to_string());
+        assert!(validate(&article, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_book_chapter_rules() {
+        let rules = ValidationRules::default();
+        let mut chapter = citation("CHAP");
+        chapter.publisher = Some("Springer".to_string());
+        let found = validate(&chapter, &rules);
+        assert_eq!(
+            issues(&chapter, &rules),
+            vec![
+                (Severity::Error, CitationField::Journal),
+                (Severity::Warning, CitationField::Pages),
+            ]
+        );
+        assert_eq!(
+            found[0].to_string(),
+            "error: journal missing, required for a book chapter"
+        );
+
+        // Pages without a volume are only inconsistent for journal articles
+        chapter.journal = Some("Handbook of Stroke Rehabilitation".to_string());
+        chapter.pages = Some("101-120".to_string());
+        assert!(validate(&chapter, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_thesis_rules() {
+        let rules = ValidationRules::default();
+        let mut thesis = citation("Thesis");
+        thesis.authors.clear();
+        assert_eq!(
+            issues(&thesis, &rules),
+            vec![
+                (Severity::Error, CitationField::Publisher),
+                (Severity::Warning, CitationField::Authors),
+            ]
+        );
+
+        thesis.publisher = Some("University of Oxford".to_string());
+        thesis.authors = citation("Thesis").authors;
+        assert!(validate(&thesis, &rules).is_empty());
+    }
+
+    #[test]
+    fn test_consistency_checks() {
+        let mut article = citation("Journal Article");
+        article.journal = Some("Stroke".to_string());
+        article.doi = Some("10.1161/strokeaha.120.032".to_string());
+        article.pages = Some("12-19".to_string());
+        article.conference = Some(ConferenceInfo {
+            name: Some("European Stroke Conference".to_string()),
+            ..Default::default()
+        });
+
+        let found = validate(&article, &ValidationRules::default());
+        let reasons: Vec<_> = found.iter().map(|issue| issue.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                IssueReason::MissingRecommended,
+                IssueReason::PagesWithoutVolume,
+                IssueReason::ConferenceInJournalArticle,
+            ]
+        );
+        assert!(
+            found
+                .iter()
+                .all(|issue| issue.severity == Severity::Warning)
+        );
+        assert_eq!(
+            found[1].to_string(),
+            "warning: volume missing although pages are given"
+        );
+
+        let rules = ValidationRules {
+            check_consistency: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            issues(&article, &rules),
+            vec![(Severity::Warning, CitationField::Volume)]
+        );
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let mut rules = ValidationRules::empty();
+        rules.types.insert(
+            CitationType::Other,
+            TypeRules::new(&[CitationField::Abstract], &[CitationField::Doi]),
+        );
+        rules.types.insert(
+            CitationType::JournalArticle,
+            TypeRules::new(&[CitationField::Doi], &[]),
+        );
+
+        assert_eq!(
+            issues(&citation("Letter"), &rules),
+            vec![
+                (Severity::Error, CitationField::Abstract),
+                (Severity::Warning, CitationField::Doi),
+            ]
+        );
+        // The journal is no longer checked, nor the pages without a volume
+        let mut article = citation("JOUR");
+        article.pages = Some("12-19".to_string());
+        assert_eq!(
+            issues(&article, &rules),
+            vec![(Severity::Error, CitationField::Doi)]
+        );
+        assert!(validate(&citation("THES"), &rules).is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+//! Helpers shared by the citation writers.
+
+pub(crate) mod sanitize;
@@ -0,0 +1,122 @@
+//! Escaping of field values for each output format.
+//!
+//! Values may contain the syntax of the format they are written in, such as a
+//! title holding "\nER  - ", which would end an RIS record early. Writers pass
+//! every value through the function for their format before writing it:
+//!
+//! - RIS: newlines in values become spaces, since each line is one tag, and
+//!   tag patterns such as `ER  -` lose a space so the parser does not split
+//!   the line at them.
+//! - PubMed: newlines in values become spaces, since a line that does not
+//!   start with a space begins a new tag and a blank line ends the record.
+//! - CSV: quoting is left to the csv crate, which honors the configured
+//!   delimiter and quote; only list items joined into one cell are escaped.
+//! - XML: `&`, `<`, `>`, `"` and `'` become entities, and characters XML 1.0
+//!   does not allow are dropped.
+
+#[cfg(any(feature = "ris", feature = "xml", feature = "pubmed"))]
+use std::borrow::Cow;
+
+/// Replaces each newline in an RIS value, including `\r\n` and lone `\r`, with a
+/// space, and writes tag patterns in the value as e.g. `ER -`.
+#[cfg(feature = "ris")]
+pub(crate) fn ris_value(value: &str) -> Cow<'_, str> {
+    use crate::ris::is_embedded_tag_at;
+
+    let value = if value.contains(['\n', '\r']) {
+        Cow::Owned(value.replace("\r\n", " ").replace(['\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(value)
+    };
+
+    let mut tags = (0..value.len())
+        .filter(|&i| is_embedded_tag_at(&value, i))
+        .peekable();
+    if tags.peek().is_none() {
+        return value;
+    }
+
+    // Tags start with an ASCII letter, so every index is a character boundary
+    let mut escaped = String::with_capacity(value.len());
+    let mut copied = 0;
+    for i in tags {
+        escaped.push_str(&value[copied..i + 3]);
+        copied = i + 4;
+    }
+    escaped.push_str(&value[copied..]);
+    Cow::Owned(escaped)
+}
+
+/// Replaces each newline in a PubMed value, including `\r\n` and lone `\r`,
+/// with a space.
+#[cfg(feature = "pubmed")]
+pub(crate) fn pubmed_value(value: &str) -> Cow<'_, str> {
+    if value.contains(['\n', '\r']) {
+        Cow::Owned(value.replace("\r\n", " ").replace(['\n', '\r'], " "))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Replaces `;` in an item of a list the CSV writer joins with `"; "`, so the
+/// parser does not split it into several items.
+#[cfg(feature = "csv")]
+pub(crate) fn csv_list_item(item: &str) -> String {
+    item.replace(';', ",")
+}
+
+/// Escapes XML text or attribute content, dropping characters not allowed in XML 1.0.
+#[cfg(feature = "xml")]
+pub(crate) fn xml_text(text: &str) -> Cow<'_, str> {
+    if text.chars().all(is_xml_char) {
+        return quick_xml::escape::escape(text);
+    }
+    let allowed: String = text.chars().filter(|&c| is_xml_char(c)).collect();
+    Cow::Owned(quick_xml::escape::escape(allowed.as_str()).into_owned())
+}
+
+/// Whether a character may appear in an XML 1.0 document.
+#[cfg(feature = "xml")]
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(feature = "ris")]
+    #[test]
+    fn test_ris_value() {
+        assert!(matches!(ris_value("Plain title"), Cow::Borrowed(_)));
+        assert_eq!(ris_value("Title\nER  - \r\nTY  - JOUR\rEnd"), "Title ER -  TY - JOUR End");
+        assert_eq!(ris_value("TI  - A/KW  - B"), "TI - A/KW - B");
+        // Unknown tags and tags glued to a word are not split by the parser
+        assert!(matches!(ris_value("QQ  - A, WORDKW  - B"), Cow::Borrowed(_)));
+    }
+
+    #[cfg(feature = "pubmed")]
+    #[test]
+    fn test_pubmed_value() {
+        assert!(matches!(pubmed_value("Plain title"), Cow::Borrowed(_)));
+        assert_eq!(pubmed_value("Title\n\r\nPMID- 1\rEnd"), "Title  PMID- 1 End");
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn test_csv_list_item() {
+        assert_eq!(csv_list_item("heart; lung"), "heart, lung");
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_xml_text() {
+        assert!(matches!(xml_text("Plain title"), Cow::Borrowed(_)));
+        assert_eq!(
+            xml_text(r#"<b>"Fish" & 'chips'</b>"#),
+            "&lt;b&gt;&quot;Fish&quot; &amp; &apos;chips&apos;&lt;/b&gt;"
+        );
+        assert_eq!(xml_text("Bell\u{7} & tab\t"), "Bell &amp; tab\t");
+    }
+}
@@ -0,0 +1,13 @@
+//! Shared helpers for the integration tests.
+
+use std::path::PathBuf;
+
+/// Reads a file from `tests/fixtures`.
+pub fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name);
+    std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("failed to read fixture {}: {}", path.display(), e))
+}
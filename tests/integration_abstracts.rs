@@ -0,0 +1,66 @@
+//! Integration tests for abstracts written with format-specific line breaks.
+
+#![cfg(all(
+    feature = "csv",
+    feature = "enw",
+    feature = "pubmed",
+    feature = "ris",
+    feature = "xml"
+))]
+
+use biblib::{CitationParser, CsvParser, EndNoteXmlParser, EnwParser, PubMedParser, RisParser};
+
+/// The abstract every input below holds, once cleaned.
+const ABSTRACT: &str = "Background: Sepsis is a leading cause of death. \
+                        Methods: We enrolled 120 patients in self-assembled cohorts.";
+
+#[test]
+fn test_abstracts_are_identical_across_formats() {
+    // Continuation lines, with a hyphen join and a doubled space
+    let pubmed = "\
+PMID- 12345678
+TI  - Sepsis cohorts.
+AB  - Background: Sepsis is a leading cause of  death. Methods: We enrolled 120
+      patients in self-
+      assembled cohorts.
+";
+    // Carriage returns and an RTF paragraph mark
+    let ris = "TY  - JOUR\r\nTI  - Sepsis cohorts\r\nAB  - Background: Sepsis is a leading cause of death.\\par\r Methods: We enrolled 120 patients in self-assembled cohorts.\r\nER  - \r\n";
+    // A literal "\n" escape and a real line break
+    let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<xml><records><record>
+<titles><title>Sepsis cohorts</title></titles>
+<abstract>Background: Sepsis is a leading cause of death.\\nMethods: We enrolled 120
+patients in self-assembled cohorts.</abstract>
+</record></records></xml>";
+    // Tabs and trailing whitespace
+    let enw = "%0 Journal Article\n%T Sepsis cohorts\n%X Background: Sepsis is a leading cause of death.\tMethods: We enrolled 120 patients in self-assembled cohorts.  \n";
+    // A quoted field spanning lines
+    let csv = "Title,Abstract\nSepsis cohorts,\"Background: Sepsis is a leading cause of death.\r\n\r\nMethods: We enrolled 120 patients in self-assembled cohorts.\"\n";
+
+    let abstracts = [
+        PubMedParser::new().parse(pubmed).unwrap(),
+        RisParser::new().parse(ris).unwrap(),
+        EndNoteXmlParser::new().parse(xml).unwrap(),
+        EnwParser::new().parse(enw).unwrap(),
+        CsvParser::new().parse(csv).unwrap(),
+    ]
+    .map(|citations| citations[0].abstract_text.clone());
+
+    for abstract_text in abstracts {
+        assert_eq!(abstract_text.as_deref(), Some(ABSTRACT));
+    }
+}
+
+#[test]
+fn test_abstract_cleaning_can_be_disabled() {
+    let ris = "TY  - JOUR\nTI  - Sepsis cohorts\nAB  - First line.\\par Second line.\nER  - \n";
+    let citations = RisParser::new()
+        .set_clean_abstracts(false)
+        .parse(ris)
+        .unwrap();
+    assert_eq!(
+        citations[0].abstract_text.as_deref(),
+        Some("First line.\\par Second line.")
+    );
+}
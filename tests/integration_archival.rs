@@ -0,0 +1,218 @@
+//! Compares archival and default parses of the fixtures field by field.
+
+#![cfg(all(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "csv",
+    feature = "dedupe"
+))]
+
+mod common;
+
+use biblib::dedupe::Deduplicator;
+use biblib::{
+    Citation, CitationParser, CsvParser, EndNoteXmlParser, EnwParser, PubMedParser,
+    RAW_FIELD_PREFIX, RisParser,
+};
+use serde_json::Value;
+
+/// Parses `name` with the parser made by `parser`, without and with archival mode.
+fn parse_both<P: CitationParser>(
+    name: &str,
+    parser: impl Fn() -> P,
+    archival: impl Fn(&mut P),
+) -> (Vec<Citation>, Vec<Citation>) {
+    let content = common::fixture(name);
+    let default = parser().parse(&content).unwrap();
+    let mut archival_parser = parser();
+    archival(&mut archival_parser);
+    let archival = archival_parser.parse(&content).unwrap();
+    assert_eq!(default.len(), archival.len(), "{name}");
+    (default, archival)
+}
+
+/// Every parser with archival mode, and the fixtures it reads.
+fn parses() -> Vec<(&'static str, Vec<Citation>, Vec<Citation>)> {
+    let mut parses = Vec::new();
+    for name in [
+        "embase_conference_abstract.ris",
+        "google_scholar.ris",
+        "ovid_embase.ris",
+        "reference_manager.ris",
+        "scopus_export.ris",
+        "zotero_export.ris",
+    ] {
+        let (default, archival) = parse_both(name, RisParser::new, |p| {
+            p.set_archival(true);
+        });
+        parses.push((name, default, archival));
+    }
+    let (default, archival) = parse_both("pubmed_export.nbib", PubMedParser::new, |p| {
+        p.set_archival(true);
+    });
+    parses.push(("pubmed_export.nbib", default, archival));
+    let (default, archival) = parse_both("google_scholar.enw", EnwParser::new, |p| {
+        p.set_archival(true);
+    });
+    parses.push(("google_scholar.enw", default, archival));
+    for name in ["endnote_x9.xml", "endnote_concatenated.xml"] {
+        let (default, archival) = parse_both(name, EndNoteXmlParser::new, |p| {
+            p.set_archival(true);
+        });
+        parses.push((name, default, archival));
+    }
+    for name in ["merged_databases.csv", "scopus_export.csv"] {
+        let (default, archival) = parse_both(name, CsvParser::new, |p| {
+            p.set_archival(true);
+        });
+        parses.push((name, default, archival));
+    }
+    parses
+}
+
+/// Fields archival mode may store differently.
+const NORMALIZED_FIELDS: [&str; 5] = ["pages", "doi", "abstract_text", "issn", "extra_fields"];
+
+fn json(citation: &Citation) -> serde_json::Map<String, Value> {
+    match serde_json::to_value(citation).unwrap() {
+        Value::Object(fields) => fields,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_archival_changes_only_normalized_fields() {
+    for (name, default, archival) in parses() {
+        for (default, archival) in default.iter().zip(&archival) {
+            let (default_fields, archival_fields) = (json(default), json(archival));
+            for (field, value) in &default_fields {
+                if !NORMALIZED_FIELDS.contains(&field.as_str()) {
+                    assert_eq!(value, &archival_fields[field], "{name}: {field}");
+                }
+            }
+
+            // Extra fields are only added to, with dates as written
+            for (key, values) in &default.extra_fields {
+                assert_eq!(
+                    Some(values),
+                    archival.extra_fields.get(key),
+                    "{name}: {key}"
+                );
+            }
+            for key in archival.extra_fields.keys() {
+                assert!(
+                    default.extra_fields.contains_key(key) || key.starts_with(RAW_FIELD_PREFIX),
+                    "{name}: {key}"
+                );
+            }
+
+            assert_eq!(default.pages.is_some(), archival.pages.is_some(), "{name}");
+            if let (Some(doi), Some(raw)) = (&default.doi, &archival.doi) {
+                assert!(
+                    raw.to_lowercase().contains(&doi.to_lowercase()),
+                    "{name}: {raw}"
+                );
+            }
+            let mut cleaned = archival.clone();
+            cleaned.clean_abstract();
+            assert_eq!(cleaned.abstract_text, default.abstract_text, "{name}");
+            assert_eq!(default.issn.len(), archival.issn.len(), "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_archival_keeps_dates_as_written() {
+    for (name, _, archival) in parses() {
+        for citation in archival.iter().filter(|citation| citation.date.is_some()) {
+            let raw_dates = citation
+                .extra_fields
+                .keys()
+                .filter(|key| key.starts_with(RAW_FIELD_PREFIX))
+                .count();
+            assert!(raw_dates > 0, "{name}: {}", citation.title);
+        }
+    }
+}
+
+#[test]
+fn test_archival_values_from_fixtures() {
+    let (_, archival) = parse_both("pubmed_export.nbib", PubMedParser::new, |p| {
+        p.set_archival(true);
+    });
+    assert!(archival[0].extra_fields.contains_key("raw:DP"));
+    assert!(archival[0].extra_fields.contains_key("raw:EDAT"));
+
+    // Ovid writes the DOI with its resolver
+    let (default, archival) = parse_both("ovid_embase.ris", RisParser::new, |p| {
+        p.set_archival(true);
+    });
+    assert_eq!(default[0].doi.as_deref(), Some("10.5555/jccr.2021.045"));
+    assert_eq!(
+        archival[0].doi.as_deref(),
+        Some("https://dx.doi.org/10.5555/jccr.2021.045")
+    );
+
+    let (default, archival) = parse_both("endnote_x9.xml", EndNoteXmlParser::new, |p| {
+        p.set_archival(true);
+    });
+    let index = default
+        .iter()
+        .position(|citation| citation.pages.as_deref() == Some("210-218"))
+        .unwrap();
+    assert_eq!(archival[index].pages.as_deref(), Some("210-8"));
+    let index = default
+        .iter()
+        .position(|citation| citation.doi.as_deref() == Some("10.5555/nrl.2019.077"))
+        .unwrap();
+    assert_eq!(archival[index].doi.as_deref(), Some("10.5555/NRL.2019.077"));
+    assert_eq!(
+        archival[index].extra_fields.get("raw:year"),
+        Some(&vec!["2019".to_string()])
+    );
+}
+
+/// Whether each pair of citations ends up in the same duplicate group.
+fn grouped_together(pairs: Vec<(Citation, Citation)>) -> Vec<bool> {
+    let mut citations = Vec::new();
+    for (index, (mut first, mut second)) in pairs.into_iter().enumerate() {
+        first.set_id(&format!("first {index}"));
+        second.set_id(&format!("second {index}"));
+        citations.extend([first, second]);
+    }
+    let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+    (0..citations.len() / 2)
+        .map(|index| {
+            groups.iter().any(|group| {
+                let ids: Vec<_> = group
+                    .duplicates
+                    .iter()
+                    .chain([&group.unique])
+                    .map(Citation::id)
+                    .collect();
+                ids.contains(&Some(format!("first {index}").as_str()))
+                    && ids.contains(&Some(format!("second {index}").as_str()))
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_archival_citations_deduplicate_like_default_ones() {
+    for (name, default, archival) in parses() {
+        let copies = default
+            .iter()
+            .cloned()
+            .zip(default.iter().cloned())
+            .collect();
+        let expected = grouped_together(copies);
+        assert!(expected.contains(&true), "{name}");
+        assert_eq!(
+            grouped_together(default.into_iter().zip(archival).collect()),
+            expected,
+            "{name}"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! Integration tests for BibTeX (.bib) exports.
+
+#![cfg(feature = "bibtex")]
+
+mod common;
+
+use biblib::{BibTexParser, CitationFormat, CitationParser, CitationType, detect_and_parse};
+
+#[test]
+fn test_zotero_export() {
+    let content = common::fixture("zotero_export.bib");
+    let citations = BibTexParser::new().parse(&content).unwrap();
+    assert_eq!(citations.len(), 4);
+
+    let article = &citations[0];
+    assert_eq!(article.citation_type, vec!["article"]);
+    assert_eq!(article.normalized_type(), CitationType::JournalArticle);
+    assert_eq!(article.title, "Deep learning");
+    let names: Vec<&str> = article.authors.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["LeCun", "Bengio", "Hinton"]);
+    assert_eq!(article.journal.as_deref(), Some("Nature"));
+    assert_eq!(article.volume.as_deref(), Some("521"));
+    assert_eq!(article.issue.as_deref(), Some("7553"));
+    assert_eq!(article.pages.as_deref(), Some("436-444"));
+    assert_eq!(article.issn, vec!["1476-4687"]);
+    assert_eq!(article.doi.as_deref(), Some("10.1038/nature14539"));
+    let date = article.date.as_ref().unwrap();
+    assert_eq!((date.year, date.month), (2015, Some(5)));
+    assert_eq!(article.language.as_deref(), Some("en"));
+    assert_eq!(
+        article.keywords,
+        vec!["Computer science", "Mathematics and computing"]
+    );
+    assert_eq!(article.extra_fields["urldate"], vec!["2024-03-01"]);
+    assert_eq!(article.extra_fields["reference_id"], vec!["lecun_deep_2015"]);
+
+    let paper = &citations[1];
+    assert_eq!(paper.normalized_type(), CitationType::ConferencePaper);
+    assert_eq!(paper.title, "Attention is All you Need");
+    assert_eq!(paper.authors.len(), 3);
+    assert_eq!(
+        paper.journal.as_deref(),
+        Some("Advances in Neural Information Processing Systems")
+    );
+    assert_eq!(paper.pages.as_deref(), Some("5998-6008"));
+    assert_eq!(paper.extra_fields["address"], vec!["Long Beach, CA"]);
+
+    let book = &citations[2];
+    assert_eq!(book.normalized_type(), CitationType::Book);
+    assert_eq!(book.publisher.as_deref(), Some("MIT Press"));
+    assert_eq!(book.extra_fields["isbn"], vec!["978-0-262-03561-3"]);
+
+    let thesis = &citations[3];
+    assert_eq!(thesis.normalized_type(), CitationType::Thesis);
+    assert_eq!(
+        thesis.title,
+        "Aprendizaje automático para la detección de delirium en UCI"
+    );
+    assert_eq!(thesis.authors[0].name, "García-Márquez");
+    assert_eq!(thesis.authors[0].given_name.as_deref(), Some("José"));
+    assert_eq!(thesis.authors[1].name, "Öztürk");
+    assert_eq!(thesis.authors[1].given_name.as_deref(), Some("Ayşe"));
+    assert_eq!(thesis.extra_fields["school"], vec!["Universidad de Granada"]);
+}
+
+#[test]
+fn test_detect_and_parse_recognizes_bibtex() {
+    let content = common::fixture("zotero_export.bib");
+    let (citations, format) = detect_and_parse(&content).unwrap();
+    assert_eq!(format, CitationFormat::BibTex);
+    assert_eq!(citations.len(), 4);
+    assert!(citations.iter().all(|c| c.parsed_from == Some(CitationFormat::BibTex)));
+}
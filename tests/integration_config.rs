@@ -0,0 +1,333 @@
+//! Integration tests for loading parser and deduplicator configurations from JSON.
+
+#![cfg(all(
+    feature = "csv",
+    feature = "dedupe",
+    feature = "pubmed",
+    feature = "ris",
+    feature = "xml"
+))]
+
+use biblib::csv::{AuditConfig, CsvConfig, HeaderLanguage};
+use biblib::dedupe::{DedupeField, Deduplicator, DeduplicatorConfig, GroupingMode, YearSource};
+use biblib::{
+    Citation, CitationParser, CsvParser, Date, EndNoteXmlParser, LenientOptions, NameOrder,
+    PubMedParser, RisParser,
+};
+use serde_json::json;
+
+fn citation(title: &str, journal: &str, doi: &str, source: &str) -> Citation {
+    let mut citation = Citation {
+        title: title.to_string(),
+        journal: Some(journal.to_string()),
+        doi: Some(doi.to_string()),
+        volume: Some("12".to_string()),
+        pages: Some("101-108".to_string()),
+        date: Some(Date {
+            year: 2021,
+            month: None,
+            day: None,
+        }),
+        ..Default::default()
+    };
+    citation.set_source(source);
+    citation
+}
+
+#[test]
+fn test_deduplicator_config_from_json() {
+    let config: DeduplicatorConfig = serde_json::from_value(json!({
+        "group_by_year": true,
+        "run_in_parallel": true,
+        "source_preferences": ["PubMed", "Embase"],
+        "grouping_mode": "transitive_closure",
+        "year_source": "print_only",
+        "ignore_fields": ["issn", "volume"],
+        "doi_denylist": [" 10.9999/PLACEHOLDER "],
+        "max_group_size": 5,
+        "use_abstract_similarity": true,
+        "short_title_length": 20,
+        "abstract_similarity_threshold": 0.6,
+        "exact_title_fast_path": false,
+        "require_author_match": false,
+        "author_overlap_threshold": 0.8,
+        "compared_authors": 3
+    }))
+    .unwrap();
+    let built = DeduplicatorConfig::builder()
+        .group_by_year(true)
+        .run_in_parallel(true)
+        .source_preferences(["PubMed", "Embase"])
+        .grouping_mode(GroupingMode::TransitiveClosure)
+        .year_source(YearSource::PrintOnly)
+        .ignore_fields([DedupeField::Issn, DedupeField::Volume])
+        .doi_denylist(["10.9999/placeholder"])
+        .max_group_size(Some(5))
+        .use_abstract_similarity(true)
+        .short_title_length(20)
+        .abstract_similarity_threshold(0.6)
+        .exact_title_fast_path(false)
+        .require_author_match(false)
+        .author_overlap_threshold(0.8)
+        .compared_authors(3)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.group_by_year, built.group_by_year);
+    assert_eq!(config.run_in_parallel, built.run_in_parallel);
+    assert_eq!(config.source_preferences, built.source_preferences);
+    assert_eq!(config.grouping_mode, built.grouping_mode);
+    assert_eq!(config.year_source, built.year_source);
+    assert_eq!(config.ignore_fields, built.ignore_fields);
+    // Validation normalizes the denylist as the builder does
+    assert_eq!(config.doi_denylist, built.doi_denylist);
+    assert_eq!(config.max_group_size, built.max_group_size);
+    assert_eq!(
+        config.use_abstract_similarity,
+        built.use_abstract_similarity
+    );
+    assert_eq!(config.short_title_length, built.short_title_length);
+    assert_eq!(
+        config.abstract_similarity_threshold,
+        built.abstract_similarity_threshold
+    );
+    assert_eq!(config.exact_title_fast_path, built.exact_title_fast_path);
+    assert_eq!(config.require_author_match, built.require_author_match);
+    assert_eq!(config.author_overlap_threshold, built.author_overlap_threshold);
+    assert_eq!(config.compared_authors, built.compared_authors);
+    assert!(config.custom_filter.is_none());
+
+    let citations = vec![
+        citation("Sepsis in older adults", "Crit Care", "10.1/a", "Embase"),
+        citation("Sepsis in older adults", "Crit Care", "10.1/a", "PubMed"),
+        citation("Delirium after surgery", "Anaesthesia", "10.1/b", "Embase"),
+        citation("Another work", "Lancet", "10.9999/PLACEHOLDER", "Embase"),
+    ];
+    let summarize = |config: DeduplicatorConfig| {
+        Deduplicator::new()
+            .with_config(config)
+            .find_duplicates(&citations)
+            .unwrap()
+            .into_iter()
+            .map(|group| {
+                let sources: Vec<_> = group
+                    .duplicates
+                    .iter()
+                    .map(|c| c.source().map(str::to_string))
+                    .collect();
+                (
+                    group.unique.source().map(str::to_string),
+                    group.unique.title,
+                    sources,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let groups = summarize(config.clone());
+    assert_eq!(groups, summarize(built));
+    assert_eq!(groups[0].0.as_deref(), Some("PubMed"));
+
+    // Serializing and reading back keeps every option
+    let reloaded: DeduplicatorConfig =
+        serde_json::from_value(serde_json::to_value(&config).unwrap()).unwrap();
+    assert_eq!(reloaded.ignore_fields, config.ignore_fields);
+    assert_eq!(
+        serde_json::to_value(&reloaded).unwrap(),
+        serde_json::to_value(&config).unwrap()
+    );
+}
+
+#[test]
+fn test_deduplicator_config_defaults_and_validation() {
+    let config: DeduplicatorConfig = serde_json::from_str(r#"{"max_group_size": 3}"#).unwrap();
+    let default = DeduplicatorConfig::default();
+    assert_eq!(config.max_group_size, Some(3));
+    assert_eq!(config.grouping_mode, default.grouping_mode);
+    assert_eq!(config.doi_denylist, default.doi_denylist);
+    assert_eq!(config.short_title_length, default.short_title_length);
+
+    for invalid in [
+        json!({"max_group_size": 0}),
+        json!({"abstract_similarity_threshold": 1.5}),
+        json!({"author_overlap_threshold": -0.5}),
+        json!({"compared_authors": 0}),
+        json!({"source_preferences": ["PubMed", "PubMed"]}),
+        json!({"doi_denylist": [" "]}),
+        json!({"grouping_mode": "closest"}),
+        json!({"group_by_yaer": true}),
+    ] {
+        assert!(
+            serde_json::from_value::<DeduplicatorConfig>(invalid.clone()).is_err(),
+            "{invalid} was accepted"
+        );
+    }
+}
+
+#[test]
+fn test_csv_config_from_json() {
+    let config: CsvConfig = serde_json::from_value(json!({
+        "header_mappings": {"title": ["Article Name"]},
+        "header_aliases": {"journal": ["Revue scientifique"]},
+        "header_languages": ["german"],
+        "delimiter": ";",
+        "has_header": true,
+        "quote": "'",
+        "trim": false,
+        "flexible": true,
+        "store_original_record": true,
+        "name_order": "given_first",
+        "require_title_column": false
+    }))
+    .unwrap();
+    let mut built = CsvConfig::new();
+    built
+        .set_header_mapping("title", vec!["Article Name".to_string()])
+        .add_header_aliases("journal", vec!["Revue scientifique".to_string()])
+        .add_header_language(HeaderLanguage::German)
+        .set_delimiter(b';')
+        .set_has_header(true)
+        .set_quote(b'\'')
+        .set_trim(false)
+        .set_flexible(true)
+        .set_store_original_record(true)
+        .set_name_order(NameOrder::GivenFirst)
+        .set_require_title_column(false);
+    assert_eq!(config.get_field_mappings(), built.get_field_mappings());
+
+    let input = "Article Name;Autoren;Revue scientifique;Jahr\n\
+                 'Delirium; a review';John Smith;Age Ageing;2020\n";
+    let parse = |config: CsvConfig| {
+        let citations = CsvParser::with_config(config).parse(input).unwrap();
+        serde_json::to_value(citations).unwrap()
+    };
+    let parsed = CsvParser::with_config(config.clone()).parse(input).unwrap();
+    assert_eq!(parse(config.clone()), parse(built));
+    assert_eq!(parsed[0].title, "Delirium; a review");
+    assert_eq!(parsed[0].authors[0].name, "Smith");
+    assert_eq!(parsed[0].journal.as_deref(), Some("Age Ageing"));
+
+    // The serialized form lists every mapping and reads back the same
+    let json = serde_json::to_value(&config).unwrap();
+    assert_eq!(json["delimiter"], ";");
+    assert_eq!(json["name_order"], "given_first");
+    let reloaded: CsvConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(reloaded.get_field_mappings(), config.get_field_mappings());
+    assert_eq!(parse(reloaded), parse(config));
+
+    for invalid in [
+        json!({"delimiter": ";;"}),
+        json!({"delimiter": "§"}),
+        json!({"delimiter": "\n"}),
+        json!({"header_mappings": {"title": []}}),
+        json!({"header_languages": ["klingon"]}),
+    ] {
+        assert!(
+            serde_json::from_value::<CsvConfig>(invalid.clone()).is_err(),
+            "{invalid} was accepted"
+        );
+    }
+}
+
+#[test]
+fn test_parser_options_from_json() {
+    let parser: RisParser = serde_json::from_value(json!({
+        "dedupe_keywords": true,
+        "tag_mappings": [["M3", "DO"]],
+        "times_cited_tag": "C8",
+        "drop_truncated": true,
+        "max_abstract_length": 12,
+        "max_authors": 1,
+        "stamp_ingestion_time": true,
+        "fold_typography": true,
+        "sentence_case_titles": true,
+        "raw_abstracts": true,
+        "strip_note_html": true,
+        "split_attachments": true,
+        "archival": true
+    }))
+    .unwrap();
+    let mut built = RisParser::new();
+    built
+        .set_dedupe_keywords(true)
+        .set_tag_mapping("M3", "DO")
+        .set_times_cited_tag("C8")
+        .set_drop_truncated(true)
+        .set_max_abstract_length(Some(12))
+        .set_max_authors(Some(1))
+        .set_stamp_ingestion_time(true)
+        .set_fold_typography(true)
+        .set_sentence_case_titles(true)
+        .set_clean_abstracts(false)
+        .set_strip_note_html(true)
+        .set_split_attachments(true)
+        .set_archival(true);
+    assert_eq!(
+        serde_json::to_value(&parser).unwrap(),
+        serde_json::to_value(&built).unwrap()
+    );
+    let input = "TY  - JOUR\nTI  - Example\nAU  - Smith, J\nAU  - Doe, A\n\
+                 M3  - 10.1000/example\nC8  - 1,204\nER  - \n";
+    let citation = &parser.parse(input).unwrap()[0];
+    assert_eq!(citation.authors.len(), 1);
+    assert_eq!(citation.doi.as_deref(), Some("10.1000/example"));
+    assert_eq!(citation.times_cited, Some(1204));
+
+    // Missing options keep their defaults
+    let mut built = PubMedParser::new();
+    built.set_max_authors(Some(3));
+    let parser: PubMedParser = serde_json::from_str(r#"{"max_authors": 3}"#).unwrap();
+    assert_eq!(
+        serde_json::to_value(&parser).unwrap(),
+        serde_json::to_value(&built).unwrap()
+    );
+    let parser: EndNoteXmlParser = serde_json::from_str(r#"{"max_depth": 8}"#).unwrap();
+    let mut built = EndNoteXmlParser::new();
+    built.set_max_depth(8);
+    assert_eq!(
+        serde_json::to_value(&parser).unwrap(),
+        serde_json::to_value(&built).unwrap()
+    );
+    assert!(serde_json::from_str::<RisParser>(r#"{"max_autors": 3}"#).is_err());
+
+    // The CSV parser nests its configuration
+    let parser: CsvParser = serde_json::from_value(json!({
+        "auto_detect": true,
+        "max_authors": 2,
+        "config": {"delimiter": "\t"}
+    }))
+    .unwrap();
+    let mut config = CsvConfig::new();
+    config.set_delimiter(b'\t');
+    let mut built = CsvParser::with_config(config);
+    built.set_auto_detection(true).set_max_authors(Some(2));
+    assert_eq!(
+        serde_json::to_value(&parser).unwrap(),
+        serde_json::to_value(&built).unwrap()
+    );
+}
+
+#[test]
+fn test_lenient_and_audit_options_from_json() {
+    let options: LenientOptions =
+        serde_json::from_str(r#"{"max_error_ratio": 0.5, "max_errors": 100}"#).unwrap();
+    assert_eq!(
+        options,
+        LenientOptions {
+            max_error_ratio: Some(0.5),
+            max_errors: Some(100),
+        }
+    );
+    assert_eq!(
+        serde_json::from_str::<LenientOptions>("{}").unwrap(),
+        LenientOptions::default()
+    );
+
+    let audit: AuditConfig = serde_json::from_str(r#"{"max_title_length": 300}"#).unwrap();
+    assert_eq!(
+        audit,
+        AuditConfig {
+            max_title_length: 300,
+            ..Default::default()
+        }
+    );
+}
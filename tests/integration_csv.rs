@@ -0,0 +1,178 @@
+//! Integration tests against a Scopus CSV export.
+
+#![cfg(feature = "csv")]
+
+mod common;
+
+use biblib::csv::{AuditConfig, AuditReason};
+use biblib::{Citation, CitationFormat, CitationParser, CsvParser};
+
+fn parse_fixture() -> Vec<Citation> {
+    CsvParser::new()
+        .parse(&common::fixture("scopus_export.csv"))
+        .unwrap()
+}
+
+#[test]
+fn test_record_count_and_identifiers() {
+    let citations = parse_fixture();
+    assert_eq!(citations.len(), 5);
+    assert!(citations.iter().all(|c| c.parsed_from == Some(CitationFormat::Csv)));
+
+    let dois: Vec<&str> = citations.iter().filter_map(|c| c.doi.as_deref()).collect();
+    assert_eq!(
+        dois,
+        vec![
+            "10.5555/jccr.2021.045",
+            "10.5555/cci.2020.0012",
+            "10.5555/dmcn.2022.064",
+            "10.5555/gtl.2021.020",
+            "10.5555/ghe.2023.007",
+        ]
+    );
+
+    let years: Vec<i32> = citations
+        .iter()
+        .map(|c| c.date.as_ref().unwrap().year)
+        .collect();
+    assert_eq!(years, vec![2021, 2020, 2022, 2021, 2023]);
+}
+
+#[test]
+fn test_semicolon_separated_authors() {
+    let citations = parse_fixture();
+
+    let counts: Vec<usize> = citations.iter().map(|c| c.authors.len()).collect();
+    assert_eq!(counts, vec![3, 4, 3, 3, 1]);
+
+    let author = &citations[1].authors[1];
+    assert_eq!(author.name, "Nguyen");
+    assert_eq!(author.given_name.as_deref(), Some("T.L."));
+
+    // Non-ASCII family names survive unchanged
+    let families: Vec<&str> = citations[3]
+        .authors
+        .iter()
+        .map(|a| a.name.as_str())
+        .collect();
+    assert_eq!(families, vec!["Sørensen", "Mäkinen", "Żukowski"]);
+}
+
+#[test]
+fn test_scopus_columns() {
+    let citations = parse_fixture();
+    let citation = &citations[0];
+
+    assert_eq!(citation.citation_type, vec!["Journal Article"]);
+    assert_eq!(
+        citation.journal.as_deref(),
+        Some("Journal of Critical Care Research")
+    );
+    assert_eq!(citation.volume.as_deref(), Some("45"));
+    assert_eq!(citation.issue.as_deref(), Some("3"));
+    assert_eq!(citation.publisher.as_deref(), Some("Example Press"));
+    assert_eq!(citation.urls.len(), 1);
+    assert!(
+        citation
+            .abstract_text
+            .as_deref()
+            .unwrap()
+            .ends_with("Example Press")
+    );
+
+    // The "Source" column names the database
+    assert_eq!(citation.source(), Some("Scopus"));
+
+    // Columns without a default mapping are kept verbatim
+    assert_eq!(citation.extra_fields["EID"], vec!["2-s2.0-85100000001"]);
+    assert_eq!(citation.extra_fields["Cited by"], vec!["14"]);
+    assert_eq!(citation.extra_fields["Page start"], vec!["210"]);
+
+    // Empty cells are not recorded (issue, pages and cited-by)
+    let in_press = &citations[4];
+    assert_eq!(in_press.issue, None);
+    assert!(!in_press.extra_fields.contains_key("Cited by"));
+}
+
+#[test]
+fn test_scopus_preset() {
+    let citations = CsvParser::scopus()
+        .parse(&common::fixture("scopus_export.csv"))
+        .unwrap();
+    assert_eq!(citations.len(), 5);
+
+    let citation = &citations[0];
+    assert_eq!(citation.citation_type, vec!["Article"]);
+    assert_eq!(citation.language.as_deref(), Some("English"));
+    // "delirium" appears in both keyword columns and is kept once
+    assert_eq!(
+        citation.keywords,
+        vec![
+            "delirium",
+            "early mobilisation",
+            "intensive care",
+            "adult",
+            "article",
+            "human",
+        ]
+    );
+    assert!(!citation.extra_fields.contains_key("Document Type"));
+    assert!(!citation.extra_fields.contains_key("Author Keywords"));
+    assert_eq!(citation.extra_fields["EID"], vec!["2-s2.0-85100000001"]);
+
+    // "Cited by" is read as the times-cited count, and left unset when empty
+    assert_eq!(citation.times_cited, Some(14));
+    assert!(!citation.extra_fields.contains_key("Cited by"));
+    assert_eq!(citations[4].times_cited, None);
+}
+
+#[test]
+fn test_merge_datasets_with_shared_ids() {
+    let parser = CsvParser::new();
+    let scopus = parser
+        .parse("id,title,year\n1,Prone positioning in ARDS,2013\n2,Sepsis bundles,2019\n")
+        .unwrap();
+    let wos = parser
+        .parse("id,title,year\n1,Early goal-directed therapy,2001\n")
+        .unwrap();
+    assert_eq!(scopus[0].id(), wos[0].id());
+
+    let merged = biblib::merge_datasets(vec![
+        ("scopus.csv".to_string(), scopus),
+        ("wos.csv".to_string(), wos),
+    ]);
+    assert_eq!(merged.citations.len(), 3);
+
+    let ids: std::collections::HashSet<_> = merged.citations.iter().filter_map(Citation::id).collect();
+    assert_eq!(ids.len(), 3);
+    let early = &merged.citations[2];
+    assert_eq!(early.title, "Early goal-directed therapy");
+    assert_eq!(early.id(), Some("wos.csv:1"));
+    assert_eq!(early.extra_fields[biblib::dataset::ORIGINAL_ID_FIELD], vec!["1"]);
+    assert_eq!(early.source(), Some("wos.csv"));
+    assert_eq!(merged.citations[0].id(), Some("1"));
+    assert_eq!(merged.citations[0].source(), Some("scopus.csv"));
+    assert_eq!(merged.ids("wos.csv").collect::<Vec<_>>(), vec!["wos.csv:1"]);
+    assert_eq!(merged.label_of(1), Some("scopus.csv"));
+}
+
+#[test]
+fn test_audit_swapped_title_and_abstract() {
+    // The header names the abstract column "Title" and the title column "Abstract"
+    let input = "\
+Authors,Title,Abstract,Year,Volume
+\"Nguyen, T.\",\"Background: Early mobilisation is recommended after cardiac surgery. We enrolled 40 patients. Mobilisation shortened the hospital stay.\",Effect of early mobilisation after cardiac surgery,2021,30
+\"Okafor, C.\",Bedside handover and patient safety,\"Handover was observed on four wards.\",2020,12
+";
+    let (citations, findings) = CsvParser::new()
+        .parse_with_audit(input, &AuditConfig::default())
+        .unwrap();
+
+    assert_eq!(citations.len(), 2);
+    assert!(citations[0].title.starts_with("Background:"));
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].row, 1);
+    assert_eq!(findings[0].field, "title");
+    assert_eq!(findings[0].reason, AuditReason::MultipleSentences { count: 3 });
+    assert!(findings[0].excerpt.starts_with("Background: Early mobilisation"));
+}
@@ -0,0 +1,354 @@
+//! Deduplication across the combined fixture corpus.
+//!
+//! Four works appear in more than one export:
+//!
+//! - the early mobilisation trial (PubMed, Embase, Scopus, EndNote)
+//! - the sepsis prediction study (PubMed, Google Scholar, Scopus)
+//! - the vitamin D review (PubMed, Embase, EndNote)
+//! - the brain injury cohort (PubMed, Google Scholar, Scopus)
+//!
+//! A conference abstract of the mobilisation trial from the previous year
+//! must stay separate from the full article.
+
+#![cfg(all(
+    feature = "dedupe",
+    feature = "csv",
+    feature = "pubmed",
+    feature = "ris",
+    feature = "xml"
+))]
+
+mod common;
+
+use biblib::dedupe::{Deduplicator, DeduplicatorConfig, to_canonical_map};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use biblib::{
+    Citation, CitationParser, CsvParser, DuplicateGroup, EndNoteXmlParser, PubMedParser, RisParser,
+    merge_datasets,
+};
+
+const SOURCES: [&str; 5] = ["PubMed", "Google Scholar", "Embase", "Scopus", "EndNote"];
+
+fn load_corpus() -> (Vec<Citation>, Vec<&'static str>) {
+    let exports = [
+        PubMedParser::new().parse(&common::fixture("pubmed_export.nbib")),
+        RisParser::new().parse(&common::fixture("google_scholar.ris")),
+        RisParser::new().parse(&common::fixture("ovid_embase.ris")),
+        CsvParser::new().parse(&common::fixture("scopus_export.csv")),
+        EndNoteXmlParser::new().parse(&common::fixture("endnote_x9.xml")),
+    ];
+
+    let mut citations = Vec::new();
+    let mut sources = Vec::new();
+    for (export, source) in exports.into_iter().zip(SOURCES) {
+        let export = export.unwrap();
+        sources.extend(std::iter::repeat_n(source, export.len()));
+        citations.extend(export);
+    }
+    (citations, sources)
+}
+
+fn group_for<'a>(groups: &'a [DuplicateGroup], title_prefix: &str) -> &'a DuplicateGroup {
+    groups
+        .iter()
+        .find(|g| g.unique.title.starts_with(title_prefix))
+        .unwrap_or_else(|| panic!("no group for {:?}", title_prefix))
+}
+
+#[test]
+fn test_combined_corpus_groups() {
+    let (citations, _) = load_corpus();
+    assert_eq!(citations.len(), 30);
+
+    let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+    assert_eq!(groups.len(), 21);
+
+    let duplicates: usize = groups.iter().map(|g| g.duplicates.len()).sum();
+    assert_eq!(duplicates, 9);
+
+    let mut sizes: Vec<usize> = groups
+        .iter()
+        .filter(|g| !g.duplicates.is_empty())
+        .map(|g| g.duplicates.len() + 1)
+        .collect();
+    sizes.sort_unstable();
+    assert_eq!(sizes, vec![3, 3, 3, 4]);
+}
+
+#[test]
+fn test_groups_match_on_doi_and_without_doi() {
+    let (citations, _) = load_corpus();
+    let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+
+    let trial = group_for(
+        &groups,
+        "Early mobilisation and delirium in critically ill adults: a",
+    );
+    assert_eq!(trial.duplicates.len(), 3);
+    assert!(
+        trial
+            .duplicates
+            .iter()
+            .all(|c| c.doi.as_deref() == Some("10.5555/jccr.2021.045"))
+    );
+
+    // Google Scholar records have no DOI and must match on title, volume and journal
+    let sepsis = group_for(&groups, "Machine learning prediction of sepsis");
+    assert_eq!(sepsis.duplicates.len(), 2);
+    assert!(sepsis.duplicates.iter().any(|c| c.doi.is_none()));
+
+    let conference = group_for(
+        &groups,
+        "Early mobilisation and delirium in critically ill adults: Preliminary",
+    );
+    assert!(conference.duplicates.is_empty());
+}
+
+#[test]
+fn test_unique_prefers_record_with_abstract_and_doi() {
+    let (citations, _) = load_corpus();
+    let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+
+    for group in groups.iter().filter(|g| !g.duplicates.is_empty()) {
+        assert!(
+            group.unique.abstract_text.is_some(),
+            "{}",
+            group.unique.title
+        );
+        assert!(group.unique.doi.is_some(), "{}", group.unique.title);
+    }
+}
+
+#[test]
+fn test_source_preferences_from_csv_database_column() {
+    let citations = CsvParser::new()
+        .parse(&common::fixture("merged_databases.csv"))
+        .unwrap();
+    let sources: Vec<Option<&str>> = citations.iter().map(|c| c.source()).collect();
+    assert_eq!(
+        sources,
+        vec![
+            Some("PubMed"),
+            Some("Embase"),
+            None,
+            Some("Scopus"),
+            Some("Embase"),
+            Some("CINAHL"),
+        ]
+    );
+
+    let unique_sources = |preferences: [&str; 2]| {
+        let groups = Deduplicator::new()
+            .with_config(
+                DeduplicatorConfig::builder()
+                    .source_preferences(preferences)
+                    .build()
+                    .unwrap(),
+            )
+            .find_duplicates(&citations)
+            .unwrap();
+        assert_eq!(groups.len(), 3);
+        let mut sources: Vec<(String, Option<String>)> = groups
+            .iter()
+            .map(|g| (g.unique.title.clone(), g.unique.source().map(str::to_string)))
+            .collect();
+        sources.sort();
+        sources.into_iter().map(|(_, source)| source).collect::<Vec<_>>()
+    };
+
+    // Groups sorted by title: mobilisation, hand hygiene, vitamin D
+    assert_eq!(
+        unique_sources(["Embase", "PubMed"]),
+        vec![Some("Embase".into()), Some("CINAHL".into()), Some("Embase".into())]
+    );
+    assert_eq!(
+        unique_sources(["PubMed", "Scopus"]),
+        vec![Some("PubMed".into()), Some("CINAHL".into()), Some("Scopus".into())]
+    );
+}
+
+#[test]
+fn test_source_preferences() {
+    let (citations, sources) = load_corpus();
+    let groups = Deduplicator::new()
+        .with_config(
+            DeduplicatorConfig::builder()
+                .source_preferences(["Embase", "Scopus"])
+                .build()
+                .unwrap(),
+        )
+        .find_duplicates_with_sources(&citations, &sources)
+        .unwrap();
+    assert_eq!(groups.len(), 21);
+
+    // The Embase record wins where one exists, otherwise Scopus
+    let trial = group_for(
+        &groups,
+        "Early mobilisation and delirium in critically ill adults: A",
+    );
+    assert_eq!(trial.unique.extra_fields["DB"], vec!["Embase"]);
+
+    let sepsis = group_for(&groups, "Machine learning prediction of sepsis");
+    assert!(sepsis.unique.extra_fields.contains_key("EID"));
+}
+
+#[test]
+fn test_duplicate_pairs_match_groups() {
+    let (mut citations, sources) = load_corpus();
+    // Tag each citation with its index so group members can be traced back
+    for (i, citation) in citations.iter_mut().enumerate() {
+        citation
+            .extra_fields
+            .insert("corpus-index".to_string(), vec![i.to_string()]);
+    }
+    let index_of = |c: &Citation| c.extra_fields["corpus-index"][0].parse::<usize>().unwrap();
+
+    for run_in_parallel in [false, true] {
+        let config = DeduplicatorConfig::builder()
+            .group_by_year(true)
+            .run_in_parallel(run_in_parallel)
+            .source_preferences(["Embase", "Scopus"])
+            .build()
+            .unwrap();
+
+        let expected: BTreeSet<(usize, usize)> = Deduplicator::new()
+            .with_config(config.clone())
+            .find_duplicates_with_sources(&citations, &sources)
+            .unwrap()
+            .iter()
+            .flat_map(|g| {
+                g.duplicates
+                    .iter()
+                    .map(|d| (index_of(&g.unique), index_of(d)))
+            })
+            .collect();
+        assert_eq!(expected.len(), 9);
+
+        let pairs = Mutex::new(Vec::new());
+        Deduplicator::new()
+            .with_config(config)
+            .for_each_duplicate_pair_with_sources(&citations, &sources, |unique, duplicate, score| {
+                assert!((0.0..=1.0).contains(&score));
+                pairs.lock().unwrap().push((unique, duplicate));
+            })
+            .unwrap();
+        let pairs = pairs.into_inner().unwrap();
+        assert_eq!(pairs.len(), expected.len());
+        assert_eq!(pairs.into_iter().collect::<BTreeSet<_>>(), expected);
+    }
+}
+
+#[test]
+fn test_records_without_authors_match_authored_twins() {
+    let pubmed = PubMedParser::new()
+        .parse(
+            "PMID- 31000001\nTI  - Early mobilisation in the ICU: an editorial view.\n\
+             AU  - [No authors listed]\nDP  - 2021 Mar\nTA  - J Crit Care Res\n\
+             JT  - Journal of Critical Care Research\nVI  - 36\nPG  - 5-6\n",
+        )
+        .unwrap();
+    let csv = CsvParser::new()
+        .parse(
+            "Title,Authors,Year,Journal,Volume,Pages\n\
+             Early mobilisation in the ICU: an editorial view,,2021,Journal of Critical Care Research,36,5-6",
+        )
+        .unwrap();
+    let ris = RisParser::new()
+        .parse(
+            "TY  - JOUR\nTI  - Early mobilisation in the ICU: an editorial view\n\
+             AU  - Okafor, Chidi\nPY  - 2021\nJO  - Journal of Critical Care Research\n\
+             VL  - 36\nSP  - 5\nEP  - 6\nER  - \n",
+        )
+        .unwrap();
+    assert!(pubmed[0].authors.is_empty());
+    assert!(csv[0].authors.is_empty());
+
+    let citations: Vec<Citation> = [pubmed, csv, ris].concat();
+    let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].duplicates.len(), 2);
+}
+
+#[test]
+fn test_canonical_map_of_parsed_exports() {
+    let pubmed = PubMedParser::new()
+        .parse(&common::fixture("pubmed_export.nbib"))
+        .unwrap();
+    let embase = RisParser::new()
+        .parse(&common::fixture("ovid_embase.ris"))
+        .unwrap();
+    assert!(pubmed.iter().chain(&embase).all(|c| c.id().is_none()));
+
+    // Parsers assign no ids, so their output cannot be mapped as it is
+    let citations: Vec<Citation> = [pubmed.clone(), embase.clone()].concat();
+    let groups = Deduplicator::new().find_duplicates(&citations).unwrap();
+    assert!(to_canonical_map(&groups).is_err());
+
+    let merged = merge_datasets(vec![
+        ("pubmed.nbib".to_string(), pubmed),
+        ("embase.ris".to_string(), embase),
+    ]);
+    let groups = Deduplicator::new().find_duplicates(&merged.citations).unwrap();
+    let map = to_canonical_map(&groups).unwrap();
+    assert_eq!(map.len(), merged.citations.len());
+    let review = group_for(&groups, "Vitamin D supplementation");
+    assert_eq!(review.duplicates.len(), 1);
+    assert_eq!(map[review.duplicates[0].id().unwrap()], review.unique.id().unwrap());
+}
+
+#[test]
+#[ignore = "benchmark; run in release mode"]
+fn bench_find_duplicates_with_sources() {
+    fn time<T>(f: impl Fn() -> T) -> Duration {
+        let start = Instant::now();
+        for _ in 0..3 {
+            std::hint::black_box(f());
+        }
+        start.elapsed() / 3
+    }
+
+    // Each work appears in two of the sources, half of them with a DOI. Years are
+    // spread out to keep the blocks compared pairwise small.
+    let citations: Vec<Citation> = (0..100_000)
+        .map(|i| {
+            let work = i / 2;
+            let mut citation = Citation {
+                title: format!("Outcomes of intervention {work} in adults, cohort {}", work % 97),
+                doi: (work % 2 == 0).then(|| format!("10.5555/bench.{work}")),
+                journal: Some(format!("Journal of Study {}", work % 50)),
+                volume: Some((work % 40).to_string()),
+                pages: Some(format!("{}-{}", work % 900 + 1, work % 900 + 9)),
+                date: Some(biblib::Date {
+                    year: 1000 + work % 1000,
+                    month: None,
+                    day: None,
+                }),
+                ..Default::default()
+            };
+            citation.set_id(&i.to_string());
+            citation
+        })
+        .collect();
+    let sources: Vec<&str> = (0..citations.len()).map(|i| SOURCES[i % SOURCES.len()]).collect();
+    let deduplicator = Deduplicator::new().with_config(
+        DeduplicatorConfig::builder()
+            .group_by_year(true)
+            .source_preferences(["Embase", "PubMed"])
+            .build()
+            .unwrap(),
+    );
+
+    let without = time(|| deduplicator.clone().find_duplicates(&citations).unwrap());
+    let with = time(|| {
+        deduplicator
+            .clone()
+            .find_duplicates_with_sources(&citations, &sources)
+            .unwrap()
+    });
+    println!(
+        "{} citations: find_duplicates {without:?}, find_duplicates_with_sources {with:?}",
+        citations.len()
+    );
+}
@@ -0,0 +1,153 @@
+//! Integration tests for the diagnostics returned by lenient parsing and
+//! deduplication.
+
+#![cfg(all(
+    feature = "csv",
+    feature = "dedupe",
+    feature = "pubmed",
+    feature = "ris",
+    feature = "xml"
+))]
+
+mod common;
+
+use biblib::dedupe::Deduplicator;
+use biblib::diagnostics::Severity;
+use biblib::{
+    CitationParser, CsvParser, DiagnosticCode, EndNoteXmlParser, LenientOptions, PubMedParser,
+    RisParser,
+};
+
+#[test]
+fn test_ris_reports_ignored_lines_and_truncated_abstracts() {
+    let input = "TY  - JOUR\nTI  - Sepsis cohorts\nAB  - A long abstract about sepsis.\nER  - \n\
+                 <div>pasted</div>\n";
+    let mut parser = RisParser::new();
+    parser.set_max_abstract_length(Some(10));
+    let result = parser
+        .parse_lenient(input, LenientOptions::default())
+        .unwrap();
+
+    let reported: Vec<_> = result
+        .diagnostics
+        .iter()
+        .map(|d| (d.code, d.line, d.record_index))
+        .collect();
+    assert_eq!(
+        reported,
+        vec![
+            (DiagnosticCode::LineIgnored, Some(5), None),
+            (DiagnosticCode::AbstractTruncated, None, Some(0)),
+        ]
+    );
+    assert_eq!(result.stats.lines_ignored, 1);
+}
+
+#[test]
+fn test_pubmed_reports_skipped_records() {
+    let input = "PMID- 1\nTI  - First\n\nPMID- 2\nAB  - No title\n\nPMID- 3\nTI  - Third\n";
+    let result = PubMedParser::new()
+        .parse_lenient(input, LenientOptions::default())
+        .unwrap();
+
+    assert_eq!(result.citations.len(), 2);
+    assert_eq!(result.diagnostics.len(), 1);
+    let diagnostic = &result.diagnostics[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::RecordSkipped);
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.line, Some(4));
+}
+
+#[test]
+fn test_endnote_xml_reports_truncated_authors() {
+    let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xml><records><record>
+<contributors><authors>
+<author>Smith, John</author><author>Doe, Jane</author><author>Roe, Richard</author>
+</authors></contributors>
+<titles><title>Consortium trial</title></titles>
+</record></records></xml>"#;
+    let mut parser = EndNoteXmlParser::new();
+    parser.set_max_authors(Some(1));
+    let result = parser
+        .parse_lenient(input, LenientOptions::default())
+        .unwrap();
+
+    assert_eq!(result.diagnostics.len(), 1);
+    let diagnostic = &result.diagnostics[0];
+    assert_eq!(diagnostic.code, DiagnosticCode::AuthorsTruncated);
+    assert_eq!(diagnostic.record_index, Some(0));
+    assert_eq!(diagnostic.message, "citation 0 keeps 1 of 3 authors");
+}
+
+#[test]
+fn test_ris_reports_header_dates_and_truncated_record() {
+    let input = "Database: Embase <1974 to 2024 March 01>\nSearch Strategy:\n\
+                 1     delirium/ (45678)\n\n<1. >\n\
+                 TY  - JOUR\nTI  - Out of range\nPY  - 3020\nER  - \n\
+                 TY  - JOUR\nTI  - Either order\nY1  - 05/06/2021\nER  - \n\
+                 TY  - JOUR\nTI  - Cut off\nPY  - 2021\n";
+    let result = RisParser::new()
+        .parse_lenient(input, LenientOptions::default())
+        .unwrap();
+
+    assert_eq!(result.citations.len(), 3);
+    let reported: Vec<_> = result
+        .diagnostics
+        .iter()
+        .map(|d| (d.code, d.line, d.record_index))
+        .collect();
+    assert_eq!(
+        reported,
+        vec![
+            (DiagnosticCode::RecordTruncated, None, Some(2)),
+            (DiagnosticCode::HeaderSkipped, Some(1), None),
+            (DiagnosticCode::DateUnreadable, Some(8), None),
+            (DiagnosticCode::DateAmbiguous, Some(12), None),
+        ]
+    );
+    assert_eq!(
+        result.diagnostics[3].message,
+        "Y1 date \"05/06/2021\" on line 12 could be month/day or day/month, \
+         only the year was kept"
+    );
+    assert!(result.diagnostics.iter().all(|d| d.severity == Severity::Warning));
+}
+
+#[test]
+fn test_endnote_xml_reports_concatenated_documents() {
+    let input = common::fixture("endnote_concatenated.xml");
+    let result = EndNoteXmlParser::new()
+        .parse_lenient(&input, LenientOptions::default())
+        .unwrap();
+
+    assert_eq!(result.citations.len(), 3);
+    assert_eq!(result.diagnostics.len(), 1);
+    assert_eq!(result.diagnostics[0].code, DiagnosticCode::DocumentsConcatenated);
+    assert_eq!(result.diagnostics[0].line, Some(5));
+}
+
+#[test]
+fn test_csv_without_problems_reports_nothing() {
+    let input = "Title,Author,Year\nSepsis cohorts,\"Smith, John\",2023\n";
+    let result = CsvParser::new()
+        .parse_lenient(input, LenientOptions::default())
+        .unwrap();
+    assert_eq!(result.citations.len(), 1);
+    assert!(result.diagnostics.is_empty());
+}
+
+#[test]
+fn test_dedupe_and_parse_diagnostics_serialize_with_their_codes() {
+    let input = "TY  - JOUR\nTI  - Placeholder DOI\nDO  - 10.0000/none\nER  - \n";
+    let citations = RisParser::new().parse(input).unwrap();
+    let (_, diagnostics) = Deduplicator::new()
+        .find_duplicates_with_warnings(&citations, &[])
+        .unwrap();
+
+    assert_eq!(diagnostics.len(), 1);
+    let json = serde_json::to_value(&diagnostics).unwrap();
+    assert_eq!(json[0]["code"], "doi_denylisted");
+    assert_eq!(json[0]["severity"], "Warning");
+    assert_eq!(json[0]["record_index"], 0);
+}
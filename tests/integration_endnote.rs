@@ -0,0 +1,219 @@
+//! Integration tests against EndNote X9 XML exports.
+
+#![cfg(feature = "xml")]
+
+mod common;
+
+use biblib::endnote_xml::{
+    DATABASE_FIELD, DATABASE_PATH_FIELD, SOURCE_APP_FIELD, SOURCE_APP_VERSION_FIELD,
+};
+use biblib::{Citation, CitationFormat, CitationParser, EndNoteXmlParser, LenientOptions};
+use std::time::{Duration, Instant};
+
+fn parse_fixture() -> Vec<Citation> {
+    EndNoteXmlParser::new()
+        .parse(&common::fixture("endnote_x9.xml"))
+        .unwrap()
+}
+
+#[test]
+fn test_record_count_and_identifiers() {
+    let citations = parse_fixture();
+    assert_eq!(citations.len(), 4);
+
+    let dois: Vec<Option<&str>> = citations.iter().map(|c| c.doi.as_deref()).collect();
+    assert_eq!(
+        dois,
+        vec![
+            Some("10.5555/jccr.2021.045"),
+            Some("10.5555/nrl.2019.077"),
+            None,
+            Some("10.5555/iccr.2021.009"),
+        ]
+    );
+
+    let years: Vec<i32> = citations
+        .iter()
+        .map(|c| c.date.as_ref().unwrap().year)
+        .collect();
+    assert_eq!(years, vec![2021, 2019, 2017, 2021]);
+
+    // Month from the pub-dates text
+    assert_eq!(citations[0].date.as_ref().unwrap().month, Some(3));
+    assert!(citations.iter().all(|c| c.date_end.is_none()));
+}
+
+#[test]
+fn test_style_runs_are_concatenated() {
+    let citations = parse_fixture();
+
+    assert_eq!(
+        citations[0].title,
+        "Early mobilisation and delirium in critically ill adults: a randomised controlled trial"
+    );
+    assert_eq!(
+        citations[3].title,
+        "Sedation depth and long-term cognitive impairment after ICU discharge"
+    );
+    assert_eq!(
+        citations[3].journal.as_deref(),
+        Some("Intensive & Critical Care Reports")
+    );
+}
+
+#[test]
+fn test_authors() {
+    let citations = parse_fixture();
+
+    assert_eq!(citations[0].authors.len(), 3);
+    assert_eq!(citations[1].authors.len(), 2);
+    assert_eq!(citations[3].authors.len(), 3);
+
+    let author = &citations[0].authors[0];
+    assert_eq!(author.name, "Okafor");
+    assert_eq!(author.given_name.as_deref(), Some("C."));
+    assert_eq!(author.middle_name.as_deref(), Some("A."));
+
+    assert_eq!(citations[2].authors[0].name, "Whitfield");
+    assert_eq!(citations[3].authors[0].name, "García-López");
+}
+
+#[test]
+fn test_fields() {
+    let citations = parse_fixture();
+
+    let article = &citations[0];
+    assert_eq!(article.citation_type, vec!["Journal Article"]);
+    assert_eq!(
+        article.journal.as_deref(),
+        Some("Journal of Critical Care Research")
+    );
+    assert_eq!(article.pages.as_deref(), Some("210-218"));
+    assert_eq!(article.volume.as_deref(), Some("45"));
+    assert_eq!(article.issue.as_deref(), Some("3"));
+    assert_eq!(article.keywords, vec!["delirium", "mobilisation"]);
+    assert_eq!(article.issn, vec!["0883-9441"]);
+    assert_eq!(article.language.as_deref(), Some("eng"));
+
+    // Print and electronic ISSNs separated by a carriage return
+    assert_eq!(citations[1].issn.len(), 2);
+
+    let chapter = &citations[2];
+    assert_eq!(chapter.citation_type, vec!["Book Section"]);
+    assert_eq!(
+        chapter.journal.as_deref(),
+        Some("Handbook of Intensive Care Nursing")
+    );
+    assert_eq!(
+        chapter.publisher.as_deref(),
+        Some("Example University Press")
+    );
+
+    assert_eq!(citations[3].pmc_id.as_deref(), Some("PMC8123456"));
+}
+
+#[test]
+fn test_detect_and_parse() {
+    let (citations, format) = biblib::detect_and_parse(&common::fixture("endnote_x9.xml")).unwrap();
+    assert_eq!(format, CitationFormat::EndNoteXml);
+    assert_eq!(citations.len(), 4);
+    assert!(citations.iter().all(|c| c.parsed_from == Some(CitationFormat::EndNoteXml)));
+}
+
+#[test]
+fn test_library_and_application_provenance() {
+    let citations = parse_fixture();
+    for citation in &citations {
+        let field = |key: &str| citation.extra_fields.get(key).map(|values| values[0].as_str());
+        assert_eq!(field(DATABASE_FIELD), Some("Systematic Review.enl"));
+        assert_eq!(
+            field(DATABASE_PATH_FIELD),
+            Some(r"C:\Users\reviewer\Documents\Systematic Review.enl")
+        );
+        assert_eq!(field(SOURCE_APP_FIELD), Some("EndNote"));
+        assert_eq!(field(SOURCE_APP_VERSION_FIELD), Some("19.3"));
+    }
+
+    // Older exports have neither element
+    let older = EndNoteXmlParser::new()
+        .parse(&common::fixture("endnote_concatenated.xml"))
+        .unwrap();
+    for key in [DATABASE_FIELD, DATABASE_PATH_FIELD, SOURCE_APP_FIELD, SOURCE_APP_VERSION_FIELD] {
+        assert!(older.iter().all(|c| !c.extra_fields.contains_key(key)));
+    }
+}
+
+#[test]
+fn test_concatenated_exports() {
+    // Two exports joined with `cat`, each with its own declaration and root
+    let input = common::fixture("endnote_concatenated.xml");
+    let titles: Vec<String> = EndNoteXmlParser::new()
+        .parse(&input)
+        .unwrap()
+        .into_iter()
+        .map(|c| c.title)
+        .collect();
+    assert_eq!(
+        titles,
+        vec![
+            "Early mobilisation and delirium in critically ill adults",
+            "Sedation practices in European intensive care units",
+            "Antimicrobial stewardship in practice",
+        ]
+    );
+
+    let (citations, format) =
+        biblib::detect_and_parse_strict(&input, biblib::CoverageLimits::default()).unwrap();
+    assert_eq!(format, CitationFormat::EndNoteXml);
+    assert_eq!(citations.len(), 3);
+}
+
+/// Three records, the second with its title wrapped in `depth` nested `<style>`
+/// elements, as in a corrupt or crafted file.
+fn deeply_nested_export(depth: usize) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<xml><records>\n\
+         <record><titles><title>Before</title></titles></record>\n\
+         <record><titles><title>{}Nested{}</title></titles></record>\n\
+         <record><titles><title>After</title></titles></record>\n\
+         </records></xml>\n",
+        "<style>".repeat(depth),
+        "</style>".repeat(depth)
+    )
+}
+
+#[test]
+fn test_deep_nesting_fails_quickly() {
+    let input = deeply_nested_export(200_000);
+
+    let started = Instant::now();
+    let err = EndNoteXmlParser::new().parse(&input).unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(err.line, Some(4));
+    assert!(err.to_string().contains("nested more than 64 deep"), "{err}");
+
+    // Lenient parsing skips the record and reads the rest
+    let result = EndNoteXmlParser::new()
+        .parse_lenient(&input, LenientOptions::default())
+        .unwrap();
+    let titles: Vec<&str> = result.citations.iter().map(|c| c.title.as_str()).collect();
+    assert_eq!(titles, vec!["Before", "After"]);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].line, Some(4));
+}
+
+#[test]
+fn test_max_depth_is_configurable() {
+    // record > titles > title > 62 styles nests 64 deep, the default maximum
+    let input = deeply_nested_export(62);
+    assert_eq!(EndNoteXmlParser::new().parse(&input).unwrap()[1].title, "Nested");
+
+    let input = deeply_nested_export(100);
+    assert!(EndNoteXmlParser::new().parse(&input).is_err());
+
+    let mut parser = EndNoteXmlParser::new();
+    parser.set_max_depth(128);
+    assert_eq!(parser.parse(&input).unwrap()[1].title, "Nested");
+    parser.set_max_depth(2);
+    assert!(parser.parse(&common::fixture("endnote_x9.xml")).is_err());
+}
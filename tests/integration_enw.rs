@@ -0,0 +1,64 @@
+//! Integration tests for EndNote tagged (.enw) exports.
+
+#![cfg(feature = "enw")]
+
+mod common;
+
+use biblib::{CitationFormat, CitationParser, EnwParser, detect_and_parse};
+
+#[test]
+fn test_google_scholar_export() {
+    let content = common::fixture("google_scholar.enw");
+    let citations = EnwParser::new().parse(&content).unwrap();
+    assert_eq!(citations.len(), 4);
+
+    let first = &citations[0];
+    assert_eq!(first.citation_type, vec!["Journal Article"]);
+    assert_eq!(first.title, "Deep learning");
+    let names: Vec<&str> = first.authors.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, vec!["LeCun", "Bengio", "Hinton"]);
+    assert_eq!(first.authors[0].given_name.as_deref(), Some("Yann"));
+    assert_eq!(first.journal.as_deref(), Some("nature"));
+    assert_eq!(first.volume.as_deref(), Some("521"));
+    assert_eq!(first.issue.as_deref(), Some("7553"));
+    assert_eq!(first.pages.as_deref(), Some("436-444"));
+    assert_eq!(first.issn, vec!["0028-0836"]);
+    assert_eq!(first.date.as_ref().unwrap().year, 2015);
+    assert_eq!(
+        first.publisher.as_deref(),
+        Some("Nature Publishing Group UK London")
+    );
+
+    let chapter = &citations[1];
+    assert_eq!(chapter.citation_type, vec!["Book Section"]);
+    assert_eq!(chapter.authors[1].name, "Bottou");
+    assert_eq!(
+        chapter.journal.as_deref(),
+        Some("Intelligent Signal Processing")
+    );
+
+    let paper = &citations[2];
+    assert_eq!(
+        paper.keywords,
+        vec!["transformers", "attention", "sequence models"]
+    );
+
+    let article = &citations[3];
+    assert_eq!(article.doi.as_deref(), Some("10.1038/nature16961"));
+    assert_eq!(
+        article.abstract_text.as_deref(),
+        Some(
+            "The game of Go has long been viewed as the most challenging of classic games \
+             for artificial intelligence."
+        )
+    );
+}
+
+#[test]
+fn test_detect_and_parse_recognizes_enw() {
+    let content = common::fixture("google_scholar.enw");
+    let (citations, format) = detect_and_parse(&content).unwrap();
+    assert_eq!(format, CitationFormat::Enw);
+    assert_eq!(citations.len(), 4);
+    assert!(citations.iter().all(|c| c.parsed_from == Some(CitationFormat::Enw)));
+}
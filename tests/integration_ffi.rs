@@ -0,0 +1,125 @@
+//! Integration tests calling the C ABI the way a foreign caller would.
+
+#![cfg(feature = "ffi")]
+
+use serde_json::Value;
+use std::ffi::{CStr, CString, c_char};
+
+unsafe extern "C" {
+    fn biblib_parse(content: *const c_char, format_hint: *const c_char) -> *mut c_char;
+    fn biblib_dedupe(json_citations: *const c_char, json_config: *const c_char) -> *mut c_char;
+    fn biblib_free_string(s: *mut c_char);
+}
+
+// Link the library so the declarations above resolve to its exports
+use biblib as _;
+
+const RIS: &str = "\
+TY  - JOUR
+TI  - Effect of early mobilisation after cardiac surgery
+AU  - Nguyen, Thi
+PY  - 2021
+JO  - Journal of Clinical Nursing
+VL  - 30
+SP  - 700
+EP  - 710
+DO  - 10.1111/jocn.15600
+ER  - 
+
+TY  - JOUR
+TI  - Effect of early mobilisation after cardiac surgery.
+AU  - Nguyen, T.
+PY  - 2021
+JO  - Journal of Clinical Nursing
+VL  - 30
+SP  - 700
+EP  - 710
+DO  - 10.1111/JOCN.15600
+ER  - 
+";
+
+/// Takes ownership of a string returned by the library and parses its JSON.
+fn take(result: *mut c_char) -> Value {
+    assert!(!result.is_null());
+    let json = unsafe { CStr::from_ptr(result) }.to_str().unwrap().to_string();
+    unsafe { biblib_free_string(result) };
+    serde_json::from_str(&json).unwrap()
+}
+
+fn parse(content: &[u8], hint: Option<&str>) -> Value {
+    let content = CString::new(content).unwrap();
+    let hint = hint.map(|h| CString::new(h).unwrap());
+    take(unsafe {
+        biblib_parse(
+            content.as_ptr(),
+            hint.as_ref().map_or(std::ptr::null(), |h| h.as_ptr()),
+        )
+    })
+}
+
+fn dedupe(citations: &str, config: Option<&str>) -> Value {
+    let citations = CString::new(citations).unwrap();
+    let config = config.map(|c| CString::new(c).unwrap());
+    take(unsafe {
+        biblib_dedupe(
+            citations.as_ptr(),
+            config.as_ref().map_or(std::ptr::null(), |c| c.as_ptr()),
+        )
+    })
+}
+
+fn error_kind(value: &Value) -> Option<&str> {
+    value["error"]["kind"].as_str()
+}
+
+#[test]
+fn test_parse_and_dedupe_round_trip() {
+    let citations = parse(RIS.as_bytes(), Some("ris"));
+    assert_eq!(citations.as_array().unwrap().len(), 2);
+    assert_eq!(citations[0]["title"], "Effect of early mobilisation after cardiac surgery");
+    assert_eq!(citations, parse(RIS.as_bytes(), None));
+
+    let groups = dedupe(&citations.to_string(), None);
+    let groups = groups.as_array().unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0]["duplicates"].as_array().unwrap().len(), 1);
+
+    let groups = dedupe(
+        &citations.to_string(),
+        Some(r#"{"grouping_mode": "transitive_closure", "ignore_fields": ["doi"]}"#),
+    );
+    assert_eq!(groups.as_array().unwrap().len(), 1);
+
+    // Every option of the deduplicator configuration is accepted
+    let groups = dedupe(
+        &citations.to_string(),
+        Some(
+            r#"{"require_author_match": false, "author_overlap_threshold": 0.8,
+                "exact_title_fast_path": false, "use_abstract_similarity": true,
+                "short_title_length": 20, "abstract_similarity_threshold": 0.6}"#,
+        ),
+    );
+    assert_eq!(groups.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_errors_are_json_objects() {
+    assert_eq!(error_kind(&parse(RIS.as_bytes(), Some("mods"))), Some("unknown_format"));
+    assert_eq!(error_kind(&parse(b"TY  - JOUR\n\xff\nER  - \n", None)), Some("invalid_utf8"));
+    assert_eq!(error_kind(&parse(b"no citations here", Some("auto"))), Some("unknown_format"));
+    assert_eq!(error_kind(&parse(b"TY  - JOUR\nER  - \n", Some("ris"))), Some("parse"));
+    assert_eq!(
+        error_kind(&take(unsafe { biblib_parse(std::ptr::null(), std::ptr::null()) })),
+        Some("null_argument")
+    );
+
+    assert_eq!(error_kind(&dedupe("{not json", None)), Some("invalid_json"));
+    assert_eq!(error_kind(&dedupe("[]", Some(r#"{"grouping_mode": "nearest"}"#))), Some("invalid_config"));
+    assert_eq!(error_kind(&dedupe("[]", Some(r#"{"group_by_yaer": true}"#))), Some("invalid_config"));
+    assert_eq!(error_kind(&dedupe("[]", Some("[]"))), Some("invalid_config"));
+    assert_eq!(error_kind(&dedupe("[]", Some(r#"{"max_group_size": 0}"#))), Some("invalid_config"));
+    assert_eq!(dedupe("[]", Some("")), Value::Array(vec![]));
+
+    // Releasing a null pointer is allowed
+    unsafe { biblib_free_string(std::ptr::null_mut()) };
+}
@@ -0,0 +1,119 @@
+//! Migration of citation JSON captured from each parser of biblib 0.2.
+
+#![cfg(feature = "migrate")]
+
+mod common;
+
+use biblib::Date;
+use biblib::migrate::{from_v02_json, is_v02_shape};
+use serde_json::Value;
+
+/// Migrates every citation of a fixture holding a JSON array.
+fn migrate(name: &str) -> Vec<biblib::Citation> {
+    let values: Vec<Value> = serde_json::from_str(&common::fixture(name)).unwrap();
+    values
+        .into_iter()
+        .map(|value| {
+            assert!(is_v02_shape(&value), "{name}");
+            from_v02_json(value).unwrap_or_else(|e| panic!("{name}: {e}"))
+        })
+        .collect()
+}
+
+fn year(year: i32) -> Option<Date> {
+    Some(Date {
+        year,
+        month: None,
+        day: None,
+    })
+}
+
+#[test]
+fn test_v02_ris() {
+    let citations = migrate("v02_ris.json");
+    assert_eq!(citations.len(), 2);
+
+    let article = &citations[0];
+    assert_eq!(article.citation_type, vec!["JOUR"]);
+    assert_eq!(article.date, year(2021));
+    assert_eq!(article.id(), Some("V1StGXR8_Z5jdHi6B-myT"));
+    assert_eq!(article.source(), Some("Scopus"));
+    assert_eq!(article.authors[0].name, "Nguyen");
+    assert_eq!(
+        article.authors[0].affiliations,
+        vec!["University of Sydney"]
+    );
+    assert!(article.authors[1].affiliations.is_empty());
+    assert_eq!(article.extra_fields["N1"], vec!["Cited By: 12"]);
+    assert_eq!(article.notes, None);
+
+    let abstract_only = &citations[1];
+    assert_eq!(abstract_only.date, None);
+    assert_eq!(abstract_only.source(), None);
+    assert_eq!(abstract_only.authors[0].given_name, None);
+}
+
+#[test]
+fn test_v02_pubmed() {
+    let citations = migrate("v02_pubmed.json");
+    let citation = &citations[0];
+    // The date of 0.2 records that have one is kept over the year
+    assert_eq!(
+        citation.date,
+        Some(Date {
+            year: 2021,
+            month: Some(3),
+            day: None,
+        })
+    );
+    assert_eq!(citation.pmid.as_deref(), Some("33350000"));
+    assert_eq!(citation.pmc_id.as_deref(), Some("PMC7890123"));
+    assert_eq!(citation.mesh_terms.len(), 4);
+    assert_eq!(citation.authors[1].given_name.as_deref(), Some("John A"));
+    assert_eq!(citation.extra_fields["STAT"], vec!["MEDLINE"]);
+    assert_eq!(citation.source(), Some("PubMed"));
+    assert!(citation.record_dates.is_empty());
+}
+
+#[test]
+fn test_v02_endnote_xml() {
+    let citations = migrate("v02_endnote_xml.json");
+    let citation = &citations[0];
+    assert_eq!(citation.date, year(2019));
+    assert_eq!(citation.authors[1].name, "Cochrane Heart Group");
+    assert_eq!(citation.authors[1].given_name, None);
+    assert_eq!(citation.extra_fields.len(), 2);
+    assert_eq!(citation.source(), Some("Cochrane"));
+}
+
+#[test]
+fn test_v02_csv() {
+    let citations = migrate("v02_csv.json");
+    assert_eq!(citations.len(), 2);
+
+    assert_eq!(citations[0].keywords, vec!["rehabilitation"]);
+    assert_eq!(citations[0].extra_fields["Cited by"], vec!["7"]);
+    assert!(!citations[0].extra_fields.contains_key("Notes"));
+
+    assert_eq!(citations[1].date, year(2020));
+    assert_eq!(citations[1].volume.as_deref(), Some("34"));
+    assert_eq!(citations[1].pmid.as_deref(), Some("32000000"));
+    assert_eq!(citations[1].extra_fields["Cited by"], vec!["3"]);
+}
+
+#[test]
+fn test_migrated_citations_serialize_in_current_shape() {
+    for name in [
+        "v02_ris.json",
+        "v02_pubmed.json",
+        "v02_endnote_xml.json",
+        "v02_csv.json",
+    ] {
+        for citation in migrate(name) {
+            let value = serde_json::to_value(&citation).unwrap();
+            assert!(!is_v02_shape(&value), "{name}");
+            let again = from_v02_json(value.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&again).unwrap(), value, "{name}");
+        }
+    }
+}
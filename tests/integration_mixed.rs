@@ -0,0 +1,53 @@
+//! Integration tests for files that concatenate exports in different formats.
+
+#![cfg(all(feature = "ris", feature = "pubmed"))]
+
+mod common;
+
+use biblib::{CitationFormat, CitationParser, PubMedParser, RisParser, detect_and_parse};
+
+#[test]
+fn test_ris_followed_by_pubmed_loses_no_records() {
+    let content = common::fixture("mixed_ris_pubmed.txt");
+
+    let (citations, format) = detect_and_parse(&content).unwrap();
+    assert_eq!(format, CitationFormat::Mixed);
+
+    let ris = RisParser::new()
+        .parse(&common::fixture("google_scholar.ris"))
+        .unwrap();
+    let pubmed = PubMedParser::new()
+        .parse(&common::fixture("pubmed_export.nbib"))
+        .unwrap();
+    assert_eq!(citations.len(), ris.len() + pubmed.len());
+
+    let titles: Vec<&str> = citations.iter().map(|c| c.title.as_str()).collect();
+    let expected: Vec<&str> = ris
+        .iter()
+        .chain(&pubmed)
+        .map(|c| c.title.as_str())
+        .collect();
+    assert_eq!(titles, expected);
+
+    // The PubMed half keeps its identifiers
+    assert_eq!(
+        citations.iter().filter(|c| c.pmid.is_some()).count(),
+        pubmed.len()
+    );
+
+    // Each citation records the format of its own section
+    let formats: Vec<_> = citations.iter().map(|c| c.parsed_from.clone()).collect();
+    let expected: Vec<_> = std::iter::repeat_n(Some(CitationFormat::Ris), ris.len())
+        .chain(std::iter::repeat_n(Some(CitationFormat::PubMed), pubmed.len()))
+        .collect();
+    assert_eq!(formats, expected);
+}
+
+#[test]
+fn test_single_format_is_not_mixed() {
+    let (_, format) = detect_and_parse(&common::fixture("google_scholar.ris")).unwrap();
+    assert_eq!(format, CitationFormat::Ris);
+
+    let (_, format) = detect_and_parse(&common::fixture("pubmed_export.nbib")).unwrap();
+    assert_eq!(format, CitationFormat::PubMed);
+}
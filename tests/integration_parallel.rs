@@ -0,0 +1,155 @@
+//! Checks that parallel parsing of large RIS and PubMed files gives the same
+//! result as sequential parsing.
+//!
+//! `bench_parse_parallel` compares their speed; run it in release mode with
+//! `cargo test --release --test integration_parallel -- --ignored --nocapture`.
+
+#![cfg(all(feature = "parallel", feature = "ris", feature = "pubmed"))]
+
+mod common;
+
+use biblib::{Citation, CitationParser, PubMedParser, RisParser};
+use std::time::{Duration, Instant};
+
+/// A RIS file of `records` generated records, some without an `ER` line.
+fn ris_file(records: usize) -> String {
+    let mut text = String::from("Provider: Example Database\r\n\r\n");
+    for i in 0..records {
+        text.push_str(&format!(
+            "TY  - JOUR\r\nTI  - Effect of intervention {i} on outcomes in older adults\r\n\
+             AU  - Author{i}, Given\r\nAU  - Nguyen, Thi\r\nT2  - Journal of Clinical Nursing\r\n\
+             PY  - {}\r\nVL  - {}\r\nSP  - {}\r\nDO  - 10.1000/example.{i}\r\n\
+             AB  - Background: we studied\r\n      intervention {i}.\r\nKW  - nursing\r\n",
+            1990 + i % 35,
+            i % 40,
+            i % 900,
+        ));
+        if i % 97 != 0 {
+            text.push_str("ER  - \r\n\r\n");
+        }
+    }
+    text
+}
+
+/// A PubMed file of `records` generated records, ending mid-record.
+fn pubmed_file(records: usize) -> String {
+    let mut text = String::new();
+    for i in 0..records {
+        text.push_str(&format!(
+            "PMID- {}\nTI  - Effect of intervention {i} on outcomes in older adults: a\n      \
+             randomised trial.\nFAU - Author{i}, Given\nAU  - Author{i} G\nJT  - Journal of \
+             Clinical Nursing\nDP  - {} Mar\nVI  - {}\nPG  - {}-{}\nLID - 10.1000/example.{i} \
+             [doi]\nAB  - Background: we studied intervention {i}.\nMH  - Humans\n\n",
+            30_000_000 + i,
+            1990 + i % 35,
+            i % 40,
+            i % 900,
+            i % 900 + 9,
+        ));
+    }
+    text.push_str("PMID- 39999999\nTI  - Cut off\nFA");
+    text
+}
+
+fn json(citations: &[Citation]) -> serde_json::Value {
+    serde_json::to_value(citations).unwrap()
+}
+
+#[test]
+fn test_ris_parse_parallel_matches_parse() {
+    let input = ris_file(10_000);
+    let parser = RisParser::new();
+
+    let parallel = parser.parse_parallel(&input).unwrap();
+    assert_eq!(parallel.len(), 10_000);
+    assert_eq!(json(&parallel), json(&parser.parse(&input).unwrap()));
+
+    for name in ["scopus_export.ris", "zotero_export.ris", "glued_lines.ris"] {
+        let input = common::fixture(name);
+        let parallel = RisParser::zotero().parse_parallel(&input).unwrap();
+        assert_eq!(json(&parallel), json(&RisParser::zotero().parse(&input).unwrap()), "{name}");
+    }
+}
+
+#[test]
+fn test_pubmed_parse_parallel_matches_parse() {
+    let input = pubmed_file(10_000);
+    let mut parser = PubMedParser::new();
+    parser.set_max_authors(Some(1)).set_stamp_ingestion_time(true);
+
+    let parallel = parser.parse_parallel(&input).unwrap();
+    let sequential = parser.parse(&input).unwrap();
+    assert_eq!(parallel.len(), 10_001);
+    // The two parses may straddle a second, so their ingestion times can differ
+    let stamp = parallel[0].ingested_at.clone().unwrap();
+    assert!(parallel.iter().all(|c| c.ingested_at.as_ref() == Some(&stamp)));
+    let unstamped = |citations: Vec<Citation>| -> Vec<Citation> {
+        citations
+            .into_iter()
+            .map(|c| Citation {
+                ingested_at: None,
+                ..c
+            })
+            .collect()
+    };
+    assert_eq!(json(&unstamped(parallel)), json(&unstamped(sequential)));
+
+    let input = common::fixture("pubmed_export.nbib");
+    let parser = PubMedParser::new();
+    assert_eq!(
+        json(&parser.parse_parallel(&input).unwrap()),
+        json(&parser.parse(&input).unwrap())
+    );
+}
+
+#[test]
+fn test_parse_parallel_error_matches_parse() {
+    let mut input = ris_file(10_000);
+    input.push_str("TY  - JOUR\r\nAB  - A record without a title\r\nER  - \r\n");
+    let parser = RisParser::new();
+    assert_eq!(
+        parser.parse_parallel(&input).unwrap_err().to_string(),
+        parser.parse(&input).unwrap_err().to_string()
+    );
+}
+
+#[test]
+#[ignore = "benchmark; run in release mode"]
+fn bench_parse_parallel() {
+    fn time<T>(f: impl Fn() -> T) -> Duration {
+        let start = Instant::now();
+        for _ in 0..3 {
+            std::hint::black_box(f());
+        }
+        start.elapsed() / 3
+    }
+
+    let ris = ris_file(200_000);
+    let pubmed = pubmed_file(200_000);
+    let ris_parser = RisParser::new();
+    let pubmed_parser = PubMedParser::new();
+    let runs = [
+        (
+            "RIS",
+            ris.len(),
+            time(|| ris_parser.parse(&ris).unwrap()),
+            time(|| ris_parser.parse_parallel(&ris).unwrap()),
+        ),
+        (
+            "PubMed",
+            pubmed.len(),
+            time(|| pubmed_parser.parse(&pubmed).unwrap()),
+            time(|| pubmed_parser.parse_parallel(&pubmed).unwrap()),
+        ),
+    ];
+
+    let threads = std::thread::available_parallelism().map_or(1, usize::from);
+    println!("{threads} threads");
+    for (format, bytes, sequential, parallel) in runs {
+        println!(
+            "{format}: {} MB, parse {sequential:?}, parse_parallel {parallel:?} ({:.1}x)",
+            bytes / 1_000_000,
+            sequential.as_secs_f64() / parallel.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! Integration tests against a PubMed (.nbib) export.
+
+#![cfg(feature = "pubmed")]
+
+mod common;
+
+use biblib::{
+    Author, Citation, CitationFormat, CitationParser, CitationWriter, ContributorRole, PubMedParser,
+    PubMedWriter,
+    RetractionStatus,
+};
+
+fn parse_fixture() -> Vec<Citation> {
+    PubMedParser::new()
+        .parse(&common::fixture("pubmed_export.nbib"))
+        .unwrap()
+}
+
+fn find<'a>(citations: &'a [Citation], pmid: &str) -> &'a Citation {
+    citations
+        .iter()
+        .find(|c| c.pmid.as_deref() == Some(pmid))
+        .unwrap_or_else(|| panic!("no citation with PMID {}", pmid))
+}
+
+#[test]
+fn test_record_count_and_identifiers() {
+    let citations = parse_fixture();
+    assert_eq!(citations.len(), 12);
+    assert!(citations.iter().all(|c| c.pmid.is_some()));
+    assert!(citations.iter().all(|c| c.parsed_from == Some(CitationFormat::PubMed)));
+
+    let with_doi = citations.iter().filter(|c| c.doi.is_some()).count();
+    assert_eq!(with_doi, 11);
+}
+
+#[test]
+fn test_author_counts_per_record() {
+    let citations = parse_fixture();
+    let counts: Vec<usize> = citations.iter().map(|c| c.authors.len()).collect();
+    assert_eq!(counts, vec![3, 4, 2, 3, 2, 2, 0, 2, 5, 2, 3, 2]);
+}
+
+#[test]
+fn test_full_author_names_and_affiliations() {
+    let citations = parse_fixture();
+    let citation = find(&citations, "33510001");
+
+    let first = &citation.authors[0];
+    assert_eq!(first.name, "Okafor");
+    assert_eq!(first.given_name.as_deref(), Some("Chidinma"));
+    assert_eq!(first.middle_name.as_deref(), Some("A"));
+    assert_eq!(first.affiliations.len(), 1);
+    assert_eq!(citation.authors[1].affiliations.len(), 2);
+
+    let multi_word = find(&citations, "28930012");
+    assert_eq!(multi_word.authors[0].name, "van der Berg");
+}
+
+#[test]
+fn test_structured_abstract_is_joined() {
+    let citations = parse_fixture();
+    let abstract_text = find(&citations, "33510001")
+        .abstract_text
+        .as_deref()
+        .unwrap();
+
+    assert!(abstract_text.starts_with("BACKGROUND: Delirium is common"));
+    assert!(abstract_text.contains("associated with worse outcomes. METHODS:"));
+    assert!(abstract_text.ends_with("reduced delirium in critically ill adults."));
+    assert!(!abstract_text.contains('\n'));
+}
+
+#[test]
+fn test_multiline_title_and_journal() {
+    let citations = parse_fixture();
+    let citation = find(&citations, "33510001");
+
+    assert_eq!(
+        citation.title,
+        "Early mobilisation and delirium in critically ill adults: a randomised controlled trial."
+    );
+    assert_eq!(
+        citation.journal.as_deref(),
+        Some("Journal of critical care research")
+    );
+    assert_eq!(citation.journal_abbr.as_deref(), Some("J Crit Care Res"));
+    assert_eq!(citation.volume.as_deref(), Some("45"));
+    assert_eq!(citation.issue.as_deref(), Some("3"));
+    assert_eq!(citation.pages.as_deref(), Some("210-218"));
+    assert_eq!(citation.issn.len(), 2);
+}
+
+#[test]
+fn test_dates() {
+    let citations = parse_fixture();
+
+    let date = find(&citations, "31290003").date.as_ref().unwrap();
+    assert_eq!((date.year, date.month, date.day), (2019, Some(8), Some(1)));
+
+    let date = find(&citations, "32480002").date.as_ref().unwrap();
+    assert_eq!((date.year, date.month, date.day), (2020, Some(6), None));
+
+    // Quarterly issues carry a month range
+    let quarterly = find(&citations, "32010008");
+    let date = quarterly.date.as_ref().unwrap();
+    assert_eq!((date.year, date.month, date.day), (2020, Some(1), None));
+    let end = quarterly.date_end.as_ref().unwrap();
+    assert_eq!((end.year, end.month, end.day), (2020, Some(3), None));
+    assert_eq!(citations.iter().filter(|c| c.date_end.is_some()).count(), 1);
+
+    let date = find(&citations, "34560010").date.as_ref().unwrap();
+    assert_eq!((date.year, date.month, date.day), (2021, None, None));
+}
+
+#[test]
+fn test_doi_selected_from_lid() {
+    let citations = parse_fixture();
+
+    // LID lists the pii before the DOI
+    assert_eq!(
+        find(&citations, "33510001").doi.as_deref(),
+        Some("10.5555/jccr.2021.045")
+    );
+    // LID lists the DOI before the electronic location
+    assert_eq!(
+        find(&citations, "34560010").doi.as_deref(),
+        Some("10.5555/pone.2021.0257001")
+    );
+    assert_eq!(find(&citations, "29870009").doi, None);
+}
+
+#[test]
+fn test_collaborators_and_errata_links() {
+    let citations = parse_fixture();
+
+    let trial = find(&citations, "33140006");
+    assert_eq!(trial.authors.len(), 2);
+    let groups: Vec<&str> = trial
+        .contributors_with_role(ContributorRole::CorporateAuthor)
+        .map(|group| group.name.as_str())
+        .collect();
+    assert_eq!(groups, vec!["PLATFORM Collaborative Group"]);
+    let investigators: Vec<&Author> = trial
+        .contributors_with_role(ContributorRole::Investigator)
+        .collect();
+    assert_eq!(investigators.len(), 2);
+    assert_eq!(investigators[0].name, "Moreau");
+    assert_eq!(investigators[0].given_name.as_deref(), Some("Claire"));
+    assert!(!trial.extra_fields.contains_key("CN"));
+    assert!(!trial.extra_fields.contains_key("IR"));
+    // Investigator affiliations are not read
+    assert!(trial.extra_fields.contains_key("IRAD"));
+    assert!(trial.extra_fields["EIN"][0].ends_with("PMID: 33640007"));
+
+    let erratum = find(&citations, "33640007");
+    assert_eq!(erratum.citation_type, vec!["Published Erratum"]);
+    assert!(erratum.authors.is_empty());
+    assert!(erratum.extra_fields["EFR"][0].ends_with("PMID: 33140006"));
+
+    let retracted = find(&citations, "34560010");
+    assert!(retracted.extra_fields.contains_key("RIN"));
+    assert_eq!(retracted.pmc_id.as_deref(), Some("PMC8460011"));
+}
+
+#[test]
+fn test_retraction_status() {
+    let citations = parse_fixture();
+
+    let retracted = find(&citations, "34560010");
+    assert!(retracted.is_retracted());
+    assert_eq!(
+        retracted.retraction_status,
+        Some(RetractionStatus::Retracted {
+            notice: Some("PLoS One. 2023 May 10;18(5):e0285001. PMID: 37160011".to_string()),
+        })
+    );
+    assert_eq!(
+        find(&citations, "33140006").retraction_status,
+        Some(RetractionStatus::HasErratum)
+    );
+    let unmarked = citations.iter().filter(|c| c.retraction_status.is_none()).count();
+    assert_eq!(unmarked, 10);
+
+    let notice = "PMID- 37160011\nTI  - Retraction: Ivermectin for outpatient treatment of viral\n      \
+                  pneumonia: a randomised trial.\nJT  - PloS one\nDP  - 2023\nVI  - 18\nIP  - 5\n\
+                  PG  - e0285001\nPT  - Retraction of Publication\n\
+                  ROF - PLoS One. 2021 Sep 24;16(9):e0257001. PMID: 34560010\n\n";
+    let notice = &PubMedParser::new().parse(notice).unwrap()[0];
+    assert_eq!(notice.retraction_status, Some(RetractionStatus::RetractionNotice));
+    assert!(!notice.is_retracted());
+    assert!(notice.extra_fields["ROF"][0].ends_with("PMID: 34560010"));
+}
+
+#[test]
+fn test_mesh_terms() {
+    let citations = parse_fixture();
+    let citation = find(&citations, "33510001");
+
+    assert_eq!(citation.mesh_terms.len(), 5);
+    assert!(
+        citation
+            .mesh_terms
+            .contains(&"*Delirium/prevention & control".to_string())
+    );
+    assert!(find(&citations, "36110011").mesh_terms.is_empty());
+}
+
+#[test]
+fn test_detect_and_parse() {
+    let (citations, format) =
+        biblib::detect_and_parse(&common::fixture("pubmed_export.nbib")).unwrap();
+    assert_eq!(format, CitationFormat::PubMed);
+    assert_eq!(citations.len(), 12);
+}
+
+#[test]
+fn test_truncated_export() {
+    let content = common::fixture("pubmed_export.nbib");
+
+    // Cut the file in the middle of the last record's "MH  - Humans" line
+    let cut = content.rfind("MH  - Humans").unwrap() + 2;
+    let truncated = &content[..cut];
+
+    let citations = PubMedParser::new().parse(truncated).unwrap();
+    assert_eq!(citations.len(), 12);
+    assert!(
+        citations[..11]
+            .iter()
+            .all(|c| !c.extra_fields.contains_key(biblib::TRUNCATED_FIELD))
+    );
+    assert_eq!(citations[11].extra_fields[biblib::TRUNCATED_FIELD], vec!["true"]);
+
+    let mut parser = PubMedParser::new();
+    parser.set_drop_truncated(true);
+    assert_eq!(parser.parse(truncated).unwrap().len(), 11);
+
+    // Cutting at the end of a line is indistinguishable from a complete record
+    let cut = content.rfind("MH  - Humans").unwrap();
+    assert_eq!(parser.parse(&content[..cut]).unwrap().len(), 12);
+    assert_eq!(parser.parse(&content).unwrap().len(), 12);
+}
+
+/// A consortium paper with 2,000 authors.
+fn consortium_record(pmid: &str) -> String {
+    let mut record = format!(
+        "PMID- {pmid}\nTI  - Genome-wide association study of 1.2 million adults identifies\n      \
+         new loci for blood pressure\nJT  - Nature Genetics\nTA  - Nat Genet\nDP  - 2022 Mar\n\
+         VI  - 54\nPG  - 250-262\n"
+    );
+    for i in 0..2000 {
+        record.push_str(&format!("FAU - Author{i}, Given\nAU  - Author{i} G\n"));
+    }
+    record.push('\n');
+    record
+}
+
+#[test]
+fn test_max_authors_on_consortium_paper() {
+    let input = consortium_record("35000001");
+    let full = &PubMedParser::new().parse(&input).unwrap()[0];
+    assert_eq!(full.authors.len(), 2000);
+    assert_eq!(full.author_count, None);
+
+    let mut parser = PubMedParser::new();
+    parser.set_max_authors(Some(10));
+    let truncated = &parser.parse(&input).unwrap()[0];
+    assert_eq!(truncated.authors.len(), 10);
+    assert_eq!(truncated.authors[..], full.authors[..10]);
+    assert_eq!(truncated.author_count, Some(2000));
+    assert_eq!(truncated.extra_fields[biblib::AUTHORS_TRUNCATED_FIELD], vec!["true"]);
+
+    #[cfg(feature = "dedupe")]
+    {
+        let citations = vec![full.clone(), truncated.clone()];
+        let groups = biblib::dedupe::Deduplicator::new()
+            .find_duplicates(&citations)
+            .unwrap();
+        assert_eq!(groups.len(), 1);
+    }
+}
+
+#[test]
+fn test_write_round_trip_of_fixture() {
+    let mut parser = PubMedParser::new();
+    for archival in [false, true] {
+        parser.set_archival(archival);
+        let citations = parser
+            .parse(&common::fixture("pubmed_export.nbib"))
+            .unwrap();
+        let output = PubMedWriter::new().write_to_string(&citations).unwrap();
+        assert!(output.lines().all(|line| line.chars().count() <= 80));
+
+        let reparsed = parser.parse(&output).unwrap();
+        assert_eq!(reparsed.len(), citations.len());
+        for (citation, reparsed) in citations.iter().zip(&reparsed) {
+            assert_eq!(
+                serde_json::to_value(reparsed).unwrap(),
+                serde_json::to_value(citation).unwrap(),
+                "PMID {:?}",
+                citation.pmid
+            );
+        }
+        assert_eq!(PubMedWriter::new().write_to_string(&reparsed).unwrap(), output);
+    }
+}
@@ -0,0 +1,105 @@
+//! Checks the journal, year and type counts of a fixture with variant
+//! spellings of journal names.
+
+#![cfg(feature = "ris")]
+
+mod common;
+
+use biblib::report::{
+    JournalCount, TypeCount, YearCount, count_by_journal, count_by_type, count_by_year,
+};
+use biblib::{Citation, CitationParser, CitationType, RisParser};
+
+fn citations() -> Vec<Citation> {
+    RisParser::new()
+        .parse(&common::fixture("journal_variants.ris"))
+        .unwrap()
+}
+
+#[test]
+fn test_journal_variants_are_counted_together() {
+    let counts = count_by_journal(&citations());
+    let summary: Vec<_> = counts
+        .iter()
+        .map(|count| (count.name.as_str(), count.key.as_str(), count.count))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("Lancet", "lancet", 4),
+            ("Critical Care Medicine", "criticalcaremedicine", 2),
+            (
+                "Handbook of Critical Care Nursing",
+                "handbookofcriticalcarenursing",
+                1
+            ),
+            ("N Engl J Med", "nengljmed", 1),
+            (
+                "New England Journal of Medicine",
+                "newenglandjournalofmedicine",
+                1
+            ),
+        ]
+    );
+    assert_eq!(counts[0].issns, vec!["0140-6736", "1474-547X"]);
+    assert!(counts[1].issns.is_empty());
+}
+
+#[test]
+fn test_years_and_types() {
+    let citations = citations();
+    let years: Vec<_> = count_by_year(&citations)
+        .into_iter()
+        .map(|count| (count.year, count.count))
+        .collect();
+    assert_eq!(
+        years,
+        vec![
+            (Some(2014), 1),
+            (Some(2019), 1),
+            (Some(2020), 3),
+            (Some(2021), 3),
+            (Some(2022), 1),
+            (None, 1),
+        ]
+    );
+    assert_eq!(
+        count_by_type(&citations),
+        vec![
+            TypeCount {
+                citation_type: CitationType::JournalArticle,
+                count: 7
+            },
+            TypeCount {
+                citation_type: CitationType::ConferencePaper,
+                count: 1
+            },
+            TypeCount {
+                citation_type: CitationType::Book,
+                count: 1
+            },
+            TypeCount {
+                citation_type: CitationType::BookChapter,
+                count: 1
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_counts_round_trip_through_json() {
+    let citations = citations();
+    let journals = count_by_journal(&citations);
+    let json = serde_json::to_string(&journals).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<JournalCount>>(&json).unwrap(),
+        journals
+    );
+    let years = count_by_year(&citations);
+    let json = serde_json::to_string(&years).unwrap();
+    assert!(json.ends_with(r#"{"year":null,"count":1}]"#));
+    assert_eq!(
+        serde_json::from_str::<Vec<YearCount>>(&json).unwrap(),
+        years
+    );
+}
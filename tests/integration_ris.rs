@@ -0,0 +1,503 @@
+//! Integration tests against Google Scholar, Ovid Embase, Scopus and Zotero RIS exports.
+
+#![cfg(feature = "ris")]
+
+mod common;
+
+use biblib::{
+    ATTACHMENTS_FIELD, Citation, CitationFormat, CitationParser, CitationWriter, LenientOptions,
+    ParseStats, RisParser, RisWriter,
+};
+
+fn parse_fixture(name: &str) -> Vec<Citation> {
+    RisParser::new().parse(&common::fixture(name)).unwrap()
+}
+
+#[test]
+fn test_google_scholar_records() {
+    let citations = parse_fixture("google_scholar.ris");
+    assert_eq!(citations.len(), 4);
+
+    let counts: Vec<usize> = citations.iter().map(|c| c.authors.len()).collect();
+    assert_eq!(counts, vec![4, 3, 2, 3]);
+
+    // Google Scholar never exports DOIs
+    assert!(citations.iter().all(|c| c.doi.is_none()));
+
+    let years: Vec<i32> = citations
+        .iter()
+        .map(|c| c.date.as_ref().unwrap().year)
+        .collect();
+    assert_eq!(years, vec![2020, 2022, 2019, 2014]);
+}
+
+#[test]
+fn test_google_scholar_fields() {
+    let citations = parse_fixture("google_scholar.ris");
+
+    let article = &citations[0];
+    assert_eq!(article.citation_type, vec!["JOUR"]);
+    assert_eq!(
+        article.title,
+        "Machine learning prediction of sepsis onset from routine vital signs"
+    );
+    assert_eq!(article.authors[2].name, "Schmidt");
+    assert_eq!(article.authors[2].given_name.as_deref(), Some("Anna-Lena"));
+    assert_eq!(
+        article.journal.as_deref(),
+        Some("Critical Care Informatics")
+    );
+    assert_eq!(article.pages.as_deref(), Some("101-109"));
+    assert_eq!(article.issn, vec!["2045-1182"]);
+    assert_eq!(article.publisher.as_deref(), Some("Example Academic Press"));
+
+    let book = &citations[3];
+    assert_eq!(book.citation_type, vec!["BOOK"]);
+    assert_eq!(book.journal, None);
+    assert_eq!(book.authors[0].middle_name.as_deref(), Some("H"));
+}
+
+#[test]
+fn test_ovid_embase_records() {
+    let citations = parse_fixture("ovid_embase.ris");
+    assert_eq!(citations.len(), 5);
+
+    let counts: Vec<usize> = citations.iter().map(|c| c.authors.len()).collect();
+    assert_eq!(counts, vec![3, 2, 2, 4, 1]);
+
+    let dois: Vec<Option<&str>> = citations.iter().map(|c| c.doi.as_deref()).collect();
+    assert_eq!(
+        dois,
+        vec![
+            Some("10.5555/jccr.2021.045"),
+            Some("10.5555/nrl.2019.077"),
+            None,
+            Some("10.5555/pecl.2020.036"),
+            Some("10.5555/rmr.2022.100142"),
+        ]
+    );
+
+    let years: Vec<i32> = citations
+        .iter()
+        .map(|c| c.date.as_ref().unwrap().year)
+        .collect();
+    assert_eq!(years, vec![2021, 2019, 2020, 2020, 2022]);
+}
+
+#[test]
+fn test_ovid_embase_fields() {
+    let citations = parse_fixture("ovid_embase.ris");
+
+    let article = &citations[0];
+    assert_eq!(
+        article.journal.as_deref(),
+        Some("Journal of Critical Care Research")
+    );
+    assert_eq!(article.journal_abbr.as_deref(), Some("J. Crit. Care Res."));
+    assert_eq!(article.pages.as_deref(), Some("210-218"));
+    assert_eq!(article.issn.len(), 2);
+    assert_eq!(article.keywords.len(), 5);
+    assert!(
+        article
+            .abstract_text
+            .as_deref()
+            .unwrap()
+            .starts_with("Background:")
+    );
+    assert_eq!(article.urls.len(), 1);
+    assert_eq!(article.extra_fields["DB"], vec!["Embase"]);
+    assert_eq!(article.extra_fields["AN"], vec!["2013510001"]);
+    assert_eq!(article.extra_fields["M3"], vec!["Article"]);
+
+    let conference = &citations[2];
+    assert_eq!(conference.extra_fields["M3"], vec!["Conference Abstract"]);
+    assert_eq!(conference.issue.as_deref(), Some("1 Supplement 1"));
+    assert_eq!(conference.pages.as_deref(), Some("45"));
+
+    assert_eq!(citations[3].pages.as_deref(), Some("e512-e518"));
+}
+
+#[test]
+fn test_detect_and_parse() {
+    let (citations, format) =
+        biblib::detect_and_parse(&common::fixture("ovid_embase.ris")).unwrap();
+    assert_eq!(format, CitationFormat::Ris);
+    assert_eq!(citations.len(), 5);
+    assert!(citations.iter().all(|c| c.parsed_from == Some(CitationFormat::Ris)));
+}
+
+#[test]
+fn test_google_scholar_preset() {
+    let citations = RisParser::google_scholar()
+        .parse(&common::fixture("google_scholar.ris"))
+        .unwrap();
+    let expected = parse_fixture("google_scholar.ris");
+    assert_eq!(citations.len(), expected.len());
+    for (citation, expected) in citations.iter().zip(&expected) {
+        assert_eq!(citation.title, expected.title);
+        assert_eq!(citation.authors, expected.authors);
+        assert_eq!(citation.date, expected.date);
+    }
+}
+
+#[test]
+fn test_ovid_embase_preset() {
+    let citations = RisParser::ovid_embase()
+        .parse(&common::fixture("ovid_embase.ris"))
+        .unwrap();
+    assert_eq!(citations.len(), 5);
+
+    // The Ovid publication type is added after the RIS type
+    assert_eq!(citations[0].citation_type, vec!["JOUR", "Article"]);
+    assert_eq!(
+        citations[2].citation_type,
+        vec!["JOUR", "Conference Abstract"]
+    );
+    assert!(!citations[2].extra_fields.contains_key("M3"));
+    assert_eq!(citations[0].extra_fields["AN"], vec!["2013510001"]);
+    assert_eq!(citations[0].keywords.len(), 5);
+}
+
+#[test]
+fn test_conference_abstract_and_full_article() {
+    let citations = RisParser::ovid_embase()
+        .parse(&common::fixture("embase_conference_abstract.ris"))
+        .unwrap();
+    assert_eq!(citations.len(), 2);
+
+    // The supplement abstract carries a journal, volume and pages like an article
+    assert_eq!(citations[0].issue.as_deref(), Some("SUPPL 1"));
+    assert!(citations[0].is_conference_abstract());
+    assert!(!citations[1].is_conference_abstract());
+
+    // Without the Ovid publication type the supplement signals still suffice
+    let plain = parse_fixture("embase_conference_abstract.ris");
+    assert!(plain[0].is_conference_abstract());
+    assert!(!plain[1].is_conference_abstract());
+}
+
+#[test]
+fn test_scopus_preset() {
+    let input = common::fixture("scopus_export.ris");
+    let citations = RisParser::scopus().parse(&input).unwrap();
+    assert_eq!(citations.len(), 3);
+
+    let pmids: Vec<Option<&str>> = citations.iter().map(|c| c.pmid.as_deref()).collect();
+    assert_eq!(pmids, vec![Some("33510001"), None, Some("34900017")]);
+
+    // A DOI in M3 is used when DO is missing, but never overrides DO
+    let dois: Vec<Option<&str>> = citations.iter().map(|c| c.doi.as_deref()).collect();
+    assert_eq!(
+        dois,
+        vec![
+            Some("10.5555/jccr.2021.045"),
+            Some("10.5555/cci.2020.0012"),
+            None,
+        ]
+    );
+    assert_eq!(
+        citations[0].journal.as_deref(),
+        Some("Journal of Critical Care Research")
+    );
+    assert_eq!(citations[0].extra_fields["DB"], vec!["Scopus"]);
+
+    // Without the preset the identifiers stay in extra fields
+    let plain = parse_fixture("scopus_export.ris");
+    assert_eq!(plain[0].pmid, None);
+    assert_eq!(plain[0].extra_fields["C7"], vec!["33510001"]);
+    assert_eq!(plain[1].doi, None);
+}
+
+#[test]
+fn test_truncated_export() {
+    let content = common::fixture("ovid_embase.ris");
+
+    // Cut the file in the middle of the last record's abstract
+    let last = content.rfind("TY  - ").unwrap();
+    let cut = last + content[last..].find("N2  - ").unwrap() + 30;
+    let truncated = &content[..cut];
+
+    let citations = RisParser::new().parse(truncated).unwrap();
+    assert_eq!(citations.len(), 5);
+    assert!(
+        citations[..4]
+            .iter()
+            .all(|c| !c.extra_fields.contains_key(biblib::TRUNCATED_FIELD))
+    );
+    assert_eq!(citations[4].extra_fields[biblib::TRUNCATED_FIELD], vec!["true"]);
+    let complete = parse_fixture("ovid_embase.ris");
+    let partial = citations[4].abstract_text.as_deref().unwrap();
+    assert!(partial.len() < complete[4].abstract_text.as_deref().unwrap().len());
+
+    let mut parser = RisParser::new();
+    parser.set_drop_truncated(true);
+    assert_eq!(parser.parse(truncated).unwrap().len(), 4);
+    assert_eq!(parser.parse(&content).unwrap().len(), 5);
+
+    // Truncated before the title, the record can only be recovered by dropping it
+    let cut = last + content[last..].find("T1  - ").unwrap();
+    assert!(RisParser::new().parse(&content[..cut]).is_err());
+    assert_eq!(parser.parse(&content[..cut]).unwrap().len(), 4);
+}
+
+#[test]
+fn test_glued_lines() {
+    // The Google Scholar records with "ER  -TY  - JOUR" glued together,
+    // "ER  - end of record" and "ER  - TY  - BOOK" terminators
+    let citations = parse_fixture("glued_lines.ris");
+    let expected = parse_fixture("google_scholar.ris");
+    assert_eq!(citations.len(), expected.len());
+
+    for (citation, expected) in citations.iter().zip(&expected) {
+        assert_eq!(citation.citation_type, expected.citation_type);
+        assert_eq!(citation.title, expected.title);
+        assert_eq!(citation.authors, expected.authors);
+        assert_eq!(citation.pages, expected.pages);
+        assert!(citation.extra_fields.is_empty());
+    }
+}
+
+#[test]
+fn test_reference_manager_tabs() {
+    // "ty\tJOUR" lowercase tags, "TY\t- JOUR" and "ER\t" terminators, CRLF
+    let citations = parse_fixture("reference_manager.ris");
+    assert_eq!(citations.len(), 3);
+
+    let first = &citations[0];
+    assert_eq!(first.title, "Prone positioning in severe acute respiratory distress syndrome");
+    assert_eq!(first.authors.len(), 2);
+    assert_eq!(first.authors[0].name, "Guerin");
+    assert_eq!(first.journal.as_deref(), Some("N Engl J Med"));
+    assert_eq!(first.date.as_ref().map(|d| d.year), Some(2013));
+    assert_eq!(first.pages.as_deref(), Some("2159-2168"));
+    assert_eq!(first.keywords, vec!["Respiratory Distress Syndrome, Adult", "Prone Position"]);
+
+    let second = &citations[1];
+    assert_eq!(second.authors[1].name, "Nguyen");
+    assert_eq!(second.volume.as_deref(), Some("345"));
+    assert_eq!(second.pages.as_deref(), Some("1368-1377"));
+
+    let third = &citations[2];
+    assert_eq!(third.citation_type, vec!["CHAP"]);
+    assert_eq!(third.publisher.as_deref(), Some("McGraw-Hill"));
+    assert!(citations.iter().all(|c| c.extra_fields.is_empty()));
+}
+
+#[test]
+fn test_zotero_preset() {
+    let content = common::fixture("zotero_export.ris");
+    let citations = RisParser::zotero().parse(&content).unwrap();
+    assert_eq!(citations.len(), 2);
+
+    let article = &citations[0];
+    assert_eq!(
+        article.notes.as_deref(),
+        Some("Screening: include & extract\nCheck the CONSORT diagram\n\nFull text retrieved 2024-02-01")
+    );
+    assert_eq!(article.urls, vec!["https://doi.org/10.5555/jccr.2021.0042"]);
+    assert_eq!(article.extra_fields[ATTACHMENTS_FIELD].len(), 1);
+    assert!(article.extra_fields[ATTACHMENTS_FIELD][0].ends_with(".pdf"));
+    assert_eq!(article.extra_fields["zotero_key"], vec!["8XKQ2M3T"]);
+    assert_eq!(article.extra_fields["DB"], vec!["PubMed"]);
+    assert_eq!(article.pmid, None);
+    assert_eq!(article.keywords.len(), 3);
+
+    let book = &citations[1];
+    assert_eq!(book.notes.as_deref(), Some("Background reading only"));
+    assert_eq!(book.urls, vec!["https://example.org/books/antimicrobial-stewardship"]);
+    assert_eq!(
+        book.extra_fields[ATTACHMENTS_FIELD],
+        vec!["zotero://select/library/items/R4M9V2CJ"]
+    );
+
+    // Without the preset the raw HTML and the local links are kept
+    let citations = parse_fixture("zotero_export.ris");
+    assert!(citations[0].notes.as_deref().unwrap().starts_with("<div"));
+    assert_eq!(citations[0].urls.len(), 2);
+    assert_eq!(citations[0].pmid, None);
+    assert_eq!(citations[0].extra_fields["reference_id"], vec!["8XKQ2M3T"]);
+}
+
+#[test]
+fn test_one_parser_shared_between_threads() {
+    let files = [
+        "google_scholar.ris",
+        "ovid_embase.ris",
+        "scopus_export.ris",
+        "zotero_export.ris",
+    ]
+    .map(common::fixture);
+    let parser = RisParser::new();
+
+    let titles: Vec<Vec<String>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .iter()
+            .map(|content| scope.spawn(|| parser.parse(content).unwrap()))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                let citations = handle.join().unwrap();
+                citations.into_iter().map(|c| c.title).collect()
+            })
+            .collect()
+    });
+
+    for (content, titles) in files.iter().zip(&titles) {
+        let expected: Vec<String> = parser
+            .parse(content)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.title)
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(titles, &expected);
+    }
+}
+
+#[test]
+fn test_lenient_parse_limits() {
+    let content = common::fixture("partly_broken.ris");
+    let parser = RisParser::new();
+    assert!(parser.parse(&content).is_err());
+
+    let result = parser
+        .parse_lenient(&content, LenientOptions::default())
+        .unwrap();
+    assert_eq!(result.citations.len(), 3);
+    assert_eq!(result.errors.len(), 7);
+    assert_eq!(
+        result.stats,
+        ParseStats {
+            attempted: 10,
+            succeeded: 3,
+            failed: 7,
+            lines_ignored: 1,
+        }
+    );
+
+    let options = LenientOptions {
+        max_error_ratio: Some(0.5),
+        ..Default::default()
+    };
+    let err = parser.parse_lenient(&content, options).unwrap_err();
+    assert!(err.to_string().contains("7 of 10 records failed"));
+
+    let options = LenientOptions {
+        max_errors: Some(7),
+        ..Default::default()
+    };
+    assert_eq!(parser.parse_lenient(&content, options).unwrap().citations.len(), 3);
+}
+
+/// Whether `text` is an RFC 3339 UTC timestamp with second precision.
+fn is_rfc3339_utc(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 20
+        && bytes.iter().enumerate().all(|(i, &b)| match i {
+            4 | 7 => b == b'-',
+            10 => b == b'T',
+            13 | 16 => b == b':',
+            19 => b == b'Z',
+            _ => b.is_ascii_digit(),
+        })
+        && (1..=12).contains(&text[5..7].parse::<u8>().unwrap())
+        && (1..=31).contains(&text[8..10].parse::<u8>().unwrap())
+        && text[11..13].parse::<u8>().unwrap() < 24
+}
+
+#[test]
+fn test_stamp_ingestion_time() {
+    let content = common::fixture("scopus_export.ris");
+
+    let mut parser = RisParser::new();
+    parser.set_stamp_ingestion_time(true);
+    let citations = parser.parse(&content).unwrap();
+    let stamp = citations[0].ingested_at.as_deref().unwrap();
+    assert!(is_rfc3339_utc(stamp), "{stamp}");
+    assert!(citations.iter().all(|c| c.ingested_at.as_deref() == Some(stamp)));
+
+    let json = serde_json::to_value(&citations[0]).unwrap();
+    assert_eq!(json["ingested_at"], stamp);
+
+    // Off by default, so repeated parses serialize identically
+    let parse = || serde_json::to_value(parse_fixture("scopus_export.ris")).unwrap();
+    let first = parse();
+    assert!(parse_fixture("scopus_export.ris").iter().all(|c| c.ingested_at.is_none()));
+    assert_eq!(first, parse());
+}
+
+#[test]
+fn test_ovid_header_and_record_numbers() {
+    let content = common::fixture("ovid_medline_header.ris");
+    let citations = RisParser::new().parse(&content).unwrap();
+    assert_eq!(citations.len(), 3);
+    assert_eq!(citations[0].pmid, None);
+    assert_eq!(citations[0].extra_fields["AN"], vec!["33510001"]);
+    assert_eq!(citations[2].citation_type, vec!["CONF"]);
+
+    // No search strategy text ends up in a citation
+    for citation in &citations {
+        let json = serde_json::to_string(citation).unwrap();
+        for strategy in ["ti,ab.", "remove duplicates", "Search Strategy", "(153)"] {
+            assert!(!json.contains(strategy), "{strategy} in {json}");
+        }
+    }
+
+    let header = biblib::ris::ovid_header(&content).unwrap();
+    assert_eq!(
+        header.databases,
+        vec![
+            "Ovid MEDLINE(R) ALL <1946 to March 01, 2024>",
+            "Embase <1974 to 2024 March 01>"
+        ]
+    );
+    assert_eq!(header.search_strategy.len(), 12);
+    assert_eq!(header.search_strategy[9].query, "3 and 6 and 9");
+    assert_eq!(header.search_strategy[11].results, Some(96));
+
+    let lenient = RisParser::new()
+        .parse_lenient(&content, LenientOptions::default())
+        .unwrap();
+    assert_eq!(lenient.stats.lines_ignored, 0);
+    let (detected, format) = biblib::detect_and_parse(&content).unwrap();
+    assert_eq!(format, CitationFormat::Ris);
+    assert_eq!(detected.len(), 3);
+
+    // Record numbers end records without an `ER` tag
+    let without_end: String = content
+        .lines()
+        .filter(|line| !line.starts_with("ER  -"))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let citations = RisParser::new().parse(&without_end).unwrap();
+    let titles: Vec<_> = citations.iter().map(|c| &c.title[..20]).collect();
+    assert_eq!(
+        titles,
+        vec!["Early mobilisation a", "Nurse-led early mobi", "Delirium after early"]
+    );
+}
+
+#[test]
+fn test_write_round_trip_of_fixtures() {
+    for name in [
+        "google_scholar.ris",
+        "ovid_embase.ris",
+        "scopus_export.ris",
+        "zotero_export.ris",
+        "journal_variants.ris",
+    ] {
+        let citations = parse_fixture(name);
+        let output = RisWriter::new().write_to_string(&citations).unwrap();
+        let reparsed = RisParser::new().parse(&output).unwrap();
+        assert_eq!(reparsed.len(), citations.len(), "{name}");
+        for (citation, reparsed) in citations.iter().zip(&reparsed) {
+            assert_eq!(
+                serde_json::to_value(reparsed).unwrap(),
+                serde_json::to_value(citation).unwrap(),
+                "{name}: {}",
+                citation.title
+            );
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Checks of `schema/citation.schema.json` against the serialized types.
+//!
+//! The schema is generated by `biblib::schema::citation_schema()` with the
+//! `schema` feature, which also checks that the file is up to date.
+
+use biblib::{
+    Author, Citation, CitationFormat, ConferenceInfo, Contributor, ContributorRole, Date, LinkKind,
+    RecordDateKind, RetractionStatus,
+};
+use serde_json::Value;
+
+fn schema() -> Value {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/citation.schema.json");
+    let text = std::fs::read_to_string(path).unwrap();
+    serde_json::from_str(&text).unwrap()
+}
+
+/// The definition of `name` in `schema`, which is the schema itself for its
+/// root type.
+fn definition<'a>(schema: &'a Value, name: &str) -> &'a Value {
+    if schema["title"] == name {
+        schema
+    } else {
+        &schema["definitions"][name]
+    }
+}
+
+/// Whether `property` of `schema` accepts `value`'s JSON type.
+fn accepts(schema: &Value, property: &Value, value: &Value) -> bool {
+    for key in ["anyOf", "oneOf"] {
+        if let Some(options) = property[key].as_array() {
+            return options.iter().any(|option| accepts(schema, option, value));
+        }
+    }
+    if let Some(options) = property["allOf"].as_array() {
+        return options.iter().all(|option| accepts(schema, option, value));
+    }
+    if let Some(reference) = property["$ref"].as_str() {
+        let name = reference.strip_prefix("#/definitions/").unwrap();
+        return accepts(schema, definition(schema, name), value);
+    }
+    // An integer is also a number
+    let kinds: &[&str] = match value {
+        Value::Null => &["null"],
+        Value::Bool(_) => &["boolean"],
+        Value::Number(number) if number.is_f64() => &["number"],
+        Value::Number(_) => &["integer", "number"],
+        Value::String(_) => &["string"],
+        Value::Array(_) => &["array"],
+        Value::Object(_) => &["object"],
+    };
+    match &property["type"] {
+        Value::String(own) => kinds.contains(&own.as_str()),
+        Value::Array(own) => own.iter().any(|own| kinds.iter().any(|kind| own == kind)),
+        _ => false,
+    }
+}
+
+/// Checks that a definition of `schema` lists exactly the fields of `value`,
+/// requires those that are never null and accepts their types.
+fn check_definition(schema: &Value, name: &str, value: Value) {
+    let definition = definition(schema, name);
+    let properties = definition["properties"].as_object().unwrap();
+    let fields = value.as_object().unwrap();
+
+    let mut listed: Vec<&str> = properties.keys().map(String::as_str).collect();
+    listed.sort_unstable();
+    let serialized: Vec<&str> = fields.keys().map(String::as_str).collect();
+    assert_eq!(listed, serialized, "fields of {name}");
+
+    let required: Vec<&str> = definition["required"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice)
+        .iter()
+        .map(|field| field.as_str().unwrap())
+        .collect();
+    for (field, value) in fields {
+        if required.contains(&field.as_str()) {
+            assert!(!accepts(schema, &properties[field], &Value::Null), "{name}.{field}");
+        }
+        assert!(accepts(schema, &properties[field], value), "type of {name}.{field}: {value}");
+    }
+}
+
+fn date() -> Date {
+    Date {
+        year: 2021,
+        month: Some(3),
+        day: None,
+    }
+}
+
+fn full_citation() -> Citation {
+    Citation {
+        citation_type: vec!["Journal Article".to_string()],
+        title: "Title".to_string(),
+        authors: vec![author()],
+        author_count: Some(12),
+        contributors: vec![Contributor::new(author(), ContributorRole::Editor)],
+        journal: Some("Journal".to_string()),
+        journal_abbr: Some("J".to_string()),
+        date: Some(date()),
+        date_end: Some(date()),
+        electronic_date: Some(date()),
+        record_dates: [(RecordDateKind::Entrez, date())].into(),
+        volume: Some("1".to_string()),
+        issue: Some("2".to_string()),
+        pages: Some("3-4".to_string()),
+        issn: vec!["1234-5678".to_string()],
+        doi: Some("10.1000/xyz".to_string()),
+        pmid: Some("12345678".to_string()),
+        pmc_id: Some("PMC1234567".to_string()),
+        abstract_text: Some("Abstract".to_string()),
+        keywords: vec!["keyword".to_string()],
+        urls: vec!["https://example.org".to_string()],
+        link_kinds: [("https://example.org".to_string(), LinkKind::FullText)].into(),
+        language: Some("eng".to_string()),
+        mesh_terms: vec!["Humans".to_string()],
+        publisher: Some("Publisher".to_string()),
+        notes: Some("Note".to_string()),
+        conference: Some(ConferenceInfo::default()),
+        times_cited: Some(3),
+        retraction_status: Some(RetractionStatus::Retracted {
+            notice: Some("Lancet. 2010 Feb 6;375(9713):445".to_string()),
+        }),
+        ingested_at: Some("2026-10-14T09:30:00Z".to_string()),
+        parsed_from: Some(CitationFormat::PubMed),
+        extra_fields: [("DB".to_string(), vec!["Medline".to_string()])].into(),
+    }
+}
+
+fn author() -> Author {
+    Author {
+        name: "Nguyen".to_string(),
+        given_name: Some("Thi".to_string()),
+        middle_name: None,
+        suffix: None,
+        affiliations: vec!["University of Sydney".to_string()],
+    }
+}
+
+#[test]
+fn test_schema_matches_serialized_types() {
+    let schema = schema();
+    for citation in [Citation::default(), full_citation()] {
+        check_definition(&schema, "Citation", serde_json::to_value(citation).unwrap());
+    }
+    check_definition(&schema, "Author", serde_json::to_value(author()).unwrap());
+    let contributor = Contributor::new(author(), ContributorRole::Translator);
+    check_definition(&schema, "Contributor", serde_json::to_value(contributor).unwrap());
+    check_definition(&schema, "Date", serde_json::to_value(date()).unwrap());
+    let conference = serde_json::to_value(ConferenceInfo::default()).unwrap();
+    check_definition(&schema, "ConferenceInfo", conference);
+    for status in [RetractionStatus::RetractionNotice, RetractionStatus::HasErratum] {
+        let value = serde_json::to_value(status).unwrap();
+        assert!(accepts(&schema, definition(&schema, "RetractionStatus"), &value), "{value}");
+    }
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn test_duplicate_group_schema_matches_serialized_types() {
+    use biblib::{DuplicateGroup, MatchInfo, MatchReason};
+
+    let schema = serde_json::to_value(schemars::schema_for!(DuplicateGroup)).unwrap();
+    let group = DuplicateGroup {
+        unique: full_citation(),
+        duplicates: vec![Citation::default()],
+        match_info: vec![MatchInfo {
+            score: 0.97,
+            matched_on: vec![
+                MatchReason::DoiExact,
+                MatchReason::TitleSimilarity(0.97),
+                MatchReason::AuthorMatch,
+                MatchReason::AbstractSimilarity(0.6),
+            ],
+        }],
+    };
+    check_definition(&schema, "DuplicateGroup", serde_json::to_value(&group).unwrap());
+    let match_info = serde_json::to_value(&group.match_info[0]).unwrap();
+    check_definition(&schema, "MatchInfo", match_info);
+    for reason in &group.match_info[0].matched_on {
+        let value = serde_json::to_value(reason).unwrap();
+        assert!(accepts(&schema, definition(&schema, "MatchReason"), &value), "{value}");
+    }
+}
+
+#[test]
+fn test_schema_references_resolve() {
+    let schema = schema();
+    let text = schema.to_string();
+    for reference in text.split("\"$ref\":\"").skip(1) {
+        let name = reference.split('"').next().unwrap();
+        let name = name.strip_prefix("#/definitions/").unwrap();
+        assert!(schema["definitions"].get(name).is_some(), "{name}");
+    }
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn test_citation_schema_snapshot() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/citation.schema.json");
+    let schema = biblib::schema::citation_schema();
+    let generated = serde_json::to_string_pretty(&schema).unwrap() + "\n";
+    if std::env::var_os("BIBLIB_UPDATE_SCHEMA").is_some() {
+        std::fs::write(path, &generated).unwrap();
+    }
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        generated,
+        "the schema changed; review it and rerun with BIBLIB_UPDATE_SCHEMA=1 to update the snapshot"
+    );
+}
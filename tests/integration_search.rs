@@ -0,0 +1,135 @@
+//! Checks title lookups on a generated corpus against scoring every title.
+//!
+//! `bench_title_index` times building the index and looking up titles; run it
+//! in release mode with
+//! `cargo test --release --test integration_search -- --ignored --nocapture`.
+
+#![cfg(feature = "dedupe")]
+
+use biblib::Citation;
+use biblib::search::TitleIndex;
+use biblib::text::normalize_title;
+use std::time::{Duration, Instant};
+
+const TOPICS: [&str; 8] = [
+    "sepsis",
+    "heart failure",
+    "type 2 diabetes",
+    "depression",
+    "hip fracture",
+    "asthma",
+    "stroke rehabilitation",
+    "chronic kidney disease",
+];
+
+const DESIGNS: [&str; 5] = [
+    "Effect of {} on outcomes in older adults",
+    "{} in primary care: a randomised trial",
+    "Machine learning for predicting {}",
+    "The burden of {} in low-income countries",
+    "{}",
+];
+
+/// `count` citations with titles varied by topic, design and number.
+fn corpus(count: usize) -> Vec<Citation> {
+    (0..count)
+        .map(|i| {
+            let topic = format!("{} {}", TOPICS[i % TOPICS.len()], i / 40);
+            let title = DESIGNS[(i / TOPICS.len()) % DESIGNS.len()].replace("{}", &topic);
+            Citation {
+                title,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_prefix_lookup_matches_scan() {
+    let citations = corpus(2_000);
+    let index = TitleIndex::new(&citations);
+    for prefix in [
+        "Effect of sepsis 1",
+        "machine learning for predicting h",
+        "asthma 4",
+        "z",
+    ] {
+        let normalized = normalize_title(prefix);
+        let mut expected: Vec<usize> = (0..citations.len())
+            .filter(|&i| normalize_title(&citations[i].title).starts_with(&normalized))
+            .collect();
+        let mut found = index.find_by_title_prefix(prefix, usize::MAX);
+        expected.sort_unstable();
+        found.sort_unstable();
+        assert_eq!(found, expected, "{prefix}");
+    }
+}
+
+#[test]
+fn test_similar_titles_match_scan() {
+    let citations = corpus(2_000);
+    let index = TitleIndex::new(&citations);
+    for query in [
+        "Effect of sepsis on outcomes in older adults",
+        "Machine learning for predicting heart failure 3",
+        "Asthma 12",
+        "The burden of stroke in low income countries",
+    ] {
+        // No title is skipped without a threshold, so these are the scores of all
+        let all = index.find_similar_titles(query, 0.0, usize::MAX);
+        assert_eq!(all.len(), citations.len(), "{query}");
+        for min_similarity in [0.7, 0.8, 0.9, 0.95] {
+            let expected: Vec<(usize, f64)> = all
+                .iter()
+                .copied()
+                .filter(|(_, similarity)| *similarity >= min_similarity)
+                .collect();
+            assert_eq!(
+                index.find_similar_titles(query, min_similarity, usize::MAX),
+                expected,
+                "{query} at {min_similarity}"
+            );
+        }
+    }
+}
+
+#[test]
+#[ignore = "benchmark; run in release mode"]
+fn bench_title_index() {
+    fn time<T>(f: impl Fn() -> T) -> Duration {
+        let start = Instant::now();
+        for _ in 0..3 {
+            std::hint::black_box(f());
+        }
+        start.elapsed() / 3
+    }
+
+    let citations = corpus(100_000);
+    let index = TitleIndex::new(&citations);
+    let query = "Machine learning for predicting type 2 diabetes 1200";
+    let scan = || {
+        let normalized = normalize_title(query);
+        citations
+            .iter()
+            .filter(|citation| normalize_title(&citation.title).starts_with(&normalized))
+            .count()
+    };
+
+    println!(
+        "prefix lookup: {:?}",
+        time(|| index.find_by_title_prefix(query, 20))
+    );
+    println!("prefix scan: {:?}", time(scan));
+    println!(
+        "similar at 0.9: {:?}",
+        time(|| index.find_similar_titles(query, 0.9, 20))
+    );
+    println!(
+        "similar at 0.0: {:?}",
+        time(|| index.find_similar_titles(query, 0.0, 20))
+    );
+    println!(
+        "build index of 100k titles: {:?}",
+        time(|| TitleIndex::new(&citations))
+    );
+}
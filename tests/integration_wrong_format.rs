@@ -0,0 +1,118 @@
+//! Integration tests for files read with the parser of another format.
+
+#![cfg(all(
+    feature = "ris",
+    feature = "pubmed",
+    feature = "xml",
+    feature = "enw",
+    feature = "csv"
+))]
+
+mod common;
+
+use biblib::error::{ParseError, ValueError};
+use biblib::{
+    Citation, CitationFormat, CitationParser, CsvParser, EndNoteXmlParser, EnwParser, PubMedParser,
+    RisParser,
+};
+
+/// Tagged fixtures with the format each is written in.
+const FIXTURES: [(&str, CitationFormat); 13] = [
+    ("embase_conference_abstract.ris", CitationFormat::Ris),
+    ("glued_lines.ris", CitationFormat::Ris),
+    ("google_scholar.ris", CitationFormat::Ris),
+    ("ovid_embase.ris", CitationFormat::Ris),
+    ("partly_broken.ris", CitationFormat::Ris),
+    ("reference_manager.ris", CitationFormat::Ris),
+    ("scopus_export.ris", CitationFormat::Ris),
+    ("zotero_export.ris", CitationFormat::Ris),
+    ("pubmed_export.nbib", CitationFormat::PubMed),
+    ("google_scholar.enw", CitationFormat::Enw),
+    ("endnote_x9.xml", CitationFormat::EndNoteXml),
+    ("endnote_concatenated.xml", CitationFormat::EndNoteXml),
+    ("mixed_ris_pubmed.txt", CitationFormat::Mixed),
+];
+
+const PARSERS: [CitationFormat; 5] = [
+    CitationFormat::Ris,
+    CitationFormat::PubMed,
+    CitationFormat::Enw,
+    CitationFormat::EndNoteXml,
+    CitationFormat::Csv,
+];
+
+fn parse(format: &CitationFormat, input: &str) -> Result<Vec<Citation>, ParseError> {
+    match format {
+        CitationFormat::Ris => RisParser::new().parse(input),
+        CitationFormat::PubMed => PubMedParser::new().parse(input),
+        CitationFormat::Enw => EnwParser::new().parse(input),
+        CitationFormat::EndNoteXml => EndNoteXmlParser::new().parse(input),
+        CitationFormat::Csv => CsvParser::new().parse(input),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_wrong_parser_names_the_format() {
+    for (name, fixture_format) in FIXTURES {
+        if fixture_format == CitationFormat::Mixed {
+            continue;
+        }
+        let content = common::fixture(name);
+        for parser in PARSERS.iter().filter(|parser| **parser != fixture_format) {
+            let err = parse(parser, &content).expect_err(&format!("{name} parsed as {parser}"));
+            assert_eq!(&err.format, parser);
+            match err.error {
+                ValueError::WrongFormat { detected } => {
+                    assert_eq!(detected, fixture_format, "{name} read as {parser}")
+                }
+                other => panic!("{name} read as {parser}: unexpected error {other}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_right_parser_accepts_messy_files() {
+    for (name, fixture_format) in FIXTURES {
+        let content = common::fixture(name);
+        let parsers = match fixture_format {
+            // Either parser reads its part of a mixed file
+            CitationFormat::Mixed => vec![CitationFormat::Ris, CitationFormat::PubMed],
+            format => vec![format],
+        };
+        for parser in parsers {
+            let result = parse(&parser, &content);
+            assert!(
+                !matches!(
+                    result,
+                    Err(ParseError {
+                        error: ValueError::WrongFormat { .. },
+                        ..
+                    })
+                ),
+                "{name} rejected by {parser}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_wrong_format_error_points_at_first_record() {
+    let input = "Exported from a reference manager\n\nTY  - JOUR\nTI  - Example\nER  - \n";
+    let err = PubMedParser::new().parse(input).unwrap_err();
+    assert_eq!(err.line, Some(3));
+    assert_eq!(
+        err.to_string(),
+        "Error in PubMed format at line 3: Input looks like RIS rather than this format; \
+         parse it with the RIS parser"
+    );
+}
+
+#[test]
+fn test_csv_fixtures_are_not_mistaken_for_tagged_formats() {
+    for name in ["merged_databases.csv", "scopus_export.csv"] {
+        let content = common::fixture(name);
+        assert!(!CsvParser::new().parse(&content).unwrap().is_empty());
+    }
+}
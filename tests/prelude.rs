@@ -0,0 +1,80 @@
+//! Guards the prelude against accidental removals: every re-exported item is
+//! named here.
+
+use biblib::prelude::*;
+
+#[test]
+fn test_core_items() {
+    let citation = Citation {
+        title: "Example".to_string(),
+        authors: vec![Author {
+            name: "Smith".to_string(),
+            given_name: None,
+            middle_name: None,
+            suffix: None,
+            affiliations: Vec::new(),
+        }],
+        date: Some(Date {
+            year: 2024,
+            month: None,
+            day: None,
+        }),
+        conference: Some(ConferenceInfo::default()),
+        ..Default::default()
+    };
+    let mut sorted = vec![citation.clone()];
+    sort_citations(&mut sorted, &[SortKey::Title]);
+    let group = DuplicateGroup {
+        unique: citation.clone(),
+        duplicates: Vec::new(),
+        match_info: Vec::new(),
+    };
+    assert_eq!(group.merged().title, citation.title);
+
+    let result: Result<(Vec<Citation>, CitationFormat), CitationError> = detect_and_parse("");
+    assert!(result.is_ok());
+
+    fn takes_parse_error(_: Option<ParseError>) {}
+    takes_parse_error(None);
+
+    fn uses_traits<P: CitationParser, W: CitationWriter>(_: Option<(P, W)>) {}
+    #[cfg(feature = "ris")]
+    uses_traits::<RisParser, RisWriter>(None);
+}
+
+#[cfg(feature = "ris")]
+#[test]
+fn test_ris_items() {
+    let _ = (RisParser::new(), RisWriter::new());
+}
+
+#[cfg(feature = "pubmed")]
+#[test]
+fn test_pubmed_items() {
+    let _ = (PubMedParser::new(), PmidListParser::new());
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn test_endnote_xml_items() {
+    let _ = (EndNoteXmlParser::new(), EndNoteXmlWriter::new());
+}
+
+#[cfg(feature = "enw")]
+#[test]
+fn test_enw_items() {
+    let _ = EnwParser::new();
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_csv_items() {
+    let _ = (CsvParser::new(), CsvWriter::new());
+}
+
+#[cfg(feature = "dedupe")]
+#[test]
+fn test_dedupe_items() {
+    let deduplicator = Deduplicator::new().with_config(DeduplicatorConfig::default());
+    assert!(deduplicator.find_duplicates(&[]).unwrap().is_empty());
+}